@@ -0,0 +1,6 @@
+CREATE TABLE
+    IF NOT EXISTS hybrid_torrents (
+        id integer PRIMARY KEY AUTO_INCREMENT,
+        info_hash VARCHAR(40) NOT NULL,
+        info_hash_v2 VARCHAR(64) NOT NULL UNIQUE
+    );
\ No newline at end of file
@@ -0,0 +1,7 @@
+CREATE TABLE
+    IF NOT EXISTS torrent_multipliers (
+        id integer PRIMARY KEY AUTO_INCREMENT,
+        info_hash VARCHAR(40) NOT NULL UNIQUE,
+        upload_multiplier DOUBLE NOT NULL DEFAULT 1.0,
+        download_multiplier DOUBLE NOT NULL DEFAULT 1.0
+    );
\ No newline at end of file
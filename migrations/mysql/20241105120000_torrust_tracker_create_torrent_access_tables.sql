@@ -0,0 +1,23 @@
+CREATE TABLE
+    IF NOT EXISTS key_torrents (
+        id integer PRIMARY KEY AUTO_INCREMENT,
+        `key` VARCHAR(32) NOT NULL,
+        info_hash VARCHAR(40) NOT NULL,
+        UNIQUE (`key`, info_hash)
+    );
+
+CREATE TABLE
+    IF NOT EXISTS key_torrent_groups (
+        id integer PRIMARY KEY AUTO_INCREMENT,
+        `key` VARCHAR(32) NOT NULL,
+        torrent_group VARCHAR(64) NOT NULL,
+        UNIQUE (`key`, torrent_group)
+    );
+
+CREATE TABLE
+    IF NOT EXISTS torrent_groups (
+        id integer PRIMARY KEY AUTO_INCREMENT,
+        torrent_group VARCHAR(64) NOT NULL,
+        info_hash VARCHAR(40) NOT NULL,
+        UNIQUE (torrent_group, info_hash)
+    );
\ No newline at end of file
@@ -0,0 +1,5 @@
+CREATE TABLE
+    IF NOT EXISTS client_prefixes (
+        id integer PRIMARY KEY AUTO_INCREMENT,
+        prefix VARCHAR(20) CHARACTER SET ascii COLLATE ascii_bin NOT NULL UNIQUE
+    );
\ No newline at end of file
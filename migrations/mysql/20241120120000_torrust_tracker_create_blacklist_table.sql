@@ -0,0 +1,5 @@
+CREATE TABLE
+    IF NOT EXISTS blacklist (
+        id integer PRIMARY KEY AUTO_INCREMENT,
+        info_hash VARCHAR(40) NOT NULL UNIQUE
+    );
\ No newline at end of file
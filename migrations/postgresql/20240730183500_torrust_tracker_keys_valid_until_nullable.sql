@@ -0,0 +1 @@
+ALTER TABLE keys ALTER COLUMN valid_until DROP NOT NULL;
\ No newline at end of file
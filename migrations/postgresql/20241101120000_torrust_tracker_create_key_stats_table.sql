@@ -0,0 +1,8 @@
+CREATE TABLE
+    IF NOT EXISTS key_stats (
+        id SERIAL PRIMARY KEY,
+        key VARCHAR(32) NOT NULL UNIQUE,
+        uploaded BIGINT NOT NULL DEFAULT 0,
+        downloaded BIGINT NOT NULL DEFAULT 0,
+        seeding_time BIGINT NOT NULL DEFAULT 0
+    );
\ No newline at end of file
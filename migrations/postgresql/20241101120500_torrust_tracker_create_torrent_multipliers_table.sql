@@ -0,0 +1,7 @@
+CREATE TABLE
+    IF NOT EXISTS torrent_multipliers (
+        id SERIAL PRIMARY KEY,
+        info_hash VARCHAR(40) NOT NULL UNIQUE,
+        upload_multiplier DOUBLE PRECISION NOT NULL DEFAULT 1.0,
+        download_multiplier DOUBLE PRECISION NOT NULL DEFAULT 1.0
+    );
\ No newline at end of file
@@ -0,0 +1,5 @@
+CREATE TABLE
+    IF NOT EXISTS client_prefixes (
+        id SERIAL PRIMARY KEY,
+        prefix VARCHAR(20) NOT NULL UNIQUE
+    );
\ No newline at end of file
@@ -0,0 +1,5 @@
+CREATE TABLE
+    IF NOT EXISTS ip_blocklist (
+        id SERIAL PRIMARY KEY,
+        network VARCHAR(43) NOT NULL UNIQUE
+    );
\ No newline at end of file
@@ -0,0 +1,5 @@
+CREATE TABLE
+    IF NOT EXISTS blacklist (
+        id SERIAL PRIMARY KEY,
+        info_hash VARCHAR(40) NOT NULL UNIQUE
+    );
\ No newline at end of file
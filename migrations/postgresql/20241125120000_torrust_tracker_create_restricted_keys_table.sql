@@ -0,0 +1,11 @@
+CREATE TABLE
+    IF NOT EXISTS restricted_keys (
+        id SERIAL PRIMARY KEY,
+        key VARCHAR(32) NOT NULL UNIQUE
+    );
+
+INSERT INTO restricted_keys (key)
+SELECT key FROM key_torrents
+UNION
+SELECT key FROM key_torrent_groups
+ON CONFLICT DO NOTHING;
\ No newline at end of file
@@ -0,0 +1,6 @@
+CREATE TABLE
+    IF NOT EXISTS hybrid_torrents (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        info_hash TEXT NOT NULL,
+        info_hash_v2 TEXT NOT NULL UNIQUE
+    );
\ No newline at end of file
@@ -0,0 +1,8 @@
+CREATE TABLE
+    IF NOT EXISTS key_stats (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        key TEXT NOT NULL UNIQUE,
+        uploaded INTEGER NOT NULL DEFAULT 0,
+        downloaded INTEGER NOT NULL DEFAULT 0,
+        seeding_time INTEGER NOT NULL DEFAULT 0
+    );
\ No newline at end of file
@@ -0,0 +1,7 @@
+CREATE TABLE
+    IF NOT EXISTS torrent_multipliers (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        info_hash TEXT NOT NULL UNIQUE,
+        upload_multiplier REAL NOT NULL DEFAULT 1.0,
+        download_multiplier REAL NOT NULL DEFAULT 1.0
+    );
\ No newline at end of file
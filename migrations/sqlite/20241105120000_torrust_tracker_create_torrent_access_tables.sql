@@ -0,0 +1,23 @@
+CREATE TABLE
+    IF NOT EXISTS key_torrents (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        key TEXT NOT NULL,
+        info_hash TEXT NOT NULL,
+        UNIQUE (key, info_hash)
+    );
+
+CREATE TABLE
+    IF NOT EXISTS key_torrent_groups (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        key TEXT NOT NULL,
+        torrent_group TEXT NOT NULL,
+        UNIQUE (key, torrent_group)
+    );
+
+CREATE TABLE
+    IF NOT EXISTS torrent_groups (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        torrent_group TEXT NOT NULL,
+        info_hash TEXT NOT NULL,
+        UNIQUE (torrent_group, info_hash)
+    );
\ No newline at end of file
@@ -0,0 +1,5 @@
+CREATE TABLE
+    IF NOT EXISTS client_prefixes (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        prefix TEXT NOT NULL UNIQUE
+    );
\ No newline at end of file
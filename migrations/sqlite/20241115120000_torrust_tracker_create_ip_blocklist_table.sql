@@ -0,0 +1,5 @@
+CREATE TABLE
+    IF NOT EXISTS ip_blocklist (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        network TEXT NOT NULL UNIQUE
+    );
\ No newline at end of file
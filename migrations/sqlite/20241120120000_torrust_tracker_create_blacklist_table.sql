@@ -0,0 +1,5 @@
+CREATE TABLE
+    IF NOT EXISTS blacklist (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        info_hash TEXT NOT NULL UNIQUE
+    );
\ No newline at end of file
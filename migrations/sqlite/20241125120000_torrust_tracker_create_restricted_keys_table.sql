@@ -0,0 +1,10 @@
+CREATE TABLE
+    IF NOT EXISTS restricted_keys (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        key TEXT NOT NULL UNIQUE
+    );
+
+INSERT OR IGNORE INTO restricted_keys (key)
+SELECT key FROM key_torrents
+UNION
+SELECT key FROM key_torrent_groups;
\ No newline at end of file
@@ -19,7 +19,9 @@ use serde_with::serde_as;
 use thiserror::Error;
 use torrust_tracker_located_error::{DynError, LocatedError};
 
-/// The maximum number of returned peers for a torrent.
+/// The maximum number of returned peers for a torrent in the tracker API.
+///
+/// The announce responses use the limits in the [`AnnouncePolicy`].
 pub const TORRENT_PEERS_LIMIT: usize = 74;
 
 /// Default timeout for sending and receiving packets. And waiting for sockets
@@ -37,10 +39,18 @@ pub const ENV_VAR_CONFIG_TOML_PATH: &str = "TORRUST_TRACKER_CONFIG_TOML_PATH";
 
 pub type Configuration = v2_0_0::Configuration;
 pub type Core = v2_0_0::core::Core;
+pub type SwarmSnapshot = v2_0_0::core::SwarmSnapshot;
+pub type FullScrape = v2_0_0::core::FullScrape;
+pub type Maintenance = v2_0_0::core::Maintenance;
+pub type Blacklist = v2_0_0::core::Blacklist;
+pub type ClientFilter = v2_0_0::core::ClientFilter;
+pub type IpBlocklist = v2_0_0::core::IpBlocklist;
 pub type HealthCheckApi = v2_0_0::health_check_api::HealthCheckApi;
 pub type HttpApi = v2_0_0::tracker_api::HttpApi;
 pub type HttpTracker = v2_0_0::http_tracker::HttpTracker;
 pub type UdpTracker = v2_0_0::udp_tracker::UdpTracker;
+pub type UdpRateLimit = v2_0_0::udp_tracker::RateLimit;
+pub type UdpRateLimitAction = v2_0_0::udp_tracker::RateLimitAction;
 pub type Database = v2_0_0::database::Database;
 pub type Driver = v2_0_0::database::Driver;
 pub type Threshold = v2_0_0::logging::Threshold;
@@ -158,6 +168,18 @@ pub struct TrackerPolicy {
     /// enabled.
     #[serde(default = "TrackerPolicy::default_remove_peerless_torrents")]
     pub remove_peerless_torrents: bool,
+
+    /// How the tracker selects the peers returned in the announce responses
+    /// when the swarm has more peers than the client wants.
+    #[serde(default = "TrackerPolicy::default_peer_selection")]
+    pub peer_selection: PeerSelectionStrategy,
+
+    /// Maximum number of peers stored for each torrent. When a swarm is full,
+    /// the announces from new peers are still answered, but the peers are not
+    /// added to the swarm. There is no limit by default.
+    #[serde(default = "TrackerPolicy::default_max_peers_per_torrent")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_peers_per_torrent: Option<u32>,
 }
 
 impl Default for TrackerPolicy {
@@ -166,6 +188,8 @@ impl Default for TrackerPolicy {
             max_peer_timeout: Self::default_max_peer_timeout(),
             persistent_torrent_completed_stat: Self::default_persistent_torrent_completed_stat(),
             remove_peerless_torrents: Self::default_remove_peerless_torrents(),
+            peer_selection: Self::default_peer_selection(),
+            max_peers_per_torrent: Self::default_max_peers_per_torrent(),
         }
     }
 }
@@ -182,6 +206,30 @@ impl TrackerPolicy {
     fn default_remove_peerless_torrents() -> bool {
         true
     }
+
+    fn default_peer_selection() -> PeerSelectionStrategy {
+        PeerSelectionStrategy::Random
+    }
+
+    fn default_max_peers_per_torrent() -> Option<u32> {
+        None
+    }
+}
+
+/// The strategies to select the peers returned to a client.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum PeerSelectionStrategy {
+    /// A contiguous run of peers starting at a random position of the swarm.
+    Random,
+    /// Like `random`, but seeders only get leechers.
+    SeedersDontGetSeeders,
+    /// Like `random`, but the peers with an address of the same IP family as
+    /// the client go first.
+    SameAddressFamily,
+    /// A random sample where the peers that announced recently are more
+    /// likely to be selected.
+    RecencyWeighted,
 }
 
 /// Information required for loading config
@@ -255,6 +303,30 @@ pub struct AnnouncePolicy {
     /// the tracker for not adhering to the rules.
     #[serde(default = "AnnouncePolicy::default_interval_min")]
     pub interval_min: u32,
+
+    /// What to do with the announces that arrive earlier than `interval_min`
+    /// after the previous announce of the same peer, without an event change.
+    /// The `interval_min` is not enforced by default.
+    #[serde(default = "AnnouncePolicy::default_interval_min_action")]
+    pub interval_min_action: IntervalMinAction,
+
+    /// Number of peers returned in the announce responses when the client
+    /// does not send the `numwant` param.
+    #[serde(default = "AnnouncePolicy::default_numwant")]
+    pub numwant: u32,
+
+    /// Maximum number of peers returned in the HTTP tracker announce
+    /// responses. Clients can ask for fewer peers, but not for more.
+    #[serde(default = "AnnouncePolicy::default_numwant_max_http")]
+    pub numwant_max_http: u32,
+
+    /// Maximum number of peers returned in the UDP tracker announce
+    /// responses. Clients can ask for fewer peers, but not for more.
+    ///
+    /// Every `IPv6` peer takes 18 bytes in the response. The default value
+    /// keeps the responses below the 1500 bytes Ethernet MTU.
+    #[serde(default = "AnnouncePolicy::default_numwant_max_udp")]
+    pub numwant_max_udp: u32,
 }
 
 impl Default for AnnouncePolicy {
@@ -262,6 +334,10 @@ impl Default for AnnouncePolicy {
         Self {
             interval: Self::default_interval(),
             interval_min: Self::default_interval_min(),
+            interval_min_action: Self::default_interval_min_action(),
+            numwant: Self::default_numwant(),
+            numwant_max_http: Self::default_numwant_max_http(),
+            numwant_max_udp: Self::default_numwant_max_udp(),
         }
     }
 }
@@ -274,6 +350,36 @@ impl AnnouncePolicy {
     fn default_interval_min() -> u32 {
         120
     }
+
+    fn default_interval_min_action() -> IntervalMinAction {
+        IntervalMinAction::Disabled
+    }
+
+    fn default_numwant() -> u32 {
+        50
+    }
+
+    fn default_numwant_max_http() -> u32 {
+        74
+    }
+
+    fn default_numwant_max_udp() -> u32 {
+        74
+    }
+}
+
+/// What to do with the announces that arrive earlier than the
+/// [`interval_min`](AnnouncePolicy::interval_min).
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum IntervalMinAction {
+    /// The `interval_min` is not enforced. All the announces are handled.
+    Disabled,
+    /// The peer is not updated. The client gets the current peers and
+    /// swarm statistics, as if the previous response had been cached.
+    Cached,
+    /// The client gets a failure response (HTTP) or an error response (UDP).
+    Reject,
 }
 
 /// Errors that can occur when loading the configuration.
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use derive_more::{Constructor, Display};
 use serde::{Deserialize, Serialize};
 
@@ -13,6 +15,16 @@ pub struct Core {
     #[serde(default = "Core::default_announce_policy")]
     pub announce_policy: AnnouncePolicy,
 
+    /// Torrent blacklist configuration. The blacklisted torrents can't be
+    /// announced or scraped, whatever the tracker mode is.
+    #[serde(default = "Core::default_blacklist")]
+    pub blacklist: Blacklist,
+
+    /// How the list of `peer_id` prefixes is used to filter the client
+    /// software that can announce. It's `disabled` by default.
+    #[serde(default = "Core::default_client_filter")]
+    pub client_filter: ClientFilter,
+
     /// Database configuration.
     #[serde(default = "Core::default_database")]
     pub database: Database,
@@ -22,10 +34,27 @@ pub struct Core {
     #[serde(default = "Core::default_inactive_peer_cleanup_interval")]
     pub inactive_peer_cleanup_interval: u64,
 
+    /// IP blocklist configuration. When present, the tracker loads the
+    /// networks in the blocklist file on startup and refuses the requests
+    /// coming from them. More networks can be blocked with the tracker API.
+    #[serde(default = "Core::default_ip_blocklist")]
+    pub ip_blocklist: Option<IpBlocklist>,
+
     /// When `true` only approved torrents can be announced in the tracker.
     #[serde(default = "Core::default_listed")]
     pub listed: bool,
 
+    /// Full scrape configuration. When present, the tracker periodically
+    /// generates the scrape data for all the torrents, and returns it for the
+    /// HTTP `scrape` requests without any `info_hash` param.
+    #[serde(default = "Core::default_full_scrape")]
+    pub full_scrape: Option<FullScrape>,
+
+    /// Maintenance mode configuration. It can also be enabled and disabled
+    /// at runtime with the tracker API.
+    #[serde(default = "Core::default_maintenance")]
+    pub maintenance: Maintenance,
+
     /// Network configuration.
     #[serde(default = "Core::default_network")]
     pub net: Network,
@@ -38,6 +67,28 @@ pub struct Core {
     #[serde(default = "Core::default_private_mode")]
     pub private_mode: Option<PrivateMode>,
 
+    /// Swarm snapshot configuration. When present, the tracker saves the
+    /// in-memory swarms to a file periodically and on graceful shutdown, and
+    /// loads them again on startup.
+    #[serde(default = "Core::default_swarm_snapshot")]
+    pub swarm_snapshot: Option<SwarmSnapshot>,
+
+    /// Warning messages for specific torrents, returned in the `warning
+    /// message` field of the HTTP announce responses. The key is the info-hash
+    /// of the torrent as a lowercase hex string. It overrides the general
+    /// `warning_message`.
+    #[serde(
+        default = "Core::default_torrent_warning_messages",
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub torrent_warning_messages: BTreeMap<String, String>,
+
+    /// The `tracker id` returned in the HTTP announce responses. Clients send
+    /// it back in the `trackerid` param of the next announce requests. The
+    /// field is not included in the responses when it's not set.
+    #[serde(default = "Core::default_tracker_id")]
+    pub tracker_id: Option<String>,
+
     /// Tracker policy configuration.
     #[serde(default = "Core::default_tracker_policy")]
     pub tracker_policy: TrackerPolicy,
@@ -49,20 +100,35 @@ pub struct Core {
     /// information about the collected metrics.
     #[serde(default = "Core::default_tracker_usage_statistics")]
     pub tracker_usage_statistics: bool,
+
+    /// A warning message returned in the `warning message` field of all the
+    /// HTTP announce responses. Unlike the `failure reason`, the request is
+    /// processed normally.
+    #[serde(default = "Core::default_warning_message")]
+    pub warning_message: Option<String>,
 }
 
 impl Default for Core {
     fn default() -> Self {
         Self {
             announce_policy: Self::default_announce_policy(),
+            blacklist: Self::default_blacklist(),
+            client_filter: Self::default_client_filter(),
             database: Self::default_database(),
             inactive_peer_cleanup_interval: Self::default_inactive_peer_cleanup_interval(),
+            ip_blocklist: Self::default_ip_blocklist(),
+            full_scrape: Self::default_full_scrape(),
             listed: Self::default_listed(),
+            maintenance: Self::default_maintenance(),
             net: Self::default_network(),
             private: Self::default_private(),
             private_mode: Self::default_private_mode(),
+            swarm_snapshot: Self::default_swarm_snapshot(),
+            torrent_warning_messages: Self::default_torrent_warning_messages(),
+            tracker_id: Self::default_tracker_id(),
             tracker_policy: Self::default_tracker_policy(),
             tracker_usage_statistics: Self::default_tracker_usage_statistics(),
+            warning_message: Self::default_warning_message(),
         }
     }
 }
@@ -72,6 +138,14 @@ impl Core {
         AnnouncePolicy::default()
     }
 
+    fn default_blacklist() -> Blacklist {
+        Blacklist::default()
+    }
+
+    fn default_client_filter() -> ClientFilter {
+        ClientFilter::Disabled
+    }
+
     fn default_database() -> Database {
         Database::default()
     }
@@ -84,6 +158,14 @@ impl Core {
         false
     }
 
+    fn default_full_scrape() -> Option<FullScrape> {
+        None
+    }
+
+    fn default_maintenance() -> Maintenance {
+        Maintenance::default()
+    }
+
     fn default_network() -> Network {
         Network::default()
     }
@@ -100,12 +182,32 @@ impl Core {
         }
     }
 
+    fn default_ip_blocklist() -> Option<IpBlocklist> {
+        None
+    }
+
+    fn default_swarm_snapshot() -> Option<SwarmSnapshot> {
+        None
+    }
+
+    fn default_torrent_warning_messages() -> BTreeMap<String, String> {
+        BTreeMap::new()
+    }
+
+    fn default_tracker_id() -> Option<String> {
+        None
+    }
+
     fn default_tracker_policy() -> TrackerPolicy {
         TrackerPolicy::default()
     }
     fn default_tracker_usage_statistics() -> bool {
         true
     }
+
+    fn default_warning_message() -> Option<String> {
+        None
+    }
 }
 
 /// Configuration specific when the tracker is running in private mode.
@@ -133,6 +235,195 @@ impl PrivateMode {
     }
 }
 
+/// Configuration for the swarm snapshots.
+///
+/// Peer lists are not stored in the database. A snapshot contains all the
+/// torrents in memory, with their peers and `downloaded` counters, so that
+/// clients do not get empty peer lists after the tracker restarts.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Constructor)]
+pub struct SwarmSnapshot {
+    /// The path to the snapshot file.
+    #[serde(default = "SwarmSnapshot::default_path")]
+    pub path: String,
+
+    /// Interval in seconds between periodic snapshots. The snapshot is
+    /// always saved on graceful shutdown. Use `0` to disable periodic
+    /// snapshots.
+    #[serde(default = "SwarmSnapshot::default_interval")]
+    pub interval: u64,
+}
+
+impl Default for SwarmSnapshot {
+    fn default() -> Self {
+        Self {
+            path: Self::default_path(),
+            interval: Self::default_interval(),
+        }
+    }
+}
+
+impl SwarmSnapshot {
+    fn default_path() -> String {
+        String::from("./storage/tracker/lib/swarms.json")
+    }
+
+    fn default_interval() -> u64 {
+        300
+    }
+}
+
+/// Configuration for the IP blocklist.
+///
+/// The file contains one entry per line. Empty lines and lines starting with
+/// `#` are ignored. The entries can be:
+///
+/// - Ranges in the P2P blocklist format: `name:start-end`, like
+///   `Some ISP:1.2.3.0-1.2.3.255`.
+/// - Networks in CIDR notation, like `1.2.3.0/24` or `2001:db8::/32`.
+/// - Single IP addresses, like `1.2.3.4`.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Constructor)]
+pub struct IpBlocklist {
+    /// The path to the blocklist file.
+    #[serde(default = "IpBlocklist::default_path")]
+    pub path: String,
+}
+
+impl Default for IpBlocklist {
+    fn default() -> Self {
+        Self {
+            path: Self::default_path(),
+        }
+    }
+}
+
+impl IpBlocklist {
+    fn default_path() -> String {
+        String::from("./storage/tracker/etc/ip_blocklist.txt")
+    }
+}
+
+/// Configuration for the full scrape.
+///
+/// The full scrape contains the `complete`, `downloaded` and `incomplete`
+/// counters of all the torrents. It's expensive to generate for big trackers,
+/// so it's generated periodically and cached.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Constructor)]
+pub struct FullScrape {
+    /// Interval in seconds between the generations of the full scrape. The
+    /// minimum is one second.
+    #[serde(default = "FullScrape::default_interval")]
+    pub interval: u64,
+
+    /// When `true` a gzip-compressed copy of the full scrape is stored too.
+    /// It's returned, with the `Content-Encoding: gzip` header, to the
+    /// clients that accept the `gzip` encoding.
+    #[serde(default = "FullScrape::default_gzip")]
+    pub gzip: bool,
+}
+
+impl Default for FullScrape {
+    fn default() -> Self {
+        Self {
+            interval: Self::default_interval(),
+            gzip: Self::default_gzip(),
+        }
+    }
+}
+
+impl FullScrape {
+    fn default_interval() -> u64 {
+        60
+    }
+
+    fn default_gzip() -> bool {
+        false
+    }
+}
+
+/// How the list of `peer_id` prefixes is used to filter the client software.
+///
+/// `BitTorrent` clients put their name and version at the beginning of the
+/// `peer_id`, like `-qB4500-` for qBittorrent 4.5.0. The prefixes are managed
+/// through the API.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum ClientFilter {
+    /// All the clients can announce.
+    Disabled,
+    /// Only the clients whose `peer_id` starts with one of the prefixes can
+    /// announce.
+    Allow,
+    /// The clients whose `peer_id` starts with one of the prefixes can't
+    /// announce.
+    Deny,
+}
+
+/// Configuration for the torrent blacklist.
+///
+/// Public trackers accept any torrent, except the ones banned through the
+/// tracker API, for example after a takedown notice. The HTTP trackers return
+/// a failure response with the `reason` for the `announce` requests on banned
+/// torrents, and the UDP trackers return an error response. The `scrape`
+/// requests get zeroed swarm metadata.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Constructor)]
+pub struct Blacklist {
+    /// The reason returned to the clients announcing a banned torrent.
+    #[serde(default = "Blacklist::default_reason")]
+    pub reason: String,
+}
+
+impl Default for Blacklist {
+    fn default() -> Self {
+        Self {
+            reason: Self::default_reason(),
+        }
+    }
+}
+
+impl Blacklist {
+    fn default_reason() -> String {
+        "The torrent has been banned".to_string()
+    }
+}
+
+/// Configuration for the maintenance mode.
+///
+/// While the tracker is in maintenance mode, the HTTP trackers return a
+/// failure response with the [BEP 31](https://www.bittorrent.org/beps/bep_0031.html)
+/// `retry in` key for the `announce` and `scrape` requests, and the UDP
+/// trackers return an error response. The API and the health check keep
+/// working.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Constructor)]
+pub struct Maintenance {
+    /// When `true` the tracker starts in maintenance mode.
+    #[serde(default = "Maintenance::default_enabled")]
+    pub enabled: bool,
+
+    /// Number of minutes the clients should wait before retrying. It's
+    /// returned in the `retry in` key of the HTTP failure responses.
+    #[serde(default = "Maintenance::default_retry_in")]
+    pub retry_in: u32,
+}
+
+impl Default for Maintenance {
+    fn default() -> Self {
+        Self {
+            enabled: Self::default_enabled(),
+            retry_in: Self::default_retry_in(),
+        }
+    }
+}
+
+impl Maintenance {
+    fn default_enabled() -> bool {
+        false
+    }
+
+    fn default_retry_in() -> u32 {
+        10
+    }
+}
+
 impl Validator for Core {
     fn validate(&self) -> Result<(), SemanticValidationError> {
         if self.private_mode.is_some() && !self.private {
@@ -199,6 +199,7 @@
 //! threshold = "info"
 //!
 //! [core]
+//! client_filter = "disabled"
 //! inactive_peer_cleanup_interval = 600
 //! listed = false
 //! private = false
@@ -207,19 +208,32 @@
 //! [core.announce_policy]
 //! interval = 120
 //! interval_min = 120
+//! interval_min_action = "disabled"
+//! numwant = 50
+//! numwant_max_http = 74
+//! numwant_max_udp = 74
+//!
+//! [core.blacklist]
+//! reason = "The torrent has been banned"
 //!
 //! [core.database]
 //! driver = "sqlite3"
 //! path = "./storage/tracker/lib/database/sqlite3.db"
 //!
+//! [core.maintenance]
+//! enabled = false
+//! retry_in = 10
+//!
 //! [core.net]
 //! external_ip = "0.0.0.0"
 //! on_reverse_proxy = false
+//! trusted_networks = []
 //!
 //! [core.tracker_policy]
 //! max_peer_timeout = 900
 //! persistent_torrent_completed_stat = false
 //! remove_peerless_torrents = true
+//! peer_selection = "random"
 //!
 //! [http_api]
 //! bind_address = "127.0.0.1:1212"
@@ -433,6 +447,7 @@ mod tests {
 
     use std::net::{IpAddr, Ipv4Addr};
 
+    use crate::v2_0_0::udp_tracker::{RateLimit, RateLimitAction};
     use crate::v2_0_0::Configuration;
     use crate::Info;
 
@@ -447,6 +462,7 @@ mod tests {
                                 threshold = "info"
 
                                 [core]
+                                client_filter = "disabled"
                                 inactive_peer_cleanup_interval = 600
                                 listed = false
                                 private = false
@@ -455,19 +471,32 @@ mod tests {
                                 [core.announce_policy]
                                 interval = 120
                                 interval_min = 120
+                                interval_min_action = "disabled"
+                                numwant = 50
+                                numwant_max_http = 74
+                                numwant_max_udp = 74
+
+                                [core.blacklist]
+                                reason = "The torrent has been banned"
 
                                 [core.database]
                                 driver = "sqlite3"
                                 path = "./storage/tracker/lib/database/sqlite3.db"
 
+                                [core.maintenance]
+                                enabled = false
+                                retry_in = 10
+
                                 [core.net]
                                 external_ip = "0.0.0.0"
                                 on_reverse_proxy = false
+                                trusted_networks = []
 
                                 [core.tracker_policy]
                                 max_peer_timeout = 900
                                 persistent_torrent_completed_stat = false
                                 remove_peerless_torrents = true
+                                peer_selection = "random"
 
                                 [health_check_api]
                                 bind_address = "127.0.0.1:1313"
@@ -649,6 +678,129 @@ mod tests {
         });
     }
 
+    #[test]
+    fn configuration_should_allow_to_enable_the_rate_limit_for_a_udp_tracker() {
+        figment::Jail::expect_with(|_jail| {
+            let config_toml = r#"
+                [metadata]
+                schema_version = "2.0.0"
+
+                [logging]
+                threshold = "info"
+
+                [core]
+                listed = false
+                private = false
+
+                [[udp_trackers]]
+                bind_address = "0.0.0.0:6969"
+
+                [udp_trackers.rate_limit]
+                requests_per_second = 10
+                exceeded_action = "error"
+            "#
+            .to_string();
+
+            let info = Info {
+                config_toml: Some(config_toml),
+                config_toml_path: String::new(),
+            };
+
+            let configuration = Configuration::load(&info).expect("Could not load configuration from file");
+
+            assert_eq!(
+                configuration.udp_trackers.unwrap()[0].rate_limit,
+                Some(RateLimit {
+                    requests_per_second: 10,
+                    exceeded_action: RateLimitAction::Error,
+                    ..Default::default()
+                })
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn configuration_should_allow_to_set_the_networks_trusted_to_send_the_peer_ip() {
+        figment::Jail::expect_with(|_jail| {
+            let config_toml = r#"
+                [metadata]
+                schema_version = "2.0.0"
+
+                [logging]
+                threshold = "info"
+
+                [core]
+                listed = false
+                private = false
+
+                [core.net]
+                trusted_networks = ["10.0.0.0/8", "2001:db8::/32"]
+            "#
+            .to_string();
+
+            let info = Info {
+                config_toml: Some(config_toml),
+                config_toml_path: String::new(),
+            };
+
+            let configuration = Configuration::load(&info).expect("Could not load configuration from file");
+
+            assert_eq!(
+                configuration.core.net.trusted_networks,
+                vec!["10.0.0.0/8".parse().unwrap(), "2001:db8::/32".parse().unwrap()]
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn configuration_should_allow_to_set_the_fields_added_to_the_http_announce_responses() {
+        figment::Jail::expect_with(|_jail| {
+            let config_toml = r#"
+                [metadata]
+                schema_version = "2.0.0"
+
+                [logging]
+                threshold = "info"
+
+                [core]
+                listed = false
+                private = false
+                tracker_id = "torrust"
+                warning_message = "The tracker will be down for maintenance"
+
+                [core.torrent_warning_messages]
+                9c38422213e30bff212b30c360d26f9a02136422 = "This torrent has been replaced"
+            "#
+            .to_string();
+
+            let info = Info {
+                config_toml: Some(config_toml),
+                config_toml_path: String::new(),
+            };
+
+            let configuration = Configuration::load(&info).expect("Could not load configuration from file");
+
+            assert_eq!(configuration.core.tracker_id, Some("torrust".to_string()));
+            assert_eq!(
+                configuration.core.warning_message,
+                Some("The tracker will be down for maintenance".to_string())
+            );
+            assert_eq!(
+                configuration
+                    .core
+                    .torrent_warning_messages
+                    .get("9c38422213e30bff212b30c360d26f9a02136422"),
+                Some(&"This torrent has been replaced".to_string())
+            );
+
+            Ok(())
+        });
+    }
+
     #[test]
     fn configuration_should_allow_to_overwrite_the_default_tracker_api_token_for_admin_with_an_env_var() {
         figment::Jail::expect_with(|jail| {
@@ -1,5 +1,6 @@
 use std::net::{IpAddr, Ipv4Addr};
 
+use ipnet::IpNet;
 use serde::{Deserialize, Serialize};
 
 #[allow(clippy::struct_excessive_bools)]
@@ -18,6 +19,16 @@ pub struct Network {
     /// sent from the proxy will be used to get the client's IP address.
     #[serde(default = "Network::default_on_reverse_proxy")]
     pub on_reverse_proxy: bool,
+
+    /// The networks trusted to send the peer IP address in the announce
+    /// requests, for example: `["10.0.0.0/8", "2001:db8::/32"]`.
+    ///
+    /// The tracker always uses the IP address of the client sending the
+    /// request, except for clients in these networks. For them, the HTTP `ip`
+    /// param and the UDP `ip_address` field are used if they contain a public
+    /// IP address. It's empty by default.
+    #[serde(default = "Network::default_trusted_networks")]
+    pub trusted_networks: Vec<IpNet>,
 }
 
 impl Default for Network {
@@ -25,6 +36,7 @@ impl Default for Network {
         Self {
             external_ip: Self::default_external_ip(),
             on_reverse_proxy: Self::default_on_reverse_proxy(),
+            trusted_networks: Self::default_trusted_networks(),
         }
     }
 }
@@ -38,4 +50,8 @@ impl Network {
     fn default_on_reverse_proxy() -> bool {
         false
     }
+
+    fn default_trusted_networks() -> Vec<IpNet> {
+        vec![]
+    }
 }
@@ -16,12 +16,18 @@ pub struct UdpTracker {
     /// the client as the `ConnectionId`.
     #[serde(default = "UdpTracker::default_cookie_lifetime")]
     pub cookie_lifetime: Duration,
+
+    /// Rate limiting per source IP and subnet. Requests exceeding the limits
+    /// are not processed. Rate limiting is disabled when not present.
+    #[serde(default = "UdpTracker::default_rate_limit")]
+    pub rate_limit: Option<RateLimit>,
 }
 impl Default for UdpTracker {
     fn default() -> Self {
         Self {
             bind_address: Self::default_bind_address(),
             cookie_lifetime: Self::default_cookie_lifetime(),
+            rate_limit: Self::default_rate_limit(),
         }
     }
 }
@@ -34,4 +40,114 @@ impl UdpTracker {
     fn default_cookie_lifetime() -> Duration {
         Duration::from_secs(120)
     }
+
+    fn default_rate_limit() -> Option<RateLimit> {
+        None
+    }
+}
+
+/// Token bucket rate limits for the UDP tracker.
+///
+/// Every source IP has a bucket that holds up to `burst` requests and is
+/// refilled with `requests_per_second` requests every second. All the IPs in
+/// the same subnet also share a bigger bucket, so that a client can not avoid
+/// the limit by using many addresses.
+///
+/// ```toml
+/// [[udp_trackers]]
+/// bind_address = "0.0.0.0:6969"
+///
+/// [udp_trackers.rate_limit]
+/// requests_per_second = 50
+/// burst = 100
+/// subnet_requests_per_second = 500
+/// subnet_burst = 1000
+/// ipv4_subnet_prefix_len = 24
+/// ipv6_subnet_prefix_len = 48
+/// exceeded_action = "drop"
+/// ```
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub struct RateLimit {
+    /// Number of requests per second allowed from a single IP.
+    #[serde(default = "RateLimit::default_requests_per_second")]
+    pub requests_per_second: u32,
+
+    /// Maximum number of requests a single IP can send at once.
+    #[serde(default = "RateLimit::default_burst")]
+    pub burst: u32,
+
+    /// Number of requests per second allowed from all the IPs in a subnet.
+    /// Use `0` to disable the subnet limit.
+    #[serde(default = "RateLimit::default_subnet_requests_per_second")]
+    pub subnet_requests_per_second: u32,
+
+    /// Maximum number of requests all the IPs in a subnet can send at once.
+    #[serde(default = "RateLimit::default_subnet_burst")]
+    pub subnet_burst: u32,
+
+    /// The network prefix length of the IPv4 subnets.
+    #[serde(default = "RateLimit::default_ipv4_subnet_prefix_len")]
+    pub ipv4_subnet_prefix_len: u8,
+
+    /// The network prefix length of the IPv6 subnets.
+    #[serde(default = "RateLimit::default_ipv6_subnet_prefix_len")]
+    pub ipv6_subnet_prefix_len: u8,
+
+    /// What to do with the requests exceeding the limits.
+    #[serde(default = "RateLimit::default_exceeded_action")]
+    pub exceeded_action: RateLimitAction,
+}
+
+impl Default for RateLimit {
+    fn default() -> Self {
+        Self {
+            requests_per_second: Self::default_requests_per_second(),
+            burst: Self::default_burst(),
+            subnet_requests_per_second: Self::default_subnet_requests_per_second(),
+            subnet_burst: Self::default_subnet_burst(),
+            ipv4_subnet_prefix_len: Self::default_ipv4_subnet_prefix_len(),
+            ipv6_subnet_prefix_len: Self::default_ipv6_subnet_prefix_len(),
+            exceeded_action: Self::default_exceeded_action(),
+        }
+    }
+}
+
+impl RateLimit {
+    fn default_requests_per_second() -> u32 {
+        50
+    }
+
+    fn default_burst() -> u32 {
+        100
+    }
+
+    fn default_subnet_requests_per_second() -> u32 {
+        500
+    }
+
+    fn default_subnet_burst() -> u32 {
+        1000
+    }
+
+    fn default_ipv4_subnet_prefix_len() -> u8 {
+        24
+    }
+
+    fn default_ipv6_subnet_prefix_len() -> u8 {
+        48
+    }
+
+    fn default_exceeded_action() -> RateLimitAction {
+        RateLimitAction::Drop
+    }
+}
+
+/// What the UDP tracker does with a request exceeding the rate limits.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum RateLimitAction {
+    /// The request is silently discarded.
+    Drop,
+    /// The tracker answers with an error response.
+    Error,
 }
@@ -17,11 +17,16 @@
 //! - <https://developer.mozilla.org/en-US/docs/Glossary/percent-encoding>
 use aquatic_udp_protocol::PeerId;
 use bittorrent_primitives::info_hash::{self, InfoHash};
+use torrust_tracker_primitives::info_hash_v2::info_hash_from_bytes;
 use torrust_tracker_primitives::peer;
 
 /// Percent decodes a percent encoded infohash. Internally an
 /// [`InfoHash`] is a 20-byte array.
 ///
+/// `BitTorrent` v2 info-hashes (32 bytes) are also accepted. They are
+/// truncated to 20 bytes as described in
+/// [BEP 52](https://www.bittorrent.org/beps/bep_0052.html).
+///
 /// For example, given the infohash `3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0`,
 /// it's percent encoded representation is `%3B%24U%04%CF%5F%11%BB%DB%E1%20%1C%EAjk%F4Z%EE%1B%C0`.
 ///
@@ -47,7 +52,7 @@ use torrust_tracker_primitives::peer;
 /// [`InfoHash`].
 pub fn percent_decode_info_hash(raw_info_hash: &str) -> Result<InfoHash, info_hash::ConversionError> {
     let bytes = percent_encoding::percent_decode_str(raw_info_hash).collect::<Vec<u8>>();
-    InfoHash::try_from(bytes)
+    info_hash_from_bytes(bytes)
 }
 
 /// Percent decodes a percent encoded peer id. Internally a peer [`Id`](PeerId)
@@ -99,15 +104,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_should_decode_a_percent_encoded_v2_info_hash_truncating_it_to_20_bytes() {
+        let encoded_infohash = "%3B%24U%04%CF%5F%11%BB%DB%E1%20%1C%EAjk%F4Z%EE%1B%C0%B8%A1%A4%D5%8D%14%D7%A2%1B%7Dz%45";
+
+        let info_hash = percent_decode_info_hash(encoded_infohash).unwrap();
+
+        assert_eq!(
+            info_hash,
+            InfoHash::from_str("3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0").unwrap()
+        );
+    }
+
     #[test]
     fn it_should_fail_decoding_an_invalid_percent_encoded_info_hash() {
-        let invalid_encoded_infohash = "invalid percent-encoded infohash";
+        // 32 bytes would be a valid v2 info-hash
+        let invalid_encoded_infohash = "invalid percent-encoded info-hash";
 
         let info_hash = percent_decode_info_hash(invalid_encoded_infohash);
 
         assert!(info_hash.is_err());
     }
 
+    #[test]
+    fn it_should_fail_decoding_a_percent_encoded_info_hash_that_is_neither_20_nor_32_bytes_long() {
+        let encoded_infohash = "%3B%24U%04%CF%5F%11%BB%DB%E1%20%1C%EAjk%F4Z%EE%1B%C0%B8%A1%A4%D5%8D%14%D7%A2%1B%7Dz";
+
+        let info_hash = percent_decode_info_hash(encoded_infohash);
+
+        assert!(info_hash.is_err());
+    }
+
     #[test]
     fn it_should_decode_a_percent_encoded_peer_id() {
         let encoded_peer_id = "%2DqB00000000000000000";
@@ -0,0 +1,210 @@
+//! `BitTorrent` v2 info-hashes.
+//!
+//! Refer to [BEP 52. The `BitTorrent` Protocol Specification v2](https://www.bittorrent.org/beps/bep_0052.html).
+//!
+//! v2 torrents are identified by the SHA-256 hash of the info dictionary,
+//! which is a 32-byte value. The tracker protocols only have room for 20-byte
+//! info-hashes, so clients send the v2 info-hash truncated to 20 bytes when
+//! they talk to a tracker. The tracker stores the swarm using that truncated
+//! value.
+//!
+//! Hybrid torrents have both a v1 (SHA-1) and a v2 (SHA-256) info-hash. Peers
+//! can announce either of them, depending on the protocol version they
+//! support.
+use std::panic::Location;
+
+use bittorrent_primitives::info_hash::{self, InfoHash, INFO_HASH_BYTES_LEN};
+
+/// The length in bytes of a v2 info-hash.
+pub const INFO_HASH_V2_BYTES_LEN: usize = 32;
+
+/// A `BitTorrent` v2 info-hash: the SHA-256 hash of the info dictionary.
+///
+/// ```rust
+/// use std::str::FromStr;
+///
+/// use bittorrent_primitives::info_hash::InfoHash;
+/// use torrust_tracker_primitives::info_hash_v2::InfoHashV2;
+///
+/// let info_hash_v2 = InfoHashV2::from_str("3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0b8a1a4d58d14d7a21b7d7a45").unwrap();
+///
+/// assert_eq!(
+///     info_hash_v2.truncated(),
+///     InfoHash::from_str("3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0").unwrap()
+/// );
+/// ```
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Default, Debug, PartialOrd, Ord)]
+pub struct InfoHashV2(pub [u8; INFO_HASH_V2_BYTES_LEN]);
+
+impl InfoHashV2 {
+    /// Returns the `InfoHashV2` internal byte array.
+    #[must_use]
+    pub fn bytes(&self) -> [u8; INFO_HASH_V2_BYTES_LEN] {
+        self.0
+    }
+
+    /// Returns the `InfoHashV2` as a hex string.
+    #[must_use]
+    pub fn to_hex_string(&self) -> String {
+        self.to_string()
+    }
+
+    /// Returns the first 20 bytes of the v2 info-hash. It's the value clients
+    /// use in the tracker protocols.
+    #[must_use]
+    pub fn truncated(&self) -> InfoHash {
+        InfoHash::from(&self.0[..INFO_HASH_BYTES_LEN])
+    }
+}
+
+impl std::fmt::Display for InfoHashV2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut chars = [0u8; 2 * INFO_HASH_V2_BYTES_LEN];
+        binascii::bin2hex(&self.0, &mut chars).expect("failed to hexlify");
+        write!(f, "{}", std::str::from_utf8(&chars).unwrap())
+    }
+}
+
+impl std::str::FromStr for InfoHashV2 {
+    type Err = binascii::ConvertError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut i = Self::default();
+        if s.len() != 2 * INFO_HASH_V2_BYTES_LEN {
+            return Err(binascii::ConvertError::InvalidInputLength);
+        }
+        binascii::hex2bin(s.as_bytes(), &mut i.0)?;
+        Ok(i)
+    }
+}
+
+impl TryFrom<Vec<u8>> for InfoHashV2 {
+    type Error = info_hash::ConversionError;
+
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+        if bytes.len() < INFO_HASH_V2_BYTES_LEN {
+            return Err(info_hash::ConversionError::NotEnoughBytes {
+                location: Location::caller(),
+                message: format! {"got {} bytes, expected {}", bytes.len(), INFO_HASH_V2_BYTES_LEN},
+            });
+        }
+        if bytes.len() > INFO_HASH_V2_BYTES_LEN {
+            return Err(info_hash::ConversionError::TooManyBytes {
+                location: Location::caller(),
+                message: format! {"got {} bytes, expected {}", bytes.len(), INFO_HASH_V2_BYTES_LEN},
+            });
+        }
+
+        let mut info_hash_v2 = Self::default();
+        info_hash_v2.0.copy_from_slice(&bytes);
+        Ok(info_hash_v2)
+    }
+}
+
+/// It parses a hex encoded info-hash. It accepts both v1 info-hashes (40
+/// chars) and v2 info-hashes (64 chars). v2 info-hashes are truncated to 20
+/// bytes.
+///
+/// # Errors
+///
+/// Will return an error if the value is not a valid v1 or v2 info-hash.
+pub fn parse_hex_info_hash(value: &str) -> Result<InfoHash, binascii::ConvertError> {
+    if value.len() == 2 * INFO_HASH_V2_BYTES_LEN {
+        return value.parse::<InfoHashV2>().map(|info_hash_v2| info_hash_v2.truncated());
+    }
+
+    value.parse::<InfoHash>()
+}
+
+/// It builds an info-hash from its raw bytes. It accepts both v1 info-hashes
+/// (20 bytes) and v2 info-hashes (32 bytes). v2 info-hashes are truncated to
+/// 20 bytes.
+///
+/// # Errors
+///
+/// Will return an error if the number of bytes is not valid for a v1 or v2
+/// info-hash.
+pub fn info_hash_from_bytes(bytes: Vec<u8>) -> Result<InfoHash, info_hash::ConversionError> {
+    if bytes.len() == INFO_HASH_V2_BYTES_LEN {
+        return InfoHashV2::try_from(bytes).map(|info_hash_v2| info_hash_v2.truncated());
+    }
+
+    InfoHash::try_from(bytes)
+}
+
+impl serde::ser::Serialize for InfoHashV2 {
+    fn serialize<S: serde::ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_hex_string())
+    }
+}
+
+impl<'de> serde::de::Deserialize<'de> for InfoHashV2 {
+    fn deserialize<D: serde::de::Deserializer<'de>>(des: D) -> Result<Self, D::Error> {
+        let value = <String as serde::de::Deserialize>::deserialize(des)?;
+
+        value.parse::<InfoHashV2>().map_err(|_| {
+            serde::de::Error::invalid_value(serde::de::Unexpected::Str(&value), &"a 64 character long hexadecimal string")
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use bittorrent_primitives::info_hash::InfoHash;
+
+    use crate::info_hash_v2::{info_hash_from_bytes, parse_hex_info_hash, InfoHashV2};
+
+    const V2_HEX: &str = "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0b8a1a4d58d14d7a21b7d7a45";
+    const TRUNCATED_HEX: &str = "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0";
+
+    #[test]
+    fn it_should_be_converted_from_and_to_a_hex_string() {
+        let info_hash_v2 = InfoHashV2::from_str(V2_HEX).unwrap();
+
+        assert_eq!(info_hash_v2.to_hex_string(), V2_HEX);
+    }
+
+    #[test]
+    fn it_should_fail_trying_to_parse_a_hex_string_with_a_wrong_length() {
+        assert!(InfoHashV2::from_str(TRUNCATED_HEX).is_err());
+    }
+
+    #[test]
+    fn it_should_be_truncated_to_the_first_20_bytes() {
+        let info_hash_v2 = InfoHashV2::from_str(V2_HEX).unwrap();
+
+        assert_eq!(info_hash_v2.truncated(), InfoHash::from_str(TRUNCATED_HEX).unwrap());
+    }
+
+    #[test]
+    fn it_should_fail_trying_to_convert_from_a_byte_vector_with_a_wrong_length() {
+        assert!(InfoHashV2::try_from(vec![0; 20]).is_err());
+        assert!(InfoHashV2::try_from(vec![0; 33]).is_err());
+    }
+
+    #[test]
+    fn it_should_parse_both_v1_and_v2_hex_info_hashes() {
+        let expected = InfoHash::from_str(TRUNCATED_HEX).unwrap();
+
+        assert_eq!(parse_hex_info_hash(TRUNCATED_HEX).unwrap(), expected);
+        assert_eq!(parse_hex_info_hash(V2_HEX).unwrap(), expected);
+        assert!(parse_hex_info_hash("3b245504").is_err());
+    }
+
+    #[test]
+    fn it_should_build_an_info_hash_from_both_v1_and_v2_bytes() {
+        let info_hash_v2 = InfoHashV2::from_str(V2_HEX).unwrap();
+
+        assert_eq!(
+            info_hash_from_bytes(info_hash_v2.bytes().to_vec()).unwrap(),
+            info_hash_v2.truncated()
+        );
+        assert_eq!(
+            info_hash_from_bytes(info_hash_v2.truncated().bytes().to_vec()).unwrap(),
+            info_hash_v2.truncated()
+        );
+        assert!(info_hash_from_bytes(vec![0; 21]).is_err());
+    }
+}
@@ -14,11 +14,14 @@
 //! peer::Peer {
 //!     peer_id: PeerId(*b"-qB00000000000000000"),
 //!     peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080),
+//!     alternative_addr: None,
+//!     key: None,
 //!     updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
 //!     uploaded: NumberOfBytes::new(0),
 //!     downloaded: NumberOfBytes::new(0),
 //!     left: NumberOfBytes::new(0),
 //!     event: AnnounceEvent::Started,
+//!     upload_only: false,
 //! };
 //! ```
 
@@ -26,7 +29,7 @@ use std::net::{IpAddr, SocketAddr};
 use std::ops::{Deref, DerefMut};
 use std::sync::Arc;
 
-use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes, PeerId};
+use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes, PeerId, PeerKey};
 use serde::Serialize;
 use zerocopy::FromBytes as _;
 
@@ -48,11 +51,14 @@ use crate::DurationSinceUnixEpoch;
 /// peer::Peer {
 ///     peer_id: PeerId(*b"-qB00000000000000000"),
 ///     peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080),
+///     alternative_addr: None,
+///     key: None,
 ///     updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
 ///     uploaded: NumberOfBytes::new(0),
 ///     downloaded: NumberOfBytes::new(0),
 ///     left: NumberOfBytes::new(0),
 ///     event: AnnounceEvent::Started,
+///     upload_only: false,
 /// };
 /// ```
 #[derive(Debug, Clone, Serialize, Copy, PartialEq, Eq, Hash)]
@@ -62,6 +68,16 @@ pub struct Peer {
     pub peer_id: PeerId,
     /// The IP and port this peer is listening on
     pub peer_addr: SocketAddr,
+    /// The IP and port this peer is listening on for the other IP version,
+    /// when the peer is reachable over both IPv4 and IPv6
+    /// ([BEP 07](https://www.bittorrent.org/beps/bep_0007.html)).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alternative_addr: Option<SocketAddr>,
+    /// The `key` sent by the client in the announce requests. It's not shared
+    /// with other peers, so the client can use it to prove its identity when
+    /// its IP address changes.
+    #[serde(skip)]
+    pub key: Option<PeerKey>,
     /// The last time the the tracker receive an announce request from this peer (timestamp)
     #[serde(serialize_with = "ser_unix_time_value")]
     pub updated: DurationSinceUnixEpoch,
@@ -77,6 +93,9 @@ pub struct Peer {
     /// This is an optional key which maps to started, completed, or stopped (or empty, which is the same as not being present).
     #[serde(serialize_with = "ser_announce_event")]
     pub event: AnnounceEvent,
+    /// The peer only wants to upload. Partial seeds announce it with the
+    /// `paused` event ([BEP 21](https://www.bittorrent.org/beps/bep_0021.html)).
+    pub upload_only: bool,
 }
 
 /// Serializes a `DurationSinceUnixEpoch` as a Unix timestamp in milliseconds.
@@ -201,12 +220,102 @@ impl Peer {
         self.left.0.get() <= 0 && self.event != AnnounceEvent::Stopped
     }
 
+    /// It returns `true` if the peer has not completed downloading but it does
+    /// not want to download more pieces
+    /// ([BEP 21](https://www.bittorrent.org/beps/bep_0021.html)).
+    #[must_use]
+    pub fn is_partial_seed(&self) -> bool {
+        self.upload_only && !self.is_seeder()
+    }
+
     pub fn ip(&mut self) -> IpAddr {
         self.peer_addr.ip()
     }
 
     pub fn change_ip(&mut self, new_ip: &IpAddr) {
         self.peer_addr = SocketAddr::new(*new_ip, self.peer_addr.port());
+
+        if self
+            .alternative_addr
+            .is_some_and(|addr| addr.is_ipv4() == self.peer_addr.is_ipv4())
+        {
+            self.alternative_addr = None;
+        }
+    }
+
+    /// The address the peer is listening on for `IPv4`, if any.
+    #[must_use]
+    pub fn ipv4_addr(&self) -> Option<SocketAddr> {
+        self.addresses().find(SocketAddr::is_ipv4)
+    }
+
+    /// The address the peer is listening on for `IPv6`, if any.
+    #[must_use]
+    pub fn ipv6_addr(&self) -> Option<SocketAddr> {
+        self.addresses().find(SocketAddr::is_ipv6)
+    }
+
+    /// All the addresses the peer is listening on. The main address first.
+    pub fn addresses(&self) -> impl Iterator<Item = SocketAddr> {
+        std::iter::once(self.peer_addr).chain(self.alternative_addr)
+    }
+
+    /// It returns `true` if the peer is listening on the `addr`.
+    #[must_use]
+    pub fn has_address(&self, addr: &SocketAddr) -> bool {
+        self.addresses().any(|peer_addr| peer_addr == *addr)
+    }
+
+    /// It sets the address for the IP version the main address does not
+    /// use. Addresses with the same IP version as the main address are
+    /// ignored.
+    pub fn set_alternative_addr(&mut self, addr: SocketAddr) {
+        if addr.is_ipv4() != self.peer_addr.is_ipv4() {
+            self.alternative_addr = Some(addr);
+        }
+    }
+
+    /// It returns `true` if the `announced` record can replace this record of
+    /// the same peer.
+    ///
+    /// Anyone who knows the peer ID could announce it. An announce from an IP
+    /// the peer is not using is only accepted when it contains the same `key`
+    /// as the previous announces. Peers that never sent a `key` can always be
+    /// updated.
+    #[must_use]
+    pub fn can_be_updated_by(&self, announced: &Peer) -> bool {
+        if self.addresses().any(|addr| addr.ip() == announced.peer_addr.ip()) {
+            return true;
+        }
+
+        match self.key {
+            Some(key) => announced.key == Some(key),
+            None => true,
+        }
+    }
+
+    /// It keeps the address the `previous` record of the same peer was
+    /// announced from, when it's for the IP version this record does not have.
+    ///
+    /// Dual-stack clients can announce the same peer from an `IPv4` and an
+    /// `IPv6` socket. Both announces update the same peer. The alternative
+    /// address of the `previous` record is not kept, so it's removed when the
+    /// client stops announcing it.
+    pub fn merge_addresses(&mut self, previous: &Peer) {
+        if self.alternative_addr.is_none() {
+            self.set_alternative_addr(previous.peer_addr);
+        }
+    }
+
+    /// It keeps the upload-only state of the `previous` record of the same
+    /// peer for the regular announces.
+    ///
+    /// Partial seeds only send the `paused` event when they stop downloading.
+    /// They are upload-only until they send another event.
+    pub fn merge_upload_only(&mut self, previous: &Peer) {
+        if self.event == AnnounceEvent::None && !self.upload_only {
+            self.upload_only = previous.upload_only;
+        }
     }
 }
 
@@ -390,7 +499,7 @@ impl<P: Encoding> FromIterator<Peer> for Vec<P> {
 pub mod fixture {
     use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
-    use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes};
+    use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes, PeerKey};
 
     use super::{Id, Peer, PeerId};
     use crate::DurationSinceUnixEpoch;
@@ -415,11 +524,14 @@ pub mod fixture {
             let peer = Peer {
                 peer_id: PeerId(*b"-qB00000000000000001"),
                 peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
+                alternative_addr: None,
+                key: None,
                 updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
                 uploaded: NumberOfBytes::new(0),
                 downloaded: NumberOfBytes::new(0),
                 left: NumberOfBytes::new(0),
                 event: AnnounceEvent::Completed,
+                upload_only: false,
             };
 
             Self { peer }
@@ -431,11 +543,14 @@ pub mod fixture {
             let peer = Peer {
                 peer_id: PeerId(*b"-qB00000000000000002"),
                 peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 2)), 8080),
+                alternative_addr: None,
+                key: None,
                 updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
                 uploaded: NumberOfBytes::new(0),
                 downloaded: NumberOfBytes::new(0),
                 left: NumberOfBytes::new(10),
                 event: AnnounceEvent::Started,
+                upload_only: false,
             };
 
             Self { peer }
@@ -455,6 +570,34 @@ pub mod fixture {
             self
         }
 
+        #[allow(dead_code)]
+        #[must_use]
+        pub fn with_alternative_addr(mut self, alternative_addr: &SocketAddr) -> Self {
+            self.peer.alternative_addr = Some(*alternative_addr);
+            self
+        }
+
+        #[allow(dead_code)]
+        #[must_use]
+        pub fn with_key(mut self, key: i32) -> Self {
+            self.peer.key = Some(PeerKey::new(key));
+            self
+        }
+
+        #[allow(dead_code)]
+        #[must_use]
+        pub fn with_event(mut self, event: AnnounceEvent) -> Self {
+            self.peer.event = event;
+            self
+        }
+
+        #[allow(dead_code)]
+        #[must_use]
+        pub fn upload_only(mut self) -> Self {
+            self.peer.upload_only = true;
+            self
+        }
+
         #[allow(dead_code)]
         #[must_use]
         pub fn with_bytes_pending_to_download(mut self, left: i64) -> Self {
@@ -494,11 +637,14 @@ pub mod fixture {
             Self {
                 peer_id: PeerId(*b"-qB00000000000000000"),
                 peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
+                alternative_addr: None,
+                key: None,
                 updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
                 uploaded: NumberOfBytes::new(0),
                 downloaded: NumberOfBytes::new(0),
                 left: NumberOfBytes::new(0),
                 event: AnnounceEvent::Started,
+                upload_only: false,
             }
         }
     }
@@ -513,6 +659,145 @@ pub mod fixture {
 
 #[cfg(test)]
 pub mod test {
+    mod torrent_peer {
+        use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+        use aquatic_udp_protocol::AnnounceEvent;
+
+        use crate::peer::fixture::PeerBuilder;
+
+        fn ipv4_addr() -> SocketAddr {
+            SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080)
+        }
+
+        fn ipv6_addr() -> SocketAddr {
+            SocketAddr::new(IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)), 8080)
+        }
+
+        #[test]
+        fn should_return_the_address_for_each_ip_version_of_a_dual_stack_peer() {
+            let peer = PeerBuilder::default()
+                .with_peer_addr(&ipv4_addr())
+                .with_alternative_addr(&ipv6_addr())
+                .build();
+
+            assert_eq!(peer.ipv4_addr(), Some(ipv4_addr()));
+            assert_eq!(peer.ipv6_addr(), Some(ipv6_addr()));
+            assert!(peer.has_address(&ipv6_addr()));
+        }
+
+        #[test]
+        fn should_ignore_an_alternative_address_with_the_same_ip_version_as_the_main_address() {
+            let mut peer = PeerBuilder::default().with_peer_addr(&ipv4_addr()).build();
+
+            peer.set_alternative_addr(SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 2)), 8080));
+
+            assert_eq!(peer.alternative_addr, None);
+        }
+
+        #[test]
+        fn should_keep_the_address_of_the_previous_record_for_the_other_ip_version() {
+            let previous = PeerBuilder::default().with_peer_addr(&ipv6_addr()).build();
+
+            let mut peer = PeerBuilder::default().with_peer_addr(&ipv4_addr()).build();
+
+            peer.merge_addresses(&previous);
+
+            assert_eq!(peer.alternative_addr, Some(ipv6_addr()));
+        }
+
+        #[test]
+        fn should_not_keep_the_alternative_address_of_the_previous_record() {
+            let previous = PeerBuilder::default()
+                .with_peer_addr(&ipv4_addr())
+                .with_alternative_addr(&ipv6_addr())
+                .build();
+
+            let mut peer = PeerBuilder::default().with_peer_addr(&ipv4_addr()).build();
+
+            peer.merge_addresses(&previous);
+
+            assert_eq!(peer.alternative_addr, None);
+        }
+
+        #[test]
+        fn should_be_a_partial_seed_when_it_is_upload_only_and_has_not_completed_downloading() {
+            let partial_seed = PeerBuilder::leecher().upload_only().build();
+            let seeder = PeerBuilder::seeder().upload_only().build();
+
+            assert!(partial_seed.is_partial_seed());
+            assert!(!seeder.is_partial_seed());
+            assert!(!PeerBuilder::leecher().build().is_partial_seed());
+        }
+
+        #[test]
+        fn should_keep_the_upload_only_state_of_the_previous_record_for_regular_announces() {
+            let previous = PeerBuilder::leecher().upload_only().build();
+
+            let mut peer = PeerBuilder::leecher().with_event(AnnounceEvent::None).build();
+
+            peer.merge_upload_only(&previous);
+
+            assert!(peer.upload_only);
+        }
+
+        #[test]
+        fn should_not_be_upload_only_anymore_after_announcing_another_event() {
+            let previous = PeerBuilder::leecher().upload_only().build();
+
+            let mut peer = PeerBuilder::leecher().with_event(AnnounceEvent::Started).build();
+
+            peer.merge_upload_only(&previous);
+
+            assert!(!peer.upload_only);
+        }
+
+        #[test]
+        fn should_be_updated_by_an_announce_from_one_of_its_ips_even_without_the_key() {
+            let peer = PeerBuilder::default()
+                .with_peer_addr(&ipv4_addr())
+                .with_alternative_addr(&ipv6_addr())
+                .with_key(1)
+                .build();
+
+            let announced = PeerBuilder::default().with_peer_addr(&ipv6_addr()).build();
+
+            assert!(peer.can_be_updated_by(&announced));
+        }
+
+        #[test]
+        fn should_only_be_updated_by_an_announce_from_another_ip_when_it_has_the_same_key() {
+            let peer = PeerBuilder::default().with_peer_addr(&ipv4_addr()).with_key(1).build();
+
+            let other_ip = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 2)), 8080);
+
+            assert!(peer.can_be_updated_by(&PeerBuilder::default().with_peer_addr(&other_ip).with_key(1).build()));
+            assert!(!peer.can_be_updated_by(&PeerBuilder::default().with_peer_addr(&other_ip).with_key(2).build()));
+            assert!(!peer.can_be_updated_by(&PeerBuilder::default().with_peer_addr(&other_ip).build()));
+        }
+
+        #[test]
+        fn should_be_updated_by_an_announce_from_another_ip_when_it_never_sent_a_key() {
+            let peer = PeerBuilder::default().with_peer_addr(&ipv4_addr()).build();
+
+            let other_ip = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 2)), 8080);
+
+            assert!(peer.can_be_updated_by(&PeerBuilder::default().with_peer_addr(&other_ip).with_key(2).build()));
+        }
+
+        #[test]
+        fn should_remove_the_alternative_address_when_the_ip_changes_to_the_same_ip_version() {
+            let mut peer = PeerBuilder::default()
+                .with_peer_addr(&ipv4_addr())
+                .with_alternative_addr(&ipv6_addr())
+                .build();
+
+            peer.change_ip(&ipv6_addr().ip());
+
+            assert_eq!(peer.alternative_addr, None);
+        }
+    }
+
     mod torrent_peer_id {
         use aquatic_udp_protocol::PeerId;
 
@@ -12,6 +12,10 @@ pub struct SwarmMetadata {
     pub complete: u32, //seeders
     /// (i.e `leechers`): The number of active peers that have not completed downloading (leechers)
     pub incomplete: u32,
+    /// The number of active leechers that want to download more pieces. It
+    /// does not include the partial seeds
+    /// ([BEP 21](https://www.bittorrent.org/beps/bep_0021.html)).
+    pub downloaders: u32,
 }
 
 impl SwarmMetadata {
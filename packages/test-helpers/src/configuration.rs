@@ -49,6 +49,7 @@ pub fn ephemeral() -> Configuration {
     config.udp_trackers = Some(vec![UdpTracker {
         bind_address: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), udp_port),
         cookie_lifetime: Duration::from_secs(120),
+        rate_limit: None,
     }]);
 
     // Ephemeral socket address for HTTP tracker
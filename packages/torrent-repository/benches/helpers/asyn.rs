@@ -3,6 +3,7 @@ use std::time::{Duration, Instant};
 
 use bittorrent_primitives::info_hash::InfoHash;
 use futures::stream::FuturesUnordered;
+use torrust_tracker_configuration::TrackerPolicy;
 use torrust_tracker_torrent_repository::repository::RepositoryAsync;
 
 use super::utils::{generate_unique_info_hashes, DEFAULT_PEER};
@@ -18,7 +19,9 @@ where
 
         let info_hash = InfoHash::default();
 
-        torrent_repository.upsert_peer(&info_hash, &DEFAULT_PEER).await;
+        torrent_repository
+            .upsert_peer(&info_hash, &DEFAULT_PEER, &TrackerPolicy::default())
+            .await;
 
         torrent_repository.get_swarm_metadata(&info_hash).await;
     }
@@ -37,7 +40,9 @@ where
     let handles = FuturesUnordered::new();
 
     // Add the torrent/peer to the torrent repository
-    torrent_repository.upsert_peer(&info_hash, &DEFAULT_PEER).await;
+    torrent_repository
+        .upsert_peer(&info_hash, &DEFAULT_PEER, &TrackerPolicy::default())
+        .await;
 
     torrent_repository.get_swarm_metadata(&info_hash).await;
 
@@ -47,7 +52,9 @@ where
         let torrent_repository_clone = torrent_repository.clone();
 
         let handle = runtime.spawn(async move {
-            torrent_repository_clone.upsert_peer(&info_hash, &DEFAULT_PEER).await;
+            torrent_repository_clone
+                .upsert_peer(&info_hash, &DEFAULT_PEER, &TrackerPolicy::default())
+                .await;
 
             torrent_repository_clone.get_swarm_metadata(&info_hash).await;
 
@@ -87,7 +94,9 @@ where
         let torrent_repository_clone = torrent_repository.clone();
 
         let handle = runtime.spawn(async move {
-            torrent_repository_clone.upsert_peer(&info_hash, &DEFAULT_PEER).await;
+            torrent_repository_clone
+                .upsert_peer(&info_hash, &DEFAULT_PEER, &TrackerPolicy::default())
+                .await;
 
             torrent_repository_clone.get_swarm_metadata(&info_hash).await;
 
@@ -123,7 +132,9 @@ where
 
     // Add the torrents/peers to the torrent repository
     for info_hash in &info_hashes {
-        torrent_repository.upsert_peer(info_hash, &DEFAULT_PEER).await;
+        torrent_repository
+            .upsert_peer(info_hash, &DEFAULT_PEER, &TrackerPolicy::default())
+            .await;
         torrent_repository.get_swarm_metadata(info_hash).await;
     }
 
@@ -133,7 +144,9 @@ where
         let torrent_repository_clone = torrent_repository.clone();
 
         let handle = runtime.spawn(async move {
-            torrent_repository_clone.upsert_peer(&info_hash, &DEFAULT_PEER).await;
+            torrent_repository_clone
+                .upsert_peer(&info_hash, &DEFAULT_PEER, &TrackerPolicy::default())
+                .await;
             torrent_repository_clone.get_swarm_metadata(&info_hash).await;
 
             if let Some(sleep_time) = sleep {
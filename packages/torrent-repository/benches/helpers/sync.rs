@@ -3,6 +3,7 @@ use std::time::{Duration, Instant};
 
 use bittorrent_primitives::info_hash::InfoHash;
 use futures::stream::FuturesUnordered;
+use torrust_tracker_configuration::TrackerPolicy;
 use torrust_tracker_torrent_repository::repository::Repository;
 
 use super::utils::{generate_unique_info_hashes, DEFAULT_PEER};
@@ -20,7 +21,7 @@ where
 
         let info_hash = InfoHash::default();
 
-        torrent_repository.upsert_peer(&info_hash, &DEFAULT_PEER);
+        torrent_repository.upsert_peer(&info_hash, &DEFAULT_PEER, &TrackerPolicy::default());
 
         torrent_repository.get_swarm_metadata(&info_hash);
     }
@@ -39,7 +40,7 @@ where
     let handles = FuturesUnordered::new();
 
     // Add the torrent/peer to the torrent repository
-    torrent_repository.upsert_peer(&info_hash, &DEFAULT_PEER);
+    torrent_repository.upsert_peer(&info_hash, &DEFAULT_PEER, &TrackerPolicy::default());
 
     torrent_repository.get_swarm_metadata(&info_hash);
 
@@ -49,7 +50,7 @@ where
         let torrent_repository_clone = torrent_repository.clone();
 
         let handle = runtime.spawn(async move {
-            torrent_repository_clone.upsert_peer(&info_hash, &DEFAULT_PEER);
+            torrent_repository_clone.upsert_peer(&info_hash, &DEFAULT_PEER, &TrackerPolicy::default());
 
             torrent_repository_clone.get_swarm_metadata(&info_hash);
 
@@ -89,7 +90,7 @@ where
         let torrent_repository_clone = torrent_repository.clone();
 
         let handle = runtime.spawn(async move {
-            torrent_repository_clone.upsert_peer(&info_hash, &DEFAULT_PEER);
+            torrent_repository_clone.upsert_peer(&info_hash, &DEFAULT_PEER, &TrackerPolicy::default());
 
             torrent_repository_clone.get_swarm_metadata(&info_hash);
 
@@ -125,7 +126,7 @@ where
 
     // Add the torrents/peers to the torrent repository
     for info_hash in &info_hashes {
-        torrent_repository.upsert_peer(info_hash, &DEFAULT_PEER);
+        torrent_repository.upsert_peer(info_hash, &DEFAULT_PEER, &TrackerPolicy::default());
         torrent_repository.get_swarm_metadata(info_hash);
     }
 
@@ -135,7 +136,7 @@ where
         let torrent_repository_clone = torrent_repository.clone();
 
         let handle = runtime.spawn(async move {
-            torrent_repository_clone.upsert_peer(&info_hash, &DEFAULT_PEER);
+            torrent_repository_clone.upsert_peer(&info_hash, &DEFAULT_PEER, &TrackerPolicy::default());
             torrent_repository_clone.get_swarm_metadata(&info_hash);
 
             if let Some(sleep_time) = sleep {
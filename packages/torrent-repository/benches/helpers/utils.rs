@@ -10,11 +10,14 @@ use zerocopy::I64;
 pub const DEFAULT_PEER: Peer = Peer {
     peer_id: PeerId([0; 20]),
     peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080),
+    alternative_addr: None,
+    key: None,
     updated: DurationSinceUnixEpoch::from_secs(0),
     uploaded: NumberOfBytes(I64::ZERO),
     downloaded: NumberOfBytes(I64::ZERO),
     left: NumberOfBytes(I64::ZERO),
     event: AnnounceEvent::Started,
+    upload_only: false,
 };
 
 #[must_use]
@@ -1,7 +1,7 @@
 use std::fmt::Debug;
-use std::net::SocketAddr;
 use std::sync::Arc;
 
+use aquatic_udp_protocol::PeerId;
 use torrust_tracker_configuration::TrackerPolicy;
 use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
 use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
@@ -12,9 +12,24 @@ pub mod mutex_parking_lot;
 pub mod mutex_std;
 pub mod mutex_tokio;
 pub mod peer_list;
+pub mod peer_selection;
 pub mod rw_lock_parking_lot;
 pub mod single;
 
+/// What an announce changed in the swarm.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PeerUpdate {
+    /// The announce was ignored and the swarm was not changed.
+    Ignored,
+    /// The peer was added, updated or removed from the swarm.
+    Applied {
+        /// The previous announce of the peer, if it was in the swarm.
+        previous: Option<Arc<peer::Peer>>,
+        /// `true` if the number of complete downloads has increased.
+        downloaded: bool,
+    },
+}
+
 pub trait Entry {
     /// It returns the swarm metadata (statistics) as a struct:
     ///
@@ -33,18 +48,29 @@ pub trait Entry {
     /// Get all swarm peers, optionally limiting the result.
     fn get_peers(&self, limit: Option<usize>) -> Vec<Arc<peer::Peer>>;
 
+    /// It returns the peer with the given peer ID, if it's in the swarm.
+    fn get_peer(&self, peer_id: &PeerId) -> Option<Arc<peer::Peer>>;
+
     /// It returns the list of peers for a given peer client, optionally limiting the
     /// result.
     ///
     /// It filters out the input peer, typically because we want to return this
-    /// list of peers to that client peer.
-    fn get_peers_for_client(&self, client: &SocketAddr, limit: Option<usize>) -> Vec<Arc<peer::Peer>>;
+    /// list of peers to that client peer. The peers are selected with the
+    /// [`peer_selection`](torrust_tracker_configuration::TrackerPolicy::peer_selection)
+    /// strategy in the Tracker Policy.
+    fn get_peers_for_client(&self, client: &peer::Peer, limit: Option<usize>, policy: &TrackerPolicy) -> Vec<Arc<peer::Peer>>;
 
-    /// It updates a peer and returns true if the number of complete downloads have increased.
+    /// It updates a peer and returns what changed in the swarm, including the
+    /// previous announce of the peer and whether the number of complete
+    /// downloads has increased.
     ///
     /// The number of peers that have complete downloading is synchronously updated when peers are updated.
     /// That's the total torrent downloads counter.
-    fn upsert_peer(&mut self, peer: &peer::Peer) -> bool;
+    ///
+    /// The announce is ignored when it changes the IP of a known peer without
+    /// presenting the same `key` the peer used before. New peers are not added
+    /// when the swarm has the maximum number of peers in the Tracker Policy.
+    fn upsert_peer(&mut self, peer: &peer::Peer, policy: &TrackerPolicy) -> PeerUpdate;
 
     /// It removes peer from the swarm that have not been updated for more than `current_cutoff` seconds
     fn remove_inactive_peers(&mut self, current_cutoff: DurationSinceUnixEpoch);
@@ -57,8 +83,9 @@ pub trait EntrySync {
     fn peers_is_empty(&self) -> bool;
     fn get_peers_len(&self) -> usize;
     fn get_peers(&self, limit: Option<usize>) -> Vec<Arc<peer::Peer>>;
-    fn get_peers_for_client(&self, client: &SocketAddr, limit: Option<usize>) -> Vec<Arc<peer::Peer>>;
-    fn upsert_peer(&self, peer: &peer::Peer) -> bool;
+    fn get_peer(&self, peer_id: &PeerId) -> Option<Arc<peer::Peer>>;
+    fn get_peers_for_client(&self, client: &peer::Peer, limit: Option<usize>, policy: &TrackerPolicy) -> Vec<Arc<peer::Peer>>;
+    fn upsert_peer(&self, peer: &peer::Peer, policy: &TrackerPolicy) -> PeerUpdate;
     fn remove_inactive_peers(&self, current_cutoff: DurationSinceUnixEpoch);
 }
 
@@ -69,12 +96,14 @@ pub trait EntryAsync {
     fn peers_is_empty(&self) -> impl std::future::Future<Output = bool> + Send;
     fn get_peers_len(&self) -> impl std::future::Future<Output = usize> + Send;
     fn get_peers(&self, limit: Option<usize>) -> impl std::future::Future<Output = Vec<Arc<peer::Peer>>> + Send;
+    fn get_peer(&self, peer_id: &PeerId) -> impl std::future::Future<Output = Option<Arc<peer::Peer>>> + Send;
     fn get_peers_for_client(
         &self,
-        client: &SocketAddr,
+        client: &peer::Peer,
         limit: Option<usize>,
+        policy: &TrackerPolicy,
     ) -> impl std::future::Future<Output = Vec<Arc<peer::Peer>>> + Send;
-    fn upsert_peer(self, peer: &peer::Peer) -> impl std::future::Future<Output = bool> + Send;
+    fn upsert_peer(self, peer: &peer::Peer, policy: &TrackerPolicy) -> impl std::future::Future<Output = PeerUpdate> + Send;
     fn remove_inactive_peers(self, current_cutoff: DurationSinceUnixEpoch) -> impl std::future::Future<Output = ()> + Send;
 }
 
@@ -1,11 +1,11 @@
-use std::net::SocketAddr;
 use std::sync::Arc;
 
+use aquatic_udp_protocol::PeerId;
 use torrust_tracker_configuration::TrackerPolicy;
 use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
 use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
 
-use super::{Entry, EntryAsync};
+use super::{Entry, EntryAsync, PeerUpdate};
 use crate::{EntryMutexTokio, EntrySingle};
 
 impl EntryAsync for EntryMutexTokio {
@@ -29,12 +29,21 @@ impl EntryAsync for EntryMutexTokio {
         self.lock().await.get_peers(limit)
     }
 
-    async fn get_peers_for_client(&self, client: &SocketAddr, limit: Option<usize>) -> Vec<Arc<peer::Peer>> {
-        self.lock().await.get_peers_for_client(client, limit)
+    async fn get_peer(&self, peer_id: &PeerId) -> Option<Arc<peer::Peer>> {
+        self.lock().await.get_peer(peer_id)
     }
 
-    async fn upsert_peer(self, peer: &peer::Peer) -> bool {
-        self.lock().await.upsert_peer(peer)
+    async fn get_peers_for_client(
+        &self,
+        client: &peer::Peer,
+        limit: Option<usize>,
+        policy: &TrackerPolicy,
+    ) -> Vec<Arc<peer::Peer>> {
+        self.lock().await.get_peers_for_client(client, limit, policy)
+    }
+
+    async fn upsert_peer(self, peer: &peer::Peer, policy: &TrackerPolicy) -> PeerUpdate {
+        self.lock().await.upsert_peer(peer, policy)
     }
 
     async fn remove_inactive_peers(self, current_cutoff: DurationSinceUnixEpoch) {
@@ -1,18 +1,21 @@
 //! A peer list.
+use std::collections::BTreeMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
 
 use aquatic_udp_protocol::PeerId;
 use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
 
-// code-review: the current implementation uses the peer Id as the ``BTreeMap``
-// key. That would allow adding two identical peers except for the Id.
-// For example, two peers with the same socket address but a different peer Id
-// would be allowed. That would lead to duplicated peers in the tracker responses.
-
+/// The peers of a swarm, indexed by the peer ID.
+///
+/// There is only one peer for each socket address. When a client announces a
+/// new peer ID from the address of another peer, for example after
+/// restarting, the old peer is replaced.
 #[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct PeerList {
-    peers: std::collections::BTreeMap<PeerId, Arc<peer::Peer>>,
+    peers: BTreeMap<PeerId, Arc<peer::Peer>>,
+    /// The ID of the peer using each (main) socket address.
+    addresses: BTreeMap<SocketAddr, PeerId>,
 }
 
 impl PeerList {
@@ -26,17 +29,90 @@ impl PeerList {
         self.peers.is_empty()
     }
 
+    /// It returns `true` if the announced `peer` is allowed to update or
+    /// remove the peer with the same ID in the list.
+    ///
+    /// Refer to [`Peer::can_be_updated_by`](torrust_tracker_primitives::peer::Peer::can_be_updated_by).
+    #[must_use]
+    pub fn accepts(&self, peer: &peer::Peer) -> bool {
+        self.peers
+            .get(&peer.peer_id)
+            .map_or(true, |previous| previous.can_be_updated_by(peer))
+    }
+
+    /// It returns `true` if inserting the `peer` would increase the number of
+    /// peers in the list. The peer is not in the list, and there is no other
+    /// peer using the same socket address.
+    #[must_use]
+    pub fn is_new(&self, peer: &peer::Peer) -> bool {
+        !self.peers.contains_key(&peer.peer_id) && !self.addresses.contains_key(&peer.peer_addr)
+    }
+
+    /// It inserts or updates a peer.
+    ///
+    /// When the peer was announced before from an address with a different IP
+    /// version, the updated peer keeps the previous address as its
+    /// alternative address. That way dual-stack peers are returned to both
+    /// `IPv4` and `IPv6` clients. It also keeps the previous `key` if the
+    /// announce does not contain one, and the upload-only state of partial
+    /// seeds for the announces without an event.
+    ///
+    /// A different peer using the same socket address is removed.
     pub fn upsert(&mut self, value: Arc<peer::Peer>) -> Option<Arc<peer::Peer>> {
-        self.peers.insert(value.peer_id, value)
+        let value = match self.peers.get(&value.peer_id) {
+            Some(previous) => {
+                let mut peer = *value;
+                peer.merge_addresses(previous);
+                peer.merge_upload_only(previous);
+                if peer.key.is_none() {
+                    peer.key = previous.key;
+                }
+                Arc::new(peer)
+            }
+            None => value,
+        };
+
+        if let Some(other_peer_id) = self.addresses.insert(value.peer_addr, value.peer_id) {
+            if other_peer_id != value.peer_id {
+                self.peers.remove(&other_peer_id);
+            }
+        }
+
+        let previous = self.peers.insert(value.peer_id, value.clone());
+
+        if let Some(previous) = &previous {
+            if previous.peer_addr != value.peer_addr {
+                self.remove_address(previous);
+            }
+        }
+
+        previous
     }
 
     pub fn remove(&mut self, key: &PeerId) -> Option<Arc<peer::Peer>> {
-        self.peers.remove(key)
+        let removed = self.peers.remove(key);
+
+        if let Some(peer) = &removed {
+            self.remove_address(peer);
+        }
+
+        removed
+    }
+
+    /// It removes the address of the `peer` from the index, unless it's
+    /// already used by another peer.
+    fn remove_address(&mut self, peer: &peer::Peer) {
+        if self.addresses.get(&peer.peer_addr) == Some(&peer.peer_id) {
+            self.addresses.remove(&peer.peer_addr);
+        }
     }
 
     pub fn remove_inactive_peers(&mut self, current_cutoff: DurationSinceUnixEpoch) {
         self.peers
             .retain(|_, peer| peer::ReadInfo::get_updated(peer) > current_cutoff);
+
+        let peers = &self.peers;
+        self.addresses.retain(|_, peer_id| peers.contains_key(peer_id));
     }
 
     #[must_use]
@@ -52,6 +128,13 @@ impl PeerList {
         }
     }
 
+    /// It iterates over all the peers, starting at the peer in the `offset`
+    /// position and wrapping around to the first one. Skipping the first
+    /// peers does not clone nor allocate anything.
+    pub fn iter_from(&self, offset: usize) -> impl Iterator<Item = &Arc<peer::Peer>> {
+        self.peers.values().skip(offset).chain(self.peers.values().take(offset))
+    }
+
     #[must_use]
     pub fn seeders_and_leechers(&self) -> (usize, usize) {
         let seeders = self.peers.values().filter(|peer| peer.is_seeder()).count();
@@ -60,6 +143,13 @@ impl PeerList {
         (seeders, leechers)
     }
 
+    /// The number of leechers that do not want to download more pieces
+    /// ([BEP 21](https://www.bittorrent.org/beps/bep_0021.html)).
+    #[must_use]
+    pub fn partial_seeds(&self) -> usize {
+        self.peers.values().filter(|peer| peer.is_partial_seed()).count()
+    }
+
     #[must_use]
     pub fn get_peers_excluding_addr(&self, peer_addr: &SocketAddr, limit: Option<usize>) -> Vec<Arc<peer::Peer>> {
         match limit {
@@ -67,7 +157,7 @@ impl PeerList {
                 .peers
                 .values()
                 // Take peers which are not the client peer
-                .filter(|peer| peer::ReadInfo::get_address(peer.as_ref()) != *peer_addr)
+                .filter(|peer| !peer.has_address(peer_addr))
                 // Limit the number of peers on the result
                 .take(limit)
                 .cloned()
@@ -76,7 +166,7 @@ impl PeerList {
                 .peers
                 .values()
                 // Take peers which are not the client peer
-                .filter(|peer| peer::ReadInfo::get_address(peer.as_ref()) != *peer_addr)
+                .filter(|peer| !peer.has_address(peer_addr))
                 .cloned()
                 .collect(),
         }
@@ -87,10 +177,10 @@ impl PeerList {
 mod tests {
 
     mod it_should {
-        use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+        use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
         use std::sync::Arc;
 
-        use aquatic_udp_protocol::PeerId;
+        use aquatic_udp_protocol::{AnnounceEvent, PeerId};
         use torrust_tracker_primitives::peer::fixture::PeerBuilder;
         use torrust_tracker_primitives::DurationSinceUnixEpoch;
 
@@ -141,6 +231,33 @@ mod tests {
             assert_eq!(peer_list.get_all(None), [Arc::new(peer)]);
         }
 
+        #[test]
+        fn allow_iterating_over_all_the_peers_from_any_position() {
+            let mut peer_list = PeerList::default();
+
+            let peer1 = PeerBuilder::default()
+                .with_peer_id(&PeerId(*b"-qB00000000000000001"))
+                .with_peer_addr(&SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080))
+                .build();
+            let peer2 = PeerBuilder::default()
+                .with_peer_id(&PeerId(*b"-qB00000000000000002"))
+                .with_peer_addr(&SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 2)), 8080))
+                .build();
+            let peer3 = PeerBuilder::default()
+                .with_peer_id(&PeerId(*b"-qB00000000000000003"))
+                .with_peer_addr(&SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 3)), 8080))
+                .build();
+
+            peer_list.upsert(peer1.into());
+            peer_list.upsert(peer2.into());
+            peer_list.upsert(peer3.into());
+
+            let peer_ids = |offset| peer_list.iter_from(offset).map(|peer| peer.peer_id).collect::<Vec<_>>();
+
+            assert_eq!(peer_ids(0), vec![peer1.peer_id, peer2.peer_id, peer3.peer_id]);
+            assert_eq!(peer_ids(2), vec![peer3.peer_id, peer1.peer_id, peer2.peer_id]);
+        }
+
         #[test]
         fn allow_getting_one_peer_by_id() {
             let mut peer_list = PeerList::default();
@@ -208,6 +325,60 @@ mod tests {
             assert_eq!(peer_list.get_peers_excluding_addr(&peer2.peer_addr, None), [Arc::new(peer1)]);
         }
 
+        #[test]
+        fn allow_getting_all_peers_excluding_peers_with_a_given_alternative_address() {
+            let mut peer_list = PeerList::default();
+
+            let peer1 = PeerBuilder::default()
+                .with_peer_id(&PeerId(*b"-qB00000000000000001"))
+                .with_peer_addr(&SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 6969))
+                .build();
+            peer_list.upsert(peer1.into());
+
+            let ipv6_addr = SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 6969);
+
+            let peer2 = PeerBuilder::default()
+                .with_peer_id(&PeerId(*b"-qB00000000000000002"))
+                .with_peer_addr(&SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 2)), 6969))
+                .with_alternative_addr(&ipv6_addr)
+                .build();
+            peer_list.upsert(peer2.into());
+
+            assert_eq!(peer_list.get_peers_excluding_addr(&ipv6_addr, None), [Arc::new(peer1)]);
+        }
+
+        #[test]
+        fn keep_the_address_of_a_peer_announced_before_with_a_different_ip_version() {
+            let mut peer_list = PeerList::default();
+
+            let ipv4_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 6969);
+            let ipv6_addr = SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 6969);
+
+            peer_list.upsert(PeerBuilder::default().with_peer_addr(&ipv4_addr).build().into());
+            peer_list.upsert(PeerBuilder::default().with_peer_addr(&ipv6_addr).build().into());
+
+            let peer = peer_list.get(&PeerBuilder::default().build().peer_id).unwrap();
+
+            assert_eq!(peer.peer_addr, ipv6_addr);
+            assert_eq!(peer.alternative_addr, Some(ipv4_addr));
+        }
+
+        #[test]
+        fn replace_the_address_of_a_peer_announced_before_with_the_same_ip_version() {
+            let mut peer_list = PeerList::default();
+
+            let old_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 6969);
+            let new_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 2)), 6969);
+
+            peer_list.upsert(PeerBuilder::default().with_peer_addr(&old_addr).build().into());
+            peer_list.upsert(PeerBuilder::default().with_peer_addr(&new_addr).build().into());
+
+            let peer = peer_list.get(&PeerBuilder::default().build().peer_id).unwrap();
+
+            assert_eq!(peer.peer_addr, new_addr);
+            assert_eq!(peer.alternative_addr, None);
+        }
+
         #[test]
         fn return_the_number_of_seeders_in_the_list() {
             let mut peer_list = PeerList::default();
@@ -238,6 +409,32 @@ mod tests {
             assert_eq!(leechers, 1);
         }
 
+        #[test]
+        fn return_the_number_of_partial_seeds_in_the_list() {
+            let mut peer_list = PeerList::default();
+
+            let seeder = PeerBuilder::seeder().upload_only().build();
+            let partial_seed = PeerBuilder::leecher().upload_only().build();
+
+            peer_list.upsert(seeder.into());
+            peer_list.upsert(partial_seed.into());
+
+            assert_eq!(peer_list.partial_seeds(), 1);
+        }
+
+        #[test]
+        fn keep_the_upload_only_state_of_a_peer_announced_without_an_event() {
+            let mut peer_list = PeerList::default();
+
+            let partial_seed = PeerBuilder::leecher().upload_only().build();
+            peer_list.upsert(partial_seed.into());
+
+            let regular_announce = PeerBuilder::leecher().with_event(AnnounceEvent::None).build();
+            peer_list.upsert(regular_announce.into());
+
+            assert!(peer_list.get(&partial_seed.peer_id).unwrap().upload_only);
+        }
+
         #[test]
         fn remove_inactive_peers() {
             let mut peer_list = PeerList::default();
@@ -271,7 +468,26 @@ mod tests {
         }
 
         #[test]
-        fn allow_inserting_two_identical_peers_except_for_the_id() {
+        fn allow_inserting_two_peers_with_different_ids_and_addresses() {
+            let mut peer_list = PeerList::default();
+
+            let peer1 = PeerBuilder::default()
+                .with_peer_id(&PeerId(*b"-qB00000000000000001"))
+                .with_peer_addr(&SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 6969))
+                .build();
+            peer_list.upsert(peer1.into());
+
+            let peer2 = PeerBuilder::default()
+                .with_peer_id(&PeerId(*b"-qB00000000000000002"))
+                .with_peer_addr(&SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 2)), 6969))
+                .build();
+            peer_list.upsert(peer2.into());
+
+            assert_eq!(peer_list.len(), 2);
+        }
+
+        #[test]
+        fn replace_the_peer_using_the_same_socket_address_with_a_different_id() {
             let mut peer_list = PeerList::default();
 
             let peer1 = PeerBuilder::default().with_peer_id(&PeerId(*b"-qB00000000000000001")).build();
@@ -280,7 +496,77 @@ mod tests {
             let peer2 = PeerBuilder::default().with_peer_id(&PeerId(*b"-qB00000000000000002")).build();
             peer_list.upsert(peer2.into());
 
+            assert_eq!(peer_list.get_all(None), [Arc::new(peer2)]);
+        }
+
+        #[test]
+        fn not_remove_a_peer_using_the_old_socket_address_of_another_peer() {
+            let mut peer_list = PeerList::default();
+
+            let old_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 6969);
+            let new_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 2)), 6969);
+
+            let peer1 = PeerBuilder::default()
+                .with_peer_id(&PeerId(*b"-qB00000000000000001"))
+                .with_peer_addr(&old_addr)
+                .build();
+            peer_list.upsert(peer1.into());
+
+            // The first peer moves to a new address
+            peer_list.upsert(
+                PeerBuilder::default()
+                    .with_peer_id(&PeerId(*b"-qB00000000000000001"))
+                    .with_peer_addr(&new_addr)
+                    .build()
+                    .into(),
+            );
+
+            // A second peer takes the old address, and then the first peer is removed
+            let peer2 = PeerBuilder::default()
+                .with_peer_id(&PeerId(*b"-qB00000000000000002"))
+                .with_peer_addr(&old_addr)
+                .build();
+            peer_list.upsert(peer2.into());
+
+            peer_list.remove(&peer1.peer_id);
+
+            // Another peer announcing from the new address does not remove the second peer
+            peer_list.upsert(
+                PeerBuilder::default()
+                    .with_peer_id(&PeerId(*b"-qB00000000000000003"))
+                    .with_peer_addr(&new_addr)
+                    .build()
+                    .into(),
+            );
+
             assert_eq!(peer_list.len(), 2);
+            assert_eq!(peer_list.get(&peer2.peer_id), Some(Arc::new(peer2)).as_ref());
+        }
+
+        #[test]
+        fn keep_the_key_of_a_peer_when_the_announce_does_not_contain_it() {
+            let mut peer_list = PeerList::default();
+
+            peer_list.upsert(PeerBuilder::default().with_key(42).build().into());
+            peer_list.upsert(PeerBuilder::default().build().into());
+
+            let peer = peer_list.get(&PeerBuilder::default().build().peer_id).unwrap();
+
+            assert_eq!(peer.key, PeerBuilder::default().with_key(42).build().key);
+        }
+
+        #[test]
+        fn not_accept_an_announce_from_another_ip_without_the_key_of_the_peer() {
+            let mut peer_list = PeerList::default();
+
+            peer_list.upsert(PeerBuilder::default().with_key(42).build().into());
+
+            let other_ip = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080);
+
+            assert!(!peer_list.accepts(&PeerBuilder::default().with_peer_addr(&other_ip).build()));
+            assert!(!peer_list.accepts(&PeerBuilder::default().with_peer_addr(&other_ip).with_key(43).build()));
+            assert!(peer_list.accepts(&PeerBuilder::default().with_peer_addr(&other_ip).with_key(42).build()));
+            assert!(peer_list.accepts(&PeerBuilder::default().build()));
         }
     }
 }
@@ -0,0 +1,171 @@
+//! Strategies to select the peers returned to a client.
+//!
+//! When the swarm has more peers than the client wants, the tracker returns
+//! only some of them. Returning always the same peers would make the swarm
+//! lopsided, so the candidates are visited starting at a random peer of the
+//! swarm. The strategies consume the candidates lazily and stop as soon as they
+//! have enough peers, so only the selected peers are cloned.
+//!
+//! The strategy is selected with the
+//! [`peer_selection`](torrust_tracker_configuration::TrackerPolicy::peer_selection)
+//! option in the tracker policy.
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use rand::Rng;
+use torrust_tracker_configuration::PeerSelectionStrategy;
+use torrust_tracker_primitives::peer;
+
+/// The number of candidates weighted for each peer returned by the
+/// [`RecencyWeighted`] strategy.
+const RECENCY_WEIGHTED_CANDIDATES_PER_PEER: usize = 4;
+
+/// A strategy to select the peers returned to a client.
+pub trait PeerSelection: Debug + Send + Sync {
+    /// It selects up to `limit` peers from the `candidates` for the `client`.
+    /// The candidates do not include the client, and they start at a random
+    /// peer of the swarm.
+    fn select(
+        &self,
+        client: &peer::Peer,
+        candidates: &mut dyn Iterator<Item = Arc<peer::Peer>>,
+        limit: Option<usize>,
+    ) -> Vec<Arc<peer::Peer>>;
+}
+
+/// It returns the implementation of a strategy.
+#[must_use]
+pub fn strategy(strategy: PeerSelectionStrategy) -> &'static dyn PeerSelection {
+    match strategy {
+        PeerSelectionStrategy::Random => &Random,
+        PeerSelectionStrategy::SeedersDontGetSeeders => &SeedersDontGetSeeders,
+        PeerSelectionStrategy::SameAddressFamily => &SameAddressFamily,
+        PeerSelectionStrategy::RecencyWeighted => &RecencyWeighted,
+    }
+}
+
+/// The first peers from a random position of the swarm.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Random;
+
+impl PeerSelection for Random {
+    fn select(
+        &self,
+        _client: &peer::Peer,
+        candidates: &mut dyn Iterator<Item = Arc<peer::Peer>>,
+        limit: Option<usize>,
+    ) -> Vec<Arc<peer::Peer>> {
+        take(candidates, limit)
+    }
+}
+
+/// Like [`Random`], but seeders only get leechers. Seeders do not need other
+/// seeders.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SeedersDontGetSeeders;
+
+impl PeerSelection for SeedersDontGetSeeders {
+    fn select(
+        &self,
+        client: &peer::Peer,
+        candidates: &mut dyn Iterator<Item = Arc<peer::Peer>>,
+        limit: Option<usize>,
+    ) -> Vec<Arc<peer::Peer>> {
+        if client.is_seeder() {
+            return take(&mut candidates.filter(|peer| !peer.is_seeder()), limit);
+        }
+
+        take(candidates, limit)
+    }
+}
+
+/// Like [`Random`], but the peers with an address of the same IP family as the
+/// client go first. The other peers are only used when there are not enough
+/// peers of the same family.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SameAddressFamily;
+
+impl PeerSelection for SameAddressFamily {
+    fn select(
+        &self,
+        client: &peer::Peer,
+        candidates: &mut dyn Iterator<Item = Arc<peer::Peer>>,
+        limit: Option<usize>,
+    ) -> Vec<Arc<peer::Peer>> {
+        let is_ipv4 = client.peer_addr.is_ipv4();
+        let limit = limit.unwrap_or(usize::MAX);
+
+        if limit == 0 {
+            return vec![];
+        }
+
+        let mut same_family = vec![];
+        let mut other_family = vec![];
+
+        for peer in candidates {
+            if peer.addresses().any(|addr| addr.is_ipv4() == is_ipv4) {
+                same_family.push(peer);
+
+                if same_family.len() == limit {
+                    return same_family;
+                }
+            } else if other_family.len() < limit {
+                other_family.push(peer);
+            }
+        }
+
+        let pending = limit.saturating_sub(same_family.len());
+
+        same_family.extend(other_family.into_iter().take(pending));
+
+        same_family
+    }
+}
+
+/// A random sample where the peers that announced recently are more likely to
+/// be selected. Peers that have not announced for a while could have left the
+/// swarm without sending the `stopped` event.
+///
+/// The sample is taken from the first `limit * 4` candidates. The weight of a
+/// peer is `1 / (1 + s)`, where `s` is the number of seconds since its last
+/// announce.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RecencyWeighted;
+
+impl PeerSelection for RecencyWeighted {
+    fn select(
+        &self,
+        client: &peer::Peer,
+        candidates: &mut dyn Iterator<Item = Arc<peer::Peer>>,
+        limit: Option<usize>,
+    ) -> Vec<Arc<peer::Peer>> {
+        let Some(limit) = limit else {
+            return candidates.collect();
+        };
+
+        let mut rng = rand::thread_rng();
+
+        // Weighted random sampling without replacement (Efraimidis-Spirakis).
+        // Every peer gets the key `u^(1/weight)` and the greatest keys win.
+        let mut keyed: Vec<(f64, Arc<peer::Peer>)> = candidates
+            .take(limit.saturating_mul(RECENCY_WEIGHTED_CANDIDATES_PER_PEER))
+            .map(|peer| {
+                let age = client.updated.saturating_sub(peer.updated).as_secs_f64();
+                let key = rng.gen::<f64>().powf(1.0 + age);
+                (key, peer)
+            })
+            .collect();
+
+        keyed.sort_unstable_by(|a, b| b.0.total_cmp(&a.0));
+
+        keyed.into_iter().take(limit).map(|(_, peer)| peer).collect()
+    }
+}
+
+/// It returns the first `limit` peers, or all the peers when there is no limit.
+fn take(peers: &mut dyn Iterator<Item = Arc<peer::Peer>>, limit: Option<usize>) -> Vec<Arc<peer::Peer>> {
+    match limit {
+        Some(limit) => peers.take(limit).collect(),
+        None => peers.collect(),
+    }
+}
@@ -1,11 +1,11 @@
-use std::net::SocketAddr;
 use std::sync::Arc;
 
+use aquatic_udp_protocol::PeerId;
 use torrust_tracker_configuration::TrackerPolicy;
 use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
 use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
 
-use super::{Entry, EntrySync};
+use super::{Entry, EntrySync, PeerUpdate};
 use crate::{EntryRwLockParkingLot, EntrySingle};
 
 impl EntrySync for EntryRwLockParkingLot {
@@ -29,12 +29,16 @@ impl EntrySync for EntryRwLockParkingLot {
         self.read().get_peers(limit)
     }
 
-    fn get_peers_for_client(&self, client: &SocketAddr, limit: Option<usize>) -> Vec<Arc<peer::Peer>> {
-        self.read().get_peers_for_client(client, limit)
+    fn get_peer(&self, peer_id: &PeerId) -> Option<Arc<peer::Peer>> {
+        self.read().get_peer(peer_id)
     }
 
-    fn upsert_peer(&self, peer: &peer::Peer) -> bool {
-        self.write().upsert_peer(peer)
+    fn get_peers_for_client(&self, client: &peer::Peer, limit: Option<usize>, policy: &TrackerPolicy) -> Vec<Arc<peer::Peer>> {
+        self.read().get_peers_for_client(client, limit, policy)
+    }
+
+    fn upsert_peer(&self, peer: &peer::Peer, policy: &TrackerPolicy) -> PeerUpdate {
+        self.write().upsert_peer(peer, policy)
     }
 
     fn remove_inactive_peers(&self, current_cutoff: DurationSinceUnixEpoch) {
@@ -1,24 +1,26 @@
-use std::net::SocketAddr;
 use std::sync::Arc;
 
-use aquatic_udp_protocol::AnnounceEvent;
+use aquatic_udp_protocol::{AnnounceEvent, PeerId};
+use rand::Rng;
 use torrust_tracker_configuration::TrackerPolicy;
 use torrust_tracker_primitives::peer::{self};
 use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
 use torrust_tracker_primitives::DurationSinceUnixEpoch;
 
-use super::Entry;
+use super::{peer_selection, Entry, PeerUpdate};
 use crate::EntrySingle;
 
 impl Entry for EntrySingle {
     #[allow(clippy::cast_possible_truncation)]
     fn get_swarm_metadata(&self) -> SwarmMetadata {
         let (seeders, leechers) = self.swarm.seeders_and_leechers();
+        let partial_seeds = self.swarm.partial_seeds();
 
         SwarmMetadata {
             downloaded: self.downloaded,
             complete: seeders as u32,
             incomplete: leechers as u32,
+            downloaders: (leechers - partial_seeds) as u32,
         }
     }
 
@@ -46,31 +48,69 @@ impl Entry for EntrySingle {
         self.swarm.get_all(limit)
     }
 
-    fn get_peers_for_client(&self, client: &SocketAddr, limit: Option<usize>) -> Vec<Arc<peer::Peer>> {
-        self.swarm.get_peers_excluding_addr(client, limit)
+    fn get_peer(&self, peer_id: &PeerId) -> Option<Arc<peer::Peer>> {
+        self.swarm.get(peer_id).cloned()
     }
 
-    fn upsert_peer(&mut self, peer: &peer::Peer) -> bool {
+    fn get_peers_for_client(&self, client: &peer::Peer, limit: Option<usize>, policy: &TrackerPolicy) -> Vec<Arc<peer::Peer>> {
+        if self.swarm.is_empty() {
+            return vec![];
+        }
+
+        // The stored record keeps the upload-only state of the previous announces
+        let client_is_upload_only = self
+            .swarm
+            .get(&client.peer_id)
+            .map_or(client.upload_only, |stored| stored.upload_only);
+
+        // Start at a random peer, so that the clients do not always get the same peers
+        let offset = rand::thread_rng().gen_range(0..self.swarm.len());
+
+        let mut candidates = self
+            .swarm
+            .iter_from(offset)
+            // Take peers which are not the client peer
+            .filter(|peer| !peer.has_address(&client.peer_addr))
+            // Upload-only peers do not want anything from each other (BEP 21)
+            .filter(|peer| !(client_is_upload_only && peer.upload_only))
+            .cloned();
+
+        peer_selection::strategy(policy.peer_selection).select(client, &mut candidates, limit)
+    }
+
+    fn upsert_peer(&mut self, peer: &peer::Peer, policy: &TrackerPolicy) -> PeerUpdate {
         let mut downloaded_stats_updated: bool = false;
 
-        match peer::ReadInfo::get_event(peer) {
-            AnnounceEvent::Stopped => {
-                drop(self.swarm.remove(&peer::ReadInfo::get_id(peer)));
+        // Ignore the announces changing the IP of a peer without its `key`
+        if !self.swarm.accepts(peer) {
+            return PeerUpdate::Ignored;
+        }
+
+        // Do not add new peers to a full swarm
+        if let Some(max_peers) = policy.max_peers_per_torrent {
+            if self.swarm.is_new(peer) && self.swarm.len() >= max_peers as usize {
+                return PeerUpdate::Ignored;
             }
+        }
+
+        let previous = match peer::ReadInfo::get_event(peer) {
+            AnnounceEvent::Stopped => self.swarm.remove(&peer::ReadInfo::get_id(peer)),
             AnnounceEvent::Completed => {
                 let previous = self.swarm.upsert(Arc::new(*peer));
                 // Don't count if peer was not previously known and not already completed.
-                if previous.is_some_and(|p| p.event != AnnounceEvent::Completed) {
+                if previous.as_ref().is_some_and(|p| p.event != AnnounceEvent::Completed) {
                     self.downloaded += 1;
                     downloaded_stats_updated = true;
                 }
+                previous
             }
-            _ => {
-                drop(self.swarm.upsert(Arc::new(*peer)));
-            }
-        }
+            _ => self.swarm.upsert(Arc::new(*peer)),
+        };
 
-        downloaded_stats_updated
+        PeerUpdate::Applied {
+            previous,
+            downloaded: downloaded_stats_updated,
+        }
     }
 
     fn remove_inactive_peers(&mut self, current_cutoff: DurationSinceUnixEpoch) {
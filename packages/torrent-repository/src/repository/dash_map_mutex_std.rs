@@ -10,7 +10,7 @@ use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch, PersistentTorrent
 
 use super::Repository;
 use crate::entry::peer_list::PeerList;
-use crate::entry::{Entry, EntrySync};
+use crate::entry::{Entry, EntrySync, PeerUpdate};
 use crate::{EntryMutexStd, EntrySingle};
 
 #[derive(Default, Debug)]
@@ -23,13 +23,14 @@ where
     EntryMutexStd: EntrySync,
     EntrySingle: Entry,
 {
-    fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer) {
+    fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer, policy: &TrackerPolicy) -> PeerUpdate {
         if let Some(entry) = self.torrents.get(info_hash) {
-            entry.upsert_peer(peer);
+            entry.upsert_peer(peer, policy)
         } else {
             let _unused = self.torrents.insert(*info_hash, Arc::default());
-            if let Some(entry) = self.torrents.get(info_hash) {
-                entry.upsert_peer(peer);
+            match self.torrents.get(info_hash) {
+                Some(entry) => entry.upsert_peer(peer, policy),
+                None => PeerUpdate::Ignored,
             }
         }
     }
@@ -7,7 +7,7 @@ use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch, PersistentTorrent
 
 use super::Repository;
 use crate::entry::peer_list::PeerList;
-use crate::entry::Entry;
+use crate::entry::{Entry, PeerUpdate};
 use crate::{EntrySingle, TorrentsRwLockStd};
 
 #[derive(Default, Debug)]
@@ -46,12 +46,12 @@ impl Repository<EntrySingle> for TorrentsRwLockStd
 where
     EntrySingle: Entry,
 {
-    fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer) {
+    fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer, policy: &TrackerPolicy) -> PeerUpdate {
         let mut db = self.get_torrents_mut();
 
         let entry = db.entry(*info_hash).or_insert(EntrySingle::default());
 
-        entry.upsert_peer(peer);
+        entry.upsert_peer(peer, policy)
     }
 
     fn get_swarm_metadata(&self, info_hash: &InfoHash) -> Option<SwarmMetadata> {
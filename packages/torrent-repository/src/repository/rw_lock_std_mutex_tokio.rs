@@ -13,7 +13,7 @@ use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch, PersistentTorrent
 
 use super::RepositoryAsync;
 use crate::entry::peer_list::PeerList;
-use crate::entry::{Entry, EntryAsync};
+use crate::entry::{Entry, EntryAsync, PeerUpdate};
 use crate::{EntryMutexTokio, EntrySingle, TorrentsRwLockStdMutexTokio};
 
 impl TorrentsRwLockStdMutexTokio {
@@ -37,7 +37,7 @@ where
     EntryMutexTokio: EntryAsync,
     EntrySingle: Entry,
 {
-    async fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer) {
+    async fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer, policy: &TrackerPolicy) -> PeerUpdate {
         let maybe_entry = self.get_torrents().get(info_hash).cloned();
 
         let entry = if let Some(entry) = maybe_entry {
@@ -48,7 +48,7 @@ where
             entry.clone()
         };
 
-        entry.upsert_peer(peer).await;
+        entry.upsert_peer(peer, policy).await
     }
 
     async fn get_swarm_metadata(&self, info_hash: &InfoHash) -> Option<SwarmMetadata> {
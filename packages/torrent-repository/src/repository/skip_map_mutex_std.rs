@@ -10,7 +10,7 @@ use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch, PersistentTorrent
 
 use super::Repository;
 use crate::entry::peer_list::PeerList;
-use crate::entry::{Entry, EntrySync};
+use crate::entry::{Entry, EntrySync, PeerUpdate};
 use crate::{EntryMutexParkingLot, EntryMutexStd, EntryRwLockParkingLot, EntrySingle};
 
 #[derive(Default, Debug)]
@@ -23,9 +23,9 @@ where
     EntryMutexStd: EntrySync,
     EntrySingle: Entry,
 {
-    fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer) {
+    fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer, policy: &TrackerPolicy) -> PeerUpdate {
         let entry = self.torrents.get_or_insert(*info_hash, Arc::default());
-        entry.value().upsert_peer(peer);
+        entry.value().upsert_peer(peer, policy)
     }
 
     fn get_swarm_metadata(&self, info_hash: &InfoHash) -> Option<SwarmMetadata> {
@@ -114,9 +114,9 @@ where
     EntryRwLockParkingLot: EntrySync,
     EntrySingle: Entry,
 {
-    fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer) {
+    fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer, policy: &TrackerPolicy) -> PeerUpdate {
         let entry = self.torrents.get_or_insert(*info_hash, Arc::default());
-        entry.value().upsert_peer(peer);
+        entry.value().upsert_peer(peer, policy)
     }
 
     fn get_swarm_metadata(&self, info_hash: &InfoHash) -> Option<SwarmMetadata> {
@@ -205,9 +205,9 @@ where
     EntryMutexParkingLot: EntrySync,
     EntrySingle: Entry,
 {
-    fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer) {
+    fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer, policy: &TrackerPolicy) -> PeerUpdate {
         let entry = self.torrents.get_or_insert(*info_hash, Arc::default());
-        entry.value().upsert_peer(peer);
+        entry.value().upsert_peer(peer, policy)
     }
 
     fn get_swarm_metadata(&self, info_hash: &InfoHash) -> Option<SwarmMetadata> {
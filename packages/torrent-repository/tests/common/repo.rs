@@ -4,6 +4,7 @@ use torrust_tracker_primitives::pagination::Pagination;
 use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
 use torrust_tracker_primitives::torrent_metrics::TorrentsMetrics;
 use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch, PersistentTorrents};
+use torrust_tracker_torrent_repository::entry::PeerUpdate;
 use torrust_tracker_torrent_repository::repository::{Repository as _, RepositoryAsync as _};
 use torrust_tracker_torrent_repository::{
     EntrySingle, TorrentsDashMapMutexStd, TorrentsRwLockStd, TorrentsRwLockStdMutexStd, TorrentsRwLockStdMutexTokio,
@@ -26,18 +27,20 @@ pub(crate) enum Repo {
 }
 
 impl Repo {
-    pub(crate) async fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer) {
+    pub(crate) async fn upsert_peer(&self, info_hash: &InfoHash, peer: &peer::Peer) -> PeerUpdate {
+        let policy = TrackerPolicy::default();
+
         match self {
-            Repo::RwLockStd(repo) => repo.upsert_peer(info_hash, peer),
-            Repo::RwLockStdMutexStd(repo) => repo.upsert_peer(info_hash, peer),
-            Repo::RwLockStdMutexTokio(repo) => repo.upsert_peer(info_hash, peer).await,
-            Repo::RwLockTokio(repo) => repo.upsert_peer(info_hash, peer).await,
-            Repo::RwLockTokioMutexStd(repo) => repo.upsert_peer(info_hash, peer).await,
-            Repo::RwLockTokioMutexTokio(repo) => repo.upsert_peer(info_hash, peer).await,
-            Repo::SkipMapMutexStd(repo) => repo.upsert_peer(info_hash, peer),
-            Repo::SkipMapMutexParkingLot(repo) => repo.upsert_peer(info_hash, peer),
-            Repo::SkipMapRwLockParkingLot(repo) => repo.upsert_peer(info_hash, peer),
-            Repo::DashMapMutexStd(repo) => repo.upsert_peer(info_hash, peer),
+            Repo::RwLockStd(repo) => repo.upsert_peer(info_hash, peer, &policy),
+            Repo::RwLockStdMutexStd(repo) => repo.upsert_peer(info_hash, peer, &policy),
+            Repo::RwLockStdMutexTokio(repo) => repo.upsert_peer(info_hash, peer, &policy).await,
+            Repo::RwLockTokio(repo) => repo.upsert_peer(info_hash, peer, &policy).await,
+            Repo::RwLockTokioMutexStd(repo) => repo.upsert_peer(info_hash, peer, &policy).await,
+            Repo::RwLockTokioMutexTokio(repo) => repo.upsert_peer(info_hash, peer, &policy).await,
+            Repo::SkipMapMutexStd(repo) => repo.upsert_peer(info_hash, peer, &policy),
+            Repo::SkipMapMutexParkingLot(repo) => repo.upsert_peer(info_hash, peer, &policy),
+            Repo::SkipMapRwLockParkingLot(repo) => repo.upsert_peer(info_hash, peer, &policy),
+            Repo::DashMapMutexStd(repo) => repo.upsert_peer(info_hash, peer, &policy),
         }
     }
 
@@ -1,10 +1,10 @@
-use std::net::SocketAddr;
 use std::sync::Arc;
 
+use aquatic_udp_protocol::PeerId;
 use torrust_tracker_configuration::TrackerPolicy;
 use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
 use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
-use torrust_tracker_torrent_repository::entry::{Entry as _, EntryAsync as _, EntrySync as _};
+use torrust_tracker_torrent_repository::entry::{Entry as _, EntryAsync as _, EntrySync as _, PeerUpdate};
 use torrust_tracker_torrent_repository::{
     EntryMutexParkingLot, EntryMutexStd, EntryMutexTokio, EntryRwLockParkingLot, EntrySingle,
 };
@@ -69,23 +69,42 @@ impl Torrent {
         }
     }
 
-    pub(crate) async fn get_peers_for_client(&self, client: &SocketAddr, limit: Option<usize>) -> Vec<Arc<peer::Peer>> {
+    pub(crate) async fn get_peer(&self, peer_id: &PeerId) -> Option<Arc<peer::Peer>> {
         match self {
-            Torrent::Single(entry) => entry.get_peers_for_client(client, limit),
-            Torrent::MutexStd(entry) => entry.get_peers_for_client(client, limit),
-            Torrent::MutexTokio(entry) => entry.clone().get_peers_for_client(client, limit).await,
-            Torrent::MutexParkingLot(entry) => entry.get_peers_for_client(client, limit),
-            Torrent::RwLockParkingLot(entry) => entry.get_peers_for_client(client, limit),
+            Torrent::Single(entry) => entry.get_peer(peer_id),
+            Torrent::MutexStd(entry) => entry.get_peer(peer_id),
+            Torrent::MutexTokio(entry) => entry.clone().get_peer(peer_id).await,
+            Torrent::MutexParkingLot(entry) => entry.get_peer(peer_id),
+            Torrent::RwLockParkingLot(entry) => entry.get_peer(peer_id),
         }
     }
 
-    pub(crate) async fn upsert_peer(&mut self, peer: &peer::Peer) -> bool {
+    pub(crate) async fn get_peers_for_client(
+        &self,
+        client: &peer::Peer,
+        limit: Option<usize>,
+        policy: &TrackerPolicy,
+    ) -> Vec<Arc<peer::Peer>> {
         match self {
-            Torrent::Single(entry) => entry.upsert_peer(peer),
-            Torrent::MutexStd(entry) => entry.upsert_peer(peer),
-            Torrent::MutexTokio(entry) => entry.clone().upsert_peer(peer).await,
-            Torrent::MutexParkingLot(entry) => entry.upsert_peer(peer),
-            Torrent::RwLockParkingLot(entry) => entry.upsert_peer(peer),
+            Torrent::Single(entry) => entry.get_peers_for_client(client, limit, policy),
+            Torrent::MutexStd(entry) => entry.get_peers_for_client(client, limit, policy),
+            Torrent::MutexTokio(entry) => entry.clone().get_peers_for_client(client, limit, policy).await,
+            Torrent::MutexParkingLot(entry) => entry.get_peers_for_client(client, limit, policy),
+            Torrent::RwLockParkingLot(entry) => entry.get_peers_for_client(client, limit, policy),
+        }
+    }
+
+    pub(crate) async fn upsert_peer(&mut self, peer: &peer::Peer) -> PeerUpdate {
+        self.upsert_peer_with_policy(peer, &TrackerPolicy::default()).await
+    }
+
+    pub(crate) async fn upsert_peer_with_policy(&mut self, peer: &peer::Peer, policy: &TrackerPolicy) -> PeerUpdate {
+        match self {
+            Torrent::Single(entry) => entry.upsert_peer(peer, policy),
+            Torrent::MutexStd(entry) => entry.upsert_peer(peer, policy),
+            Torrent::MutexTokio(entry) => entry.clone().upsert_peer(peer, policy).await,
+            Torrent::MutexParkingLot(entry) => entry.upsert_peer(peer, policy),
+            Torrent::RwLockParkingLot(entry) => entry.upsert_peer(peer, policy),
         }
     }
 
@@ -1,4 +1,4 @@
-use std::net::SocketAddr;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
 use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes, PeerId};
 use torrust_tracker_clock::clock::Time;
@@ -74,6 +74,7 @@ pub fn a_completed_peer(id: i32) -> peer::Peer {
         .with_number_of_bytes_left(0)
         .with_event_completed()
         .with_peer_id(*peer_id)
+        .with_peer_address(a_peer_address(id))
         .into()
 }
 
@@ -86,5 +87,13 @@ pub fn a_started_peer(id: i32) -> peer::Peer {
         .with_number_of_bytes_left(1)
         .with_event_started()
         .with_peer_id(*peer_id)
+        .with_peer_address(a_peer_address(id))
         .into()
 }
+
+/// A different socket address for each peer id. The tracker only keeps one
+/// peer for each socket address.
+#[must_use]
+fn a_peer_address(id: i32) -> SocketAddr {
+    SocketAddr::new(IpAddr::V4(Ipv4Addr::from(id.to_be_bytes())), 8080)
+}
@@ -1,14 +1,17 @@
-use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::collections::HashSet;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::ops::Sub;
+use std::sync::Arc;
 use std::time::Duration;
 
-use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes};
+use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes, PeerKey};
 use rstest::{fixture, rstest};
 use torrust_tracker_clock::clock::stopped::Stopped as _;
 use torrust_tracker_clock::clock::{self, Time as _};
-use torrust_tracker_configuration::{TrackerPolicy, TORRENT_PEERS_LIMIT};
+use torrust_tracker_configuration::{PeerSelectionStrategy, TrackerPolicy, TORRENT_PEERS_LIMIT};
 use torrust_tracker_primitives::peer;
 use torrust_tracker_primitives::peer::Peer;
+use torrust_tracker_torrent_repository::entry::PeerUpdate;
 use torrust_tracker_torrent_repository::{
     EntryMutexParkingLot, EntryMutexStd, EntryMutexTokio, EntryRwLockParkingLot, EntrySingle,
 };
@@ -43,22 +46,42 @@ fn rw_lock_parking_lot() -> Torrent {
 
 #[fixture]
 fn policy_none() -> TrackerPolicy {
-    TrackerPolicy::new(0, false, false)
+    TrackerPolicy::new(0, false, false, PeerSelectionStrategy::Random, None)
 }
 
 #[fixture]
 fn policy_persist() -> TrackerPolicy {
-    TrackerPolicy::new(0, true, false)
+    TrackerPolicy::new(0, true, false, PeerSelectionStrategy::Random, None)
 }
 
 #[fixture]
 fn policy_remove() -> TrackerPolicy {
-    TrackerPolicy::new(0, false, true)
+    TrackerPolicy::new(0, false, true, PeerSelectionStrategy::Random, None)
 }
 
 #[fixture]
 fn policy_remove_persist() -> TrackerPolicy {
-    TrackerPolicy::new(0, true, true)
+    TrackerPolicy::new(0, true, true, PeerSelectionStrategy::Random, None)
+}
+
+#[fixture]
+fn policy_random() -> TrackerPolicy {
+    TrackerPolicy::new(0, false, false, PeerSelectionStrategy::Random, None)
+}
+
+#[fixture]
+fn policy_seeders_dont_get_seeders() -> TrackerPolicy {
+    TrackerPolicy::new(0, false, false, PeerSelectionStrategy::SeedersDontGetSeeders, None)
+}
+
+#[fixture]
+fn policy_same_address_family() -> TrackerPolicy {
+    TrackerPolicy::new(0, false, false, PeerSelectionStrategy::SameAddressFamily, None)
+}
+
+#[fixture]
+fn policy_recency_weighted() -> TrackerPolicy {
+    TrackerPolicy::new(0, false, false, PeerSelectionStrategy::RecencyWeighted, None)
 }
 
 pub enum Makes {
@@ -157,6 +180,25 @@ async fn it_should_check_if_entry_should_be_retained_based_on_the_tracker_policy
     }
 }
 
+#[rstest]
+#[case::started(&Makes::Started)]
+#[case::completed(&Makes::Completed)]
+#[case::downloaded(&Makes::Downloaded)]
+#[case::three(&Makes::Three)]
+#[tokio::test]
+async fn it_should_get_a_peer_by_id(
+    #[values(single(), mutex_std(), mutex_tokio(), mutex_parking_lot(), rw_lock_parking_lot())] mut torrent: Torrent,
+    #[case] makes: &Makes,
+) {
+    make(&mut torrent, makes).await;
+
+    for peer in torrent.get_peers(None).await {
+        assert_eq!(torrent.get_peer(&peer.peer_id).await, Some(peer));
+    }
+
+    assert_eq!(torrent.get_peer(&a_started_peer(-1).peer_id).await, None);
+}
+
 #[rstest]
 #[case::empty(&Makes::Empty)]
 #[case::started(&Makes::Started)]
@@ -362,6 +404,13 @@ async fn it_should_update_a_peer_as_incomplete(
 async fn it_should_get_peers_excluding_the_client_socket(
     #[values(single(), mutex_std(), mutex_tokio(), mutex_parking_lot(), rw_lock_parking_lot())] mut torrent: Torrent,
     #[case] makes: &Makes,
+    #[values(
+        policy_random(),
+        policy_seeders_dont_get_seeders(),
+        policy_same_address_family(),
+        policy_recency_weighted()
+    )]
+    policy: TrackerPolicy,
 ) {
     make(&mut torrent, makes).await;
 
@@ -370,18 +419,27 @@ async fn it_should_get_peers_excluding_the_client_socket(
 
     let socket = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8081);
 
+    let mut client = a_started_peer(-1);
+    client.peer_addr = socket;
+
     // for this test, we should not already use this socket.
     assert_ne!(peer.peer_addr, socket);
 
     // it should get the peer as it dose not share the socket.
-    assert!(torrent.get_peers_for_client(&socket, None).await.contains(&peer.into()));
+    assert!(torrent
+        .get_peers_for_client(&client, None, &policy)
+        .await
+        .contains(&peer.into()));
 
     // set the address to the socket.
     peer.peer_addr = socket;
     torrent.upsert_peer(&peer).await; // Add peer
 
     // It should not include the peer that has the same socket.
-    assert!(!torrent.get_peers_for_client(&socket, None).await.contains(&peer.into()));
+    assert!(!torrent
+        .get_peers_for_client(&client, None, &policy)
+        .await
+        .contains(&peer.into()));
 }
 
 #[rstest]
@@ -399,9 +457,7 @@ async fn it_should_limit_the_number_of_peers_returned(
 
     // We add one more peer than the scrape limit
     for peer_number in 1..=74 + 1 {
-        let mut peer = a_started_peer(1);
-        peer.peer_id = *peer::Id::new(peer_number);
-        torrent.upsert_peer(&peer).await;
+        torrent.upsert_peer(&a_started_peer(peer_number)).await;
     }
 
     let peers = torrent.get_peers(Some(TORRENT_PEERS_LIMIT)).await;
@@ -441,3 +497,296 @@ async fn it_should_remove_inactive_peers_beyond_cutoff(
 
     assert_eq!(torrent.get_peers_len().await, peers.len());
 }
+
+#[rstest]
+#[case::empty(&Makes::Empty)]
+#[case::started(&Makes::Started)]
+#[case::completed(&Makes::Completed)]
+#[case::downloaded(&Makes::Downloaded)]
+#[case::three(&Makes::Three)]
+#[tokio::test]
+async fn it_should_ignore_the_announces_from_another_ip_without_the_key_of_the_peer(
+    #[values(single(), mutex_std(), mutex_tokio(), mutex_parking_lot(), rw_lock_parking_lot())] mut torrent: Torrent,
+    #[case] makes: &Makes,
+) {
+    let peers = make(&mut torrent, makes).await;
+
+    let mut peer = a_started_peer(-1);
+    peer.key = Some(PeerKey::new(42));
+    torrent.upsert_peer(&peer).await;
+
+    let mut hijacker = peer;
+    hijacker.peer_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080);
+    hijacker.key = None;
+
+    // It should not update the peer
+    assert_eq!(torrent.upsert_peer(&hijacker).await, PeerUpdate::Ignored);
+
+    // It should not remove the peer
+    hijacker.event = AnnounceEvent::Stopped;
+    assert_eq!(torrent.upsert_peer(&hijacker).await, PeerUpdate::Ignored);
+
+    assert_eq!(torrent.get_peers_len().await, peers.len() + 1);
+    assert!(torrent.get_peers(None).await.contains(&peer.into()));
+}
+
+#[rstest]
+#[case::empty(&Makes::Empty)]
+#[case::started(&Makes::Started)]
+#[case::completed(&Makes::Completed)]
+#[case::downloaded(&Makes::Downloaded)]
+#[case::three(&Makes::Three)]
+#[tokio::test]
+async fn it_should_accept_the_announces_from_another_ip_with_the_key_of_the_peer(
+    #[values(single(), mutex_std(), mutex_tokio(), mutex_parking_lot(), rw_lock_parking_lot())] mut torrent: Torrent,
+    #[case] makes: &Makes,
+) {
+    let peers = make(&mut torrent, makes).await;
+
+    let mut peer = a_started_peer(-1);
+    peer.key = Some(PeerKey::new(42));
+    torrent.upsert_peer(&peer).await;
+
+    let previous = peer;
+    peer.peer_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080);
+
+    assert_eq!(
+        torrent.upsert_peer(&peer).await,
+        PeerUpdate::Applied {
+            previous: Some(Arc::new(previous)),
+            downloaded: false
+        }
+    );
+    assert_eq!(torrent.get_peers_len().await, peers.len() + 1);
+    assert!(torrent.get_peers(None).await.contains(&peer.into()));
+}
+
+#[rstest]
+#[case::empty(&Makes::Empty)]
+#[case::started(&Makes::Started)]
+#[case::completed(&Makes::Completed)]
+#[case::downloaded(&Makes::Downloaded)]
+#[case::three(&Makes::Three)]
+#[tokio::test]
+async fn it_should_replace_the_peer_using_the_same_socket_address_with_another_id(
+    #[values(single(), mutex_std(), mutex_tokio(), mutex_parking_lot(), rw_lock_parking_lot())] mut torrent: Torrent,
+    #[case] makes: &Makes,
+) {
+    let peers = make(&mut torrent, makes).await;
+
+    let peer = a_started_peer(-1);
+    torrent.upsert_peer(&peer).await;
+
+    // The client restarts with a new peer id
+    let mut restarted_peer = a_started_peer(-2);
+    restarted_peer.peer_addr = peer.peer_addr;
+    torrent.upsert_peer(&restarted_peer).await;
+
+    let all_peers = torrent.get_peers(None).await;
+
+    assert_eq!(all_peers.len(), peers.len() + 1);
+    assert!(all_peers.contains(&restarted_peer.into()));
+    assert!(!all_peers.contains(&peer.into()));
+}
+
+#[rstest]
+#[case::empty(&Makes::Empty)]
+#[case::started(&Makes::Started)]
+#[case::completed(&Makes::Completed)]
+#[case::downloaded(&Makes::Downloaded)]
+#[case::three(&Makes::Three)]
+#[tokio::test]
+async fn it_should_not_store_more_peers_than_the_max_peers_per_torrent(
+    #[values(single(), mutex_std(), mutex_tokio(), mutex_parking_lot(), rw_lock_parking_lot())] mut torrent: Torrent,
+    #[case] makes: &Makes,
+) {
+    let peers = make(&mut torrent, makes).await;
+
+    let policy = TrackerPolicy::new(0, false, false, PeerSelectionStrategy::Random, Some(3));
+
+    let mut ignored = 0;
+    for peer_number in 1..=5 {
+        if torrent.upsert_peer_with_policy(&a_started_peer(-peer_number), &policy).await == PeerUpdate::Ignored {
+            ignored += 1;
+        }
+    }
+
+    assert_eq!(torrent.get_peers_len().await, peers.len().max(3));
+    assert_eq!(ignored, 5 - (3 - peers.len().min(3)));
+}
+
+#[rstest]
+#[tokio::test]
+async fn it_should_update_the_peers_already_in_a_full_swarm(
+    #[values(single(), mutex_std(), mutex_tokio(), mutex_parking_lot(), rw_lock_parking_lot())] mut torrent: Torrent,
+) {
+    let policy = TrackerPolicy::new(0, false, false, PeerSelectionStrategy::Random, Some(3));
+
+    for peer_number in 1..=3 {
+        torrent.upsert_peer_with_policy(&a_started_peer(-peer_number), &policy).await;
+    }
+
+    let mut peer = a_started_peer(-1);
+    peer.event = AnnounceEvent::Completed;
+    peer.left = NumberOfBytes(0.into());
+
+    assert!(matches!(
+        torrent.upsert_peer_with_policy(&peer, &policy).await,
+        PeerUpdate::Applied {
+            previous: Some(_),
+            downloaded: true
+        }
+    ));
+
+    assert!(torrent.get_peers(None).await.contains(&peer.into()));
+}
+
+#[rstest]
+#[case::empty(&Makes::Empty)]
+#[case::started(&Makes::Started)]
+#[case::completed(&Makes::Completed)]
+#[case::downloaded(&Makes::Downloaded)]
+#[case::three(&Makes::Three)]
+#[tokio::test]
+async fn it_should_limit_the_number_of_peers_selected_for_a_client(
+    #[values(single(), mutex_std(), mutex_tokio(), mutex_parking_lot(), rw_lock_parking_lot())] mut torrent: Torrent,
+    #[case] makes: &Makes,
+    #[values(
+        policy_random(),
+        policy_seeders_dont_get_seeders(),
+        policy_same_address_family(),
+        policy_recency_weighted()
+    )]
+    policy: TrackerPolicy,
+) {
+    make(&mut torrent, makes).await;
+
+    for peer_number in 10..20 {
+        torrent.upsert_peer(&a_started_peer(peer_number)).await;
+    }
+
+    let client = a_started_peer(-1);
+
+    let peers = torrent.get_peers_for_client(&client, Some(5), &policy).await;
+
+    assert_eq!(peers.len(), 5);
+    assert_eq!(peers.iter().collect::<HashSet<_>>().len(), 5);
+}
+
+#[rstest]
+#[tokio::test]
+async fn it_should_not_select_any_peer_for_a_client_that_wants_none(
+    #[values(single(), mutex_std(), mutex_tokio(), mutex_parking_lot(), rw_lock_parking_lot())] mut torrent: Torrent,
+    #[values(
+        policy_random(),
+        policy_seeders_dont_get_seeders(),
+        policy_same_address_family(),
+        policy_recency_weighted()
+    )]
+    policy: TrackerPolicy,
+) {
+    for peer_number in 1..=3 {
+        torrent.upsert_peer(&a_started_peer(peer_number)).await;
+    }
+
+    let client = a_started_peer(-1);
+
+    assert_eq!(torrent.get_peers_for_client(&client, Some(0), &policy).await, vec![]);
+}
+
+#[rstest]
+#[tokio::test]
+async fn it_should_not_always_select_the_same_peers_for_a_client(
+    #[values(single(), mutex_std(), mutex_tokio(), mutex_parking_lot(), rw_lock_parking_lot())] mut torrent: Torrent,
+    #[values(
+        policy_random(),
+        policy_seeders_dont_get_seeders(),
+        policy_same_address_family(),
+        policy_recency_weighted()
+    )]
+    policy: TrackerPolicy,
+) {
+    for peer_number in 1..=20 {
+        torrent.upsert_peer(&a_started_peer(peer_number)).await;
+    }
+
+    let client = a_started_peer(-1);
+
+    let mut selected_peers = HashSet::new();
+
+    for _ in 0..20 {
+        selected_peers.extend(torrent.get_peers_for_client(&client, Some(2), &policy).await);
+    }
+
+    assert!(selected_peers.len() > 2);
+}
+
+#[rstest]
+#[tokio::test]
+async fn it_should_only_select_leechers_for_a_seeder_when_seeders_dont_get_seeders(
+    #[values(single(), mutex_std(), mutex_tokio(), mutex_parking_lot(), rw_lock_parking_lot())] mut torrent: Torrent,
+    #[from(policy_seeders_dont_get_seeders)] policy: TrackerPolicy,
+) {
+    torrent.upsert_peer(&a_started_peer(1)).await;
+    torrent.upsert_peer(&a_completed_peer(2)).await;
+    torrent.upsert_peer(&a_completed_peer(3)).await;
+
+    let seeder = a_completed_peer(-1);
+    let leecher = a_started_peer(-2);
+
+    assert_eq!(
+        torrent.get_peers_for_client(&seeder, None, &policy).await,
+        vec![a_started_peer(1).into()]
+    );
+    assert_eq!(torrent.get_peers_for_client(&leecher, None, &policy).await.len(), 3);
+}
+
+#[rstest]
+#[tokio::test]
+async fn it_should_select_the_peers_with_the_same_address_family_first_when_using_the_same_address_family_strategy(
+    #[values(single(), mutex_std(), mutex_tokio(), mutex_parking_lot(), rw_lock_parking_lot())] mut torrent: Torrent,
+    #[from(policy_same_address_family)] policy: TrackerPolicy,
+) {
+    for peer_number in 1..=3 {
+        torrent.upsert_peer(&a_started_peer(peer_number)).await;
+
+        let mut ipv6_peer = a_started_peer(peer_number + 10);
+        ipv6_peer.peer_addr = SocketAddr::new(IpAddr::V6(Ipv6Addr::from(u128::from(peer_number.unsigned_abs()))), 8080);
+        torrent.upsert_peer(&ipv6_peer).await;
+    }
+
+    let mut client = a_started_peer(-1);
+    client.peer_addr = SocketAddr::new(IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)), 8080);
+
+    let peers = torrent.get_peers_for_client(&client, Some(3), &policy).await;
+
+    assert!(peers.iter().all(|peer| peer.peer_addr.is_ipv6()));
+
+    let peers = torrent.get_peers_for_client(&client, Some(5), &policy).await;
+
+    assert_eq!(peers.iter().filter(|peer| peer.peer_addr.is_ipv6()).count(), 3);
+    assert_eq!(peers.iter().filter(|peer| peer.peer_addr.is_ipv4()).count(), 2);
+}
+
+#[rstest]
+#[tokio::test]
+async fn it_should_prefer_the_peers_that_announced_recently_when_using_the_recency_weighted_strategy(
+    #[values(single(), mutex_std(), mutex_tokio(), mutex_parking_lot(), rw_lock_parking_lot())] mut torrent: Torrent,
+    #[from(policy_recency_weighted)] policy: TrackerPolicy,
+) {
+    let client = a_started_peer(-1);
+
+    let recent_peer = a_started_peer(1);
+    torrent.upsert_peer(&recent_peer).await;
+
+    let mut old_peer = a_started_peer(2);
+    old_peer.updated = client.updated.sub(Duration::from_secs(3600));
+    torrent.upsert_peer(&old_peer).await;
+
+    for _ in 0..10 {
+        assert_eq!(
+            torrent.get_peers_for_client(&client, Some(1), &policy).await,
+            vec![recent_peer.into()]
+        );
+    }
+}
@@ -4,7 +4,7 @@ use std::hash::{DefaultHasher, Hash, Hasher};
 use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes};
 use bittorrent_primitives::info_hash::InfoHash;
 use rstest::{fixture, rstest};
-use torrust_tracker_configuration::TrackerPolicy;
+use torrust_tracker_configuration::{PeerSelectionStrategy, TrackerPolicy};
 use torrust_tracker_primitives::pagination::Pagination;
 use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
 use torrust_tracker_primitives::PersistentTorrents;
@@ -83,14 +83,14 @@ fn default() -> Entries {
 #[fixture]
 fn started() -> Entries {
     let mut torrent = EntrySingle::default();
-    torrent.upsert_peer(&a_started_peer(1));
+    torrent.upsert_peer(&a_started_peer(1), &TrackerPolicy::default());
     vec![(InfoHash::default(), torrent)]
 }
 
 #[fixture]
 fn completed() -> Entries {
     let mut torrent = EntrySingle::default();
-    torrent.upsert_peer(&a_completed_peer(2));
+    torrent.upsert_peer(&a_completed_peer(2), &TrackerPolicy::default());
     vec![(InfoHash::default(), torrent)]
 }
 
@@ -98,10 +98,10 @@ fn completed() -> Entries {
 fn downloaded() -> Entries {
     let mut torrent = EntrySingle::default();
     let mut peer = a_started_peer(3);
-    torrent.upsert_peer(&peer);
+    torrent.upsert_peer(&peer, &TrackerPolicy::default());
     peer.event = AnnounceEvent::Completed;
     peer.left = NumberOfBytes::new(0);
-    torrent.upsert_peer(&peer);
+    torrent.upsert_peer(&peer, &TrackerPolicy::default());
     vec![(InfoHash::default(), torrent)]
 }
 
@@ -109,21 +109,21 @@ fn downloaded() -> Entries {
 fn three() -> Entries {
     let mut started = EntrySingle::default();
     let started_h = &mut DefaultHasher::default();
-    started.upsert_peer(&a_started_peer(1));
+    started.upsert_peer(&a_started_peer(1), &TrackerPolicy::default());
     started.hash(started_h);
 
     let mut completed = EntrySingle::default();
     let completed_h = &mut DefaultHasher::default();
-    completed.upsert_peer(&a_completed_peer(2));
+    completed.upsert_peer(&a_completed_peer(2), &TrackerPolicy::default());
     completed.hash(completed_h);
 
     let mut downloaded = EntrySingle::default();
     let downloaded_h = &mut DefaultHasher::default();
     let mut downloaded_peer = a_started_peer(3);
-    downloaded.upsert_peer(&downloaded_peer);
+    downloaded.upsert_peer(&downloaded_peer, &TrackerPolicy::default());
     downloaded_peer.event = AnnounceEvent::Completed;
     downloaded_peer.left = NumberOfBytes::new(0);
-    downloaded.upsert_peer(&downloaded_peer);
+    downloaded.upsert_peer(&downloaded_peer, &TrackerPolicy::default());
     downloaded.hash(downloaded_h);
 
     vec![
@@ -139,7 +139,7 @@ fn many_out_of_order() -> Entries {
 
     for i in 0..408 {
         let mut entry = EntrySingle::default();
-        entry.upsert_peer(&a_started_peer(i));
+        entry.upsert_peer(&a_started_peer(i), &TrackerPolicy::default());
 
         entries.insert((InfoHash::from(&i), entry));
     }
@@ -154,7 +154,7 @@ fn many_hashed_in_order() -> Entries {
 
     for i in 0..408 {
         let mut entry = EntrySingle::default();
-        entry.upsert_peer(&a_started_peer(i));
+        entry.upsert_peer(&a_started_peer(i), &TrackerPolicy::default());
 
         let hash: &mut DefaultHasher = &mut DefaultHasher::default();
         hash.write_i32(i);
@@ -220,22 +220,22 @@ fn paginated_limit_one_offset_one() -> Pagination {
 
 #[fixture]
 fn policy_none() -> TrackerPolicy {
-    TrackerPolicy::new(0, false, false)
+    TrackerPolicy::new(0, false, false, PeerSelectionStrategy::Random, None)
 }
 
 #[fixture]
 fn policy_persist() -> TrackerPolicy {
-    TrackerPolicy::new(0, true, false)
+    TrackerPolicy::new(0, true, false, PeerSelectionStrategy::Random, None)
 }
 
 #[fixture]
 fn policy_remove() -> TrackerPolicy {
-    TrackerPolicy::new(0, false, true)
+    TrackerPolicy::new(0, false, true, PeerSelectionStrategy::Random, None)
 }
 
 #[fixture]
 fn policy_remove_persist() -> TrackerPolicy {
-    TrackerPolicy::new(0, true, true)
+    TrackerPolicy::new(0, true, true, PeerSelectionStrategy::Random, None)
 }
 
 #[rstest]
@@ -576,7 +576,8 @@ async fn it_should_remove_inactive_peers(
             Some(SwarmMetadata {
                 downloaded: 0,
                 complete: 1,
-                incomplete: 0
+                incomplete: 0,
+                downloaders: 0,
             })
         );
     }
@@ -40,6 +40,10 @@ pub struct File {
     pub complete: i64,   // The number of active peers that have completed downloading
     pub downloaded: i64, // The number of peers that have ever completed downloading
     pub incomplete: i64, // The number of active peers that have not completed downloading
+    /// The number of active peers that want to download more pieces. Only the
+    /// trackers supporting partial seeds (BEP 21) include it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub downloaders: Option<i64>,
 }
 
 impl File {
@@ -175,6 +179,7 @@ fn parse_bencoded_file(value: &Value) -> Result<File, BencodeParseError> {
             let mut complete = None;
             let mut downloaded = None;
             let mut incomplete = None;
+            let mut downloaders = None;
 
             for file_field in dict {
                 let field_name = file_field.0;
@@ -192,6 +197,8 @@ fn parse_bencoded_file(value: &Value) -> Result<File, BencodeParseError> {
                     downloaded = Some(field_value);
                 } else if field_name == b"incomplete" {
                     incomplete = Some(field_value);
+                } else if field_name == b"downloaders" {
+                    downloaders = Some(field_value);
                 } else {
                     return Err(BencodeParseError::InvalidFileField {
                         value: file_field.1.clone(),
@@ -221,6 +228,7 @@ fn parse_bencoded_file(value: &Value) -> Result<File, BencodeParseError> {
                 complete: complete.unwrap(),
                 downloaded: downloaded.unwrap(),
                 incomplete: incomplete.unwrap(),
+                downloaders,
             }
         }
         _ => return Err(BencodeParseError::InvalidValueExpectedDict { value: value.clone() }),
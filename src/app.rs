@@ -2,9 +2,7 @@
 //!
 //! The tracker application has a global configuration for multiple jobs.
 //! It's basically a container for other services.
-//! It also check constraint and dependencies between services. For example:
-//! It's not safe to run a UDP tracker on top of a core public tracker, as UDP trackers
-//! do not allow private access to the tracker data.
+//! It also check constraint and dependencies between services.
 //!
 //! The application is responsible for:
 //!
@@ -18,6 +16,7 @@
 //! Optional jobs:
 //!
 //! - Torrent cleaner: it removes inactive peers and (optionally) peerless torrents.
+//! - Swarm snapshots: it saves the in-memory swarms periodically and on shutdown.
 //! - UDP trackers: the user can enable multiple UDP tracker on several ports.
 //! - HTTP trackers: the user can enable multiple HTTP tracker on several ports.
 //! - Tracker REST API: the tracker API can be enabled/disabled.
@@ -27,7 +26,9 @@ use tokio::task::JoinHandle;
 use torrust_tracker_configuration::Configuration;
 use tracing::instrument;
 
-use crate::bootstrap::jobs::{health_check_api, http_tracker, torrent_cleanup, tracker_apis, udp_tracker};
+use crate::bootstrap::jobs::{
+    full_scrape, health_check_api, http_tracker, key_stats, swarm_snapshot, torrent_cleanup, tracker_apis, udp_tracker,
+};
 use crate::servers::registar::Registar;
 use crate::{core, servers};
 
@@ -37,6 +38,7 @@ use crate::{core, servers};
 ///
 /// - Can't retrieve tracker keys from database.
 /// - Can't load whitelist from database.
+/// - Can't load blacklist from database.
 #[instrument(skip(config, tracker))]
 pub async fn start(config: &Configuration, tracker: Arc<core::Tracker>) -> Vec<JoinHandle<()>> {
     if config.http_api.is_none()
@@ -56,6 +58,12 @@ pub async fn start(config: &Configuration, tracker: Arc<core::Tracker>) -> Vec<J
             .load_keys_from_database()
             .await
             .expect("Could not retrieve keys from database.");
+        tracker
+            .load_key_stats_from_database()
+            .expect("Could not load key stats from database.");
+        tracker
+            .load_torrent_access_from_database()
+            .expect("Could not load key allow-lists from database.");
     }
 
     // Load whitelisted torrents
@@ -66,17 +74,39 @@ pub async fn start(config: &Configuration, tracker: Arc<core::Tracker>) -> Vec<J
             .expect("Could not load whitelist from database.");
     }
 
+    // Load blacklisted torrents
+    tracker
+        .load_blacklist_from_database()
+        .await
+        .expect("Could not load blacklist from database.");
+
+    // Load hybrid torrents
+    tracker
+        .load_hybrid_torrents_from_database()
+        .expect("Could not load hybrid torrents from database.");
+
+    // Load torrent multipliers
+    tracker
+        .load_torrent_multipliers_from_database()
+        .expect("Could not load torrent multipliers from database.");
+
+    // Load client filter prefixes
+    tracker
+        .load_client_prefixes_from_database()
+        .expect("Could not load client prefixes from database.");
+
+    // Load the IP blocklist
+    tracker
+        .load_blocked_networks_from_database()
+        .expect("Could not load blocked networks from database.");
+    tracker
+        .load_ip_blocklist_from_file()
+        .expect("Could not load the IP blocklist file.");
+
     // Start the UDP blocks
     if let Some(udp_trackers) = &config.udp_trackers {
         for udp_tracker_config in udp_trackers {
-            if tracker.is_private() {
-                tracing::warn!(
-                    "Could not start UDP tracker on: {} while in private mode. UDP is not safe for private trackers!",
-                    udp_tracker_config.bind_address
-                );
-            } else {
-                jobs.push(udp_tracker::start_job(udp_tracker_config, tracker.clone(), registar.give_form()).await);
-            }
+            jobs.push(udp_tracker::start_job(udp_tracker_config, tracker.clone(), registar.give_form()).await);
         }
     } else {
         tracing::info!("No UDP blocks in configuration");
@@ -121,6 +151,21 @@ pub async fn start(config: &Configuration, tracker: Arc<core::Tracker>) -> Vec<J
         jobs.push(torrent_cleanup::start_job(&config.core, &tracker));
     }
 
+    // Start runner to save the swarm snapshots, every interval and on shutdown
+    if let Some(swarm_snapshot_config) = &config.core.swarm_snapshot {
+        jobs.push(swarm_snapshot::start_job(swarm_snapshot_config, &tracker));
+    }
+
+    // Start runner to save the transfer stats of the keys, every interval and on shutdown
+    if tracker.is_private() {
+        jobs.push(key_stats::start_job(&tracker));
+    }
+
+    // Start runner to generate the full scrape, every interval
+    if let Some(full_scrape_config) = &config.core.full_scrape {
+        jobs.push(full_scrape::start_job(full_scrape_config, &tracker));
+    }
+
     // Start Health Check API
     jobs.push(health_check_api::start_job(&config.health_check_api, registar.entries()).await);
 
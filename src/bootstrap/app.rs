@@ -11,6 +11,7 @@
 //! 2. Initialize static variables.
 //! 3. Initialize logging.
 //! 4. Initialize the domain tracker.
+//! 5. Load the swarm snapshot, if enabled.
 use std::sync::Arc;
 
 use torrust_tracker_clock::static_time;
@@ -69,7 +70,9 @@ pub fn check_seed() {
 pub fn initialize_with_configuration(configuration: &Configuration) -> Arc<Tracker> {
     initialize_static();
     initialize_logging(configuration);
-    Arc::new(initialize_tracker(configuration))
+    let tracker = Arc::new(initialize_tracker(configuration));
+    load_swarm_snapshot(&tracker);
+    tracker
 }
 
 /// It initializes the application static values.
@@ -103,6 +106,16 @@ pub fn initialize_tracker(config: &Configuration) -> Tracker {
     tracker_factory(config)
 }
 
+/// It loads the swarms saved in the last snapshot, if snapshots are enabled.
+///
+/// The tracker starts with empty swarms if the snapshot cannot be loaded.
+#[instrument(skip(tracker))]
+pub fn load_swarm_snapshot(tracker: &Tracker) {
+    if let Err(err) = tracker.load_swarm_snapshot() {
+        tracing::warn!("Failed to load swarm snapshot: {err}");
+    }
+}
+
 /// It initializes the log threshold, format and channel.
 ///
 /// See [the logging setup](crate::bootstrap::logging::setup) for more info about logging.
@@ -0,0 +1,58 @@
+//! Job that generates the full scrape on intervals.
+//!
+//! The full scrape contains the scrape data for all the torrents. It's
+//! generated when the job starts and then every `interval` seconds, so that
+//! the HTTP tracker and the API do not walk the torrent repository for every
+//! request.
+//!
+//! Refer to the [`full_scrape`](crate::core::full_scrape) module for more info
+//! about the cached full scrape.
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use tokio::task::JoinHandle;
+use torrust_tracker_configuration::FullScrape;
+use tracing::instrument;
+
+use crate::core;
+use crate::servers::http::v1::services::scrape::update_full_scrape;
+
+/// It starts a job for generating the full scrape.
+///
+/// Refer to [`torrust-tracker-configuration documentation`](https://docs.rs/torrust-tracker-configuration) for more info about those options.
+#[must_use]
+#[instrument(skip(config, tracker))]
+pub fn start_job(config: &FullScrape, tracker: &Arc<core::Tracker>) -> JoinHandle<()> {
+    let weak_tracker = std::sync::Arc::downgrade(tracker);
+    let interval = config.interval;
+
+    tokio::spawn(async move {
+        let interval = std::time::Duration::from_secs(interval.max(1));
+        let mut interval = tokio::time::interval(interval);
+
+        loop {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {
+                    tracing::info!("Stopping full scrape job..");
+                    break;
+                }
+                _ = interval.tick() => {
+                    if let Some(tracker) = weak_tracker.upgrade() {
+                        let start_time = Utc::now().time();
+                        tracing::info!("Generating full scrape..");
+                        match update_full_scrape(&tracker).await {
+                            Ok(()) => tracing::info!(
+                                "Generated full scrape in: {}ms",
+                                (Utc::now().time() - start_time).num_milliseconds()
+                            ),
+                            Err(err) => tracing::error!("Failed to generate full scrape: {err}"),
+                        }
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+    })
+}
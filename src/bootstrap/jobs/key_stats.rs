@@ -0,0 +1,60 @@
+//! Job that saves the transfer stats of the authentication keys.
+//!
+//! The tracker accounts the transfers of the keys in memory. This job saves
+//! the stats that have changed into the database every
+//! [`KEY_STATS_SAVE_INTERVAL_IN_SECS`] seconds and when the tracker is
+//! gracefully shut down.
+//!
+//! Refer to the [`ratio`](crate::core::ratio) module for more info about the
+//! transfer accounting.
+use std::sync::Arc;
+
+use chrono::Utc;
+use tokio::task::JoinHandle;
+use tracing::instrument;
+
+use crate::core;
+
+/// How often the changed key stats are saved into the database.
+pub const KEY_STATS_SAVE_INTERVAL_IN_SECS: u64 = 60;
+
+/// It starts a job for saving the transfer stats of the authentication keys.
+#[must_use]
+#[instrument(skip(tracker))]
+pub fn start_job(tracker: &Arc<core::Tracker>) -> JoinHandle<()> {
+    let tracker = tracker.clone();
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(KEY_STATS_SAVE_INTERVAL_IN_SECS));
+        interval.tick().await;
+
+        loop {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {
+                    tracing::info!("Stopping key stats job..");
+                    save_key_stats(&tracker).await;
+                    break;
+                }
+                _ = interval.tick() => {
+                    save_key_stats(&tracker).await;
+                }
+            }
+        }
+    })
+}
+
+async fn save_key_stats(tracker: &Arc<core::Tracker>) {
+    let start_time = Utc::now().time();
+    tracing::debug!("Saving key stats..");
+
+    let tracker = tracker.clone();
+
+    match tokio::task::spawn_blocking(move || tracker.save_unsaved_key_stats()).await {
+        Ok(Ok(())) => tracing::debug!(
+            "Saved key stats in: {}ms",
+            (Utc::now().time() - start_time).num_milliseconds()
+        ),
+        Ok(Err(err)) => tracing::error!("Failed to save key stats: {err}"),
+        Err(err) => tracing::error!("Failed to run the key stats saving task: {err}"),
+    }
+}
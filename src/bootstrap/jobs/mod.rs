@@ -6,8 +6,11 @@
 //! 2. Launch all the application services as concurrent jobs.
 //!
 //! This modules contains all the functions needed to start those jobs.
+pub mod full_scrape;
 pub mod health_check_api;
 pub mod http_tracker;
+pub mod key_stats;
+pub mod swarm_snapshot;
 pub mod torrent_cleanup;
 pub mod tracker_apis;
 pub mod udp_tracker;
@@ -0,0 +1,73 @@
+//! Job that saves snapshots of the in-memory swarms.
+//!
+//! The snapshot is saved every `interval` seconds and when the tracker is
+//! gracefully shut down. It's loaded again on startup by
+//! [`bootstrap::app`](crate::bootstrap::app).
+//!
+//! Refer to the [`snapshot`](crate::core::torrent::snapshot) module for more
+//! info about snapshots.
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use tokio::task::JoinHandle;
+use torrust_tracker_configuration::SwarmSnapshot;
+use tracing::instrument;
+
+use crate::core;
+
+/// It starts a job for saving swarm snapshots.
+///
+/// Periodic snapshots are disabled if the `interval` is `0`. The snapshot is
+/// still saved on shutdown.
+///
+/// Refer to [`torrust-tracker-configuration documentation`](https://docs.rs/torrust-tracker-configuration) for more info about those options.
+#[must_use]
+#[instrument(skip(config, tracker))]
+pub fn start_job(config: &SwarmSnapshot, tracker: &Arc<core::Tracker>) -> JoinHandle<()> {
+    let tracker = tracker.clone();
+    let interval = config.interval;
+
+    tokio::spawn(async move {
+        let mut interval = (interval > 0).then(|| tokio::time::interval(std::time::Duration::from_secs(interval)));
+
+        if let Some(interval) = &mut interval {
+            interval.tick().await;
+        }
+
+        loop {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {
+                    tracing::info!("Stopping swarm snapshot job..");
+                    save_swarm_snapshot(&tracker);
+                    break;
+                }
+                () = tick(&mut interval) => {
+                    save_swarm_snapshot(&tracker);
+                }
+            }
+        }
+    })
+}
+
+async fn tick(interval: &mut Option<tokio::time::Interval>) {
+    match interval {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+fn save_swarm_snapshot(tracker: &core::Tracker) {
+    let start_time = Utc::now().time();
+    tracing::info!("Saving swarm snapshot..");
+
+    match tracker.save_swarm_snapshot() {
+        Ok(()) => tracing::info!(
+            "Saved swarm snapshot in: {}ms",
+            (Utc::now().time() - start_time).num_milliseconds()
+        ),
+        Err(err) => tracing::error!("Failed to save swarm snapshot: {err}"),
+    }
+}
@@ -33,9 +33,10 @@ use crate::servers::udp::UDP_TRACKER_LOG_TARGET;
 pub async fn start_job(config: &UdpTracker, tracker: Arc<core::Tracker>, form: ServiceRegistrationForm) -> JoinHandle<()> {
     let bind_to = config.bind_address;
     let cookie_lifetime = config.cookie_lifetime;
+    let rate_limit = config.rate_limit;
 
     let server = Server::new(Spawner::new(bind_to))
-        .start(tracker, form, cookie_lifetime)
+        .start(tracker, form, cookie_lifetime, rate_limit)
         .await
         .expect("it should be able to start the udp tracker");
 
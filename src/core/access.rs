@@ -0,0 +1,188 @@
+//! Per-key torrent access control.
+//!
+//! In `private` and `private_listed` modes any valid authentication
+//! [`Key`](crate::core::auth::Key) can announce and scrape any torrent (any
+//! whitelisted torrent in `private_listed` mode). Keys can optionally have an
+//! [`AllowList`] to restrict them to some torrents, so that a single tracker
+//! instance can serve several invite-only sub-trackers.
+//!
+//! An allow-list contains infohashes and [`TorrentGroup`]s. A torrent group is
+//! a named set of torrents, so that a torrent can be added to the group once
+//! instead of adding it to the allow-list of every key.
+//!
+//! - A key without allow-list can access all the torrents.
+//! - A key with an allow-list can only access the torrents in the list, and
+//!   the torrents in the groups in the list.
+//!
+//! Adding the first torrent or group to the allow-list of a key restricts the
+//! key. The key stays restricted when the last entry is removed from its
+//! allow-list: an empty allow-list does not allow any torrent. The restriction
+//! can only be lifted explicitly, by removing the whole allow-list of the key.
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::panic::Location;
+use std::str::FromStr;
+
+use bittorrent_primitives::info_hash::InfoHash;
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The maximum length of a torrent group name.
+pub const TORRENT_GROUP_MAX_LENGTH: usize = 64;
+
+/// The name of a group of torrents. For example: `linux-isos`.
+///
+/// It can only contain ASCII letters, digits, `-` and `_`, and it can't be
+/// longer than [`TORRENT_GROUP_MAX_LENGTH`] chars.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Hash, Display)]
+pub struct TorrentGroup(String);
+
+impl TorrentGroup {
+    #[must_use]
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Error returned when a torrent group name can not be parsed.
+#[derive(Debug, Error, Clone)]
+#[error("Invalid torrent group name: {name}, {location}")]
+pub struct ParseTorrentGroupError {
+    name: String,
+    location: &'static Location<'static>,
+}
+
+impl FromStr for TorrentGroup {
+    type Err = ParseTorrentGroupError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let is_valid = !s.is_empty()
+            && s.len() <= TORRENT_GROUP_MAX_LENGTH
+            && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+        if !is_valid {
+            return Err(ParseTorrentGroupError {
+                name: s.to_string(),
+                location: Location::caller(),
+            });
+        }
+
+        Ok(Self(s.to_string()))
+    }
+}
+
+/// The torrents an authentication key is allowed to access.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct AllowList {
+    /// The torrents the key can access.
+    pub info_hashes: BTreeSet<InfoHash>,
+    /// The groups of torrents the key can access.
+    pub groups: BTreeSet<TorrentGroup>,
+}
+
+impl AllowList {
+    /// It returns `true` if the allow-list has no torrents nor groups.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.info_hashes.is_empty() && self.groups.is_empty()
+    }
+
+    /// It returns `true` if the torrent is in the allow-list, directly or
+    /// through one of its groups. An empty allow-list does not allow any
+    /// torrent.
+    #[must_use]
+    pub fn allows(&self, info_hash: &InfoHash, torrent_groups: &HashMap<TorrentGroup, HashSet<InfoHash>>) -> bool {
+        if self.info_hashes.contains(info_hash) {
+            return true;
+        }
+
+        self.groups.iter().any(|group| {
+            torrent_groups
+                .get(group)
+                .is_some_and(|info_hashes| info_hashes.contains(info_hash))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod torrent_group {
+        use std::str::FromStr;
+
+        use crate::core::access::TorrentGroup;
+
+        #[test]
+        fn should_be_parsed_from_a_name_with_letters_digits_dashes_and_underscores() {
+            let group = TorrentGroup::from_str("linux-isos_2024").unwrap();
+
+            assert_eq!(group.value(), "linux-isos_2024");
+        }
+
+        #[test]
+        fn should_not_be_parsed_from_an_empty_name() {
+            assert!(TorrentGroup::from_str("").is_err());
+        }
+
+        #[test]
+        fn should_not_be_parsed_from_a_name_with_other_chars() {
+            assert!(TorrentGroup::from_str("linux isos").is_err());
+            assert!(TorrentGroup::from_str("linux/isos").is_err());
+        }
+
+        #[test]
+        fn should_not_be_parsed_from_a_too_long_name() {
+            assert!(TorrentGroup::from_str(&"a".repeat(65)).is_err());
+        }
+    }
+
+    mod allow_list {
+        use std::collections::{HashMap, HashSet};
+        use std::str::FromStr;
+
+        use bittorrent_primitives::info_hash::InfoHash;
+
+        use crate::core::access::{AllowList, TorrentGroup};
+
+        fn sample_info_hash() -> InfoHash {
+            "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap()
+        }
+
+        fn linux_isos() -> TorrentGroup {
+            TorrentGroup::from_str("linux-isos").unwrap()
+        }
+
+        #[test]
+        fn should_not_allow_any_torrent_when_it_is_empty() {
+            let allow_list = AllowList::default();
+
+            assert!(!allow_list.allows(&sample_info_hash(), &HashMap::new()));
+        }
+
+        #[test]
+        fn should_allow_the_torrents_in_the_list() {
+            let mut allow_list = AllowList::default();
+            allow_list.info_hashes.insert(sample_info_hash());
+
+            assert!(allow_list.allows(&sample_info_hash(), &HashMap::new()));
+        }
+
+        #[test]
+        fn should_allow_the_torrents_in_the_groups_in_the_list() {
+            let mut allow_list = AllowList::default();
+            allow_list.groups.insert(linux_isos());
+
+            let torrent_groups = HashMap::from([(linux_isos(), HashSet::from([sample_info_hash()]))]);
+
+            assert!(allow_list.allows(&sample_info_hash(), &torrent_groups));
+        }
+
+        #[test]
+        fn should_not_allow_the_torrents_that_are_not_in_the_list() {
+            let mut allow_list = AllowList::default();
+            allow_list.groups.insert(linux_isos());
+
+            assert!(!allow_list.allows(&sample_info_hash(), &HashMap::new()));
+        }
+    }
+}
@@ -0,0 +1,161 @@
+//! Client software filtering.
+//!
+//! `BitTorrent` clients identify themselves at the beginning of the `peer_id`.
+//! There are two common conventions:
+//!
+//! - Azureus-style: a dash, two chars for the client and four for the
+//!   version, and another dash. For example: `-qB4500-` for qBittorrent 4.5.0.
+//! - Shad0w-style: one char for the client and several for the version,
+//!   padded with dashes. For example: `S58B----` for Shadow's client 5.8.11.
+//!
+//! The tracker keeps a list of [`PeerIdPrefix`]es. Depending on the
+//! [`ClientFilter`](torrust_tracker_configuration::ClientFilter) mode the list
+//! is used as:
+//!
+//! - `disabled`: the list is ignored and all the clients can announce.
+//! - `allow`: only the clients whose `peer_id` starts with one of the prefixes
+//!   can announce.
+//! - `deny`: the clients whose `peer_id` starts with one of the prefixes can't
+//!   announce.
+//!
+//! A prefix like `-qB` matches all the qBittorrent versions, while `-qB4500-`
+//! only matches one version.
+use std::panic::Location;
+use std::str::FromStr;
+
+use aquatic_udp_protocol::PeerId;
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use torrust_tracker_primitives::peer;
+
+/// The maximum length of a `peer_id` prefix. It's the length of the `peer_id`.
+pub const PEER_ID_PREFIX_MAX_LENGTH: usize = 20;
+
+/// The number of bytes of the `peer_id` used to describe the client.
+const CLIENT_ID_LENGTH: usize = 8;
+
+/// The beginning of a `peer_id`. For example: `-qB4500-`.
+///
+/// It can only contain printable ASCII chars (without spaces), and it can't be
+/// longer than [`PEER_ID_PREFIX_MAX_LENGTH`] chars.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Hash, Display)]
+pub struct PeerIdPrefix(String);
+
+impl PeerIdPrefix {
+    #[must_use]
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+
+    /// It returns `true` if the `peer_id` starts with the prefix.
+    #[must_use]
+    pub fn matches(&self, peer_id: &PeerId) -> bool {
+        peer_id.0.starts_with(self.0.as_bytes())
+    }
+}
+
+/// Error returned when a `peer_id` prefix can not be parsed.
+#[derive(Debug, Error, Clone)]
+#[error("Invalid peer id prefix: {prefix}, {location}")]
+pub struct ParsePeerIdPrefixError {
+    prefix: String,
+    location: &'static Location<'static>,
+}
+
+impl FromStr for PeerIdPrefix {
+    type Err = ParsePeerIdPrefixError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let is_valid = !s.is_empty() && s.len() <= PEER_ID_PREFIX_MAX_LENGTH && s.chars().all(|c| c.is_ascii_graphic());
+
+        if !is_valid {
+            return Err(ParsePeerIdPrefixError {
+                prefix: s.to_string(),
+                location: Location::caller(),
+            });
+        }
+
+        Ok(Self(s.to_string()))
+    }
+}
+
+/// It returns a human readable description of the client that generated the
+/// `peer_id`, like `qBittorrent (-qB4500-)`. It's used in the failure
+/// reason returned to the rejected clients.
+#[must_use]
+pub fn describe_client(peer_id: &PeerId) -> String {
+    let client_id = peer_id.0[..CLIENT_ID_LENGTH].escape_ascii().to_string();
+
+    match peer::Id::from(*peer_id).get_client_name() {
+        Some(name) => format!("{name} ({client_id})"),
+        None => client_id,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod peer_id_prefix {
+        use std::str::FromStr;
+
+        use aquatic_udp_protocol::PeerId;
+
+        use crate::core::client_filter::PeerIdPrefix;
+
+        fn qbittorrent_peer_id() -> PeerId {
+            PeerId(*b"-qB4500-000000000000")
+        }
+
+        #[test]
+        fn should_be_parsed_from_printable_ascii_chars() {
+            let prefix = PeerIdPrefix::from_str("-qB4500-").unwrap();
+
+            assert_eq!(prefix.value(), "-qB4500-");
+        }
+
+        #[test]
+        fn should_not_be_parsed_from_an_empty_string() {
+            assert!(PeerIdPrefix::from_str("").is_err());
+        }
+
+        #[test]
+        fn should_not_be_parsed_from_a_string_with_other_chars() {
+            assert!(PeerIdPrefix::from_str("-qB 4500-").is_err());
+            assert!(PeerIdPrefix::from_str("-qB\n").is_err());
+        }
+
+        #[test]
+        fn should_not_be_parsed_from_a_string_longer_than_a_peer_id() {
+            assert!(PeerIdPrefix::from_str(&"a".repeat(21)).is_err());
+        }
+
+        #[test]
+        fn should_match_the_peer_ids_starting_with_it() {
+            assert!(PeerIdPrefix::from_str("-qB").unwrap().matches(&qbittorrent_peer_id()));
+            assert!(PeerIdPrefix::from_str("-qB4500-").unwrap().matches(&qbittorrent_peer_id()));
+        }
+
+        #[test]
+        fn should_not_match_the_peer_ids_not_starting_with_it() {
+            assert!(!PeerIdPrefix::from_str("-TR").unwrap().matches(&qbittorrent_peer_id()));
+            assert!(!PeerIdPrefix::from_str("qB").unwrap().matches(&qbittorrent_peer_id()));
+        }
+    }
+
+    mod describing_the_client {
+        use aquatic_udp_protocol::PeerId;
+
+        use crate::core::client_filter::describe_client;
+
+        #[test]
+        fn should_include_the_client_name_when_it_is_known() {
+            assert_eq!(describe_client(&PeerId(*b"-qB4500-000000000000")), "qBittorrent (-qB4500-)");
+        }
+
+        #[test]
+        fn should_escape_the_non_printable_bytes() {
+            assert_eq!(describe_client(&PeerId([0u8; 20])), "\\x00".repeat(8));
+        }
+    }
+}
@@ -40,6 +40,15 @@ pub enum Error {
         driver: Driver,
     },
 
+    /// The database schema was upgraded by a newer version of the tracker
+    #[error("The {driver} database schema version {schema_version} is newer than the latest version supported by the tracker {supported_version}, {location}")]
+    UnsupportedSchemaVersion {
+        location: &'static Location<'static>,
+        schema_version: i64,
+        supported_version: i64,
+        driver: Driver,
+    },
+
     /// Unable to connect to the database
     #[error("Failed to connect to {driver} database: {source}")]
     ConnectionError {
@@ -82,6 +91,17 @@ impl From<r2d2_mysql::mysql::Error> for Error {
     }
 }
 
+impl From<r2d2_postgres::postgres::Error> for Error {
+    #[track_caller]
+    fn from(err: r2d2_postgres::postgres::Error) -> Self {
+        let e: DynError = Arc::new(err);
+        Error::InvalidQuery {
+            source: e.into(),
+            driver: Driver::PostgreSQL,
+        }
+    }
+}
+
 impl From<UrlError> for Error {
     #[track_caller]
     fn from(err: UrlError) -> Self {
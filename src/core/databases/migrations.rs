@@ -0,0 +1,371 @@
+//! Database schema migrations.
+//!
+//! The SQL files in the `migrations/<driver>` folders are embedded into the
+//! binary. Each file is a migration identified by the timestamp at the
+//! beginning of the file name, for example:
+//!
+//! ```text
+//! migrations/sqlite/20240730183000_torrust_tracker_create_all_tables.sql
+//! ```
+//!
+//! The drivers keep the versions of the migrations already applied in the
+//! `schema_migrations` table:
+//!
+//!  Field     | Sample data                         | Description
+//! ---|---|---
+//!  `version` | 20240730183000                      | The migration version
+//!  `name`    | `torrust_tracker_create_all_tables` | The migration name
+//!
+//! When the tracker starts, it applies the pending migrations in order. The
+//! tracker refuses to use a database which has migrations it does not know,
+//! because the schema was upgraded by a newer version of the tracker.
+//!
+//! > **NOTICE**: New migration files must be added to the lists in this module
+//! > to be embedded into the binary.
+use std::panic::Location;
+
+use super::driver::Driver;
+use super::{Database, Error};
+
+/// A versioned SQL script that changes the database schema.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Migration {
+    /// The timestamp at the beginning of the file name.
+    pub version: i64,
+    /// The rest of the file name without the extension.
+    pub name: &'static str,
+    /// The SQL statements.
+    pub sql: &'static str,
+}
+
+impl Migration {
+    /// It splits the SQL script into statements, for the drivers that run one
+    /// statement at a time. Semicolons in quoted strings and identifiers do
+    /// not end a statement.
+    #[must_use]
+    pub fn statements(&self) -> Vec<&'static str> {
+        let sql = self.sql;
+        let mut statements = vec![];
+        let mut start = 0;
+        let mut quote = None;
+
+        for (index, char) in sql.char_indices() {
+            match quote {
+                Some(opening) if char == opening => quote = None,
+                None if matches!(char, '\'' | '"' | '`') => quote = Some(char),
+                None if char == ';' => {
+                    statements.push(&sql[start..index]);
+                    start = index + 1;
+                }
+                _ => {}
+            }
+        }
+
+        statements.push(&sql[start..]);
+
+        statements
+            .into_iter()
+            .map(str::trim)
+            .filter(|statement| !statement.is_empty())
+            .collect()
+    }
+}
+
+macro_rules! migration {
+    ($driver:literal, $version:literal, $name:literal) => {
+        Migration {
+            version: $version,
+            name: $name,
+            sql: include_str!(concat!("../../../migrations/", $driver, "/", $version, "_", $name, ".sql")),
+        }
+    };
+}
+
+const SQLITE_MIGRATIONS: &[Migration] = &[
+    migration!("sqlite", 20_240_730_183_000, "torrust_tracker_create_all_tables"),
+    migration!("sqlite", 20_240_730_183_500, "torrust_tracker_keys_valid_until_nullable"),
+    migration!("sqlite", 20_241_016_120_000, "torrust_tracker_create_hybrid_torrents_table"),
+    migration!("sqlite", 20_241_101_120_000, "torrust_tracker_create_key_stats_table"),
+    migration!(
+        "sqlite",
+        20_241_101_120_500,
+        "torrust_tracker_create_torrent_multipliers_table"
+    ),
+    migration!("sqlite", 20_241_105_120_000, "torrust_tracker_create_torrent_access_tables"),
+    migration!("sqlite", 20_241_110_120_000, "torrust_tracker_create_client_prefixes_table"),
+    migration!("sqlite", 20_241_115_120_000, "torrust_tracker_create_ip_blocklist_table"),
+    migration!("sqlite", 20_241_120_120_000, "torrust_tracker_create_blacklist_table"),
+    migration!("sqlite", 20_241_125_120_000, "torrust_tracker_create_restricted_keys_table"),
+];
+
+const MYSQL_MIGRATIONS: &[Migration] = &[
+    migration!("mysql", 20_240_730_183_000, "torrust_tracker_create_all_tables"),
+    migration!("mysql", 20_240_730_183_500, "torrust_tracker_keys_valid_until_nullable"),
+    migration!("mysql", 20_241_016_120_000, "torrust_tracker_create_hybrid_torrents_table"),
+    migration!("mysql", 20_241_101_120_000, "torrust_tracker_create_key_stats_table"),
+    migration!(
+        "mysql",
+        20_241_101_120_500,
+        "torrust_tracker_create_torrent_multipliers_table"
+    ),
+    migration!("mysql", 20_241_105_120_000, "torrust_tracker_create_torrent_access_tables"),
+    migration!("mysql", 20_241_110_120_000, "torrust_tracker_create_client_prefixes_table"),
+    migration!("mysql", 20_241_115_120_000, "torrust_tracker_create_ip_blocklist_table"),
+    migration!("mysql", 20_241_120_120_000, "torrust_tracker_create_blacklist_table"),
+    migration!("mysql", 20_241_125_120_000, "torrust_tracker_create_restricted_keys_table"),
+];
+
+const POSTGRESQL_MIGRATIONS: &[Migration] = &[
+    migration!("postgresql", 20_240_730_183_000, "torrust_tracker_create_all_tables"),
+    migration!("postgresql", 20_240_730_183_500, "torrust_tracker_keys_valid_until_nullable"),
+    migration!(
+        "postgresql",
+        20_241_016_120_000,
+        "torrust_tracker_create_hybrid_torrents_table"
+    ),
+    migration!("postgresql", 20_241_101_120_000, "torrust_tracker_create_key_stats_table"),
+    migration!(
+        "postgresql",
+        20_241_101_120_500,
+        "torrust_tracker_create_torrent_multipliers_table"
+    ),
+    migration!(
+        "postgresql",
+        20_241_105_120_000,
+        "torrust_tracker_create_torrent_access_tables"
+    ),
+    migration!(
+        "postgresql",
+        20_241_110_120_000,
+        "torrust_tracker_create_client_prefixes_table"
+    ),
+    migration!("postgresql", 20_241_115_120_000, "torrust_tracker_create_ip_blocklist_table"),
+    migration!("postgresql", 20_241_120_120_000, "torrust_tracker_create_blacklist_table"),
+    migration!(
+        "postgresql",
+        20_241_125_120_000,
+        "torrust_tracker_create_restricted_keys_table"
+    ),
+];
+
+/// It returns the migrations for a driver sorted by version.
+#[must_use]
+pub fn migrations(driver: &Driver) -> &'static [Migration] {
+    match driver {
+        Driver::Sqlite3 => SQLITE_MIGRATIONS,
+        Driver::MySQL => MYSQL_MIGRATIONS,
+        Driver::PostgreSQL => POSTGRESQL_MIGRATIONS,
+    }
+}
+
+/// It applies the pending migrations to the database.
+///
+/// # Errors
+///
+/// Will return an error if the database has migrations newer than the
+/// latest migration embedded in the binary, or if a migration fails.
+pub fn run<T: Database + ?Sized>(database: &T, driver: &Driver) -> Result<(), Error> {
+    let migrations = migrations(driver);
+
+    database.create_migrations_table()?;
+
+    let applied = database.load_applied_migrations()?;
+
+    let supported_version = migrations.last().map_or(0, |migration| migration.version);
+
+    if let Some(schema_version) = applied.iter().copied().filter(|version| *version > supported_version).max() {
+        return Err(Error::UnsupportedSchemaVersion {
+            location: Location::caller(),
+            schema_version,
+            supported_version,
+            driver: driver.clone(),
+        });
+    }
+
+    for migration in migrations.iter().filter(|migration| !applied.contains(&migration.version)) {
+        tracing::info!(target: "DATABASE", "Applying migration {}_{}", migration.version, migration.name);
+
+        database.apply_migration(migration)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod the_embedded_migrations {
+        use std::fs;
+
+        use crate::core::databases::driver::Driver;
+        use crate::core::databases::migrations::migrations;
+
+        fn migration_files(folder: &str) -> Vec<String> {
+            let path = format!("{}/migrations/{folder}", env!("CARGO_MANIFEST_DIR"));
+
+            let mut files: Vec<String> = fs::read_dir(path)
+                .unwrap()
+                .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+                .collect();
+
+            files.sort();
+
+            files
+        }
+
+        #[test]
+        fn should_include_all_the_files_in_the_driver_migrations_folder() {
+            for (driver, folder) in [
+                (Driver::Sqlite3, "sqlite"),
+                (Driver::MySQL, "mysql"),
+                (Driver::PostgreSQL, "postgresql"),
+            ] {
+                let embedded: Vec<String> = migrations(&driver)
+                    .iter()
+                    .map(|migration| format!("{}_{}.sql", migration.version, migration.name))
+                    .collect();
+
+                assert_eq!(embedded, migration_files(folder), "migrations for the {driver} driver");
+            }
+        }
+    }
+
+    mod splitting_a_migration_into_statements {
+        use crate::core::databases::migrations::Migration;
+
+        fn migration(sql: &'static str) -> Migration {
+            Migration {
+                version: 20_240_730_183_000,
+                name: "sample",
+                sql,
+            }
+        }
+
+        #[test]
+        fn should_return_each_statement_without_the_semicolon() {
+            assert_eq!(
+                migration("CREATE TABLE a (id INT);\n\nCREATE TABLE b (id INT);").statements(),
+                vec!["CREATE TABLE a (id INT)", "CREATE TABLE b (id INT)"]
+            );
+        }
+
+        #[test]
+        fn should_ignore_the_empty_statements() {
+            assert_eq!(migration(" ;SELECT 1;\n;\n").statements(), vec!["SELECT 1"]);
+        }
+
+        #[test]
+        fn should_not_split_the_statements_on_the_semicolons_in_quotes() {
+            assert_eq!(
+                migration("INSERT INTO `a;b` VALUES ('c;d', \"e;f');g\");SELECT 1").statements(),
+                vec!["INSERT INTO `a;b` VALUES ('c;d', \"e;f');g\")", "SELECT 1"]
+            );
+        }
+    }
+
+    mod running_the_migrations {
+        use std::env;
+
+        use bittorrent_primitives::info_hash::InfoHash;
+
+        use crate::core::auth::{self, Key};
+        use crate::core::databases::driver::Driver;
+        use crate::core::databases::error::Error;
+        use crate::core::databases::migrations::{migrations, run, Migration};
+        use crate::core::databases::sqlite::Sqlite;
+        use crate::core::databases::Database;
+
+        fn new_sqlite_database() -> Sqlite {
+            let path = env::temp_dir().join(format!("tracker_{}.db", uuid::Uuid::new_v4()));
+
+            Sqlite::new(&path.display().to_string()).unwrap()
+        }
+
+        #[test]
+        fn should_apply_all_the_migrations_to_a_new_database() {
+            let database = new_sqlite_database();
+
+            run(&database, &Driver::Sqlite3).unwrap();
+
+            let expected: Vec<i64> = migrations(&Driver::Sqlite3).iter().map(|m| m.version).collect();
+
+            assert_eq!(database.load_applied_migrations().unwrap(), expected);
+        }
+
+        #[test]
+        fn should_not_apply_the_same_migration_twice() {
+            let database = new_sqlite_database();
+
+            run(&database, &Driver::Sqlite3).unwrap();
+            run(&database, &Driver::Sqlite3).unwrap();
+
+            assert_eq!(
+                database.load_applied_migrations().unwrap().len(),
+                migrations(&Driver::Sqlite3).len()
+            );
+        }
+
+        #[test]
+        fn should_apply_the_pending_migrations_to_an_outdated_database() {
+            let database = new_sqlite_database();
+
+            // Initial schema, where keys must have an expiration date
+            database.create_migrations_table().unwrap();
+            database.apply_migration(&migrations(&Driver::Sqlite3)[0]).unwrap();
+
+            run(&database, &Driver::Sqlite3).unwrap();
+
+            let permanent_key = auth::PeerKey {
+                key: "YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ".parse::<Key>().unwrap(),
+                valid_until: None,
+            };
+
+            assert!(database.add_key_to_keys(&permanent_key).is_ok());
+        }
+
+        #[test]
+        fn should_keep_the_keys_with_an_allow_list_restricted_after_upgrading_the_database() {
+            let database = new_sqlite_database();
+
+            // Schema before the restricted keys table
+            let key = "YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ".parse::<Key>().unwrap();
+            let info_hash = "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".parse::<InfoHash>().unwrap();
+
+            database.create_migrations_table().unwrap();
+            for migration in migrations(&Driver::Sqlite3)
+                .iter()
+                .take_while(|migration| migration.name != "torrust_tracker_create_restricted_keys_table")
+            {
+                database.apply_migration(migration).unwrap();
+            }
+            database.add_key_torrent(&key, &info_hash).unwrap();
+
+            run(&database, &Driver::Sqlite3).unwrap();
+
+            assert_eq!(database.load_restricted_keys().unwrap(), vec![key]);
+        }
+
+        #[test]
+        fn should_refuse_a_database_schema_newer_than_the_tracker() {
+            let database = new_sqlite_database();
+
+            run(&database, &Driver::Sqlite3).unwrap();
+
+            database
+                .apply_migration(&Migration {
+                    version: 99_990_101_000_000,
+                    name: "from_the_future",
+                    sql: "SELECT 1;",
+                })
+                .unwrap();
+
+            assert!(matches!(
+                run(&database, &Driver::Sqlite3),
+                Err(Error::UnsupportedSchemaVersion {
+                    schema_version: 99_990_101_000_000,
+                    ..
+                })
+            ));
+        }
+    }
+}
@@ -2,19 +2,30 @@
 //!
 //! Persistence is currently implemented with one [`Database`] trait.
 //!
-//! There are two implementations of the trait (two drivers):
+//! There are three implementations of the trait (three drivers):
 //!
 //! - [`Mysql`](crate::core::databases::mysql::Mysql)
+//! - [`Postgres`](crate::core::databases::postgres::Postgres)
 //! - [`Sqlite`](crate::core::databases::sqlite::Sqlite)
 //!
-//! > **NOTICE**: There are no database migrations. If there are any changes,
-//! > we will implemented them or provide a script to migrate to the new schema.
+//! The database schema is created and upgraded with the [migrations](crate::core::databases::migrations)
+//! in the `migrations` folder. They are applied automatically when the tracker starts.
 //!
 //! The persistent objects are:
 //!
 //! - [Torrent metrics](#torrent-metrics)
 //! - [Torrent whitelist](torrent-whitelist)
+//! - [Torrent blacklist](torrent-blacklist)
+//! - [Hybrid torrents](hybrid-torrents)
 //! - [Authentication keys](authentication-keys)
+//! - [Key stats](key-stats)
+//! - [Torrent multipliers](torrent-multipliers)
+//! - [Key torrents](key-torrents)
+//! - [Key torrent groups](key-torrent-groups)
+//! - [Restricted keys](restricted-keys)
+//! - [Torrent groups](torrent-groups)
+//! - [Client prefixes](client-prefixes)
+//! - [IP blocklist](ip-blocklist)
 //!
 //! # Torrent metrics
 //!
@@ -25,7 +36,8 @@
 //!  `completed`   | 20                                       | The number of peers that have ever completed downloading the torrent associated to this entry. See [`Entry`](torrust_tracker_torrent_repository::entry::Entry) for more information.
 //!
 //! > **NOTICE**: The peer list for a torrent is not persisted. Since peer have to re-announce themselves on intervals, the data is be
-//! > regenerated again after some minutes.
+//! > regenerated again after some minutes. Swarm [snapshots](crate::core::torrent::snapshot) can be enabled to keep the
+//! > peer lists across restarts.
 //!
 //! # Torrent whitelist
 //!
@@ -34,6 +46,24 @@
 //! `id`          | 1                                        | Autoincrement id
 //! `info_hash`   | `c1277613db1d28709b034a017ab2cae4be07ae10` | `BitTorrent` infohash V1
 //!
+//! # Torrent blacklist
+//!
+//! Field         | Sample data                              | Description
+//! ---|---|---
+//! `id`          | 1                                        | Autoincrement id
+//! `info_hash`   | `c1277613db1d28709b034a017ab2cae4be07ae10` | `BitTorrent` infohash V1 of the banned torrent
+//!
+//! # Hybrid torrents
+//!
+//! Field          | Sample data                              | Description
+//! ---|---|---
+//! `id`           | 1                                        | Autoincrement id
+//! `info_hash`    | `c1277613db1d28709b034a017ab2cae4be07ae10` | `BitTorrent` infohash V1
+//! `info_hash_v2` | `3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0b8a1a4d58d14d7a21b7d7a45` | `BitTorrent` infohash V2 (SHA-256)
+//!
+//! Hybrid torrents have a V1 and a V2 infohash. Peers announcing the V2
+//! infohash (truncated to 20 bytes) join the swarm of the V1 infohash.
+//!
 //! # Authentication keys
 //!
 //! Field         | Sample data                      | Description                  
@@ -43,18 +73,101 @@
 //! `valid_until` | 1672419840                       | Timestamp for the expiring date  
 //!
 //! > **NOTICE**: All keys must have an expiration date.
+//!
+//! # Key stats
+//!
+//! Field          | Sample data                      | Description
+//! ---|---|---
+//! `id`           | 1                                | Autoincrement id
+//! `key`          | `IrweYtVuQPGbG9Jzx1DihcPmJGGpVy82` | Authentication key
+//! `uploaded`     | 1073741824                       | Total uploaded bytes, after applying the multipliers
+//! `downloaded`   | 536870912                        | Total downloaded bytes, after applying the multipliers
+//! `seeding_time` | 86400                            | Total seeding time in seconds
+//!
+//! Refer to the [`ratio`](crate::core::ratio) module for more information.
+//!
+//! # Torrent multipliers
+//!
+//! Field                 | Sample data                              | Description
+//! ---|---|---
+//! `id`                  | 1                                        | Autoincrement id
+//! `info_hash`           | `c1277613db1d28709b034a017ab2cae4be07ae10` | `BitTorrent` infohash V1
+//! `upload_multiplier`   | 2.0                                      | Multiplier for the uploaded bytes
+//! `download_multiplier` | 0.0                                      | Multiplier for the downloaded bytes
+//!
+//! # Key torrents
+//!
+//! Field         | Sample data                              | Description
+//! ---|---|---
+//! `id`          | 1                                        | Autoincrement id
+//! `key`         | `IrweYtVuQPGbG9Jzx1DihcPmJGGpVy82`         | Authentication key
+//! `info_hash`   | `c1277613db1d28709b034a017ab2cae4be07ae10` | Torrent in the allow-list of the key
+//!
+//! # Key torrent groups
+//!
+//! Field           | Sample data                      | Description
+//! ---|---|---
+//! `id`            | 1                                | Autoincrement id
+//! `key`           | `IrweYtVuQPGbG9Jzx1DihcPmJGGpVy82` | Authentication key
+//! `torrent_group` | `linux-isos`                     | Torrent group in the allow-list of the key
+//!
+//! # Restricted keys
+//!
+//! Field | Sample data                      | Description
+//! ---|---|---
+//! `id`  | 1                                | Autoincrement id
+//! `key` | `IrweYtVuQPGbG9Jzx1DihcPmJGGpVy82` | Authentication key restricted to its allow-list
+//!
+//! # Torrent groups
+//!
+//! Field           | Sample data                              | Description
+//! ---|---|---
+//! `id`            | 1                                        | Autoincrement id
+//! `torrent_group` | `linux-isos`                             | Torrent group name
+//! `info_hash`     | `c1277613db1d28709b034a017ab2cae4be07ae10` | Torrent in the group
+//!
+//! Refer to the [`access`](crate::core::access) module for more information.
+//!
+//! # Client prefixes
+//!
+//! Field         | Sample data  | Description
+//! ---|---|---
+//! `id`          | 1            | Autoincrement id
+//! `prefix`      | `-qB4500-`   | The beginning of the `peer_id` of the client software
+//!
+//! Refer to the [`client_filter`](crate::core::client_filter) module for more information.
+//!
+//! # IP blocklist
+//!
+//! Field         | Sample data     | Description
+//! ---|---|---
+//! `id`          | 1               | Autoincrement id
+//! `network`     | `10.0.0.0/8`    | Blocked network in CIDR notation
+//!
+//! > **NOTICE**: Only the networks blocked with the API are persisted. The
+//! > networks in the IP blocklist file are loaded from the file on startup.
+//!
+//! Refer to the [`ip_blocklist`](crate::core::ip_blocklist) module for more information.
 pub mod driver;
 pub mod error;
+pub mod migrations;
 pub mod mysql;
+pub mod postgres;
 pub mod sqlite;
 
 use std::marker::PhantomData;
 
 use bittorrent_primitives::info_hash::InfoHash;
+use ipnet::IpNet;
+use torrust_tracker_primitives::info_hash_v2::InfoHashV2;
 use torrust_tracker_primitives::PersistentTorrents;
 
 use self::error::Error;
+use self::migrations::Migration;
+use crate::core::access::TorrentGroup;
 use crate::core::auth::{self, Key};
+use crate::core::client_filter::PeerIdPrefix;
+use crate::core::ratio::{KeyStats, Multipliers};
 
 struct Builder<T>
 where
@@ -90,14 +203,15 @@ pub trait Database: Sync + Send {
 
     // Schema
 
-    /// It generates the database tables. SQL queries are hardcoded in the trait
-    /// implementation.
+    /// It generates the database tables, or upgrades them, applying the
+    /// pending [migrations](crate::core::databases::migrations).
     ///
     /// # Context: Schema
     ///
     /// # Errors
     ///
-    /// Will return `Error` if unable to create own tables.
+    /// Will return `Error` if unable to create own tables, or if the database
+    /// schema is newer than the one supported by the tracker.
     fn create_database_tables(&self) -> Result<(), Error>;
 
     /// It drops the database tables.
@@ -109,6 +223,34 @@ pub trait Database: Sync + Send {
     /// Will return `Err` if unable to drop tables.
     fn drop_database_tables(&self) -> Result<(), Error>;
 
+    /// It creates the table with the applied migrations if it does not exist.
+    ///
+    /// # Context: Schema
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to create the table.
+    fn create_migrations_table(&self) -> Result<(), Error>;
+
+    /// It loads the versions of the applied migrations sorted in ascending
+    /// order.
+    ///
+    /// # Context: Schema
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to load.
+    fn load_applied_migrations(&self) -> Result<Vec<i64>, Error>;
+
+    /// It runs the migration SQL and records it as applied.
+    ///
+    /// # Context: Schema
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the migration fails.
+    fn apply_migration(&self, migration: &Migration) -> Result<(), Error>;
+
     // Torrent Metrics
 
     /// It loads the torrent metrics data from the database.
@@ -186,6 +328,101 @@ pub trait Database: Sync + Send {
     /// Will return `Err` if unable to save.
     fn remove_info_hash_from_whitelist(&self, info_hash: InfoHash) -> Result<usize, Error>;
 
+    // Blacklist
+
+    /// It loads the blacklisted torrents from the database.
+    ///
+    /// # Context: Blacklist
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to load.
+    fn load_blacklist(&self) -> Result<Vec<InfoHash>, Error>;
+
+    /// It checks if the torrent is blacklisted.
+    ///
+    /// It returns `Some(InfoHash)` if the torrent is blacklisted, `None` otherwise.
+    ///
+    /// # Context: Blacklist
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to load.
+    fn get_info_hash_from_blacklist(&self, info_hash: InfoHash) -> Result<Option<InfoHash>, Error>;
+
+    /// It adds the torrent to the blacklist.
+    ///
+    /// # Context: Blacklist
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save.
+    fn add_info_hash_to_blacklist(&self, info_hash: InfoHash) -> Result<usize, Error>;
+
+    /// It checks if the torrent is blacklisted.
+    ///
+    /// # Context: Blacklist
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to load.
+    fn is_info_hash_blacklisted(&self, info_hash: InfoHash) -> Result<bool, Error> {
+        Ok(self.get_info_hash_from_blacklist(info_hash)?.is_some())
+    }
+
+    /// It removes the torrent from the blacklist.
+    ///
+    /// # Context: Blacklist
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save.
+    fn remove_info_hash_from_blacklist(&self, info_hash: InfoHash) -> Result<usize, Error>;
+
+    // Hybrid torrents
+
+    /// It loads the hybrid torrents from the database.
+    ///
+    /// It returns an array of tuples with the V1 [`InfoHash`] and the
+    /// [`InfoHashV2`] of each torrent.
+    ///
+    /// # Context: Hybrid torrents
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to load.
+    fn load_hybrid_torrents(&self) -> Result<Vec<(InfoHash, InfoHashV2)>, Error>;
+
+    /// It gets the V1 infohash of a hybrid torrent from its V2 infohash.
+    ///
+    /// It returns `Some(InfoHash)` if the torrent is a known hybrid torrent,
+    /// `None` otherwise.
+    ///
+    /// # Context: Hybrid torrents
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to load.
+    fn get_info_hash_from_hybrid_torrents(&self, info_hash_v2: &InfoHashV2) -> Result<Option<InfoHash>, Error>;
+
+    /// It adds a hybrid torrent.
+    ///
+    /// # Context: Hybrid torrents
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save.
+    fn add_hybrid_torrent(&self, info_hash: &InfoHash, info_hash_v2: &InfoHashV2) -> Result<usize, Error>;
+
+    /// It removes a hybrid torrent.
+    ///
+    /// # Context: Hybrid torrents
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save.
+    fn remove_hybrid_torrent(&self, info_hash_v2: &InfoHashV2) -> Result<usize, Error>;
+
     // Authentication keys
 
     /// It loads the expiring authentication keys from the database.
@@ -226,4 +463,230 @@ pub trait Database: Sync + Send {
     ///
     /// Will return `Err` if unable to load.
     fn remove_key_from_keys(&self, key: &Key) -> Result<usize, Error>;
+
+    // Transfer accounting
+
+    /// It loads the transfer stats of all the authentication keys.
+    ///
+    /// # Context: Transfer accounting
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to load.
+    fn load_key_stats(&self) -> Result<Vec<(Key, KeyStats)>, Error>;
+
+    /// It saves the transfer stats of an authentication key, replacing the
+    /// previous ones.
+    ///
+    /// # Context: Transfer accounting
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save.
+    fn save_key_stats(&self, key: &Key, stats: &KeyStats) -> Result<(), Error>;
+
+    /// It removes the transfer stats of an authentication key, if any.
+    ///
+    /// # Context: Transfer accounting
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to remove.
+    fn remove_key_stats(&self, key: &Key) -> Result<(), Error>;
+
+    /// It loads the multipliers of all the torrents that have them.
+    ///
+    /// # Context: Transfer accounting
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to load.
+    fn load_torrent_multipliers(&self) -> Result<Vec<(InfoHash, Multipliers)>, Error>;
+
+    /// It saves the multipliers of a torrent, replacing the previous ones.
+    ///
+    /// # Context: Transfer accounting
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save.
+    fn save_torrent_multipliers(&self, info_hash: &InfoHash, multipliers: &Multipliers) -> Result<(), Error>;
+
+    /// It removes the multipliers of a torrent.
+    ///
+    /// # Context: Transfer accounting
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save.
+    fn remove_torrent_multipliers(&self, info_hash: &InfoHash) -> Result<usize, Error>;
+
+    // Torrent access
+
+    /// It loads the torrents in the allow-lists of all the keys.
+    ///
+    /// # Context: Torrent access
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to load.
+    fn load_key_torrents(&self) -> Result<Vec<(Key, InfoHash)>, Error>;
+
+    /// It adds a torrent to the allow-list of a key.
+    ///
+    /// # Context: Torrent access
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save.
+    fn add_key_torrent(&self, key: &Key, info_hash: &InfoHash) -> Result<usize, Error>;
+
+    /// It removes a torrent from the allow-list of a key.
+    ///
+    /// # Context: Torrent access
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save.
+    fn remove_key_torrent(&self, key: &Key, info_hash: &InfoHash) -> Result<usize, Error>;
+
+    /// It loads the torrent groups in the allow-lists of all the keys.
+    ///
+    /// # Context: Torrent access
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to load.
+    fn load_key_torrent_groups(&self) -> Result<Vec<(Key, TorrentGroup)>, Error>;
+
+    /// It adds a torrent group to the allow-list of a key.
+    ///
+    /// # Context: Torrent access
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save.
+    fn add_key_torrent_group(&self, key: &Key, group: &TorrentGroup) -> Result<usize, Error>;
+
+    /// It removes a torrent group from the allow-list of a key.
+    ///
+    /// # Context: Torrent access
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save.
+    fn remove_key_torrent_group(&self, key: &Key, group: &TorrentGroup) -> Result<usize, Error>;
+
+    /// It loads the keys restricted to their allow-lists.
+    ///
+    /// # Context: Torrent access
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to load.
+    fn load_restricted_keys(&self) -> Result<Vec<Key>, Error>;
+
+    /// It restricts a key to its allow-list.
+    ///
+    /// # Context: Torrent access
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save.
+    fn add_restricted_key(&self, key: &Key) -> Result<usize, Error>;
+
+    /// It removes the restriction of a key, together with the torrents and
+    /// torrent groups in its allow-list.
+    ///
+    /// # Context: Torrent access
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save.
+    fn remove_restricted_key(&self, key: &Key) -> Result<usize, Error>;
+
+    /// It loads the torrents of all the torrent groups.
+    ///
+    /// # Context: Torrent access
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to load.
+    fn load_torrent_groups(&self) -> Result<Vec<(TorrentGroup, InfoHash)>, Error>;
+
+    /// It adds a torrent to a torrent group.
+    ///
+    /// # Context: Torrent access
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save.
+    fn add_torrent_to_group(&self, group: &TorrentGroup, info_hash: &InfoHash) -> Result<usize, Error>;
+
+    /// It removes a torrent from a torrent group.
+    ///
+    /// # Context: Torrent access
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save.
+    fn remove_torrent_from_group(&self, group: &TorrentGroup, info_hash: &InfoHash) -> Result<usize, Error>;
+
+    // Client filter
+
+    /// It loads the `peer_id` prefixes of the client filter.
+    ///
+    /// # Context: Client filter
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to load.
+    fn load_client_prefixes(&self) -> Result<Vec<PeerIdPrefix>, Error>;
+
+    /// It adds a `peer_id` prefix to the client filter.
+    ///
+    /// # Context: Client filter
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save.
+    fn add_client_prefix(&self, prefix: &PeerIdPrefix) -> Result<usize, Error>;
+
+    /// It removes a `peer_id` prefix from the client filter.
+    ///
+    /// # Context: Client filter
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save.
+    fn remove_client_prefix(&self, prefix: &PeerIdPrefix) -> Result<usize, Error>;
+
+    // IP blocklist
+
+    /// It loads the networks blocked with the API.
+    ///
+    /// # Context: IP blocklist
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to load.
+    fn load_blocked_networks(&self) -> Result<Vec<IpNet>, Error>;
+
+    /// It adds a network to the IP blocklist.
+    ///
+    /// # Context: IP blocklist
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save.
+    fn add_blocked_network(&self, network: &IpNet) -> Result<usize, Error>;
+
+    /// It removes a network from the IP blocklist.
+    ///
+    /// # Context: IP blocklist
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to save.
+    fn remove_blocked_network(&self, network: &IpNet) -> Result<usize, Error>;
 }
@@ -3,16 +3,21 @@ use std::str::FromStr;
 use std::time::Duration;
 
 use bittorrent_primitives::info_hash::InfoHash;
+use ipnet::IpNet;
 use r2d2::Pool;
 use r2d2_mysql::mysql::prelude::Queryable;
-use r2d2_mysql::mysql::{params, Opts, OptsBuilder};
+use r2d2_mysql::mysql::{params, Opts, OptsBuilder, TxOpts};
 use r2d2_mysql::MySqlConnectionManager;
+use torrust_tracker_primitives::info_hash_v2::InfoHashV2;
 use torrust_tracker_primitives::PersistentTorrents;
 
 use super::driver::Driver;
+use super::migrations::{self, Migration};
 use super::{Database, Error};
+use crate::core::access::TorrentGroup;
 use crate::core::auth::{self, Key};
-use crate::shared::bit_torrent::common::AUTH_KEY_LENGTH;
+use crate::core::client_filter::PeerIdPrefix;
+use crate::core::ratio::{KeyStats, Multipliers};
 
 const DRIVER: Driver = Driver::MySQL;
 
@@ -39,42 +44,7 @@ impl Database for Mysql {
 
     /// Refer to [`databases::Database::create_database_tables`](crate::core::databases::Database::create_database_tables).
     fn create_database_tables(&self) -> Result<(), Error> {
-        let create_whitelist_table = "
-        CREATE TABLE IF NOT EXISTS whitelist (
-            id integer PRIMARY KEY AUTO_INCREMENT,
-            info_hash VARCHAR(40) NOT NULL UNIQUE
-        );"
-        .to_string();
-
-        let create_torrents_table = "
-        CREATE TABLE IF NOT EXISTS torrents (
-            id integer PRIMARY KEY AUTO_INCREMENT,
-            info_hash VARCHAR(40) NOT NULL UNIQUE,
-            completed INTEGER DEFAULT 0 NOT NULL
-        );"
-        .to_string();
-
-        let create_keys_table = format!(
-            "
-        CREATE TABLE IF NOT EXISTS `keys` (
-          `id` INT NOT NULL AUTO_INCREMENT,
-          `key` VARCHAR({}) NOT NULL,
-          `valid_until` INT(10),
-          PRIMARY KEY (`id`),
-          UNIQUE (`key`)
-        );",
-            i8::try_from(AUTH_KEY_LENGTH).expect("auth::Auth Key Length Should fit within a i8!")
-        );
-
-        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
-
-        conn.query_drop(&create_torrents_table)
-            .expect("Could not create torrents table.");
-        conn.query_drop(&create_keys_table).expect("Could not create keys table.");
-        conn.query_drop(&create_whitelist_table)
-            .expect("Could not create whitelist table.");
-
-        Ok(())
+        migrations::run(self, &DRIVER)
     }
 
     /// Refer to [`databases::Database::drop_database_tables`](crate::core::databases::Database::drop_database_tables).
@@ -87,17 +57,134 @@ impl Database for Mysql {
         DROP TABLE `torrents`;"
             .to_string();
 
+        let drop_hybrid_torrents_table = "
+        DROP TABLE `hybrid_torrents`;"
+            .to_string();
+
         let drop_keys_table = "
             DROP TABLE `keys`;"
             .to_string();
 
+        let drop_key_stats_table = "
+        DROP TABLE `key_stats`;"
+            .to_string();
+
+        let drop_torrent_multipliers_table = "
+        DROP TABLE `torrent_multipliers`;"
+            .to_string();
+
+        let drop_key_torrents_table = "
+        DROP TABLE `key_torrents`;"
+            .to_string();
+
+        let drop_key_torrent_groups_table = "
+        DROP TABLE `key_torrent_groups`;"
+            .to_string();
+
+        let drop_torrent_groups_table = "
+        DROP TABLE `torrent_groups`;"
+            .to_string();
+
+        let drop_restricted_keys_table = "
+        DROP TABLE `restricted_keys`;"
+            .to_string();
+
+        let drop_client_prefixes_table = "
+        DROP TABLE `client_prefixes`;"
+            .to_string();
+
+        let drop_ip_blocklist_table = "
+        DROP TABLE `ip_blocklist`;"
+            .to_string();
+
+        let drop_blacklist_table = "
+        DROP TABLE `blacklist`;"
+            .to_string();
+
+        let drop_migrations_table = "
+        DROP TABLE `schema_migrations`;"
+            .to_string();
+
         let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
 
         conn.query_drop(&drop_whitelist_table)
             .expect("Could not drop `whitelist` table.");
         conn.query_drop(&drop_torrents_table)
             .expect("Could not drop `torrents` table.");
+        conn.query_drop(&drop_hybrid_torrents_table)
+            .expect("Could not drop `hybrid_torrents` table.");
         conn.query_drop(&drop_keys_table).expect("Could not drop `keys` table.");
+        conn.query_drop(&drop_key_stats_table)
+            .expect("Could not drop `key_stats` table.");
+        conn.query_drop(&drop_torrent_multipliers_table)
+            .expect("Could not drop `torrent_multipliers` table.");
+        conn.query_drop(&drop_key_torrents_table)
+            .expect("Could not drop `key_torrents` table.");
+        conn.query_drop(&drop_key_torrent_groups_table)
+            .expect("Could not drop `key_torrent_groups` table.");
+        conn.query_drop(&drop_torrent_groups_table)
+            .expect("Could not drop `torrent_groups` table.");
+        conn.query_drop(&drop_restricted_keys_table)
+            .expect("Could not drop `restricted_keys` table.");
+        conn.query_drop(&drop_client_prefixes_table)
+            .expect("Could not drop `client_prefixes` table.");
+        conn.query_drop(&drop_ip_blocklist_table)
+            .expect("Could not drop `ip_blocklist` table.");
+        conn.query_drop(&drop_blacklist_table)
+            .expect("Could not drop `blacklist` table.");
+        conn.query_drop(&drop_migrations_table)
+            .expect("Could not drop `schema_migrations` table.");
+
+        Ok(())
+    }
+
+    /// Refer to [`databases::Database::create_migrations_table`](crate::core::databases::Database::create_migrations_table).
+    fn create_migrations_table(&self) -> Result<(), Error> {
+        let create_migrations_table = "
+        CREATE TABLE IF NOT EXISTS schema_migrations (
+            version BIGINT NOT NULL PRIMARY KEY,
+            name VARCHAR(255) NOT NULL
+        );"
+        .to_string();
+
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        conn.query_drop(&create_migrations_table)?;
+
+        Ok(())
+    }
+
+    /// Refer to [`databases::Database::load_applied_migrations`](crate::core::databases::Database::load_applied_migrations).
+    fn load_applied_migrations(&self) -> Result<Vec<i64>, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let versions = conn.query("SELECT version FROM schema_migrations ORDER BY version")?;
+
+        Ok(versions)
+    }
+
+    /// Refer to [`databases::Database::apply_migration`](crate::core::databases::Database::apply_migration).
+    ///
+    /// The statements of the migration run one at a time in a transaction.
+    ///
+    /// > **NOTICE**: `MySQL` commits schema changes (`CREATE TABLE`,
+    /// > `ALTER TABLE`, ...) implicitly, so only the data changes of a
+    /// > migration that fails halfway are rolled back.
+    fn apply_migration(&self, migration: &Migration) -> Result<(), Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let mut tx = conn.start_transaction(TxOpts::default())?;
+
+        for statement in migration.statements() {
+            tx.query_drop(statement)?;
+        }
+
+        tx.exec_drop(
+            "INSERT INTO schema_migrations (version, name) VALUES (:version, :name)",
+            params! { "version" => migration.version, "name" => migration.name },
+        )?;
+
+        tx.commit()?;
 
         Ok(())
     }
@@ -201,6 +288,116 @@ impl Database for Mysql {
         Ok(1)
     }
 
+    /// Refer to [`databases::Database::load_blacklist`](crate::core::databases::Database::load_blacklist).
+    fn load_blacklist(&self) -> Result<Vec<InfoHash>, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let info_hashes = conn.query_map("SELECT info_hash FROM blacklist", |info_hash: String| {
+            InfoHash::from_str(&info_hash).unwrap()
+        })?;
+
+        Ok(info_hashes)
+    }
+
+    /// Refer to [`databases::Database::get_info_hash_from_blacklist`](crate::core::databases::Database::get_info_hash_from_blacklist).
+    fn get_info_hash_from_blacklist(&self, info_hash: InfoHash) -> Result<Option<InfoHash>, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let select = conn.exec_first::<String, _, _>(
+            "SELECT info_hash FROM blacklist WHERE info_hash = :info_hash",
+            params! { "info_hash" => info_hash.to_hex_string() },
+        )?;
+
+        let info_hash = select.map(|f| InfoHash::from_str(&f).expect("Failed to decode InfoHash String from DB!"));
+
+        Ok(info_hash)
+    }
+
+    /// Refer to [`databases::Database::add_info_hash_to_blacklist`](crate::core::databases::Database::add_info_hash_to_blacklist).
+    fn add_info_hash_to_blacklist(&self, info_hash: InfoHash) -> Result<usize, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let info_hash_str = info_hash.to_string();
+
+        conn.exec_drop(
+            "INSERT INTO blacklist (info_hash) VALUES (:info_hash_str)",
+            params! { info_hash_str },
+        )?;
+
+        Ok(1)
+    }
+
+    /// Refer to [`databases::Database::remove_info_hash_from_blacklist`](crate::core::databases::Database::remove_info_hash_from_blacklist).
+    fn remove_info_hash_from_blacklist(&self, info_hash: InfoHash) -> Result<usize, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let info_hash = info_hash.to_string();
+
+        conn.exec_drop("DELETE FROM blacklist WHERE info_hash = :info_hash", params! { info_hash })?;
+
+        Ok(1)
+    }
+
+    /// Refer to [`databases::Database::load_hybrid_torrents`](crate::core::databases::Database::load_hybrid_torrents).
+    fn load_hybrid_torrents(&self) -> Result<Vec<(InfoHash, InfoHashV2)>, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let hybrid_torrents = conn.query_map(
+            "SELECT info_hash, info_hash_v2 FROM hybrid_torrents",
+            |(info_hash, info_hash_v2): (String, String)| {
+                (
+                    InfoHash::from_str(&info_hash).unwrap(),
+                    InfoHashV2::from_str(&info_hash_v2).unwrap(),
+                )
+            },
+        )?;
+
+        Ok(hybrid_torrents)
+    }
+
+    /// Refer to [`databases::Database::get_info_hash_from_hybrid_torrents`](crate::core::databases::Database::get_info_hash_from_hybrid_torrents).
+    fn get_info_hash_from_hybrid_torrents(&self, info_hash_v2: &InfoHashV2) -> Result<Option<InfoHash>, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let select = conn.exec_first::<String, _, _>(
+            "SELECT info_hash FROM hybrid_torrents WHERE info_hash_v2 = :info_hash_v2",
+            params! { "info_hash_v2" => info_hash_v2.to_hex_string() },
+        )?;
+
+        let info_hash = select.map(|f| InfoHash::from_str(&f).expect("Failed to decode InfoHash String from DB!"));
+
+        Ok(info_hash)
+    }
+
+    /// Refer to [`databases::Database::add_hybrid_torrent`](crate::core::databases::Database::add_hybrid_torrent).
+    fn add_hybrid_torrent(&self, info_hash: &InfoHash, info_hash_v2: &InfoHashV2) -> Result<usize, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let info_hash_str = info_hash.to_string();
+        let info_hash_v2_str = info_hash_v2.to_string();
+
+        conn.exec_drop(
+            "INSERT INTO hybrid_torrents (info_hash, info_hash_v2) VALUES (:info_hash_str, :info_hash_v2_str)",
+            params! { info_hash_str, info_hash_v2_str },
+        )?;
+
+        Ok(1)
+    }
+
+    /// Refer to [`databases::Database::remove_hybrid_torrent`](crate::core::databases::Database::remove_hybrid_torrent).
+    fn remove_hybrid_torrent(&self, info_hash_v2: &InfoHashV2) -> Result<usize, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let info_hash_v2 = info_hash_v2.to_string();
+
+        conn.exec_drop(
+            "DELETE FROM hybrid_torrents WHERE info_hash_v2 = :info_hash_v2",
+            params! { info_hash_v2 },
+        )?;
+
+        Ok(1)
+    }
+
     /// Refer to [`databases::Database::get_key_from_keys`](crate::core::databases::Database::get_key_from_keys).
     fn get_key_from_keys(&self, key: &Key) -> Result<Option<auth::PeerKey>, Error> {
         let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
@@ -250,4 +447,328 @@ impl Database for Mysql {
 
         Ok(1)
     }
+
+    /// Refer to [`databases::Database::load_key_stats`](crate::core::databases::Database::load_key_stats).
+    fn load_key_stats(&self) -> Result<Vec<(Key, KeyStats)>, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let key_stats = conn.query_map(
+            "SELECT `key`, uploaded, downloaded, seeding_time FROM key_stats",
+            |(key, uploaded, downloaded, seeding_time): (String, i64, i64, i64)| {
+                (
+                    key.parse::<Key>().unwrap(),
+                    KeyStats {
+                        uploaded: uploaded.unsigned_abs(),
+                        downloaded: downloaded.unsigned_abs(),
+                        seeding_time: Duration::from_secs(seeding_time.unsigned_abs()),
+                    },
+                )
+            },
+        )?;
+
+        Ok(key_stats)
+    }
+
+    /// Refer to [`databases::Database::save_key_stats`](crate::core::databases::Database::save_key_stats).
+    fn save_key_stats(&self, key: &Key, stats: &KeyStats) -> Result<(), Error> {
+        const COMMAND : &str = "INSERT INTO key_stats (`key`, uploaded, downloaded, seeding_time) VALUES (:key, :uploaded, :downloaded, :seeding_time) ON DUPLICATE KEY UPDATE uploaded = VALUES(uploaded), downloaded = VALUES(downloaded), seeding_time = VALUES(seeding_time)";
+
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        Ok(conn.exec_drop(
+            COMMAND,
+            params! {
+                "key" => key.to_string(),
+                "uploaded" => stats.uploaded,
+                "downloaded" => stats.downloaded,
+                "seeding_time" => stats.seeding_time.as_secs(),
+            },
+        )?)
+    }
+
+    /// Refer to [`databases::Database::remove_key_stats`](crate::core::databases::Database::remove_key_stats).
+    fn remove_key_stats(&self, key: &Key) -> Result<(), Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        conn.exec_drop(
+            "DELETE FROM key_stats WHERE `key` = :key",
+            params! { "key" => key.to_string() },
+        )?;
+
+        Ok(())
+    }
+
+    /// Refer to [`databases::Database::load_torrent_multipliers`](crate::core::databases::Database::load_torrent_multipliers).
+    fn load_torrent_multipliers(&self) -> Result<Vec<(InfoHash, Multipliers)>, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let multipliers = conn.query_map(
+            "SELECT info_hash, upload_multiplier, download_multiplier FROM torrent_multipliers",
+            |(info_hash, upload, download): (String, f64, f64)| {
+                (InfoHash::from_str(&info_hash).unwrap(), Multipliers { upload, download })
+            },
+        )?;
+
+        Ok(multipliers)
+    }
+
+    /// Refer to [`databases::Database::save_torrent_multipliers`](crate::core::databases::Database::save_torrent_multipliers).
+    fn save_torrent_multipliers(&self, info_hash: &InfoHash, multipliers: &Multipliers) -> Result<(), Error> {
+        const COMMAND : &str = "INSERT INTO torrent_multipliers (info_hash, upload_multiplier, download_multiplier) VALUES (:info_hash, :upload, :download) ON DUPLICATE KEY UPDATE upload_multiplier = VALUES(upload_multiplier), download_multiplier = VALUES(download_multiplier)";
+
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        Ok(conn.exec_drop(
+            COMMAND,
+            params! {
+                "info_hash" => info_hash.to_string(),
+                "upload" => multipliers.upload,
+                "download" => multipliers.download,
+            },
+        )?)
+    }
+
+    /// Refer to [`databases::Database::remove_torrent_multipliers`](crate::core::databases::Database::remove_torrent_multipliers).
+    fn remove_torrent_multipliers(&self, info_hash: &InfoHash) -> Result<usize, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let info_hash = info_hash.to_string();
+
+        conn.exec_drop(
+            "DELETE FROM torrent_multipliers WHERE info_hash = :info_hash",
+            params! { info_hash },
+        )?;
+
+        Ok(1)
+    }
+
+    /// Refer to [`databases::Database::load_key_torrents`](crate::core::databases::Database::load_key_torrents).
+    fn load_key_torrents(&self) -> Result<Vec<(Key, InfoHash)>, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let key_torrents = conn.query_map(
+            "SELECT `key`, info_hash FROM key_torrents",
+            |(key, info_hash): (String, String)| (key.parse::<Key>().unwrap(), info_hash.parse::<InfoHash>().unwrap()),
+        )?;
+
+        Ok(key_torrents)
+    }
+
+    /// Refer to [`databases::Database::add_key_torrent`](crate::core::databases::Database::add_key_torrent).
+    fn add_key_torrent(&self, key: &Key, info_hash: &InfoHash) -> Result<usize, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        conn.exec_drop(
+            "INSERT INTO key_torrents (`key`, info_hash) VALUES (:key, :info_hash)",
+            params! { "key" => key.to_string(), "info_hash" => info_hash.to_string() },
+        )?;
+
+        Ok(1)
+    }
+
+    /// Refer to [`databases::Database::remove_key_torrent`](crate::core::databases::Database::remove_key_torrent).
+    fn remove_key_torrent(&self, key: &Key, info_hash: &InfoHash) -> Result<usize, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        conn.exec_drop(
+            "DELETE FROM key_torrents WHERE `key` = :key AND info_hash = :info_hash",
+            params! { "key" => key.to_string(), "info_hash" => info_hash.to_string() },
+        )?;
+
+        Ok(1)
+    }
+
+    /// Refer to [`databases::Database::load_key_torrent_groups`](crate::core::databases::Database::load_key_torrent_groups).
+    fn load_key_torrent_groups(&self) -> Result<Vec<(Key, TorrentGroup)>, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let key_torrent_groups = conn.query_map(
+            "SELECT `key`, torrent_group FROM key_torrent_groups",
+            |(key, torrent_group): (String, String)| {
+                (key.parse::<Key>().unwrap(), torrent_group.parse::<TorrentGroup>().unwrap())
+            },
+        )?;
+
+        Ok(key_torrent_groups)
+    }
+
+    /// Refer to [`databases::Database::add_key_torrent_group`](crate::core::databases::Database::add_key_torrent_group).
+    fn add_key_torrent_group(&self, key: &Key, group: &TorrentGroup) -> Result<usize, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        conn.exec_drop(
+            "INSERT INTO key_torrent_groups (`key`, torrent_group) VALUES (:key, :torrent_group)",
+            params! { "key" => key.to_string(), "torrent_group" => group.to_string() },
+        )?;
+
+        Ok(1)
+    }
+
+    /// Refer to [`databases::Database::remove_key_torrent_group`](crate::core::databases::Database::remove_key_torrent_group).
+    fn remove_key_torrent_group(&self, key: &Key, group: &TorrentGroup) -> Result<usize, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        conn.exec_drop(
+            "DELETE FROM key_torrent_groups WHERE `key` = :key AND torrent_group = :torrent_group",
+            params! { "key" => key.to_string(), "torrent_group" => group.to_string() },
+        )?;
+
+        Ok(1)
+    }
+
+    /// Refer to [`databases::Database::load_restricted_keys`](crate::core::databases::Database::load_restricted_keys).
+    fn load_restricted_keys(&self) -> Result<Vec<Key>, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let keys = conn.query_map("SELECT `key` FROM restricted_keys", |key: String| key.parse::<Key>().unwrap())?;
+
+        Ok(keys)
+    }
+
+    /// Refer to [`databases::Database::add_restricted_key`](crate::core::databases::Database::add_restricted_key).
+    fn add_restricted_key(&self, key: &Key) -> Result<usize, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        conn.exec_drop(
+            "INSERT INTO restricted_keys (`key`) VALUES (:key)",
+            params! { "key" => key.to_string() },
+        )?;
+
+        Ok(1)
+    }
+
+    /// Refer to [`databases::Database::remove_restricted_key`](crate::core::databases::Database::remove_restricted_key).
+    fn remove_restricted_key(&self, key: &Key) -> Result<usize, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let mut tx = conn.start_transaction(TxOpts::default())?;
+
+        tx.exec_drop(
+            "DELETE FROM key_torrents WHERE `key` = :key",
+            params! { "key" => key.to_string() },
+        )?;
+        tx.exec_drop(
+            "DELETE FROM key_torrent_groups WHERE `key` = :key",
+            params! { "key" => key.to_string() },
+        )?;
+        tx.exec_drop(
+            "DELETE FROM restricted_keys WHERE `key` = :key",
+            params! { "key" => key.to_string() },
+        )?;
+
+        tx.commit()?;
+
+        Ok(1)
+    }
+
+    /// Refer to [`databases::Database::load_torrent_groups`](crate::core::databases::Database::load_torrent_groups).
+    fn load_torrent_groups(&self) -> Result<Vec<(TorrentGroup, InfoHash)>, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let torrent_groups = conn.query_map(
+            "SELECT torrent_group, info_hash FROM torrent_groups",
+            |(torrent_group, info_hash): (String, String)| {
+                (
+                    torrent_group.parse::<TorrentGroup>().unwrap(),
+                    info_hash.parse::<InfoHash>().unwrap(),
+                )
+            },
+        )?;
+
+        Ok(torrent_groups)
+    }
+
+    /// Refer to [`databases::Database::add_torrent_to_group`](crate::core::databases::Database::add_torrent_to_group).
+    fn add_torrent_to_group(&self, group: &TorrentGroup, info_hash: &InfoHash) -> Result<usize, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        conn.exec_drop(
+            "INSERT INTO torrent_groups (torrent_group, info_hash) VALUES (:torrent_group, :info_hash)",
+            params! { "torrent_group" => group.to_string(), "info_hash" => info_hash.to_string() },
+        )?;
+
+        Ok(1)
+    }
+
+    /// Refer to [`databases::Database::remove_torrent_from_group`](crate::core::databases::Database::remove_torrent_from_group).
+    fn remove_torrent_from_group(&self, group: &TorrentGroup, info_hash: &InfoHash) -> Result<usize, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        conn.exec_drop(
+            "DELETE FROM torrent_groups WHERE torrent_group = :torrent_group AND info_hash = :info_hash",
+            params! { "torrent_group" => group.to_string(), "info_hash" => info_hash.to_string() },
+        )?;
+
+        Ok(1)
+    }
+
+    /// Refer to [`databases::Database::load_client_prefixes`](crate::core::databases::Database::load_client_prefixes).
+    fn load_client_prefixes(&self) -> Result<Vec<PeerIdPrefix>, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let prefixes = conn.query_map("SELECT prefix FROM client_prefixes", |prefix: String| {
+            prefix.parse::<PeerIdPrefix>().unwrap()
+        })?;
+
+        Ok(prefixes)
+    }
+
+    /// Refer to [`databases::Database::add_client_prefix`](crate::core::databases::Database::add_client_prefix).
+    fn add_client_prefix(&self, prefix: &PeerIdPrefix) -> Result<usize, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        conn.exec_drop(
+            "INSERT INTO client_prefixes (prefix) VALUES (:prefix)",
+            params! { "prefix" => prefix.to_string() },
+        )?;
+
+        Ok(1)
+    }
+
+    /// Refer to [`databases::Database::remove_client_prefix`](crate::core::databases::Database::remove_client_prefix).
+    fn remove_client_prefix(&self, prefix: &PeerIdPrefix) -> Result<usize, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        conn.exec_drop(
+            "DELETE FROM client_prefixes WHERE prefix = :prefix",
+            params! { "prefix" => prefix.to_string() },
+        )?;
+
+        Ok(1)
+    }
+
+    /// Refer to [`databases::Database::load_blocked_networks`](crate::core::databases::Database::load_blocked_networks).
+    fn load_blocked_networks(&self) -> Result<Vec<IpNet>, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let networks = conn.query_map("SELECT network FROM ip_blocklist", |network: String| {
+            network.parse::<IpNet>().unwrap()
+        })?;
+
+        Ok(networks)
+    }
+
+    /// Refer to [`databases::Database::add_blocked_network`](crate::core::databases::Database::add_blocked_network).
+    fn add_blocked_network(&self, network: &IpNet) -> Result<usize, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        conn.exec_drop(
+            "INSERT INTO ip_blocklist (network) VALUES (:network)",
+            params! { "network" => network.to_string() },
+        )?;
+
+        Ok(1)
+    }
+
+    /// Refer to [`databases::Database::remove_blocked_network`](crate::core::databases::Database::remove_blocked_network).
+    fn remove_blocked_network(&self, network: &IpNet) -> Result<usize, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        conn.exec_drop(
+            "DELETE FROM ip_blocklist WHERE network = :network",
+            params! { "network" => network.to_string() },
+        )?;
+
+        Ok(1)
+    }
 }
@@ -1,17 +1,24 @@
 //! The `SQLite3` database driver.
 use std::panic::Location;
 use std::str::FromStr;
+use std::time::Duration;
 
 use bittorrent_primitives::info_hash::InfoHash;
+use ipnet::IpNet;
 use r2d2::Pool;
 use r2d2_sqlite::rusqlite::params;
 use r2d2_sqlite::rusqlite::types::Null;
 use r2d2_sqlite::SqliteConnectionManager;
+use torrust_tracker_primitives::info_hash_v2::InfoHashV2;
 use torrust_tracker_primitives::{DurationSinceUnixEpoch, PersistentTorrents};
 
 use super::driver::Driver;
+use super::migrations::{self, Migration};
 use super::{Database, Error};
+use crate::core::access::TorrentGroup;
 use crate::core::auth::{self, Key};
+use crate::core::client_filter::PeerIdPrefix;
+use crate::core::ratio::{KeyStats, Multipliers};
 
 const DRIVER: Driver = Driver::Sqlite3;
 
@@ -34,36 +41,7 @@ impl Database for Sqlite {
 
     /// Refer to [`databases::Database::create_database_tables`](crate::core::databases::Database::create_database_tables).
     fn create_database_tables(&self) -> Result<(), Error> {
-        let create_whitelist_table = "
-        CREATE TABLE IF NOT EXISTS whitelist (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            info_hash TEXT NOT NULL UNIQUE
-        );"
-        .to_string();
-
-        let create_torrents_table = "
-        CREATE TABLE IF NOT EXISTS torrents (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            info_hash TEXT NOT NULL UNIQUE,
-            completed INTEGER DEFAULT 0 NOT NULL
-        );"
-        .to_string();
-
-        let create_keys_table = "
-        CREATE TABLE IF NOT EXISTS keys (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            key TEXT NOT NULL UNIQUE,
-            valid_until INTEGER
-         );"
-        .to_string();
-
-        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
-
-        conn.execute(&create_whitelist_table, [])?;
-        conn.execute(&create_keys_table, [])?;
-        conn.execute(&create_torrents_table, [])?;
-
-        Ok(())
+        migrations::run(self, &DRIVER)
     }
 
     /// Refer to [`databases::Database::drop_database_tables`](crate::core::databases::Database::drop_database_tables).
@@ -76,15 +54,114 @@ impl Database for Sqlite {
         DROP TABLE torrents;"
             .to_string();
 
+        let drop_hybrid_torrents_table = "
+        DROP TABLE hybrid_torrents;"
+            .to_string();
+
         let drop_keys_table = "
         DROP TABLE keys;"
             .to_string();
 
+        let drop_key_stats_table = "
+        DROP TABLE key_stats;"
+            .to_string();
+
+        let drop_torrent_multipliers_table = "
+        DROP TABLE torrent_multipliers;"
+            .to_string();
+
+        let drop_key_torrents_table = "
+        DROP TABLE key_torrents;"
+            .to_string();
+
+        let drop_key_torrent_groups_table = "
+        DROP TABLE key_torrent_groups;"
+            .to_string();
+
+        let drop_torrent_groups_table = "
+        DROP TABLE torrent_groups;"
+            .to_string();
+
+        let drop_restricted_keys_table = "
+        DROP TABLE restricted_keys;"
+            .to_string();
+
+        let drop_client_prefixes_table = "
+        DROP TABLE client_prefixes;"
+            .to_string();
+
+        let drop_ip_blocklist_table = "
+        DROP TABLE ip_blocklist;"
+            .to_string();
+
+        let drop_blacklist_table = "
+        DROP TABLE blacklist;"
+            .to_string();
+
+        let drop_migrations_table = "
+        DROP TABLE schema_migrations;"
+            .to_string();
+
         let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
 
         conn.execute(&drop_whitelist_table, [])
             .and_then(|_| conn.execute(&drop_torrents_table, []))
-            .and_then(|_| conn.execute(&drop_keys_table, []))?;
+            .and_then(|_| conn.execute(&drop_hybrid_torrents_table, []))
+            .and_then(|_| conn.execute(&drop_keys_table, []))
+            .and_then(|_| conn.execute(&drop_key_stats_table, []))
+            .and_then(|_| conn.execute(&drop_torrent_multipliers_table, []))
+            .and_then(|_| conn.execute(&drop_key_torrents_table, []))
+            .and_then(|_| conn.execute(&drop_key_torrent_groups_table, []))
+            .and_then(|_| conn.execute(&drop_torrent_groups_table, []))
+            .and_then(|_| conn.execute(&drop_restricted_keys_table, []))
+            .and_then(|_| conn.execute(&drop_client_prefixes_table, []))
+            .and_then(|_| conn.execute(&drop_ip_blocklist_table, []))
+            .and_then(|_| conn.execute(&drop_blacklist_table, []))
+            .and_then(|_| conn.execute(&drop_migrations_table, []))?;
+
+        Ok(())
+    }
+
+    /// Refer to [`databases::Database::create_migrations_table`](crate::core::databases::Database::create_migrations_table).
+    fn create_migrations_table(&self) -> Result<(), Error> {
+        let create_migrations_table = "
+        CREATE TABLE IF NOT EXISTS schema_migrations (
+            version INTEGER PRIMARY KEY,
+            name TEXT NOT NULL
+        );"
+        .to_string();
+
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        conn.execute(&create_migrations_table, [])?;
+
+        Ok(())
+    }
+
+    /// Refer to [`databases::Database::load_applied_migrations`](crate::core::databases::Database::load_applied_migrations).
+    fn load_applied_migrations(&self) -> Result<Vec<i64>, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let mut stmt = conn.prepare("SELECT version FROM schema_migrations ORDER BY version")?;
+
+        let versions = stmt.query_map([], |row| row.get(0))?;
+
+        Ok(versions.collect::<Result<Vec<i64>, _>>()?)
+    }
+
+    /// Refer to [`databases::Database::apply_migration`](crate::core::databases::Database::apply_migration).
+    fn apply_migration(&self, migration: &Migration) -> Result<(), Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let tx = conn.transaction()?;
+
+        tx.execute_batch(migration.sql)?;
+        tx.execute(
+            "INSERT INTO schema_migrations (version, name) VALUES (?1, ?2)",
+            params![migration.version, migration.name],
+        )?;
+
+        tx.commit()?;
 
         Ok(())
     }
@@ -215,6 +292,142 @@ impl Database for Sqlite {
         }
     }
 
+    /// Refer to [`databases::Database::load_blacklist`](crate::core::databases::Database::load_blacklist).
+    fn load_blacklist(&self) -> Result<Vec<InfoHash>, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let mut stmt = conn.prepare("SELECT info_hash FROM blacklist")?;
+
+        let info_hash_iter = stmt.query_map([], |row| {
+            let info_hash: String = row.get(0)?;
+
+            Ok(InfoHash::from_str(&info_hash).unwrap())
+        })?;
+
+        let info_hashes: Vec<InfoHash> = info_hash_iter.filter_map(std::result::Result::ok).collect();
+
+        Ok(info_hashes)
+    }
+
+    /// Refer to [`databases::Database::get_info_hash_from_blacklist`](crate::core::databases::Database::get_info_hash_from_blacklist).
+    fn get_info_hash_from_blacklist(&self, info_hash: InfoHash) -> Result<Option<InfoHash>, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let mut stmt = conn.prepare("SELECT info_hash FROM blacklist WHERE info_hash = ?")?;
+
+        let mut rows = stmt.query([info_hash.to_hex_string()])?;
+
+        let query = rows.next()?;
+
+        Ok(query.map(|f| InfoHash::from_str(&f.get_unwrap::<_, String>(0)).unwrap()))
+    }
+
+    /// Refer to [`databases::Database::add_info_hash_to_blacklist`](crate::core::databases::Database::add_info_hash_to_blacklist).
+    fn add_info_hash_to_blacklist(&self, info_hash: InfoHash) -> Result<usize, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let insert = conn.execute("INSERT INTO blacklist (info_hash) VALUES (?)", [info_hash.to_string()])?;
+
+        if insert == 0 {
+            Err(Error::InsertFailed {
+                location: Location::caller(),
+                driver: DRIVER,
+            })
+        } else {
+            Ok(insert)
+        }
+    }
+
+    /// Refer to [`databases::Database::remove_info_hash_from_blacklist`](crate::core::databases::Database::remove_info_hash_from_blacklist).
+    fn remove_info_hash_from_blacklist(&self, info_hash: InfoHash) -> Result<usize, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let deleted = conn.execute("DELETE FROM blacklist WHERE info_hash = ?", [info_hash.to_string()])?;
+
+        if deleted == 1 {
+            // should only remove a single record.
+            Ok(deleted)
+        } else {
+            Err(Error::DeleteFailed {
+                location: Location::caller(),
+                error_code: deleted,
+                driver: DRIVER,
+            })
+        }
+    }
+
+    /// Refer to [`databases::Database::load_hybrid_torrents`](crate::core::databases::Database::load_hybrid_torrents).
+    fn load_hybrid_torrents(&self) -> Result<Vec<(InfoHash, InfoHashV2)>, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let mut stmt = conn.prepare("SELECT info_hash, info_hash_v2 FROM hybrid_torrents")?;
+
+        let hybrid_torrent_iter = stmt.query_map([], |row| {
+            let info_hash: String = row.get(0)?;
+            let info_hash_v2: String = row.get(1)?;
+
+            Ok((
+                InfoHash::from_str(&info_hash).unwrap(),
+                InfoHashV2::from_str(&info_hash_v2).unwrap(),
+            ))
+        })?;
+
+        Ok(hybrid_torrent_iter.filter_map(std::result::Result::ok).collect())
+    }
+
+    /// Refer to [`databases::Database::get_info_hash_from_hybrid_torrents`](crate::core::databases::Database::get_info_hash_from_hybrid_torrents).
+    fn get_info_hash_from_hybrid_torrents(&self, info_hash_v2: &InfoHashV2) -> Result<Option<InfoHash>, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let mut stmt = conn.prepare("SELECT info_hash FROM hybrid_torrents WHERE info_hash_v2 = ?")?;
+
+        let mut rows = stmt.query([info_hash_v2.to_hex_string()])?;
+
+        let query = rows.next()?;
+
+        Ok(query.map(|f| InfoHash::from_str(&f.get_unwrap::<_, String>(0)).unwrap()))
+    }
+
+    /// Refer to [`databases::Database::add_hybrid_torrent`](crate::core::databases::Database::add_hybrid_torrent).
+    fn add_hybrid_torrent(&self, info_hash: &InfoHash, info_hash_v2: &InfoHashV2) -> Result<usize, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let insert = conn.execute(
+            "INSERT INTO hybrid_torrents (info_hash, info_hash_v2) VALUES (?1, ?2)",
+            [info_hash.to_string(), info_hash_v2.to_string()],
+        )?;
+
+        if insert == 0 {
+            Err(Error::InsertFailed {
+                location: Location::caller(),
+                driver: DRIVER,
+            })
+        } else {
+            Ok(insert)
+        }
+    }
+
+    /// Refer to [`databases::Database::remove_hybrid_torrent`](crate::core::databases::Database::remove_hybrid_torrent).
+    fn remove_hybrid_torrent(&self, info_hash_v2: &InfoHashV2) -> Result<usize, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let deleted = conn.execute(
+            "DELETE FROM hybrid_torrents WHERE info_hash_v2 = ?",
+            [info_hash_v2.to_string()],
+        )?;
+
+        if deleted == 1 {
+            // should only remove a single record.
+            Ok(deleted)
+        } else {
+            Err(Error::DeleteFailed {
+                location: Location::caller(),
+                error_code: deleted,
+                driver: DRIVER,
+            })
+        }
+    }
+
     /// Refer to [`databases::Database::get_key_from_keys`](crate::core::databases::Database::get_key_from_keys).
     fn get_key_from_keys(&self, key: &Key) -> Result<Option<auth::PeerKey>, Error> {
         let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
@@ -284,4 +497,450 @@ impl Database for Sqlite {
             })
         }
     }
+
+    /// Refer to [`databases::Database::load_key_stats`](crate::core::databases::Database::load_key_stats).
+    fn load_key_stats(&self) -> Result<Vec<(Key, KeyStats)>, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let mut stmt = conn.prepare("SELECT key, uploaded, downloaded, seeding_time FROM key_stats")?;
+
+        let key_stats_iter = stmt.query_map([], |row| {
+            let key: String = row.get(0)?;
+            let uploaded: i64 = row.get(1)?;
+            let downloaded: i64 = row.get(2)?;
+            let seeding_time: i64 = row.get(3)?;
+
+            Ok((
+                key.parse::<Key>().unwrap(),
+                KeyStats {
+                    uploaded: uploaded.unsigned_abs(),
+                    downloaded: downloaded.unsigned_abs(),
+                    seeding_time: Duration::from_secs(seeding_time.unsigned_abs()),
+                },
+            ))
+        })?;
+
+        Ok(key_stats_iter.filter_map(std::result::Result::ok).collect())
+    }
+
+    /// Refer to [`databases::Database::save_key_stats`](crate::core::databases::Database::save_key_stats).
+    fn save_key_stats(&self, key: &Key, stats: &KeyStats) -> Result<(), Error> {
+        const COMMAND: &str = "INSERT INTO key_stats (key, uploaded, downloaded, seeding_time) VALUES (?1, ?2, ?3, ?4) \
+            ON CONFLICT(key) DO UPDATE SET uploaded = ?2, downloaded = ?3, seeding_time = ?4";
+
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let insert = conn.execute(
+            COMMAND,
+            params![
+                key.to_string(),
+                i64::try_from(stats.uploaded).unwrap_or(i64::MAX),
+                i64::try_from(stats.downloaded).unwrap_or(i64::MAX),
+                i64::try_from(stats.seeding_time.as_secs()).unwrap_or(i64::MAX)
+            ],
+        )?;
+
+        if insert == 0 {
+            Err(Error::InsertFailed {
+                location: Location::caller(),
+                driver: DRIVER,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Refer to [`databases::Database::remove_key_stats`](crate::core::databases::Database::remove_key_stats).
+    fn remove_key_stats(&self, key: &Key) -> Result<(), Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        conn.execute("DELETE FROM key_stats WHERE key = ?", [key.to_string()])?;
+
+        Ok(())
+    }
+
+    /// Refer to [`databases::Database::load_torrent_multipliers`](crate::core::databases::Database::load_torrent_multipliers).
+    fn load_torrent_multipliers(&self) -> Result<Vec<(InfoHash, Multipliers)>, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let mut stmt = conn.prepare("SELECT info_hash, upload_multiplier, download_multiplier FROM torrent_multipliers")?;
+
+        let multipliers_iter = stmt.query_map([], |row| {
+            let info_hash: String = row.get(0)?;
+
+            Ok((
+                InfoHash::from_str(&info_hash).unwrap(),
+                Multipliers {
+                    upload: row.get(1)?,
+                    download: row.get(2)?,
+                },
+            ))
+        })?;
+
+        Ok(multipliers_iter.filter_map(std::result::Result::ok).collect())
+    }
+
+    /// Refer to [`databases::Database::save_torrent_multipliers`](crate::core::databases::Database::save_torrent_multipliers).
+    fn save_torrent_multipliers(&self, info_hash: &InfoHash, multipliers: &Multipliers) -> Result<(), Error> {
+        const COMMAND: &str =
+            "INSERT INTO torrent_multipliers (info_hash, upload_multiplier, download_multiplier) VALUES (?1, ?2, ?3) \
+            ON CONFLICT(info_hash) DO UPDATE SET upload_multiplier = ?2, download_multiplier = ?3";
+
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let insert = conn.execute(
+            COMMAND,
+            params![info_hash.to_string(), multipliers.upload, multipliers.download],
+        )?;
+
+        if insert == 0 {
+            Err(Error::InsertFailed {
+                location: Location::caller(),
+                driver: DRIVER,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Refer to [`databases::Database::remove_torrent_multipliers`](crate::core::databases::Database::remove_torrent_multipliers).
+    fn remove_torrent_multipliers(&self, info_hash: &InfoHash) -> Result<usize, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let deleted = conn.execute("DELETE FROM torrent_multipliers WHERE info_hash = ?", [info_hash.to_string()])?;
+
+        if deleted == 1 {
+            // should only remove a single record.
+            Ok(deleted)
+        } else {
+            Err(Error::DeleteFailed {
+                location: Location::caller(),
+                error_code: deleted,
+                driver: DRIVER,
+            })
+        }
+    }
+
+    /// Refer to [`databases::Database::load_key_torrents`](crate::core::databases::Database::load_key_torrents).
+    fn load_key_torrents(&self) -> Result<Vec<(Key, InfoHash)>, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let mut stmt = conn.prepare("SELECT key, info_hash FROM key_torrents")?;
+
+        let key_torrent_iter = stmt.query_map([], |row| {
+            let key: String = row.get(0)?;
+            let info_hash: String = row.get(1)?;
+
+            Ok((key.parse::<Key>().unwrap(), info_hash.parse::<InfoHash>().unwrap()))
+        })?;
+
+        Ok(key_torrent_iter.filter_map(std::result::Result::ok).collect())
+    }
+
+    /// Refer to [`databases::Database::add_key_torrent`](crate::core::databases::Database::add_key_torrent).
+    fn add_key_torrent(&self, key: &Key, info_hash: &InfoHash) -> Result<usize, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let insert = conn.execute(
+            "INSERT INTO key_torrents (key, info_hash) VALUES (?1, ?2)",
+            [key.to_string(), info_hash.to_string()],
+        )?;
+
+        if insert == 0 {
+            Err(Error::InsertFailed {
+                location: Location::caller(),
+                driver: DRIVER,
+            })
+        } else {
+            Ok(insert)
+        }
+    }
+
+    /// Refer to [`databases::Database::remove_key_torrent`](crate::core::databases::Database::remove_key_torrent).
+    fn remove_key_torrent(&self, key: &Key, info_hash: &InfoHash) -> Result<usize, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let deleted = conn.execute(
+            "DELETE FROM key_torrents WHERE key = ?1 AND info_hash = ?2",
+            [key.to_string(), info_hash.to_string()],
+        )?;
+
+        if deleted == 1 {
+            // should only remove a single record.
+            Ok(deleted)
+        } else {
+            Err(Error::DeleteFailed {
+                location: Location::caller(),
+                error_code: deleted,
+                driver: DRIVER,
+            })
+        }
+    }
+
+    /// Refer to [`databases::Database::load_key_torrent_groups`](crate::core::databases::Database::load_key_torrent_groups).
+    fn load_key_torrent_groups(&self) -> Result<Vec<(Key, TorrentGroup)>, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let mut stmt = conn.prepare("SELECT key, torrent_group FROM key_torrent_groups")?;
+
+        let key_torrent_group_iter = stmt.query_map([], |row| {
+            let key: String = row.get(0)?;
+            let torrent_group: String = row.get(1)?;
+
+            Ok((key.parse::<Key>().unwrap(), torrent_group.parse::<TorrentGroup>().unwrap()))
+        })?;
+
+        Ok(key_torrent_group_iter.filter_map(std::result::Result::ok).collect())
+    }
+
+    /// Refer to [`databases::Database::add_key_torrent_group`](crate::core::databases::Database::add_key_torrent_group).
+    fn add_key_torrent_group(&self, key: &Key, group: &TorrentGroup) -> Result<usize, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let insert = conn.execute(
+            "INSERT INTO key_torrent_groups (key, torrent_group) VALUES (?1, ?2)",
+            [key.to_string(), group.to_string()],
+        )?;
+
+        if insert == 0 {
+            Err(Error::InsertFailed {
+                location: Location::caller(),
+                driver: DRIVER,
+            })
+        } else {
+            Ok(insert)
+        }
+    }
+
+    /// Refer to [`databases::Database::remove_key_torrent_group`](crate::core::databases::Database::remove_key_torrent_group).
+    fn remove_key_torrent_group(&self, key: &Key, group: &TorrentGroup) -> Result<usize, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let deleted = conn.execute(
+            "DELETE FROM key_torrent_groups WHERE key = ?1 AND torrent_group = ?2",
+            [key.to_string(), group.to_string()],
+        )?;
+
+        if deleted == 1 {
+            // should only remove a single record.
+            Ok(deleted)
+        } else {
+            Err(Error::DeleteFailed {
+                location: Location::caller(),
+                error_code: deleted,
+                driver: DRIVER,
+            })
+        }
+    }
+
+    /// Refer to [`databases::Database::load_restricted_keys`](crate::core::databases::Database::load_restricted_keys).
+    fn load_restricted_keys(&self) -> Result<Vec<Key>, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let mut stmt = conn.prepare("SELECT key FROM restricted_keys")?;
+
+        let key_iter = stmt.query_map([], |row| {
+            let key: String = row.get(0)?;
+
+            Ok(key.parse::<Key>().unwrap())
+        })?;
+
+        Ok(key_iter.filter_map(std::result::Result::ok).collect())
+    }
+
+    /// Refer to [`databases::Database::add_restricted_key`](crate::core::databases::Database::add_restricted_key).
+    fn add_restricted_key(&self, key: &Key) -> Result<usize, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let insert = conn.execute("INSERT INTO restricted_keys (key) VALUES (?)", [key.to_string()])?;
+
+        if insert == 0 {
+            Err(Error::InsertFailed {
+                location: Location::caller(),
+                driver: DRIVER,
+            })
+        } else {
+            Ok(insert)
+        }
+    }
+
+    /// Refer to [`databases::Database::remove_restricted_key`](crate::core::databases::Database::remove_restricted_key).
+    fn remove_restricted_key(&self, key: &Key) -> Result<usize, Error> {
+        let mut conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let tx = conn.transaction()?;
+
+        tx.execute("DELETE FROM key_torrents WHERE key = ?", [key.to_string()])?;
+        tx.execute("DELETE FROM key_torrent_groups WHERE key = ?", [key.to_string()])?;
+        let deleted = tx.execute("DELETE FROM restricted_keys WHERE key = ?", [key.to_string()])?;
+
+        if deleted == 1 {
+            // should only remove a single record.
+            tx.commit()?;
+            Ok(deleted)
+        } else {
+            Err(Error::DeleteFailed {
+                location: Location::caller(),
+                error_code: deleted,
+                driver: DRIVER,
+            })
+        }
+    }
+
+    /// Refer to [`databases::Database::load_torrent_groups`](crate::core::databases::Database::load_torrent_groups).
+    fn load_torrent_groups(&self) -> Result<Vec<(TorrentGroup, InfoHash)>, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let mut stmt = conn.prepare("SELECT torrent_group, info_hash FROM torrent_groups")?;
+
+        let torrent_group_iter = stmt.query_map([], |row| {
+            let torrent_group: String = row.get(0)?;
+            let info_hash: String = row.get(1)?;
+
+            Ok((
+                torrent_group.parse::<TorrentGroup>().unwrap(),
+                info_hash.parse::<InfoHash>().unwrap(),
+            ))
+        })?;
+
+        Ok(torrent_group_iter.filter_map(std::result::Result::ok).collect())
+    }
+
+    /// Refer to [`databases::Database::add_torrent_to_group`](crate::core::databases::Database::add_torrent_to_group).
+    fn add_torrent_to_group(&self, group: &TorrentGroup, info_hash: &InfoHash) -> Result<usize, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let insert = conn.execute(
+            "INSERT INTO torrent_groups (torrent_group, info_hash) VALUES (?1, ?2)",
+            [group.to_string(), info_hash.to_string()],
+        )?;
+
+        if insert == 0 {
+            Err(Error::InsertFailed {
+                location: Location::caller(),
+                driver: DRIVER,
+            })
+        } else {
+            Ok(insert)
+        }
+    }
+
+    /// Refer to [`databases::Database::remove_torrent_from_group`](crate::core::databases::Database::remove_torrent_from_group).
+    fn remove_torrent_from_group(&self, group: &TorrentGroup, info_hash: &InfoHash) -> Result<usize, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let deleted = conn.execute(
+            "DELETE FROM torrent_groups WHERE torrent_group = ?1 AND info_hash = ?2",
+            [group.to_string(), info_hash.to_string()],
+        )?;
+
+        if deleted == 1 {
+            // should only remove a single record.
+            Ok(deleted)
+        } else {
+            Err(Error::DeleteFailed {
+                location: Location::caller(),
+                error_code: deleted,
+                driver: DRIVER,
+            })
+        }
+    }
+
+    /// Refer to [`databases::Database::load_client_prefixes`](crate::core::databases::Database::load_client_prefixes).
+    fn load_client_prefixes(&self) -> Result<Vec<PeerIdPrefix>, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let mut stmt = conn.prepare("SELECT prefix FROM client_prefixes")?;
+
+        let prefix_iter = stmt.query_map([], |row| {
+            let prefix: String = row.get(0)?;
+
+            Ok(prefix.parse::<PeerIdPrefix>().unwrap())
+        })?;
+
+        Ok(prefix_iter.filter_map(std::result::Result::ok).collect())
+    }
+
+    /// Refer to [`databases::Database::add_client_prefix`](crate::core::databases::Database::add_client_prefix).
+    fn add_client_prefix(&self, prefix: &PeerIdPrefix) -> Result<usize, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let insert = conn.execute("INSERT INTO client_prefixes (prefix) VALUES (?1)", [prefix.to_string()])?;
+
+        if insert == 0 {
+            Err(Error::InsertFailed {
+                location: Location::caller(),
+                driver: DRIVER,
+            })
+        } else {
+            Ok(insert)
+        }
+    }
+
+    /// Refer to [`databases::Database::remove_client_prefix`](crate::core::databases::Database::remove_client_prefix).
+    fn remove_client_prefix(&self, prefix: &PeerIdPrefix) -> Result<usize, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let deleted = conn.execute("DELETE FROM client_prefixes WHERE prefix = ?1", [prefix.to_string()])?;
+
+        if deleted == 1 {
+            // should only remove a single record.
+            Ok(deleted)
+        } else {
+            Err(Error::DeleteFailed {
+                location: Location::caller(),
+                error_code: deleted,
+                driver: DRIVER,
+            })
+        }
+    }
+
+    /// Refer to [`databases::Database::load_blocked_networks`](crate::core::databases::Database::load_blocked_networks).
+    fn load_blocked_networks(&self) -> Result<Vec<IpNet>, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let mut stmt = conn.prepare("SELECT network FROM ip_blocklist")?;
+
+        let network_iter = stmt.query_map([], |row| {
+            let network: String = row.get(0)?;
+
+            Ok(network.parse::<IpNet>().unwrap())
+        })?;
+
+        Ok(network_iter.filter_map(std::result::Result::ok).collect())
+    }
+
+    /// Refer to [`databases::Database::add_blocked_network`](crate::core::databases::Database::add_blocked_network).
+    fn add_blocked_network(&self, network: &IpNet) -> Result<usize, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let insert = conn.execute("INSERT INTO ip_blocklist (network) VALUES (?1)", [network.to_string()])?;
+
+        if insert == 0 {
+            Err(Error::InsertFailed {
+                location: Location::caller(),
+                driver: DRIVER,
+            })
+        } else {
+            Ok(insert)
+        }
+    }
+
+    /// Refer to [`databases::Database::remove_blocked_network`](crate::core::databases::Database::remove_blocked_network).
+    fn remove_blocked_network(&self, network: &IpNet) -> Result<usize, Error> {
+        let conn = self.pool.get().map_err(|e| (e, DRIVER))?;
+
+        let deleted = conn.execute("DELETE FROM ip_blocklist WHERE network = ?1", [network.to_string()])?;
+
+        if deleted == 1 {
+            // should only remove a single record.
+            Ok(deleted)
+        } else {
+            Err(Error::DeleteFailed {
+                location: Location::caller(),
+                error_code: deleted,
+                driver: DRIVER,
+            })
+        }
+    }
 }
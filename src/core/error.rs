@@ -5,7 +5,14 @@
 //! `PeerKeyNotValid` | Authentication | The supplied key is not valid. It may not be registered or expired.
 //! `PeerNotAuthenticated` | Authentication | The peer did not provide the authentication key.
 //! `TorrentNotWhitelisted` | Authorization | The action cannot be perform on a not-whitelisted torrent (it only applies for trackers running in `listed` or `private_listed` modes).
+//! `TorrentBlacklisted` | Authorization | The action cannot be perform on a blacklisted torrent (it applies for all the tracker modes).
+//! `TorrentNotAllowedForKey` | Authorization | The authentication key has an allow-list and the torrent is not in it (it only applies for trackers running in `private` or `private_listed` modes).
+//! `ClientNotAllowed` | Client filter | The `peer_id` prefix of the client is denied, or it is not allowed (it only applies when the `client_filter` is `allow` or `deny`).
+//! `AnnounceTooFrequent` | Announce | The peer announced again earlier than the `interval_min` (it only applies when the `interval_min_action` is `reject`).
+//! `IpBlocked` | IP blocklist | The request comes from a network in the IP blocklist.
+//! `TrackerInMaintenance` | Availability | The tracker is in maintenance mode. The client should retry later.
 //!
+use std::net::IpAddr;
 use std::panic::Location;
 
 use bittorrent_primitives::info_hash::InfoHash;
@@ -33,6 +40,48 @@ pub enum Error {
         info_hash: InfoHash,
         location: &'static Location<'static>,
     },
+
+    #[error("The torrent: {info_hash}, is blacklisted: {reason}, {location}")]
+    TorrentBlacklisted {
+        info_hash: InfoHash,
+        reason: String,
+        location: &'static Location<'static>,
+    },
+
+    #[error("The torrent: {info_hash}, is not allowed for the key: {key}, {location}")]
+    TorrentNotAllowedForKey {
+        key: super::auth::Key,
+        info_hash: InfoHash,
+        location: &'static Location<'static>,
+    },
+
+    // Client filter errors
+    #[error("The client: {client}, is not allowed, {location}")]
+    ClientNotAllowed {
+        client: String,
+        location: &'static Location<'static>,
+    },
+
+    // Announce errors
+    #[error("The peer announced again before the min interval. Retry in {retry_in} seconds, {location}")]
+    AnnounceTooFrequent {
+        retry_in: u64,
+        location: &'static Location<'static>,
+    },
+
+    // IP blocklist errors
+    #[error("The IP: {ip}, is blocked, {location}")]
+    IpBlocked {
+        ip: IpAddr,
+        location: &'static Location<'static>,
+    },
+
+    // Availability errors
+    #[error("The tracker is in maintenance mode. Retry in {retry_in} minutes, {location}")]
+    TrackerInMaintenance {
+        retry_in: u32,
+        location: &'static Location<'static>,
+    },
 }
 
 /// Errors related to peers keys.
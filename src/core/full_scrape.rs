@@ -0,0 +1,96 @@
+//! The cached full scrape.
+//!
+//! The full scrape contains the swarm metadata of all the torrents. Walking
+//! the torrent repository for every request would be too expensive for big
+//! trackers, so the [`full_scrape`](crate::bootstrap::jobs::full_scrape) job
+//! generates the response periodically and the [`Tracker`](crate::core::Tracker)
+//! keeps the last one.
+//!
+//! Refer to the [`FullScrape`](torrust_tracker_configuration::FullScrape)
+//! configuration for the available options.
+use std::io::Write;
+
+use bytes::Bytes;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use torrust_tracker_primitives::DurationSinceUnixEpoch;
+
+/// The last generated full scrape.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FullScrape {
+    /// The bencoded scrape response.
+    pub body: Bytes,
+    /// The gzip-compressed `body`, when the full scrape is compressed. It's
+    /// sent to the clients that accept the `gzip` content encoding.
+    pub gzipped_body: Option<Bytes>,
+    /// The number of torrents in the full scrape.
+    pub torrents: usize,
+    /// When the full scrape was generated.
+    pub generated_at: DurationSinceUnixEpoch,
+}
+
+impl FullScrape {
+    /// It builds a full scrape from the bencoded scrape response, keeping a
+    /// compressed copy too when `gzip` is `true`.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the response cannot be compressed.
+    pub fn new(
+        bencoded: Vec<u8>,
+        gzip: bool,
+        torrents: usize,
+        generated_at: DurationSinceUnixEpoch,
+    ) -> Result<Self, std::io::Error> {
+        let gzipped_body = if gzip { Some(Bytes::from(compress(&bencoded)?)) } else { None };
+
+        Ok(Self {
+            body: Bytes::from(bencoded),
+            gzipped_body,
+            torrents,
+            generated_at,
+        })
+    }
+}
+
+fn compress(bytes: &[u8]) -> Result<Vec<u8>, std::io::Error> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(bytes)?;
+    encoder.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use flate2::read::GzDecoder;
+    use torrust_tracker_primitives::DurationSinceUnixEpoch;
+
+    use super::FullScrape;
+
+    fn sample_bencoded_scrape() -> Vec<u8> {
+        // cspell:disable-next-line
+        b"d5:filesd20:iiiiiiiiiiiiiiiiiiiid8:completei1e10:downloadedi2e10:incompletei3eeee".to_vec()
+    }
+
+    #[test]
+    fn it_should_keep_the_bencoded_response_as_it_is_when_it_is_not_compressed() {
+        let full_scrape = FullScrape::new(sample_bencoded_scrape(), false, 1, DurationSinceUnixEpoch::ZERO).unwrap();
+
+        assert_eq!(full_scrape.body, sample_bencoded_scrape());
+        assert_eq!(full_scrape.gzipped_body, None);
+    }
+
+    #[test]
+    fn it_should_keep_a_gzip_compressed_copy_of_the_bencoded_response() {
+        let full_scrape = FullScrape::new(sample_bencoded_scrape(), true, 1, DurationSinceUnixEpoch::ZERO).unwrap();
+
+        let mut decompressed = vec![];
+        GzDecoder::new(full_scrape.gzipped_body.unwrap().as_ref())
+            .read_to_end(&mut decompressed)
+            .unwrap();
+
+        assert_eq!(decompressed, sample_bencoded_scrape());
+        assert_eq!(full_scrape.body, sample_bencoded_scrape());
+    }
+}
@@ -0,0 +1,365 @@
+//! IP blocklist.
+//!
+//! The tracker can refuse the `connect`, `announce` and `scrape` requests
+//! coming from abusive networks. The blocked networks come from two sources:
+//!
+//! - The blocklist file configured in the `core.ip_blocklist` section. It's
+//!   loaded on startup and it can be reloaded with the tracker API.
+//! - The networks added with the tracker API. They are persisted in the
+//!   database.
+//!
+//! The UDP tracker drops the requests from blocked IPs without response, so
+//! that spoofed requests can't be used to reflect traffic. The IPs announced
+//! with the `ip` param are checked too, and blocked `ipv4` and `ipv6`
+//! addresses of dual-stack peers are ignored.
+//!
+//! The blocklist file contains one entry per line. Empty lines and lines
+//! starting with `#` are ignored. The entries can be:
+//!
+//! - Ranges in the P2P blocklist format (`name:start-end`), used by most
+//!   `BitTorrent` clients: `Some ISP:1.2.3.0-1.2.3.255`.
+//! - Networks in CIDR notation: `1.2.3.0/24` or `2001:db8::/32`.
+//! - Single IP addresses: `1.2.3.4`.
+//!
+//! P2P ranges are converted to the smallest list of CIDR networks covering
+//! them. All the networks are stored in a [`IpBlocklist`], a binary prefix
+//! tree for each IP version, so the cost of a lookup depends on the length of
+//! the address (32 or 128 bits) and not on the number of blocked networks.
+use std::io;
+use std::net::{IpAddr, Ipv4Addr};
+use std::panic::Location;
+use std::str::FromStr;
+
+use ipnet::{IpNet, Ipv4Subnets};
+use thiserror::Error;
+
+use crate::shared::net::canonical;
+
+/// Error returned when an IP blocklist can not be parsed.
+#[derive(Debug, Error, Clone)]
+#[error("Invalid IP blocklist entry in line {line_number}: {line}, {location}")]
+pub struct ParseIpBlocklistError {
+    line_number: usize,
+    line: String,
+    location: &'static Location<'static>,
+}
+
+/// Errors that can occur loading the IP blocklist file.
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("unable to read the IP blocklist file {path}: {source}")]
+    Io { path: String, source: io::Error },
+
+    #[error("unable to parse the IP blocklist file {path}: {source}")]
+    Parse { path: String, source: ParseIpBlocklistError },
+}
+
+/// It parses a blocklist with one entry per line. Refer to the
+/// [module documentation](crate::core::ip_blocklist) for the supported
+/// formats.
+///
+/// # Errors
+///
+/// Will return a `ParseIpBlocklistError` with the first line that can't be
+/// parsed.
+pub fn parse(text: &str) -> Result<Vec<IpNet>, ParseIpBlocklistError> {
+    let mut networks = vec![];
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        match parse_entry(line) {
+            Some(entry_networks) => networks.extend(entry_networks),
+            None => {
+                return Err(ParseIpBlocklistError {
+                    line_number: index + 1,
+                    line: line.to_string(),
+                    location: Location::caller(),
+                })
+            }
+        }
+    }
+
+    Ok(networks)
+}
+
+fn parse_entry(entry: &str) -> Option<Vec<IpNet>> {
+    if let Ok(network) = IpNet::from_str(entry) {
+        return Some(vec![network.trunc()]);
+    }
+
+    if let Ok(ip) = IpAddr::from_str(entry) {
+        return Some(vec![IpNet::from(ip)]);
+    }
+
+    // P2P format. The name can contain colons, but the range can't.
+    let (_name, range) = entry.rsplit_once(':')?;
+    let (start, end) = range.split_once('-')?;
+    let start = Ipv4Addr::from_str(start.trim()).ok()?;
+    let end = Ipv4Addr::from_str(end.trim()).ok()?;
+
+    if start > end {
+        return None;
+    }
+
+    Some(Ipv4Subnets::new(start, end, 0).map(IpNet::V4).collect())
+}
+
+/// A set of blocked networks, with fast lookups of IP addresses.
+#[derive(Debug, Clone, Default)]
+pub struct IpBlocklist {
+    v4: PrefixTree,
+    v6: PrefixTree,
+    len: usize,
+}
+
+impl IpBlocklist {
+    #[must_use]
+    pub fn new<'a>(networks: impl IntoIterator<Item = &'a IpNet>) -> Self {
+        let mut blocklist = Self::default();
+
+        for network in networks {
+            blocklist.insert(network);
+        }
+
+        blocklist
+    }
+
+    /// It adds a network to the blocklist.
+    pub fn insert(&mut self, network: &IpNet) {
+        match network {
+            IpNet::V4(network) => self
+                .v4
+                .insert(u128::from(u32::from(network.network())), network.prefix_len(), 32),
+            IpNet::V6(network) => self.v6.insert(u128::from(network.network()), network.prefix_len(), 128),
+        }
+
+        self.len += 1;
+    }
+
+    /// It returns `true` if the IP belongs to any of the blocked networks.
+    /// IPv4-mapped IPv6 addresses are checked as IPv4 addresses.
+    #[must_use]
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        match canonical(ip) {
+            IpAddr::V4(ip) => self.v4.contains(u128::from(u32::from(ip)), 32),
+            IpAddr::V6(ip) => self.v6.contains(u128::from(ip), 128),
+        }
+    }
+
+    /// The number of networks added to the blocklist.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// A binary prefix tree. Each level of the tree is a bit of the address, from
+/// the most significant one. The addresses are stored in the lower `width`
+/// bits of a `u128`.
+#[derive(Debug, Clone)]
+struct PrefixTree {
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Node {
+    /// The indexes of the children nodes. The root is never a child, so `0`
+    /// means there is no child.
+    children: [usize; 2],
+    /// Whether the prefix ending in this node is blocked.
+    blocked: bool,
+}
+
+impl Default for PrefixTree {
+    fn default() -> Self {
+        Self {
+            nodes: vec![Node::default()],
+        }
+    }
+}
+
+impl PrefixTree {
+    fn bit(address: u128, index: u8, width: u8) -> usize {
+        usize::from((address >> (width - 1 - index)) & 1 == 1)
+    }
+
+    fn insert(&mut self, address: u128, prefix_len: u8, width: u8) {
+        let mut current = 0;
+
+        for index in 0..prefix_len {
+            if self.nodes[current].blocked {
+                // A shorter prefix already covers the network.
+                return;
+            }
+
+            let bit = Self::bit(address, index, width);
+
+            if self.nodes[current].children[bit] == 0 {
+                self.nodes.push(Node::default());
+                self.nodes[current].children[bit] = self.nodes.len() - 1;
+            }
+
+            current = self.nodes[current].children[bit];
+        }
+
+        // The longer prefixes are covered by this one.
+        self.nodes[current] = Node {
+            children: [0, 0],
+            blocked: true,
+        };
+    }
+
+    fn contains(&self, address: u128, width: u8) -> bool {
+        let mut current = 0;
+
+        for index in 0..width {
+            if self.nodes[current].blocked {
+                return true;
+            }
+
+            current = self.nodes[current].children[Self::bit(address, index, width)];
+
+            if current == 0 {
+                return false;
+            }
+        }
+
+        self.nodes[current].blocked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod parsing_a_blocklist {
+        use std::str::FromStr;
+
+        use ipnet::IpNet;
+
+        use crate::core::ip_blocklist::parse;
+
+        fn networks(networks: &[&str]) -> Vec<IpNet> {
+            networks.iter().map(|network| IpNet::from_str(network).unwrap()).collect()
+        }
+
+        #[test]
+        fn should_parse_ranges_in_the_p2p_format() {
+            assert_eq!(parse("Some ISP:1.2.3.0-1.2.3.255").unwrap(), networks(&["1.2.3.0/24"]));
+        }
+
+        #[test]
+        fn should_split_the_p2p_ranges_that_are_not_a_cidr_network() {
+            assert_eq!(
+                parse("Some ISP:1.2.3.4-1.2.3.8").unwrap(),
+                networks(&["1.2.3.4/30", "1.2.3.8/32"])
+            );
+        }
+
+        #[test]
+        fn should_allow_colons_in_the_name_of_p2p_ranges() {
+            assert_eq!(parse("Bad: peers:1.2.3.4-1.2.3.4").unwrap(), networks(&["1.2.3.4/32"]));
+        }
+
+        #[test]
+        fn should_parse_cidr_networks_and_single_ips() {
+            assert_eq!(
+                parse("1.2.3.4/24\n2001:db8::/32\n5.6.7.8\n::1").unwrap(),
+                networks(&["1.2.3.0/24", "2001:db8::/32", "5.6.7.8/32", "::1/128"])
+            );
+        }
+
+        #[test]
+        fn should_ignore_empty_lines_and_comments() {
+            assert_eq!(parse("# Comment\n\n  1.2.3.4  \n").unwrap(), networks(&["1.2.3.4/32"]));
+        }
+
+        #[test]
+        fn should_fail_with_the_number_of_the_invalid_line() {
+            let error = parse("1.2.3.4\nnot an ip").unwrap_err();
+
+            assert!(error.to_string().contains("line 2: not an ip"));
+        }
+
+        #[test]
+        fn should_fail_with_reversed_p2p_ranges() {
+            assert!(parse("Some ISP:1.2.3.255-1.2.3.0").is_err());
+        }
+    }
+
+    mod the_ip_blocklist {
+        use std::net::IpAddr;
+        use std::str::FromStr;
+
+        use ipnet::IpNet;
+
+        use crate::core::ip_blocklist::IpBlocklist;
+
+        fn blocklist(networks: &[&str]) -> IpBlocklist {
+            let networks: Vec<IpNet> = networks.iter().map(|network| IpNet::from_str(network).unwrap()).collect();
+            IpBlocklist::new(&networks)
+        }
+
+        fn ip(ip: &str) -> IpAddr {
+            IpAddr::from_str(ip).unwrap()
+        }
+
+        #[test]
+        fn should_not_contain_any_ip_when_it_is_empty() {
+            let blocklist = IpBlocklist::default();
+
+            assert!(blocklist.is_empty());
+            assert!(!blocklist.contains(&ip("1.2.3.4")));
+            assert!(!blocklist.contains(&ip("::1")));
+        }
+
+        #[test]
+        fn should_contain_the_ips_in_the_blocked_networks() {
+            let blocklist = blocklist(&["1.2.3.0/24", "2001:db8::/32"]);
+
+            assert!(blocklist.contains(&ip("1.2.3.0")));
+            assert!(blocklist.contains(&ip("1.2.3.255")));
+            assert!(blocklist.contains(&ip("2001:db8::1")));
+        }
+
+        #[test]
+        fn should_not_contain_the_ips_outside_the_blocked_networks() {
+            let blocklist = blocklist(&["1.2.3.0/24", "2001:db8::/32"]);
+
+            assert!(!blocklist.contains(&ip("1.2.4.0")));
+            assert!(!blocklist.contains(&ip("2001:db9::1")));
+        }
+
+        #[test]
+        fn should_check_the_ipv4_mapped_ipv6_addresses_as_ipv4_addresses() {
+            let blocklist = blocklist(&["1.2.3.0/24"]);
+
+            assert!(blocklist.contains(&ip("::ffff:1.2.3.4")));
+        }
+
+        #[test]
+        fn should_contain_the_ips_in_a_network_that_covers_a_smaller_one_added_before() {
+            let blocklist = blocklist(&["1.2.3.4/32", "1.0.0.0/8"]);
+
+            assert!(blocklist.contains(&ip("1.200.0.1")));
+            assert_eq!(blocklist.len(), 2);
+        }
+
+        #[test]
+        fn should_contain_all_the_ips_when_it_blocks_the_whole_address_space() {
+            let blocklist = blocklist(&["0.0.0.0/0"]);
+
+            assert!(blocklist.contains(&ip("255.255.255.255")));
+            assert!(!blocklist.contains(&ip("::2")));
+        }
+    }
+}
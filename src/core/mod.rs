@@ -36,8 +36,11 @@
 //!
 //! - **Core tracker**: it handles the information about torrents and peers.
 //! - **Authentication**: it handles authentication keys which are used by HTTP trackers.
-//! - **Authorization**: it handles the permission to perform requests.
+//! - **Authorization**: it handles the permission to perform requests. Authentication keys can be restricted to some torrents. See [`access`].
+//! - **Client filter**: it can refuse announces from some client software, based on the `peer_id` prefix. See [`client_filter`].
+//! - **IP blocklist**: it can refuse the requests coming from some networks. See [`ip_blocklist`].
 //! - **Whitelist**: when the tracker runs in `listed` or `private_listed` mode all operations are restricted to whitelisted torrents.
+//! - **Blacklist**: in any mode, the operations on blacklisted torrents are refused.
 //! - **Statistics**: it keeps and serves the tracker statistics.
 //!
 //! Refer to [torrust-tracker-configuration](https://docs.rs/torrust-tracker-configuration) crate docs to get more information about the tracker settings.
@@ -70,11 +73,14 @@
 //! let peer = peer::Peer {
 //!     peer_id: PeerId(*b"-qB00000000000000001"),
 //!     peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8081),
+//!     alternative_addr: None,
+//!     key: None,
 //!     updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
 //!     uploaded: NumberOfBytes::new(0),
 //!     downloaded: NumberOfBytes::new(0),
 //!     left: NumberOfBytes::new(0),
 //!     event: AnnounceEvent::Completed,
+//!     upload_only: false,
 //! };
 //!
 //! let peer_ip = IpAddr::V4(Ipv4Addr::from_str("126.0.0.1").unwrap());
@@ -121,6 +127,11 @@
 //! }
 //! ```
 //!
+//! The `interval_min` is not enforced by default. With the `interval_min_action`
+//! option, the tracker can answer the announces that arrive too early (without
+//! an event change) with the current swarm data but without updating the peer,
+//! or reject them. Refer to [`Tracker::check_announce_interval`].
+//!
 //! Refer to `BitTorrent` BEPs and other sites for more information about the `announce` request:
 //!
 //! - [BEP 3. The `BitTorrent` Protocol Specification](https://www.bittorrent.org/beps/bep_0003.html)
@@ -147,6 +158,7 @@
 //!     pub complete: u32,   // The number of active peers that have completed downloading (seeders)
 //!     pub downloaded: u32, // The number of peers that have ever completed downloading
 //!     pub incomplete: u32, // The number of active peers that have not completed downloading (leechers)
+//!     pub downloaders: u32, // The number of leechers that are not partial seeds (BEP 21)
 //! }
 //! ```
 //!
@@ -230,6 +242,7 @@
 //!     pub complete: u32,   // The number of active peers that have completed downloading (seeders)
 //!     pub downloaded: u32, // The number of peers that have ever completed downloading
 //!     pub incomplete: u32, // The number of active peers that have not completed downloading (leechers)
+//!     pub downloaders: u32, // The number of leechers that are not partial seeds (BEP 21)
 //! }
 //!
 //! ```
@@ -248,7 +261,7 @@
 //! ```rust,no_run
 //! use std::net::SocketAddr;
 
-//! use aquatic_udp_protocol::PeerId;
+//! use aquatic_udp_protocol::{PeerId, PeerKey};
 //! use torrust_tracker_primitives::DurationSinceUnixEpoch;
 //! use aquatic_udp_protocol::NumberOfBytes;
 //! use aquatic_udp_protocol::AnnounceEvent;
@@ -256,11 +269,14 @@
 //! pub struct Peer {
 //!     pub peer_id: PeerId,                     // The peer ID
 //!     pub peer_addr: SocketAddr,           // Peer socket address
+//!     pub alternative_addr: Option<SocketAddr>, // Peer socket address for the other IP version (dual-stack peers)
+//!     pub key: Option<PeerKey>,            // The `key` sent by the client to prove its identity
 //!     pub updated: DurationSinceUnixEpoch, // Last time (timestamp) when the peer was updated
 //!     pub uploaded: NumberOfBytes,         // Number of bytes the peer has uploaded so far
 //!     pub downloaded: NumberOfBytes,       // Number of bytes the peer has downloaded so far   
 //!     pub left: NumberOfBytes,             // The number of bytes this peer still has to download
 //!     pub event: AnnounceEvent,            // The event the peer has announced: `started`, `completed`, `stopped`
+//!     pub upload_only: bool,               // Whether the peer is a partial seed (it announced the `paused` event)
 //! }
 //! ```
 //!
@@ -317,6 +333,7 @@
 //! threshold = "debug"
 //!
 //! [core]
+//! client_filter = "disabled"
 //! inactive_peer_cleanup_interval = 600
 //! listed = false
 //! private = false
@@ -325,11 +342,22 @@
 //! [core.announce_policy]
 //! interval = 120
 //! interval_min = 120
+//! interval_min_action = "disabled"
+//! numwant = 50
+//! numwant_max_http = 74
+//! numwant_max_udp = 74
+//!
+//! [core.blacklist]
+//! reason = "The torrent has been banned"
 //!
 //! [core.database]
 //! driver = "sqlite3"
 //! path = "./storage/tracker/lib/database/sqlite3.db"
 //!
+//! [core.maintenance]
+//! enabled = false
+//! retry_in = 10
+//!
 //! [core.net]
 //! on_reverse_proxy = false
 //! external_ip = "2.137.87.41"
@@ -338,6 +366,7 @@
 //! max_peer_timeout = 900
 //! persistent_torrent_completed_stat = false
 //! remove_peerless_torrents = true
+//! peer_selection = "random"
 //! ```
 //!
 //! Refer to the [`configuration` module documentation](https://docs.rs/torrust-tracker-configuration) to get more information about all options.
@@ -360,13 +389,16 @@
 //!
 //! # Authentication
 //!
-//! One of the core `Tracker` responsibilities is to create and keep authentication keys. Auth keys are used by HTTP trackers
+//! One of the core `Tracker` responsibilities is to create and keep authentication keys. Auth keys are used by HTTP and UDP trackers
 //! when the tracker is running in `private` or `private_listed` mode.
 //!
 //! HTTP tracker's clients need to obtain an auth key before starting requesting the tracker. Once the get one they have to include
 //! a `PATH` param with the key in all the HTTP requests. For example, when a peer wants to `announce` itself it has to use the
 //! HTTP tracker endpoint `GET /announce/:key`.
 //!
+//! UDP tracker's clients use the same path in the tracker URL (`udp://host:port/announce/:key`). The path is sent to the
+//! tracker in the [BEP 41](https://www.bittorrent.org/beps/bep_0041.html) `URLData` option of the `announce` request.
+//!
 //! The common way to obtain the keys is by using the tracker API directly or via other applications like the [Torrust Index](https://github.com/torrust/torrust-index).
 //!
 //! To learn more about tracker authentication, refer to the following modules :
@@ -422,9 +454,11 @@
 //! For example, the HTTP tracker would send an event like the following when it handles an `announce` request received from a peer using IP version 4.
 //!
 //! ```text
-//! tracker.send_stats_event(statistics::Event::Tcp4Announce).await
+//! tracker.send_stats_event(statistics::Event::http(server_socket_addr, &peer_ip, statistics::RequestKind::Announce)).await
 //! ```
 //!
+//! The event contains the socket address the server is bound to, so the tracker also keeps the counters for each server.
+//!
 //! Refer to [`statistics`] module for more information about statistics.
 //!
 //! # Persistence
@@ -432,50 +466,69 @@
 //! Right now the `Tracker` is responsible for storing and load data into and
 //! from the database, when persistence is enabled.
 //!
-//! There are three types of persistent object:
+//! There are these types of persistent object:
 //!
 //! - Authentication keys (only expiring keys)
 //! - Torrent whitelist
+//! - Torrent blacklist
+//! - Hybrid torrents
 //! - Torrent metrics
+//! - Transfer stats per authentication key and torrent multipliers
+//! - Torrent allow-lists per authentication key and torrent groups
 //!
 //! Refer to [`databases`] module for more information about persistence.
+pub mod access;
 pub mod auth;
+pub mod client_filter;
 pub mod databases;
 pub mod error;
+pub mod full_scrape;
+pub mod ip_blocklist;
+pub mod ratio;
 pub mod services;
 pub mod statistics;
 pub mod torrent;
 
 pub mod peer_tests;
 
-use std::cmp::max;
-use std::collections::HashMap;
-use std::net::IpAddr;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::net::{IpAddr, SocketAddr};
 use std::panic::Location;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
+use aquatic_udp_protocol::{AnnounceEvent, PeerId};
 use auth::PeerKey;
 use bittorrent_primitives::info_hash::InfoHash;
 use databases::driver::Driver;
 use derive_more::Constructor;
 use error::PeerKeyError;
+use ipnet::IpNet;
 use tokio::sync::mpsc::error::SendError;
 use torrust_tracker_clock::clock::Time;
 use torrust_tracker_configuration::v2_0_0::database;
-use torrust_tracker_configuration::{AnnouncePolicy, Core, TORRENT_PEERS_LIMIT};
+use torrust_tracker_configuration::{AnnouncePolicy, ClientFilter, Core, IntervalMinAction, TORRENT_PEERS_LIMIT};
 use torrust_tracker_located_error::Located;
+use torrust_tracker_primitives::info_hash_v2::InfoHashV2;
 use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
 use torrust_tracker_primitives::torrent_metrics::TorrentsMetrics;
 use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
-use torrust_tracker_torrent_repository::entry::EntrySync;
+use torrust_tracker_torrent_repository::entry::{EntrySync, PeerUpdate};
 use torrust_tracker_torrent_repository::repository::Repository;
 use tracing::instrument;
 
+use self::access::{AllowList, TorrentGroup};
 use self::auth::Key;
+use self::client_filter::PeerIdPrefix;
 use self::error::Error;
+use self::full_scrape::FullScrape;
+use self::ip_blocklist::IpBlocklist;
+use self::ratio::{KeyStats, Multipliers, Transfer};
+use self::torrent::snapshot::{self, Snapshot};
 use self::torrent::Torrents;
 use crate::core::databases::Database;
+use crate::shared::net::{canonical, is_bogon};
 use crate::CurrentClock;
 
 /// The domain layer tracker service.
@@ -501,9 +554,56 @@ pub struct Tracker {
     /// The list of allowed torrents. Only for listed trackers.
     whitelist: tokio::sync::RwLock<std::collections::HashSet<InfoHash>>,
 
+    /// The list of banned torrents, for any tracker mode.
+    blacklist: tokio::sync::RwLock<std::collections::HashSet<InfoHash>>,
+
+    /// Hybrid torrents. It maps the truncated V2 infohash of a torrent to its
+    /// V1 infohash, so that both resolve to the same swarm.
+    hybrid_torrents: std::sync::RwLock<HashMap<InfoHash, InfoHash>>,
+
+    /// The transfer stats of each authentication key. Only for private
+    /// trackers.
+    key_stats: std::sync::RwLock<HashMap<Key, KeyStats>>,
+
+    /// The keys whose transfer stats have changed since they were last saved
+    /// into the database.
+    unsaved_key_stats: std::sync::Mutex<std::collections::HashSet<Key>>,
+
+    /// The multipliers of the torrents with special transfer accounting, like
+    /// freeleech torrents.
+    torrent_multipliers: std::sync::RwLock<HashMap<InfoHash, Multipliers>>,
+
+    /// The torrents each authentication key is allowed to access. Keys
+    /// without allow-list can access all the torrents. Only for private
+    /// trackers.
+    key_allow_lists: std::sync::RwLock<HashMap<Key, AllowList>>,
+
+    /// The torrent groups that can be added to the key allow-lists.
+    torrent_groups: std::sync::RwLock<HashMap<TorrentGroup, HashSet<InfoHash>>>,
+
+    /// The `peer_id` prefixes used to filter the client software. Only when
+    /// the client filter is enabled.
+    client_prefixes: std::sync::RwLock<BTreeSet<PeerIdPrefix>>,
+
+    /// The networks blocked with the API. They are persisted in the database.
+    blocked_networks: std::sync::RwLock<BTreeSet<IpNet>>,
+
+    /// The networks in the IP blocklist file. Only when the IP blocklist file
+    /// is configured.
+    ip_blocklist_file_networks: std::sync::RwLock<Vec<IpNet>>,
+
+    /// All the blocked networks, from the file and the database.
+    ip_blocklist: std::sync::RwLock<IpBlocklist>,
+
     /// The in-memory torrents repository.
     torrents: Arc<Torrents>,
 
+    /// The last generated full scrape. Only when the full scrape is enabled.
+    full_scrape: std::sync::RwLock<Option<Arc<FullScrape>>>,
+
+    /// Whether the tracker is in maintenance mode or not.
+    maintenance: AtomicBool,
+
     /// Service to send stats events.
     stats_event_sender: Option<Box<dyn statistics::EventSender>>,
 
@@ -537,15 +637,28 @@ impl PeersWanted {
     pub fn only(limit: u32) -> Self {
         let amount: usize = match limit.try_into() {
             Ok(amount) => amount,
-            Err(_) => TORRENT_PEERS_LIMIT,
+            Err(_) => usize::MAX,
         };
 
         Self::Only { amount }
     }
 
-    fn limit(&self) -> usize {
+    /// It limits the amount of peers wanted to the `max` peers allowed in a
+    /// response. When the peer does not say how many peers it wants, it gets
+    /// the `default` amount, also limited to the `max`.
+    #[must_use]
+    pub fn at_most(&self, default: u32, max: u32) -> Self {
+        let wanted = match self {
+            PeersWanted::All => default,
+            PeersWanted::Only { amount } => u32::try_from(*amount).unwrap_or(u32::MAX),
+        };
+
+        Self::only(wanted.min(max))
+    }
+
+    fn limit(&self, default: u32) -> usize {
         match self {
-            PeersWanted::All => TORRENT_PEERS_LIMIT,
+            PeersWanted::All => usize::try_from(default).unwrap_or(usize::MAX),
             PeersWanted::Only { amount } => *amount,
         }
     }
@@ -630,6 +743,7 @@ impl Tracker {
         let driver = match config.database.driver {
             database::Driver::Sqlite3 => Driver::Sqlite3,
             database::Driver::MySQL => Driver::MySQL,
+            database::Driver::PostgreSQL => Driver::PostgreSQL,
         };
 
         let database = Arc::new(databases::driver::build(&driver, &config.database.path)?);
@@ -638,7 +752,20 @@ impl Tracker {
             config: config.clone(),
             keys: tokio::sync::RwLock::new(std::collections::HashMap::new()),
             whitelist: tokio::sync::RwLock::new(std::collections::HashSet::new()),
+            blacklist: tokio::sync::RwLock::new(std::collections::HashSet::new()),
+            hybrid_torrents: std::sync::RwLock::new(HashMap::new()),
+            key_stats: std::sync::RwLock::new(HashMap::new()),
+            unsaved_key_stats: std::sync::Mutex::new(std::collections::HashSet::new()),
+            torrent_multipliers: std::sync::RwLock::new(HashMap::new()),
+            key_allow_lists: std::sync::RwLock::new(HashMap::new()),
+            torrent_groups: std::sync::RwLock::new(HashMap::new()),
+            client_prefixes: std::sync::RwLock::new(BTreeSet::new()),
+            blocked_networks: std::sync::RwLock::new(BTreeSet::new()),
+            ip_blocklist_file_networks: std::sync::RwLock::new(Vec::new()),
+            ip_blocklist: std::sync::RwLock::new(IpBlocklist::default()),
             torrents: Arc::default(),
+            full_scrape: std::sync::RwLock::new(None),
+            maintenance: AtomicBool::new(config.maintenance.enabled),
             stats_event_sender,
             stats_repository,
             database,
@@ -670,6 +797,44 @@ impl Tracker {
         self.config.net.on_reverse_proxy
     }
 
+    /// Returns `true` if the tracker is in maintenance mode.
+    pub fn is_in_maintenance(&self) -> bool {
+        self.maintenance.load(Ordering::Relaxed)
+    }
+
+    /// It enables or disables the maintenance mode. While the tracker is in
+    /// maintenance mode it does not handle `announce` and `scrape` requests.
+    ///
+    /// # Context: Tracker
+    pub fn set_maintenance(&self, enabled: bool) {
+        self.maintenance.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Number of minutes the clients should wait before retrying when the
+    /// tracker is in maintenance mode.
+    pub fn get_maintenance_retry_in(&self) -> u32 {
+        self.config.maintenance.retry_in
+    }
+
+    /// It checks the tracker is not in maintenance mode.
+    ///
+    /// # Context: Tracker
+    ///
+    /// # Errors
+    ///
+    /// Will return a `Error::TrackerInMaintenance` with the minutes the client
+    /// should wait before retrying if the tracker is in maintenance mode.
+    pub fn check_availability(&self) -> Result<(), Error> {
+        if self.is_in_maintenance() {
+            return Err(Error::TrackerInMaintenance {
+                retry_in: self.get_maintenance_retry_in(),
+                location: Location::caller(),
+            });
+        }
+
+        Ok(())
+    }
+
     pub fn get_announce_policy(&self) -> AnnouncePolicy {
         self.config.announce_policy
     }
@@ -678,6 +843,62 @@ impl Tracker {
         self.config.net.external_ip
     }
 
+    /// It returns the `tracker id` for the HTTP announce responses, if it's
+    /// configured.
+    pub fn get_tracker_id(&self) -> Option<String> {
+        self.config.tracker_id.clone()
+    }
+
+    /// It returns the warning message for the HTTP announce responses of a
+    /// torrent. The message configured for the torrent has precedence over the
+    /// general one.
+    ///
+    /// # Context: Tracker
+    #[must_use]
+    pub fn get_warning_message(&self, info_hash: &InfoHash) -> Option<String> {
+        self.config
+            .torrent_warning_messages
+            .get(&info_hash.to_hex_string())
+            .or(self.config.warning_message.as_ref())
+            .cloned()
+    }
+
+    /// It returns the IP address of the peer making an announce request.
+    ///
+    /// The tracker uses the `remote_client_ip` of the request, unless the
+    /// client is in one of the [`trusted_networks`](torrust_tracker_configuration::v2_0_0::network::Network::trusted_networks)
+    /// and the `announced_ip` sent by the client is a public IP address.
+    ///
+    /// # Context: Tracker
+    #[must_use]
+    pub fn resolve_peer_ip(&self, remote_client_ip: &IpAddr, announced_ip: Option<IpAddr>) -> IpAddr {
+        match announced_ip {
+            Some(ip) if self.is_trusted(remote_client_ip) && !is_bogon(&ip) => ip,
+            _ => *remote_client_ip,
+        }
+    }
+
+    /// It returns the alternative address of a dual-stack peer, announced with
+    /// the BEP 07 `ipv4` or `ipv6` param for the IP version the client is not
+    /// using to connect.
+    ///
+    /// Any client can announce its address for the other IP version, but it's
+    /// ignored when it's not a public IP address or it's in the IP blocklist.
+    ///
+    /// # Context: Tracker
+    #[must_use]
+    pub fn resolve_alternative_addr(&self, announced_addr: Option<SocketAddr>) -> Option<SocketAddr> {
+        announced_addr.filter(|addr| !is_bogon(&addr.ip()) && self.check_ip(&addr.ip()).is_ok())
+    }
+
+    fn is_trusted(&self, remote_client_ip: &IpAddr) -> bool {
+        self.config
+            .net
+            .trusted_networks
+            .iter()
+            .any(|network| network.contains(&canonical(remote_client_ip)))
+    }
+
     /// It handles an announce request.
     ///
     /// # Context: Tracker
@@ -689,6 +910,35 @@ impl Tracker {
         peer: &mut peer::Peer,
         remote_client_ip: &IpAddr,
         peers_wanted: &PeersWanted,
+    ) -> AnnounceData {
+        self.announce_for(None, info_hash, peer, remote_client_ip, peers_wanted)
+    }
+
+    /// It handles an announce request authenticated with a key.
+    ///
+    /// The transfer since the previous announce of the peer is added to the
+    /// stats of the key, but only if the announce was applied to the swarm.
+    /// Refer to the [`ratio`] module for more information.
+    ///
+    /// # Context: Tracker
+    pub fn announce_with_key(
+        &self,
+        key: &Key,
+        info_hash: &InfoHash,
+        peer: &mut peer::Peer,
+        remote_client_ip: &IpAddr,
+        peers_wanted: &PeersWanted,
+    ) -> AnnounceData {
+        self.announce_for(Some(key), info_hash, peer, remote_client_ip, peers_wanted)
+    }
+
+    fn announce_for(
+        &self,
+        maybe_key: Option<&Key>,
+        info_hash: &InfoHash,
+        peer: &mut peer::Peer,
+        remote_client_ip: &IpAddr,
+        peers_wanted: &PeersWanted,
     ) -> AnnounceData {
         // code-review: maybe instead of mutating the peer we could just return
         // a tuple with the new peer and the announce data: (Peer, AnnounceData).
@@ -709,9 +959,97 @@ impl Tracker {
         peer.change_ip(&assign_ip_address_to_peer(remote_client_ip, self.config.net.external_ip));
         tracing::debug!("After: {peer:?}");
 
-        let stats = self.upsert_peer_and_get_stats(info_hash, peer);
+        let info_hash = self.resolve_info_hash(info_hash);
+
+        let (update, stats) = self.upsert_peer_and_get_update(&info_hash, peer);
+
+        if let (Some(key), PeerUpdate::Applied { previous, .. }) = (maybe_key, update) {
+            self.account_transfer(key, &info_hash, previous.as_deref(), peer);
+        }
+
+        let peers = self.get_peers_for(&info_hash, peer, peers_wanted.limit(self.config.announce_policy.numwant));
+
+        AnnounceData {
+            peers,
+            stats,
+            policy: self.get_announce_policy(),
+        }
+    }
+
+    /// It checks that the peer did not announce again earlier than the
+    /// [`interval_min`](torrust_tracker_configuration::AnnouncePolicy::interval_min)
+    /// in the announce policy.
+    ///
+    /// Announces with an event different from the event of the previous
+    /// announce (for example, `completed` or `stopped`) are always accepted,
+    /// and so are the `paused` announces of new partial seeds.
+    ///
+    /// The check is disabled when the
+    /// [`interval_min_action`](torrust_tracker_configuration::AnnouncePolicy::interval_min_action)
+    /// is `disabled`.
+    ///
+    /// # Context: Tracker
+    ///
+    /// # Errors
+    ///
+    /// Will return a `Error::AnnounceTooFrequent` with the seconds left until
+    /// the `interval_min` if the peer announced too early.
+    pub fn check_announce_interval(&self, info_hash: &InfoHash, peer: &peer::Peer) -> Result<(), Error> {
+        if self.config.announce_policy.interval_min_action == IntervalMinAction::Disabled {
+            return Ok(());
+        }
+
+        let Some(torrent_entry) = self.torrents.get(&self.resolve_info_hash(info_hash)) else {
+            return Ok(());
+        };
+
+        let Some(previous_announce) = torrent_entry.get_peer(&peer.peer_id) else {
+            return Ok(());
+        };
+
+        if peer.event != AnnounceEvent::None && peer.event != previous_announce.event {
+            return Ok(());
+        }
+
+        if peer.upload_only && !previous_announce.upload_only {
+            return Ok(());
+        }
+
+        let interval_min = Duration::from_secs(u64::from(self.config.announce_policy.interval_min));
+        let elapsed = peer.updated.saturating_sub(previous_announce.updated);
+
+        match interval_min.checked_sub(elapsed) {
+            Some(remaining) if !remaining.is_zero() => Err(Error::AnnounceTooFrequent {
+                retry_in: remaining.as_secs().max(1),
+                location: Location::caller(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// It returns the announce data for a peer without updating the swarm.
+    ///
+    /// It's used to answer the announces that arrive earlier than the
+    /// `interval_min` when the `interval_min_action` is `cached`. The client
+    /// gets the same data it would have gotten from a cached response.
+    ///
+    /// The IP of the peer is assigned like in [`Tracker::announce`].
+    ///
+    /// # Context: Tracker
+    pub fn get_announce_data(
+        &self,
+        info_hash: &InfoHash,
+        peer: &mut peer::Peer,
+        remote_client_ip: &IpAddr,
+        peers_wanted: &PeersWanted,
+    ) -> AnnounceData {
+        peer.change_ip(&assign_ip_address_to_peer(remote_client_ip, self.config.net.external_ip));
+
+        let info_hash = self.resolve_info_hash(info_hash);
+
+        let stats = self.get_swarm_metadata(&info_hash);
 
-        let peers = self.get_peers_for(info_hash, peer, peers_wanted.limit());
+        let peers = self.get_peers_for(&info_hash, peer, peers_wanted.limit(self.config.announce_policy.numwant));
 
         AnnounceData {
             peers,
@@ -725,12 +1063,15 @@ impl Tracker {
     /// # Context: Tracker
     ///
     /// BEP 48: [Tracker Protocol Extension: Scrape](https://www.bittorrent.org/beps/bep_0048.html).
-    pub async fn scrape(&self, info_hashes: &Vec<InfoHash>) -> ScrapeData {
+    ///
+    /// The `key` is the authentication key of the peer, if any. The torrents
+    /// the peer is not authorized to access get zeroed swarm metadata.
+    pub async fn scrape(&self, info_hashes: &Vec<InfoHash>, key: Option<&Key>) -> ScrapeData {
         let mut scrape_data = ScrapeData::empty();
 
         for info_hash in info_hashes {
-            let swarm_metadata = match self.authorize(info_hash).await {
-                Ok(()) => self.get_swarm_metadata(info_hash),
+            let swarm_metadata = match self.authorize(info_hash, key).await {
+                Ok(()) => self.get_swarm_metadata(&self.resolve_info_hash(info_hash)),
                 Err(_) => SwarmMetadata::zeroed(),
             };
             scrape_data.add_file(info_hash, swarm_metadata);
@@ -739,6 +1080,67 @@ impl Tracker {
         scrape_data
     }
 
+    /// Returns `true` if the tracker generates the full scrape.
+    pub fn is_full_scrape_enabled(&self) -> bool {
+        self.config.full_scrape.is_some()
+    }
+
+    /// It returns the scrape data for all the torrents in the tracker. In
+    /// `listed` mode it only includes the whitelisted torrents.
+    ///
+    /// It walks the whole torrent repository. Use the cached
+    /// [`get_full_scrape`](Self::get_full_scrape) to answer requests.
+    ///
+    /// # Context: Tracker
+    pub async fn get_full_scrape_data(&self) -> ScrapeData {
+        let mut scrape_data = ScrapeData::empty();
+
+        for (info_hash, torrent_entry) in self.torrents.get_paginated(None) {
+            if self.authorize(&info_hash, None).await.is_ok() {
+                scrape_data.add_file(&info_hash, torrent_entry.get_swarm_metadata());
+            }
+        }
+
+        scrape_data
+    }
+
+    /// It replaces the cached full scrape with the new bencoded scrape
+    /// response. A gzip-compressed copy is kept when it's enabled in the
+    /// [`FullScrape`](torrust_tracker_configuration::FullScrape) section. It
+    /// does nothing if the full scrape is not enabled.
+    ///
+    /// # Context: Tracker
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the response cannot be compressed.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the full scrape lock is poisoned.
+    pub fn update_full_scrape(&self, bencoded: Vec<u8>, torrents: usize) -> Result<(), std::io::Error> {
+        let Some(config) = &self.config.full_scrape else {
+            return Ok(());
+        };
+
+        let full_scrape = FullScrape::new(bencoded, config.gzip, torrents, CurrentClock::now())?;
+
+        *self.full_scrape.write().expect("it should get a write lock") = Some(Arc::new(full_scrape));
+
+        Ok(())
+    }
+
+    /// It returns the last generated full scrape, if any.
+    ///
+    /// # Context: Tracker
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the full scrape lock is poisoned.
+    pub fn get_full_scrape(&self) -> Option<Arc<FullScrape>> {
+        self.full_scrape.read().expect("it should get a read lock").clone()
+    }
+
     /// It returns the data for a `scrape` response.
     fn get_swarm_metadata(&self, info_hash: &InfoHash) -> SwarmMetadata {
         match self.torrents.get(info_hash) {
@@ -767,11 +1169,12 @@ impl Tracker {
     ///
     /// Get torrent peers for a given torrent and client.
     ///
-    /// It filters out the client making the request.
+    /// It filters out the client making the request. The peers are selected
+    /// with the strategy in the Tracker Policy.
     fn get_peers_for(&self, info_hash: &InfoHash, peer: &peer::Peer, limit: usize) -> Vec<Arc<peer::Peer>> {
         match self.torrents.get(info_hash) {
             None => vec![],
-            Some(entry) => entry.get_peers_for_client(&peer.peer_addr, Some(max(limit, TORRENT_PEERS_LIMIT))),
+            Some(entry) => entry.get_peers_for_client(peer, Some(limit), &self.config.tracker_policy),
         }
     }
 
@@ -779,7 +1182,7 @@ impl Tracker {
     ///
     /// Get torrent peers for a given torrent.
     pub fn get_torrent_peers(&self, info_hash: &InfoHash) -> Vec<Arc<peer::Peer>> {
-        match self.torrents.get(info_hash) {
+        match self.torrents.get(&self.resolve_info_hash(info_hash)) {
             None => vec![],
             Some(entry) => entry.get_peers(Some(TORRENT_PEERS_LIMIT)),
         }
@@ -791,12 +1194,18 @@ impl Tracker {
     ///
     /// # Context: Tracker
     pub fn upsert_peer_and_get_stats(&self, info_hash: &InfoHash, peer: &peer::Peer) -> SwarmMetadata {
+        let (_, swarm_metadata) = self.upsert_peer_and_get_update(info_hash, peer);
+
+        swarm_metadata
+    }
+
+    fn upsert_peer_and_get_update(&self, info_hash: &InfoHash, peer: &peer::Peer) -> (PeerUpdate, SwarmMetadata) {
         let swarm_metadata_before = match self.torrents.get_swarm_metadata(info_hash) {
             Some(swarm_metadata) => swarm_metadata,
             None => SwarmMetadata::zeroed(),
         };
 
-        self.torrents.upsert_peer(info_hash, peer);
+        let update = self.torrents.upsert_peer(info_hash, peer, &self.config.tracker_policy);
 
         let swarm_metadata_after = match self.torrents.get_swarm_metadata(info_hash) {
             Some(swarm_metadata) => swarm_metadata,
@@ -807,7 +1216,7 @@ impl Tracker {
             self.persist_stats(info_hash, &swarm_metadata_after);
         }
 
-        swarm_metadata_after
+        (update, swarm_metadata_after)
     }
 
     /// It stores the torrents stats into the database (if persistency is enabled).
@@ -847,6 +1256,51 @@ impl Tracker {
         }
     }
 
+    /// It saves a snapshot of the in-memory swarms into the file configured
+    /// in the [`SwarmSnapshot`](torrust_tracker_configuration::SwarmSnapshot)
+    /// section. It does nothing if snapshots are not enabled.
+    ///
+    /// # Context: Tracker
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the snapshot file cannot be written.
+    pub fn save_swarm_snapshot(&self) -> Result<(), snapshot::Error> {
+        let Some(swarm_snapshot) = &self.config.swarm_snapshot else {
+            return Ok(());
+        };
+
+        Snapshot::take(&self.torrents).save(std::path::Path::new(&swarm_snapshot.path))
+    }
+
+    /// It loads the swarms from the snapshot file configured in the
+    /// [`SwarmSnapshot`](torrust_tracker_configuration::SwarmSnapshot) section.
+    /// Peers that have not been updated for more than `max_peer_timeout`
+    /// seconds are discarded.
+    ///
+    /// It does nothing if snapshots are not enabled or the file does not exist.
+    ///
+    /// # Context: Tracker
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the snapshot file cannot be read or decoded.
+    pub fn load_swarm_snapshot(&self) -> Result<(), snapshot::Error> {
+        let Some(swarm_snapshot) = &self.config.swarm_snapshot else {
+            return Ok(());
+        };
+
+        if let Some(snapshot) = Snapshot::load(std::path::Path::new(&swarm_snapshot.path))? {
+            let current_cutoff =
+                CurrentClock::now_sub(&Duration::from_secs(u64::from(self.config.tracker_policy.max_peer_timeout)))
+                    .unwrap_or_default();
+
+            snapshot.restore(&self.torrents, current_cutoff, &self.config.tracker_policy);
+        }
+
+        Ok(())
+    }
+
     /// It authenticates the peer `key` against the `Tracker` authentication
     /// key list.
     ///
@@ -1032,6 +1486,7 @@ impl Tracker {
     pub async fn remove_auth_key(&self, key: &Key) -> Result<(), databases::error::Error> {
         self.database.remove_key_from_keys(key)?;
         self.keys.write().await.remove(key);
+        self.remove_key_stats(key)?;
         Ok(())
     }
 
@@ -1084,29 +1539,55 @@ impl Tracker {
         Ok(())
     }
 
-    /// Right now, there is only authorization when the `Tracker` runs in
-    /// `listed` or `private_listed` modes.
+    /// It checks whether the torrent is blacklisted, whether it's
+    /// whitelisted, when the `Tracker` runs in `listed` or `private_listed`
+    /// modes, and whether the authentication `key` of the peer, if any, is
+    /// allowed to access the torrent.
     ///
     /// # Context: Authorization
     ///
     /// # Errors
     ///
-    /// Will return an error if the tracker is running in `listed` mode
-    /// and the infohash is not whitelisted.
-    #[instrument(skip(self, info_hash), err)]
-    pub async fn authorize(&self, info_hash: &InfoHash) -> Result<(), Error> {
-        if !self.is_listed() {
-            return Ok(());
+    /// Will return an error if the torrent is blacklisted, if the tracker is
+    /// running in `listed` mode and the infohash is not whitelisted, or if
+    /// the torrent is not in the [`AllowList`] of the key.
+    ///
+    /// The truncated V2 infohash of a hybrid torrent is authorized when the
+    /// V1 infohash is whitelisted or allowed, and it's refused when the V1
+    /// infohash is blacklisted.
+    #[instrument(skip(self, info_hash, key), err)]
+    pub async fn authorize(&self, info_hash: &InfoHash, key: Option<&Key>) -> Result<(), Error> {
+        if self.is_info_hash_blacklisted(info_hash).await
+            || self.is_info_hash_blacklisted(&self.resolve_info_hash(info_hash)).await
+        {
+            return Err(Error::TorrentBlacklisted {
+                info_hash: *info_hash,
+                reason: self.config.blacklist.reason.clone(),
+                location: Location::caller(),
+            });
         }
 
-        if self.is_info_hash_whitelisted(info_hash).await {
-            return Ok(());
+        if self.is_listed()
+            && !self.is_info_hash_whitelisted(info_hash).await
+            && !self.is_info_hash_whitelisted(&self.resolve_info_hash(info_hash)).await
+        {
+            return Err(Error::TorrentNotWhitelisted {
+                info_hash: *info_hash,
+                location: Location::caller(),
+            });
         }
 
-        Err(Error::TorrentNotWhitelisted {
-            info_hash: *info_hash,
-            location: Location::caller(),
-        })
+        if let Some(key) = key {
+            if !self.is_torrent_allowed_for_key(key, info_hash) {
+                return Err(Error::TorrentNotAllowedForKey {
+                    key: key.clone(),
+                    info_hash: *info_hash,
+                    location: Location::caller(),
+                });
+            }
+        }
+
+        Ok(())
     }
 
     /// It adds a torrent to the whitelist.
@@ -1207,596 +1688,2653 @@ impl Tracker {
         Ok(())
     }
 
-    /// It return the `Tracker` [`statistics::Metrics`].
+    /// It adds a torrent to the blacklist.
     ///
-    /// # Context: Statistics
-    pub async fn get_stats(&self) -> tokio::sync::RwLockReadGuard<'_, statistics::Metrics> {
-        self.stats_repository.get_stats().await
+    /// # Context: Blacklist
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to add the `info_hash` into the blacklist database.
+    pub async fn add_torrent_to_blacklist(&self, info_hash: &InfoHash) -> Result<(), databases::error::Error> {
+        self.add_torrent_to_database_blacklist(info_hash)?;
+        self.add_torrent_to_memory_blacklist(info_hash).await;
+        Ok(())
     }
 
-    /// It allows to send a statistic events which eventually will be used to update [`statistics::Metrics`].
-    ///
-    /// # Context: Statistics
-    pub async fn send_stats_event(&self, event: statistics::Event) -> Option<Result<(), SendError<statistics::Event>>> {
-        match &self.stats_event_sender {
-            None => None,
-            Some(stats_event_sender) => stats_event_sender.send_event(event).await,
+    /// It adds a torrent to the blacklist if it has not been blacklisted previously
+    fn add_torrent_to_database_blacklist(&self, info_hash: &InfoHash) -> Result<(), databases::error::Error> {
+        let is_blacklisted = self.database.is_info_hash_blacklisted(*info_hash)?;
+
+        if is_blacklisted {
+            return Ok(());
         }
+
+        self.database.add_info_hash_to_blacklist(*info_hash)?;
+
+        Ok(())
     }
 
-    /// It drops the database tables.
+    pub async fn add_torrent_to_memory_blacklist(&self, info_hash: &InfoHash) -> bool {
+        self.blacklist.write().await.insert(*info_hash)
+    }
+
+    /// It removes a torrent from the blacklist.
+    ///
+    /// # Context: Blacklist
     ///
     /// # Errors
     ///
-    /// Will return `Err` if unable to drop tables.
-    pub fn drop_database_tables(&self) -> Result<(), databases::error::Error> {
-        // todo: this is only used for testing. WE have to pass the database
-        // reference directly to the tests instead of via the tracker.
-        self.database.drop_database_tables()
+    /// Will return a `database::Error` if unable to remove the `info_hash` from the blacklist database.
+    pub async fn remove_torrent_from_blacklist(&self, info_hash: &InfoHash) -> Result<(), databases::error::Error> {
+        self.remove_torrent_from_database_blacklist(info_hash)?;
+        self.remove_torrent_from_memory_blacklist(info_hash).await;
+        Ok(())
     }
-}
 
-#[must_use]
-fn assign_ip_address_to_peer(remote_client_ip: &IpAddr, tracker_external_ip: Option<IpAddr>) -> IpAddr {
-    if let Some(host_ip) = tracker_external_ip.filter(|_| remote_client_ip.is_loopback()) {
-        host_ip
-    } else {
-        *remote_client_ip
-    }
-}
+    /// It removes a torrent from the blacklist in the database.
+    ///
+    /// # Context: Blacklist
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to remove the `info_hash` from the blacklist database.
+    pub fn remove_torrent_from_database_blacklist(&self, info_hash: &InfoHash) -> Result<(), databases::error::Error> {
+        let is_blacklisted = self.database.is_info_hash_blacklisted(*info_hash)?;
 
-#[cfg(test)]
+        if !is_blacklisted {
+            return Ok(());
+        }
+
+        self.database.remove_info_hash_from_blacklist(*info_hash)?;
+
+        Ok(())
+    }
+
+    /// It removes a torrent from the blacklist in memory.
+    ///
+    /// # Context: Blacklist
+    pub async fn remove_torrent_from_memory_blacklist(&self, info_hash: &InfoHash) -> bool {
+        self.blacklist.write().await.remove(info_hash)
+    }
+
+    /// It checks if a torrent is blacklisted.
+    ///
+    /// # Context: Blacklist
+    pub async fn is_info_hash_blacklisted(&self, info_hash: &InfoHash) -> bool {
+        self.blacklist.read().await.contains(info_hash)
+    }
+
+    /// It loads the blacklist from the database.
+    ///
+    /// # Context: Blacklist
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to load the list blacklisted `info_hash`s from the database.
+    pub async fn load_blacklist_from_database(&self) -> Result<(), databases::error::Error> {
+        let blacklisted_torrents_from_database = self.database.load_blacklist()?;
+        let mut blacklist = self.blacklist.write().await;
+
+        blacklist.clear();
+
+        for info_hash in blacklisted_torrents_from_database {
+            let _: bool = blacklist.insert(info_hash);
+        }
+
+        Ok(())
+    }
+
+    /// It returns the infohash used to store the swarm of a torrent.
+    ///
+    /// Peers of a hybrid torrent can announce the V1 infohash or the truncated
+    /// V2 infohash. The truncated V2 infohash of a known hybrid torrent
+    /// resolves to the V1 infohash. Any other infohash resolves to itself.
+    ///
+    /// # Context: Hybrid torrents
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the hybrid torrents lock is poisoned.
+    pub fn resolve_info_hash(&self, info_hash: &InfoHash) -> InfoHash {
+        self.hybrid_torrents
+            .read()
+            .expect("it should get a read lock")
+            .get(info_hash)
+            .copied()
+            .unwrap_or(*info_hash)
+    }
+
+    /// It adds a hybrid torrent. From now on, peers announcing the truncated
+    /// V2 infohash join the swarm of the V1 infohash.
+    ///
+    /// Peers that announced the truncated V2 infohash before the torrent was
+    /// added stay in their own swarm until they are removed for inactivity.
+    ///
+    /// # Context: Hybrid torrents
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to add the hybrid torrent into the database.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the hybrid torrents lock is poisoned.
+    pub fn add_hybrid_torrent(&self, info_hash: &InfoHash, info_hash_v2: &InfoHashV2) -> Result<(), databases::error::Error> {
+        self.add_hybrid_torrent_to_database(info_hash, info_hash_v2)?;
+
+        self.hybrid_torrents
+            .write()
+            .expect("it should get a write lock")
+            .insert(info_hash_v2.truncated(), *info_hash);
+
+        Ok(())
+    }
+
+    /// It adds a hybrid torrent to the database. It replaces the V1 infohash
+    /// if the V2 infohash was already stored.
+    fn add_hybrid_torrent_to_database(
+        &self,
+        info_hash: &InfoHash,
+        info_hash_v2: &InfoHashV2,
+    ) -> Result<(), databases::error::Error> {
+        match self.database.get_info_hash_from_hybrid_torrents(info_hash_v2)? {
+            Some(stored_info_hash) if stored_info_hash == *info_hash => return Ok(()),
+            Some(_) => {
+                self.database.remove_hybrid_torrent(info_hash_v2)?;
+            }
+            None => {}
+        }
+
+        self.database.add_hybrid_torrent(info_hash, info_hash_v2)?;
+
+        Ok(())
+    }
+
+    /// It removes a hybrid torrent.
+    ///
+    /// # Context: Hybrid torrents
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to remove the hybrid torrent from the database.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the hybrid torrents lock is poisoned.
+    pub fn remove_hybrid_torrent(&self, info_hash_v2: &InfoHashV2) -> Result<(), databases::error::Error> {
+        if self.database.get_info_hash_from_hybrid_torrents(info_hash_v2)?.is_some() {
+            self.database.remove_hybrid_torrent(info_hash_v2)?;
+        }
+
+        self.hybrid_torrents
+            .write()
+            .expect("it should get a write lock")
+            .remove(&info_hash_v2.truncated());
+
+        Ok(())
+    }
+
+    /// It loads the hybrid torrents from the database.
+    ///
+    /// # Context: Hybrid torrents
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to load the hybrid torrents from the database.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the hybrid torrents lock is poisoned.
+    pub fn load_hybrid_torrents_from_database(&self) -> Result<(), databases::error::Error> {
+        let hybrid_torrents_from_database = self.database.load_hybrid_torrents()?;
+        let mut hybrid_torrents = self.hybrid_torrents.write().expect("it should get a write lock");
+
+        hybrid_torrents.clear();
+
+        for (info_hash, info_hash_v2) in hybrid_torrents_from_database {
+            hybrid_torrents.insert(info_hash_v2.truncated(), info_hash);
+        }
+
+        Ok(())
+    }
+
+    /// It adds the transfer between the `previous` announce of the peer and
+    /// the current one to the stats of the authentication key.
+    ///
+    /// The stats are only updated in memory. They are saved into the database
+    /// by [`save_unsaved_key_stats`](Self::save_unsaved_key_stats). Refer to
+    /// the [`ratio`] module for more information.
+    ///
+    /// # Context: Transfer accounting
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the key stats or the torrent multipliers locks are poisoned.
+    pub fn account_transfer(&self, key: &Key, info_hash: &InfoHash, previous: Option<&peer::Peer>, peer: &peer::Peer) {
+        let info_hash = self.resolve_info_hash(info_hash);
+
+        let transfer = Transfer::between(previous, peer, &self.get_torrent_multipliers(&info_hash));
+
+        self.key_stats
+            .write()
+            .expect("it should get a write lock")
+            .entry(key.clone())
+            .or_default()
+            .add(&transfer);
+
+        self.unsaved_key_stats
+            .lock()
+            .expect("it should lock the unsaved key stats")
+            .insert(key.clone());
+    }
+
+    /// It removes the transfer stats of an authentication key, in memory and
+    /// in the database.
+    ///
+    /// # Context: Transfer accounting
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to remove the key stats from the database.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the key stats locks are poisoned.
+    fn remove_key_stats(&self, key: &Key) -> Result<(), databases::error::Error> {
+        self.unsaved_key_stats
+            .lock()
+            .expect("it should lock the unsaved key stats")
+            .remove(key);
+        self.key_stats.write().expect("it should get a write lock").remove(key);
+
+        self.database.remove_key_stats(key)
+    }
+
+    /// It saves into the database the transfer stats of the keys that have
+    /// changed since they were last saved.
+    ///
+    /// # Context: Transfer accounting
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to save the key stats. The
+    /// stats that could not be saved are kept to be saved the next time.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the key stats locks are poisoned.
+    pub fn save_unsaved_key_stats(&self) -> Result<(), databases::error::Error> {
+        let keys = std::mem::take(&mut *self.unsaved_key_stats.lock().expect("it should lock the unsaved key stats"));
+
+        let mut keys = keys.into_iter();
+
+        while let Some(key) = keys.next() {
+            let stats = self.get_key_stats(&key);
+
+            if let Err(err) = self.database.save_key_stats(&key, &stats) {
+                self.unsaved_key_stats
+                    .lock()
+                    .expect("it should lock the unsaved key stats")
+                    .extend(std::iter::once(key).chain(keys));
+
+                return Err(err);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// It returns the transfer stats of an authentication key. They are zero
+    /// if the key has not announced anything yet.
+    ///
+    /// # Context: Transfer accounting
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the key stats lock is poisoned.
+    pub fn get_key_stats(&self, key: &Key) -> KeyStats {
+        self.key_stats
+            .read()
+            .expect("it should get a read lock")
+            .get(key)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// It returns the transfer stats of all the authentication keys that
+    /// have announced something.
+    ///
+    /// # Context: Transfer accounting
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the key stats lock is poisoned.
+    pub fn get_all_key_stats(&self) -> Vec<(Key, KeyStats)> {
+        self.key_stats
+            .read()
+            .expect("it should get a read lock")
+            .iter()
+            .map(|(key, stats)| (key.clone(), *stats))
+            .collect()
+    }
+
+    /// It loads the transfer stats of the authentication keys from the
+    /// database.
+    ///
+    /// # Context: Transfer accounting
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to load the key stats from the database.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the key stats lock is poisoned.
+    pub fn load_key_stats_from_database(&self) -> Result<(), databases::error::Error> {
+        let key_stats_from_database = self.database.load_key_stats()?;
+        let mut key_stats = self.key_stats.write().expect("it should get a write lock");
+
+        key_stats.clear();
+        key_stats.extend(key_stats_from_database);
+
+        Ok(())
+    }
+
+    /// It returns the multipliers of a torrent. Torrents without special
+    /// multipliers count the transfers as they are.
+    ///
+    /// # Context: Transfer accounting
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the torrent multipliers lock is poisoned.
+    pub fn get_torrent_multipliers(&self, info_hash: &InfoHash) -> Multipliers {
+        self.torrent_multipliers
+            .read()
+            .expect("it should get a read lock")
+            .get(info_hash)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// It sets the multipliers of a torrent, for example to make it
+    /// freeleech.
+    ///
+    /// # Context: Transfer accounting
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to save the multipliers into the database.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the torrent multipliers lock is poisoned.
+    pub fn set_torrent_multipliers(
+        &self,
+        info_hash: &InfoHash,
+        multipliers: &Multipliers,
+    ) -> Result<(), databases::error::Error> {
+        self.database.save_torrent_multipliers(info_hash, multipliers)?;
+
+        self.torrent_multipliers
+            .write()
+            .expect("it should get a write lock")
+            .insert(*info_hash, *multipliers);
+
+        Ok(())
+    }
+
+    /// It removes the multipliers of a torrent, so that its transfers count
+    /// as they are again.
+    ///
+    /// # Context: Transfer accounting
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to remove the multipliers from the database.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the torrent multipliers lock is poisoned.
+    pub fn remove_torrent_multipliers(&self, info_hash: &InfoHash) -> Result<(), databases::error::Error> {
+        let has_multipliers = self
+            .torrent_multipliers
+            .read()
+            .expect("it should get a read lock")
+            .contains_key(info_hash);
+
+        if has_multipliers {
+            self.database.remove_torrent_multipliers(info_hash)?;
+        }
+
+        self.torrent_multipliers
+            .write()
+            .expect("it should get a write lock")
+            .remove(info_hash);
+
+        Ok(())
+    }
+
+    /// It loads the torrent multipliers from the database.
+    ///
+    /// # Context: Transfer accounting
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to load the torrent multipliers from the database.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the torrent multipliers lock is poisoned.
+    pub fn load_torrent_multipliers_from_database(&self) -> Result<(), databases::error::Error> {
+        let multipliers_from_database = self.database.load_torrent_multipliers()?;
+        let mut torrent_multipliers = self.torrent_multipliers.write().expect("it should get a write lock");
+
+        torrent_multipliers.clear();
+        torrent_multipliers.extend(multipliers_from_database);
+
+        Ok(())
+    }
+
+    /// It returns `true` if the key is allowed to access the torrent, directly
+    /// or through the hybrid torrent it belongs to.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the key allow-lists or torrent groups locks are poisoned.
+    fn is_torrent_allowed_for_key(&self, key: &Key, info_hash: &InfoHash) -> bool {
+        let key_allow_lists = self.key_allow_lists.read().expect("it should get a read lock");
+
+        let Some(allow_list) = key_allow_lists.get(key) else {
+            return true;
+        };
+
+        let torrent_groups = self.torrent_groups.read().expect("it should get a read lock");
+
+        allow_list.allows(info_hash, &torrent_groups) || allow_list.allows(&self.resolve_info_hash(info_hash), &torrent_groups)
+    }
+
+    /// It returns the torrents a key is allowed to access, or `None` if the
+    /// key is not restricted and it can access all the torrents.
+    ///
+    /// # Context: Torrent access
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the key allow-lists lock is poisoned.
+    pub fn get_key_allow_list(&self, key: &Key) -> Option<AllowList> {
+        self.key_allow_lists
+            .read()
+            .expect("it should get a read lock")
+            .get(key)
+            .cloned()
+    }
+
+    /// It restricts a key to its allow-list, if it was not restricted yet.
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to add the restriction into the database.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the key allow-lists lock is poisoned.
+    fn restrict_key(&self, key: &Key) -> Result<(), databases::error::Error> {
+        if self.get_key_allow_list(key).is_some() {
+            return Ok(());
+        }
+
+        self.database.add_restricted_key(key)?;
+
+        self.key_allow_lists
+            .write()
+            .expect("it should get a write lock")
+            .insert(key.clone(), AllowList::default());
+
+        Ok(())
+    }
+
+    /// It removes the allow-list of a key, so that the key can access all the
+    /// torrents again.
+    ///
+    /// # Context: Torrent access
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to remove the allow-list from the database.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the key allow-lists lock is poisoned.
+    pub fn remove_key_allow_list(&self, key: &Key) -> Result<(), databases::error::Error> {
+        if self.get_key_allow_list(key).is_none() {
+            return Ok(());
+        }
+
+        self.database.remove_restricted_key(key)?;
+
+        self.key_allow_lists.write().expect("it should get a write lock").remove(key);
+
+        Ok(())
+    }
+
+    /// It adds a torrent to the allow-list of a key. From then on, the key
+    /// can only access the torrents in its allow-list.
+    ///
+    /// # Context: Torrent access
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to add the torrent into the database.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the key allow-lists lock is poisoned.
+    pub fn add_torrent_to_key_allow_list(&self, key: &Key, info_hash: &InfoHash) -> Result<(), databases::error::Error> {
+        if self
+            .get_key_allow_list(key)
+            .is_some_and(|allow_list| allow_list.info_hashes.contains(info_hash))
+        {
+            return Ok(());
+        }
+
+        self.restrict_key(key)?;
+        self.database.add_key_torrent(key, info_hash)?;
+
+        self.key_allow_lists
+            .write()
+            .expect("it should get a write lock")
+            .entry(key.clone())
+            .or_default()
+            .info_hashes
+            .insert(*info_hash);
+
+        Ok(())
+    }
+
+    /// It removes a torrent from the allow-list of a key.
+    ///
+    /// > **NOTICE**: the key is still restricted when its allow-list gets
+    /// > empty, so it can not access any torrent. Use
+    /// > [`remove_key_allow_list`](Tracker::remove_key_allow_list) to lift the
+    /// > restriction.
+    ///
+    /// # Context: Torrent access
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to remove the torrent from the database.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the key allow-lists lock is poisoned.
+    pub fn remove_torrent_from_key_allow_list(&self, key: &Key, info_hash: &InfoHash) -> Result<(), databases::error::Error> {
+        if !self
+            .get_key_allow_list(key)
+            .is_some_and(|allow_list| allow_list.info_hashes.contains(info_hash))
+        {
+            return Ok(());
+        }
+
+        self.database.remove_key_torrent(key, info_hash)?;
+
+        if let Some(allow_list) = self.key_allow_lists.write().expect("it should get a write lock").get_mut(key) {
+            allow_list.info_hashes.remove(info_hash);
+        }
+
+        Ok(())
+    }
+
+    /// It adds a torrent group to the allow-list of a key. From then on, the
+    /// key can only access the torrents in its allow-list.
+    ///
+    /// # Context: Torrent access
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to add the group into the database.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the key allow-lists lock is poisoned.
+    pub fn add_torrent_group_to_key_allow_list(&self, key: &Key, group: &TorrentGroup) -> Result<(), databases::error::Error> {
+        if self
+            .get_key_allow_list(key)
+            .is_some_and(|allow_list| allow_list.groups.contains(group))
+        {
+            return Ok(());
+        }
+
+        self.restrict_key(key)?;
+        self.database.add_key_torrent_group(key, group)?;
+
+        self.key_allow_lists
+            .write()
+            .expect("it should get a write lock")
+            .entry(key.clone())
+            .or_default()
+            .groups
+            .insert(group.clone());
+
+        Ok(())
+    }
+
+    /// It removes a torrent group from the allow-list of a key.
+    ///
+    /// > **NOTICE**: the key is still restricted when its allow-list gets
+    /// > empty, so it can not access any torrent. Use
+    /// > [`remove_key_allow_list`](Tracker::remove_key_allow_list) to lift the
+    /// > restriction.
+    ///
+    /// # Context: Torrent access
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to remove the group from the database.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the key allow-lists lock is poisoned.
+    pub fn remove_torrent_group_from_key_allow_list(
+        &self,
+        key: &Key,
+        group: &TorrentGroup,
+    ) -> Result<(), databases::error::Error> {
+        if !self
+            .get_key_allow_list(key)
+            .is_some_and(|allow_list| allow_list.groups.contains(group))
+        {
+            return Ok(());
+        }
+
+        self.database.remove_key_torrent_group(key, group)?;
+
+        if let Some(allow_list) = self.key_allow_lists.write().expect("it should get a write lock").get_mut(key) {
+            allow_list.groups.remove(group);
+        }
+
+        Ok(())
+    }
+
+    /// It returns the torrents in a torrent group, sorted.
+    ///
+    /// # Context: Torrent access
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the torrent groups lock is poisoned.
+    pub fn get_torrent_group(&self, group: &TorrentGroup) -> Vec<InfoHash> {
+        let mut info_hashes: Vec<InfoHash> = self
+            .torrent_groups
+            .read()
+            .expect("it should get a read lock")
+            .get(group)
+            .map(|info_hashes| info_hashes.iter().copied().collect())
+            .unwrap_or_default();
+
+        info_hashes.sort();
+
+        info_hashes
+    }
+
+    /// It adds a torrent to a torrent group. The group is created if it does
+    /// not exist.
+    ///
+    /// # Context: Torrent access
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to add the torrent into the database.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the torrent groups lock is poisoned.
+    pub fn add_torrent_to_group(&self, group: &TorrentGroup, info_hash: &InfoHash) -> Result<(), databases::error::Error> {
+        if self.get_torrent_group(group).contains(info_hash) {
+            return Ok(());
+        }
+
+        self.database.add_torrent_to_group(group, info_hash)?;
+
+        self.torrent_groups
+            .write()
+            .expect("it should get a write lock")
+            .entry(group.clone())
+            .or_default()
+            .insert(*info_hash);
+
+        Ok(())
+    }
+
+    /// It removes a torrent from a torrent group.
+    ///
+    /// # Context: Torrent access
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to remove the torrent from the database.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the torrent groups lock is poisoned.
+    pub fn remove_torrent_from_group(&self, group: &TorrentGroup, info_hash: &InfoHash) -> Result<(), databases::error::Error> {
+        if !self.get_torrent_group(group).contains(info_hash) {
+            return Ok(());
+        }
+
+        self.database.remove_torrent_from_group(group, info_hash)?;
+
+        let mut torrent_groups = self.torrent_groups.write().expect("it should get a write lock");
+
+        if let Some(info_hashes) = torrent_groups.get_mut(group) {
+            info_hashes.remove(info_hash);
+
+            if info_hashes.is_empty() {
+                torrent_groups.remove(group);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// It loads the key allow-lists and the torrent groups from the database.
+    ///
+    /// # Context: Torrent access
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to load them from the database.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the key allow-lists or torrent groups locks are poisoned.
+    pub fn load_torrent_access_from_database(&self) -> Result<(), databases::error::Error> {
+        let restricted_keys = self.database.load_restricted_keys()?;
+        let key_torrents = self.database.load_key_torrents()?;
+        let key_torrent_groups = self.database.load_key_torrent_groups()?;
+        let groups_from_database = self.database.load_torrent_groups()?;
+
+        let mut key_allow_lists = self.key_allow_lists.write().expect("it should get a write lock");
+
+        key_allow_lists.clear();
+
+        for key in restricted_keys {
+            key_allow_lists.insert(key, AllowList::default());
+        }
+
+        for (key, info_hash) in key_torrents {
+            key_allow_lists.entry(key).or_default().info_hashes.insert(info_hash);
+        }
+
+        for (key, group) in key_torrent_groups {
+            key_allow_lists.entry(key).or_default().groups.insert(group);
+        }
+
+        let mut torrent_groups = self.torrent_groups.write().expect("it should get a write lock");
+
+        torrent_groups.clear();
+
+        for (group, info_hash) in groups_from_database {
+            torrent_groups.entry(group).or_default().insert(info_hash);
+        }
+
+        Ok(())
+    }
+
+    /// It checks if the client software that generated the `peer_id` can
+    /// announce, depending on the client filter mode:
+    ///
+    /// - `disabled`: all the clients can announce.
+    /// - `allow`: the `peer_id` must start with one of the prefixes.
+    /// - `deny`: the `peer_id` must not start with any of the prefixes.
+    ///
+    /// # Context: Client filter
+    ///
+    /// # Errors
+    ///
+    /// Will return an `error::Error::ClientNotAllowed` if the client can't
+    /// announce.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the client prefixes lock is poisoned.
+    pub fn check_client(&self, peer_id: &PeerId) -> Result<(), Error> {
+        let is_listed = || {
+            self.client_prefixes
+                .read()
+                .expect("it should get a read lock")
+                .iter()
+                .any(|prefix| prefix.matches(peer_id))
+        };
+
+        let is_allowed = match self.config.client_filter {
+            ClientFilter::Disabled => true,
+            ClientFilter::Allow => is_listed(),
+            ClientFilter::Deny => !is_listed(),
+        };
+
+        if is_allowed {
+            Ok(())
+        } else {
+            Err(Error::ClientNotAllowed {
+                client: client_filter::describe_client(peer_id),
+                location: Location::caller(),
+            })
+        }
+    }
+
+    /// It returns the client filter mode.
+    ///
+    /// # Context: Client filter
+    pub fn get_client_filter(&self) -> ClientFilter {
+        self.config.client_filter
+    }
+
+    /// It returns the `peer_id` prefixes used to filter the client software,
+    /// sorted.
+    ///
+    /// # Context: Client filter
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the client prefixes lock is poisoned.
+    pub fn get_client_prefixes(&self) -> Vec<PeerIdPrefix> {
+        self.client_prefixes
+            .read()
+            .expect("it should get a read lock")
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// It adds a `peer_id` prefix to the client filter.
+    ///
+    /// # Context: Client filter
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to add the prefix into the database.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the client prefixes lock is poisoned.
+    pub fn add_client_prefix(&self, prefix: &PeerIdPrefix) -> Result<(), databases::error::Error> {
+        if self
+            .client_prefixes
+            .read()
+            .expect("it should get a read lock")
+            .contains(prefix)
+        {
+            return Ok(());
+        }
+
+        self.database.add_client_prefix(prefix)?;
+
+        self.client_prefixes
+            .write()
+            .expect("it should get a write lock")
+            .insert(prefix.clone());
+
+        Ok(())
+    }
+
+    /// It removes a `peer_id` prefix from the client filter.
+    ///
+    /// # Context: Client filter
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to remove the prefix from the database.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the client prefixes lock is poisoned.
+    pub fn remove_client_prefix(&self, prefix: &PeerIdPrefix) -> Result<(), databases::error::Error> {
+        if !self
+            .client_prefixes
+            .read()
+            .expect("it should get a read lock")
+            .contains(prefix)
+        {
+            return Ok(());
+        }
+
+        self.database.remove_client_prefix(prefix)?;
+
+        self.client_prefixes
+            .write()
+            .expect("it should get a write lock")
+            .remove(prefix);
+
+        Ok(())
+    }
+
+    /// It loads the client filter `peer_id` prefixes from the database.
+    ///
+    /// # Context: Client filter
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to load them from the database.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the client prefixes lock is poisoned.
+    pub fn load_client_prefixes_from_database(&self) -> Result<(), databases::error::Error> {
+        let prefixes_from_database = self.database.load_client_prefixes()?;
+        let mut client_prefixes = self.client_prefixes.write().expect("it should get a write lock");
+
+        client_prefixes.clear();
+        client_prefixes.extend(prefixes_from_database);
+
+        Ok(())
+    }
+
+    /// It checks if the request comes from a blocked network.
+    ///
+    /// # Context: IP blocklist
+    ///
+    /// # Errors
+    ///
+    /// Will return an `error::Error::IpBlocked` if the IP is in the blocklist.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the IP blocklist lock is poisoned.
+    pub fn check_ip(&self, ip: &IpAddr) -> Result<(), Error> {
+        if self.ip_blocklist.read().expect("it should get a read lock").contains(ip) {
+            return Err(Error::IpBlocked {
+                ip: *ip,
+                location: Location::caller(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// It returns the networks blocked with the API, sorted.
+    ///
+    /// # Context: IP blocklist
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the blocked networks lock is poisoned.
+    pub fn get_blocked_networks(&self) -> Vec<IpNet> {
+        self.blocked_networks
+            .read()
+            .expect("it should get a read lock")
+            .iter()
+            .copied()
+            .collect()
+    }
+
+    /// It returns the number of networks loaded from the IP blocklist file.
+    ///
+    /// # Context: IP blocklist
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the IP blocklist file networks lock is poisoned.
+    pub fn get_ip_blocklist_file_len(&self) -> usize {
+        self.ip_blocklist_file_networks
+            .read()
+            .expect("it should get a read lock")
+            .len()
+    }
+
+    /// It blocks a network. The host bits of the network are ignored, so
+    /// `10.1.2.3/8` blocks `10.0.0.0/8`.
+    ///
+    /// # Context: IP blocklist
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to add the network into the database.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the blocked networks or IP blocklist locks are poisoned.
+    pub fn add_blocked_network(&self, network: &IpNet) -> Result<(), databases::error::Error> {
+        let network = network.trunc();
+
+        if self
+            .blocked_networks
+            .read()
+            .expect("it should get a read lock")
+            .contains(&network)
+        {
+            return Ok(());
+        }
+
+        self.database.add_blocked_network(&network)?;
+
+        self.blocked_networks
+            .write()
+            .expect("it should get a write lock")
+            .insert(network);
+
+        self.ip_blocklist
+            .write()
+            .expect("it should get a write lock")
+            .insert(&network);
+
+        Ok(())
+    }
+
+    /// It unblocks a network blocked with the API. The networks in the IP
+    /// blocklist file can't be unblocked.
+    ///
+    /// # Context: IP blocklist
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to remove the network from the database.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the blocked networks or IP blocklist locks are poisoned.
+    pub fn remove_blocked_network(&self, network: &IpNet) -> Result<(), databases::error::Error> {
+        let network = network.trunc();
+
+        if !self
+            .blocked_networks
+            .read()
+            .expect("it should get a read lock")
+            .contains(&network)
+        {
+            return Ok(());
+        }
+
+        self.database.remove_blocked_network(&network)?;
+
+        self.blocked_networks
+            .write()
+            .expect("it should get a write lock")
+            .remove(&network);
+
+        self.rebuild_ip_blocklist();
+
+        Ok(())
+    }
+
+    /// It loads the networks blocked with the API from the database.
+    ///
+    /// # Context: IP blocklist
+    ///
+    /// # Errors
+    ///
+    /// Will return a `database::Error` if unable to load them from the database.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the blocked networks or IP blocklist locks are poisoned.
+    pub fn load_blocked_networks_from_database(&self) -> Result<(), databases::error::Error> {
+        let networks_from_database = self.database.load_blocked_networks()?;
+
+        {
+            let mut blocked_networks = self.blocked_networks.write().expect("it should get a write lock");
+
+            blocked_networks.clear();
+            blocked_networks.extend(networks_from_database);
+        }
+
+        self.rebuild_ip_blocklist();
+
+        Ok(())
+    }
+
+    /// It loads the networks in the IP blocklist file configured in the
+    /// `core.ip_blocklist` section. It does nothing if the IP blocklist file
+    /// is not configured.
+    ///
+    /// # Context: IP blocklist
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the file cannot be read or parsed. The
+    /// previously loaded networks are kept in that case.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the IP blocklist file networks or IP blocklist locks are poisoned.
+    pub fn load_ip_blocklist_from_file(&self) -> Result<(), ip_blocklist::Error> {
+        let Some(ip_blocklist_config) = &self.config.ip_blocklist else {
+            return Ok(());
+        };
+
+        let path = &ip_blocklist_config.path;
+
+        let text = std::fs::read_to_string(path).map_err(|source| ip_blocklist::Error::Io {
+            path: path.clone(),
+            source,
+        })?;
+
+        let networks = ip_blocklist::parse(&text).map_err(|source| ip_blocklist::Error::Parse {
+            path: path.clone(),
+            source,
+        })?;
+
+        *self.ip_blocklist_file_networks.write().expect("it should get a write lock") = networks;
+
+        self.rebuild_ip_blocklist();
+
+        Ok(())
+    }
+
+    fn rebuild_ip_blocklist(&self) {
+        let ip_blocklist = {
+            let file_networks = self.ip_blocklist_file_networks.read().expect("it should get a read lock");
+            let blocked_networks = self.blocked_networks.read().expect("it should get a read lock");
+
+            IpBlocklist::new(file_networks.iter().chain(blocked_networks.iter()))
+        };
+
+        *self.ip_blocklist.write().expect("it should get a write lock") = ip_blocklist;
+    }
+
+    /// It return the `Tracker` [`statistics::Metrics`].
+    ///
+    /// # Context: Statistics
+    pub async fn get_stats(&self) -> tokio::sync::RwLockReadGuard<'_, statistics::Metrics> {
+        self.stats_repository.get_stats().await
+    }
+
+    /// It return the [`statistics::ListenerMetrics`] for each running server.
+    ///
+    /// # Context: Statistics
+    pub async fn get_listeners_stats(
+        &self,
+    ) -> tokio::sync::RwLockReadGuard<'_, std::collections::BTreeMap<statistics::Listener, statistics::ListenerMetrics>> {
+        self.stats_repository.get_listeners_stats().await
+    }
+
+    /// It return the [`statistics::LatencyHistogram`] for each protocol and
+    /// request type.
+    ///
+    /// # Context: Statistics
+    pub async fn get_latency_stats(
+        &self,
+    ) -> tokio::sync::RwLockReadGuard<
+        '_,
+        std::collections::BTreeMap<(statistics::Protocol, statistics::RequestKind), statistics::LatencyHistogram>,
+    > {
+        self.stats_repository.get_latency_stats().await
+    }
+
+    /// It allows to send a statistic events which eventually will be used to update [`statistics::Metrics`].
+    ///
+    /// # Context: Statistics
+    pub async fn send_stats_event(&self, event: statistics::Event) -> Option<Result<(), SendError<statistics::Event>>> {
+        match &self.stats_event_sender {
+            None => None,
+            Some(stats_event_sender) => stats_event_sender.send_event(event).await,
+        }
+    }
+
+    /// It allows to send the time taken to handle a request, which eventually
+    /// will be added to the [`statistics::LatencyHistogram`] for the request type.
+    ///
+    /// # Context: Statistics
+    pub async fn send_stats_latency(
+        &self,
+        latency: statistics::RequestLatency,
+    ) -> Option<Result<(), SendError<statistics::RequestLatency>>> {
+        match &self.stats_event_sender {
+            None => None,
+            Some(stats_event_sender) => stats_event_sender.send_latency(latency).await,
+        }
+    }
+
+    /// It drops the database tables.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if unable to drop tables.
+    pub fn drop_database_tables(&self) -> Result<(), databases::error::Error> {
+        // todo: this is only used for testing. WE have to pass the database
+        // reference directly to the tests instead of via the tracker.
+        self.database.drop_database_tables()
+    }
+}
+
+#[must_use]
+fn assign_ip_address_to_peer(remote_client_ip: &IpAddr, tracker_external_ip: Option<IpAddr>) -> IpAddr {
+    if let Some(host_ip) = tracker_external_ip.filter(|_| remote_client_ip.is_loopback()) {
+        host_ip
+    } else {
+        *remote_client_ip
+    }
+}
+
+#[cfg(test)]
 mod tests {
 
-    mod the_tracker {
+    mod the_peers_wanted {
+        use crate::core::PeersWanted;
+
+        #[test]
+        fn should_be_the_default_amount_when_the_peer_does_not_say_how_many_peers_it_wants() {
+            assert_eq!(PeersWanted::All.at_most(50, 74), PeersWanted::only(50));
+        }
+
+        #[test]
+        fn should_be_the_amount_the_peer_wants_when_it_does_not_exceed_the_max() {
+            assert_eq!(PeersWanted::only(5).at_most(50, 74), PeersWanted::only(5));
+            assert_eq!(PeersWanted::only(0).at_most(50, 74), PeersWanted::only(0));
+        }
+
+        #[test]
+        fn should_not_exceed_the_max() {
+            assert_eq!(PeersWanted::only(200).at_most(50, 74), PeersWanted::only(74));
+            assert_eq!(PeersWanted::All.at_most(100, 74), PeersWanted::only(74));
+        }
+    }
+
+    mod the_tracker {
+
+        use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+        use std::str::FromStr;
+        use std::sync::Arc;
+
+        use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes, PeerId};
+        use bittorrent_primitives::info_hash::fixture::gen_seeded_infohash;
+        use bittorrent_primitives::info_hash::InfoHash;
+        use torrust_tracker_configuration::{SwarmSnapshot, TORRENT_PEERS_LIMIT};
+        use torrust_tracker_primitives::DurationSinceUnixEpoch;
+        use torrust_tracker_test_helpers::configuration;
+
+        use crate::core::peer::Peer;
+        use crate::core::services::tracker_factory;
+        use crate::core::{TorrentsMetrics, Tracker};
+
+        fn public_tracker() -> Tracker {
+            tracker_factory(&configuration::ephemeral_public())
+        }
+
+        fn private_tracker() -> Tracker {
+            tracker_factory(&configuration::ephemeral_private())
+        }
+
+        fn whitelisted_tracker() -> Tracker {
+            tracker_factory(&configuration::ephemeral_listed())
+        }
+
+        pub fn tracker_saving_swarm_snapshots(dir: &std::path::Path) -> Tracker {
+            let mut configuration = configuration::ephemeral();
+            configuration.core.swarm_snapshot = Some(SwarmSnapshot {
+                path: dir.join("swarms.json").display().to_string(),
+                interval: 0,
+            });
+            tracker_factory(&configuration)
+        }
+
+        pub fn tracker_persisting_torrents_in_database() -> Tracker {
+            let mut configuration = configuration::ephemeral();
+            configuration.core.tracker_policy.persistent_torrent_completed_stat = true;
+            tracker_factory(&configuration)
+        }
+
+        fn sample_info_hash() -> InfoHash {
+            "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap()
+        }
+
+        // The client peer IP
+        fn peer_ip() -> IpAddr {
+            IpAddr::V4(Ipv4Addr::from_str("126.0.0.1").unwrap())
+        }
+
+        /// Sample peer whose state is not relevant for the tests
+        fn sample_peer() -> Peer {
+            complete_peer()
+        }
+
+        /// Sample peer when for tests that need more than one peer
+        fn sample_peer_1() -> Peer {
+            Peer {
+                peer_id: PeerId(*b"-qB00000000000000001"),
+                peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8081),
+                alternative_addr: None,
+                key: None,
+                updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
+                uploaded: NumberOfBytes::new(0),
+                downloaded: NumberOfBytes::new(0),
+                left: NumberOfBytes::new(0),
+                event: AnnounceEvent::Completed,
+                upload_only: false,
+            }
+        }
+
+        /// Sample peer when for tests that need more than one peer
+        fn sample_peer_2() -> Peer {
+            Peer {
+                peer_id: PeerId(*b"-qB00000000000000002"),
+                peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 2)), 8082),
+                alternative_addr: None,
+                key: None,
+                updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
+                uploaded: NumberOfBytes::new(0),
+                downloaded: NumberOfBytes::new(0),
+                left: NumberOfBytes::new(0),
+                event: AnnounceEvent::Completed,
+                upload_only: false,
+            }
+        }
+
+        fn seeder() -> Peer {
+            complete_peer()
+        }
+
+        fn leecher() -> Peer {
+            incomplete_peer()
+        }
+
+        fn started_peer() -> Peer {
+            incomplete_peer()
+        }
+
+        fn completed_peer() -> Peer {
+            complete_peer()
+        }
+
+        /// A peer that counts as `complete` is swarm metadata
+        /// IMPORTANT!: it only counts if the it has been announce at least once before
+        /// announcing the `AnnounceEvent::Completed` event.
+        fn complete_peer() -> Peer {
+            Peer {
+                peer_id: PeerId(*b"-qB00000000000000000"),
+                peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080),
+                alternative_addr: None,
+                key: None,
+                updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
+                uploaded: NumberOfBytes::new(0),
+                downloaded: NumberOfBytes::new(0),
+                left: NumberOfBytes::new(0), // No bytes left to download
+                event: AnnounceEvent::Completed,
+                upload_only: false,
+            }
+        }
+
+        /// A peer that counts as `incomplete` is swarm metadata
+        fn incomplete_peer() -> Peer {
+            Peer {
+                peer_id: PeerId(*b"-qB00000000000000000"),
+                peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080),
+                alternative_addr: None,
+                key: None,
+                updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
+                uploaded: NumberOfBytes::new(0),
+                downloaded: NumberOfBytes::new(0),
+                left: NumberOfBytes::new(1000), // Still bytes to download
+                event: AnnounceEvent::Started,
+                upload_only: false,
+            }
+        }
+
+        #[tokio::test]
+        async fn should_collect_torrent_metrics() {
+            let tracker = public_tracker();
+
+            let torrents_metrics = tracker.get_torrents_metrics();
+
+            assert_eq!(
+                torrents_metrics,
+                TorrentsMetrics {
+                    complete: 0,
+                    downloaded: 0,
+                    incomplete: 0,
+                    torrents: 0
+                }
+            );
+        }
+
+        #[tokio::test]
+        async fn it_should_return_the_peers_for_a_given_torrent() {
+            let tracker = public_tracker();
+
+            let info_hash = sample_info_hash();
+            let peer = sample_peer();
+
+            tracker.upsert_peer_and_get_stats(&info_hash, &peer);
+
+            let peers = tracker.get_torrent_peers(&info_hash);
+
+            assert_eq!(peers, vec![Arc::new(peer)]);
+        }
+
+        /// It generates a peer id from a number where the number is the last
+        /// part of the peer ID. For example, for `12` it returns
+        /// `-qB00000000000000012`.
+        fn numeric_peer_id(two_digits_value: i32) -> PeerId {
+            // Format idx as a string with leading zeros, ensuring it has exactly 2 digits
+            let idx_str = format!("{two_digits_value:02}");
+
+            // Create the base part of the peer ID.
+            let base = b"-qB00000000000000000";
+
+            // Concatenate the base with idx bytes, ensuring the total length is 20 bytes.
+            let mut peer_id_bytes = [0u8; 20];
+            peer_id_bytes[..base.len()].copy_from_slice(base);
+            peer_id_bytes[base.len() - idx_str.len()..].copy_from_slice(idx_str.as_bytes());
+
+            PeerId(peer_id_bytes)
+        }
+
+        #[tokio::test]
+        async fn it_should_return_74_peers_at_the_most_for_a_given_torrent() {
+            let tracker = public_tracker();
+
+            let info_hash = sample_info_hash();
+
+            for idx in 1..=75 {
+                let peer = Peer {
+                    peer_id: numeric_peer_id(idx),
+                    peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, idx.try_into().unwrap())), 8080),
+                    alternative_addr: None,
+                    key: None,
+                    updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
+                    uploaded: NumberOfBytes::new(0),
+                    downloaded: NumberOfBytes::new(0),
+                    left: NumberOfBytes::new(0), // No bytes left to download
+                    event: AnnounceEvent::Completed,
+                    upload_only: false,
+                };
+
+                tracker.upsert_peer_and_get_stats(&info_hash, &peer);
+            }
+
+            let peers = tracker.get_torrent_peers(&info_hash);
+
+            assert_eq!(peers.len(), 74);
+        }
+
+        #[tokio::test]
+        async fn it_should_return_the_peers_for_a_given_torrent_excluding_a_given_peer() {
+            let tracker = public_tracker();
+
+            let info_hash = sample_info_hash();
+            let peer = sample_peer();
+
+            tracker.upsert_peer_and_get_stats(&info_hash, &peer);
+
+            let peers = tracker.get_peers_for(&info_hash, &peer, TORRENT_PEERS_LIMIT);
+
+            assert_eq!(peers, vec![]);
+        }
+
+        #[tokio::test]
+        async fn it_should_return_74_peers_at_the_most_for_a_given_torrent_when_it_filters_out_a_given_peer() {
+            let tracker = public_tracker();
+
+            let info_hash = sample_info_hash();
+
+            let excluded_peer = sample_peer();
+
+            tracker.upsert_peer_and_get_stats(&info_hash, &excluded_peer);
+
+            // Add 74 peers
+            for idx in 2..=75 {
+                let peer = Peer {
+                    peer_id: numeric_peer_id(idx),
+                    peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, idx.try_into().unwrap())), 8080),
+                    alternative_addr: None,
+                    key: None,
+                    updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
+                    uploaded: NumberOfBytes::new(0),
+                    downloaded: NumberOfBytes::new(0),
+                    left: NumberOfBytes::new(0), // No bytes left to download
+                    event: AnnounceEvent::Completed,
+                    upload_only: false,
+                };
+
+                tracker.upsert_peer_and_get_stats(&info_hash, &peer);
+            }
+
+            let peers = tracker.get_peers_for(&info_hash, &excluded_peer, TORRENT_PEERS_LIMIT);
+
+            assert_eq!(peers.len(), 74);
+        }
+
+        #[tokio::test]
+        async fn it_should_return_only_the_number_of_peers_wanted_for_a_given_torrent() {
+            let tracker = public_tracker();
+
+            let info_hash = sample_info_hash();
+
+            let excluded_peer = sample_peer();
+
+            tracker.upsert_peer_and_get_stats(&info_hash, &excluded_peer);
+
+            for idx in 2..=11 {
+                let peer = Peer {
+                    peer_id: numeric_peer_id(idx),
+                    peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, idx.try_into().unwrap())), 8080),
+                    ..sample_peer()
+                };
+
+                tracker.upsert_peer_and_get_stats(&info_hash, &peer);
+            }
+
+            let peers = tracker.get_peers_for(&info_hash, &excluded_peer, 5);
+
+            assert_eq!(peers.len(), 5);
+        }
+
+        #[tokio::test]
+        async fn it_should_return_the_torrent_metrics() {
+            let tracker = public_tracker();
+
+            tracker.upsert_peer_and_get_stats(&sample_info_hash(), &leecher());
+
+            let torrent_metrics = tracker.get_torrents_metrics();
+
+            assert_eq!(
+                torrent_metrics,
+                TorrentsMetrics {
+                    complete: 0,
+                    downloaded: 0,
+                    incomplete: 1,
+                    torrents: 1,
+                }
+            );
+        }
+
+        #[tokio::test]
+        async fn it_should_get_many_the_torrent_metrics() {
+            let tracker = public_tracker();
+
+            let start_time = std::time::Instant::now();
+            for i in 0..1_000_000 {
+                tracker.upsert_peer_and_get_stats(&gen_seeded_infohash(&i), &leecher());
+            }
+            let result_a = start_time.elapsed();
+
+            let start_time = std::time::Instant::now();
+            let torrent_metrics = tracker.get_torrents_metrics();
+            let result_b = start_time.elapsed();
+
+            assert_eq!(
+                (torrent_metrics),
+                (TorrentsMetrics {
+                    complete: 0,
+                    downloaded: 0,
+                    incomplete: 1_000_000,
+                    torrents: 1_000_000,
+                }),
+                "{result_a:?} {result_b:?}"
+            );
+        }
+
+        mod for_all_config_modes {
+
+            mod handling_an_announce_request {
+
+                use std::sync::Arc;
+
+                use torrust_tracker_test_helpers::configuration;
+
+                use crate::core::services::tracker_factory;
+                use crate::core::tests::the_tracker::{
+                    peer_ip, public_tracker, sample_info_hash, sample_peer, sample_peer_1, sample_peer_2,
+                };
+                use crate::core::PeersWanted;
+
+                mod should_assign_the_ip_to_the_peer {
+
+                    use std::net::{IpAddr, Ipv4Addr};
+
+                    use crate::core::assign_ip_address_to_peer;
+
+                    #[test]
+                    fn using_the_source_ip_instead_of_the_ip_in_the_announce_request() {
+                        let remote_ip = IpAddr::V4(Ipv4Addr::new(126, 0, 0, 2));
+
+                        let peer_ip = assign_ip_address_to_peer(&remote_ip, None);
+
+                        assert_eq!(peer_ip, remote_ip);
+                    }
+
+                    mod and_when_the_client_ip_is_a_ipv4_loopback_ip {
+
+                        use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+                        use std::str::FromStr;
+
+                        use crate::core::assign_ip_address_to_peer;
+
+                        #[test]
+                        fn it_should_use_the_loopback_ip_if_the_tracker_does_not_have_the_external_ip_configuration() {
+                            let remote_ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+                            let peer_ip = assign_ip_address_to_peer(&remote_ip, None);
+
+                            assert_eq!(peer_ip, remote_ip);
+                        }
+
+                        #[test]
+                        fn it_should_use_the_external_tracker_ip_in_tracker_configuration_if_it_is_defined() {
+                            let remote_ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+                            let tracker_external_ip = IpAddr::V4(Ipv4Addr::from_str("126.0.0.1").unwrap());
+
+                            let peer_ip = assign_ip_address_to_peer(&remote_ip, Some(tracker_external_ip));
+
+                            assert_eq!(peer_ip, tracker_external_ip);
+                        }
+
+                        #[test]
+                        fn it_should_use_the_external_ip_in_the_tracker_configuration_if_it_is_defined_even_if_the_external_ip_is_an_ipv6_ip(
+                        ) {
+                            let remote_ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+                            let tracker_external_ip =
+                                IpAddr::V6(Ipv6Addr::from_str("2345:0425:2CA1:0000:0000:0567:5673:23b5").unwrap());
+
+                            let peer_ip = assign_ip_address_to_peer(&remote_ip, Some(tracker_external_ip));
+
+                            assert_eq!(peer_ip, tracker_external_ip);
+                        }
+                    }
+
+                    mod and_when_client_ip_is_a_ipv6_loopback_ip {
+
+                        use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+                        use std::str::FromStr;
+
+                        use crate::core::assign_ip_address_to_peer;
+
+                        #[test]
+                        fn it_should_use_the_loopback_ip_if_the_tracker_does_not_have_the_external_ip_configuration() {
+                            let remote_ip = IpAddr::V6(Ipv6Addr::LOCALHOST);
+
+                            let peer_ip = assign_ip_address_to_peer(&remote_ip, None);
+
+                            assert_eq!(peer_ip, remote_ip);
+                        }
+
+                        #[test]
+                        fn it_should_use_the_external_ip_in_tracker_configuration_if_it_is_defined() {
+                            let remote_ip = IpAddr::V6(Ipv6Addr::LOCALHOST);
+
+                            let tracker_external_ip =
+                                IpAddr::V6(Ipv6Addr::from_str("2345:0425:2CA1:0000:0000:0567:5673:23b5").unwrap());
+
+                            let peer_ip = assign_ip_address_to_peer(&remote_ip, Some(tracker_external_ip));
+
+                            assert_eq!(peer_ip, tracker_external_ip);
+                        }
+
+                        #[test]
+                        fn it_should_use_the_external_ip_in_the_tracker_configuration_if_it_is_defined_even_if_the_external_ip_is_an_ipv4_ip(
+                        ) {
+                            let remote_ip = IpAddr::V6(Ipv6Addr::LOCALHOST);
+
+                            let tracker_external_ip = IpAddr::V4(Ipv4Addr::from_str("126.0.0.1").unwrap());
+
+                            let peer_ip = assign_ip_address_to_peer(&remote_ip, Some(tracker_external_ip));
+
+                            assert_eq!(peer_ip, tracker_external_ip);
+                        }
+                    }
+
+                    mod and_when_the_client_sends_the_ip_param {
+
+                        use std::net::{IpAddr, Ipv4Addr};
+
+                        use torrust_tracker_test_helpers::configuration;
+
+                        use crate::core::services::tracker_factory;
+                        use crate::core::Tracker;
+
+                        fn tracker_trusting(network: &str) -> Tracker {
+                            let mut configuration = configuration::ephemeral();
+                            configuration.core.net.trusted_networks = vec![network.parse().unwrap()];
+                            tracker_factory(&configuration)
+                        }
+
+                        #[tokio::test]
+                        async fn it_should_ignore_the_announced_ip_if_the_client_is_not_in_a_trusted_network() {
+                            let tracker = tracker_trusting("10.0.0.0/8");
+
+                            let remote_ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+                            let announced_ip = IpAddr::V4(Ipv4Addr::new(126, 0, 0, 2));
+
+                            assert_eq!(tracker.resolve_peer_ip(&remote_ip, Some(announced_ip)), remote_ip);
+                        }
+
+                        #[tokio::test]
+                        async fn it_should_use_the_announced_ip_if_the_client_is_in_a_trusted_network() {
+                            let tracker = tracker_trusting("127.0.0.0/8");
+
+                            let remote_ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+                            let announced_ip = IpAddr::V4(Ipv4Addr::new(126, 0, 0, 2));
+
+                            assert_eq!(tracker.resolve_peer_ip(&remote_ip, Some(announced_ip)), announced_ip);
+                        }
+
+                        #[tokio::test]
+                        async fn it_should_ignore_the_announced_ip_if_it_is_not_a_public_ip() {
+                            let tracker = tracker_trusting("127.0.0.0/8");
+
+                            let remote_ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+                            let announced_ip = IpAddr::V4(Ipv4Addr::new(192, 168, 0, 2));
+
+                            assert_eq!(tracker.resolve_peer_ip(&remote_ip, Some(announced_ip)), remote_ip);
+                        }
+
+                        #[tokio::test]
+                        async fn it_should_use_the_source_ip_if_the_trusted_client_does_not_send_the_ip_param() {
+                            let tracker = tracker_trusting("127.0.0.0/8");
+
+                            let remote_ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+                            assert_eq!(tracker.resolve_peer_ip(&remote_ip, None), remote_ip);
+                        }
+                    }
+
+                    mod and_when_the_client_sends_the_ipv4_or_ipv6_param {
+
+                        use std::net::SocketAddr;
+                        use std::str::FromStr;
+
+                        use crate::core::tests::the_tracker::public_tracker;
+
+                        fn announced_addr(addr: &str) -> SocketAddr {
+                            SocketAddr::from_str(addr).unwrap()
+                        }
+
+                        #[tokio::test]
+                        async fn it_should_use_the_announced_address_if_it_is_a_public_ip() {
+                            let tracker = public_tracker();
+
+                            let addr = announced_addr("[2606:4700::1111]:8080");
+
+                            assert_eq!(tracker.resolve_alternative_addr(Some(addr)), Some(addr));
+                        }
+
+                        #[tokio::test]
+                        async fn it_should_ignore_the_announced_address_if_it_is_not_a_public_ip() {
+                            let tracker = public_tracker();
+
+                            let addr = announced_addr("[fd00::1]:8080");
+
+                            assert_eq!(tracker.resolve_alternative_addr(Some(addr)), None);
+                        }
+
+                        #[tokio::test]
+                        async fn it_should_ignore_the_announced_address_if_it_is_blocked() {
+                            let tracker = public_tracker();
+
+                            tracker.add_blocked_network(&"2606:4700::/32".parse().unwrap()).unwrap();
+
+                            let addr = announced_addr("[2606:4700::1111]:8080");
+
+                            assert_eq!(tracker.resolve_alternative_addr(Some(addr)), None);
+                        }
+                    }
+                }
+
+                #[tokio::test]
+                async fn it_should_return_the_announce_data_with_an_empty_peer_list_when_it_is_the_first_announced_peer() {
+                    let tracker = public_tracker();
+
+                    let mut peer = sample_peer();
+
+                    let announce_data = tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+
+                    assert_eq!(announce_data.peers, vec![]);
+                }
+
+                #[tokio::test]
+                async fn it_should_return_the_default_number_of_peers_when_the_peer_does_not_say_how_many_peers_it_wants() {
+                    let mut configuration = configuration::ephemeral_public();
+                    configuration.core.announce_policy.numwant = 1;
+                    let tracker = tracker_factory(&configuration);
+
+                    let mut previously_announced_peer = sample_peer_1();
+                    tracker.announce(
+                        &sample_info_hash(),
+                        &mut previously_announced_peer,
+                        &peer_ip(),
+                        &PeersWanted::All,
+                    );
+
+                    let mut another_previously_announced_peer = sample_peer_2();
+                    tracker.announce(
+                        &sample_info_hash(),
+                        &mut another_previously_announced_peer,
+                        &peer_ip(),
+                        &PeersWanted::All,
+                    );
+
+                    let mut peer = sample_peer();
+                    let announce_data = tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+
+                    assert_eq!(announce_data.peers.len(), 1);
+                }
+
+                #[tokio::test]
+                async fn it_should_return_the_announce_data_with_the_previously_announced_peers() {
+                    let tracker = public_tracker();
+
+                    let mut previously_announced_peer = sample_peer_1();
+                    tracker.announce(
+                        &sample_info_hash(),
+                        &mut previously_announced_peer,
+                        &peer_ip(),
+                        &PeersWanted::All,
+                    );
+
+                    let mut peer = sample_peer_2();
+                    let announce_data = tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+
+                    assert_eq!(announce_data.peers, vec![Arc::new(previously_announced_peer)]);
+                }
+
+                mod it_should_update_the_swarm_stats_for_the_torrent {
+
+                    use crate::core::tests::the_tracker::{
+                        completed_peer, leecher, peer_ip, public_tracker, sample_info_hash, seeder, started_peer,
+                    };
+                    use crate::core::PeersWanted;
+
+                    #[tokio::test]
+                    async fn when_the_peer_is_a_seeder() {
+                        let tracker = public_tracker();
+
+                        let mut peer = seeder();
+
+                        let announce_data = tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+
+                        assert_eq!(announce_data.stats.complete, 1);
+                    }
+
+                    #[tokio::test]
+                    async fn when_the_peer_is_a_leecher() {
+                        let tracker = public_tracker();
+
+                        let mut peer = leecher();
+
+                        let announce_data = tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+
+                        assert_eq!(announce_data.stats.incomplete, 1);
+                    }
+
+                    #[tokio::test]
+                    async fn when_a_previously_announced_started_peer_has_completed_downloading() {
+                        let tracker = public_tracker();
+
+                        // We have to announce with "started" event because peer does not count if peer was not previously known
+                        let mut started_peer = started_peer();
+                        tracker.announce(&sample_info_hash(), &mut started_peer, &peer_ip(), &PeersWanted::All);
+
+                        let mut completed_peer = completed_peer();
+                        let announce_data =
+                            tracker.announce(&sample_info_hash(), &mut completed_peer, &peer_ip(), &PeersWanted::All);
+
+                        assert_eq!(announce_data.stats.downloaded, 1);
+                    }
+                }
+            }
+
+            mod handling_a_scrape_request {
 
-        use std::net::{IpAddr, Ipv4Addr, SocketAddr};
-        use std::str::FromStr;
-        use std::sync::Arc;
+                use std::net::{IpAddr, Ipv4Addr};
 
-        use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes, PeerId};
-        use bittorrent_primitives::info_hash::fixture::gen_seeded_infohash;
-        use bittorrent_primitives::info_hash::InfoHash;
-        use torrust_tracker_configuration::TORRENT_PEERS_LIMIT;
-        use torrust_tracker_primitives::DurationSinceUnixEpoch;
-        use torrust_tracker_test_helpers::configuration;
+                use bittorrent_primitives::info_hash::InfoHash;
 
-        use crate::core::peer::Peer;
-        use crate::core::services::tracker_factory;
-        use crate::core::{TorrentsMetrics, Tracker};
+                use crate::core::tests::the_tracker::{complete_peer, incomplete_peer, public_tracker};
+                use crate::core::{PeersWanted, ScrapeData, SwarmMetadata};
 
-        fn public_tracker() -> Tracker {
-            tracker_factory(&configuration::ephemeral_public())
-        }
+                #[tokio::test]
+                async fn it_should_return_a_zeroed_swarm_metadata_for_the_requested_file_if_the_tracker_does_not_have_that_torrent(
+                ) {
+                    let tracker = public_tracker();
 
-        fn private_tracker() -> Tracker {
-            tracker_factory(&configuration::ephemeral_private())
-        }
+                    let info_hashes = vec!["3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap()];
 
-        fn whitelisted_tracker() -> Tracker {
-            tracker_factory(&configuration::ephemeral_listed())
-        }
+                    let scrape_data = tracker.scrape(&info_hashes, None).await;
 
-        pub fn tracker_persisting_torrents_in_database() -> Tracker {
-            let mut configuration = configuration::ephemeral();
-            configuration.core.tracker_policy.persistent_torrent_completed_stat = true;
-            tracker_factory(&configuration)
-        }
+                    let mut expected_scrape_data = ScrapeData::empty();
 
-        fn sample_info_hash() -> InfoHash {
-            "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap()
-        }
+                    expected_scrape_data.add_file_with_zeroed_metadata(&info_hashes[0]);
 
-        // The client peer IP
-        fn peer_ip() -> IpAddr {
-            IpAddr::V4(Ipv4Addr::from_str("126.0.0.1").unwrap())
-        }
+                    assert_eq!(scrape_data, expected_scrape_data);
+                }
 
-        /// Sample peer whose state is not relevant for the tests
-        fn sample_peer() -> Peer {
-            complete_peer()
-        }
+                #[tokio::test]
+                async fn it_should_return_the_swarm_metadata_for_the_requested_file_if_the_tracker_has_that_torrent() {
+                    let tracker = public_tracker();
 
-        /// Sample peer when for tests that need more than one peer
-        fn sample_peer_1() -> Peer {
-            Peer {
-                peer_id: PeerId(*b"-qB00000000000000001"),
-                peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8081),
-                updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
-                uploaded: NumberOfBytes::new(0),
-                downloaded: NumberOfBytes::new(0),
-                left: NumberOfBytes::new(0),
-                event: AnnounceEvent::Completed,
+                    let info_hash = "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap();
+
+                    // Announce a "complete" peer for the torrent
+                    let mut complete_peer = complete_peer();
+                    tracker.announce(
+                        &info_hash,
+                        &mut complete_peer,
+                        &IpAddr::V4(Ipv4Addr::new(126, 0, 0, 10)),
+                        &PeersWanted::All,
+                    );
+
+                    // Announce an "incomplete" peer for the torrent
+                    let mut incomplete_peer = incomplete_peer();
+                    tracker.announce(
+                        &info_hash,
+                        &mut incomplete_peer,
+                        &IpAddr::V4(Ipv4Addr::new(126, 0, 0, 11)),
+                        &PeersWanted::All,
+                    );
+
+                    // Scrape
+                    let scrape_data = tracker.scrape(&vec![info_hash], None).await;
+
+                    // The expected swarm metadata for the file
+                    let mut expected_scrape_data = ScrapeData::empty();
+                    expected_scrape_data.add_file(
+                        &info_hash,
+                        SwarmMetadata {
+                            complete: 0, // the "complete" peer does not count because it was not previously known
+                            downloaded: 0,
+                            incomplete: 1, // the "incomplete" peer we have just announced
+                            downloaders: 1,
+                        },
+                    );
+
+                    assert_eq!(scrape_data, expected_scrape_data);
+                }
+
+                #[tokio::test]
+                async fn it_should_allow_scraping_for_multiple_torrents() {
+                    let tracker = public_tracker();
+
+                    let info_hashes = vec![
+                        "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap(),
+                        "99c82bb73505a3c0b453f9fa0e881d6e5a32a0c1".parse::<InfoHash>().unwrap(),
+                    ];
+
+                    let scrape_data = tracker.scrape(&info_hashes, None).await;
+
+                    let mut expected_scrape_data = ScrapeData::empty();
+                    expected_scrape_data.add_file_with_zeroed_metadata(&info_hashes[0]);
+                    expected_scrape_data.add_file_with_zeroed_metadata(&info_hashes[1]);
+
+                    assert_eq!(scrape_data, expected_scrape_data);
+                }
             }
         }
 
-        /// Sample peer when for tests that need more than one peer
-        fn sample_peer_2() -> Peer {
-            Peer {
-                peer_id: PeerId(*b"-qB00000000000000002"),
-                peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 2)), 8082),
-                updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
-                uploaded: NumberOfBytes::new(0),
-                downloaded: NumberOfBytes::new(0),
-                left: NumberOfBytes::new(0),
-                event: AnnounceEvent::Completed,
+        mod handling_the_warning_messages {
+
+            use torrust_tracker_test_helpers::configuration;
+
+            use crate::core::services::tracker_factory;
+            use crate::core::tests::the_tracker::{public_tracker, sample_info_hash};
+
+            #[tokio::test]
+            async fn it_should_not_return_a_warning_message_by_default() {
+                let tracker = public_tracker();
+
+                assert_eq!(tracker.get_warning_message(&sample_info_hash()), None);
             }
-        }
 
-        fn seeder() -> Peer {
-            complete_peer()
-        }
+            #[tokio::test]
+            async fn it_should_return_the_general_warning_message_for_all_the_torrents() {
+                let mut configuration = configuration::ephemeral_public();
+                configuration.core.warning_message = Some("General warning".to_string());
+                let tracker = tracker_factory(&configuration);
+
+                assert_eq!(
+                    tracker.get_warning_message(&sample_info_hash()),
+                    Some("General warning".to_string())
+                );
+            }
 
-        fn leecher() -> Peer {
-            incomplete_peer()
+            #[tokio::test]
+            async fn it_should_prefer_the_warning_message_configured_for_the_torrent() {
+                let mut configuration = configuration::ephemeral_public();
+                configuration.core.warning_message = Some("General warning".to_string());
+                configuration
+                    .core
+                    .torrent_warning_messages
+                    .insert(sample_info_hash().to_hex_string(), "Torrent warning".to_string());
+                let tracker = tracker_factory(&configuration);
+
+                assert_eq!(
+                    tracker.get_warning_message(&sample_info_hash()),
+                    Some("Torrent warning".to_string())
+                );
+            }
         }
 
-        fn started_peer() -> Peer {
-            incomplete_peer()
-        }
+        mod handling_the_min_announce_interval {
 
-        fn completed_peer() -> Peer {
-            complete_peer()
-        }
+            use std::time::Duration;
 
-        /// A peer that counts as `complete` is swarm metadata
-        /// IMPORTANT!: it only counts if the it has been announce at least once before
-        /// announcing the `AnnounceEvent::Completed` event.
-        fn complete_peer() -> Peer {
-            Peer {
-                peer_id: PeerId(*b"-qB00000000000000000"),
-                peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080),
-                updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
-                uploaded: NumberOfBytes::new(0),
-                downloaded: NumberOfBytes::new(0),
-                left: NumberOfBytes::new(0), // No bytes left to download
-                event: AnnounceEvent::Completed,
+            use aquatic_udp_protocol::AnnounceEvent;
+            use torrust_tracker_configuration::IntervalMinAction;
+            use torrust_tracker_test_helpers::configuration;
+
+            use crate::core::error::Error;
+            use crate::core::services::tracker_factory;
+            use crate::core::tests::the_tracker::{leecher, peer_ip, public_tracker, sample_info_hash};
+            use crate::core::{PeersWanted, Tracker};
+
+            fn tracker_with_interval_min_action(action: IntervalMinAction) -> Tracker {
+                let mut configuration = configuration::ephemeral_public();
+                configuration.core.announce_policy.interval_min = 120;
+                configuration.core.announce_policy.interval_min_action = action;
+                tracker_factory(&configuration)
             }
-        }
 
-        /// A peer that counts as `incomplete` is swarm metadata
-        fn incomplete_peer() -> Peer {
-            Peer {
-                peer_id: PeerId(*b"-qB00000000000000000"),
-                peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080),
-                updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
-                uploaded: NumberOfBytes::new(0),
-                downloaded: NumberOfBytes::new(0),
-                left: NumberOfBytes::new(1000), // Still bytes to download
-                event: AnnounceEvent::Started,
+            #[tokio::test]
+            async fn it_should_not_check_the_min_interval_by_default() {
+                let tracker = public_tracker();
+
+                let mut peer = leecher();
+                tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+
+                assert!(tracker.check_announce_interval(&sample_info_hash(), &peer).is_ok());
+            }
+
+            #[tokio::test]
+            async fn it_should_accept_the_first_announce_of_a_peer() {
+                let tracker = tracker_with_interval_min_action(IntervalMinAction::Reject);
+
+                assert!(tracker.check_announce_interval(&sample_info_hash(), &leecher()).is_ok());
+            }
+
+            #[tokio::test]
+            async fn it_should_reject_an_announce_received_before_the_min_interval() {
+                let tracker = tracker_with_interval_min_action(IntervalMinAction::Reject);
+
+                let mut peer = leecher();
+                tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+
+                let mut next_announce = leecher();
+                next_announce.event = AnnounceEvent::None;
+                next_announce.updated = peer.updated + Duration::from_secs(20);
+
+                let result = tracker.check_announce_interval(&sample_info_hash(), &next_announce);
+
+                assert!(matches!(result, Err(Error::AnnounceTooFrequent { retry_in: 100, .. })));
+            }
+
+            #[tokio::test]
+            async fn it_should_accept_an_announce_received_after_the_min_interval() {
+                let tracker = tracker_with_interval_min_action(IntervalMinAction::Reject);
+
+                let mut peer = leecher();
+                tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+
+                let mut next_announce = leecher();
+                next_announce.event = AnnounceEvent::None;
+                next_announce.updated = peer.updated + Duration::from_secs(120);
+
+                assert!(tracker.check_announce_interval(&sample_info_hash(), &next_announce).is_ok());
+            }
+
+            #[tokio::test]
+            async fn it_should_always_accept_an_announce_changing_the_event() {
+                let tracker = tracker_with_interval_min_action(IntervalMinAction::Reject);
+
+                let mut peer = leecher();
+                tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+
+                let mut next_announce = leecher();
+                next_announce.event = AnnounceEvent::Stopped;
+
+                assert!(tracker.check_announce_interval(&sample_info_hash(), &next_announce).is_ok());
+            }
+
+            #[tokio::test]
+            async fn it_should_return_the_announce_data_without_updating_the_swarm() {
+                let tracker = tracker_with_interval_min_action(IntervalMinAction::Cached);
+
+                let mut peer = leecher();
+                tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+
+                let mut next_announce = leecher();
+                next_announce.event = AnnounceEvent::None;
+                next_announce.updated = peer.updated + Duration::from_secs(20);
+
+                let announce_data =
+                    tracker.get_announce_data(&sample_info_hash(), &mut next_announce, &peer_ip(), &PeersWanted::All);
+
+                assert_eq!(announce_data.stats.incomplete, 1);
+                assert_eq!(tracker.get_torrent_peers(&sample_info_hash())[0].updated, peer.updated);
             }
         }
 
-        #[tokio::test]
-        async fn should_collect_torrent_metrics() {
-            let tracker = public_tracker();
+        mod handling_the_maintenance_mode {
 
-            let torrents_metrics = tracker.get_torrents_metrics();
+            use torrust_tracker_test_helpers::configuration;
 
-            assert_eq!(
-                torrents_metrics,
-                TorrentsMetrics {
-                    complete: 0,
-                    downloaded: 0,
-                    incomplete: 0,
-                    torrents: 0
-                }
-            );
+            use crate::core::error::Error;
+            use crate::core::services::tracker_factory;
+            use crate::core::tests::the_tracker::public_tracker;
+
+            #[tokio::test]
+            async fn it_should_not_be_in_maintenance_mode_by_default() {
+                let tracker = public_tracker();
+
+                assert!(!tracker.is_in_maintenance());
+                assert!(tracker.check_availability().is_ok());
+            }
+
+            #[tokio::test]
+            async fn it_should_start_in_maintenance_mode_when_it_is_enabled_in_the_configuration() {
+                let mut configuration = configuration::ephemeral_public();
+                configuration.core.maintenance.enabled = true;
+                let tracker = tracker_factory(&configuration);
+
+                assert!(tracker.is_in_maintenance());
+            }
+
+            #[tokio::test]
+            async fn it_should_tell_the_clients_when_to_retry_while_it_is_in_maintenance_mode() {
+                let mut configuration = configuration::ephemeral_public();
+                configuration.core.maintenance.retry_in = 5;
+                let tracker = tracker_factory(&configuration);
+
+                tracker.set_maintenance(true);
+
+                assert!(matches!(
+                    tracker.check_availability(),
+                    Err(Error::TrackerInMaintenance { retry_in: 5, .. })
+                ));
+            }
+
+            #[tokio::test]
+            async fn it_should_allow_disabling_the_maintenance_mode() {
+                let tracker = public_tracker();
+
+                tracker.set_maintenance(true);
+                tracker.set_maintenance(false);
+
+                assert!(tracker.check_availability().is_ok());
+            }
         }
 
-        #[tokio::test]
-        async fn it_should_return_the_peers_for_a_given_torrent() {
-            let tracker = public_tracker();
+        mod handling_the_client_filter {
 
-            let info_hash = sample_info_hash();
-            let peer = sample_peer();
+            use std::str::FromStr;
+
+            use aquatic_udp_protocol::PeerId;
+            use torrust_tracker_configuration::ClientFilter;
+            use torrust_tracker_test_helpers::configuration;
+
+            use crate::core::client_filter::PeerIdPrefix;
+            use crate::core::error::Error;
+            use crate::core::services::tracker_factory;
+            use crate::core::tests::the_tracker::public_tracker;
+            use crate::core::Tracker;
+
+            fn tracker_with_client_filter(client_filter: ClientFilter) -> Tracker {
+                let mut configuration = configuration::ephemeral_public();
+                configuration.core.client_filter = client_filter;
+                tracker_factory(&configuration)
+            }
+
+            fn qbittorrent() -> PeerIdPrefix {
+                PeerIdPrefix::from_str("-qB").unwrap()
+            }
+
+            fn qbittorrent_peer_id() -> PeerId {
+                PeerId(*b"-qB4500-000000000000")
+            }
+
+            fn transmission_peer_id() -> PeerId {
+                PeerId(*b"-TR3000-000000000000")
+            }
+
+            #[tokio::test]
+            async fn it_should_allow_all_the_clients_when_the_filter_is_disabled() {
+                let tracker = public_tracker();
 
-            tracker.upsert_peer_and_get_stats(&info_hash, &peer);
+                tracker.add_client_prefix(&qbittorrent()).unwrap();
 
-            let peers = tracker.get_torrent_peers(&info_hash);
+                assert!(tracker.check_client(&qbittorrent_peer_id()).is_ok());
+                assert!(tracker.check_client(&transmission_peer_id()).is_ok());
+            }
 
-            assert_eq!(peers, vec![Arc::new(peer)]);
-        }
+            #[tokio::test]
+            async fn it_should_only_allow_the_clients_with_a_listed_prefix_in_allow_mode() {
+                let tracker = tracker_with_client_filter(ClientFilter::Allow);
 
-        /// It generates a peer id from a number where the number is the last
-        /// part of the peer ID. For example, for `12` it returns
-        /// `-qB00000000000000012`.
-        fn numeric_peer_id(two_digits_value: i32) -> PeerId {
-            // Format idx as a string with leading zeros, ensuring it has exactly 2 digits
-            let idx_str = format!("{two_digits_value:02}");
+                tracker.add_client_prefix(&qbittorrent()).unwrap();
 
-            // Create the base part of the peer ID.
-            let base = b"-qB00000000000000000";
+                assert!(tracker.check_client(&qbittorrent_peer_id()).is_ok());
+                assert!(matches!(
+                    tracker.check_client(&transmission_peer_id()),
+                    Err(Error::ClientNotAllowed { .. })
+                ));
+            }
 
-            // Concatenate the base with idx bytes, ensuring the total length is 20 bytes.
-            let mut peer_id_bytes = [0u8; 20];
-            peer_id_bytes[..base.len()].copy_from_slice(base);
-            peer_id_bytes[base.len() - idx_str.len()..].copy_from_slice(idx_str.as_bytes());
+            #[tokio::test]
+            async fn it_should_reject_the_clients_with_a_listed_prefix_in_deny_mode() {
+                let tracker = tracker_with_client_filter(ClientFilter::Deny);
 
-            PeerId(peer_id_bytes)
-        }
+                tracker.add_client_prefix(&qbittorrent()).unwrap();
 
-        #[tokio::test]
-        async fn it_should_return_74_peers_at_the_most_for_a_given_torrent() {
-            let tracker = public_tracker();
+                assert!(tracker.check_client(&transmission_peer_id()).is_ok());
+                assert!(matches!(
+                    tracker.check_client(&qbittorrent_peer_id()),
+                    Err(Error::ClientNotAllowed { .. })
+                ));
+            }
 
-            let info_hash = sample_info_hash();
+            #[tokio::test]
+            async fn it_should_describe_the_rejected_client() {
+                let tracker = tracker_with_client_filter(ClientFilter::Deny);
 
-            for idx in 1..=75 {
-                let peer = Peer {
-                    peer_id: numeric_peer_id(idx),
-                    peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, idx.try_into().unwrap())), 8080),
-                    updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
-                    uploaded: NumberOfBytes::new(0),
-                    downloaded: NumberOfBytes::new(0),
-                    left: NumberOfBytes::new(0), // No bytes left to download
-                    event: AnnounceEvent::Completed,
+                tracker.add_client_prefix(&qbittorrent()).unwrap();
+
+                let Err(Error::ClientNotAllowed { client, .. }) = tracker.check_client(&qbittorrent_peer_id()) else {
+                    panic!("the client should be rejected");
                 };
 
-                tracker.upsert_peer_and_get_stats(&info_hash, &peer);
+                assert_eq!(client, "qBittorrent (-qB4500-)");
             }
 
-            let peers = tracker.get_torrent_peers(&info_hash);
+            #[tokio::test]
+            async fn it_should_allow_removing_a_prefix() {
+                let tracker = tracker_with_client_filter(ClientFilter::Deny);
 
-            assert_eq!(peers.len(), 74);
-        }
+                tracker.add_client_prefix(&qbittorrent()).unwrap();
+                tracker.remove_client_prefix(&qbittorrent()).unwrap();
 
-        #[tokio::test]
-        async fn it_should_return_the_peers_for_a_given_torrent_excluding_a_given_peer() {
-            let tracker = public_tracker();
+                assert!(tracker.get_client_prefixes().is_empty());
+                assert!(tracker.check_client(&qbittorrent_peer_id()).is_ok());
+            }
 
-            let info_hash = sample_info_hash();
-            let peer = sample_peer();
+            #[tokio::test]
+            async fn it_should_load_the_prefixes_from_the_database() {
+                let tracker = tracker_with_client_filter(ClientFilter::Deny);
 
-            tracker.upsert_peer_and_get_stats(&info_hash, &peer);
+                tracker.add_client_prefix(&qbittorrent()).unwrap();
+                tracker.client_prefixes.write().unwrap().clear();
 
-            let peers = tracker.get_peers_for(&info_hash, &peer, TORRENT_PEERS_LIMIT);
+                tracker.load_client_prefixes_from_database().unwrap();
 
-            assert_eq!(peers, vec![]);
+                assert_eq!(tracker.get_client_prefixes(), vec![qbittorrent()]);
+            }
         }
 
-        #[tokio::test]
-        async fn it_should_return_74_peers_at_the_most_for_a_given_torrent_when_it_filters_out_a_given_peer() {
-            let tracker = public_tracker();
-
-            let info_hash = sample_info_hash();
+        mod handling_the_ip_blocklist {
 
-            let excluded_peer = sample_peer();
+            use std::net::IpAddr;
+            use std::str::FromStr;
 
-            tracker.upsert_peer_and_get_stats(&info_hash, &excluded_peer);
+            use ipnet::IpNet;
+            use torrust_tracker_configuration::IpBlocklist;
+            use torrust_tracker_test_helpers::configuration;
 
-            // Add 74 peers
-            for idx in 2..=75 {
-                let peer = Peer {
-                    peer_id: numeric_peer_id(idx),
-                    peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, idx.try_into().unwrap())), 8080),
-                    updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
-                    uploaded: NumberOfBytes::new(0),
-                    downloaded: NumberOfBytes::new(0),
-                    left: NumberOfBytes::new(0), // No bytes left to download
-                    event: AnnounceEvent::Completed,
-                };
+            use crate::core::error::Error;
+            use crate::core::services::tracker_factory;
+            use crate::core::tests::the_tracker::public_tracker;
+            use crate::core::Tracker;
 
-                tracker.upsert_peer_and_get_stats(&info_hash, &peer);
+            fn network(network: &str) -> IpNet {
+                IpNet::from_str(network).unwrap()
             }
 
-            let peers = tracker.get_peers_for(&info_hash, &excluded_peer, TORRENT_PEERS_LIMIT);
+            fn ip(ip: &str) -> IpAddr {
+                IpAddr::from_str(ip).unwrap()
+            }
 
-            assert_eq!(peers.len(), 74);
-        }
+            fn tracker_with_ip_blocklist_file(contents: &str) -> Tracker {
+                let path = std::env::temp_dir().join(format!("ip_blocklist_{}.txt", uuid::Uuid::new_v4()));
+                std::fs::write(&path, contents).unwrap();
 
-        #[tokio::test]
-        async fn it_should_return_the_torrent_metrics() {
-            let tracker = public_tracker();
+                let mut configuration = configuration::ephemeral_public();
+                configuration.core.ip_blocklist = Some(IpBlocklist {
+                    path: path.to_string_lossy().to_string(),
+                });
+                tracker_factory(&configuration)
+            }
 
-            tracker.upsert_peer_and_get_stats(&sample_info_hash(), &leecher());
+            #[tokio::test]
+            async fn it_should_allow_all_the_ips_by_default() {
+                let tracker = public_tracker();
 
-            let torrent_metrics = tracker.get_torrents_metrics();
+                assert!(tracker.check_ip(&ip("126.0.0.1")).is_ok());
+            }
 
-            assert_eq!(
-                torrent_metrics,
-                TorrentsMetrics {
-                    complete: 0,
-                    downloaded: 0,
-                    incomplete: 1,
-                    torrents: 1,
-                }
-            );
-        }
+            #[tokio::test]
+            async fn it_should_reject_the_ips_in_a_blocked_network() {
+                let tracker = public_tracker();
 
-        #[tokio::test]
-        async fn it_should_get_many_the_torrent_metrics() {
-            let tracker = public_tracker();
+                tracker.add_blocked_network(&network("126.0.0.0/8")).unwrap();
 
-            let start_time = std::time::Instant::now();
-            for i in 0..1_000_000 {
-                tracker.upsert_peer_and_get_stats(&gen_seeded_infohash(&i), &leecher());
+                assert!(matches!(tracker.check_ip(&ip("126.0.0.1")), Err(Error::IpBlocked { .. })));
+                assert!(tracker.check_ip(&ip("127.0.0.1")).is_ok());
             }
-            let result_a = start_time.elapsed();
 
-            let start_time = std::time::Instant::now();
-            let torrent_metrics = tracker.get_torrents_metrics();
-            let result_b = start_time.elapsed();
+            #[tokio::test]
+            async fn it_should_ignore_the_host_bits_of_the_blocked_networks() {
+                let tracker = public_tracker();
 
-            assert_eq!(
-                (torrent_metrics),
-                (TorrentsMetrics {
-                    complete: 0,
-                    downloaded: 0,
-                    incomplete: 1_000_000,
-                    torrents: 1_000_000,
-                }),
-                "{result_a:?} {result_b:?}"
-            );
-        }
+                tracker.add_blocked_network(&network("126.1.2.3/8")).unwrap();
 
-        mod for_all_config_modes {
+                assert_eq!(tracker.get_blocked_networks(), vec![network("126.0.0.0/8")]);
+            }
 
-            mod handling_an_announce_request {
+            #[tokio::test]
+            async fn it_should_allow_unblocking_a_network() {
+                let tracker = public_tracker();
 
-                use std::sync::Arc;
+                tracker.add_blocked_network(&network("126.0.0.0/8")).unwrap();
+                tracker.remove_blocked_network(&network("126.0.0.0/8")).unwrap();
 
-                use crate::core::tests::the_tracker::{
-                    peer_ip, public_tracker, sample_info_hash, sample_peer, sample_peer_1, sample_peer_2,
-                };
-                use crate::core::PeersWanted;
+                assert!(tracker.get_blocked_networks().is_empty());
+                assert!(tracker.check_ip(&ip("126.0.0.1")).is_ok());
+            }
 
-                mod should_assign_the_ip_to_the_peer {
+            #[tokio::test]
+            async fn it_should_load_the_blocked_networks_from_the_database() {
+                let tracker = public_tracker();
 
-                    use std::net::{IpAddr, Ipv4Addr};
+                tracker.add_blocked_network(&network("126.0.0.0/8")).unwrap();
+                tracker.blocked_networks.write().unwrap().clear();
 
-                    use crate::core::assign_ip_address_to_peer;
+                tracker.load_blocked_networks_from_database().unwrap();
 
-                    #[test]
-                    fn using_the_source_ip_instead_of_the_ip_in_the_announce_request() {
-                        let remote_ip = IpAddr::V4(Ipv4Addr::new(126, 0, 0, 2));
+                assert_eq!(tracker.get_blocked_networks(), vec![network("126.0.0.0/8")]);
+                assert!(tracker.check_ip(&ip("126.0.0.1")).is_err());
+            }
 
-                        let peer_ip = assign_ip_address_to_peer(&remote_ip, None);
+            #[tokio::test]
+            async fn it_should_load_the_blocked_networks_from_the_ip_blocklist_file() {
+                let tracker = tracker_with_ip_blocklist_file("# Comment\nSome ISP:126.0.0.0-126.0.0.255\n2001:db8::/32\n");
 
-                        assert_eq!(peer_ip, remote_ip);
-                    }
+                tracker.load_ip_blocklist_from_file().unwrap();
 
-                    mod and_when_the_client_ip_is_a_ipv4_loopback_ip {
+                std::fs::remove_file(&tracker.config.ip_blocklist.as_ref().unwrap().path).unwrap();
 
-                        use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
-                        use std::str::FromStr;
+                assert_eq!(tracker.get_ip_blocklist_file_len(), 2);
+                assert!(tracker.check_ip(&ip("126.0.0.1")).is_err());
+                assert!(tracker.check_ip(&ip("2001:db8::1")).is_err());
+                assert!(tracker.check_ip(&ip("126.0.1.1")).is_ok());
+            }
 
-                        use crate::core::assign_ip_address_to_peer;
+            #[tokio::test]
+            async fn it_should_keep_the_loaded_networks_when_the_ip_blocklist_file_is_invalid() {
+                let tracker = tracker_with_ip_blocklist_file("126.0.0.0/24");
 
-                        #[test]
-                        fn it_should_use_the_loopback_ip_if_the_tracker_does_not_have_the_external_ip_configuration() {
-                            let remote_ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+                tracker.load_ip_blocklist_from_file().unwrap();
 
-                            let peer_ip = assign_ip_address_to_peer(&remote_ip, None);
+                std::fs::write(&tracker.config.ip_blocklist.as_ref().unwrap().path, "not an ip").unwrap();
 
-                            assert_eq!(peer_ip, remote_ip);
-                        }
+                assert!(tracker.load_ip_blocklist_from_file().is_err());
 
-                        #[test]
-                        fn it_should_use_the_external_tracker_ip_in_tracker_configuration_if_it_is_defined() {
-                            let remote_ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+                std::fs::remove_file(&tracker.config.ip_blocklist.as_ref().unwrap().path).unwrap();
 
-                            let tracker_external_ip = IpAddr::V4(Ipv4Addr::from_str("126.0.0.1").unwrap());
+                assert!(tracker.check_ip(&ip("126.0.0.1")).is_err());
+            }
+        }
 
-                            let peer_ip = assign_ip_address_to_peer(&remote_ip, Some(tracker_external_ip));
+        mod handling_the_torrent_blacklist {
 
-                            assert_eq!(peer_ip, tracker_external_ip);
-                        }
+            use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
+            use torrust_tracker_test_helpers::configuration;
 
-                        #[test]
-                        fn it_should_use_the_external_ip_in_the_tracker_configuration_if_it_is_defined_even_if_the_external_ip_is_an_ipv6_ip(
-                        ) {
-                            let remote_ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+            use crate::core::error::Error;
+            use crate::core::services::tracker_factory;
+            use crate::core::tests::the_tracker::{
+                complete_peer, peer_ip, public_tracker, sample_info_hash, whitelisted_tracker,
+            };
+            use crate::core::{PeersWanted, ScrapeData};
 
-                            let tracker_external_ip =
-                                IpAddr::V6(Ipv6Addr::from_str("2345:0425:2CA1:0000:0000:0567:5673:23b5").unwrap());
+            #[tokio::test]
+            async fn it_should_authorize_the_torrents_that_are_not_blacklisted() {
+                let tracker = public_tracker();
 
-                            let peer_ip = assign_ip_address_to_peer(&remote_ip, Some(tracker_external_ip));
+                assert!(tracker.authorize(&sample_info_hash(), None).await.is_ok());
+            }
 
-                            assert_eq!(peer_ip, tracker_external_ip);
-                        }
-                    }
+            #[tokio::test]
+            async fn it_should_not_authorize_the_announce_and_scrape_actions_on_blacklisted_torrents() {
+                let tracker = public_tracker();
 
-                    mod and_when_client_ip_is_a_ipv6_loopback_ip {
+                tracker.add_torrent_to_blacklist(&sample_info_hash()).await.unwrap();
 
-                        use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
-                        use std::str::FromStr;
+                assert!(matches!(
+                    tracker.authorize(&sample_info_hash(), None).await,
+                    Err(Error::TorrentBlacklisted { .. })
+                ));
+            }
 
-                        use crate::core::assign_ip_address_to_peer;
+            #[tokio::test]
+            async fn it_should_not_authorize_the_blacklisted_torrents_even_if_they_are_whitelisted() {
+                let tracker = whitelisted_tracker();
 
-                        #[test]
-                        fn it_should_use_the_loopback_ip_if_the_tracker_does_not_have_the_external_ip_configuration() {
-                            let remote_ip = IpAddr::V6(Ipv6Addr::LOCALHOST);
+                tracker.add_torrent_to_whitelist(&sample_info_hash()).await.unwrap();
+                tracker.add_torrent_to_blacklist(&sample_info_hash()).await.unwrap();
 
-                            let peer_ip = assign_ip_address_to_peer(&remote_ip, None);
+                assert!(matches!(
+                    tracker.authorize(&sample_info_hash(), None).await,
+                    Err(Error::TorrentBlacklisted { .. })
+                ));
+            }
 
-                            assert_eq!(peer_ip, remote_ip);
-                        }
+            #[tokio::test]
+            async fn it_should_include_the_configured_reason_in_the_error() {
+                let mut configuration = configuration::ephemeral_public();
+                configuration.core.blacklist.reason = "DMCA takedown".to_string();
+                let tracker = tracker_factory(&configuration);
 
-                        #[test]
-                        fn it_should_use_the_external_ip_in_tracker_configuration_if_it_is_defined() {
-                            let remote_ip = IpAddr::V6(Ipv6Addr::LOCALHOST);
+                tracker.add_torrent_to_blacklist(&sample_info_hash()).await.unwrap();
 
-                            let tracker_external_ip =
-                                IpAddr::V6(Ipv6Addr::from_str("2345:0425:2CA1:0000:0000:0567:5673:23b5").unwrap());
+                let Err(Error::TorrentBlacklisted { reason, .. }) = tracker.authorize(&sample_info_hash(), None).await else {
+                    panic!("the torrent should be refused");
+                };
 
-                            let peer_ip = assign_ip_address_to_peer(&remote_ip, Some(tracker_external_ip));
+                assert_eq!(reason, "DMCA takedown");
+            }
 
-                            assert_eq!(peer_ip, tracker_external_ip);
-                        }
+            #[tokio::test]
+            async fn it_should_remove_a_torrent_from_the_blacklist() {
+                let tracker = public_tracker();
 
-                        #[test]
-                        fn it_should_use_the_external_ip_in_the_tracker_configuration_if_it_is_defined_even_if_the_external_ip_is_an_ipv4_ip(
-                        ) {
-                            let remote_ip = IpAddr::V6(Ipv6Addr::LOCALHOST);
+                tracker.add_torrent_to_blacklist(&sample_info_hash()).await.unwrap();
+                tracker.remove_torrent_from_blacklist(&sample_info_hash()).await.unwrap();
 
-                            let tracker_external_ip = IpAddr::V4(Ipv4Addr::from_str("126.0.0.1").unwrap());
+                assert!(!tracker.is_info_hash_blacklisted(&sample_info_hash()).await);
+                assert!(tracker.authorize(&sample_info_hash(), None).await.is_ok());
+            }
 
-                            let peer_ip = assign_ip_address_to_peer(&remote_ip, Some(tracker_external_ip));
+            #[tokio::test]
+            async fn it_should_load_the_blacklist_from_the_database() {
+                let tracker = public_tracker();
 
-                            assert_eq!(peer_ip, tracker_external_ip);
-                        }
-                    }
-                }
+                tracker.add_torrent_to_blacklist(&sample_info_hash()).await.unwrap();
 
-                #[tokio::test]
-                async fn it_should_return_the_announce_data_with_an_empty_peer_list_when_it_is_the_first_announced_peer() {
-                    let tracker = public_tracker();
+                // Remove torrent from the in-memory blacklist
+                tracker.blacklist.write().await.remove(&sample_info_hash());
+                assert!(!tracker.is_info_hash_blacklisted(&sample_info_hash()).await);
 
-                    let mut peer = sample_peer();
+                tracker.load_blacklist_from_database().await.unwrap();
 
-                    let announce_data = tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+                assert!(tracker.is_info_hash_blacklisted(&sample_info_hash()).await);
+            }
 
-                    assert_eq!(announce_data.peers, vec![]);
-                }
+            #[tokio::test]
+            async fn it_should_return_the_zeroed_swarm_metadata_for_the_blacklisted_torrents() {
+                let tracker = public_tracker();
 
-                #[tokio::test]
-                async fn it_should_return_the_announce_data_with_the_previously_announced_peers() {
-                    let tracker = public_tracker();
+                let mut peer = complete_peer();
+                tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
 
-                    let mut previously_announced_peer = sample_peer_1();
-                    tracker.announce(
-                        &sample_info_hash(),
-                        &mut previously_announced_peer,
-                        &peer_ip(),
-                        &PeersWanted::All,
-                    );
+                tracker.add_torrent_to_blacklist(&sample_info_hash()).await.unwrap();
 
-                    let mut peer = sample_peer_2();
-                    let announce_data = tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+                let scrape_data = tracker.scrape(&vec![sample_info_hash()], None).await;
 
-                    assert_eq!(announce_data.peers, vec![Arc::new(previously_announced_peer)]);
-                }
+                let mut expected_scrape_data = ScrapeData::empty();
+                expected_scrape_data.add_file(&sample_info_hash(), SwarmMetadata::zeroed());
 
-                mod it_should_update_the_swarm_stats_for_the_torrent {
+                assert_eq!(scrape_data, expected_scrape_data);
+            }
+        }
 
-                    use crate::core::tests::the_tracker::{
-                        completed_peer, leecher, peer_ip, public_tracker, sample_info_hash, seeder, started_peer,
-                    };
-                    use crate::core::PeersWanted;
+        mod handling_partial_seeds {
 
-                    #[tokio::test]
-                    async fn when_the_peer_is_a_seeder() {
-                        let tracker = public_tracker();
+            use std::net::SocketAddr;
+            use std::sync::Arc;
 
-                        let mut peer = seeder();
+            use aquatic_udp_protocol::PeerId;
+            use torrust_tracker_primitives::peer::Peer;
 
-                        let announce_data = tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+            use crate::core::tests::the_tracker::{leecher, peer_ip, public_tracker, sample_info_hash};
+            use crate::core::PeersWanted;
 
-                        assert_eq!(announce_data.stats.complete, 1);
-                    }
+            fn leecher_with_id(id: u8) -> Peer {
+                let mut peer = leecher();
+                peer.peer_id = PeerId(*b"-qB00000000000000000");
+                peer.peer_id.0[19] = id;
+                peer.peer_addr = SocketAddr::new(peer_ip(), 8080 + u16::from(id));
+                peer
+            }
 
-                    #[tokio::test]
-                    async fn when_the_peer_is_a_leecher() {
-                        let tracker = public_tracker();
+            fn partial_seed_with_id(id: u8) -> Peer {
+                let mut peer = leecher_with_id(id);
+                peer.upload_only = true;
+                peer
+            }
+
+            #[tokio::test]
+            async fn it_should_not_count_the_partial_seeds_as_downloaders() {
+                let tracker = public_tracker();
 
-                        let mut peer = leecher();
+                let mut peer = leecher_with_id(1);
+                tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
 
-                        let announce_data = tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+                let mut peer = partial_seed_with_id(2);
+                tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
 
-                        assert_eq!(announce_data.stats.incomplete, 1);
-                    }
+                let swarm_metadata = tracker.get_swarm_metadata(&sample_info_hash());
 
-                    #[tokio::test]
-                    async fn when_a_previously_announced_started_peer_has_completed_downloading() {
-                        let tracker = public_tracker();
+                assert_eq!(swarm_metadata.incomplete, 2);
+                assert_eq!(swarm_metadata.downloaders, 1);
+            }
 
-                        // We have to announce with "started" event because peer does not count if peer was not previously known
-                        let mut started_peer = started_peer();
-                        tracker.announce(&sample_info_hash(), &mut started_peer, &peer_ip(), &PeersWanted::All);
+            #[tokio::test]
+            async fn it_should_not_return_upload_only_peers_to_each_other() {
+                let tracker = public_tracker();
 
-                        let mut completed_peer = completed_peer();
-                        let announce_data =
-                            tracker.announce(&sample_info_hash(), &mut completed_peer, &peer_ip(), &PeersWanted::All);
+                let mut partial_seed = partial_seed_with_id(1);
+                tracker.announce(&sample_info_hash(), &mut partial_seed, &peer_ip(), &PeersWanted::All);
 
-                        assert_eq!(announce_data.stats.downloaded, 1);
-                    }
-                }
+                let mut another_partial_seed = partial_seed_with_id(2);
+                let announce_data =
+                    tracker.announce(&sample_info_hash(), &mut another_partial_seed, &peer_ip(), &PeersWanted::All);
+
+                assert!(announce_data.peers.is_empty());
             }
 
-            mod handling_a_scrape_request {
+            #[tokio::test]
+            async fn it_should_return_the_partial_seeds_to_the_downloaders() {
+                let tracker = public_tracker();
 
-                use std::net::{IpAddr, Ipv4Addr};
+                let mut partial_seed = partial_seed_with_id(1);
+                tracker.announce(&sample_info_hash(), &mut partial_seed, &peer_ip(), &PeersWanted::All);
 
-                use bittorrent_primitives::info_hash::InfoHash;
+                let mut downloader = leecher_with_id(2);
+                let announce_data = tracker.announce(&sample_info_hash(), &mut downloader, &peer_ip(), &PeersWanted::All);
 
-                use crate::core::tests::the_tracker::{complete_peer, incomplete_peer, public_tracker};
-                use crate::core::{PeersWanted, ScrapeData, SwarmMetadata};
+                assert_eq!(announce_data.peers, vec![Arc::new(partial_seed)]);
+            }
+        }
 
-                #[tokio::test]
-                async fn it_should_return_a_zeroed_swarm_metadata_for_the_requested_file_if_the_tracker_does_not_have_that_torrent(
-                ) {
-                    let tracker = public_tracker();
+        mod handling_hybrid_torrents {
 
-                    let info_hashes = vec!["3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap()];
+            use std::str::FromStr;
 
-                    let scrape_data = tracker.scrape(&info_hashes).await;
+            use torrust_tracker_primitives::info_hash_v2::InfoHashV2;
 
-                    let mut expected_scrape_data = ScrapeData::empty();
+            use crate::core::tests::the_tracker::{
+                peer_ip, public_tracker, sample_info_hash, sample_peer_1, sample_peer_2, whitelisted_tracker,
+            };
+            use crate::core::PeersWanted;
 
-                    expected_scrape_data.add_file_with_zeroed_metadata(&info_hashes[0]);
+            fn sample_info_hash_v2() -> InfoHashV2 {
+                InfoHashV2::from_str("99c82bb73505a3c0b453f9fa0e881d6e5a32a0c1b8a1a4d58d14d7a21b7d7a45").unwrap()
+            }
 
-                    assert_eq!(scrape_data, expected_scrape_data);
-                }
+            #[tokio::test]
+            async fn it_should_resolve_the_truncated_v2_info_hash_to_the_v1_info_hash() {
+                let tracker = public_tracker();
+
+                tracker
+                    .add_hybrid_torrent(&sample_info_hash(), &sample_info_hash_v2())
+                    .unwrap();
+
+                assert_eq!(
+                    tracker.resolve_info_hash(&sample_info_hash_v2().truncated()),
+                    sample_info_hash()
+                );
+                assert_eq!(tracker.resolve_info_hash(&sample_info_hash()), sample_info_hash());
+            }
 
-                #[tokio::test]
-                async fn it_should_return_the_swarm_metadata_for_the_requested_file_if_the_tracker_has_that_torrent() {
-                    let tracker = public_tracker();
+            #[tokio::test]
+            async fn it_should_put_the_peers_announcing_the_v1_and_the_v2_info_hashes_in_the_same_swarm() {
+                let tracker = public_tracker();
 
-                    let info_hash = "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap();
+                tracker
+                    .add_hybrid_torrent(&sample_info_hash(), &sample_info_hash_v2())
+                    .unwrap();
 
-                    // Announce a "complete" peer for the torrent
-                    let mut complete_peer = complete_peer();
-                    tracker.announce(
-                        &info_hash,
-                        &mut complete_peer,
-                        &IpAddr::V4(Ipv4Addr::new(126, 0, 0, 10)),
-                        &PeersWanted::All,
-                    );
+                let mut peer = sample_peer_1();
+                tracker.announce(&sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
 
-                    // Announce an "incomplete" peer for the torrent
-                    let mut incomplete_peer = incomplete_peer();
-                    tracker.announce(
-                        &info_hash,
-                        &mut incomplete_peer,
-                        &IpAddr::V4(Ipv4Addr::new(126, 0, 0, 11)),
-                        &PeersWanted::All,
-                    );
+                let mut peer = sample_peer_2();
+                let announce_data =
+                    tracker.announce(&sample_info_hash_v2().truncated(), &mut peer, &peer_ip(), &PeersWanted::All);
 
-                    // Scrape
-                    let scrape_data = tracker.scrape(&vec![info_hash]).await;
+                assert_eq!(announce_data.peers.len(), 1);
+                assert_eq!(tracker.get_torrent_peers(&sample_info_hash()).len(), 2);
+            }
 
-                    // The expected swarm metadata for the file
-                    let mut expected_scrape_data = ScrapeData::empty();
-                    expected_scrape_data.add_file(
-                        &info_hash,
-                        SwarmMetadata {
-                            complete: 0, // the "complete" peer does not count because it was not previously known
-                            downloaded: 0,
-                            incomplete: 1, // the "incomplete" peer we have just announced
-                        },
-                    );
+            #[tokio::test]
+            async fn it_should_stop_resolving_the_v2_info_hash_after_removing_the_hybrid_torrent() {
+                let tracker = public_tracker();
+
+                tracker
+                    .add_hybrid_torrent(&sample_info_hash(), &sample_info_hash_v2())
+                    .unwrap();
+                tracker.remove_hybrid_torrent(&sample_info_hash_v2()).unwrap();
+
+                assert_eq!(
+                    tracker.resolve_info_hash(&sample_info_hash_v2().truncated()),
+                    sample_info_hash_v2().truncated()
+                );
+            }
 
-                    assert_eq!(scrape_data, expected_scrape_data);
-                }
+            #[tokio::test]
+            async fn it_should_load_the_hybrid_torrents_from_the_database() {
+                let tracker = public_tracker();
 
-                #[tokio::test]
-                async fn it_should_allow_scraping_for_multiple_torrents() {
-                    let tracker = public_tracker();
+                tracker
+                    .add_hybrid_torrent(&sample_info_hash(), &sample_info_hash_v2())
+                    .unwrap();
+                tracker.hybrid_torrents.write().unwrap().clear();
 
-                    let info_hashes = vec![
-                        "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap(),
-                        "99c82bb73505a3c0b453f9fa0e881d6e5a32a0c1".parse::<InfoHash>().unwrap(),
-                    ];
+                tracker.load_hybrid_torrents_from_database().unwrap();
 
-                    let scrape_data = tracker.scrape(&info_hashes).await;
+                assert_eq!(
+                    tracker.resolve_info_hash(&sample_info_hash_v2().truncated()),
+                    sample_info_hash()
+                );
+            }
 
-                    let mut expected_scrape_data = ScrapeData::empty();
-                    expected_scrape_data.add_file_with_zeroed_metadata(&info_hashes[0]);
-                    expected_scrape_data.add_file_with_zeroed_metadata(&info_hashes[1]);
+            #[tokio::test]
+            async fn it_should_authorize_the_v2_info_hash_when_the_v1_info_hash_is_whitelisted() {
+                let tracker = whitelisted_tracker();
 
-                    assert_eq!(scrape_data, expected_scrape_data);
-                }
+                tracker.add_torrent_to_whitelist(&sample_info_hash()).await.unwrap();
+                tracker
+                    .add_hybrid_torrent(&sample_info_hash(), &sample_info_hash_v2())
+                    .unwrap();
+
+                assert!(tracker.authorize(&sample_info_hash_v2().truncated(), None).await.is_ok());
             }
         }
 
@@ -1814,7 +4352,7 @@ mod tests {
                     let result = tracker.add_torrent_to_whitelist(&info_hash).await;
                     assert!(result.is_ok());
 
-                    let result = tracker.authorize(&info_hash).await;
+                    let result = tracker.authorize(&info_hash, None).await;
                     assert!(result.is_ok());
                 }
 
@@ -1824,7 +4362,7 @@ mod tests {
 
                     let info_hash = sample_info_hash();
 
-                    let result = tracker.authorize(&info_hash).await;
+                    let result = tracker.authorize(&info_hash, None).await;
                     assert!(result.is_err());
                 }
             }
@@ -1915,7 +4453,7 @@ mod tests {
                     let mut peer = complete_peer();
                     tracker.announce(&info_hash, &mut peer, &peer_ip(), &PeersWanted::All);
 
-                    let scrape_data = tracker.scrape(&vec![info_hash]).await;
+                    let scrape_data = tracker.scrape(&vec![info_hash], None).await;
 
                     // The expected zeroed swarm metadata for the file
                     let mut expected_scrape_data = ScrapeData::empty();
@@ -2169,6 +4707,315 @@ mod tests {
                 }
             }
 
+            mod handling_the_transfer_accounting {
+                use std::net::{IpAddr, Ipv4Addr};
+                use std::str::FromStr;
+                use std::time::Duration;
+
+                use aquatic_udp_protocol::{NumberOfBytes, PeerKey};
+                use torrust_tracker_primitives::peer::Peer;
+
+                use crate::core::auth::Key;
+                use crate::core::ratio::{KeyStats, Multipliers};
+                use crate::core::tests::the_tracker::{leecher, peer_ip, private_tracker, sample_info_hash};
+                use crate::core::PeersWanted;
+
+                fn sample_key() -> Key {
+                    Key::from_str("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ").unwrap()
+                }
+
+                fn leecher_that_has_transferred(uploaded: i64, downloaded: i64) -> Peer {
+                    let mut peer = leecher();
+                    peer.uploaded = NumberOfBytes::new(uploaded);
+                    peer.downloaded = NumberOfBytes::new(downloaded);
+                    peer
+                }
+
+                #[tokio::test]
+                async fn it_should_add_the_transfer_between_two_announces_of_a_peer_to_the_key_stats() {
+                    let tracker = private_tracker();
+
+                    for mut peer in [leecher_that_has_transferred(100, 200), leecher_that_has_transferred(150, 500)] {
+                        tracker.announce_with_key(&sample_key(), &sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+                    }
+
+                    let stats = tracker.get_key_stats(&sample_key());
+
+                    assert_eq!((stats.uploaded, stats.downloaded), (150, 500));
+                    assert_eq!(stats.ratio(), Some(0.3));
+                }
+
+                #[tokio::test]
+                async fn it_should_not_account_the_announces_that_are_not_applied_to_the_swarm() {
+                    let tracker = private_tracker();
+
+                    let mut peer = leecher_that_has_transferred(100, 200);
+                    peer.key = Some(PeerKey::new(42));
+                    tracker.announce_with_key(&sample_key(), &sample_info_hash(), &mut peer, &peer_ip(), &PeersWanted::All);
+
+                    // Another client using the same peer id, but without the peer key
+                    let mut hijacker = leecher_that_has_transferred(10_000, 20_000);
+                    hijacker.key = None;
+                    let hijacker_ip = IpAddr::V4(Ipv4Addr::new(126, 0, 0, 2));
+                    tracker.announce_with_key(
+                        &sample_key(),
+                        &sample_info_hash(),
+                        &mut hijacker,
+                        &hijacker_ip,
+                        &PeersWanted::All,
+                    );
+
+                    let stats = tracker.get_key_stats(&sample_key());
+
+                    assert_eq!((stats.uploaded, stats.downloaded), (100, 200));
+                }
+
+                #[tokio::test]
+                async fn it_should_apply_the_torrent_multipliers_to_the_transfer() {
+                    let tracker = private_tracker();
+
+                    let freeleech_and_double_upload = Multipliers {
+                        upload: 2.0,
+                        download: 0.0,
+                    };
+
+                    tracker
+                        .set_torrent_multipliers(&sample_info_hash(), &freeleech_and_double_upload)
+                        .unwrap();
+
+                    tracker.account_transfer(
+                        &sample_key(),
+                        &sample_info_hash(),
+                        None,
+                        &leecher_that_has_transferred(100, 200),
+                    );
+
+                    let stats = tracker.get_key_stats(&sample_key());
+
+                    assert_eq!((stats.uploaded, stats.downloaded), (200, 0));
+                }
+
+                #[tokio::test]
+                async fn it_should_count_the_transfers_as_they_are_after_removing_the_torrent_multipliers() {
+                    let tracker = private_tracker();
+
+                    let freeleech = Multipliers {
+                        upload: 1.0,
+                        download: 0.0,
+                    };
+
+                    tracker.set_torrent_multipliers(&sample_info_hash(), &freeleech).unwrap();
+                    tracker.remove_torrent_multipliers(&sample_info_hash()).unwrap();
+
+                    assert_eq!(tracker.get_torrent_multipliers(&sample_info_hash()), Multipliers::default());
+                }
+
+                #[tokio::test]
+                async fn it_should_load_the_key_stats_and_the_torrent_multipliers_from_the_database() {
+                    let tracker = private_tracker();
+
+                    let freeleech = Multipliers {
+                        upload: 1.0,
+                        download: 0.0,
+                    };
+
+                    tracker.set_torrent_multipliers(&sample_info_hash(), &freeleech).unwrap();
+                    tracker.account_transfer(
+                        &sample_key(),
+                        &sample_info_hash(),
+                        None,
+                        &leecher_that_has_transferred(100, 200),
+                    );
+                    tracker.save_unsaved_key_stats().unwrap();
+
+                    // Remove the stats and the multipliers in memory
+                    tracker.key_stats.write().unwrap().clear();
+                    tracker.torrent_multipliers.write().unwrap().clear();
+
+                    tracker.load_key_stats_from_database().unwrap();
+                    tracker.load_torrent_multipliers_from_database().unwrap();
+
+                    assert_eq!(
+                        tracker.get_key_stats(&sample_key()),
+                        KeyStats {
+                            uploaded: 100,
+                            downloaded: 0,
+                            seeding_time: Duration::ZERO
+                        }
+                    );
+                    assert_eq!(tracker.get_torrent_multipliers(&sample_info_hash()), freeleech);
+                }
+
+                #[tokio::test]
+                async fn it_should_remove_the_key_stats_when_the_key_is_removed() {
+                    let tracker = private_tracker();
+
+                    let peer_key = tracker.generate_auth_key(None).await.unwrap();
+
+                    tracker.account_transfer(
+                        &peer_key.key(),
+                        &sample_info_hash(),
+                        None,
+                        &leecher_that_has_transferred(100, 200),
+                    );
+                    tracker.save_unsaved_key_stats().unwrap();
+
+                    tracker.remove_auth_key(&peer_key.key()).await.unwrap();
+
+                    assert_eq!(tracker.get_key_stats(&peer_key.key()), KeyStats::default());
+                    assert_eq!(tracker.database.load_key_stats().unwrap(), vec![]);
+                }
+            }
+
+            mod handling_the_torrent_access {
+                use std::str::FromStr;
+
+                use bittorrent_primitives::info_hash::InfoHash;
+
+                use crate::core::access::TorrentGroup;
+                use crate::core::auth::Key;
+                use crate::core::error::Error;
+                use crate::core::tests::the_tracker::{leecher, peer_ip, private_tracker, sample_info_hash};
+                use crate::core::{PeersWanted, ScrapeData};
+
+                fn sample_key() -> Key {
+                    Key::from_str("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ").unwrap()
+                }
+
+                fn other_info_hash() -> InfoHash {
+                    "99c82bb73505a3c0b453f9fa0e881d6e5a32a0c1".parse::<InfoHash>().unwrap()
+                }
+
+                fn linux_isos() -> TorrentGroup {
+                    TorrentGroup::from_str("linux-isos").unwrap()
+                }
+
+                #[tokio::test]
+                async fn it_should_authorize_any_torrent_for_a_key_without_allow_list() {
+                    let tracker = private_tracker();
+
+                    assert!(tracker.authorize(&sample_info_hash(), Some(&sample_key())).await.is_ok());
+                }
+
+                #[tokio::test]
+                async fn it_should_not_authorize_a_torrent_that_is_not_in_the_allow_list_of_the_key() {
+                    let tracker = private_tracker();
+
+                    tracker
+                        .add_torrent_to_key_allow_list(&sample_key(), &sample_info_hash())
+                        .unwrap();
+
+                    assert!(tracker.authorize(&sample_info_hash(), Some(&sample_key())).await.is_ok());
+                    assert!(matches!(
+                        tracker.authorize(&other_info_hash(), Some(&sample_key())).await,
+                        Err(Error::TorrentNotAllowedForKey { .. })
+                    ));
+                }
+
+                #[tokio::test]
+                async fn it_should_authorize_the_torrents_in_the_groups_in_the_allow_list_of_the_key() {
+                    let tracker = private_tracker();
+
+                    tracker.add_torrent_to_group(&linux_isos(), &other_info_hash()).unwrap();
+                    tracker
+                        .add_torrent_group_to_key_allow_list(&sample_key(), &linux_isos())
+                        .unwrap();
+
+                    assert!(tracker.authorize(&other_info_hash(), Some(&sample_key())).await.is_ok());
+                    assert!(tracker.authorize(&sample_info_hash(), Some(&sample_key())).await.is_err());
+                }
+
+                #[tokio::test]
+                async fn it_should_not_authorize_any_torrent_when_the_allow_list_of_the_key_gets_empty() {
+                    let tracker = private_tracker();
+
+                    tracker
+                        .add_torrent_to_key_allow_list(&sample_key(), &sample_info_hash())
+                        .unwrap();
+                    tracker
+                        .remove_torrent_from_key_allow_list(&sample_key(), &sample_info_hash())
+                        .unwrap();
+
+                    assert!(tracker.get_key_allow_list(&sample_key()).unwrap().is_empty());
+                    assert!(tracker.authorize(&sample_info_hash(), Some(&sample_key())).await.is_err());
+                    assert!(tracker.authorize(&other_info_hash(), Some(&sample_key())).await.is_err());
+                }
+
+                #[tokio::test]
+                async fn it_should_authorize_all_the_torrents_again_when_the_allow_list_of_the_key_is_removed() {
+                    let tracker = private_tracker();
+
+                    tracker
+                        .add_torrent_to_key_allow_list(&sample_key(), &sample_info_hash())
+                        .unwrap();
+                    tracker.remove_key_allow_list(&sample_key()).unwrap();
+
+                    assert!(tracker.get_key_allow_list(&sample_key()).is_none());
+                    assert!(tracker.authorize(&other_info_hash(), Some(&sample_key())).await.is_ok());
+                }
+
+                #[tokio::test]
+                async fn it_should_return_zeroed_swarm_metadata_when_scraping_a_torrent_that_is_not_in_the_allow_list_of_the_key()
+                {
+                    let tracker = private_tracker();
+
+                    tracker
+                        .add_torrent_to_key_allow_list(&sample_key(), &sample_info_hash())
+                        .unwrap();
+
+                    tracker.announce(&other_info_hash(), &mut leecher(), &peer_ip(), &PeersWanted::All);
+
+                    let scrape_data = tracker.scrape(&vec![other_info_hash()], Some(&sample_key())).await;
+
+                    let mut expected_scrape_data = ScrapeData::empty();
+                    expected_scrape_data.add_file_with_zeroed_metadata(&other_info_hash());
+
+                    assert_eq!(scrape_data, expected_scrape_data);
+                }
+
+                #[tokio::test]
+                async fn it_should_load_the_allow_lists_and_the_torrent_groups_from_the_database() {
+                    let tracker = private_tracker();
+
+                    tracker
+                        .add_torrent_to_key_allow_list(&sample_key(), &sample_info_hash())
+                        .unwrap();
+                    tracker
+                        .add_torrent_group_to_key_allow_list(&sample_key(), &linux_isos())
+                        .unwrap();
+                    tracker.add_torrent_to_group(&linux_isos(), &other_info_hash()).unwrap();
+
+                    // Remove the allow-lists and the groups in memory
+                    tracker.key_allow_lists.write().unwrap().clear();
+                    tracker.torrent_groups.write().unwrap().clear();
+
+                    tracker.load_torrent_access_from_database().unwrap();
+
+                    let allow_list = tracker.get_key_allow_list(&sample_key()).unwrap();
+
+                    assert!(allow_list.info_hashes.contains(&sample_info_hash()));
+                    assert!(allow_list.groups.contains(&linux_isos()));
+                    assert_eq!(tracker.get_torrent_group(&linux_isos()), vec![other_info_hash()]);
+                }
+
+                #[tokio::test]
+                async fn it_should_keep_the_key_restricted_after_reloading_an_empty_allow_list_from_the_database() {
+                    let tracker = private_tracker();
+
+                    tracker
+                        .add_torrent_to_key_allow_list(&sample_key(), &sample_info_hash())
+                        .unwrap();
+                    tracker
+                        .remove_torrent_from_key_allow_list(&sample_key(), &sample_info_hash())
+                        .unwrap();
+
+                    tracker.load_torrent_access_from_database().unwrap();
+
+                    assert!(tracker.get_key_allow_list(&sample_key()).unwrap().is_empty());
+                    assert!(tracker.authorize(&sample_info_hash(), Some(&sample_key())).await.is_err());
+                }
+            }
+
             mod handling_an_announce_request {}
 
             mod handling_an_scrape_request {}
@@ -2183,11 +5030,17 @@ mod tests {
 
         mod handling_torrent_persistence {
 
+            use std::sync::Arc;
+
             use aquatic_udp_protocol::AnnounceEvent;
+            use torrust_tracker_clock::clock::Time;
             use torrust_tracker_torrent_repository::entry::EntrySync;
             use torrust_tracker_torrent_repository::repository::Repository;
 
-            use crate::core::tests::the_tracker::{sample_info_hash, sample_peer, tracker_persisting_torrents_in_database};
+            use crate::core::tests::the_tracker::{
+                sample_info_hash, sample_peer, tracker_persisting_torrents_in_database, tracker_saving_swarm_snapshots,
+            };
+            use crate::CurrentClock;
 
             #[tokio::test]
             async fn it_should_persist_the_number_of_completed_peers_for_all_torrents_into_the_database() {
@@ -2218,6 +5071,31 @@ mod tests {
                 // It does not persist the peers
                 assert!(torrent_entry.peers_is_empty());
             }
+
+            #[tokio::test]
+            async fn it_should_restore_the_peers_from_the_swarm_snapshot() {
+                let snapshot_dir = tempfile::tempdir().unwrap();
+
+                let tracker = tracker_saving_swarm_snapshots(snapshot_dir.path());
+
+                let info_hash = sample_info_hash();
+
+                let mut peer = sample_peer();
+                peer.updated = CurrentClock::now();
+
+                let _ = tracker.upsert_peer_and_get_stats(&info_hash, &peer);
+
+                tracker.save_swarm_snapshot().unwrap();
+
+                // Remove the torrent from memory
+                tracker.torrents.remove(&info_hash);
+
+                tracker.load_swarm_snapshot().unwrap();
+
+                let torrent_entry = tracker.torrents.get(&info_hash).expect("it should be able to get entry");
+
+                assert_eq!(torrent_entry.get_peers(None), vec![Arc::new(peer)]);
+            }
         }
     }
 }
@@ -16,11 +16,14 @@ fn it_should_be_serializable() {
     let torrent_peer = peer::Peer {
         peer_id: PeerId(*b"-qB0000-000000000000"),
         peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080),
+        alternative_addr: None,
+        key: None,
         updated: CurrentClock::now(),
         uploaded: NumberOfBytes::new(0),
         downloaded: NumberOfBytes::new(0),
         left: NumberOfBytes::new(0),
         event: AnnounceEvent::Started,
+        upload_only: false,
     };
 
     let raw_json = serde_json::to_string(&torrent_peer).unwrap();
@@ -36,7 +39,8 @@ fn it_should_be_serializable() {
                 "uploaded":0,
                 "downloaded":0,
                 "left":0,
-                "event":"Started"
+                "event":"Started",
+                "upload_only":false
             }
         "#;
 
@@ -0,0 +1,270 @@
+//! Upload and download accounting per authentication key.
+//!
+//! In private mode every announce comes with an authentication
+//! [`Key`](crate::core::auth::Key). Peers report the total number of bytes
+//! they have uploaded and downloaded since they joined the swarm, so the
+//! tracker computes the [`Transfer`] between two successive announces of the
+//! same peer and adds it to the [`KeyStats`] of the key. The
+//! [`Tracker`](crate::core::Tracker) keeps the totals in memory and a job
+//! persists the changed totals in the database periodically. Announces that
+//! are not applied to the swarm, like the ones refused because the swarm is
+//! full, are not accounted.
+//!
+//! Torrents can have [`Multipliers`] to reward some transfers. For example:
+//!
+//! - Freeleech: the download multiplier is `0.0`, so downloading the torrent
+//!   does not count.
+//! - Double upload: the upload multiplier is `2.0`, so every uploaded byte
+//!   counts twice.
+use std::time::Duration;
+
+use aquatic_udp_protocol::AnnounceEvent;
+use serde::{Deserialize, Serialize};
+use torrust_tracker_primitives::peer;
+
+/// The transfer totals of an authentication key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct KeyStats {
+    /// The total number of bytes uploaded, after applying the multipliers.
+    pub uploaded: u64,
+    /// The total number of bytes downloaded, after applying the multipliers.
+    pub downloaded: u64,
+    /// The total time the key has been seeding, adding up all the torrents.
+    pub seeding_time: Duration,
+}
+
+impl KeyStats {
+    /// The upload/download ratio. It's `None` when nothing has been downloaded
+    /// yet.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn ratio(&self) -> Option<f64> {
+        if self.downloaded == 0 {
+            None
+        } else {
+            Some(self.uploaded as f64 / self.downloaded as f64)
+        }
+    }
+
+    /// It adds a transfer to the totals.
+    pub fn add(&mut self, transfer: &Transfer) {
+        self.uploaded = self.uploaded.saturating_add(transfer.uploaded);
+        self.downloaded = self.downloaded.saturating_add(transfer.downloaded);
+        self.seeding_time = self.seeding_time.saturating_add(transfer.seeding_time);
+    }
+}
+
+/// The multipliers applied to the transfers of a torrent.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Multipliers {
+    /// The multiplier for the uploaded bytes.
+    pub upload: f64,
+    /// The multiplier for the downloaded bytes.
+    pub download: f64,
+}
+
+impl Default for Multipliers {
+    fn default() -> Self {
+        Self {
+            upload: 1.0,
+            download: 1.0,
+        }
+    }
+}
+
+impl Multipliers {
+    /// Multipliers must be finite and not negative.
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        [self.upload, self.download]
+            .iter()
+            .all(|multiplier| multiplier.is_finite() && *multiplier >= 0.0)
+    }
+}
+
+/// The bytes transferred, and the time spent seeding, between two announces
+/// of the same peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Transfer {
+    pub uploaded: u64,
+    pub downloaded: u64,
+    pub seeding_time: Duration,
+}
+
+impl Transfer {
+    /// It calculates the transfer from the `previous` announce of the peer,
+    /// if any, to the new one.
+    ///
+    /// Without a previous announce, the counters only count when the peer is
+    /// starting. Otherwise the tracker has lost the previous announce (for
+    /// example, the peer was removed for inactivity) and the counters include
+    /// bytes that have already been accounted, so nothing is counted.
+    ///
+    /// The counters are reset when a client restarts, so when a counter is
+    /// lower than in the previous announce the whole new value is taken as the
+    /// transfer. The peer has been seeding since the previous announce if it
+    /// was a seeder then.
+    #[must_use]
+    pub fn between(previous: Option<&peer::Peer>, peer: &peer::Peer, multipliers: &Multipliers) -> Self {
+        if previous.is_none() && peer.event != AnnounceEvent::Started {
+            return Self::default();
+        }
+
+        let (previous_uploaded, previous_downloaded) = previous.map_or((0, 0), |previous| {
+            (bytes(previous.uploaded.0.get()), bytes(previous.downloaded.0.get()))
+        });
+
+        let seeding_time = previous
+            .filter(|previous| previous.is_seeder())
+            .map_or(Duration::ZERO, |previous| peer.updated.saturating_sub(previous.updated));
+
+        Self {
+            uploaded: apply(delta(previous_uploaded, bytes(peer.uploaded.0.get())), multipliers.upload),
+            downloaded: apply(
+                delta(previous_downloaded, bytes(peer.downloaded.0.get())),
+                multipliers.download,
+            ),
+            seeding_time,
+        }
+    }
+}
+
+/// Negative counters sent by buggy clients are ignored.
+fn bytes(counter: i64) -> u64 {
+    u64::try_from(counter).unwrap_or_default()
+}
+
+fn delta(previous: u64, current: u64) -> u64 {
+    if current >= previous {
+        current - previous
+    } else {
+        current
+    }
+}
+
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn apply(bytes: u64, multiplier: f64) -> u64 {
+    // The float to integer cast saturates.
+    (bytes as f64 * multiplier) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes};
+    use torrust_tracker_primitives::peer::fixture::PeerBuilder;
+    use torrust_tracker_primitives::peer::Peer;
+    use torrust_tracker_primitives::DurationSinceUnixEpoch;
+
+    use super::{KeyStats, Multipliers, Transfer};
+
+    fn peer(uploaded: i64, downloaded: i64, left: i64, updated: u64) -> Peer {
+        let mut peer = PeerBuilder::default()
+            .with_bytes_pending_to_download(left)
+            .last_updated_on(DurationSinceUnixEpoch::from_secs(updated))
+            .build();
+
+        peer.uploaded = NumberOfBytes::new(uploaded);
+        peer.downloaded = NumberOfBytes::new(downloaded);
+        peer.event = AnnounceEvent::None;
+
+        peer
+    }
+
+    #[test]
+    fn the_first_announce_of_a_peer_should_count_all_the_transferred_bytes() {
+        let mut started_peer = peer(100, 200, 10, 0);
+        started_peer.event = AnnounceEvent::Started;
+
+        let transfer = Transfer::between(None, &started_peer, &Multipliers::default());
+
+        assert_eq!(
+            transfer,
+            Transfer {
+                uploaded: 100,
+                downloaded: 200,
+                seeding_time: Duration::ZERO
+            }
+        );
+    }
+
+    #[test]
+    fn nothing_should_count_when_the_previous_announce_of_a_peer_that_is_not_starting_is_unknown() {
+        let transfer = Transfer::between(None, &peer(100, 200, 10, 0), &Multipliers::default());
+
+        assert_eq!(transfer, Transfer::default());
+    }
+
+    #[test]
+    fn the_transfer_should_be_the_difference_between_two_announces_of_the_same_peer() {
+        let transfer = Transfer::between(Some(&peer(100, 200, 10, 0)), &peer(150, 260, 5, 60), &Multipliers::default());
+
+        assert_eq!((transfer.uploaded, transfer.downloaded), (50, 60));
+    }
+
+    #[test]
+    fn the_new_counter_should_be_taken_as_the_transfer_when_the_client_has_restarted() {
+        let transfer = Transfer::between(Some(&peer(100, 200, 10, 0)), &peer(30, 40, 5, 60), &Multipliers::default());
+
+        assert_eq!((transfer.uploaded, transfer.downloaded), (30, 40));
+    }
+
+    #[test]
+    fn the_time_between_two_announces_should_count_as_seeding_time_when_the_peer_was_a_seeder() {
+        let multipliers = Multipliers::default();
+
+        let seeding = Transfer::between(Some(&peer(0, 0, 0, 100)), &peer(0, 0, 0, 160), &multipliers);
+        let leeching = Transfer::between(Some(&peer(0, 0, 10, 100)), &peer(0, 0, 0, 160), &multipliers);
+
+        assert_eq!(seeding.seeding_time, Duration::from_secs(60));
+        assert_eq!(leeching.seeding_time, Duration::ZERO);
+    }
+
+    #[test]
+    fn the_torrent_multipliers_should_be_applied_to_the_transfer() {
+        let freeleech_and_double_upload = Multipliers {
+            upload: 2.0,
+            download: 0.0,
+        };
+
+        let transfer = Transfer::between(
+            Some(&peer(0, 0, 10, 0)),
+            &peer(100, 200, 10, 60),
+            &freeleech_and_double_upload,
+        );
+
+        assert_eq!((transfer.uploaded, transfer.downloaded), (200, 0));
+    }
+
+    #[test]
+    fn negative_or_not_finite_multipliers_should_not_be_valid() {
+        assert!(Multipliers::default().is_valid());
+        assert!(!Multipliers {
+            upload: -1.0,
+            download: 1.0
+        }
+        .is_valid());
+        assert!(!Multipliers {
+            upload: 1.0,
+            download: f64::NAN
+        }
+        .is_valid());
+    }
+
+    #[test]
+    fn the_ratio_should_be_the_uploaded_bytes_divided_by_the_downloaded_bytes() {
+        let mut stats = KeyStats::default();
+
+        assert_eq!(stats.ratio(), None);
+
+        stats.add(&Transfer {
+            uploaded: 300,
+            downloaded: 200,
+            seeding_time: Duration::from_secs(10),
+        });
+
+        assert_eq!(stats.ratio(), Some(1.5));
+        assert_eq!(stats.seeding_time, Duration::from_secs(10));
+    }
+}
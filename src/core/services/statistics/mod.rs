@@ -21,10 +21,10 @@
 //! There is an event listener that is receiving all the events and processing them with an event handler.
 //! Then, the event handler updates the metrics depending on the received event.
 //!
-//! For example, if you send the event [`Event::Udp4Connect`](crate::core::statistics::Event::Udp4Connect):
+//! For example, if you send an UDP `connect` [`Event`](crate::core::statistics::Event) for a peer using IPv4:
 //!
 //! ```text
-//! let result = event_sender.send_event(Event::Udp4Connect).await;
+//! let result = event_sender.send_event(Event::udp(server_socket_addr, &remote_addr, RequestKind::Connect)).await;
 //! ```
 //!
 //! Eventually the counter for UDP connections from IPv4 peers will be increased.
@@ -38,11 +38,12 @@
 //! ```
 pub mod setup;
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use torrust_tracker_primitives::torrent_metrics::TorrentsMetrics;
 
-use crate::core::statistics::Metrics;
+use crate::core::statistics::{LatencyHistogram, Listener, ListenerMetrics, Metrics, Protocol, RequestKind};
 use crate::core::Tracker;
 
 /// All the metrics collected by the tracker.
@@ -57,12 +58,24 @@ pub struct TrackerMetrics {
     ///
     /// Metrics about how the tracker is been used (number of udp announce requests, number of http scrape requests, etcetera)
     pub protocol_metrics: Metrics,
+
+    /// Application level metrics for each running tracker server.
+    ///
+    /// The same request counters as `protocol_metrics` but for every listener.
+    pub listeners_metrics: BTreeMap<Listener, ListenerMetrics>,
+
+    /// Application level metrics. Time taken to handle requests.
+    ///
+    /// A histogram for each protocol and request type.
+    pub latency_metrics: BTreeMap<(Protocol, RequestKind), LatencyHistogram>,
 }
 
 /// It returns all the [`TrackerMetrics`]
 pub async fn get_metrics(tracker: Arc<Tracker>) -> TrackerMetrics {
     let torrents_metrics = tracker.get_torrents_metrics();
     let stats = tracker.get_stats().await;
+    let listeners_metrics = tracker.get_listeners_stats().await.clone();
+    let latency_metrics = tracker.get_latency_stats().await.clone();
 
     TrackerMetrics {
         torrents_metrics,
@@ -70,23 +83,32 @@ pub async fn get_metrics(tracker: Arc<Tracker>) -> TrackerMetrics {
             tcp4_connections_handled: stats.tcp4_connections_handled,
             tcp4_announces_handled: stats.tcp4_announces_handled,
             tcp4_scrapes_handled: stats.tcp4_scrapes_handled,
+            tcp4_announces_too_frequent: stats.tcp4_announces_too_frequent,
             tcp6_connections_handled: stats.tcp6_connections_handled,
             tcp6_announces_handled: stats.tcp6_announces_handled,
             tcp6_scrapes_handled: stats.tcp6_scrapes_handled,
+            tcp6_announces_too_frequent: stats.tcp6_announces_too_frequent,
             udp4_connections_handled: stats.udp4_connections_handled,
             udp4_announces_handled: stats.udp4_announces_handled,
             udp4_scrapes_handled: stats.udp4_scrapes_handled,
             udp4_errors_handled: stats.udp4_errors_handled,
+            udp4_requests_rate_limited: stats.udp4_requests_rate_limited,
+            udp4_announces_too_frequent: stats.udp4_announces_too_frequent,
             udp6_connections_handled: stats.udp6_connections_handled,
             udp6_announces_handled: stats.udp6_announces_handled,
             udp6_scrapes_handled: stats.udp6_scrapes_handled,
             udp6_errors_handled: stats.udp6_errors_handled,
+            udp6_requests_rate_limited: stats.udp6_requests_rate_limited,
+            udp6_announces_too_frequent: stats.udp6_announces_too_frequent,
         },
+        listeners_metrics,
+        latency_metrics,
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
     use std::sync::Arc;
 
     use torrust_tracker_configuration::Configuration;
@@ -112,6 +134,8 @@ mod tests {
             TrackerMetrics {
                 torrents_metrics: TorrentsMetrics::default(),
                 protocol_metrics: core::statistics::Metrics::default(),
+                listeners_metrics: BTreeMap::new(),
+                latency_metrics: BTreeMap::new(),
             }
         );
     }
@@ -45,8 +45,11 @@ pub struct BasicInfo {
 }
 
 /// It returns all the information the tracker has about one torrent in a [Info] struct.
+///
+/// The truncated V2 infohash of a hybrid torrent returns the swarm of the V1
+/// infohash.
 pub async fn get_torrent_info(tracker: Arc<Tracker>, info_hash: &InfoHash) -> Option<Info> {
-    let torrent_entry_option = tracker.torrents.get(info_hash);
+    let torrent_entry_option = tracker.torrents.get(&tracker.resolve_info_hash(info_hash));
 
     let torrent_entry = torrent_entry_option?;
 
@@ -88,7 +91,11 @@ pub async fn get_torrents(tracker: Arc<Tracker>, info_hashes: &[InfoHash]) -> Ve
     let mut basic_infos: Vec<BasicInfo> = vec![];
 
     for info_hash in info_hashes {
-        if let Some(stats) = tracker.torrents.get(info_hash).map(|t| t.get_swarm_metadata()) {
+        if let Some(stats) = tracker
+            .torrents
+            .get(&tracker.resolve_info_hash(info_hash))
+            .map(|t| t.get_swarm_metadata())
+        {
             basic_infos.push(BasicInfo {
                 info_hash: *info_hash,
                 seeders: u64::from(stats.complete),
@@ -112,11 +119,14 @@ mod tests {
         peer::Peer {
             peer_id: PeerId(*b"-qB00000000000000000"),
             peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080),
+            alternative_addr: None,
+            key: None,
             updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
             uploaded: NumberOfBytes::new(0),
             downloaded: NumberOfBytes::new(0),
             left: NumberOfBytes::new(0),
             event: AnnounceEvent::Started,
+            upload_only: false,
         }
     }
 
@@ -16,8 +16,19 @@
 //! The tracker uses an [`statistics::EventSender`](crate::core::statistics::EventSender) instance to send an event.
 //! The [`statistics::Keeper`](crate::core::statistics::Keeper) listens to new events and uses the [`statistics::Repo`](crate::core::statistics::Repo) to upgrade and store metrics.
 //!
-//! See the [`statistics::Event`](crate::core::statistics::Event) enum to check which events are available.
+//! See the [`statistics::Event`](crate::core::statistics::Event) struct to check which events are available.
+//!
+//! Besides the totals, the tracker keeps the same counters for each server
+//! (listener), so that it's possible to know which of the running UDP and HTTP
+//! trackers handles the traffic. See [`Listener`](crate::core::statistics::Listener).
+//!
+//! The servers also send the time they took to handle every request. The
+//! tracker keeps a [`LatencyHistogram`](crate::core::statistics::LatencyHistogram)
+//! for each protocol and request type.
+use std::collections::BTreeMap;
+use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
+use std::time::Duration;
 
 use futures::future::BoxFuture;
 use futures::FutureExt;
@@ -30,28 +41,202 @@ const CHANNEL_BUFFER_SIZE: usize = 65_535;
 
 /// An statistics event. It is used to collect tracker metrics.
 ///
-/// - `Tcp` prefix means the event was triggered by the HTTP tracker
-/// - `Udp` prefix means the event was triggered by the UDP tracker
-/// - `4` or `6` prefixes means the IP version used by the peer
-/// - Finally the event suffix is the type of request: `announce`, `scrape` or `connection`
+/// It's sent every time a tracker server handles a request. It contains:
+///
+/// - The socket address the server is bound to.
+/// - The protocol of the server: UDP or HTTP.
+/// - The IP version used by the peer: IPv4 or IPv6.
+/// - The type of request: `connect`, `announce`, `scrape` or `error`. Or
+///   `rate_limited` when the UDP tracker rejects a request because the client
+///   exceeded the rate limits, and `announce_too_frequent` when a peer
+///   announces again before the `min interval`.
 ///
 /// > NOTE: HTTP trackers do not use `connection` requests.
-#[derive(Debug, PartialEq, Eq)]
-pub enum Event {
-    // code-review: consider one single event for request type with data: Event::Announce { scheme: HTTPorUDP, ip_version: V4orV6 }
-    // Attributes are enums too.
-    Tcp4Announce,
-    Tcp4Scrape,
-    Tcp6Announce,
-    Tcp6Scrape,
-    Udp4Connect,
-    Udp4Announce,
-    Udp4Scrape,
-    Udp4Error,
-    Udp6Connect,
-    Udp6Announce,
-    Udp6Scrape,
-    Udp6Error,
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Event {
+    pub server_socket_addr: SocketAddr,
+    pub protocol: Protocol,
+    pub ip_version: IpVersion,
+    pub kind: RequestKind,
+}
+
+impl Event {
+    /// An event triggered by the HTTP tracker bound to `server_socket_addr`
+    /// for a request from a peer using the `peer_ip` IP address.
+    #[must_use]
+    pub fn http(server_socket_addr: SocketAddr, peer_ip: &IpAddr, kind: RequestKind) -> Self {
+        Self {
+            server_socket_addr,
+            protocol: Protocol::Http,
+            ip_version: IpVersion::from(peer_ip),
+            kind,
+        }
+    }
+
+    /// An event triggered by the UDP tracker bound to `server_socket_addr`
+    /// for a request received from `remote_addr`.
+    #[must_use]
+    pub fn udp(server_socket_addr: SocketAddr, remote_addr: &SocketAddr, kind: RequestKind) -> Self {
+        Self {
+            server_socket_addr,
+            protocol: Protocol::Udp,
+            ip_version: IpVersion::from(&remote_addr.ip()),
+            kind,
+        }
+    }
+}
+
+/// The protocol of the tracker server.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum Protocol {
+    Http,
+    Udp,
+}
+
+impl std::fmt::Display for Protocol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Http => write!(f, "http"),
+            Self::Udp => write!(f, "udp"),
+        }
+    }
+}
+
+/// The IP version used by the peer.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum IpVersion {
+    V4,
+    V6,
+}
+
+impl From<&IpAddr> for IpVersion {
+    fn from(ip: &IpAddr) -> Self {
+        match ip {
+            IpAddr::V4(_) => Self::V4,
+            IpAddr::V6(_) => Self::V6,
+        }
+    }
+}
+
+/// The type of request handled by the tracker.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum RequestKind {
+    Connect,
+    Announce,
+    Scrape,
+    Error,
+    /// A request rejected by the UDP tracker rate limiter.
+    RateLimited,
+    /// An `announce` request received before the `min interval`.
+    AnnounceTooFrequent,
+}
+
+impl std::fmt::Display for RequestKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Connect => write!(f, "connect"),
+            Self::Announce => write!(f, "announce"),
+            Self::Scrape => write!(f, "scrape"),
+            Self::Error => write!(f, "error"),
+            Self::RateLimited => write!(f, "rate_limited"),
+            Self::AnnounceTooFrequent => write!(f, "announce_too_frequent"),
+        }
+    }
+}
+
+/// The time a tracker server took to handle a request, from the moment the
+/// request was received until the response was built.
+///
+/// The `kind` is `Error` when the server responds with an error, whatever
+/// the type of the request was.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct RequestLatency {
+    pub protocol: Protocol,
+    pub kind: RequestKind,
+    pub duration: Duration,
+}
+
+/// The upper bounds, in microseconds, of the [`LatencyHistogram`] buckets.
+pub const LATENCY_BUCKETS: [u64; 13] = [
+    100, 250, 500, 1_000, 2_500, 5_000, 10_000, 25_000, 50_000, 100_000, 250_000, 500_000, 1_000_000,
+];
+
+/// A histogram of the time taken to handle requests.
+///
+/// Every request is counted in the first bucket whose upper bound (see
+/// [`LATENCY_BUCKETS`]) is greater than or equal to its latency. Requests
+/// slower than the last bound are only counted in the `count` total.
+#[derive(Debug, PartialEq, Eq, Default, Clone, Copy)]
+pub struct LatencyHistogram {
+    /// Number of requests in each bucket.
+    pub buckets: [u64; LATENCY_BUCKETS.len()],
+    /// Total number of requests.
+    pub count: u64,
+    /// Sum of the latencies of all the requests in microseconds.
+    pub sum: u64,
+}
+
+impl LatencyHistogram {
+    /// It adds a request latency to the histogram.
+    pub fn observe(&mut self, duration: Duration) {
+        let microseconds = u64::try_from(duration.as_micros()).unwrap_or(u64::MAX);
+
+        if let Some(bucket) = LATENCY_BUCKETS.iter().position(|upper_bound| microseconds <= *upper_bound) {
+            self.buckets[bucket] += 1;
+        }
+
+        self.count += 1;
+        self.sum = self.sum.saturating_add(microseconds);
+    }
+
+    /// It returns the upper bound of every bucket with the number of requests
+    /// whose latency is lower than or equal to it, like Prometheus histograms.
+    #[must_use]
+    pub fn cumulative_buckets(&self) -> Vec<(u64, u64)> {
+        LATENCY_BUCKETS
+            .iter()
+            .zip(self.buckets.iter())
+            .scan(0, |cumulative_count, (upper_bound, count)| {
+                *cumulative_count += count;
+                Some((*upper_bound, *cumulative_count))
+            })
+            .collect()
+    }
+}
+
+/// A running tracker server, identified by its protocol and the socket
+/// address it's bound to.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Listener {
+    pub protocol: Protocol,
+    pub socket_addr: SocketAddr,
+}
+
+/// Metrics collected for one [`Listener`].
+#[derive(Debug, PartialEq, Eq, Default, Clone, Copy)]
+pub struct ListenerMetrics {
+    /// Requests from IPv4 peers.
+    pub ipv4: RequestMetrics,
+    /// Requests from IPv6 peers.
+    pub ipv6: RequestMetrics,
+}
+
+/// Number of requests handled by a [`Listener`] for one IP version.
+#[derive(Debug, PartialEq, Eq, Default, Clone, Copy)]
+pub struct RequestMetrics {
+    /// Number of connections handled. Since the HTTP tracker spec does not
+    /// require a handshake, this metric increases for every HTTP request.
+    pub connections_handled: u64,
+    /// Number of `announce` requests handled.
+    pub announces_handled: u64,
+    /// Number of `scrape` requests handled.
+    pub scrapes_handled: u64,
+    /// Number of `error` responses sent. Only for the UDP tracker.
+    pub errors_handled: u64,
+    /// Number of requests rejected by the rate limiter. Only for the UDP tracker.
+    pub requests_rate_limited: u64,
+    /// Number of `announce` requests received before the `min interval`.
+    pub announces_too_frequent: u64,
 }
 
 /// Metrics collected by the tracker.
@@ -72,12 +257,18 @@ pub struct Metrics {
     pub tcp4_announces_handled: u64,
     /// Total number of TCP (HTTP tracker) `scrape` requests from IPv4 peers.
     pub tcp4_scrapes_handled: u64,
+    /// Total number of TCP (HTTP tracker) `announce` requests from IPv4 peers
+    /// received before the `min interval`.
+    pub tcp4_announces_too_frequent: u64,
     /// Total number of TCP (HTTP tracker) connections from IPv6 peers.
     pub tcp6_connections_handled: u64,
     /// Total number of TCP (HTTP tracker) `announce` requests from IPv6 peers.
     pub tcp6_announces_handled: u64,
     /// Total number of TCP (HTTP tracker) `scrape` requests from IPv6 peers.
     pub tcp6_scrapes_handled: u64,
+    /// Total number of TCP (HTTP tracker) `announce` requests from IPv6 peers
+    /// received before the `min interval`.
+    pub tcp6_announces_too_frequent: u64,
     /// Total number of UDP (UDP tracker) connections from IPv4 peers.
     pub udp4_connections_handled: u64,
     /// Total number of UDP (UDP tracker) `announce` requests from IPv4 peers.
@@ -86,6 +277,12 @@ pub struct Metrics {
     pub udp4_scrapes_handled: u64,
     /// Total number of UDP (UDP tracker) `error` requests from IPv4 peers.
     pub udp4_errors_handled: u64,
+    /// Total number of UDP (UDP tracker) requests from IPv4 peers rejected
+    /// by the rate limiter.
+    pub udp4_requests_rate_limited: u64,
+    /// Total number of UDP (UDP tracker) `announce` requests from IPv4 peers
+    /// received before the `min interval`.
+    pub udp4_announces_too_frequent: u64,
     /// Total number of UDP (UDP tracker) `connection` requests from IPv6 peers.
     pub udp6_connections_handled: u64,
     /// Total number of UDP (UDP tracker) `announce` requests from IPv6 peers.
@@ -94,6 +291,12 @@ pub struct Metrics {
     pub udp6_scrapes_handled: u64,
     /// Total number of UDP (UDP tracker) `error` requests from IPv6 peers.
     pub udp6_errors_handled: u64,
+    /// Total number of UDP (UDP tracker) requests from IPv6 peers rejected
+    /// by the rate limiter.
+    pub udp6_requests_rate_limited: u64,
+    /// Total number of UDP (UDP tracker) `announce` requests from IPv6 peers
+    /// received before the `min interval`.
+    pub udp6_announces_too_frequent: u64,
 }
 
 /// The service responsible for keeping tracker metrics (listening to statistics events and handle them).
@@ -127,12 +330,17 @@ impl Keeper {
 
     pub fn run_event_listener(&mut self) -> Box<dyn EventSender> {
         let (sender, receiver) = mpsc::channel::<Event>(CHANNEL_BUFFER_SIZE);
+        let (latency_sender, latency_receiver) = mpsc::channel::<RequestLatency>(CHANNEL_BUFFER_SIZE);
 
         let stats_repository = self.repository.clone();
 
         tokio::spawn(async move { event_listener(receiver, stats_repository).await });
 
-        Box::new(Sender { sender })
+        let stats_repository = self.repository.clone();
+
+        tokio::spawn(async move { latency_listener(latency_receiver, stats_repository).await });
+
+        Box::new(Sender { sender, latency_sender })
     }
 }
 
@@ -142,57 +350,88 @@ async fn event_listener(mut receiver: mpsc::Receiver<Event>, stats_repository: R
     }
 }
 
+async fn latency_listener(mut receiver: mpsc::Receiver<RequestLatency>, stats_repository: Repo) {
+    while let Some(latency) = receiver.recv().await {
+        stats_repository.observe_latency(&latency).await;
+    }
+}
+
 async fn event_handler(event: Event, stats_repository: &Repo) {
-    match event {
+    match (event.protocol, event.ip_version, event.kind) {
         // TCP4
-        Event::Tcp4Announce => {
+        (Protocol::Http, IpVersion::V4, RequestKind::Announce) => {
             stats_repository.increase_tcp4_announces().await;
             stats_repository.increase_tcp4_connections().await;
         }
-        Event::Tcp4Scrape => {
+        (Protocol::Http, IpVersion::V4, RequestKind::Scrape) => {
             stats_repository.increase_tcp4_scrapes().await;
             stats_repository.increase_tcp4_connections().await;
         }
+        (Protocol::Http, IpVersion::V4, RequestKind::AnnounceTooFrequent) => {
+            stats_repository.increase_tcp4_announces_too_frequent().await;
+            stats_repository.increase_tcp4_connections().await;
+        }
 
         // TCP6
-        Event::Tcp6Announce => {
+        (Protocol::Http, IpVersion::V6, RequestKind::Announce) => {
             stats_repository.increase_tcp6_announces().await;
             stats_repository.increase_tcp6_connections().await;
         }
-        Event::Tcp6Scrape => {
+        (Protocol::Http, IpVersion::V6, RequestKind::Scrape) => {
             stats_repository.increase_tcp6_scrapes().await;
             stats_repository.increase_tcp6_connections().await;
         }
+        (Protocol::Http, IpVersion::V6, RequestKind::AnnounceTooFrequent) => {
+            stats_repository.increase_tcp6_announces_too_frequent().await;
+            stats_repository.increase_tcp6_connections().await;
+        }
 
         // UDP4
-        Event::Udp4Connect => {
+        (Protocol::Udp, IpVersion::V4, RequestKind::Connect) => {
             stats_repository.increase_udp4_connections().await;
         }
-        Event::Udp4Announce => {
+        (Protocol::Udp, IpVersion::V4, RequestKind::Announce) => {
             stats_repository.increase_udp4_announces().await;
         }
-        Event::Udp4Scrape => {
+        (Protocol::Udp, IpVersion::V4, RequestKind::Scrape) => {
             stats_repository.increase_udp4_scrapes().await;
         }
-        Event::Udp4Error => {
+        (Protocol::Udp, IpVersion::V4, RequestKind::Error) => {
             stats_repository.increase_udp4_errors().await;
         }
+        (Protocol::Udp, IpVersion::V4, RequestKind::RateLimited) => {
+            stats_repository.increase_udp4_requests_rate_limited().await;
+        }
+        (Protocol::Udp, IpVersion::V4, RequestKind::AnnounceTooFrequent) => {
+            stats_repository.increase_udp4_announces_too_frequent().await;
+        }
 
         // UDP6
-        Event::Udp6Connect => {
+        (Protocol::Udp, IpVersion::V6, RequestKind::Connect) => {
             stats_repository.increase_udp6_connections().await;
         }
-        Event::Udp6Announce => {
+        (Protocol::Udp, IpVersion::V6, RequestKind::Announce) => {
             stats_repository.increase_udp6_announces().await;
         }
-        Event::Udp6Scrape => {
+        (Protocol::Udp, IpVersion::V6, RequestKind::Scrape) => {
             stats_repository.increase_udp6_scrapes().await;
         }
-        Event::Udp6Error => {
+        (Protocol::Udp, IpVersion::V6, RequestKind::Error) => {
             stats_repository.increase_udp6_errors().await;
         }
+        (Protocol::Udp, IpVersion::V6, RequestKind::RateLimited) => {
+            stats_repository.increase_udp6_requests_rate_limited().await;
+        }
+        (Protocol::Udp, IpVersion::V6, RequestKind::AnnounceTooFrequent) => {
+            stats_repository.increase_udp6_announces_too_frequent().await;
+        }
+
+        // HTTP trackers do not have `connect` requests, `error` responses nor rate limits
+        (Protocol::Http, _, RequestKind::Connect | RequestKind::Error | RequestKind::RateLimited) => {}
     }
 
+    stats_repository.increase_listener_requests(&event).await;
+
     tracing::debug!("stats: {:?}", stats_repository.get_stats().await);
 }
 
@@ -200,6 +439,8 @@ async fn event_handler(event: Event, stats_repository: &Repo) {
 #[cfg_attr(test, automock)]
 pub trait EventSender: Sync + Send {
     fn send_event(&self, event: Event) -> BoxFuture<'_, Option<Result<(), SendError<Event>>>>;
+
+    fn send_latency(&self, latency: RequestLatency) -> BoxFuture<'_, Option<Result<(), SendError<RequestLatency>>>>;
 }
 
 /// An [`statistics::EventSender`](crate::core::statistics::EventSender) implementation.
@@ -208,18 +449,25 @@ pub trait EventSender: Sync + Send {
 /// [`statistics::Keeper`](crate::core::statistics::Keeper)
 pub struct Sender {
     sender: mpsc::Sender<Event>,
+    latency_sender: mpsc::Sender<RequestLatency>,
 }
 
 impl EventSender for Sender {
     fn send_event(&self, event: Event) -> BoxFuture<'_, Option<Result<(), SendError<Event>>>> {
         async move { Some(self.sender.send(event).await) }.boxed()
     }
+
+    fn send_latency(&self, latency: RequestLatency) -> BoxFuture<'_, Option<Result<(), SendError<RequestLatency>>>> {
+        async move { Some(self.latency_sender.send(latency).await) }.boxed()
+    }
 }
 
 /// A repository for the tracker metrics.
 #[derive(Clone)]
 pub struct Repo {
     pub stats: Arc<RwLock<Metrics>>,
+    pub listeners_stats: Arc<RwLock<BTreeMap<Listener, ListenerMetrics>>>,
+    pub latency_stats: Arc<RwLock<BTreeMap<(Protocol, RequestKind), LatencyHistogram>>>,
 }
 
 impl Default for Repo {
@@ -233,6 +481,8 @@ impl Repo {
     pub fn new() -> Self {
         Self {
             stats: Arc::new(RwLock::new(Metrics::default())),
+            listeners_stats: Arc::new(RwLock::new(BTreeMap::new())),
+            latency_stats: Arc::new(RwLock::new(BTreeMap::new())),
         }
     }
 
@@ -240,6 +490,66 @@ impl Repo {
         self.stats.read().await
     }
 
+    pub async fn get_listeners_stats(&self) -> RwLockReadGuard<'_, BTreeMap<Listener, ListenerMetrics>> {
+        self.listeners_stats.read().await
+    }
+
+    pub async fn get_latency_stats(&self) -> RwLockReadGuard<'_, BTreeMap<(Protocol, RequestKind), LatencyHistogram>> {
+        self.latency_stats.read().await
+    }
+
+    /// It adds the request latency to the histogram for its protocol and
+    /// request type.
+    pub async fn observe_latency(&self, latency: &RequestLatency) {
+        let mut latency_stats_lock = self.latency_stats.write().await;
+        latency_stats_lock
+            .entry((latency.protocol, latency.kind))
+            .or_default()
+            .observe(latency.duration);
+        drop(latency_stats_lock);
+    }
+
+    /// It increases the counter for the request type of the event in the
+    /// metrics of the listener that handled the request.
+    pub async fn increase_listener_requests(&self, event: &Event) {
+        let listener = Listener {
+            protocol: event.protocol,
+            socket_addr: event.server_socket_addr,
+        };
+
+        let mut listeners_stats_lock = self.listeners_stats.write().await;
+
+        let listener_metrics = listeners_stats_lock.entry(listener).or_default();
+
+        let requests = match event.ip_version {
+            IpVersion::V4 => &mut listener_metrics.ipv4,
+            IpVersion::V6 => &mut listener_metrics.ipv6,
+        };
+
+        match (event.protocol, event.kind) {
+            (Protocol::Http, RequestKind::Announce) => {
+                requests.announces_handled += 1;
+                requests.connections_handled += 1;
+            }
+            (Protocol::Http, RequestKind::Scrape) => {
+                requests.scrapes_handled += 1;
+                requests.connections_handled += 1;
+            }
+            (Protocol::Http, RequestKind::AnnounceTooFrequent) => {
+                requests.announces_too_frequent += 1;
+                requests.connections_handled += 1;
+            }
+            (_, RequestKind::Connect) => requests.connections_handled += 1,
+            (_, RequestKind::Announce) => requests.announces_handled += 1,
+            (_, RequestKind::Scrape) => requests.scrapes_handled += 1,
+            (_, RequestKind::Error) => requests.errors_handled += 1,
+            (_, RequestKind::RateLimited) => requests.requests_rate_limited += 1,
+            (_, RequestKind::AnnounceTooFrequent) => requests.announces_too_frequent += 1,
+        }
+
+        drop(listeners_stats_lock);
+    }
+
     pub async fn increase_tcp4_announces(&self) {
         let mut stats_lock = self.stats.write().await;
         stats_lock.tcp4_announces_handled += 1;
@@ -258,6 +568,12 @@ impl Repo {
         drop(stats_lock);
     }
 
+    pub async fn increase_tcp4_announces_too_frequent(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.tcp4_announces_too_frequent += 1;
+        drop(stats_lock);
+    }
+
     pub async fn increase_tcp6_announces(&self) {
         let mut stats_lock = self.stats.write().await;
         stats_lock.tcp6_announces_handled += 1;
@@ -276,6 +592,12 @@ impl Repo {
         drop(stats_lock);
     }
 
+    pub async fn increase_tcp6_announces_too_frequent(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.tcp6_announces_too_frequent += 1;
+        drop(stats_lock);
+    }
+
     pub async fn increase_udp4_connections(&self) {
         let mut stats_lock = self.stats.write().await;
         stats_lock.udp4_connections_handled += 1;
@@ -300,6 +622,18 @@ impl Repo {
         drop(stats_lock);
     }
 
+    pub async fn increase_udp4_requests_rate_limited(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.udp4_requests_rate_limited += 1;
+        drop(stats_lock);
+    }
+
+    pub async fn increase_udp4_announces_too_frequent(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.udp4_announces_too_frequent += 1;
+        drop(stats_lock);
+    }
+
     pub async fn increase_udp6_connections(&self) {
         let mut stats_lock = self.stats.write().await;
         stats_lock.udp6_connections_handled += 1;
@@ -323,13 +657,43 @@ impl Repo {
         stats_lock.udp6_errors_handled += 1;
         drop(stats_lock);
     }
+
+    pub async fn increase_udp6_requests_rate_limited(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.udp6_requests_rate_limited += 1;
+        drop(stats_lock);
+    }
+
+    pub async fn increase_udp6_announces_too_frequent(&self) {
+        let mut stats_lock = self.stats.write().await;
+        stats_lock.udp6_announces_too_frequent += 1;
+        drop(stats_lock);
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+    use crate::core::statistics::{Event, IpVersion, Protocol, RequestKind};
+
+    fn sample_server_socket_addr() -> SocketAddr {
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), 6969)
+    }
+
+    fn sample_event(protocol: Protocol, ip_version: IpVersion, kind: RequestKind) -> Event {
+        Event {
+            server_socket_addr: sample_server_socket_addr(),
+            protocol,
+            ip_version,
+            kind,
+        }
+    }
+
     mod stats_tracker {
-        use crate::core::statistics::{Event, Keeper, Metrics};
+        use super::sample_event;
+        use crate::core::statistics::{IpVersion, Keeper, Metrics, Protocol, RequestKind};
 
         #[tokio::test]
         async fn should_contain_the_tracker_statistics() {
@@ -346,20 +710,96 @@ mod tests {
 
             let event_sender = stats_tracker.run_event_listener();
 
-            let result = event_sender.send_event(Event::Udp4Connect).await;
+            let result = event_sender
+                .send_event(sample_event(Protocol::Udp, IpVersion::V4, RequestKind::Connect))
+                .await;
 
             assert!(result.is_some());
         }
     }
 
+    mod latency_histogram {
+        use std::time::Duration;
+
+        use crate::core::statistics::{LatencyHistogram, Protocol, Repo, RequestKind, RequestLatency};
+
+        #[test]
+        fn should_count_the_request_in_the_first_bucket_whose_upper_bound_is_not_lower_than_the_latency() {
+            let mut histogram = LatencyHistogram::default();
+
+            histogram.observe(Duration::from_micros(100));
+            histogram.observe(Duration::from_micros(101));
+
+            assert_eq!(histogram.buckets[0], 1);
+            assert_eq!(histogram.buckets[1], 1);
+            assert_eq!(histogram.count, 2);
+            assert_eq!(histogram.sum, 201);
+        }
+
+        #[test]
+        fn should_only_count_in_the_total_the_requests_slower_than_the_last_bucket() {
+            let mut histogram = LatencyHistogram::default();
+
+            histogram.observe(Duration::from_secs(2));
+
+            assert_eq!(histogram.buckets.iter().sum::<u64>(), 0);
+            assert_eq!(histogram.count, 1);
+        }
+
+        #[test]
+        fn should_return_the_cumulative_count_for_each_bucket() {
+            let mut histogram = LatencyHistogram::default();
+
+            histogram.observe(Duration::from_micros(50));
+            histogram.observe(Duration::from_micros(300));
+
+            let cumulative_buckets = histogram.cumulative_buckets();
+
+            assert_eq!(cumulative_buckets[0], (100, 1));
+            assert_eq!(cumulative_buckets[1], (250, 1));
+            assert_eq!(cumulative_buckets[2], (500, 2));
+            assert_eq!(cumulative_buckets.last(), Some(&(1_000_000, 2)));
+        }
+
+        #[tokio::test]
+        async fn should_keep_a_histogram_for_each_protocol_and_request_type() {
+            let stats_repository = Repo::new();
+
+            for (protocol, kind) in [
+                (Protocol::Udp, RequestKind::Announce),
+                (Protocol::Udp, RequestKind::Announce),
+                (Protocol::Http, RequestKind::Announce),
+            ] {
+                stats_repository
+                    .observe_latency(&RequestLatency {
+                        protocol,
+                        kind,
+                        duration: Duration::from_millis(1),
+                    })
+                    .await;
+            }
+
+            let latency_stats = stats_repository.get_latency_stats().await;
+
+            assert_eq!(latency_stats[&(Protocol::Udp, RequestKind::Announce)].count, 2);
+            assert_eq!(latency_stats[&(Protocol::Http, RequestKind::Announce)].count, 1);
+            assert!(!latency_stats.contains_key(&(Protocol::Udp, RequestKind::Scrape)));
+        }
+    }
+
     mod event_handler {
-        use crate::core::statistics::{event_handler, Event, Repo};
+        use super::{sample_event, sample_server_socket_addr};
+        use crate::core::statistics::{event_handler, IpVersion, Listener, Protocol, Repo, RequestKind};
 
         #[tokio::test]
         async fn should_increase_the_tcp4_announces_counter_when_it_receives_a_tcp4_announce_event() {
             let stats_repository = Repo::new();
 
-            event_handler(Event::Tcp4Announce, &stats_repository).await;
+            event_handler(
+                sample_event(Protocol::Http, IpVersion::V4, RequestKind::Announce),
+                &stats_repository,
+            )
+            .await;
 
             let stats = stats_repository.get_stats().await;
 
@@ -370,7 +810,11 @@ mod tests {
         async fn should_increase_the_tcp4_connections_counter_when_it_receives_a_tcp4_announce_event() {
             let stats_repository = Repo::new();
 
-            event_handler(Event::Tcp4Announce, &stats_repository).await;
+            event_handler(
+                sample_event(Protocol::Http, IpVersion::V4, RequestKind::Announce),
+                &stats_repository,
+            )
+            .await;
 
             let stats = stats_repository.get_stats().await;
 
@@ -381,7 +825,11 @@ mod tests {
         async fn should_increase_the_tcp4_scrapes_counter_when_it_receives_a_tcp4_scrape_event() {
             let stats_repository = Repo::new();
 
-            event_handler(Event::Tcp4Scrape, &stats_repository).await;
+            event_handler(
+                sample_event(Protocol::Http, IpVersion::V4, RequestKind::Scrape),
+                &stats_repository,
+            )
+            .await;
 
             let stats = stats_repository.get_stats().await;
 
@@ -392,7 +840,11 @@ mod tests {
         async fn should_increase_the_tcp4_connections_counter_when_it_receives_a_tcp4_scrape_event() {
             let stats_repository = Repo::new();
 
-            event_handler(Event::Tcp4Scrape, &stats_repository).await;
+            event_handler(
+                sample_event(Protocol::Http, IpVersion::V4, RequestKind::Scrape),
+                &stats_repository,
+            )
+            .await;
 
             let stats = stats_repository.get_stats().await;
 
@@ -403,7 +855,11 @@ mod tests {
         async fn should_increase_the_tcp6_announces_counter_when_it_receives_a_tcp6_announce_event() {
             let stats_repository = Repo::new();
 
-            event_handler(Event::Tcp6Announce, &stats_repository).await;
+            event_handler(
+                sample_event(Protocol::Http, IpVersion::V6, RequestKind::Announce),
+                &stats_repository,
+            )
+            .await;
 
             let stats = stats_repository.get_stats().await;
 
@@ -414,7 +870,11 @@ mod tests {
         async fn should_increase_the_tcp6_connections_counter_when_it_receives_a_tcp6_announce_event() {
             let stats_repository = Repo::new();
 
-            event_handler(Event::Tcp6Announce, &stats_repository).await;
+            event_handler(
+                sample_event(Protocol::Http, IpVersion::V6, RequestKind::Announce),
+                &stats_repository,
+            )
+            .await;
 
             let stats = stats_repository.get_stats().await;
 
@@ -425,7 +885,11 @@ mod tests {
         async fn should_increase_the_tcp6_scrapes_counter_when_it_receives_a_tcp6_scrape_event() {
             let stats_repository = Repo::new();
 
-            event_handler(Event::Tcp6Scrape, &stats_repository).await;
+            event_handler(
+                sample_event(Protocol::Http, IpVersion::V6, RequestKind::Scrape),
+                &stats_repository,
+            )
+            .await;
 
             let stats = stats_repository.get_stats().await;
 
@@ -436,7 +900,11 @@ mod tests {
         async fn should_increase_the_tcp6_connections_counter_when_it_receives_a_tcp6_scrape_event() {
             let stats_repository = Repo::new();
 
-            event_handler(Event::Tcp6Scrape, &stats_repository).await;
+            event_handler(
+                sample_event(Protocol::Http, IpVersion::V6, RequestKind::Scrape),
+                &stats_repository,
+            )
+            .await;
 
             let stats = stats_repository.get_stats().await;
 
@@ -447,7 +915,11 @@ mod tests {
         async fn should_increase_the_udp4_connections_counter_when_it_receives_a_udp4_connect_event() {
             let stats_repository = Repo::new();
 
-            event_handler(Event::Udp4Connect, &stats_repository).await;
+            event_handler(
+                sample_event(Protocol::Udp, IpVersion::V4, RequestKind::Connect),
+                &stats_repository,
+            )
+            .await;
 
             let stats = stats_repository.get_stats().await;
 
@@ -458,7 +930,11 @@ mod tests {
         async fn should_increase_the_udp4_announces_counter_when_it_receives_a_udp4_announce_event() {
             let stats_repository = Repo::new();
 
-            event_handler(Event::Udp4Announce, &stats_repository).await;
+            event_handler(
+                sample_event(Protocol::Udp, IpVersion::V4, RequestKind::Announce),
+                &stats_repository,
+            )
+            .await;
 
             let stats = stats_repository.get_stats().await;
 
@@ -469,7 +945,11 @@ mod tests {
         async fn should_increase_the_udp4_scrapes_counter_when_it_receives_a_udp4_scrape_event() {
             let stats_repository = Repo::new();
 
-            event_handler(Event::Udp4Scrape, &stats_repository).await;
+            event_handler(
+                sample_event(Protocol::Udp, IpVersion::V4, RequestKind::Scrape),
+                &stats_repository,
+            )
+            .await;
 
             let stats = stats_repository.get_stats().await;
 
@@ -480,7 +960,11 @@ mod tests {
         async fn should_increase_the_udp6_connections_counter_when_it_receives_a_udp6_connect_event() {
             let stats_repository = Repo::new();
 
-            event_handler(Event::Udp6Connect, &stats_repository).await;
+            event_handler(
+                sample_event(Protocol::Udp, IpVersion::V6, RequestKind::Connect),
+                &stats_repository,
+            )
+            .await;
 
             let stats = stats_repository.get_stats().await;
 
@@ -491,7 +975,11 @@ mod tests {
         async fn should_increase_the_udp6_announces_counter_when_it_receives_a_udp6_announce_event() {
             let stats_repository = Repo::new();
 
-            event_handler(Event::Udp6Announce, &stats_repository).await;
+            event_handler(
+                sample_event(Protocol::Udp, IpVersion::V6, RequestKind::Announce),
+                &stats_repository,
+            )
+            .await;
 
             let stats = stats_repository.get_stats().await;
 
@@ -502,11 +990,136 @@ mod tests {
         async fn should_increase_the_udp6_scrapes_counter_when_it_receives_a_udp6_scrape_event() {
             let stats_repository = Repo::new();
 
-            event_handler(Event::Udp6Scrape, &stats_repository).await;
+            event_handler(
+                sample_event(Protocol::Udp, IpVersion::V6, RequestKind::Scrape),
+                &stats_repository,
+            )
+            .await;
 
             let stats = stats_repository.get_stats().await;
 
             assert_eq!(stats.udp6_scrapes_handled, 1);
         }
+
+        #[tokio::test]
+        async fn should_increase_the_udp4_requests_rate_limited_counter_when_it_receives_a_udp4_rate_limited_event() {
+            let stats_repository = Repo::new();
+
+            event_handler(
+                sample_event(Protocol::Udp, IpVersion::V4, RequestKind::RateLimited),
+                &stats_repository,
+            )
+            .await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.udp4_requests_rate_limited, 1);
+        }
+
+        #[tokio::test]
+        async fn should_increase_the_udp6_requests_rate_limited_counter_when_it_receives_a_udp6_rate_limited_event() {
+            let stats_repository = Repo::new();
+
+            event_handler(
+                sample_event(Protocol::Udp, IpVersion::V6, RequestKind::RateLimited),
+                &stats_repository,
+            )
+            .await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.udp6_requests_rate_limited, 1);
+        }
+
+        #[tokio::test]
+        async fn should_increase_the_tcp4_announces_too_frequent_and_connections_counters_when_it_receives_a_tcp4_announce_too_frequent_event(
+        ) {
+            let stats_repository = Repo::new();
+
+            event_handler(
+                sample_event(Protocol::Http, IpVersion::V4, RequestKind::AnnounceTooFrequent),
+                &stats_repository,
+            )
+            .await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.tcp4_announces_too_frequent, 1);
+            assert_eq!(stats.tcp4_connections_handled, 1);
+            assert_eq!(stats.tcp4_announces_handled, 0);
+        }
+
+        #[tokio::test]
+        async fn should_increase_the_udp6_announces_too_frequent_counter_when_it_receives_a_udp6_announce_too_frequent_event() {
+            let stats_repository = Repo::new();
+
+            event_handler(
+                sample_event(Protocol::Udp, IpVersion::V6, RequestKind::AnnounceTooFrequent),
+                &stats_repository,
+            )
+            .await;
+
+            let stats = stats_repository.get_stats().await;
+
+            assert_eq!(stats.udp6_announces_too_frequent, 1);
+        }
+
+        #[tokio::test]
+        async fn should_increase_the_counters_of_the_listener_that_handled_the_request() {
+            let stats_repository = Repo::new();
+
+            event_handler(
+                sample_event(Protocol::Udp, IpVersion::V4, RequestKind::Connect),
+                &stats_repository,
+            )
+            .await;
+            event_handler(
+                sample_event(Protocol::Udp, IpVersion::V6, RequestKind::Announce),
+                &stats_repository,
+            )
+            .await;
+
+            let listeners_stats = stats_repository.get_listeners_stats().await;
+
+            let udp_listener_stats = listeners_stats
+                .get(&Listener {
+                    protocol: Protocol::Udp,
+                    socket_addr: sample_server_socket_addr(),
+                })
+                .unwrap();
+
+            assert_eq!(udp_listener_stats.ipv4.connections_handled, 1);
+            assert_eq!(udp_listener_stats.ipv6.announces_handled, 1);
+        }
+
+        #[tokio::test]
+        async fn should_keep_separate_counters_for_http_and_udp_listeners_bound_to_the_same_socket_address() {
+            let stats_repository = Repo::new();
+
+            event_handler(
+                sample_event(Protocol::Udp, IpVersion::V4, RequestKind::Scrape),
+                &stats_repository,
+            )
+            .await;
+            event_handler(
+                sample_event(Protocol::Http, IpVersion::V4, RequestKind::Scrape),
+                &stats_repository,
+            )
+            .await;
+
+            let listeners_stats = stats_repository.get_listeners_stats().await;
+
+            assert_eq!(listeners_stats.len(), 2);
+
+            let http_listener_stats = listeners_stats
+                .get(&Listener {
+                    protocol: Protocol::Http,
+                    socket_addr: sample_server_socket_addr(),
+                })
+                .unwrap();
+
+            assert_eq!(http_listener_stats.ipv4.scrapes_handled, 1);
+            assert_eq!(http_listener_stats.ipv4.connections_handled, 1);
+        }
     }
 }
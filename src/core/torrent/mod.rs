@@ -25,6 +25,10 @@
 //! - The number of peers that have NOT completed downloading the torrent and are still active, that means they are actively participating in the network.
 //!   Peer that don not have a full copy of the torrent data are called "leechers".
 //!
+//! The swarms can be saved into a file and restored after a restart. Refer to
+//! the [`snapshot`] module for more information.
+pub mod snapshot;
+
 use torrust_tracker_torrent_repository::TorrentsSkipMapMutexStd;
 
 pub type Torrents = TorrentsSkipMapMutexStd; // Currently Used
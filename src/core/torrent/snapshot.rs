@@ -0,0 +1,367 @@
+//! Snapshots of the in-memory torrent repository.
+//!
+//! Peer lists are not stored in the database. Without snapshots, every
+//! restart empties all swarms and clients get empty peer lists until the other
+//! peers re-announce.
+//!
+//! A [`Snapshot`] contains all the torrents in memory with their peers and
+//! `downloaded` counters. It's saved as a JSON file:
+//!
+//! ```json
+//! {
+//!   "torrents": [
+//!     {
+//!       "info_hash": "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d",
+//!       "downloaded": 1,
+//!       "peers": [
+//!         {
+//!           "peer_id": [45, 113, 66, 48, 48, 48, 48, 48, 48, 48, 48, 48, 48, 48, 48, 48, 48, 48, 48, 49],
+//!           "peer_addr": "126.0.0.1:8080",
+//!           "updated": { "secs": 1669397478, "nanos": 934000000 },
+//!           "uploaded": 0,
+//!           "downloaded": 0,
+//!           "left": 0,
+//!           "event": "completed"
+//!         }
+//!       ]
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! Refer to the [`SwarmSnapshot`](torrust_tracker_configuration::SwarmSnapshot)
+//! configuration section for more information.
+use std::net::SocketAddr;
+use std::path::Path;
+use std::{fs, io};
+
+use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes, PeerId, PeerKey};
+use bittorrent_primitives::info_hash::InfoHash;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use torrust_tracker_configuration::TrackerPolicy;
+use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch, PersistentTorrents};
+use torrust_tracker_torrent_repository::entry::EntrySync;
+use torrust_tracker_torrent_repository::repository::Repository;
+
+use super::Torrents;
+
+/// Errors that can occur saving or loading a snapshot.
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("unable to access the swarm snapshot file {path}: {source}")]
+    Io { path: String, source: io::Error },
+
+    #[error("unable to decode the swarm snapshot file {path}: {source}")]
+    Decode { path: String, source: serde_json::Error },
+}
+
+/// The swarms stored in memory at a given time.
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+pub struct Snapshot {
+    pub torrents: Vec<Torrent>,
+}
+
+/// One torrent in the snapshot.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Torrent {
+    pub info_hash: InfoHash,
+    /// The number of peers that have ever completed downloading the torrent.
+    pub downloaded: u32,
+    pub peers: Vec<Peer>,
+}
+
+/// One peer in the snapshot.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Peer {
+    pub peer_id: [u8; 20],
+    pub peer_addr: SocketAddr,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alternative_addr: Option<SocketAddr>,
+    /// The announce `key` of the peer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<i32>,
+    pub updated: DurationSinceUnixEpoch,
+    pub uploaded: i64,
+    pub downloaded: i64,
+    pub left: i64,
+    pub event: Event,
+    /// Whether the peer is a partial seed.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub upload_only: bool,
+}
+
+/// The last announce event of a peer.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum Event {
+    Started,
+    Stopped,
+    Completed,
+    None,
+}
+
+impl From<AnnounceEvent> for Event {
+    fn from(event: AnnounceEvent) -> Self {
+        match event {
+            AnnounceEvent::Started => Self::Started,
+            AnnounceEvent::Stopped => Self::Stopped,
+            AnnounceEvent::Completed => Self::Completed,
+            AnnounceEvent::None => Self::None,
+        }
+    }
+}
+
+impl From<Event> for AnnounceEvent {
+    fn from(event: Event) -> Self {
+        match event {
+            Event::Started => Self::Started,
+            Event::Stopped => Self::Stopped,
+            Event::Completed => Self::Completed,
+            Event::None => Self::None,
+        }
+    }
+}
+
+impl From<&peer::Peer> for Peer {
+    fn from(peer: &peer::Peer) -> Self {
+        Self {
+            peer_id: peer.peer_id.0,
+            peer_addr: peer.peer_addr,
+            alternative_addr: peer.alternative_addr,
+            key: peer.key.map(|key| key.0.get()),
+            updated: peer.updated,
+            uploaded: peer.uploaded.0.get(),
+            downloaded: peer.downloaded.0.get(),
+            left: peer.left.0.get(),
+            event: peer.event.into(),
+            upload_only: peer.upload_only,
+        }
+    }
+}
+
+impl From<&Peer> for peer::Peer {
+    fn from(peer: &Peer) -> Self {
+        Self {
+            peer_id: PeerId(peer.peer_id),
+            peer_addr: peer.peer_addr,
+            alternative_addr: peer.alternative_addr,
+            key: peer.key.map(PeerKey::new),
+            updated: peer.updated,
+            uploaded: NumberOfBytes::new(peer.uploaded),
+            downloaded: NumberOfBytes::new(peer.downloaded),
+            left: NumberOfBytes::new(peer.left),
+            event: peer.event.into(),
+            upload_only: peer.upload_only,
+        }
+    }
+}
+
+impl Snapshot {
+    /// It takes a snapshot of all the torrents in the repository.
+    #[must_use]
+    pub fn take(torrents: &Torrents) -> Self {
+        let torrents = torrents
+            .get_paginated(None)
+            .into_iter()
+            .map(|(info_hash, entry)| Torrent {
+                info_hash,
+                downloaded: entry.get_swarm_metadata().downloaded,
+                peers: entry.get_peers(None).iter().map(|peer| Peer::from(peer.as_ref())).collect(),
+            })
+            .collect();
+
+        Self { torrents }
+    }
+
+    /// It restores the snapshot into the repository.
+    ///
+    /// Peers that have not been updated since the `current_cutoff` are
+    /// discarded. Torrents that are already in the repository keep their
+    /// `downloaded` counter. The peers are added following the Tracker Policy.
+    pub fn restore(&self, torrents: &Torrents, current_cutoff: DurationSinceUnixEpoch, policy: &TrackerPolicy) {
+        let persistent_torrents: PersistentTorrents = self
+            .torrents
+            .iter()
+            .map(|torrent| (torrent.info_hash, torrent.downloaded))
+            .collect();
+
+        torrents.import_persistent(&persistent_torrents);
+
+        for torrent in &self.torrents {
+            for peer in torrent.peers.iter().filter(|peer| peer.updated > current_cutoff) {
+                torrents.upsert_peer(&torrent.info_hash, &peer::Peer::from(peer), policy);
+            }
+        }
+    }
+
+    /// It saves the snapshot into a file. The file is replaced atomically, so
+    /// a crash while saving does not corrupt the previous snapshot.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the file cannot be written.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the snapshot cannot be serialized.
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let io_error = |source| Error::Io {
+            path: path.display().to_string(),
+            source,
+        };
+
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).map_err(io_error)?;
+        }
+
+        let contents = serde_json::to_vec(self).expect("it should serialize the snapshot");
+
+        let tmp_path = path.with_extension("tmp");
+
+        fs::write(&tmp_path, contents).map_err(io_error)?;
+        fs::rename(&tmp_path, path).map_err(io_error)
+    }
+
+    /// It loads a snapshot from a file. It returns `None` if the file does
+    /// not exist.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the file cannot be read or decoded.
+    pub fn load(path: &Path) -> Result<Option<Self>, Error> {
+        let contents = match fs::read(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(source) => {
+                return Err(Error::Io {
+                    path: path.display().to_string(),
+                    source,
+                })
+            }
+        };
+
+        serde_json::from_slice(&contents).map(Some).map_err(|source| Error::Decode {
+            path: path.display().to_string(),
+            source,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+    use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes, PeerId, PeerKey};
+    use bittorrent_primitives::info_hash::InfoHash;
+    use torrust_tracker_configuration::TrackerPolicy;
+    use torrust_tracker_primitives::{peer, DurationSinceUnixEpoch};
+    use torrust_tracker_torrent_repository::entry::EntrySync;
+    use torrust_tracker_torrent_repository::repository::Repository;
+
+    use crate::core::torrent::snapshot::Snapshot;
+    use crate::core::torrent::Torrents;
+
+    fn sample_info_hash() -> InfoHash {
+        "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".parse::<InfoHash>().unwrap()
+    }
+
+    fn sample_peer(id: u8, updated_secs: u64) -> peer::Peer {
+        let mut peer_id = *b"-qB00000000000000000";
+        peer_id[19] = id;
+
+        peer::Peer {
+            peer_id: PeerId(peer_id),
+            peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, id)), 8080),
+            alternative_addr: None,
+            key: Some(PeerKey::new(i32::from(id))),
+            updated: DurationSinceUnixEpoch::from_secs(updated_secs),
+            uploaded: NumberOfBytes::new(1),
+            downloaded: NumberOfBytes::new(2),
+            left: NumberOfBytes::new(0),
+            event: AnnounceEvent::Completed,
+            upload_only: false,
+        }
+    }
+
+    fn torrents_with_one_peer() -> Torrents {
+        let torrents = Torrents::default();
+
+        torrents.upsert_peer(&sample_info_hash(), &sample_peer(1, 100), &TrackerPolicy::default());
+
+        torrents
+    }
+
+    #[test]
+    fn it_should_restore_the_peers_of_a_snapshot() {
+        let snapshot = Snapshot::take(&torrents_with_one_peer());
+
+        let torrents = Torrents::default();
+
+        snapshot.restore(&torrents, DurationSinceUnixEpoch::from_secs(0), &TrackerPolicy::default());
+
+        let entry = torrents.get(&sample_info_hash()).unwrap();
+
+        assert_eq!(*entry.get_peers(None)[0], sample_peer(1, 100));
+    }
+
+    #[test]
+    fn it_should_discard_the_peers_that_have_not_been_updated_since_the_cutoff() {
+        let snapshot = Snapshot::take(&torrents_with_one_peer());
+
+        let torrents = Torrents::default();
+
+        snapshot.restore(&torrents, DurationSinceUnixEpoch::from_secs(100), &TrackerPolicy::default());
+
+        let entry = torrents.get(&sample_info_hash()).unwrap();
+
+        assert!(entry.peers_is_empty());
+    }
+
+    #[test]
+    fn it_should_restore_the_downloaded_counter() {
+        let torrents = torrents_with_one_peer();
+
+        // The peer announces again with the `completed` event after having started
+        let mut peer = sample_peer(2, 100);
+        peer.event = AnnounceEvent::Started;
+        torrents.upsert_peer(&sample_info_hash(), &peer, &TrackerPolicy::default());
+        peer.event = AnnounceEvent::Completed;
+        torrents.upsert_peer(&sample_info_hash(), &peer, &TrackerPolicy::default());
+
+        let snapshot = Snapshot::take(&torrents);
+
+        let restored_torrents = Torrents::default();
+
+        snapshot.restore(
+            &restored_torrents,
+            DurationSinceUnixEpoch::from_secs(0),
+            &TrackerPolicy::default(),
+        );
+
+        let entry = restored_torrents.get(&sample_info_hash()).unwrap();
+
+        assert_eq!(entry.get_swarm_metadata().downloaded, 1);
+    }
+
+    #[test]
+    fn it_should_be_saved_into_and_loaded_from_a_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("swarms.json");
+
+        let snapshot = Snapshot::take(&torrents_with_one_peer());
+
+        snapshot.save(&path).unwrap();
+
+        let loaded_snapshot = Snapshot::load(&path).unwrap();
+
+        assert_eq!(loaded_snapshot, Some(snapshot));
+    }
+
+    #[test]
+    fn it_should_not_load_anything_when_the_file_does_not_exist() {
+        let path = std::env::temp_dir().join(format!("swarms_{}.json", uuid::Uuid::new_v4()));
+
+        assert!(Snapshot::load(&path).unwrap().is_none());
+    }
+}
@@ -49,7 +49,7 @@
 //! - Built-in API
 //! - Peer authentication using time-bound keys
 //! - Database persistence for authentication keys, whitelist and completed peers counter
-//! - DB Support for `SQLite` and `MySQl`
+//! - DB Support for `SQLite`, `MySQl` and `PostgreSQL`
 //!
 //! # Services
 //!
@@ -171,6 +171,7 @@
 //! threshold = "info"
 //!
 //! [core]
+//! client_filter = "disabled"
 //! inactive_peer_cleanup_interval = 600
 //! listed = false
 //! private = false
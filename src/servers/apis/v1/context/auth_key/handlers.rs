@@ -4,18 +4,21 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use axum::extract::{self, Path, State};
-use axum::response::Response;
+use axum::response::{IntoResponse, Response};
 use serde::Deserialize;
+use torrust_tracker_primitives::info_hash_v2::parse_hex_info_hash;
 
 use super::forms::AddKeyForm;
 use super::responses::{
-    auth_key_response, failed_to_delete_key_response, failed_to_generate_key_response, failed_to_reload_keys_response,
-    invalid_auth_key_duration_response, invalid_auth_key_response,
+    allow_list_response, auth_key_response, failed_to_delete_key_response, failed_to_generate_key_response,
+    failed_to_reload_keys_response, failed_to_update_allow_list_response, failed_to_update_torrent_group_response,
+    invalid_auth_key_duration_response, invalid_auth_key_response, invalid_torrent_group_param_response, torrent_group_response,
 };
+use crate::core::access::TorrentGroup;
 use crate::core::auth::Key;
 use crate::core::{AddKeyRequest, Tracker};
-use crate::servers::apis::v1::context::auth_key::resources::AuthKey;
-use crate::servers::apis::v1::responses::{invalid_auth_key_param_response, ok_response};
+use crate::servers::apis::v1::context::auth_key::resources::{self, AllowList, AuthKey};
+use crate::servers::apis::v1::responses::{invalid_auth_key_param_response, invalid_info_hash_param_response, ok_response};
 
 /// It handles the request to add a new authentication key.
 ///
@@ -129,7 +132,250 @@ pub async fn delete_auth_key_handler(
 /// for more information about this endpoint.
 pub async fn reload_keys_handler(State(tracker): State<Arc<Tracker>>) -> Response {
     match tracker.load_keys_from_database().await {
-        Ok(()) => ok_response(),
+        Ok(()) => match tracker.load_torrent_access_from_database() {
+            Ok(()) => ok_response(),
+            Err(e) => failed_to_reload_keys_response(e),
+        },
         Err(e) => failed_to_reload_keys_response(e),
     }
 }
+
+/// The action to perform on an allow-list or a torrent group.
+enum Update {
+    Add,
+    Remove,
+}
+
+/// It handles the request to get the torrents an authentication key can
+/// access.
+///
+/// It returns:
+///
+/// - `200` response with a json [`AllowList`].
+/// - `400` response if the key is not valid.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::auth_key#get-the-allow-list-of-a-key)
+/// for more information about this endpoint.
+#[allow(clippy::unused_async)]
+pub async fn get_allow_list_handler(State(tracker): State<Arc<Tracker>>, Path(key): Path<String>) -> Response {
+    match Key::from_str(&key) {
+        Err(_) => invalid_auth_key_param_response(&key),
+        Ok(key) => {
+            let allow_list = tracker.get_key_allow_list(&key);
+            allow_list_response(AllowList::from((key, allow_list))).into_response()
+        }
+    }
+}
+
+/// It handles the request to remove the allow-list of a key, so that the key
+/// can access all the torrents again.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `400` response if the key is not valid.
+/// - `500` with serialized error in debug format if the allow-list couldn't be removed.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::auth_key#remove-the-allow-list-of-a-key)
+/// for more information about this endpoint.
+#[allow(clippy::unused_async)]
+pub async fn remove_allow_list_handler(State(tracker): State<Arc<Tracker>>, Path(key): Path<String>) -> Response {
+    let Ok(parsed_key) = Key::from_str(&key) else {
+        return invalid_auth_key_param_response(&key);
+    };
+
+    match tracker.remove_key_allow_list(&parsed_key) {
+        Ok(()) => ok_response(),
+        Err(e) => failed_to_update_allow_list_response(e),
+    }
+}
+
+/// It handles the request to add a torrent to the allow-list of a key.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `400` response if the key or the infohash are not valid.
+/// - `500` with serialized error in debug format if the allow-list couldn't be updated.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::auth_key#add-a-torrent-to-the-allow-list-of-a-key)
+/// for more information about this endpoint.
+#[allow(clippy::unused_async)]
+pub async fn add_torrent_to_allow_list_handler(
+    State(tracker): State<Arc<Tracker>>,
+    Path((key, info_hash)): Path<(String, String)>,
+) -> Response {
+    update_allow_list_torrent(&tracker, &key, &info_hash, &Update::Add)
+}
+
+/// It handles the request to remove a torrent from the allow-list of a key.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `400` response if the key or the infohash are not valid.
+/// - `500` with serialized error in debug format if the allow-list couldn't be updated.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::auth_key#remove-a-torrent-from-the-allow-list-of-a-key)
+/// for more information about this endpoint.
+#[allow(clippy::unused_async)]
+pub async fn remove_torrent_from_allow_list_handler(
+    State(tracker): State<Arc<Tracker>>,
+    Path((key, info_hash)): Path<(String, String)>,
+) -> Response {
+    update_allow_list_torrent(&tracker, &key, &info_hash, &Update::Remove)
+}
+
+fn update_allow_list_torrent(tracker: &Arc<Tracker>, key: &str, info_hash: &str, update: &Update) -> Response {
+    let Ok(parsed_key) = Key::from_str(key) else {
+        return invalid_auth_key_param_response(key);
+    };
+
+    let Ok(parsed_info_hash) = parse_hex_info_hash(info_hash) else {
+        return invalid_info_hash_param_response(info_hash);
+    };
+
+    let result = match update {
+        Update::Add => tracker.add_torrent_to_key_allow_list(&parsed_key, &parsed_info_hash),
+        Update::Remove => tracker.remove_torrent_from_key_allow_list(&parsed_key, &parsed_info_hash),
+    };
+
+    match result {
+        Ok(()) => ok_response(),
+        Err(e) => failed_to_update_allow_list_response(e),
+    }
+}
+
+/// It handles the request to add a torrent group to the allow-list of a key.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `400` response if the key or the group name are not valid.
+/// - `500` with serialized error in debug format if the allow-list couldn't be updated.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::auth_key#add-a-torrent-group-to-the-allow-list-of-a-key)
+/// for more information about this endpoint.
+#[allow(clippy::unused_async)]
+pub async fn add_group_to_allow_list_handler(
+    State(tracker): State<Arc<Tracker>>,
+    Path((key, group)): Path<(String, String)>,
+) -> Response {
+    update_allow_list_group(&tracker, &key, &group, &Update::Add)
+}
+
+/// It handles the request to remove a torrent group from the allow-list of a
+/// key.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `400` response if the key or the group name are not valid.
+/// - `500` with serialized error in debug format if the allow-list couldn't be updated.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::auth_key#remove-a-torrent-group-from-the-allow-list-of-a-key)
+/// for more information about this endpoint.
+#[allow(clippy::unused_async)]
+pub async fn remove_group_from_allow_list_handler(
+    State(tracker): State<Arc<Tracker>>,
+    Path((key, group)): Path<(String, String)>,
+) -> Response {
+    update_allow_list_group(&tracker, &key, &group, &Update::Remove)
+}
+
+fn update_allow_list_group(tracker: &Arc<Tracker>, key: &str, group: &str, update: &Update) -> Response {
+    let Ok(parsed_key) = Key::from_str(key) else {
+        return invalid_auth_key_param_response(key);
+    };
+
+    let Ok(parsed_group) = TorrentGroup::from_str(group) else {
+        return invalid_torrent_group_param_response(group);
+    };
+
+    let result = match update {
+        Update::Add => tracker.add_torrent_group_to_key_allow_list(&parsed_key, &parsed_group),
+        Update::Remove => tracker.remove_torrent_group_from_key_allow_list(&parsed_key, &parsed_group),
+    };
+
+    match result {
+        Ok(()) => ok_response(),
+        Err(e) => failed_to_update_allow_list_response(e),
+    }
+}
+
+/// It handles the request to get the torrents in a torrent group.
+///
+/// It returns:
+///
+/// - `200` response with a json [`TorrentGroup`](resources::TorrentGroup).
+/// - `400` response if the group name is not valid.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::auth_key#get-a-torrent-group)
+/// for more information about this endpoint.
+#[allow(clippy::unused_async)]
+pub async fn get_torrent_group_handler(State(tracker): State<Arc<Tracker>>, Path(group): Path<String>) -> Response {
+    match TorrentGroup::from_str(&group) {
+        Err(_) => invalid_torrent_group_param_response(&group),
+        Ok(group) => torrent_group_response(resources::TorrentGroup {
+            name: group.to_string(),
+            info_hashes: tracker.get_torrent_group(&group).iter().map(ToString::to_string).collect(),
+        })
+        .into_response(),
+    }
+}
+
+/// It handles the request to add a torrent to a torrent group.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `400` response if the group name or the infohash are not valid.
+/// - `500` with serialized error in debug format if the group couldn't be updated.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::auth_key#add-a-torrent-to-a-torrent-group)
+/// for more information about this endpoint.
+#[allow(clippy::unused_async)]
+pub async fn add_torrent_to_group_handler(
+    State(tracker): State<Arc<Tracker>>,
+    Path((group, info_hash)): Path<(String, String)>,
+) -> Response {
+    update_torrent_group(&tracker, &group, &info_hash, &Update::Add)
+}
+
+/// It handles the request to remove a torrent from a torrent group.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `400` response if the group name or the infohash are not valid.
+/// - `500` with serialized error in debug format if the group couldn't be updated.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::auth_key#remove-a-torrent-from-a-torrent-group)
+/// for more information about this endpoint.
+#[allow(clippy::unused_async)]
+pub async fn remove_torrent_from_group_handler(
+    State(tracker): State<Arc<Tracker>>,
+    Path((group, info_hash)): Path<(String, String)>,
+) -> Response {
+    update_torrent_group(&tracker, &group, &info_hash, &Update::Remove)
+}
+
+fn update_torrent_group(tracker: &Arc<Tracker>, group: &str, info_hash: &str, update: &Update) -> Response {
+    let Ok(parsed_group) = TorrentGroup::from_str(group) else {
+        return invalid_torrent_group_param_response(group);
+    };
+
+    let Ok(parsed_info_hash) = parse_hex_info_hash(info_hash) else {
+        return invalid_info_hash_param_response(info_hash);
+    };
+
+    let result = match update {
+        Update::Add => tracker.add_torrent_to_group(&parsed_group, &parsed_info_hash),
+        Update::Remove => tracker.remove_torrent_from_group(&parsed_group, &parsed_info_hash),
+    };
+
+    match result {
+        Ok(()) => ok_response(),
+        Err(e) => failed_to_update_torrent_group_response(e),
+    }
+}
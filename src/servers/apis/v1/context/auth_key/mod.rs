@@ -6,6 +6,10 @@
 //! When the tracker is running in `private` mode, the authentication keys are
 //! required to announce and scrape torrents.
 //!
+//! Keys can optionally have an allow-list of torrents and torrent groups. A
+//! key with an allow-list can only announce and scrape the torrents in it.
+//! Refer to the [`access`](crate::core::access) module for more information.
+//!
 //! A sample `announce` request **without** authentication key:
 //!
 //! <http://0.0.0.0:7070/announce?info_hash=12345678901234567890&peer_id=ABCDEFGHIJKLMNOPQRST&ip=255.255.255.255&port=6881&downloaded=1234&left=98765&event=stopped>
@@ -19,6 +23,15 @@
 //! - [Generate a new authentication key](#generate-a-new-authentication-key)
 //! - [Delete an authentication key](#delete-an-authentication-key)
 //! - [Reload authentication keys](#reload-authentication-keys)
+//! - [Get the allow-list of a key](#get-the-allow-list-of-a-key)
+//! - [Add a torrent to the allow-list of a key](#add-a-torrent-to-the-allow-list-of-a-key)
+//! - [Remove a torrent from the allow-list of a key](#remove-a-torrent-from-the-allow-list-of-a-key)
+//! - [Add a torrent group to the allow-list of a key](#add-a-torrent-group-to-the-allow-list-of-a-key)
+//! - [Remove a torrent group from the allow-list of a key](#remove-a-torrent-group-from-the-allow-list-of-a-key)
+//! - [Remove the allow-list of a key](#remove-the-allow-list-of-a-key)
+//! - [Get a torrent group](#get-a-torrent-group)
+//! - [Add a torrent to a torrent group](#add-a-torrent-to-a-torrent-group)
+//! - [Remove a torrent from a torrent group](#remove-a-torrent-from-a-torrent-group)
 //!
 //! # Generate a new authentication key
 //!
@@ -111,7 +124,8 @@
 //! `GET /keys/reload`
 //!
 //! The tracker persists the authentication keys in a database. This endpoint
-//! reloads the keys from the database.
+//! reloads the keys, their allow-lists and the torrent groups from the
+//! database.
 //!
 //! **Example request**
 //!
@@ -126,6 +140,221 @@
 //!     "status": "ok"
 //! }
 //! ```
+//!
+//! # Get the allow-list of a key
+//!
+//! `GET /keys/:key/torrents`
+//!
+//! It returns the torrents and torrent groups the key can access. A key which
+//! is not `restricted` can access all the torrents, and both lists are empty.
+//! A restricted key with empty lists can not access any torrent.
+//!
+//! **Path parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `key` | 32-char string | The authentication key. | Yes | `xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/keys/xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6/torrents?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "key": "xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6",
+//!     "restricted": true,
+//!     "info_hashes": ["9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d"],
+//!     "groups": ["linux-isos"]
+//! }
+//! ```
+//!
+//! **Resource**
+//!
+//! Refer to the API [`AllowList`](crate::servers::apis::v1::context::auth_key::resources::AllowList)
+//! resource for more information about the response attributes.
+//!
+//! # Add a torrent to the allow-list of a key
+//!
+//! `POST /keys/:key/torrents/:info_hash`
+//!
+//! It adds a torrent to the allow-list of the key. From then on, the key can
+//! only access the torrents in its allow-list, until the allow-list is
+//! [removed](#remove-the-allow-list-of-a-key).
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X POST "http://127.0.0.1:1212/api/v1/keys/xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6/torrents/9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+//!
+//! # Remove a torrent from the allow-list of a key
+//!
+//! `DELETE /keys/:key/torrents/:info_hash`
+//!
+//! It removes a torrent from the allow-list of the key.
+//!
+//! > **NOTICE**: the key is still restricted when its allow-list gets empty,
+//! > so it can not access any torrent. Remove the whole
+//! > [allow-list](#remove-the-allow-list-of-a-key) to lift the restriction.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X DELETE "http://127.0.0.1:1212/api/v1/keys/xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6/torrents/9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+//!
+//! # Add a torrent group to the allow-list of a key
+//!
+//! `POST /keys/:key/groups/:group`
+//!
+//! It adds a torrent group to the allow-list of the key, so that the key can
+//! access all the torrents in the group. Group names can only contain up to
+//! 64 ASCII letters, digits, `-` and `_`.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X POST "http://127.0.0.1:1212/api/v1/keys/xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6/groups/linux-isos?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+//!
+//! # Remove a torrent group from the allow-list of a key
+//!
+//! `DELETE /keys/:key/groups/:group`
+//!
+//! It removes a torrent group from the allow-list of the key.
+//!
+//! > **NOTICE**: like when removing a torrent, the key is still restricted
+//! > when its allow-list gets empty.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X DELETE "http://127.0.0.1:1212/api/v1/keys/xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6/groups/linux-isos?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+//!
+//! # Remove the allow-list of a key
+//!
+//! `DELETE /keys/:key/torrents`
+//!
+//! It removes all the torrents and torrent groups from the allow-list of the
+//! key and lifts the restriction, so that the key can access all the torrents
+//! again.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X DELETE "http://127.0.0.1:1212/api/v1/keys/xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6/torrents?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+//!
+//! # Get a torrent group
+//!
+//! `GET /torrent-groups/:group`
+//!
+//! It returns the torrents in the group.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/torrent-groups/linux-isos?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "name": "linux-isos",
+//!     "info_hashes": ["9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d"]
+//! }
+//! ```
+//!
+//! **Resource**
+//!
+//! Refer to the API [`TorrentGroup`](crate::servers::apis::v1::context::auth_key::resources::TorrentGroup)
+//! resource for more information about the response attributes.
+//!
+//! # Add a torrent to a torrent group
+//!
+//! `POST /torrent-groups/:group/torrents/:info_hash`
+//!
+//! It adds a torrent to the group. The group is created if it does not exist.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X POST "http://127.0.0.1:1212/api/v1/torrent-groups/linux-isos/torrents/9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+//!
+//! # Remove a torrent from a torrent group
+//!
+//! `DELETE /torrent-groups/:group/torrents/:info_hash`
+//!
+//! It removes a torrent from the group.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X DELETE "http://127.0.0.1:1212/api/v1/torrent-groups/linux-isos/torrents/9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
 pub mod forms;
 pub mod handlers;
 pub mod resources;
@@ -3,6 +3,7 @@
 use serde::{Deserialize, Serialize};
 use torrust_tracker_clock::conv::convert_from_iso_8601_to_timestamp;
 
+use crate::core::access;
 use crate::core::auth::{self, Key};
 
 /// A resource that represents an authentication key.
@@ -46,6 +47,43 @@ impl From<auth::PeerKey> for AuthKey {
     }
 }
 
+/// A resource that represents the torrents an authentication key can access.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct AllowList {
+    /// The authentication key.
+    pub key: String,
+    /// Whether the key can only access the torrents in its allow-list. A
+    /// restricted key with an empty allow-list can not access any torrent.
+    pub restricted: bool,
+    /// The infohashes of the torrents the key can access.
+    pub info_hashes: Vec<String>,
+    /// The torrent groups the key can access.
+    pub groups: Vec<String>,
+}
+
+impl From<(Key, Option<access::AllowList>)> for AllowList {
+    fn from((key, maybe_allow_list): (Key, Option<access::AllowList>)) -> Self {
+        let restricted = maybe_allow_list.is_some();
+        let allow_list = maybe_allow_list.unwrap_or_default();
+
+        Self {
+            key: key.to_string(),
+            restricted,
+            info_hashes: allow_list.info_hashes.iter().map(ToString::to_string).collect(),
+            groups: allow_list.groups.iter().map(ToString::to_string).collect(),
+        }
+    }
+}
+
+/// A resource that represents a group of torrents.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct TorrentGroup {
+    /// The name of the group.
+    pub name: String,
+    /// The infohashes of the torrents in the group.
+    pub info_hashes: Vec<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use std::time::Duration;
@@ -2,9 +2,9 @@
 use std::error::Error;
 
 use axum::http::{header, StatusCode};
-use axum::response::{IntoResponse, Response};
+use axum::response::{IntoResponse, Json, Response};
 
-use crate::servers::apis::v1::context::auth_key::resources::AuthKey;
+use crate::servers::apis::v1::context::auth_key::resources::{AllowList, AuthKey, TorrentGroup};
 use crate::servers::apis::v1::responses::{bad_request_response, unhandled_rejection_response};
 
 /// `200` response that contains the `AuthKey` resource as json.
@@ -22,6 +22,16 @@ pub fn auth_key_response(auth_key: &AuthKey) -> Response {
         .into_response()
 }
 
+/// `200` response that contains the [`AllowList`] resource as json.
+pub fn allow_list_response(allow_list: AllowList) -> Json<AllowList> {
+    Json(allow_list)
+}
+
+/// `200` response that contains the [`TorrentGroup`] resource as json.
+pub fn torrent_group_response(torrent_group: TorrentGroup) -> Json<TorrentGroup> {
+    Json(torrent_group)
+}
+
 // Error responses
 
 /// `500` error response when a new authentication key cannot be generated.
@@ -58,3 +68,22 @@ pub fn invalid_auth_key_response<E: Error>(auth_key: &str, e: E) -> Response {
 pub fn invalid_auth_key_duration_response(duration: u64) -> Response {
     bad_request_response(&format!("Invalid URL: invalid auth key duration: \"{duration}\""))
 }
+
+#[must_use]
+pub fn invalid_torrent_group_param_response(group: &str) -> Response {
+    bad_request_response(&format!(
+        "Invalid URL: invalid torrent group param: string \"{group}\", expected up to 64 letters, digits, '-' or '_'"
+    ))
+}
+
+/// `500` error response when the allow-list of a key cannot be updated.
+#[must_use]
+pub fn failed_to_update_allow_list_response<E: Error>(e: E) -> Response {
+    unhandled_rejection_response(format!("failed to update allow-list: {e}"))
+}
+
+/// `500` error response when a torrent group cannot be updated.
+#[must_use]
+pub fn failed_to_update_torrent_group_response<E: Error>(e: E) -> Response {
+    unhandled_rejection_response(format!("failed to update torrent group: {e}"))
+}
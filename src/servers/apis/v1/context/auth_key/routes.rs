@@ -4,6 +4,15 @@
 //! - `POST /key/:seconds_valid`
 //! - `DELETE /key/:key`
 //! - `GET /keys/reload`
+//! - `GET /keys/:key/torrents`
+//! - `DELETE /keys/:key/torrents`
+//! - `POST /keys/:key/torrents/:info_hash`
+//! - `DELETE /keys/:key/torrents/:info_hash`
+//! - `POST /keys/:key/groups/:group`
+//! - `DELETE /keys/:key/groups/:group`
+//! - `GET /torrent-groups/:group`
+//! - `POST /torrent-groups/:group/torrents/:info_hash`
+//! - `DELETE /torrent-groups/:group/torrents/:info_hash`
 //!
 //! Refer to the [API endpoint documentation](crate::servers::apis::v1::context::auth_key).
 use std::sync::Arc;
@@ -11,7 +20,12 @@ use std::sync::Arc;
 use axum::routing::{get, post};
 use axum::Router;
 
-use super::handlers::{add_auth_key_handler, delete_auth_key_handler, generate_auth_key_handler, reload_keys_handler};
+use super::handlers::{
+    add_auth_key_handler, add_group_to_allow_list_handler, add_torrent_to_allow_list_handler, add_torrent_to_group_handler,
+    delete_auth_key_handler, generate_auth_key_handler, get_allow_list_handler, get_torrent_group_handler, reload_keys_handler,
+    remove_allow_list_handler, remove_group_from_allow_list_handler, remove_torrent_from_allow_list_handler,
+    remove_torrent_from_group_handler,
+};
 use crate::core::Tracker;
 
 /// It adds the routes to the router for the [`auth_key`](crate::servers::apis::v1::context::auth_key) API context.
@@ -38,5 +52,38 @@ pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
             &format!("{prefix}/keys/reload"),
             get(reload_keys_handler).with_state(tracker.clone()),
         )
-        .route(&format!("{prefix}/keys"), post(add_auth_key_handler).with_state(tracker))
+        .route(
+            &format!("{prefix}/keys"),
+            post(add_auth_key_handler).with_state(tracker.clone()),
+        )
+        // Key allow-lists
+        .route(
+            &format!("{prefix}/keys/:key/torrents"),
+            get(get_allow_list_handler)
+                .delete(remove_allow_list_handler)
+                .with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/keys/:key/torrents/:info_hash"),
+            post(add_torrent_to_allow_list_handler)
+                .delete(remove_torrent_from_allow_list_handler)
+                .with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/keys/:key/groups/:group"),
+            post(add_group_to_allow_list_handler)
+                .delete(remove_group_from_allow_list_handler)
+                .with_state(tracker.clone()),
+        )
+        // Torrent groups
+        .route(
+            &format!("{prefix}/torrent-groups/:group"),
+            get(get_torrent_group_handler).with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/torrent-groups/:group/torrents/:info_hash"),
+            post(add_torrent_to_group_handler)
+                .delete(remove_torrent_from_group_handler)
+                .with_state(tracker),
+        )
 }
@@ -0,0 +1,76 @@
+//! API handlers for the [`blacklist`](crate::servers::apis::v1::context::blacklist)
+//! API context.
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::response::Response;
+use torrust_tracker_primitives::info_hash_v2::parse_hex_info_hash;
+
+use super::responses::{
+    failed_to_blacklist_torrent_response, failed_to_reload_blacklist_response, failed_to_remove_torrent_from_blacklist_response,
+};
+use crate::core::Tracker;
+use crate::servers::apis::v1::responses::{invalid_info_hash_param_response, ok_response};
+use crate::servers::apis::InfoHashParam;
+
+/// It handles the request to add a torrent to the blacklist.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `500` with serialized error in debug format if the torrent couldn't be blacklisted.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::blacklist#add-a-torrent-to-the-blacklist)
+/// for more information about this endpoint.
+pub async fn add_torrent_to_blacklist_handler(
+    State(tracker): State<Arc<Tracker>>,
+    Path(info_hash): Path<InfoHashParam>,
+) -> Response {
+    match parse_hex_info_hash(&info_hash.0) {
+        Err(_) => invalid_info_hash_param_response(&info_hash.0),
+        Ok(info_hash) => match tracker.add_torrent_to_blacklist(&info_hash).await {
+            Ok(()) => ok_response(),
+            Err(e) => failed_to_blacklist_torrent_response(e),
+        },
+    }
+}
+
+/// It handles the request to remove a torrent from the blacklist.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `500` with serialized error in debug format if the torrent couldn't be
+///   removed from the blacklist.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::blacklist#remove-a-torrent-from-the-blacklist)
+/// for more information about this endpoint.
+pub async fn remove_torrent_from_blacklist_handler(
+    State(tracker): State<Arc<Tracker>>,
+    Path(info_hash): Path<InfoHashParam>,
+) -> Response {
+    match parse_hex_info_hash(&info_hash.0) {
+        Err(_) => invalid_info_hash_param_response(&info_hash.0),
+        Ok(info_hash) => match tracker.remove_torrent_from_blacklist(&info_hash).await {
+            Ok(()) => ok_response(),
+            Err(e) => failed_to_remove_torrent_from_blacklist_response(e),
+        },
+    }
+}
+
+/// It handles the request to reload the torrent blacklist from the database.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `500` with serialized error in debug format if the torrent blacklist
+///   couldn't be reloaded from the database.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::blacklist#reload-the-blacklist)
+/// for more information about this endpoint.
+pub async fn reload_blacklist_handler(State(tracker): State<Arc<Tracker>>) -> Response {
+    match tracker.load_blacklist_from_database().await {
+        Ok(()) => ok_response(),
+        Err(e) => failed_to_reload_blacklist_response(e),
+    }
+}
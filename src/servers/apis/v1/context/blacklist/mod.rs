@@ -0,0 +1,99 @@
+//! Blacklist API context.
+//!
+//! This API context is responsible for handling all the requests related to
+//! the torrent blacklist.
+//!
+//! A torrent blacklist is a list of Info Hashes that are banned from the
+//! tracker. This is useful for public trackers that accept any torrent except
+//! a few ones, for example after a takedown notice.
+//!
+//! The `announce` requests on blacklisted torrents are refused with the
+//! `reason` in the `core.blacklist` section of the configuration, and the
+//! `scrape` requests get zeroed swarm metadata. The blacklist can be updated
+//! using the API.
+//!
+//! > **NOTICE**: unlike the whitelist, the blacklist is used in all the
+//! > tracker modes. A blacklisted torrent is refused even if it's also
+//! > whitelisted.
+//!
+//! # Endpoints
+//!
+//! - [Add a torrent to the blacklist](#add-a-torrent-to-the-blacklist)
+//! - [Remove a torrent from the blacklist](#remove-a-torrent-from-the-blacklist)
+//! - [Reload the blacklist](#reload-the-blacklist)
+//!
+//! # Add a torrent to the blacklist
+//!
+//! `POST /blacklist/:info_hash`
+//!
+//! It adds a torrent infohash to the blacklist.
+//!
+//! A v2 infohash (64-char string) is truncated to 20 bytes, which is the value
+//! `BitTorrent` v2 clients send to the tracker.
+//!
+//! **Path parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `info_hash` | 40-char or 64-char string | The Info Hash v1 or v2 | Yes | `5452869be36f9f3350ccee6b4544e7e76caaadab`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X POST "http://127.0.0.1:1212/api/v1/blacklist/5452869be36f9f3350ccee6b4544e7e76caaadab?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+//!
+//! # Remove a torrent from the blacklist
+//!
+//! `DELETE /blacklist/:info_hash`
+//!
+//! It removes a torrent infohash from the blacklist.
+//!
+//! **Path parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `info_hash` | 40-char or 64-char string | The Info Hash v1 or v2 | Yes | `5452869be36f9f3350ccee6b4544e7e76caaadab`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X DELETE "http://127.0.0.1:1212/api/v1/blacklist/5452869be36f9f3350ccee6b4544e7e76caaadab?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+//!
+//! # Reload the blacklist
+//!
+//! It reloads the blacklist from the database.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/blacklist/reload?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+pub mod handlers;
+pub mod responses;
+pub mod routes;
@@ -0,0 +1,25 @@
+//! API responses for the [`blacklist`](crate::servers::apis::v1::context::blacklist)
+//! API context.
+use std::error::Error;
+
+use axum::response::Response;
+
+use crate::servers::apis::v1::responses::unhandled_rejection_response;
+
+/// `500` error response when a torrent cannot be removed from the blacklist.
+#[must_use]
+pub fn failed_to_remove_torrent_from_blacklist_response<E: Error>(e: E) -> Response {
+    unhandled_rejection_response(format!("failed to remove torrent from blacklist: {e}"))
+}
+
+/// `500` error response when a torrent cannot be added to the blacklist.
+#[must_use]
+pub fn failed_to_blacklist_torrent_response<E: Error>(e: E) -> Response {
+    unhandled_rejection_response(format!("failed to blacklist torrent: {e}"))
+}
+
+/// `500` error response when the blacklist cannot be reloaded from the database.
+#[must_use]
+pub fn failed_to_reload_blacklist_response<E: Error>(e: E) -> Response {
+    unhandled_rejection_response(format!("failed to reload blacklist: {e}"))
+}
@@ -0,0 +1,32 @@
+//! API routes for the [`blacklist`](crate::servers::apis::v1::context::blacklist) API context.
+//!
+//! - `POST /blacklist/:info_hash`
+//! - `DELETE /blacklist/:info_hash`
+//! - `GET /blacklist/reload`
+//!
+//! Refer to the [API endpoint documentation](crate::servers::apis::v1::context::blacklist).
+use std::sync::Arc;
+
+use axum::routing::{delete, get, post};
+use axum::Router;
+
+use super::handlers::{add_torrent_to_blacklist_handler, reload_blacklist_handler, remove_torrent_from_blacklist_handler};
+use crate::core::Tracker;
+
+/// It adds the routes to the router for the [`blacklist`](crate::servers::apis::v1::context::blacklist) API context.
+pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
+    let prefix = format!("{prefix}/blacklist");
+
+    router
+        // Blacklisted torrents
+        .route(
+            &format!("{prefix}/:info_hash"),
+            post(add_torrent_to_blacklist_handler).with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/:info_hash"),
+            delete(remove_torrent_from_blacklist_handler).with_state(tracker.clone()),
+        )
+        // Blacklist commands
+        .route(&format!("{prefix}/reload"), get(reload_blacklist_handler).with_state(tracker))
+}
@@ -0,0 +1,87 @@
+//! API handlers for the [`client_filter`](crate::servers::apis::v1::context::client_filter)
+//! API context.
+use std::str::FromStr;
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::response::{IntoResponse, Response};
+
+use super::resources::ClientFilter;
+use super::responses::{
+    client_filter_response, failed_to_reload_client_filter_response, failed_to_update_client_filter_response,
+    invalid_peer_id_prefix_param_response,
+};
+use crate::core::client_filter::PeerIdPrefix;
+use crate::core::Tracker;
+use crate::servers::apis::v1::responses::ok_response;
+
+/// It handles the request to get the client filter.
+///
+/// It returns a `200` response with a json [`ClientFilter`].
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::client_filter#get-the-client-filter)
+/// for more information about this endpoint.
+#[allow(clippy::unused_async)]
+pub async fn get_client_filter_handler(State(tracker): State<Arc<Tracker>>) -> Response {
+    client_filter_response(ClientFilter::from(tracker.as_ref())).into_response()
+}
+
+/// It handles the request to add a `peer_id` prefix to the client filter.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `400` response if the prefix is not valid.
+/// - `500` with serialized error in debug format if the prefix couldn't be added.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::client_filter#add-a-prefix-to-the-client-filter)
+/// for more information about this endpoint.
+#[allow(clippy::unused_async)]
+pub async fn add_client_prefix_handler(State(tracker): State<Arc<Tracker>>, Path(prefix): Path<String>) -> Response {
+    match PeerIdPrefix::from_str(&prefix) {
+        Err(_) => invalid_peer_id_prefix_param_response(&prefix),
+        Ok(prefix) => match tracker.add_client_prefix(&prefix) {
+            Ok(()) => ok_response(),
+            Err(e) => failed_to_update_client_filter_response(e),
+        },
+    }
+}
+
+/// It handles the request to remove a `peer_id` prefix from the client filter.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `400` response if the prefix is not valid.
+/// - `500` with serialized error in debug format if the prefix couldn't be removed.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::client_filter#remove-a-prefix-from-the-client-filter)
+/// for more information about this endpoint.
+#[allow(clippy::unused_async)]
+pub async fn remove_client_prefix_handler(State(tracker): State<Arc<Tracker>>, Path(prefix): Path<String>) -> Response {
+    match PeerIdPrefix::from_str(&prefix) {
+        Err(_) => invalid_peer_id_prefix_param_response(&prefix),
+        Ok(prefix) => match tracker.remove_client_prefix(&prefix) {
+            Ok(()) => ok_response(),
+            Err(e) => failed_to_update_client_filter_response(e),
+        },
+    }
+}
+
+/// It handles the request to reload the client filter prefixes from the database.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `500` with serialized error in debug format if the prefixes couldn't be
+///   reloaded from the database.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::client_filter#reload-the-client-filter)
+/// for more information about this endpoint.
+#[allow(clippy::unused_async)]
+pub async fn reload_client_filter_handler(State(tracker): State<Arc<Tracker>>) -> Response {
+    match tracker.load_client_prefixes_from_database() {
+        Ok(()) => ok_response(),
+        Err(e) => failed_to_reload_client_filter_response(e),
+    }
+}
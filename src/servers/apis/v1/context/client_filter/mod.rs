@@ -0,0 +1,134 @@
+//! Client filter API context.
+//!
+//! This API context is responsible for handling all the requests related to
+//! the client software filter.
+//!
+//! `BitTorrent` clients put their name and version at the beginning of the
+//! `peer_id`, like `-qB4500-` for qBittorrent 4.5.0. The tracker keeps a list
+//! of `peer_id` prefixes that can be used to refuse announces from broken or
+//! cheating clients. Depending on the `client_filter` configuration option the
+//! list is:
+//!
+//! - `disabled`: ignored. All the clients can announce.
+//! - `allow`: an allow-list. Only the clients whose `peer_id` starts with one
+//!   of the prefixes can announce.
+//! - `deny`: a deny-list. The clients whose `peer_id` starts with one of the
+//!   prefixes can't announce.
+//!
+//! Refer to the [`client_filter`](crate::core::client_filter) module for more
+//! information.
+//!
+//! > **NOTICE**: the mode can only be changed in the configuration. The list
+//! > of prefixes can be updated using the API in any mode.
+//!
+//! # Endpoints
+//!
+//! - [Get the client filter](#get-the-client-filter)
+//! - [Add a prefix to the client filter](#add-a-prefix-to-the-client-filter)
+//! - [Remove a prefix from the client filter](#remove-a-prefix-from-the-client-filter)
+//! - [Reload the client filter](#reload-the-client-filter)
+//!
+//! # Get the client filter
+//!
+//! `GET /client-filter`
+//!
+//! It returns the client filter mode and the list of `peer_id` prefixes.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/client-filter?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "mode": "deny",
+//!     "prefixes": [
+//!         "-XL0012-",
+//!         "-qB4500-"
+//!     ]
+//! }
+//! ```
+//!
+//! **Resource**
+//!
+//! Refer to the API [`ClientFilter`](crate::servers::apis::v1::context::client_filter::resources::ClientFilter)
+//! resource for more information about the response attributes.
+//!
+//! # Add a prefix to the client filter
+//!
+//! `POST /client-filter/prefixes/:prefix`
+//!
+//! It adds a `peer_id` prefix to the client filter.
+//!
+//! **Path parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `prefix` | Up to 20 printable ASCII chars | The beginning of the `peer_id` | Yes | `-qB4500-`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X POST "http://127.0.0.1:1212/api/v1/client-filter/prefixes/-qB4500-?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+//!
+//! # Remove a prefix from the client filter
+//!
+//! `DELETE /client-filter/prefixes/:prefix`
+//!
+//! It removes a `peer_id` prefix from the client filter.
+//!
+//! **Path parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `prefix` | Up to 20 printable ASCII chars | The beginning of the `peer_id` | Yes | `-qB4500-`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X DELETE "http://127.0.0.1:1212/api/v1/client-filter/prefixes/-qB4500-?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+//!
+//! # Reload the client filter
+//!
+//! `GET /client-filter/reload`
+//!
+//! It reloads the list of `peer_id` prefixes from the database.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/client-filter/reload?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+pub mod handlers;
+pub mod resources;
+pub mod responses;
+pub mod routes;
@@ -0,0 +1,23 @@
+//! API resources for the [`client_filter`](crate::servers::apis::v1::context::client_filter)
+//! API context.
+use serde::{Deserialize, Serialize};
+
+use crate::core::Tracker;
+
+/// The client filter mode and its `peer_id` prefixes.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct ClientFilter {
+    /// How the prefixes are used: `disabled`, `allow` or `deny`.
+    pub mode: torrust_tracker_configuration::ClientFilter,
+    /// The `peer_id` prefixes, sorted.
+    pub prefixes: Vec<String>,
+}
+
+impl From<&Tracker> for ClientFilter {
+    fn from(tracker: &Tracker) -> Self {
+        Self {
+            mode: tracker.get_client_filter(),
+            prefixes: tracker.get_client_prefixes().iter().map(ToString::to_string).collect(),
+        }
+    }
+}
@@ -0,0 +1,33 @@
+//! API responses for the [`client_filter`](crate::servers::apis::v1::context::client_filter)
+//! API context.
+use std::error::Error;
+
+use axum::response::{Json, Response};
+
+use super::resources::ClientFilter;
+use crate::core::client_filter::PEER_ID_PREFIX_MAX_LENGTH;
+use crate::servers::apis::v1::responses::{bad_request_response, unhandled_rejection_response};
+
+/// `200` response that contains the [`ClientFilter`] resource as json.
+pub fn client_filter_response(client_filter: ClientFilter) -> Json<ClientFilter> {
+    Json(client_filter)
+}
+
+#[must_use]
+pub fn invalid_peer_id_prefix_param_response(prefix: &str) -> Response {
+    bad_request_response(&format!(
+        "Invalid URL: invalid peer id prefix param: string \"{prefix}\", expected up to {PEER_ID_PREFIX_MAX_LENGTH} printable ASCII chars"
+    ))
+}
+
+/// `500` error response when the client filter cannot be updated.
+#[must_use]
+pub fn failed_to_update_client_filter_response<E: Error>(e: E) -> Response {
+    unhandled_rejection_response(format!("failed to update client filter: {e}"))
+}
+
+/// `500` error response when the client filter cannot be reloaded from the database.
+#[must_use]
+pub fn failed_to_reload_client_filter_response<E: Error>(e: E) -> Response {
+    unhandled_rejection_response(format!("failed to reload client filter: {e}"))
+}
@@ -0,0 +1,35 @@
+//! API routes for the [`client_filter`](crate::servers::apis::v1::context::client_filter) API context.
+//!
+//! - `GET /client-filter`
+//! - `POST /client-filter/prefixes/:prefix`
+//! - `DELETE /client-filter/prefixes/:prefix`
+//! - `GET /client-filter/reload`
+//!
+//! Refer to the [API endpoint documentation](crate::servers::apis::v1::context::client_filter).
+use std::sync::Arc;
+
+use axum::routing::{get, post};
+use axum::Router;
+
+use super::handlers::{
+    add_client_prefix_handler, get_client_filter_handler, reload_client_filter_handler, remove_client_prefix_handler,
+};
+use crate::core::Tracker;
+
+/// It adds the routes to the router for the [`client_filter`](crate::servers::apis::v1::context::client_filter) API context.
+pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
+    let prefix = format!("{prefix}/client-filter");
+
+    router
+        .route(&prefix, get(get_client_filter_handler).with_state(tracker.clone()))
+        .route(
+            &format!("{prefix}/prefixes/:prefix"),
+            post(add_client_prefix_handler)
+                .delete(remove_client_prefix_handler)
+                .with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/reload"),
+            get(reload_client_filter_handler).with_state(tracker),
+        )
+}
@@ -0,0 +1,102 @@
+//! API handlers for the [`ip_blocklist`](crate::servers::apis::v1::context::ip_blocklist)
+//! API context.
+use std::str::FromStr;
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::response::{IntoResponse, Response};
+use ipnet::IpNet;
+
+use super::resources::IpBlocklist;
+use super::responses::{
+    failed_to_reload_ip_blocklist_response, failed_to_update_ip_blocklist_response, invalid_network_param_response,
+    ip_blocklist_response,
+};
+use crate::core::Tracker;
+use crate::servers::apis::v1::responses::ok_response;
+
+/// It handles the request to get the IP blocklist.
+///
+/// It returns a `200` response with a json [`IpBlocklist`].
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::ip_blocklist#get-the-ip-blocklist)
+/// for more information about this endpoint.
+#[allow(clippy::unused_async)]
+pub async fn get_ip_blocklist_handler(State(tracker): State<Arc<Tracker>>) -> Response {
+    ip_blocklist_response(IpBlocklist::from(tracker.as_ref())).into_response()
+}
+
+/// It handles the request to block a network.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `400` response if the network is not valid.
+/// - `500` with serialized error in debug format if the network couldn't be blocked.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::ip_blocklist#block-a-network)
+/// for more information about this endpoint.
+#[allow(clippy::unused_async)]
+pub async fn add_blocked_network_handler(
+    State(tracker): State<Arc<Tracker>>,
+    Path((ip, prefix_len)): Path<(String, String)>,
+) -> Response {
+    let network = format!("{ip}/{prefix_len}");
+
+    match IpNet::from_str(&network) {
+        Err(_) => invalid_network_param_response(&network),
+        Ok(network) => match tracker.add_blocked_network(&network) {
+            Ok(()) => ok_response(),
+            Err(e) => failed_to_update_ip_blocklist_response(e),
+        },
+    }
+}
+
+/// It handles the request to unblock a network.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `400` response if the network is not valid.
+/// - `500` with serialized error in debug format if the network couldn't be unblocked.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::ip_blocklist#unblock-a-network)
+/// for more information about this endpoint.
+#[allow(clippy::unused_async)]
+pub async fn remove_blocked_network_handler(
+    State(tracker): State<Arc<Tracker>>,
+    Path((ip, prefix_len)): Path<(String, String)>,
+) -> Response {
+    let network = format!("{ip}/{prefix_len}");
+
+    match IpNet::from_str(&network) {
+        Err(_) => invalid_network_param_response(&network),
+        Ok(network) => match tracker.remove_blocked_network(&network) {
+            Ok(()) => ok_response(),
+            Err(e) => failed_to_update_ip_blocklist_response(e),
+        },
+    }
+}
+
+/// It handles the request to reload the IP blocklist from the database and
+/// the blocklist file.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `500` with serialized error in debug format if the IP blocklist
+///   couldn't be reloaded.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::ip_blocklist#reload-the-ip-blocklist)
+/// for more information about this endpoint.
+#[allow(clippy::unused_async)]
+pub async fn reload_ip_blocklist_handler(State(tracker): State<Arc<Tracker>>) -> Response {
+    if let Err(e) = tracker.load_blocked_networks_from_database() {
+        return failed_to_reload_ip_blocklist_response(e);
+    }
+
+    match tracker.load_ip_blocklist_from_file() {
+        Ok(()) => ok_response(),
+        Err(e) => failed_to_reload_ip_blocklist_response(e),
+    }
+}
@@ -0,0 +1,135 @@
+//! IP blocklist API context.
+//!
+//! This API context is responsible for handling all the requests related to
+//! the IP blocklist.
+//!
+//! The tracker refuses the `connect`, `announce` and `scrape` requests coming
+//! from the networks in the IP blocklist. The blocked networks come from two
+//! sources:
+//!
+//! - The blocklist file configured in the `core.ip_blocklist` section. It
+//!   supports the P2P blocklist format (`name:start-end`), CIDR networks and
+//!   single IP addresses.
+//! - The networks blocked with this API. They are persisted in the database.
+//!
+//! Refer to the [`ip_blocklist`](crate::core::ip_blocklist) module for more
+//! information.
+//!
+//! > **NOTICE**: the networks in the blocklist file can't be unblocked with the
+//! > API. Edit the file and reload the blocklist instead.
+//!
+//! # Endpoints
+//!
+//! - [Get the IP blocklist](#get-the-ip-blocklist)
+//! - [Block a network](#block-a-network)
+//! - [Unblock a network](#unblock-a-network)
+//! - [Reload the IP blocklist](#reload-the-ip-blocklist)
+//!
+//! # Get the IP blocklist
+//!
+//! `GET /ip-blocklist`
+//!
+//! It returns the networks blocked with the API and the number of networks
+//! loaded from the blocklist file.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/ip-blocklist?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "networks": [
+//!         "10.0.0.0/8",
+//!         "2001:db8::/32"
+//!     ],
+//!     "file_networks": 250321
+//! }
+//! ```
+//!
+//! **Resource**
+//!
+//! Refer to the API [`IpBlocklist`](crate::servers::apis::v1::context::ip_blocklist::resources::IpBlocklist)
+//! resource for more information about the response attributes.
+//!
+//! # Block a network
+//!
+//! `POST /ip-blocklist/networks/:ip/:prefix_len`
+//!
+//! It adds a network to the IP blocklist. The host bits of the network are
+//! ignored, so `10.1.2.3/8` blocks `10.0.0.0/8`.
+//!
+//! **Path parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `ip` | IPv4 or IPv6 address | The network address | Yes | `10.0.0.0`
+//! `prefix_len` | positive integer | The network prefix length. Use `32` (IPv4) or `128` (IPv6) to block a single IP | Yes | `8`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X POST "http://127.0.0.1:1212/api/v1/ip-blocklist/networks/10.0.0.0/8?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+//!
+//! # Unblock a network
+//!
+//! `DELETE /ip-blocklist/networks/:ip/:prefix_len`
+//!
+//! It removes a network blocked with the API from the IP blocklist.
+//!
+//! **Path parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `ip` | IPv4 or IPv6 address | The network address | Yes | `10.0.0.0`
+//! `prefix_len` | positive integer | The network prefix length | Yes | `8`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X DELETE "http://127.0.0.1:1212/api/v1/ip-blocklist/networks/10.0.0.0/8?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+//!
+//! # Reload the IP blocklist
+//!
+//! `GET /ip-blocklist/reload`
+//!
+//! It reloads the blocked networks from the database and the blocklist file.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/ip-blocklist/reload?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+pub mod handlers;
+pub mod resources;
+pub mod responses;
+pub mod routes;
@@ -0,0 +1,23 @@
+//! API resources for the [`ip_blocklist`](crate::servers::apis::v1::context::ip_blocklist)
+//! API context.
+use serde::{Deserialize, Serialize};
+
+use crate::core::Tracker;
+
+/// The networks in the IP blocklist.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct IpBlocklist {
+    /// The networks blocked with the API, in CIDR notation and sorted.
+    pub networks: Vec<String>,
+    /// The number of networks loaded from the blocklist file.
+    pub file_networks: usize,
+}
+
+impl From<&Tracker> for IpBlocklist {
+    fn from(tracker: &Tracker) -> Self {
+        Self {
+            networks: tracker.get_blocked_networks().iter().map(ToString::to_string).collect(),
+            file_networks: tracker.get_ip_blocklist_file_len(),
+        }
+    }
+}
@@ -0,0 +1,32 @@
+//! API responses for the [`ip_blocklist`](crate::servers::apis::v1::context::ip_blocklist)
+//! API context.
+use std::error::Error;
+
+use axum::response::{Json, Response};
+
+use super::resources::IpBlocklist;
+use crate::servers::apis::v1::responses::{bad_request_response, unhandled_rejection_response};
+
+/// `200` response that contains the [`IpBlocklist`] resource as json.
+pub fn ip_blocklist_response(ip_blocklist: IpBlocklist) -> Json<IpBlocklist> {
+    Json(ip_blocklist)
+}
+
+#[must_use]
+pub fn invalid_network_param_response(network: &str) -> Response {
+    bad_request_response(&format!(
+        "Invalid URL: invalid network param: string \"{network}\", expected an IP address and a prefix length"
+    ))
+}
+
+/// `500` error response when the IP blocklist cannot be updated.
+#[must_use]
+pub fn failed_to_update_ip_blocklist_response<E: Error>(e: E) -> Response {
+    unhandled_rejection_response(format!("failed to update IP blocklist: {e}"))
+}
+
+/// `500` error response when the IP blocklist cannot be reloaded.
+#[must_use]
+pub fn failed_to_reload_ip_blocklist_response<E: Error>(e: E) -> Response {
+    unhandled_rejection_response(format!("failed to reload IP blocklist: {e}"))
+}
@@ -0,0 +1,35 @@
+//! API routes for the [`ip_blocklist`](crate::servers::apis::v1::context::ip_blocklist) API context.
+//!
+//! - `GET /ip-blocklist`
+//! - `POST /ip-blocklist/networks/:ip/:prefix_len`
+//! - `DELETE /ip-blocklist/networks/:ip/:prefix_len`
+//! - `GET /ip-blocklist/reload`
+//!
+//! Refer to the [API endpoint documentation](crate::servers::apis::v1::context::ip_blocklist).
+use std::sync::Arc;
+
+use axum::routing::{get, post};
+use axum::Router;
+
+use super::handlers::{
+    add_blocked_network_handler, get_ip_blocklist_handler, reload_ip_blocklist_handler, remove_blocked_network_handler,
+};
+use crate::core::Tracker;
+
+/// It adds the routes to the router for the [`ip_blocklist`](crate::servers::apis::v1::context::ip_blocklist) API context.
+pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
+    let prefix = format!("{prefix}/ip-blocklist");
+
+    router
+        .route(&prefix, get(get_ip_blocklist_handler).with_state(tracker.clone()))
+        .route(
+            &format!("{prefix}/networks/:ip/:prefix_len"),
+            post(add_blocked_network_handler)
+                .delete(remove_blocked_network_handler)
+                .with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/reload"),
+            get(reload_ip_blocklist_handler).with_state(tracker),
+        )
+}
@@ -0,0 +1,48 @@
+//! API handlers for the [`maintenance`](crate::servers::apis::v1::context::maintenance)
+//! API context.
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::response::{IntoResponse, Response};
+
+use super::resources::Maintenance;
+use super::responses::maintenance_response;
+use crate::core::Tracker;
+use crate::servers::apis::v1::responses::ok_response;
+
+/// It handles the request to get the state of the maintenance mode.
+///
+/// It returns a `200` response with a json [`Maintenance`].
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::maintenance#get-the-maintenance-mode)
+/// for more information about this endpoint.
+#[allow(clippy::unused_async)]
+pub async fn get_maintenance_handler(State(tracker): State<Arc<Tracker>>) -> Response {
+    maintenance_response(Maintenance::from(tracker.as_ref())).into_response()
+}
+
+/// It handles the request to enable the maintenance mode.
+///
+/// It returns a `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::maintenance#enable-the-maintenance-mode)
+/// for more information about this endpoint.
+#[allow(clippy::unused_async)]
+pub async fn enable_maintenance_handler(State(tracker): State<Arc<Tracker>>) -> Response {
+    tracker.set_maintenance(true);
+
+    ok_response()
+}
+
+/// It handles the request to disable the maintenance mode.
+///
+/// It returns a `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::maintenance#disable-the-maintenance-mode)
+/// for more information about this endpoint.
+#[allow(clippy::unused_async)]
+pub async fn disable_maintenance_handler(State(tracker): State<Arc<Tracker>>) -> Response {
+    tracker.set_maintenance(false);
+
+    ok_response()
+}
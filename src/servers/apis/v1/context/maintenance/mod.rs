@@ -0,0 +1,92 @@
+//! Maintenance API context.
+//!
+//! This API context is responsible for handling all the requests related to
+//! the maintenance mode.
+//!
+//! While the tracker is in maintenance mode, the HTTP trackers return a
+//! failure response with the [BEP 31](https://www.bittorrent.org/beps/bep_0031.html)
+//! `retry in` key for the `announce` and `scrape` requests, and the UDP
+//! trackers return an error response. The UDP trackers still answer the
+//! `connect` requests, so the API and the health check keep working.
+//!
+//! The tracker starts in maintenance mode when it's enabled in the
+//! configuration:
+//!
+//! ```toml
+//! [core.maintenance]
+//! enabled = true
+//! retry_in = 10
+//! ```
+//!
+//! # Endpoints
+//!
+//! - [Get the maintenance mode](#get-the-maintenance-mode)
+//! - [Enable the maintenance mode](#enable-the-maintenance-mode)
+//! - [Disable the maintenance mode](#disable-the-maintenance-mode)
+//!
+//! # Get the maintenance mode
+//!
+//! `GET /maintenance`
+//!
+//! It returns whether the tracker is in maintenance mode, and the minutes the
+//! clients are told to wait before retrying.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/maintenance?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "enabled": false,
+//!     "retry_in": 10
+//! }
+//! ```
+//!
+//! **Resource**
+//!
+//! Refer to the API [`Maintenance`](crate::servers::apis::v1::context::maintenance::resources::Maintenance)
+//! resource for more information about the response attributes.
+//!
+//! # Enable the maintenance mode
+//!
+//! `POST /maintenance`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X POST "http://127.0.0.1:1212/api/v1/maintenance?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+//!
+//! # Disable the maintenance mode
+//!
+//! `DELETE /maintenance`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X DELETE "http://127.0.0.1:1212/api/v1/maintenance?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+pub mod handlers;
+pub mod resources;
+pub mod responses;
+pub mod routes;
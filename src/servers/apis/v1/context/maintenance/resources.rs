@@ -0,0 +1,23 @@
+//! API resources for the [`maintenance`](crate::servers::apis::v1::context::maintenance)
+//! API context.
+use serde::{Deserialize, Serialize};
+
+use crate::core::Tracker;
+
+/// The state of the maintenance mode.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct Maintenance {
+    /// Whether the tracker is in maintenance mode.
+    pub enabled: bool,
+    /// Number of minutes the clients are told to wait before retrying.
+    pub retry_in: u32,
+}
+
+impl From<&Tracker> for Maintenance {
+    fn from(tracker: &Tracker) -> Self {
+        Self {
+            enabled: tracker.is_in_maintenance(),
+            retry_in: tracker.get_maintenance_retry_in(),
+        }
+    }
+}
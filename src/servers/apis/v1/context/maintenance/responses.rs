@@ -0,0 +1,10 @@
+//! API responses for the [`maintenance`](crate::servers::apis::v1::context::maintenance)
+//! API context.
+use axum::response::Json;
+
+use super::resources::Maintenance;
+
+/// `200` response that contains the [`Maintenance`] resource as json.
+pub fn maintenance_response(maintenance: Maintenance) -> Json<Maintenance> {
+    Json(maintenance)
+}
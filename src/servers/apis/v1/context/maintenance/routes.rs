@@ -0,0 +1,25 @@
+//! API routes for the [`maintenance`](crate::servers::apis::v1::context::maintenance) API context.
+//!
+//! - `GET /maintenance`
+//! - `POST /maintenance`
+//! - `DELETE /maintenance`
+//!
+//! Refer to the [API endpoint documentation](crate::servers::apis::v1::context::maintenance).
+use std::sync::Arc;
+
+use axum::routing::get;
+use axum::Router;
+
+use super::handlers::{disable_maintenance_handler, enable_maintenance_handler, get_maintenance_handler};
+use crate::core::Tracker;
+
+/// It adds the routes to the router for the [`maintenance`](crate::servers::apis::v1::context::maintenance) API context.
+pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
+    router.route(
+        &format!("{prefix}/maintenance"),
+        get(get_maintenance_handler)
+            .post(enable_maintenance_handler)
+            .delete(disable_maintenance_handler)
+            .with_state(tracker),
+    )
+}
@@ -3,7 +3,12 @@
 //! Each context is a module that contains the API endpoints related to a
 //! specific resource group.
 pub mod auth_key;
+pub mod blacklist;
+pub mod client_filter;
 pub mod health_check;
+pub mod ip_blocklist;
+pub mod maintenance;
+pub mod ratio;
 pub mod stats;
 pub mod torrent;
 pub mod whitelist;
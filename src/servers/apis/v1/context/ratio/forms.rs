@@ -0,0 +1,31 @@
+//! API forms for the [`ratio`](crate::servers::apis::v1::context::ratio)
+//! API context.
+use serde::{Deserialize, Serialize};
+
+use crate::core::ratio::Multipliers;
+
+/// This type contains the multipliers of a torrent. The omitted multipliers
+/// are `1.0`, so the transfers count as they are.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MultipliersForm {
+    /// The multiplier for the uploaded bytes.
+    #[serde(default = "default_multiplier")]
+    pub upload: f64,
+
+    /// The multiplier for the downloaded bytes. Use `0.0` for freeleech.
+    #[serde(default = "default_multiplier")]
+    pub download: f64,
+}
+
+fn default_multiplier() -> f64 {
+    1.0
+}
+
+impl From<MultipliersForm> for Multipliers {
+    fn from(form: MultipliersForm) -> Self {
+        Self {
+            upload: form.upload,
+            download: form.download,
+        }
+    }
+}
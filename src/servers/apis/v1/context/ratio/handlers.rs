@@ -0,0 +1,134 @@
+//! API handlers for the [`ratio`](crate::servers::apis::v1::context::ratio)
+//! API context.
+use std::str::FromStr;
+use std::sync::Arc;
+
+use axum::extract::{self, Path, State};
+use axum::response::{IntoResponse, Response};
+use torrust_tracker_primitives::info_hash_v2::parse_hex_info_hash;
+
+use super::forms::MultipliersForm;
+use super::resources::{KeyStats, TorrentMultipliers};
+use super::responses::{
+    failed_to_remove_torrent_multipliers_response, failed_to_set_torrent_multipliers_response, invalid_multipliers_response,
+    key_stats_list_response, key_stats_response, torrent_multipliers_response,
+};
+use crate::core::auth::Key;
+use crate::core::ratio::Multipliers;
+use crate::core::Tracker;
+use crate::servers::apis::v1::responses::{invalid_auth_key_param_response, invalid_info_hash_param_response, ok_response};
+use crate::servers::apis::InfoHashParam;
+
+/// It handles the request to get the transfer stats of all the keys.
+///
+/// It returns a `200` response with a json array of [`KeyStats`] resources.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::ratio#get-the-stats-of-all-the-keys)
+/// for more information about this endpoint.
+#[allow(clippy::unused_async)]
+pub async fn get_all_key_stats_handler(State(tracker): State<Arc<Tracker>>) -> Response {
+    let mut key_stats: Vec<KeyStats> = tracker.get_all_key_stats().into_iter().map(KeyStats::from).collect();
+
+    key_stats.sort_by(|a, b| a.key.cmp(&b.key));
+
+    key_stats_list_response(key_stats).into_response()
+}
+
+/// It handles the request to get the transfer stats of a key.
+///
+/// It returns:
+///
+/// - `200` response with a json [`KeyStats`].
+/// - `400` response if the key is not valid.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::ratio#get-the-stats-of-a-key)
+/// for more information about this endpoint.
+#[allow(clippy::unused_async)]
+pub async fn get_key_stats_handler(State(tracker): State<Arc<Tracker>>, Path(key): Path<String>) -> Response {
+    match Key::from_str(&key) {
+        Err(_) => invalid_auth_key_param_response(&key),
+        Ok(key) => {
+            let stats = tracker.get_key_stats(&key);
+            key_stats_response(KeyStats::from((key, stats))).into_response()
+        }
+    }
+}
+
+/// It handles the request to get the multipliers of a torrent.
+///
+/// It returns:
+///
+/// - `200` response with a json [`TorrentMultipliers`].
+/// - `400` response if the infohash is not valid.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::ratio#get-the-multipliers-of-a-torrent)
+/// for more information about this endpoint.
+#[allow(clippy::unused_async)]
+pub async fn get_torrent_multipliers_handler(
+    State(tracker): State<Arc<Tracker>>,
+    Path(info_hash): Path<InfoHashParam>,
+) -> Response {
+    match parse_hex_info_hash(&info_hash.0) {
+        Err(_) => invalid_info_hash_param_response(&info_hash.0),
+        Ok(info_hash) => {
+            let multipliers = tracker.get_torrent_multipliers(&info_hash);
+            torrent_multipliers_response(TorrentMultipliers::from((info_hash, multipliers))).into_response()
+        }
+    }
+}
+
+/// It handles the request to set the multipliers of a torrent.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `400` response if the infohash or the multipliers are not valid.
+/// - `500` with serialized error in debug format if the multipliers couldn't be saved.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::ratio#set-the-multipliers-of-a-torrent)
+/// for more information about this endpoint.
+#[allow(clippy::unused_async)]
+pub async fn set_torrent_multipliers_handler(
+    State(tracker): State<Arc<Tracker>>,
+    Path(info_hash): Path<InfoHashParam>,
+    extract::Json(multipliers_form): extract::Json<MultipliersForm>,
+) -> Response {
+    let Ok(info_hash) = parse_hex_info_hash(&info_hash.0) else {
+        return invalid_info_hash_param_response(&info_hash.0);
+    };
+
+    let multipliers = Multipliers::from(multipliers_form);
+
+    if !multipliers.is_valid() {
+        return invalid_multipliers_response();
+    }
+
+    match tracker.set_torrent_multipliers(&info_hash, &multipliers) {
+        Ok(()) => ok_response(),
+        Err(e) => failed_to_set_torrent_multipliers_response(e),
+    }
+}
+
+/// It handles the request to remove the multipliers of a torrent.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `400` response if the infohash is not valid.
+/// - `500` with serialized error in debug format if the multipliers couldn't be removed.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::ratio#remove-the-multipliers-of-a-torrent)
+/// for more information about this endpoint.
+#[allow(clippy::unused_async)]
+pub async fn remove_torrent_multipliers_handler(
+    State(tracker): State<Arc<Tracker>>,
+    Path(info_hash): Path<InfoHashParam>,
+) -> Response {
+    match parse_hex_info_hash(&info_hash.0) {
+        Err(_) => invalid_info_hash_param_response(&info_hash.0),
+        Ok(info_hash) => match tracker.remove_torrent_multipliers(&info_hash) {
+            Ok(()) => ok_response(),
+            Err(e) => failed_to_remove_torrent_multipliers_response(e),
+        },
+    }
+}
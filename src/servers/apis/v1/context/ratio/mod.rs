@@ -0,0 +1,179 @@
+//! Ratio API context.
+//!
+//! This API context is responsible for handling all the requests related to
+//! the transfer accounting per authentication key.
+//!
+//! In private mode, the tracker adds up the bytes uploaded and downloaded by
+//! the peers of each authentication key, and the time they have been seeding.
+//! Torrents can have multipliers to change how their transfers count, for
+//! example to make them freeleech or double upload.
+//!
+//! Refer to the [`ratio`](crate::core::ratio) module for more information
+//! about how the transfers are calculated.
+//!
+//! # Endpoints
+//!
+//! - [Get the stats of all the keys](#get-the-stats-of-all-the-keys)
+//! - [Get the stats of a key](#get-the-stats-of-a-key)
+//! - [Get the multipliers of a torrent](#get-the-multipliers-of-a-torrent)
+//! - [Set the multipliers of a torrent](#set-the-multipliers-of-a-torrent)
+//! - [Remove the multipliers of a torrent](#remove-the-multipliers-of-a-torrent)
+//!
+//! # Get the stats of all the keys
+//!
+//! `GET /ratio/keys`
+//!
+//! It returns the stats of all the keys that have announced something, sorted
+//! by key.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/ratio/keys?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! [
+//!     {
+//!         "key": "xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6",
+//!         "uploaded": 1073741824,
+//!         "downloaded": 536870912,
+//!         "ratio": 2.0,
+//!         "seeding_time": 86400
+//!     }
+//! ]
+//! ```
+//!
+//! **Resource**
+//!
+//! Refer to the API [`KeyStats`](crate::servers::apis::v1::context::ratio::resources::KeyStats)
+//! resource for more information about the response attributes.
+//!
+//! # Get the stats of a key
+//!
+//! `GET /ratio/keys/:key`
+//!
+//! It returns the stats of a key. They are zero if the key has not announced
+//! anything yet.
+//!
+//! **Path parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `key` | 32-char string | The authentication key | Yes | `xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/ratio/keys/xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "key": "xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6",
+//!     "uploaded": 0,
+//!     "downloaded": 0,
+//!     "ratio": null,
+//!     "seeding_time": 0
+//! }
+//! ```
+//!
+//! The `ratio` is `null` when the key has not downloaded anything.
+//!
+//! # Get the multipliers of a torrent
+//!
+//! `GET /ratio/torrents/:info_hash`
+//!
+//! It returns the multipliers of a torrent. They are `1.0` if the torrent does
+//! not have special multipliers.
+//!
+//! **Path parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `info_hash` | 40-char or 64-char string | The Info Hash v1 or v2 | Yes | `5452869be36f9f3350ccee6b4544e7e76caaadab`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/ratio/torrents/5452869be36f9f3350ccee6b4544e7e76caaadab?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "info_hash": "5452869be36f9f3350ccee6b4544e7e76caaadab",
+//!     "upload": 1.0,
+//!     "download": 0.0
+//! }
+//! ```
+//!
+//! **Resource**
+//!
+//! Refer to the API [`TorrentMultipliers`](crate::servers::apis::v1::context::ratio::resources::TorrentMultipliers)
+//! resource for more information about the response attributes.
+//!
+//! # Set the multipliers of a torrent
+//!
+//! `POST /ratio/torrents/:info_hash`
+//!
+//! It sets the multipliers of a torrent. The omitted multipliers are `1.0`.
+//! Multipliers must not be negative.
+//!
+//! **Post parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `upload` | float | The multiplier for the uploaded bytes | No | `2.0`
+//! `download` | float | The multiplier for the downloaded bytes | No | `0.0`
+//!
+//! **Example request**
+//!
+//! A freeleech torrent:
+//!
+//! ```bash
+//! curl -X POST http://localhost:1212/api/v1/ratio/torrents/5452869be36f9f3350ccee6b4544e7e76caaadab?token=MyAccessToken \
+//!      -H "Content-Type: application/json" \
+//!      -d '{
+//!            "download": 0.0
+//!          }'
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+//!
+//! # Remove the multipliers of a torrent
+//!
+//! `DELETE /ratio/torrents/:info_hash`
+//!
+//! It removes the multipliers of a torrent, so that its transfers count as
+//! they are.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X DELETE "http://127.0.0.1:1212/api/v1/ratio/torrents/5452869be36f9f3350ccee6b4544e7e76caaadab?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+pub mod forms;
+pub mod handlers;
+pub mod resources;
+pub mod responses;
+pub mod routes;
@@ -0,0 +1,86 @@
+//! API resources for the [`ratio`](crate::servers::apis::v1::context::ratio)
+//! API context.
+use bittorrent_primitives::info_hash::InfoHash;
+use serde::{Deserialize, Serialize};
+
+use crate::core::auth::Key;
+use crate::core::ratio::{self, Multipliers};
+
+/// The transfer stats of an authentication key.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct KeyStats {
+    /// The authentication key.
+    pub key: String,
+    /// Total uploaded bytes, after applying the torrent multipliers.
+    pub uploaded: u64,
+    /// Total downloaded bytes, after applying the torrent multipliers.
+    pub downloaded: u64,
+    /// The upload/download ratio. `None` if nothing has been downloaded.
+    pub ratio: Option<f64>,
+    /// Total seeding time in seconds.
+    pub seeding_time: u64,
+}
+
+impl From<(Key, ratio::KeyStats)> for KeyStats {
+    fn from((key, stats): (Key, ratio::KeyStats)) -> Self {
+        Self {
+            key: key.to_string(),
+            uploaded: stats.uploaded,
+            downloaded: stats.downloaded,
+            ratio: stats.ratio(),
+            seeding_time: stats.seeding_time.as_secs(),
+        }
+    }
+}
+
+/// The multipliers of a torrent.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct TorrentMultipliers {
+    /// The torrent infohash.
+    pub info_hash: String,
+    /// The multiplier for the uploaded bytes.
+    pub upload: f64,
+    /// The multiplier for the downloaded bytes.
+    pub download: f64,
+}
+
+impl From<(InfoHash, Multipliers)> for TorrentMultipliers {
+    fn from((info_hash, multipliers): (InfoHash, Multipliers)) -> Self {
+        Self {
+            info_hash: info_hash.to_string(),
+            upload: multipliers.upload,
+            download: multipliers.download,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::KeyStats;
+    use crate::core::auth::Key;
+    use crate::core::ratio;
+
+    #[test]
+    fn it_should_be_converted_from_the_domain_key_stats() {
+        let key = "xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6".parse::<Key>().unwrap();
+
+        let stats = ratio::KeyStats {
+            uploaded: 200,
+            downloaded: 100,
+            seeding_time: Duration::from_secs(60),
+        };
+
+        assert_eq!(
+            KeyStats::from((key, stats)),
+            KeyStats {
+                key: "xqD6NWH9TcKrOCwDmqcdH5hF5RrbL0A6".to_string(),
+                uploaded: 200,
+                downloaded: 100,
+                ratio: Some(2.0),
+                seeding_time: 60,
+            }
+        );
+    }
+}
@@ -0,0 +1,41 @@
+//! API responses for the [`ratio`](crate::servers::apis::v1::context::ratio)
+//! API context.
+use std::error::Error;
+
+use axum::response::{Json, Response};
+
+use super::resources::{KeyStats, TorrentMultipliers};
+use crate::servers::apis::v1::responses::{bad_request_response, unhandled_rejection_response};
+
+/// `200` response that contains an array of [`KeyStats`] resources as json.
+pub fn key_stats_list_response(key_stats: Vec<KeyStats>) -> Json<Vec<KeyStats>> {
+    Json(key_stats)
+}
+
+/// `200` response that contains the [`KeyStats`] resource as json.
+pub fn key_stats_response(key_stats: KeyStats) -> Json<KeyStats> {
+    Json(key_stats)
+}
+
+/// `200` response that contains the [`TorrentMultipliers`] resource as json.
+pub fn torrent_multipliers_response(multipliers: TorrentMultipliers) -> Json<TorrentMultipliers> {
+    Json(multipliers)
+}
+
+/// `400` error response when the multipliers are negative or not finite.
+#[must_use]
+pub fn invalid_multipliers_response() -> Response {
+    bad_request_response("Invalid multipliers: they must be finite and not negative")
+}
+
+/// `500` error response when the multipliers of a torrent cannot be saved.
+#[must_use]
+pub fn failed_to_set_torrent_multipliers_response<E: Error>(e: E) -> Response {
+    unhandled_rejection_response(format!("failed to set torrent multipliers: {e}"))
+}
+
+/// `500` error response when the multipliers of a torrent cannot be removed.
+#[must_use]
+pub fn failed_to_remove_torrent_multipliers_response<E: Error>(e: E) -> Response {
+    unhandled_rejection_response(format!("failed to remove torrent multipliers: {e}"))
+}
@@ -0,0 +1,43 @@
+//! API routes for the [`ratio`](crate::servers::apis::v1::context::ratio) API context.
+//!
+//! - `GET /ratio/keys`
+//! - `GET /ratio/keys/:key`
+//! - `GET /ratio/torrents/:info_hash`
+//! - `POST /ratio/torrents/:info_hash`
+//! - `DELETE /ratio/torrents/:info_hash`
+//!
+//! Refer to the [API endpoint documentation](crate::servers::apis::v1::context::ratio).
+use std::sync::Arc;
+
+use axum::routing::get;
+use axum::Router;
+
+use super::handlers::{
+    get_all_key_stats_handler, get_key_stats_handler, get_torrent_multipliers_handler, remove_torrent_multipliers_handler,
+    set_torrent_multipliers_handler,
+};
+use crate::core::Tracker;
+
+/// It adds the routes to the router for the [`ratio`](crate::servers::apis::v1::context::ratio) API context.
+pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
+    let prefix = format!("{prefix}/ratio");
+
+    router
+        // Key stats
+        .route(
+            &format!("{prefix}/keys"),
+            get(get_all_key_stats_handler).with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/keys/:key"),
+            get(get_key_stats_handler).with_state(tracker.clone()),
+        )
+        // Torrent multipliers
+        .route(
+            &format!("{prefix}/torrents/:info_hash"),
+            get(get_torrent_multipliers_handler)
+                .post(set_torrent_multipliers_handler)
+                .delete(remove_torrent_multipliers_handler)
+                .with_state(tracker),
+        )
+}
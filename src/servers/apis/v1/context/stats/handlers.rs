@@ -2,20 +2,48 @@
 //! API context.
 use std::sync::Arc;
 
-use axum::extract::State;
-use axum::response::Json;
+use axum::extract::{Query, State};
+use axum::response::{IntoResponse, Response};
+use serde::Deserialize;
 
-use super::resources::Stats;
-use super::responses::stats_response;
+use super::responses::{metrics_response, stats_response};
 use crate::core::services::statistics::get_metrics;
 use crate::core::Tracker;
 
+/// The format of the statistics.
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    /// A json [`Stats`](super::resources::Stats) resource.
+    #[default]
+    Json,
+    /// The Prometheus text exposition format.
+    Prometheus,
+}
+
+/// Query parameters for the statistics endpoint.
+///
+/// For example: `GET /stats?format=prometheus`.
+#[derive(Deserialize, Debug)]
+pub struct QueryParams {
+    /// The format of the response. It's `json` by default.
+    #[serde(default)]
+    pub format: Format,
+}
+
 /// It handles the request to get the tracker statistics.
 ///
-/// It returns a `200` response with a json [`Stats`]
+/// It returns a `200` response with a json [`Stats`](super::resources::Stats)
+/// or the statistics in the Prometheus text format, depending on the `format`
+/// query param.
 ///
 /// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::stats#get-tracker-statistics)
 /// for more information about this endpoint.
-pub async fn get_stats_handler(State(tracker): State<Arc<Tracker>>) -> Json<Stats> {
-    stats_response(get_metrics(tracker.clone()).await)
+pub async fn get_stats_handler(State(tracker): State<Arc<Tracker>>, params: Query<QueryParams>) -> Response {
+    let tracker_metrics = get_metrics(tracker.clone()).await;
+
+    match params.0.format {
+        Format::Json => stats_response(tracker_metrics).into_response(),
+        Format::Prometheus => metrics_response(&tracker_metrics),
+    }
 }
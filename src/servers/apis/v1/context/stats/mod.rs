@@ -3,9 +3,16 @@
 //! The tracker collects statistics about the number of torrents, seeders,
 //! leechers, completed downloads, and the number of requests handled.
 //!
+//! The number of requests handled is also broken down by listener, that is,
+//! by each HTTP and UDP tracker running on a different socket address.
+//!
+//! It also keeps a histogram of the time taken to handle the requests for each
+//! protocol and request type: `connect`, `announce`, `scrape` and `error`.
+//!
 //! # Endpoints
 //!
 //! - [Get tracker statistics](#get-tracker-statistics)
+//! - [Get tracker statistics in Prometheus format](#prometheus-format)
 //!
 //! # Get tracker statistics
 //!
@@ -30,15 +37,64 @@
 //!     "tcp4_connections_handled": 0,
 //!     "tcp4_announces_handled": 0,
 //!     "tcp4_scrapes_handled": 0,
+//!     "tcp4_announces_too_frequent": 0,
 //!     "tcp6_connections_handled": 0,
 //!     "tcp6_announces_handled": 0,
 //!     "tcp6_scrapes_handled": 0,
+//!     "tcp6_announces_too_frequent": 0,
 //!     "udp4_connections_handled": 0,
 //!     "udp4_announces_handled": 0,
 //!     "udp4_scrapes_handled": 0,
+//!     "udp4_errors_handled": 0,
+//!     "udp4_requests_rate_limited": 0,
+//!     "udp4_announces_too_frequent": 0,
 //!     "udp6_connections_handled": 0,
 //!     "udp6_announces_handled": 0,
-//!     "udp6_scrapes_handled": 0
+//!     "udp6_scrapes_handled": 0,
+//!     "udp6_errors_handled": 0,
+//!     "udp6_requests_rate_limited": 0,
+//!     "udp6_announces_too_frequent": 0,
+//!     "listeners": [
+//!       {
+//!         "protocol": "udp",
+//!         "socket_addr": "0.0.0.0:6969",
+//!         "ipv4": {
+//!           "connections_handled": 1,
+//!           "announces_handled": 1,
+//!           "scrapes_handled": 0,
+//!           "errors_handled": 0,
+//!           "requests_rate_limited": 0,
+//!           "announces_too_frequent": 0
+//!         },
+//!         "ipv6": {
+//!           "connections_handled": 0,
+//!           "announces_handled": 0,
+//!           "scrapes_handled": 0,
+//!           "errors_handled": 0,
+//!           "requests_rate_limited": 0,
+//!           "announces_too_frequent": 0
+//!         }
+//!       }
+//!     ],
+//!     "latencies": [
+//!       {
+//!         "protocol": "udp",
+//!         "request": "announce",
+//!         "count": 1,
+//!         "sum_microseconds": 180,
+//!         "buckets": [
+//!           {
+//!             "le_microseconds": 100,
+//!             "count": 0
+//!           },
+//!           {
+//!             "le_microseconds": 250,
+//!             "count": 1
+//!           },
+//!           ...
+//!         ]
+//!       }
+//!     ]
 //!   }
 //! ```
 //!
@@ -46,6 +102,54 @@
 //!
 //! Refer to the API [`Stats`](crate::servers::apis::v1::context::stats::resources::Stats)
 //! resource for more information about the response attributes.
+//!
+//! ## Prometheus format
+//!
+//! `GET /stats?format=prometheus`
+//!
+//! Returns the same statistics in the [Prometheus text exposition format](https://prometheus.io/docs/instrumenting/exposition_formats/),
+//! so they can be scraped directly by Prometheus. Request counters have a
+//! `protocol` label (`http` or `udp`) and an `ip_version` label (`ipv4` or
+//! `ipv6`).
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/stats?token=MyAccessToken&format=prometheus"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```text
+//! # HELP torrust_tracker_torrents Total number of torrents.
+//! # TYPE torrust_tracker_torrents gauge
+//! torrust_tracker_torrents 0
+//! ...
+//! # HELP torrust_tracker_announces_handled_total Total number of `announce` requests handled.
+//! # TYPE torrust_tracker_announces_handled_total counter
+//! torrust_tracker_announces_handled_total{protocol="http",ip_version="ipv4"} 0
+//! torrust_tracker_announces_handled_total{protocol="http",ip_version="ipv6"} 0
+//! torrust_tracker_announces_handled_total{protocol="udp",ip_version="ipv4"} 0
+//! torrust_tracker_announces_handled_total{protocol="udp",ip_version="ipv6"} 0
+//! ...
+//! ```
+//!
+//! Exported metrics:
+//!
+//! Metric                                            | Type      | Labels
+//! ---|---|---
+//! `torrust_tracker_torrents`                        | gauge     |
+//! `torrust_tracker_seeders`                         | gauge     |
+//! `torrust_tracker_leechers`                        | gauge     |
+//! `torrust_tracker_completed_total`                 | counter   |
+//! `torrust_tracker_connections_handled_total`       | counter   | `protocol`, `ip_version`
+//! `torrust_tracker_announces_handled_total`         | counter   | `protocol`, `ip_version`
+//! `torrust_tracker_scrapes_handled_total`           | counter   | `protocol`, `ip_version`
+//! `torrust_tracker_errors_handled_total`            | counter   | `protocol`, `ip_version`
+//! `torrust_tracker_requests_rate_limited_total`     | counter   | `protocol`, `ip_version`
+//! `torrust_tracker_announces_too_frequent_total`    | counter   | `protocol`, `ip_version`
+//! `torrust_tracker_listener_requests_handled_total` | counter   | `protocol`, `server_socket_addr`, `ip_version`, `request`
+//! `torrust_tracker_request_duration_seconds`        | histogram | `protocol`, `request`
 pub mod handlers;
 pub mod resources;
 pub mod responses;
@@ -1,8 +1,11 @@
 //! API resources for the [`stats`](crate::servers::apis::v1::context::stats)
 //! API context.
+use std::net::SocketAddr;
+
 use serde::{Deserialize, Serialize};
 
 use crate::core::services::statistics::TrackerMetrics;
+use crate::core::statistics::{LatencyHistogram, Listener, ListenerMetrics, Protocol, RequestKind, RequestMetrics};
 
 /// It contains all the statistics generated by the tracker.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
@@ -26,12 +29,18 @@ pub struct Stats {
     pub tcp4_announces_handled: u64,
     /// Total number of TCP (HTTP tracker) `scrape` requests from IPv4 peers.
     pub tcp4_scrapes_handled: u64,
+    /// Total number of TCP (HTTP tracker) `announce` requests from IPv4 peers
+    /// received before the `min interval`.
+    pub tcp4_announces_too_frequent: u64,
     /// Total number of TCP (HTTP tracker) connections from IPv6 peers.
     pub tcp6_connections_handled: u64,
     /// Total number of TCP (HTTP tracker) `announce` requests from IPv6 peers.
     pub tcp6_announces_handled: u64,
     /// Total number of TCP (HTTP tracker) `scrape` requests from IPv6 peers.
     pub tcp6_scrapes_handled: u64,
+    /// Total number of TCP (HTTP tracker) `announce` requests from IPv6 peers
+    /// received before the `min interval`.
+    pub tcp6_announces_too_frequent: u64,
     /// Total number of UDP (UDP tracker) connections from IPv4 peers.
     pub udp4_connections_handled: u64,
     /// Total number of UDP (UDP tracker) `announce` requests from IPv4 peers.
@@ -40,6 +49,12 @@ pub struct Stats {
     pub udp4_scrapes_handled: u64,
     /// Total number of UDP (UDP tracker) `scrape` requests from IPv4 peers.
     pub udp4_errors_handled: u64,
+    /// Total number of UDP (UDP tracker) requests from IPv4 peers rejected by
+    /// the rate limiter.
+    pub udp4_requests_rate_limited: u64,
+    /// Total number of UDP (UDP tracker) `announce` requests from IPv4 peers
+    /// received before the `min interval`.
+    pub udp4_announces_too_frequent: u64,
     /// Total number of UDP (UDP tracker) `connection` requests from IPv6 peers.
     pub udp6_connections_handled: u64,
     /// Total number of UDP (UDP tracker) `announce` requests from IPv6 peers.
@@ -48,6 +63,120 @@ pub struct Stats {
     pub udp6_scrapes_handled: u64,
     /// Total number of UDP (UDP tracker) `scrape` requests from IPv6 peers.
     pub udp6_errors_handled: u64,
+    /// Total number of UDP (UDP tracker) requests from IPv6 peers rejected by
+    /// the rate limiter.
+    pub udp6_requests_rate_limited: u64,
+    /// Total number of UDP (UDP tracker) `announce` requests from IPv6 peers
+    /// received before the `min interval`.
+    pub udp6_announces_too_frequent: u64,
+
+    // Listener metrics
+    /// The protocol metrics broken down by the server socket address the
+    /// HTTP and UDP trackers are bound to.
+    pub listeners: Vec<ListenerStats>,
+
+    // Latency metrics
+    /// The histograms of the time taken to handle the requests, for each
+    /// protocol and request type.
+    pub latencies: Vec<LatencyStats>,
+}
+
+/// The requests handled by one of the HTTP or UDP trackers.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct ListenerStats {
+    /// The tracker protocol: `http` or `udp`.
+    pub protocol: String,
+    /// The socket address the tracker is bound to.
+    pub socket_addr: SocketAddr,
+    /// Requests from IPv4 peers.
+    pub ipv4: RequestStats,
+    /// Requests from IPv6 peers.
+    pub ipv6: RequestStats,
+}
+
+/// The number of requests handled by a tracker for one IP version.
+///
+/// Since the HTTP tracker spec does not require a handshake, the connections
+/// increase for every HTTP request and the HTTP tracker does not count errors.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Default)]
+pub struct RequestStats {
+    /// Total number of connections.
+    pub connections_handled: u64,
+    /// Total number of `announce` requests.
+    pub announces_handled: u64,
+    /// Total number of `scrape` requests.
+    pub scrapes_handled: u64,
+    /// Total number of error responses.
+    pub errors_handled: u64,
+    /// Total number of requests rejected by the rate limiter.
+    pub requests_rate_limited: u64,
+    /// Total number of `announce` requests received before the `min interval`.
+    pub announces_too_frequent: u64,
+}
+
+/// The histogram of the time taken by the HTTP or UDP trackers to handle one
+/// type of request.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct LatencyStats {
+    /// The tracker protocol: `http` or `udp`.
+    pub protocol: String,
+    /// The request type: `connect`, `announce`, `scrape` or `error`.
+    pub request: String,
+    /// Total number of requests.
+    pub count: u64,
+    /// Sum of the latencies of all the requests in microseconds.
+    pub sum_microseconds: u64,
+    /// The histogram buckets.
+    pub buckets: Vec<LatencyBucketStats>,
+}
+
+/// A bucket of a [`LatencyStats`] histogram.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct LatencyBucketStats {
+    /// The upper bound of the bucket in microseconds.
+    pub le_microseconds: u64,
+    /// Number of requests handled in less than or equal to `le_microseconds`.
+    pub count: u64,
+}
+
+impl From<(&(Protocol, RequestKind), &LatencyHistogram)> for LatencyStats {
+    fn from(((protocol, kind), histogram): (&(Protocol, RequestKind), &LatencyHistogram)) -> Self {
+        Self {
+            protocol: protocol.to_string(),
+            request: kind.to_string(),
+            count: histogram.count,
+            sum_microseconds: histogram.sum,
+            buckets: histogram
+                .cumulative_buckets()
+                .into_iter()
+                .map(|(le_microseconds, count)| LatencyBucketStats { le_microseconds, count })
+                .collect(),
+        }
+    }
+}
+
+impl From<(&Listener, &ListenerMetrics)> for ListenerStats {
+    fn from((listener, metrics): (&Listener, &ListenerMetrics)) -> Self {
+        Self {
+            protocol: listener.protocol.to_string(),
+            socket_addr: listener.socket_addr,
+            ipv4: metrics.ipv4.into(),
+            ipv6: metrics.ipv6.into(),
+        }
+    }
+}
+
+impl From<RequestMetrics> for RequestStats {
+    fn from(metrics: RequestMetrics) -> Self {
+        Self {
+            connections_handled: metrics.connections_handled,
+            announces_handled: metrics.announces_handled,
+            scrapes_handled: metrics.scrapes_handled,
+            errors_handled: metrics.errors_handled,
+            requests_rate_limited: metrics.requests_rate_limited,
+            announces_too_frequent: metrics.announces_too_frequent,
+        }
+    }
 }
 
 impl From<TrackerMetrics> for Stats {
@@ -60,28 +189,68 @@ impl From<TrackerMetrics> for Stats {
             tcp4_connections_handled: metrics.protocol_metrics.tcp4_connections_handled,
             tcp4_announces_handled: metrics.protocol_metrics.tcp4_announces_handled,
             tcp4_scrapes_handled: metrics.protocol_metrics.tcp4_scrapes_handled,
+            tcp4_announces_too_frequent: metrics.protocol_metrics.tcp4_announces_too_frequent,
             tcp6_connections_handled: metrics.protocol_metrics.tcp6_connections_handled,
             tcp6_announces_handled: metrics.protocol_metrics.tcp6_announces_handled,
             tcp6_scrapes_handled: metrics.protocol_metrics.tcp6_scrapes_handled,
+            tcp6_announces_too_frequent: metrics.protocol_metrics.tcp6_announces_too_frequent,
             udp4_connections_handled: metrics.protocol_metrics.udp4_connections_handled,
             udp4_announces_handled: metrics.protocol_metrics.udp4_announces_handled,
             udp4_scrapes_handled: metrics.protocol_metrics.udp4_scrapes_handled,
             udp4_errors_handled: metrics.protocol_metrics.udp4_errors_handled,
+            udp4_requests_rate_limited: metrics.protocol_metrics.udp4_requests_rate_limited,
+            udp4_announces_too_frequent: metrics.protocol_metrics.udp4_announces_too_frequent,
             udp6_connections_handled: metrics.protocol_metrics.udp6_connections_handled,
             udp6_announces_handled: metrics.protocol_metrics.udp6_announces_handled,
             udp6_scrapes_handled: metrics.protocol_metrics.udp6_scrapes_handled,
             udp6_errors_handled: metrics.protocol_metrics.udp6_errors_handled,
+            udp6_requests_rate_limited: metrics.protocol_metrics.udp6_requests_rate_limited,
+            udp6_announces_too_frequent: metrics.protocol_metrics.udp6_announces_too_frequent,
+            listeners: metrics.listeners_metrics.iter().map(ListenerStats::from).collect(),
+            latencies: metrics.latency_metrics.iter().map(LatencyStats::from).collect(),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+    use std::net::SocketAddr;
+    use std::time::Duration;
+
     use torrust_tracker_primitives::torrent_metrics::TorrentsMetrics;
 
-    use super::Stats;
+    use super::{LatencyBucketStats, LatencyStats, ListenerStats, RequestStats, Stats};
     use crate::core::services::statistics::TrackerMetrics;
-    use crate::core::statistics::Metrics;
+    use crate::core::statistics::{
+        LatencyHistogram, Listener, ListenerMetrics, Metrics, Protocol, RequestKind, RequestMetrics, LATENCY_BUCKETS,
+    };
+
+    fn sample_latency_histogram() -> LatencyHistogram {
+        let mut histogram = LatencyHistogram::default();
+        histogram.observe(Duration::from_micros(200));
+        histogram
+    }
+
+    fn sample_latency_stats() -> LatencyStats {
+        LatencyStats {
+            protocol: "udp".to_string(),
+            request: "announce".to_string(),
+            count: 1,
+            sum_microseconds: 200,
+            buckets: LATENCY_BUCKETS
+                .iter()
+                .map(|le_microseconds| LatencyBucketStats {
+                    le_microseconds: *le_microseconds,
+                    count: u64::from(*le_microseconds >= 200),
+                })
+                .collect(),
+        }
+    }
+
+    fn sample_socket_addr() -> SocketAddr {
+        "0.0.0.0:6969".parse().unwrap()
+    }
 
     #[test]
     fn stats_resource_should_be_converted_from_tracker_metrics() {
@@ -97,18 +266,42 @@ mod tests {
                     tcp4_connections_handled: 5,
                     tcp4_announces_handled: 6,
                     tcp4_scrapes_handled: 7,
+                    tcp4_announces_too_frequent: 21,
                     tcp6_connections_handled: 8,
                     tcp6_announces_handled: 9,
                     tcp6_scrapes_handled: 10,
+                    tcp6_announces_too_frequent: 22,
                     udp4_connections_handled: 11,
                     udp4_announces_handled: 12,
                     udp4_scrapes_handled: 13,
                     udp4_errors_handled: 14,
+                    udp4_requests_rate_limited: 19,
+                    udp4_announces_too_frequent: 23,
                     udp6_connections_handled: 15,
                     udp6_announces_handled: 16,
                     udp6_scrapes_handled: 17,
-                    udp6_errors_handled: 18
-                }
+                    udp6_errors_handled: 18,
+                    udp6_requests_rate_limited: 20,
+                    udp6_announces_too_frequent: 24
+                },
+                listeners_metrics: BTreeMap::from([(
+                    Listener {
+                        protocol: Protocol::Udp,
+                        socket_addr: sample_socket_addr(),
+                    },
+                    ListenerMetrics {
+                        ipv4: RequestMetrics {
+                            connections_handled: 11,
+                            announces_handled: 12,
+                            scrapes_handled: 13,
+                            errors_handled: 14,
+                            requests_rate_limited: 19,
+                            announces_too_frequent: 23
+                        },
+                        ipv6: RequestMetrics::default()
+                    }
+                )]),
+                latency_metrics: BTreeMap::from([((Protocol::Udp, RequestKind::Announce), sample_latency_histogram())])
             }),
             Stats {
                 torrents: 4,
@@ -118,17 +311,37 @@ mod tests {
                 tcp4_connections_handled: 5,
                 tcp4_announces_handled: 6,
                 tcp4_scrapes_handled: 7,
+                tcp4_announces_too_frequent: 21,
                 tcp6_connections_handled: 8,
                 tcp6_announces_handled: 9,
                 tcp6_scrapes_handled: 10,
+                tcp6_announces_too_frequent: 22,
                 udp4_connections_handled: 11,
                 udp4_announces_handled: 12,
                 udp4_scrapes_handled: 13,
                 udp4_errors_handled: 14,
+                udp4_requests_rate_limited: 19,
+                udp4_announces_too_frequent: 23,
                 udp6_connections_handled: 15,
                 udp6_announces_handled: 16,
                 udp6_scrapes_handled: 17,
-                udp6_errors_handled: 18
+                udp6_errors_handled: 18,
+                udp6_requests_rate_limited: 20,
+                udp6_announces_too_frequent: 24,
+                listeners: vec![ListenerStats {
+                    protocol: "udp".to_string(),
+                    socket_addr: sample_socket_addr(),
+                    ipv4: RequestStats {
+                        connections_handled: 11,
+                        announces_handled: 12,
+                        scrapes_handled: 13,
+                        errors_handled: 14,
+                        requests_rate_limited: 19,
+                        announces_too_frequent: 23
+                    },
+                    ipv6: RequestStats::default()
+                }],
+                latencies: vec![sample_latency_stats()]
             }
         );
     }
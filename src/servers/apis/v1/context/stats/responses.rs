@@ -1,6 +1,9 @@
 //! API responses for the [`stats`](crate::servers::apis::v1::context::stats)
 //! API context.
-use axum::response::Json;
+use std::fmt::Write;
+
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Json, Response};
 
 use super::resources::Stats;
 use crate::core::services::statistics::TrackerMetrics;
@@ -9,3 +12,307 @@ use crate::core::services::statistics::TrackerMetrics;
 pub fn stats_response(tracker_metrics: TrackerMetrics) -> Json<Stats> {
     Json(Stats::from(tracker_metrics))
 }
+
+/// `200` response that contains the [`TrackerMetrics`] in the Prometheus text
+/// exposition format.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::stats#prometheus-format)
+/// for the list of exported metrics.
+#[must_use]
+pub fn metrics_response(tracker_metrics: &TrackerMetrics) -> Response {
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4; charset=utf-8")],
+        prometheus_text(tracker_metrics),
+    )
+        .into_response()
+}
+
+/// The labels for the protocol and IP version of the requests.
+const HTTP_IPV4: &str = r#"protocol="http",ip_version="ipv4""#;
+const HTTP_IPV6: &str = r#"protocol="http",ip_version="ipv6""#;
+const UDP_IPV4: &str = r#"protocol="udp",ip_version="ipv4""#;
+const UDP_IPV6: &str = r#"protocol="udp",ip_version="ipv6""#;
+
+fn prometheus_text(tracker_metrics: &TrackerMetrics) -> String {
+    let mut text = String::new();
+
+    write_torrent_metrics(&mut text, tracker_metrics);
+    write_request_metrics(&mut text, tracker_metrics);
+    write_metric(
+        &mut text,
+        "listener_requests_handled_total",
+        "counter",
+        "Total number of requests handled by each HTTP and UDP tracker.",
+        &listener_samples(tracker_metrics),
+    );
+    write_latency_histograms(&mut text, tracker_metrics);
+
+    text
+}
+
+/// It writes the swarm metrics for all torrents.
+fn write_torrent_metrics(text: &mut String, tracker_metrics: &TrackerMetrics) {
+    let torrents = &tracker_metrics.torrents_metrics;
+
+    write_metric(
+        text,
+        "torrents",
+        "gauge",
+        "Total number of torrents.",
+        &[("", torrents.torrents)],
+    );
+    write_metric(
+        text,
+        "seeders",
+        "gauge",
+        "Total number of seeders for all torrents.",
+        &[("", torrents.complete)],
+    );
+    write_metric(
+        text,
+        "leechers",
+        "gauge",
+        "Total number of leechers for all torrents.",
+        &[("", torrents.incomplete)],
+    );
+    write_metric(
+        text,
+        "completed_total",
+        "counter",
+        "Total number of peers that have ever completed downloading for all torrents.",
+        &[("", torrents.downloaded)],
+    );
+}
+
+/// It writes the request counters, with the `protocol` and `ip_version`
+/// labels.
+fn write_request_metrics(text: &mut String, tracker_metrics: &TrackerMetrics) {
+    let requests = &tracker_metrics.protocol_metrics;
+
+    write_metric(
+        text,
+        "connections_handled_total",
+        "counter",
+        "Total number of connections handled. The HTTP tracker counts every request.",
+        &[
+            (HTTP_IPV4, requests.tcp4_connections_handled),
+            (HTTP_IPV6, requests.tcp6_connections_handled),
+            (UDP_IPV4, requests.udp4_connections_handled),
+            (UDP_IPV6, requests.udp6_connections_handled),
+        ],
+    );
+    write_metric(
+        text,
+        "announces_handled_total",
+        "counter",
+        "Total number of `announce` requests handled.",
+        &[
+            (HTTP_IPV4, requests.tcp4_announces_handled),
+            (HTTP_IPV6, requests.tcp6_announces_handled),
+            (UDP_IPV4, requests.udp4_announces_handled),
+            (UDP_IPV6, requests.udp6_announces_handled),
+        ],
+    );
+    write_metric(
+        text,
+        "scrapes_handled_total",
+        "counter",
+        "Total number of `scrape` requests handled.",
+        &[
+            (HTTP_IPV4, requests.tcp4_scrapes_handled),
+            (HTTP_IPV6, requests.tcp6_scrapes_handled),
+            (UDP_IPV4, requests.udp4_scrapes_handled),
+            (UDP_IPV6, requests.udp6_scrapes_handled),
+        ],
+    );
+    write_metric(
+        text,
+        "errors_handled_total",
+        "counter",
+        "Total number of error responses sent by the UDP tracker.",
+        &[
+            (UDP_IPV4, requests.udp4_errors_handled),
+            (UDP_IPV6, requests.udp6_errors_handled),
+        ],
+    );
+    write_metric(
+        text,
+        "requests_rate_limited_total",
+        "counter",
+        "Total number of requests rejected by the UDP tracker rate limiter.",
+        &[
+            (UDP_IPV4, requests.udp4_requests_rate_limited),
+            (UDP_IPV6, requests.udp6_requests_rate_limited),
+        ],
+    );
+    write_metric(
+        text,
+        "announces_too_frequent_total",
+        "counter",
+        "Total number of `announce` requests received before the `min interval`.",
+        &[
+            (HTTP_IPV4, requests.tcp4_announces_too_frequent),
+            (HTTP_IPV6, requests.tcp6_announces_too_frequent),
+            (UDP_IPV4, requests.udp4_announces_too_frequent),
+            (UDP_IPV6, requests.udp6_announces_too_frequent),
+        ],
+    );
+}
+
+/// It writes the request latency histograms, with the `protocol` and
+/// `request` labels.
+fn write_latency_histograms(text: &mut String, tracker_metrics: &TrackerMetrics) {
+    let name = "torrust_tracker_request_duration_seconds";
+
+    writeln!(text, "# HELP {name} Time taken to handle the requests.").expect("it should write into a string");
+    writeln!(text, "# TYPE {name} histogram").expect("it should write into a string");
+
+    for ((protocol, kind), histogram) in &tracker_metrics.latency_metrics {
+        let labels = format!(r#"protocol="{protocol}",request="{kind}""#);
+
+        for (upper_bound, count) in histogram.cumulative_buckets() {
+            let le = seconds(upper_bound);
+            writeln!(text, r#"{name}_bucket{{{labels},le="{le}"}} {count}"#).expect("it should write into a string");
+        }
+
+        let count = histogram.count;
+        let sum = seconds(histogram.sum);
+
+        writeln!(text, r#"{name}_bucket{{{labels},le="+Inf"}} {count}"#).expect("it should write into a string");
+        writeln!(text, "{name}_sum{{{labels}}} {sum}").expect("it should write into a string");
+        writeln!(text, "{name}_count{{{labels}}} {count}").expect("it should write into a string");
+    }
+}
+
+/// It formats a number of microseconds as seconds, without trailing zeros.
+fn seconds(microseconds: u64) -> String {
+    let seconds = format!("{}.{:06}", microseconds / 1_000_000, microseconds % 1_000_000);
+
+    seconds.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+/// The samples of the requests handled by each listener. They are labeled
+/// with the server socket address, IP version and request kind.
+fn listener_samples(tracker_metrics: &TrackerMetrics) -> Vec<(String, u64)> {
+    let mut samples = vec![];
+
+    for (listener, metrics) in &tracker_metrics.listeners_metrics {
+        for (ip_version, requests) in [("ipv4", &metrics.ipv4), ("ipv6", &metrics.ipv6)] {
+            for (request, value) in [
+                ("connect", requests.connections_handled),
+                ("announce", requests.announces_handled),
+                ("scrape", requests.scrapes_handled),
+                ("error", requests.errors_handled),
+                ("rate_limited", requests.requests_rate_limited),
+                ("announce_too_frequent", requests.announces_too_frequent),
+            ] {
+                samples.push((
+                    format!(
+                        r#"protocol="{}",server_socket_addr="{}",ip_version="{ip_version}",request="{request}""#,
+                        listener.protocol, listener.socket_addr
+                    ),
+                    value,
+                ));
+            }
+        }
+    }
+
+    samples
+}
+
+/// It writes one metric family. Every sample is a pair with the labels
+/// (without braces) and the value.
+fn write_metric<L: AsRef<str>>(text: &mut String, name: &str, metric_type: &str, help: &str, samples: &[(L, u64)]) {
+    let name = format!("torrust_tracker_{name}");
+
+    writeln!(text, "# HELP {name} {help}").expect("it should write into a string");
+    writeln!(text, "# TYPE {name} {metric_type}").expect("it should write into a string");
+
+    for (labels, value) in samples {
+        let labels = labels.as_ref();
+
+        if labels.is_empty() {
+            writeln!(text, "{name} {value}").expect("it should write into a string");
+        } else {
+            writeln!(text, "{name}{{{labels}}} {value}").expect("it should write into a string");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
+    use torrust_tracker_primitives::torrent_metrics::TorrentsMetrics;
+
+    use super::{prometheus_text, seconds};
+    use crate::core::services::statistics::TrackerMetrics;
+    use crate::core::statistics::{LatencyHistogram, Listener, ListenerMetrics, Metrics, Protocol, RequestKind, RequestMetrics};
+
+    #[test]
+    fn tracker_metrics_should_be_exported_in_the_prometheus_text_format() {
+        let text = prometheus_text(&TrackerMetrics {
+            torrents_metrics: TorrentsMetrics {
+                complete: 1,
+                downloaded: 2,
+                incomplete: 3,
+                torrents: 4,
+            },
+            protocol_metrics: Metrics {
+                tcp4_announces_handled: 5,
+                udp6_errors_handled: 6,
+                ..Default::default()
+            },
+            listeners_metrics: BTreeMap::from([(
+                Listener {
+                    protocol: Protocol::Http,
+                    socket_addr: "0.0.0.0:7070".parse().unwrap(),
+                },
+                ListenerMetrics {
+                    ipv4: RequestMetrics {
+                        announces_handled: 5,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+            )]),
+            latency_metrics: BTreeMap::from([((Protocol::Udp, RequestKind::Scrape), {
+                let mut histogram = LatencyHistogram::default();
+                histogram.observe(Duration::from_micros(1_500));
+                histogram
+            })]),
+        });
+
+        assert!(text.contains("# TYPE torrust_tracker_torrents gauge\ntorrust_tracker_torrents 4\n"));
+        assert!(text.contains("torrust_tracker_seeders 1\n"));
+        assert!(text.contains("torrust_tracker_leechers 3\n"));
+        assert!(text.contains("torrust_tracker_completed_total 2\n"));
+        assert!(text.contains("torrust_tracker_announces_handled_total{protocol=\"http\",ip_version=\"ipv4\"} 5\n"));
+        assert!(text.contains("torrust_tracker_announces_handled_total{protocol=\"udp\",ip_version=\"ipv6\"} 0\n"));
+        assert!(text.contains("torrust_tracker_errors_handled_total{protocol=\"udp\",ip_version=\"ipv6\"} 6\n"));
+        assert!(text.contains(
+            "torrust_tracker_listener_requests_handled_total{protocol=\"http\",server_socket_addr=\"0.0.0.0:7070\",ip_version=\"ipv4\",request=\"announce\"} 5\n"
+        ));
+        assert!(text.contains("# TYPE torrust_tracker_request_duration_seconds histogram\n"));
+        assert!(text
+            .contains("torrust_tracker_request_duration_seconds_bucket{protocol=\"udp\",request=\"scrape\",le=\"0.001\"} 0\n"));
+        assert!(text
+            .contains("torrust_tracker_request_duration_seconds_bucket{protocol=\"udp\",request=\"scrape\",le=\"0.0025\"} 1\n"));
+        assert!(
+            text.contains("torrust_tracker_request_duration_seconds_bucket{protocol=\"udp\",request=\"scrape\",le=\"+Inf\"} 1\n")
+        );
+        assert!(text.contains("torrust_tracker_request_duration_seconds_sum{protocol=\"udp\",request=\"scrape\"} 0.0015\n"));
+        assert!(text.contains("torrust_tracker_request_duration_seconds_count{protocol=\"udp\",request=\"scrape\"} 1\n"));
+    }
+
+    #[test]
+    fn microseconds_should_be_formatted_as_seconds_without_trailing_zeros() {
+        assert_eq!(seconds(0), "0");
+        assert_eq!(seconds(100), "0.0001");
+        assert_eq!(seconds(2_500), "0.0025");
+        assert_eq!(seconds(1_000_000), "1");
+        assert_eq!(seconds(1_234_567), "1.234567");
+    }
+}
@@ -5,18 +5,24 @@ use std::str::FromStr;
 use std::sync::Arc;
 
 use axum::extract::{Path, State};
+use axum::http::HeaderMap;
 use axum::response::{IntoResponse, Response};
 use axum_extra::extract::Query;
 use bittorrent_primitives::info_hash::InfoHash;
 use serde::{de, Deserialize, Deserializer};
 use thiserror::Error;
+use torrust_tracker_primitives::info_hash_v2::{parse_hex_info_hash, InfoHashV2};
 use torrust_tracker_primitives::pagination::Pagination;
 
-use super::responses::{torrent_info_response, torrent_list_response, torrent_not_known_response};
+use super::responses::{
+    failed_to_add_hybrid_torrent_response, failed_to_remove_hybrid_torrent_response, full_scrape_not_available_response,
+    full_scrape_response, torrent_info_response, torrent_list_response, torrent_not_known_response,
+};
 use crate::core::services::torrent::{get_torrent_info, get_torrents, get_torrents_page};
 use crate::core::Tracker;
-use crate::servers::apis::v1::responses::invalid_info_hash_param_response;
+use crate::servers::apis::v1::responses::{invalid_info_hash_param_response, ok_response};
 use crate::servers::apis::InfoHashParam;
+use crate::servers::content_encoding::accepts_gzip;
 
 /// It handles the request to get the torrent data.
 ///
@@ -28,7 +34,7 @@ use crate::servers::apis::InfoHashParam;
 /// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::torrent#get-a-torrent)
 /// for more information about this endpoint.
 pub async fn get_torrent_handler(State(tracker): State<Arc<Tracker>>, Path(info_hash): Path<InfoHashParam>) -> Response {
-    match InfoHash::from_str(&info_hash.0) {
+    match parse_hex_info_hash(&info_hash.0) {
         Err(_) => invalid_info_hash_param_response(&info_hash.0),
         Ok(info_hash) => match get_torrent_info(tracker.clone(), &info_hash).await {
             Some(info) => torrent_info_response(info).into_response(),
@@ -97,6 +103,79 @@ pub async fn get_torrents_handler(State(tracker): State<Arc<Tracker>>, paginatio
     }
 }
 
+/// It handles the request to get the full scrape.
+///
+/// It returns:
+///
+/// - `200` response with the bencoded scrape data for all the torrents.
+/// - `500` with serialized error in debug format if the full scrape is not
+///   enabled or it has not been generated yet.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::torrent#get-the-full-scrape)
+/// for more information about this endpoint.
+pub async fn get_full_scrape_handler(State(tracker): State<Arc<Tracker>>, headers: HeaderMap) -> Response {
+    match tracker.get_full_scrape() {
+        Some(full_scrape) => full_scrape_response(&full_scrape, accepts_gzip(&headers)),
+        None => full_scrape_not_available_response(),
+    }
+}
+
+/// It handles the request to add a hybrid torrent.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `500` with serialized error in debug format if the hybrid torrent couldn't be added.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::torrent#add-a-hybrid-torrent)
+/// for more information about this endpoint.
+pub async fn add_hybrid_torrent_handler(
+    State(tracker): State<Arc<Tracker>>,
+    Path((info_hash, info_hash_v2)): Path<(String, String)>,
+) -> Response {
+    match parse_hybrid_info_hashes(&info_hash, &info_hash_v2) {
+        Err(response) => response,
+        Ok((info_hash, info_hash_v2)) => match tracker.add_hybrid_torrent(&info_hash, &info_hash_v2) {
+            Ok(()) => ok_response(),
+            Err(e) => failed_to_add_hybrid_torrent_response(e),
+        },
+    }
+}
+
+/// It handles the request to remove a hybrid torrent.
+///
+/// It returns:
+///
+/// - `200` response with a [`ActionStatus::Ok`](crate::servers::apis::v1::responses::ActionStatus::Ok) in json.
+/// - `500` with serialized error in debug format if the hybrid torrent couldn't be removed.
+///
+/// Refer to the [API endpoint documentation](crate::servers::apis::v1::context::torrent#remove-a-hybrid-torrent)
+/// for more information about this endpoint.
+pub async fn remove_hybrid_torrent_handler(
+    State(tracker): State<Arc<Tracker>>,
+    Path((info_hash, info_hash_v2)): Path<(String, String)>,
+) -> Response {
+    match parse_hybrid_info_hashes(&info_hash, &info_hash_v2) {
+        Err(response) => response,
+        Ok((_info_hash, info_hash_v2)) => match tracker.remove_hybrid_torrent(&info_hash_v2) {
+            Ok(()) => ok_response(),
+            Err(e) => failed_to_remove_hybrid_torrent_response(e),
+        },
+    }
+}
+
+fn parse_hybrid_info_hashes(info_hash: &str, info_hash_v2: &str) -> Result<(InfoHash, InfoHashV2), Response> {
+    let Ok(parsed_info_hash) = InfoHash::from_str(info_hash) else {
+        return Err(invalid_info_hash_param_response(info_hash));
+    };
+
+    let Ok(parsed_info_hash_v2) = InfoHashV2::from_str(info_hash_v2) else {
+        return Err(invalid_info_hash_param_response(info_hash_v2));
+    };
+
+    Ok((parsed_info_hash, parsed_info_hash_v2))
+}
+
 #[derive(Error, Debug)]
 pub enum QueryParamError {
     #[error("invalid infohash {info_hash}")]
@@ -107,7 +186,7 @@ fn parse_info_hashes(info_hashes_str: Vec<String>) -> Result<Vec<InfoHash>, Quer
     let mut info_hashes: Vec<InfoHash> = Vec::new();
 
     for info_hash_str in info_hashes_str {
-        match InfoHash::from_str(&info_hash_str) {
+        match parse_hex_info_hash(&info_hash_str) {
             Ok(info_hash) => info_hashes.push(info_hash),
             Err(_err) => {
                 return Err(QueryParamError::InvalidInfoHash {
@@ -7,6 +7,9 @@
 //!
 //! - [Get a torrent](#get-a-torrent)
 //! - [List torrents](#list-torrents)
+//! - [Get the full scrape](#get-the-full-scrape)
+//! - [Add a hybrid torrent](#add-a-hybrid-torrent)
+//! - [Remove a hybrid torrent](#remove-a-hybrid-torrent)
 //!
 //! # Get a torrent
 //!
@@ -14,11 +17,14 @@
 //!
 //! Returns all the information about a torrent.
 //!
+//! A v2 infohash (64-char string) is truncated to 20 bytes. The truncated v2
+//! infohash of a hybrid torrent returns the swarm of its v1 infohash.
+//!
 //! **Path parameters**
 //!
 //! Name | Type | Description | Required | Example
 //! ---|---|---|---|---
-//! `info_hash` | 40-char string | The Info Hash v1 | Yes | `5452869be36f9f3350ccee6b4544e7e76caaadab`
+//! `info_hash` | 40-char or 64-char string | The Info Hash v1 or v2 | Yes | `5452869be36f9f3350ccee6b4544e7e76caaadab`
 //!
 //! **Example request**
 //!
@@ -107,6 +113,99 @@
 //! response.
 //!
 //! > **NOTICE**: this endpoint does not include the `peers` list.
+//!
+//! # Get the full scrape
+//!
+//! `GET /torrents/scrape`
+//!
+//! Returns the last full scrape generated by the tracker: the bencoded scrape
+//! response with the `complete`, `downloaded` and `incomplete` counters of all
+//! the torrents. It's the same document returned by the HTTP tracker for the
+//! `scrape` requests without any `info_hash` param.
+//!
+//! The response is compressed, with the `Content-Encoding: gzip` header, when
+//! the full scrape is gzip-compressed and the client accepts the `gzip`
+//! encoding.
+//!
+//! Refer to the [`FullScrape`](torrust_tracker_configuration::FullScrape)
+//! configuration for more information about how to enable the full scrape.
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl "http://127.0.0.1:1212/api/v1/torrents/scrape?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```text
+//! d5:filesd20:iiiiiiiiiiiiiiiiiiiid8:completei1e10:downloadedi2e10:incompletei3eeee
+//! ```
+//!
+//! **Not available response** `500`
+//!
+//! This response is returned when the full scrape is not enabled or it has
+//! not been generated yet.
+//!
+//! ```text
+//! Unhandled rejection: Err { reason: "full scrape not available" }
+//! ```
+//!
+//! # Add a hybrid torrent
+//!
+//! `POST /torrent/:info_hash/hybrid/:info_hash_v2`
+//!
+//! Hybrid torrents have a v1 and a v2 infohash. After adding a hybrid torrent,
+//! peers announcing the v2 infohash (truncated to 20 bytes) join the swarm of
+//! the v1 infohash.
+//!
+//! **Path parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `info_hash` | 40-char string | The Info Hash v1 | Yes | `5452869be36f9f3350ccee6b4544e7e76caaadab`
+//! `info_hash_v2` | 64-char string | The Info Hash v2 | Yes | `3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0b8a1a4d58d14d7a21b7d7a45`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X POST "http://127.0.0.1:1212/api/v1/torrent/5452869be36f9f3350ccee6b4544e7e76caaadab/hybrid/3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0b8a1a4d58d14d7a21b7d7a45?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
+//!
+//! # Remove a hybrid torrent
+//!
+//! `DELETE /torrent/:info_hash/hybrid/:info_hash_v2`
+//!
+//! It removes the link between the v1 and the v2 infohashes.
+//!
+//! **Path parameters**
+//!
+//! Name | Type | Description | Required | Example
+//! ---|---|---|---|---
+//! `info_hash` | 40-char string | The Info Hash v1 | Yes | `5452869be36f9f3350ccee6b4544e7e76caaadab`
+//! `info_hash_v2` | 64-char string | The Info Hash v2 | Yes | `3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0b8a1a4d58d14d7a21b7d7a45`
+//!
+//! **Example request**
+//!
+//! ```bash
+//! curl -X DELETE "http://127.0.0.1:1212/api/v1/torrent/5452869be36f9f3350ccee6b4544e7e76caaadab/hybrid/3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0b8a1a4d58d14d7a21b7d7a45?token=MyAccessToken"
+//! ```
+//!
+//! **Example response** `200`
+//!
+//! ```json
+//! {
+//!     "status": "ok"
+//! }
+//! ```
 pub mod handlers;
 pub mod resources;
 pub mod responses;
@@ -1,10 +1,15 @@
 //! API responses for the [`torrent`](crate::servers::apis::v1::context::torrent)
 //! API context.
+use std::error::Error;
+
+use axum::http::{header, StatusCode};
 use axum::response::{IntoResponse, Json, Response};
 use serde_json::json;
 
 use super::resources::torrent::{ListItem, Torrent};
+use crate::core::full_scrape::FullScrape;
 use crate::core::services::torrent::{BasicInfo, Info};
+use crate::servers::apis::v1::responses::unhandled_rejection_response;
 
 /// `200` response that contains an array of
 /// [`ListItem`]
@@ -25,3 +30,40 @@ pub fn torrent_info_response(info: Info) -> Json<Torrent> {
 pub fn torrent_not_known_response() -> Response {
     Json(json!("torrent not known")).into_response()
 }
+
+/// `200` response that contains the bencoded [`FullScrape`]. The
+/// gzip-compressed full scrape is returned, with the `Content-Encoding: gzip`
+/// header, when there is one and the client accepts it.
+#[must_use]
+pub fn full_scrape_response(full_scrape: &FullScrape, accepts_gzip: bool) -> Response {
+    let content_type = (header::CONTENT_TYPE, "application/octet-stream");
+
+    match &full_scrape.gzipped_body {
+        Some(gzipped_body) if accepts_gzip => (
+            StatusCode::OK,
+            [content_type, (header::CONTENT_ENCODING, "gzip")],
+            gzipped_body.clone(),
+        )
+            .into_response(),
+        _ => (StatusCode::OK, [content_type], full_scrape.body.clone()).into_response(),
+    }
+}
+
+/// `500` error response when the full scrape is not enabled or it has not been
+/// generated yet.
+#[must_use]
+pub fn full_scrape_not_available_response() -> Response {
+    unhandled_rejection_response("full scrape not available".to_string())
+}
+
+/// `500` error response when a hybrid torrent cannot be added.
+#[must_use]
+pub fn failed_to_add_hybrid_torrent_response<E: Error>(e: E) -> Response {
+    unhandled_rejection_response(format!("failed to add hybrid torrent: {e}"))
+}
+
+/// `500` error response when a hybrid torrent cannot be removed.
+#[must_use]
+pub fn failed_to_remove_hybrid_torrent_response<E: Error>(e: E) -> Response {
+    unhandled_rejection_response(format!("failed to remove hybrid torrent: {e}"))
+}
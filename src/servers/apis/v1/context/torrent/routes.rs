@@ -2,14 +2,19 @@
 //!
 //! - `GET /torrent/:info_hash`
 //! - `GET /torrents`
+//! - `GET /torrents/scrape`
+//! - `POST /torrent/:info_hash/hybrid/:info_hash_v2`
+//! - `DELETE /torrent/:info_hash/hybrid/:info_hash_v2`
 //!
 //! Refer to the [API endpoint documentation](crate::servers::apis::v1::context::torrent).
 use std::sync::Arc;
 
-use axum::routing::get;
+use axum::routing::{delete, get, post};
 use axum::Router;
 
-use super::handlers::{get_torrent_handler, get_torrents_handler};
+use super::handlers::{
+    add_hybrid_torrent_handler, get_full_scrape_handler, get_torrent_handler, get_torrents_handler, remove_hybrid_torrent_handler,
+};
 use crate::core::Tracker;
 
 /// It adds the routes to the router for the [`torrent`](crate::servers::apis::v1::context::torrent) API context.
@@ -20,5 +25,20 @@ pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
             &format!("{prefix}/torrent/:info_hash"),
             get(get_torrent_handler).with_state(tracker.clone()),
         )
-        .route(&format!("{prefix}/torrents"), get(get_torrents_handler).with_state(tracker))
+        .route(
+            &format!("{prefix}/torrents"),
+            get(get_torrents_handler).with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/torrents/scrape"),
+            get(get_full_scrape_handler).with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/torrent/:info_hash/hybrid/:info_hash_v2"),
+            post(add_hybrid_torrent_handler).with_state(tracker.clone()),
+        )
+        .route(
+            &format!("{prefix}/torrent/:info_hash/hybrid/:info_hash_v2"),
+            delete(remove_hybrid_torrent_handler).with_state(tracker),
+        )
 }
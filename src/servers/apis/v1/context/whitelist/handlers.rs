@@ -1,11 +1,10 @@
 //! API handlers for the [`whitelist`](crate::servers::apis::v1::context::whitelist)
 //! API context.
-use std::str::FromStr;
 use std::sync::Arc;
 
 use axum::extract::{Path, State};
 use axum::response::Response;
-use bittorrent_primitives::info_hash::InfoHash;
+use torrust_tracker_primitives::info_hash_v2::parse_hex_info_hash;
 
 use super::responses::{
     failed_to_reload_whitelist_response, failed_to_remove_torrent_from_whitelist_response, failed_to_whitelist_torrent_response,
@@ -27,7 +26,7 @@ pub async fn add_torrent_to_whitelist_handler(
     State(tracker): State<Arc<Tracker>>,
     Path(info_hash): Path<InfoHashParam>,
 ) -> Response {
-    match InfoHash::from_str(&info_hash.0) {
+    match parse_hex_info_hash(&info_hash.0) {
         Err(_) => invalid_info_hash_param_response(&info_hash.0),
         Ok(info_hash) => match tracker.add_torrent_to_whitelist(&info_hash).await {
             Ok(()) => ok_response(),
@@ -50,7 +49,7 @@ pub async fn remove_torrent_from_whitelist_handler(
     State(tracker): State<Arc<Tracker>>,
     Path(info_hash): Path<InfoHashParam>,
 ) -> Response {
-    match InfoHash::from_str(&info_hash.0) {
+    match parse_hex_info_hash(&info_hash.0) {
         Err(_) => invalid_info_hash_param_response(&info_hash.0),
         Ok(info_hash) => match tracker.remove_torrent_from_whitelist(&info_hash).await {
             Ok(()) => ok_response(),
@@ -30,11 +30,14 @@
 //!
 //! It adds a torrent infohash to the whitelist.
 //!
+//! A v2 infohash (64-char string) is truncated to 20 bytes, which is the value
+//! `BitTorrent` v2 clients send to the tracker.
+//!
 //! **Path parameters**
 //!
 //! Name | Type | Description | Required | Example
 //! ---|---|---|---|---
-//! `info_hash` | 40-char string | The Info Hash v1 | Yes | `5452869be36f9f3350ccee6b4544e7e76caaadab`
+//! `info_hash` | 40-char or 64-char string | The Info Hash v1 or v2 | Yes | `5452869be36f9f3350ccee6b4544e7e76caaadab`
 //!
 //! **Example request**
 //!
@@ -60,7 +63,7 @@
 //!
 //! Name | Type | Description | Required | Example
 //! ---|---|---|---|---
-//! `info_hash` | 40-char string | The Info Hash v1 | Yes | `5452869be36f9f3350ccee6b4544e7e76caaadab`
+//! `info_hash` | 40-char or 64-char string | The Info Hash v1 or v2 | Yes | `5452869be36f9f3350ccee6b4544e7e76caaadab`
 //!
 //! **Example request**
 //!
@@ -7,10 +7,19 @@
 //! `Stats` | Tracker statistics | [`v1`](crate::servers::apis::v1::context::stats)
 //! `Torrents` | Torrents | [`v1`](crate::servers::apis::v1::context::torrent)
 //! `Whitelist` | Torrents whitelist | [`v1`](crate::servers::apis::v1::context::whitelist)
+//! `Blacklist` | Torrents blacklist | [`v1`](crate::servers::apis::v1::context::blacklist)
 //! `Authentication keys` | Authentication keys | [`v1`](crate::servers::apis::v1::context::auth_key)
+//! `Maintenance` | Maintenance mode | [`v1`](crate::servers::apis::v1::context::maintenance)
+//! `Ratio` | Transfer accounting per authentication key | [`v1`](crate::servers::apis::v1::context::ratio)
+//! `Client filter` | Client software allow/deny list | [`v1`](crate::servers::apis::v1::context::client_filter)
+//! `IP blocklist` | Blocked networks | [`v1`](crate::servers::apis::v1::context::ip_blocklist)
 //!
 //! > **NOTICE**:
 //! - The authentication keys are only used by the HTTP tracker.
+//! - The transfers are only accounted when the tracker is running in
+//!   `private` or `private_listed` mode.
+//! - The client filter prefixes are only used when the `client_filter` mode is
+//!   `allow` or `deny`.
 //! - The whitelist is only used when the tracker is running in `listed` or
 //!   `private_listed` mode.
 //!
@@ -14,7 +14,7 @@ use serde::Serialize;
     We should show a message similar to the one we use when we parse the value in the handler.
     For example:
 
-    "Invalid URL: invalid infohash param: string \"INVALID VALUE\", expected a 40 character long string"
+    "Invalid URL: invalid infohash param: string \"INVALID VALUE\", expected a 40 or 64 character long string"
 
     We can customize the error message by using a custom type with custom serde deserialization.
     The same we are using for the "InfoHashVisitor".
@@ -52,7 +52,7 @@ pub fn ok_response() -> Response {
 #[must_use]
 pub fn invalid_info_hash_param_response(info_hash: &str) -> Response {
     bad_request_response(&format!(
-        "Invalid URL: invalid infohash param: string \"{info_hash}\", expected a 40 character long string"
+        "Invalid URL: invalid infohash param: string \"{info_hash}\", expected a 40 or 64 character long string"
     ))
 }
 
@@ -3,7 +3,7 @@ use std::sync::Arc;
 
 use axum::Router;
 
-use super::context::{auth_key, stats, torrent, whitelist};
+use super::context::{auth_key, blacklist, client_filter, ip_blocklist, maintenance, ratio, stats, torrent, whitelist};
 use crate::core::Tracker;
 
 /// Add the routes for the v1 API.
@@ -11,6 +11,11 @@ pub fn add(prefix: &str, router: Router, tracker: Arc<Tracker>) -> Router {
     let v1_prefix = format!("{prefix}/v1");
 
     let router = auth_key::routes::add(&v1_prefix, router, tracker.clone());
+    let router = blacklist::routes::add(&v1_prefix, router, tracker.clone());
+    let router = client_filter::routes::add(&v1_prefix, router, tracker.clone());
+    let router = ip_blocklist::routes::add(&v1_prefix, router, tracker.clone());
+    let router = maintenance::routes::add(&v1_prefix, router, tracker.clone());
+    let router = ratio::routes::add(&v1_prefix, router, tracker.clone());
     let router = stats::routes::add(&v1_prefix, router, tracker.clone());
     let router = whitelist::routes::add(&v1_prefix, router, tracker.clone());
 
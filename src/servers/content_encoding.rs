@@ -0,0 +1,83 @@
+//! Content negotiation for the responses that are stored already compressed.
+//!
+//! Most responses are compressed on the fly by the `CompressionLayer`, but
+//! the cached [`FullScrape`](crate::core::full_scrape::FullScrape) can be
+//! stored gzip-compressed. It's only sent compressed to the clients that
+//! accept the `gzip` encoding in the `Accept-Encoding` header.
+use axum::http::{header, HeaderMap};
+
+/// It returns `true` if the client accepts the `gzip` content encoding.
+///
+/// The `gzip` (or `x-gzip`) coding is accepted when it's listed with a
+/// non-zero quality value, or when it's not listed and the `*` coding is
+/// accepted.
+#[must_use]
+pub fn accepts_gzip(headers: &HeaderMap) -> bool {
+    let mut wildcard = false;
+
+    for coding in headers
+        .get_all(header::ACCEPT_ENCODING)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+    {
+        let mut parts = coding.split(';');
+        let name = parts.next().unwrap_or_default().trim();
+        let accepted = parts
+            .filter_map(|param| param.trim().strip_prefix("q="))
+            .all(|quality| quality.trim().parse::<f32>().is_ok_and(|quality| quality > 0.0));
+
+        if name.eq_ignore_ascii_case("gzip") || name.eq_ignore_ascii_case("x-gzip") {
+            return accepted;
+        }
+
+        if name == "*" {
+            wildcard = accepted;
+        }
+    }
+
+    wildcard
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::{header, HeaderMap, HeaderValue};
+
+    use super::accepts_gzip;
+
+    fn headers(accept_encoding: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT_ENCODING, HeaderValue::from_str(accept_encoding).unwrap());
+        headers
+    }
+
+    #[test]
+    fn it_should_not_accept_gzip_when_there_is_no_accept_encoding_header() {
+        assert!(!accepts_gzip(&HeaderMap::new()));
+    }
+
+    #[test]
+    fn it_should_accept_gzip_when_it_is_listed() {
+        assert!(accepts_gzip(&headers("gzip")));
+        assert!(accepts_gzip(&headers("deflate, GZIP;q=0.5, br")));
+        assert!(accepts_gzip(&headers("x-gzip")));
+    }
+
+    #[test]
+    fn it_should_not_accept_gzip_when_it_is_not_listed() {
+        assert!(!accepts_gzip(&headers("deflate, br")));
+        assert!(!accepts_gzip(&headers("identity")));
+    }
+
+    #[test]
+    fn it_should_not_accept_gzip_when_its_quality_is_zero() {
+        assert!(!accepts_gzip(&headers("gzip;q=0")));
+        assert!(!accepts_gzip(&headers("*, gzip;q=0.0")));
+    }
+
+    #[test]
+    fn it_should_accept_gzip_when_any_coding_is_accepted() {
+        assert!(accepts_gzip(&headers("*")));
+        assert!(!accepts_gzip(&headers("*;q=0")));
+    }
+}
@@ -45,14 +45,20 @@
 //! ---|---|---|---|---|---
 //! [`info_hash`](crate::servers::http::v1::requests::announce::Announce::info_hash) | percent encoded of 20-byte array | The `Info Hash` of the torrent. | Yes | No | `%81%00%00%00%00%00%00%00%00%00%00%00%00%00%00%00%00%00%00%00`
 //! `peer_addr` | string |The IP address of the peer. | No | No | `2.137.87.41`
+//! [`ip`](crate::servers::http::v1::requests::announce::Announce::ip) | IPv4 or IPv6 address | The IP address of the peer. Only used for clients in a trusted network. | No | No | `2.137.87.41`
 //! [`downloaded`](crate::servers::http::v1::requests::announce::Announce::downloaded) | positive integer |The number of bytes downloaded by the peer. | No | `0` | `0`
 //! [`uploaded`](crate::servers::http::v1::requests::announce::Announce::uploaded) | positive integer | The number of bytes uploaded by the peer. | No | `0` | `0`
 //! [`peer_id`](crate::servers::http::v1::requests::announce::Announce::peer_id) | percent encoded of 20-byte array  | The ID of the peer. | Yes | No | `-qB00000000000000001`
 //! [`port`](crate::servers::http::v1::requests::announce::Announce::port) | positive integer | The port used by the peer. | Yes | No | `17548`
 //! [`left`](crate::servers::http::v1::requests::announce::Announce::left) | positive integer | The number of bytes pending to download. | No | `0` | `0`
-//! [`event`](crate::servers::http::v1::requests::announce::Announce::event) | positive integer | The event that triggered the `Announce` request: `started`, `completed`, `stopped` | No | `None` | `completed`
+//! [`event`](crate::servers::http::v1::requests::announce::Announce::event) | positive integer | The event that triggered the `Announce` request: `started`, `completed`, `stopped`, `paused` | No | `None` | `completed`
 //! [`compact`](crate::servers::http::v1::requests::announce::Announce::compact) | `0` or `1` | Whether the tracker should return a compact peer list. | No | `None` | `0`
-//! `numwant` | positive integer | **Not implemented**. The maximum number of peers you want in the reply. | No | `50` | `50`
+//! [`numwant`](crate::servers::http::v1::requests::announce::Announce::numwant) | positive integer | The maximum number of peers you want in the reply. | No | `50` | `50`
+//! [`ipv4`](crate::servers::http::v1::requests::announce::Announce::ipv4) | percent encoded IPv4 address, with an optional port | The IPv4 address of a dual-stack peer. | No | No | `2.137.87.41`
+//! [`ipv6`](crate::servers::http::v1::requests::announce::Announce::ipv6) | percent encoded IPv6 address, with an optional port | The IPv6 address of a dual-stack peer. | No | No | `2001%3Adb8%3A%3A1`
+//! [`no_peer_id`](crate::servers::http::v1::requests::announce::Announce::no_peer_id) | `0` or `1` | Whether the tracker can omit the peer IDs in the non-compact peer list. | No | `0` | `1`
+//! [`trackerid`](crate::servers::http::v1::requests::announce::Announce::tracker_id) | string | The `tracker id` returned in a previous announce response. | No | No | `torrust`
+//! [`key`](crate::servers::http::v1::requests::announce::Announce::key) | string | A secret value to identify the peer when its IP changes. | No | No | `1A2B3C4D`
 //!
 //! Refer to the [`Announce`](crate::servers::http::v1::requests::announce::Announce)
 //! request for more information about the parameters.
@@ -62,6 +68,9 @@
 //! > tracker assigns default values to the optional parameters if they are not
 //! > provided.
 //!
+//! > **NOTICE**: the tracker ignores the announces that change the IP of a
+//! > peer without the `key` the peer sent before.
+//!
 //! > **NOTICE**: the `peer_addr` parameter is not part of the original
 //! > specification. But the peer IP was added in the
 //! > [UDP Tracker protocol](https://www.bittorrent.org/beps/bep_0015.html). It is
@@ -70,10 +79,25 @@
 //! > request or the right-most-ip in the `X-Forwarded-For` header if the tracker
 //! > is behind a reverse proxy.
 //!
+//! > **NOTICE**: the `ip` parameter is ignored unless the client connects
+//! > from one of the `trusted_networks` in the `[core.net]` configuration, for
+//! > example, a proxy or a seedbox gateway. Private and reserved addresses are
+//! > always ignored.
+//!
+//! > **NOTICE**: the `ipv4` and `ipv6` parameters are defined in
+//! > [BEP 07](https://www.bittorrent.org/beps/bep_0007.html). The address for
+//! > the IP version the peer is not using to connect to the tracker is
+//! > returned in the `peers` or `peers6` list of the compact responses
+//! > together with the address of the request. Private, reserved and blocked
+//! > addresses are ignored. The address for the IP version the peer is using
+//! > to connect is handled like the `ip` parameter when there is no `ip`
+//! > parameter.
+//!
 //! > **NOTICE**: the maximum number of peers that the tracker can return is
-//! > `74`. Defined with a hardcoded const [`TORRENT_PEERS_LIMIT`](torrust_tracker_configuration::TORRENT_PEERS_LIMIT).
-//! > Refer to [issue 262](https://github.com/torrust/torrust-tracker/issues/262)
-//! > for more information about this limitation.
+//! > `74` by default. It can be changed with the
+//! > [`numwant_max_http`](torrust_tracker_configuration::AnnouncePolicy::numwant_max_http)
+//! > option. Clients that do not send the `numwant` param get
+//! > [`numwant`](torrust_tracker_configuration::AnnouncePolicy::numwant) peers.
 //!
 //! > **NOTICE**: the `info_hash` parameter is NOT a `URL` encoded string param.
 //! > It is percent encode of the raw `info_hash` bytes (40 bytes). URL `GET` params
@@ -193,6 +217,19 @@
 //! Refer to the [`Compact`](crate::servers::http::v1::responses::announce::Compact)
 //! response for more information about the response.
 //!
+//! **Optional response fields**
+//!
+//! Both forms of the response can also contain these fields:
+//!
+//! Field | Description
+//! ---|---
+//! `external ip` | The IP address of the client as seen by the tracker, as 4 or 16 bytes. Defined in [BEP 24](https://www.bittorrent.org/beps/bep_0024.html). It's not included in the samples above.
+//! `tracker id` | The `tracker_id` in the `[core]` configuration. Clients send it back in the `trackerid` param.
+//! `warning message` | The message in the `torrent_warning_messages` for the torrent, or the general `warning_message` in the `[core]` configuration.
+//!
+//! Refer to the [`OptionalFields`](crate::servers::http::v1::responses::announce::OptionalFields)
+//! for more information.
+//!
 //! **Protocol**
 //!
 //! Original specification in [BEP 03. The `BitTorrent` Protocol Specification](https://www.bittorrent.org/beps/bep_0003.html).
@@ -215,6 +252,7 @@
 //! - [complete](torrust_tracker_primitives::swarm_metadata::SwarmMetadata::complete)
 //! - [downloaded](torrust_tracker_primitives::swarm_metadata::SwarmMetadata::downloaded)
 //! - [incomplete](torrust_tracker_primitives::swarm_metadata::SwarmMetadata::incomplete)
+//! - [downloaders](torrust_tracker_primitives::swarm_metadata::SwarmMetadata::downloaders)
 //!
 //! **Query parameters**
 //!
@@ -246,7 +284,7 @@
 //! byte array like the following:
 //!
 //! ```text
-//! d5:filesd20:iiiiiiiiiiiiiiiiiiiid8:completei1e10:downloadedi2e10:incompletei3eeee
+//! d5:filesd20:iiiiiiiiiiiiiiiiiiiid8:completei1e10:downloadedi2e11:downloadersi2e10:incompletei3eeee
 //! ```
 //!
 //! And represented as a json:
@@ -257,6 +295,7 @@
 //!        "iiiiiiiiiiiiiiiiiiii": {
 //!           "complete": 1,
 //!           "downloaded": 2,
+//!           "downloaders": 2,
 //!           "incomplete": 3
 //!        }
 //!     }
@@ -275,11 +314,24 @@
 //! 00000000: 6435 3a66 696c 6573 6432 303a 6969 6969  d5:filesd20:iiii
 //! 00000010: 6969 6969 6969 6969 6969 6969 6969 6969  iiiiiiiiiiiiiiii
 //! 00000020: 6438 3a63 6f6d 706c 6574 6569 3165 3130  d8:completei1e10
-//! 00000030: 3a64 6f77 6e6c 6f61 6465 6469 3265 3130  :downloadedi2e10
-//! 00000040: 3a69 6e63 6f6d 706c 6574 6569 3365 6565  :incompletei3eee
-//! 00000050: 65                                       e
+//! 00000030: 3a64 6f77 6e6c 6f61 6465 6469 3265 3131  :downloadedi2e11
+//! 00000040: 3a64 6f77 6e6c 6f61 6465 7273 6932 6531  :downloadersi2e1
+//! 00000050: 303a 696e 636f 6d70 6c65 7465 6933 6565  0:incompletei3ee
+//! 00000060: 6565                                     ee
 //! ```
 //!
+//! **Full scrape**
+//!
+//! When the [`full_scrape`](torrust_tracker_configuration::FullScrape) is
+//! enabled, a `scrape` request without any `info_hash` param returns the
+//! scrape data for all the torrents: <http://0.0.0.0:7070/scrape>
+//!
+//! The response is generated periodically and cached, so it can be a few
+//! seconds old. If the full scrape is gzip-compressed and the client accepts
+//! the `gzip` encoding, the response is compressed and it includes the
+//! `Content-Encoding: gzip` header. In `private` mode the full scrape
+//! requires a valid authentication key.
+//!
 //! **Protocol**
 //!
 //! If you want to know more about the `scrape` request:
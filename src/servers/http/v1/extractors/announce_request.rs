@@ -127,6 +127,12 @@ mod tests {
                 event: Some(Event::Completed),
                 compact: Some(Compact::NotAccepted),
                 numwant: Some(50),
+                ip: None,
+                ipv4: None,
+                ipv6: None,
+                no_peer_id: false,
+                tracker_id: None,
+                key: None,
             }
         );
     }
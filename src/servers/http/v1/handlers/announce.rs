@@ -4,25 +4,29 @@
 //! `announce` request.
 //!
 //! The handlers perform the authentication and authorization of the request,
-//! and resolve the client IP address.
+//! and resolve the client IP address. In private mode, they also add the
+//! transfer reported by the peer to the stats of the authentication key.
 use std::net::{IpAddr, SocketAddr};
 use std::panic::Location;
 use std::sync::Arc;
+use std::time::Instant;
 
 use aquatic_udp_protocol::{AnnounceEvent, NumberOfBytes};
 use axum::extract::State;
 use axum::response::{IntoResponse, Response};
 use torrust_tracker_clock::clock::Time;
+use torrust_tracker_configuration::IntervalMinAction;
 use torrust_tracker_primitives::peer;
 
 use crate::core::auth::Key;
+use crate::core::statistics::{self, Protocol, RequestKind, RequestLatency};
 use crate::core::{AnnounceData, PeersWanted, Tracker};
 use crate::servers::http::v1::extractors::announce_request::ExtractRequest;
 use crate::servers::http::v1::extractors::authentication_key::Extract as ExtractKey;
 use crate::servers::http::v1::extractors::client_ip_sources::Extract as ExtractClientIpSources;
 use crate::servers::http::v1::handlers::common::auth;
 use crate::servers::http::v1::requests::announce::{Announce, Compact, Event};
-use crate::servers::http::v1::responses::{self};
+use crate::servers::http::v1::responses::{self, OptionalFields};
 use crate::servers::http::v1::services::peer_ip_resolver::ClientIpSources;
 use crate::servers::http::v1::services::{self, peer_ip_resolver};
 use crate::CurrentClock;
@@ -31,44 +35,68 @@ use crate::CurrentClock;
 /// authentication (no PATH `key` parameter required).
 #[allow(clippy::unused_async)]
 pub async fn handle_without_key(
-    State(tracker): State<Arc<Tracker>>,
+    State((tracker, server_socket_addr)): State<(Arc<Tracker>, SocketAddr)>,
     ExtractRequest(announce_request): ExtractRequest,
     ExtractClientIpSources(client_ip_sources): ExtractClientIpSources,
 ) -> Response {
     tracing::debug!("http announce request: {:#?}", announce_request);
 
-    handle(&tracker, &announce_request, &client_ip_sources, None).await
+    handle(&tracker, &announce_request, &client_ip_sources, &server_socket_addr, None).await
 }
 
 /// It handles the `announce` request when the HTTP tracker requires
 /// authentication (PATH `key` parameter required).
 #[allow(clippy::unused_async)]
 pub async fn handle_with_key(
-    State(tracker): State<Arc<Tracker>>,
+    State((tracker, server_socket_addr)): State<(Arc<Tracker>, SocketAddr)>,
     ExtractRequest(announce_request): ExtractRequest,
     ExtractClientIpSources(client_ip_sources): ExtractClientIpSources,
     ExtractKey(key): ExtractKey,
 ) -> Response {
     tracing::debug!("http announce request: {:#?}", announce_request);
 
-    handle(&tracker, &announce_request, &client_ip_sources, Some(key)).await
+    handle(
+        &tracker,
+        &announce_request,
+        &client_ip_sources,
+        &server_socket_addr,
+        Some(key),
+    )
+    .await
 }
 
 /// It handles the `announce` request.
 ///
 /// Internal implementation that handles both the `authenticated` and
-/// `unauthenticated` modes.
+/// `unauthenticated` modes. It also sends the time taken to handle the request
+/// to the tracker statistics.
 async fn handle(
     tracker: &Arc<Tracker>,
     announce_request: &Announce,
     client_ip_sources: &ClientIpSources,
+    server_socket_addr: &SocketAddr,
     maybe_key: Option<Key>,
 ) -> Response {
-    let announce_data = match handle_announce(tracker, announce_request, client_ip_sources, maybe_key).await {
-        Ok(announce_data) => announce_data,
-        Err(error) => return error.into_response(),
-    };
-    build_response(announce_request, announce_data)
+    let start_time = Instant::now();
+
+    let (response, request_kind) =
+        match handle_announce(tracker, announce_request, client_ip_sources, server_socket_addr, maybe_key).await {
+            Ok((announce_data, client_ip)) => (
+                build_response(tracker, announce_request, announce_data, client_ip),
+                RequestKind::Announce,
+            ),
+            Err(error) => (error.into_response(), RequestKind::Error),
+        };
+
+    tracker
+        .send_stats_latency(RequestLatency {
+            protocol: Protocol::Http,
+            kind: request_kind,
+            duration: start_time.elapsed(),
+        })
+        .await;
+
+    response
 }
 
 /* code-review: authentication, authorization and peer IP resolution could be moved
@@ -77,17 +105,26 @@ async fn handle(
    See https://github.com/torrust/torrust-tracker/discussions/240.
 */
 
+/// It returns the announce data and the IP address of the client, as seen by
+/// the tracker.
 async fn handle_announce(
     tracker: &Arc<Tracker>,
     announce_request: &Announce,
     client_ip_sources: &ClientIpSources,
+    server_socket_addr: &SocketAddr,
     maybe_key: Option<Key>,
-) -> Result<AnnounceData, responses::error::Error> {
+) -> Result<(AnnounceData, IpAddr), responses::error::Error> {
+    // Availability
+    match tracker.check_availability() {
+        Ok(()) => (),
+        Err(error) => return Err(responses::error::Error::from(error)),
+    }
+
     // Authentication
-    if tracker.requires_authentication() {
+    let authenticated_key = if tracker.requires_authentication() {
         match maybe_key {
             Some(key) => match tracker.authenticate(&key).await {
-                Ok(()) => (),
+                Ok(()) => Some(key),
                 Err(error) => return Err(responses::error::Error::from(error)),
             },
             None => {
@@ -96,54 +133,146 @@ async fn handle_announce(
                 }))
             }
         }
-    }
+    } else {
+        None
+    };
 
     // Authorization
-    match tracker.authorize(&announce_request.info_hash).await {
+    match tracker
+        .authorize(&announce_request.info_hash, authenticated_key.as_ref())
+        .await
+    {
         Ok(()) => (),
         Err(error) => return Err(responses::error::Error::from(error)),
     }
 
-    let peer_ip = match peer_ip_resolver::invoke(tracker.is_behind_reverse_proxy(), client_ip_sources) {
-        Ok(peer_ip) => peer_ip,
+    // Client filter
+    if let Err(error) = tracker.check_client(&announce_request.peer_id) {
+        return Err(responses::error::Error::from(error));
+    }
+
+    let client_ip = match peer_ip_resolver::invoke(tracker.is_behind_reverse_proxy(), client_ip_sources) {
+        Ok(client_ip) => client_ip,
         Err(error) => return Err(responses::error::Error::from(error)),
     };
 
+    // IP blocklist
+    if let Err(error) = tracker.check_ip(&client_ip) {
+        return Err(responses::error::Error::from(error));
+    }
+
+    let peer_ip = tracker.resolve_peer_ip(&client_ip, announced_ip(announce_request, &client_ip));
+
+    // The announced IP can be blocked too. Blocked `ipv4` and `ipv6`
+    // addresses are ignored when resolving the alternative address.
+    if let Err(error) = tracker.check_ip(&peer_ip) {
+        return Err(responses::error::Error::from(error));
+    }
+
     let mut peer = peer_from_request(announce_request, &peer_ip);
+    peer.alternative_addr = tracker.resolve_alternative_addr(peer.alternative_addr);
+    let announce_policy = tracker.get_announce_policy();
     let peers_wanted = match announce_request.numwant {
         Some(numwant) => PeersWanted::only(numwant),
         None => PeersWanted::All,
-    };
-
-    let announce_data = services::announce::invoke(tracker.clone(), announce_request.info_hash, &mut peer, &peers_wanted).await;
+    }
+    .at_most(announce_policy.numwant, announce_policy.numwant_max_http);
+
+    // Minimum announce interval
+    if let Err(error) = tracker.check_announce_interval(&announce_request.info_hash, &peer) {
+        tracker
+            .send_stats_event(statistics::Event::http(
+                *server_socket_addr,
+                &peer_ip,
+                RequestKind::AnnounceTooFrequent,
+            ))
+            .await;
+
+        return match announce_policy.interval_min_action {
+            IntervalMinAction::Cached => Ok((
+                tracker.get_announce_data(&announce_request.info_hash, &mut peer, &peer_ip, &peers_wanted),
+                client_ip,
+            )),
+            IntervalMinAction::Disabled | IntervalMinAction::Reject => Err(responses::error::Error::from(error)),
+        };
+    }
 
-    Ok(announce_data)
+    let announce_data = services::announce::invoke(
+        tracker.clone(),
+        announce_request.info_hash,
+        &mut peer,
+        &peers_wanted,
+        authenticated_key.as_ref(),
+        server_socket_addr,
+    )
+    .await;
+
+    Ok((announce_data, client_ip))
 }
 
-fn build_response(announce_request: &Announce, announce_data: AnnounceData) -> Response {
+fn build_response(tracker: &Tracker, announce_request: &Announce, announce_data: AnnounceData, client_ip: IpAddr) -> Response {
+    let optional_fields = OptionalFields {
+        warning_message: tracker.get_warning_message(&announce_request.info_hash),
+        tracker_id: tracker.get_tracker_id(),
+        external_ip: Some(client_ip),
+    };
+
     if announce_request.compact.as_ref().is_some_and(|f| *f == Compact::Accepted) {
-        let response: responses::Announce<responses::Compact> = announce_data.into();
-        response.into_response()
+        let response = responses::Compact::from(announce_data).with_optional_fields(optional_fields);
+        responses::Announce::new(response).into_response()
     } else {
-        let response: responses::Announce<responses::Normal> = announce_data.into();
-        response.into_response()
+        let mut response = responses::Normal::from(announce_data).with_optional_fields(optional_fields);
+        if announce_request.no_peer_id {
+            response = response.without_peer_ids();
+        }
+        responses::Announce::new(response).into_response()
     }
 }
 
+/// It returns the IP announced for the IP version the client is using to
+/// connect: the `ip` param or, when it's missing, the BEP 07 `ipv4` or `ipv6`
+/// param.
+fn announced_ip(announce_request: &Announce, client_ip: &IpAddr) -> Option<IpAddr> {
+    let same_ip_version_addr = if client_ip.is_ipv4() {
+        announce_request.ipv4
+    } else {
+        announce_request.ipv6
+    };
+
+    announce_request.ip.or(same_ip_version_addr.map(|addr| addr.ip()))
+}
+
 /// It builds a `Peer` from the announce request.
 ///
-/// It ignores the peer address in the announce request params.
+/// The `peer_ip` is the IP resolved by the tracker. The BEP 07
+/// `ipv4` or `ipv6` param for the IP version the client is not using to
+/// connect is added as the alternative address of the peer.
 #[must_use]
 fn peer_from_request(announce_request: &Announce, peer_ip: &IpAddr) -> peer::Peer {
-    peer::Peer {
+    let mut peer = peer::Peer {
         peer_id: announce_request.peer_id,
         peer_addr: SocketAddr::new(*peer_ip, announce_request.port),
+        alternative_addr: None,
+        key: announce_request.key,
         updated: CurrentClock::now(),
         uploaded: announce_request.uploaded.unwrap_or(NumberOfBytes::new(0)),
         downloaded: announce_request.downloaded.unwrap_or(NumberOfBytes::new(0)),
         left: announce_request.left.unwrap_or(NumberOfBytes::new(0)),
         event: map_to_torrust_event(&announce_request.event),
+        upload_only: announce_request.event == Some(Event::Paused),
+    };
+
+    let other_ip_version_addr = if peer_ip.is_ipv4() {
+        announce_request.ipv6
+    } else {
+        announce_request.ipv4
+    };
+
+    if let Some(addr) = other_ip_version_addr {
+        peer.set_alternative_addr(addr);
     }
+
+    peer
 }
 
 #[must_use]
@@ -153,6 +282,7 @@ pub fn map_to_aquatic_event(event: &Option<Event>) -> aquatic_udp_protocol::Anno
             Event::Started => aquatic_udp_protocol::AnnounceEvent::Started,
             Event::Stopped => aquatic_udp_protocol::AnnounceEvent::Stopped,
             Event::Completed => aquatic_udp_protocol::AnnounceEvent::Completed,
+            Event::Paused => aquatic_udp_protocol::AnnounceEvent::None,
         },
         None => aquatic_udp_protocol::AnnounceEvent::None,
     }
@@ -165,6 +295,8 @@ pub fn map_to_torrust_event(event: &Option<Event>) -> AnnounceEvent {
             Event::Started => AnnounceEvent::Started,
             Event::Stopped => AnnounceEvent::Stopped,
             Event::Completed => AnnounceEvent::Completed,
+            // Partial seeds keep announcing as regular peers (BEP 21)
+            Event::Paused => AnnounceEvent::None,
         },
         None => AnnounceEvent::None,
     }
@@ -173,6 +305,8 @@ pub fn map_to_torrust_event(event: &Option<Event>) -> AnnounceEvent {
 #[cfg(test)]
 mod tests {
 
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
     use aquatic_udp_protocol::PeerId;
     use bittorrent_primitives::info_hash::InfoHash;
     use torrust_tracker_test_helpers::configuration;
@@ -210,6 +344,12 @@ mod tests {
             event: None,
             compact: None,
             numwant: None,
+            ip: None,
+            ipv4: None,
+            ipv6: None,
+            no_peer_id: false,
+            tracker_id: None,
+            key: None,
         }
     }
 
@@ -220,6 +360,10 @@ mod tests {
         }
     }
 
+    fn sample_server_socket_addr() -> SocketAddr {
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), 7070)
+    }
+
     fn assert_error_response(error: &responses::error::Error, error_message: &str) {
         assert!(
             error.failure_reason.contains(error_message),
@@ -232,7 +376,7 @@ mod tests {
         use std::str::FromStr;
         use std::sync::Arc;
 
-        use super::{private_tracker, sample_announce_request, sample_client_ip_sources};
+        use super::{private_tracker, sample_announce_request, sample_client_ip_sources, sample_server_socket_addr};
         use crate::core::auth;
         use crate::servers::http::v1::handlers::announce::handle_announce;
         use crate::servers::http::v1::handlers::announce::tests::assert_error_response;
@@ -243,9 +387,15 @@ mod tests {
 
             let maybe_key = None;
 
-            let response = handle_announce(&tracker, &sample_announce_request(), &sample_client_ip_sources(), maybe_key)
-                .await
-                .unwrap_err();
+            let response = handle_announce(
+                &tracker,
+                &sample_announce_request(),
+                &sample_client_ip_sources(),
+                &sample_server_socket_addr(),
+                maybe_key,
+            )
+            .await
+            .unwrap_err();
 
             assert_error_response(
                 &response,
@@ -261,9 +411,15 @@ mod tests {
 
             let maybe_key = Some(unregistered_key);
 
-            let response = handle_announce(&tracker, &sample_announce_request(), &sample_client_ip_sources(), maybe_key)
-                .await
-                .unwrap_err();
+            let response = handle_announce(
+                &tracker,
+                &sample_announce_request(),
+                &sample_client_ip_sources(),
+                &sample_server_socket_addr(),
+                maybe_key,
+            )
+            .await
+            .unwrap_err();
 
             assert_error_response(&response, "Authentication error: Failed to read key");
         }
@@ -273,7 +429,7 @@ mod tests {
 
         use std::sync::Arc;
 
-        use super::{sample_announce_request, sample_client_ip_sources, whitelisted_tracker};
+        use super::{sample_announce_request, sample_client_ip_sources, sample_server_socket_addr, whitelisted_tracker};
         use crate::servers::http::v1::handlers::announce::handle_announce;
         use crate::servers::http::v1::handlers::announce::tests::assert_error_response;
 
@@ -283,9 +439,15 @@ mod tests {
 
             let announce_request = sample_announce_request();
 
-            let response = handle_announce(&tracker, &announce_request, &sample_client_ip_sources(), None)
-                .await
-                .unwrap_err();
+            let response = handle_announce(
+                &tracker,
+                &announce_request,
+                &sample_client_ip_sources(),
+                &sample_server_socket_addr(),
+                None,
+            )
+            .await
+            .unwrap_err();
 
             assert_error_response(
                 &response,
@@ -301,7 +463,7 @@ mod tests {
 
         use std::sync::Arc;
 
-        use super::{sample_announce_request, tracker_on_reverse_proxy};
+        use super::{sample_announce_request, sample_server_socket_addr, tracker_on_reverse_proxy};
         use crate::servers::http::v1::handlers::announce::handle_announce;
         use crate::servers::http::v1::handlers::announce::tests::assert_error_response;
         use crate::servers::http::v1::services::peer_ip_resolver::ClientIpSources;
@@ -315,9 +477,15 @@ mod tests {
                 connection_info_ip: None,
             };
 
-            let response = handle_announce(&tracker, &sample_announce_request(), &client_ip_sources, None)
-                .await
-                .unwrap_err();
+            let response = handle_announce(
+                &tracker,
+                &sample_announce_request(),
+                &client_ip_sources,
+                &sample_server_socket_addr(),
+                None,
+            )
+            .await
+            .unwrap_err();
 
             assert_error_response(
                 &response,
@@ -330,7 +498,7 @@ mod tests {
 
         use std::sync::Arc;
 
-        use super::{sample_announce_request, tracker_not_on_reverse_proxy};
+        use super::{sample_announce_request, sample_server_socket_addr, tracker_not_on_reverse_proxy};
         use crate::servers::http::v1::handlers::announce::handle_announce;
         use crate::servers::http::v1::handlers::announce::tests::assert_error_response;
         use crate::servers::http::v1::services::peer_ip_resolver::ClientIpSources;
@@ -344,9 +512,15 @@ mod tests {
                 connection_info_ip: None,
             };
 
-            let response = handle_announce(&tracker, &sample_announce_request(), &client_ip_sources, None)
-                .await
-                .unwrap_err();
+            let response = handle_announce(
+                &tracker,
+                &sample_announce_request(),
+                &client_ip_sources,
+                &sample_server_socket_addr(),
+                None,
+            )
+            .await
+            .unwrap_err();
 
             assert_error_response(
                 &response,
@@ -12,8 +12,14 @@ pub mod scrape;
 
 impl From<Error> for responses::error::Error {
     fn from(err: Error) -> Self {
+        let retry_in = match err {
+            Error::TrackerInMaintenance { retry_in, .. } => Some(retry_in),
+            _ => None,
+        };
+
         responses::error::Error {
             failure_reason: format!("Tracker error: {err}"),
+            retry_in,
         }
     }
 }
@@ -5,17 +5,25 @@
 //!
 //! The handlers perform the authentication and authorization of the request,
 //! and resolve the client IP address.
+use std::net::SocketAddr;
+use std::panic::Location;
 use std::sync::Arc;
+use std::time::Instant;
 
-use axum::extract::State;
+use axum::extract::{RawQuery, State};
+use axum::http::HeaderMap;
 use axum::response::{IntoResponse, Response};
 
 use crate::core::auth::Key;
+use crate::core::full_scrape::FullScrape;
+use crate::core::statistics::{Protocol, RequestKind, RequestLatency};
 use crate::core::{ScrapeData, Tracker};
+use crate::servers::content_encoding::accepts_gzip;
 use crate::servers::http::v1::extractors::authentication_key::Extract as ExtractKey;
 use crate::servers::http::v1::extractors::client_ip_sources::Extract as ExtractClientIpSources;
 use crate::servers::http::v1::extractors::scrape_request::ExtractRequest;
-use crate::servers::http::v1::requests::scrape::Scrape;
+use crate::servers::http::v1::handlers::common::auth;
+use crate::servers::http::v1::requests::scrape::{is_full_scrape, Scrape};
 use crate::servers::http::v1::services::peer_ip_resolver::{self, ClientIpSources};
 use crate::servers::http::v1::{responses, services};
 
@@ -23,13 +31,35 @@ use crate::servers::http::v1::{responses, services};
 /// to run in `public` mode.
 #[allow(clippy::unused_async)]
 pub async fn handle_without_key(
-    State(tracker): State<Arc<Tracker>>,
-    ExtractRequest(scrape_request): ExtractRequest,
+    State((tracker, server_socket_addr)): State<(Arc<Tracker>, SocketAddr)>,
+    maybe_scrape_request: Result<ExtractRequest, Response>,
+    RawQuery(raw_query): RawQuery,
+    headers: HeaderMap,
     ExtractClientIpSources(client_ip_sources): ExtractClientIpSources,
 ) -> Response {
+    let scrape_request = match maybe_scrape_request {
+        Ok(ExtractRequest(scrape_request)) => scrape_request,
+        Err(rejection) => {
+            if tracker.is_full_scrape_enabled() && is_full_scrape(raw_query.as_deref()) {
+                tracing::debug!("http full scrape request");
+
+                return handle_full(
+                    &tracker,
+                    &client_ip_sources,
+                    &server_socket_addr,
+                    None,
+                    accepts_gzip(&headers),
+                )
+                .await;
+            }
+
+            return rejection;
+        }
+    };
+
     tracing::debug!("http scrape request: {:#?}", &scrape_request);
 
-    handle(&tracker, &scrape_request, &client_ip_sources, None).await
+    handle(&tracker, &scrape_request, &client_ip_sources, &server_socket_addr, None).await
 }
 
 /// It handles the `scrape` request when the HTTP tracker is configured
@@ -38,27 +68,88 @@ pub async fn handle_without_key(
 /// In this case, the authentication `key` parameter is required.
 #[allow(clippy::unused_async)]
 pub async fn handle_with_key(
-    State(tracker): State<Arc<Tracker>>,
-    ExtractRequest(scrape_request): ExtractRequest,
+    State((tracker, server_socket_addr)): State<(Arc<Tracker>, SocketAddr)>,
+    maybe_scrape_request: Result<ExtractRequest, Response>,
+    RawQuery(raw_query): RawQuery,
+    headers: HeaderMap,
     ExtractClientIpSources(client_ip_sources): ExtractClientIpSources,
     ExtractKey(key): ExtractKey,
 ) -> Response {
+    let scrape_request = match maybe_scrape_request {
+        Ok(ExtractRequest(scrape_request)) => scrape_request,
+        Err(rejection) => {
+            if tracker.is_full_scrape_enabled() && is_full_scrape(raw_query.as_deref()) {
+                tracing::debug!("http full scrape request");
+
+                return handle_full(
+                    &tracker,
+                    &client_ip_sources,
+                    &server_socket_addr,
+                    Some(key),
+                    accepts_gzip(&headers),
+                )
+                .await;
+            }
+
+            return rejection;
+        }
+    };
+
     tracing::debug!("http scrape request: {:#?}", &scrape_request);
 
-    handle(&tracker, &scrape_request, &client_ip_sources, Some(key)).await
+    handle(&tracker, &scrape_request, &client_ip_sources, &server_socket_addr, Some(key)).await
 }
 
 async fn handle(
     tracker: &Arc<Tracker>,
     scrape_request: &Scrape,
     client_ip_sources: &ClientIpSources,
+    server_socket_addr: &SocketAddr,
     maybe_key: Option<Key>,
 ) -> Response {
-    let scrape_data = match handle_scrape(tracker, scrape_request, client_ip_sources, maybe_key).await {
-        Ok(scrape_data) => scrape_data,
-        Err(error) => return error.into_response(),
+    let start_time = Instant::now();
+
+    let (response, request_kind) =
+        match handle_scrape(tracker, scrape_request, client_ip_sources, server_socket_addr, maybe_key).await {
+            Ok(scrape_data) => (build_response(scrape_data), RequestKind::Scrape),
+            Err(error) => (error.into_response(), RequestKind::Error),
+        };
+
+    send_stats_latency(tracker, request_kind, start_time).await;
+
+    response
+}
+
+async fn handle_full(
+    tracker: &Arc<Tracker>,
+    client_ip_sources: &ClientIpSources,
+    server_socket_addr: &SocketAddr,
+    maybe_key: Option<Key>,
+    accepts_gzip: bool,
+) -> Response {
+    let start_time = Instant::now();
+
+    let (response, request_kind) = match handle_full_scrape(tracker, client_ip_sources, server_socket_addr, maybe_key).await {
+        Ok(full_scrape) => (
+            responses::scrape::full_scrape_response(&full_scrape, accepts_gzip),
+            RequestKind::Scrape,
+        ),
+        Err(error) => (error.into_response(), RequestKind::Error),
     };
-    build_response(scrape_data)
+
+    send_stats_latency(tracker, request_kind, start_time).await;
+
+    response
+}
+
+async fn send_stats_latency(tracker: &Arc<Tracker>, request_kind: RequestKind, start_time: Instant) {
+    tracker
+        .send_stats_latency(RequestLatency {
+            protocol: Protocol::Http,
+            kind: request_kind,
+            duration: start_time.elapsed(),
+        })
+        .await;
 }
 
 /* code-review: authentication, authorization and peer IP resolution could be moved
@@ -71,33 +162,107 @@ async fn handle_scrape(
     tracker: &Arc<Tracker>,
     scrape_request: &Scrape,
     client_ip_sources: &ClientIpSources,
+    server_socket_addr: &SocketAddr,
     maybe_key: Option<Key>,
 ) -> Result<ScrapeData, responses::error::Error> {
+    // Availability
+    match tracker.check_availability() {
+        Ok(()) => (),
+        Err(error) => return Err(responses::error::Error::from(error)),
+    }
+
     // Authentication
-    let return_real_scrape_data = if tracker.requires_authentication() {
+    let (return_real_scrape_data, authenticated_key) = if tracker.requires_authentication() {
         match maybe_key {
             Some(key) => match tracker.authenticate(&key).await {
-                Ok(()) => true,
-                Err(_error) => false,
+                Ok(()) => (true, Some(key)),
+                Err(_error) => (false, None),
             },
-            None => false,
+            None => (false, None),
         }
     } else {
-        true
+        (true, None)
     };
 
     // Authorization for scrape requests is handled at the `Tracker` level
-    // for each torrent.
+    // for each torrent, including the allow-list of the key.
 
     let peer_ip = match peer_ip_resolver::invoke(tracker.is_behind_reverse_proxy(), client_ip_sources) {
         Ok(peer_ip) => peer_ip,
         Err(error) => return Err(responses::error::Error::from(error)),
     };
 
+    // IP blocklist
+    if let Err(error) = tracker.check_ip(&peer_ip) {
+        return Err(responses::error::Error::from(error));
+    }
+
     if return_real_scrape_data {
-        Ok(services::scrape::invoke(tracker, &scrape_request.info_hashes, &peer_ip).await)
+        Ok(services::scrape::invoke(
+            tracker,
+            &scrape_request.info_hashes,
+            authenticated_key.as_ref(),
+            &peer_ip,
+            server_socket_addr,
+        )
+        .await)
     } else {
-        Ok(services::scrape::fake(tracker, &scrape_request.info_hashes, &peer_ip).await)
+        Ok(services::scrape::fake(tracker, &scrape_request.info_hashes, &peer_ip, server_socket_addr).await)
+    }
+}
+
+async fn handle_full_scrape(
+    tracker: &Arc<Tracker>,
+    client_ip_sources: &ClientIpSources,
+    server_socket_addr: &SocketAddr,
+    maybe_key: Option<Key>,
+) -> Result<Arc<FullScrape>, responses::error::Error> {
+    // Availability
+    match tracker.check_availability() {
+        Ok(()) => (),
+        Err(error) => return Err(responses::error::Error::from(error)),
+    }
+
+    // Authentication. Unlike the normal scrape, there are no fake data for
+    // the full scrape.
+    if tracker.requires_authentication() {
+        match maybe_key {
+            Some(key) => match tracker.authenticate(&key).await {
+                // Authorization. The cached full scrape includes all the
+                // torrents, so it's not available for restricted keys.
+                Ok(()) if tracker.get_key_allow_list(&key).is_some() => {
+                    return Err(responses::error::Error {
+                        failure_reason: "Full scrape is not available for keys restricted to some torrents".to_string(),
+                        retry_in: None,
+                    })
+                }
+                Ok(()) => (),
+                Err(error) => return Err(responses::error::Error::from(error)),
+            },
+            None => {
+                return Err(responses::error::Error::from(auth::Error::MissingAuthKey {
+                    location: Location::caller(),
+                }))
+            }
+        }
+    }
+
+    let peer_ip = match peer_ip_resolver::invoke(tracker.is_behind_reverse_proxy(), client_ip_sources) {
+        Ok(peer_ip) => peer_ip,
+        Err(error) => return Err(responses::error::Error::from(error)),
+    };
+
+    // IP blocklist
+    if let Err(error) = tracker.check_ip(&peer_ip) {
+        return Err(responses::error::Error::from(error));
+    }
+
+    match services::scrape::full(tracker, &peer_ip, server_socket_addr).await {
+        Some(full_scrape) => Ok(full_scrape),
+        None => Err(responses::error::Error {
+            failure_reason: "Full scrape is not available yet".to_string(),
+            retry_in: None,
+        }),
     }
 }
 
@@ -107,7 +272,7 @@ fn build_response(scrape_data: ScrapeData) -> Response {
 
 #[cfg(test)]
 mod tests {
-    use std::net::IpAddr;
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
     use std::str::FromStr;
 
     use bittorrent_primitives::info_hash::InfoHash;
@@ -148,6 +313,10 @@ mod tests {
         }
     }
 
+    fn sample_server_socket_addr() -> SocketAddr {
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), 7070)
+    }
+
     fn assert_error_response(error: &responses::error::Error, error_message: &str) {
         assert!(
             error.failure_reason.contains(error_message),
@@ -159,7 +328,7 @@ mod tests {
         use std::str::FromStr;
         use std::sync::Arc;
 
-        use super::{private_tracker, sample_client_ip_sources, sample_scrape_request};
+        use super::{private_tracker, sample_client_ip_sources, sample_scrape_request, sample_server_socket_addr};
         use crate::core::{auth, ScrapeData};
         use crate::servers::http::v1::handlers::scrape::handle_scrape;
 
@@ -170,9 +339,15 @@ mod tests {
             let scrape_request = sample_scrape_request();
             let maybe_key = None;
 
-            let scrape_data = handle_scrape(&tracker, &scrape_request, &sample_client_ip_sources(), maybe_key)
-                .await
-                .unwrap();
+            let scrape_data = handle_scrape(
+                &tracker,
+                &scrape_request,
+                &sample_client_ip_sources(),
+                &sample_server_socket_addr(),
+                maybe_key,
+            )
+            .await
+            .unwrap();
 
             let expected_scrape_data = ScrapeData::zeroed(&scrape_request.info_hashes);
 
@@ -187,9 +362,15 @@ mod tests {
             let unregistered_key = auth::Key::from_str("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ").unwrap();
             let maybe_key = Some(unregistered_key);
 
-            let scrape_data = handle_scrape(&tracker, &scrape_request, &sample_client_ip_sources(), maybe_key)
-                .await
-                .unwrap();
+            let scrape_data = handle_scrape(
+                &tracker,
+                &scrape_request,
+                &sample_client_ip_sources(),
+                &sample_server_socket_addr(),
+                maybe_key,
+            )
+            .await
+            .unwrap();
 
             let expected_scrape_data = ScrapeData::zeroed(&scrape_request.info_hashes);
 
@@ -201,7 +382,7 @@ mod tests {
 
         use std::sync::Arc;
 
-        use super::{sample_client_ip_sources, sample_scrape_request, whitelisted_tracker};
+        use super::{sample_client_ip_sources, sample_scrape_request, sample_server_socket_addr, whitelisted_tracker};
         use crate::core::ScrapeData;
         use crate::servers::http::v1::handlers::scrape::handle_scrape;
 
@@ -211,9 +392,15 @@ mod tests {
 
             let scrape_request = sample_scrape_request();
 
-            let scrape_data = handle_scrape(&tracker, &scrape_request, &sample_client_ip_sources(), None)
-                .await
-                .unwrap();
+            let scrape_data = handle_scrape(
+                &tracker,
+                &scrape_request,
+                &sample_client_ip_sources(),
+                &sample_server_socket_addr(),
+                None,
+            )
+            .await
+            .unwrap();
 
             let expected_scrape_data = ScrapeData::zeroed(&scrape_request.info_hashes);
 
@@ -224,7 +411,7 @@ mod tests {
     mod with_tracker_on_reverse_proxy {
         use std::sync::Arc;
 
-        use super::{sample_scrape_request, tracker_on_reverse_proxy};
+        use super::{sample_scrape_request, sample_server_socket_addr, tracker_on_reverse_proxy};
         use crate::servers::http::v1::handlers::scrape::handle_scrape;
         use crate::servers::http::v1::handlers::scrape::tests::assert_error_response;
         use crate::servers::http::v1::services::peer_ip_resolver::ClientIpSources;
@@ -238,9 +425,15 @@ mod tests {
                 connection_info_ip: None,
             };
 
-            let response = handle_scrape(&tracker, &sample_scrape_request(), &client_ip_sources, None)
-                .await
-                .unwrap_err();
+            let response = handle_scrape(
+                &tracker,
+                &sample_scrape_request(),
+                &client_ip_sources,
+                &sample_server_socket_addr(),
+                None,
+            )
+            .await
+            .unwrap_err();
 
             assert_error_response(
                 &response,
@@ -252,7 +445,7 @@ mod tests {
     mod with_tracker_not_on_reverse_proxy {
         use std::sync::Arc;
 
-        use super::{sample_scrape_request, tracker_not_on_reverse_proxy};
+        use super::{sample_scrape_request, sample_server_socket_addr, tracker_not_on_reverse_proxy};
         use crate::servers::http::v1::handlers::scrape::handle_scrape;
         use crate::servers::http::v1::handlers::scrape::tests::assert_error_response;
         use crate::servers::http::v1::services::peer_ip_resolver::ClientIpSources;
@@ -266,13 +459,59 @@ mod tests {
                 connection_info_ip: None,
             };
 
-            let response = handle_scrape(&tracker, &sample_scrape_request(), &client_ip_sources, None)
+            let response = handle_scrape(
+                &tracker,
+                &sample_scrape_request(),
+                &client_ip_sources,
+                &sample_server_socket_addr(),
+                None,
+            )
+            .await
+            .unwrap_err();
+
+            assert_error_response(
+                &response,
+                "Error resolving peer IP: cannot get the client IP from the connection info",
+            );
+        }
+    }
+
+    mod with_full_scrape_enabled {
+        use std::sync::Arc;
+
+        use torrust_tracker_configuration::FullScrape;
+        use torrust_tracker_test_helpers::configuration;
+
+        use super::{assert_error_response, sample_client_ip_sources, sample_server_socket_addr};
+        use crate::core::services::tracker_factory;
+        use crate::servers::http::v1::handlers::scrape::handle_full_scrape;
+
+        #[tokio::test]
+        async fn it_should_fail_when_the_full_scrape_has_not_been_generated_yet() {
+            let mut configuration = configuration::ephemeral_public();
+            configuration.core.full_scrape = Some(FullScrape::default());
+            let tracker = Arc::new(tracker_factory(&configuration));
+
+            let response = handle_full_scrape(&tracker, &sample_client_ip_sources(), &sample_server_socket_addr(), None)
+                .await
+                .unwrap_err();
+
+            assert_error_response(&response, "Full scrape is not available yet");
+        }
+
+        #[tokio::test]
+        async fn it_should_fail_when_the_authentication_key_is_missing_in_private_mode() {
+            let mut configuration = configuration::ephemeral_private();
+            configuration.core.full_scrape = Some(FullScrape::default());
+            let tracker = Arc::new(tracker_factory(&configuration));
+
+            let response = handle_full_scrape(&tracker, &sample_client_ip_sources(), &sample_server_socket_addr(), None)
                 .await
                 .unwrap_err();
 
             assert_error_response(
                 &response,
-                "Error resolving peer IP: cannot get the client IP from the connection info",
+                "Authentication error: Missing authentication key param for private tracker",
             );
         }
     }
@@ -2,12 +2,14 @@
 //!
 //! Data structures and logic for parsing the `announce` request.
 use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
 use std::panic::Location;
 use std::str::FromStr;
 
-use aquatic_udp_protocol::{NumberOfBytes, PeerId};
+use aquatic_udp_protocol::{NumberOfBytes, PeerId, PeerKey};
 use bittorrent_http_protocol::percent_encoding::{percent_decode_info_hash, percent_decode_peer_id};
 use bittorrent_primitives::info_hash::{self, InfoHash};
+use percent_encoding::percent_decode_str;
 use thiserror::Error;
 use torrust_tracker_located_error::{Located, LocatedError};
 use torrust_tracker_primitives::peer;
@@ -25,6 +27,12 @@ const LEFT: &str = "left";
 const EVENT: &str = "event";
 const COMPACT: &str = "compact";
 const NUMWANT: &str = "numwant";
+const IP: &str = "ip";
+const IPV4: &str = "ipv4";
+const IPV6: &str = "ipv6";
+const NO_PEER_ID: &str = "no_peer_id";
+const TRACKER_ID: &str = "trackerid";
+const KEY: &str = "key";
 
 /// The `Announce` request. Fields use the domain types after parsing the
 /// query params of the request.
@@ -45,7 +53,13 @@ const NUMWANT: &str = "numwant";
 ///     left: Some(NumberOfBytes::new(1)),
 ///     event: Some(Event::Started),
 ///     compact: Some(Compact::NotAccepted),
-///     numwant: Some(50)
+///     numwant: Some(50),
+///     ip: None,
+///     ipv4: None,
+///     ipv6: None,
+///     no_peer_id: false,
+///     tracker_id: None,
+///     key: None,
 /// };
 /// ```
 ///
@@ -53,9 +67,10 @@ const NUMWANT: &str = "numwant";
 /// > specifies that only the peer `IP` and `event`are optional. However, the
 /// > tracker defines default values for some of the mandatory params.
 ///
-/// > **NOTICE**: The struct does not contain the `IP` of the peer. It's not
-/// > mandatory and it's not used by the tracker. The `IP` is obtained from the
-/// > request itself.
+/// > **NOTICE**: The `IP` of the peer is obtained from the request itself. The
+/// > `ip` param is only used for clients in the trusted networks. The `ipv4`
+/// > and `ipv6` params are only used to add the address for the other IP
+/// > version to dual-stack peers.
 #[derive(Debug, PartialEq)]
 pub struct Announce {
     // Mandatory params
@@ -78,8 +93,8 @@ pub struct Announce {
     /// The number of bytes left to download by the peer.
     pub left: Option<NumberOfBytes>,
 
-    /// The event that the peer is reporting. It can be `Started`, `Stopped` or
-    /// `Completed`.
+    /// The event that the peer is reporting. It can be `Started`, `Stopped`,
+    /// `Completed` or `Paused`.
     pub event: Option<Event>,
 
     /// Whether the response should be in compact mode or not.
@@ -88,13 +103,50 @@ pub struct Announce {
     /// Number of peers that the client would receive from the tracker. The
     /// value is permitted to be zero.
     pub numwant: Option<u32>,
+
+    /// The IP address of the peer. It's only used when the client sending the
+    /// request is in one of the [`trusted_networks`](torrust_tracker_configuration::v2_0_0::network::Network::trusted_networks).
+    ///
+    /// The param can also contain a DNS name. Values that are not IP addresses
+    /// are ignored.
+    pub ip: Option<IpAddr>,
+
+    /// The `IPv4` address of a dual-stack peer. The `port` param is used
+    /// when the value does not include the port. When the client connects
+    /// with `IPv4` and there is no `ip` param, it's handled like the `ip`
+    /// param.
+    ///
+    /// Refer to [BEP 07. IPv6 Tracker Extension](https://www.bittorrent.org/beps/bep_0007.html).
+    pub ipv4: Option<SocketAddr>,
+
+    /// The `IPv6` address of a dual-stack peer. The `port` param is used
+    /// when the value does not include the port. When the client connects
+    /// with `IPv6` and there is no `ip` param, it's handled like the `ip`
+    /// param.
+    ///
+    /// Refer to [BEP 07. IPv6 Tracker Extension](https://www.bittorrent.org/beps/bep_0007.html).
+    pub ipv6: Option<SocketAddr>,
+
+    /// Whether the tracker can omit the peer IDs in the non-compact response.
+    /// It's `false` when the param is not present.
+    pub no_peer_id: bool,
+
+    /// The `tracker id` returned by the tracker in a previous announce
+    /// response. Clients send it back in the next announce requests.
+    pub tracker_id: Option<String>,
+
+    /// A value that is not shared with other peers. It allows the tracker to
+    /// verify the identity of the peer when its IP changes.
+    ///
+    /// Clients usually send 8 hexadecimal digits. Other values are hashed.
+    pub key: Option<PeerKey>,
 }
 
 /// Errors that can occur when parsing the `Announce` request.
 ///
 /// The `info_hash` and `peer_id` query params are special because they contain
-/// binary data. The `info_hash` is a 20-byte SHA1 hash and the `peer_id` is a
-/// 20-byte array.
+/// binary data. The `info_hash` is a 20-byte SHA1 hash (or a 32-byte SHA-256
+/// hash for `BitTorrent` v2 torrents) and the `peer_id` is a 20-byte array.
 #[derive(Error, Debug)]
 pub enum ParseAnnounceQueryError {
     /// A mandatory param is missing.
@@ -135,12 +187,14 @@ pub enum ParseAnnounceQueryError {
     },
 }
 
-/// The event that the peer is reporting: `started`, `completed` or `stopped`.
+/// The event that the peer is reporting: `started`, `completed`, `stopped` or
+/// `paused`.
 ///
 /// If the event is not present or empty that means that the peer is just
 /// updating its status. It's one of the announcements done at regular intervals.
 ///
 /// Refer to [BEP 03. The `BitTorrent Protocol` Specification](https://www.bittorrent.org/beps/bep_0003.html)
+/// and [BEP 21. Extension for partial seeds](https://www.bittorrent.org/beps/bep_0021.html)
 /// for more information.
 #[derive(PartialEq, Debug)]
 pub enum Event {
@@ -151,6 +205,9 @@ pub enum Event {
     /// Event sent when the download is complete.
     /// No `completed` is sent if the file was complete when started
     Completed,
+    /// Event sent when a partial seed does not want to download more pieces.
+    /// The peer keeps uploading the pieces it has.
+    Paused,
 }
 
 impl FromStr for Event {
@@ -161,6 +218,7 @@ impl FromStr for Event {
             "started" => Ok(Self::Started),
             "stopped" => Ok(Self::Stopped),
             "completed" => Ok(Self::Completed),
+            "paused" => Ok(Self::Paused),
             _ => Err(ParseAnnounceQueryError::InvalidParam {
                 param_name: EVENT.to_owned(),
                 param_value: raw_param.to_owned(),
@@ -176,6 +234,7 @@ impl fmt::Display for Event {
             Event::Started => write!(f, "started"),
             Event::Stopped => write!(f, "stopped"),
             Event::Completed => write!(f, "completed"),
+            Event::Paused => write!(f, "paused"),
         }
     }
 }
@@ -227,6 +286,7 @@ impl From<ParseQueryError> for responses::error::Error {
     fn from(err: ParseQueryError) -> Self {
         responses::error::Error {
             failure_reason: format!("Bad request. Cannot parse query params: {err}"),
+            retry_in: None,
         }
     }
 }
@@ -235,6 +295,7 @@ impl From<ParseAnnounceQueryError> for responses::error::Error {
     fn from(err: ParseAnnounceQueryError) -> Self {
         responses::error::Error {
             failure_reason: format!("Bad request. Cannot parse query params for announce request: {err}"),
+            retry_in: None,
         }
     }
 }
@@ -243,16 +304,24 @@ impl TryFrom<Query> for Announce {
     type Error = ParseAnnounceQueryError;
 
     fn try_from(query: Query) -> Result<Self, Self::Error> {
+        let port = extract_port(&query)?;
+
         Ok(Self {
             info_hash: extract_info_hash(&query)?,
             peer_id: extract_peer_id(&query)?,
-            port: extract_port(&query)?,
+            port,
             downloaded: extract_downloaded(&query)?,
             uploaded: extract_uploaded(&query)?,
             left: extract_left(&query)?,
             event: extract_event(&query)?,
             compact: extract_compact(&query)?,
             numwant: extract_numwant(&query)?,
+            ip: extract_ip(&query),
+            ipv4: extract_ipv4(&query, port)?,
+            ipv6: extract_ipv6(&query, port)?,
+            no_peer_id: extract_no_peer_id(&query)?,
+            tracker_id: extract_tracker_id(&query),
+            key: extract_key(&query),
         })
     }
 }
@@ -373,17 +442,118 @@ fn extract_numwant(query: &Query) -> Result<Option<u32>, ParseAnnounceQueryError
     }
 }
 
+fn extract_ip(query: &Query) -> Option<IpAddr> {
+    query
+        .get_param(IP)
+        .and_then(|raw_param| percent_decode_str(&raw_param).decode_utf8_lossy().parse::<IpAddr>().ok())
+}
+
+fn extract_ipv4(query: &Query, port: u16) -> Result<Option<SocketAddr>, ParseAnnounceQueryError> {
+    match query.get_param(IPV4) {
+        Some(raw_param) => {
+            let value = percent_decode_str(&raw_param).decode_utf8_lossy();
+
+            let addr = match value.parse::<SocketAddrV4>() {
+                Ok(addr) => addr,
+                Err(_) => SocketAddrV4::new(
+                    value
+                        .parse::<Ipv4Addr>()
+                        .map_err(|_e| ParseAnnounceQueryError::InvalidParam {
+                            param_name: IPV4.to_owned(),
+                            param_value: raw_param.clone(),
+                            location: Location::caller(),
+                        })?,
+                    port,
+                ),
+            };
+
+            Ok(Some(SocketAddr::V4(addr)))
+        }
+        None => Ok(None),
+    }
+}
+
+fn extract_ipv6(query: &Query, port: u16) -> Result<Option<SocketAddr>, ParseAnnounceQueryError> {
+    match query.get_param(IPV6) {
+        Some(raw_param) => {
+            let value = percent_decode_str(&raw_param).decode_utf8_lossy();
+
+            let addr = match value.parse::<SocketAddrV6>() {
+                Ok(addr) => addr,
+                Err(_) => SocketAddrV6::new(
+                    value
+                        .parse::<Ipv6Addr>()
+                        .map_err(|_e| ParseAnnounceQueryError::InvalidParam {
+                            param_name: IPV6.to_owned(),
+                            param_value: raw_param.clone(),
+                            location: Location::caller(),
+                        })?,
+                    port,
+                    0,
+                    0,
+                ),
+            };
+
+            Ok(Some(SocketAddr::V6(addr)))
+        }
+        None => Ok(None),
+    }
+}
+
+fn extract_no_peer_id(query: &Query) -> Result<bool, ParseAnnounceQueryError> {
+    match query.get_param(NO_PEER_ID) {
+        Some(raw_param) => match raw_param.as_str() {
+            "1" => Ok(true),
+            "0" => Ok(false),
+            _ => Err(ParseAnnounceQueryError::InvalidParam {
+                param_name: NO_PEER_ID.to_owned(),
+                param_value: raw_param.clone(),
+                location: Location::caller(),
+            }),
+        },
+        None => Ok(false),
+    }
+}
+
+fn extract_tracker_id(query: &Query) -> Option<String> {
+    query
+        .get_param(TRACKER_ID)
+        .map(|raw_param| percent_decode_str(&raw_param).decode_utf8_lossy().to_string())
+}
+
+fn extract_key(query: &Query) -> Option<PeerKey> {
+    query.get_param(KEY).map(|raw_param| {
+        let value: Vec<u8> = percent_decode_str(&raw_param).collect();
+
+        let key = match std::str::from_utf8(&value) {
+            Ok(hex) if hex.len() <= 8 => u32::from_str_radix(hex, 16).unwrap_or_else(|_| fnv1a(&value)),
+            _ => fnv1a(&value),
+        };
+
+        PeerKey::new(i32::from_ne_bytes(key.to_ne_bytes()))
+    })
+}
+
+/// 32-bit FNV-1a hash. It's used to convert the keys that are not
+/// hexadecimal numbers into a stable number.
+fn fnv1a(bytes: &[u8]) -> u32 {
+    bytes
+        .iter()
+        .fold(0x811c_9dc5, |hash, byte| (hash ^ u32::from(*byte)).wrapping_mul(0x0100_0193))
+}
+
 #[cfg(test)]
 mod tests {
 
     mod announce_request {
 
-        use aquatic_udp_protocol::{NumberOfBytes, PeerId};
+        use aquatic_udp_protocol::{NumberOfBytes, PeerId, PeerKey};
         use bittorrent_primitives::info_hash::InfoHash;
 
         use crate::servers::http::v1::query::Query;
         use crate::servers::http::v1::requests::announce::{
-            Announce, Compact, Event, COMPACT, DOWNLOADED, EVENT, INFO_HASH, LEFT, NUMWANT, PEER_ID, PORT, UPLOADED,
+            Announce, Compact, Event, COMPACT, DOWNLOADED, EVENT, INFO_HASH, IP, IPV4, IPV6, KEY, LEFT, NO_PEER_ID, NUMWANT,
+            PEER_ID, PORT, TRACKER_ID, UPLOADED,
         };
 
         #[test]
@@ -411,6 +581,12 @@ mod tests {
                     event: None,
                     compact: None,
                     numwant: None,
+                    ip: None,
+                    ipv4: None,
+                    ipv6: None,
+                    no_peer_id: false,
+                    tracker_id: None,
+                    key: None,
                 }
             );
         }
@@ -427,6 +603,10 @@ mod tests {
                 (EVENT, "started"),
                 (COMPACT, "0"),
                 (NUMWANT, "50"),
+                (IP, "126.0.0.1"),
+                (NO_PEER_ID, "1"),
+                (TRACKER_ID, "torrust"),
+                (KEY, "1A2B3C4D"),
             ])
             .to_string();
 
@@ -446,15 +626,117 @@ mod tests {
                     event: Some(Event::Started),
                     compact: Some(Compact::NotAccepted),
                     numwant: Some(50),
+                    ip: Some("126.0.0.1".parse().unwrap()),
+                    ipv4: None,
+                    ipv6: None,
+                    no_peer_id: true,
+                    tracker_id: Some("torrust".to_string()),
+                    key: Some(PeerKey::new(0x1a2b_3c4d)),
                 }
             );
         }
 
+        #[test]
+        fn should_be_instantiated_with_the_addresses_of_a_dual_stack_peer() {
+            let raw_query = Query::from(vec![
+                (INFO_HASH, "%3B%24U%04%CF%5F%11%BB%DB%E1%20%1C%EAjk%F4Z%EE%1B%C0"),
+                (PEER_ID, "-qB00000000000000001"),
+                (PORT, "17548"),
+                (IPV4, "126.0.0.1"),
+                (IPV6, "%5B2001%3Adb8%3A%3A1%5D%3A17549"),
+            ])
+            .to_string();
+
+            let query = raw_query.parse::<Query>().unwrap();
+
+            let announce_request = Announce::try_from(query).unwrap();
+
+            assert_eq!(announce_request.ipv4, Some("126.0.0.1:17548".parse().unwrap()));
+            assert_eq!(announce_request.ipv6, Some("[2001:db8::1]:17549".parse().unwrap()));
+        }
+
+        #[test]
+        fn should_hash_a_key_param_that_is_not_a_hexadecimal_number() {
+            let announce_request_with_key = |key: &str| {
+                let raw_query = Query::from(vec![
+                    (INFO_HASH, "%3B%24U%04%CF%5F%11%BB%DB%E1%20%1C%EAjk%F4Z%EE%1B%C0"),
+                    (PEER_ID, "-qB00000000000000001"),
+                    (PORT, "17548"),
+                    (KEY, key),
+                ])
+                .to_string();
+
+                Announce::try_from(raw_query.parse::<Query>().unwrap()).unwrap()
+            };
+
+            let key = announce_request_with_key("a-secret-key").key;
+
+            assert!(key.is_some());
+            assert_eq!(key, announce_request_with_key("a-secret-key").key);
+            assert_ne!(key, announce_request_with_key("another-secret-key").key);
+        }
+
+        #[test]
+        fn should_accept_the_paused_event_sent_by_partial_seeds() {
+            let raw_query = Query::from(vec![
+                (INFO_HASH, "%3B%24U%04%CF%5F%11%BB%DB%E1%20%1C%EAjk%F4Z%EE%1B%C0"),
+                (PEER_ID, "-qB00000000000000001"),
+                (PORT, "17548"),
+                (EVENT, "paused"),
+            ])
+            .to_string();
+
+            let query = raw_query.parse::<Query>().unwrap();
+
+            let announce_request = Announce::try_from(query).unwrap();
+
+            assert_eq!(announce_request.event, Some(Event::Paused));
+        }
+
+        #[test]
+        fn should_ignore_an_ip_param_that_is_not_an_ip_address() {
+            let raw_query = Query::from(vec![
+                (INFO_HASH, "%3B%24U%04%CF%5F%11%BB%DB%E1%20%1C%EAjk%F4Z%EE%1B%C0"),
+                (PEER_ID, "-qB00000000000000001"),
+                (PORT, "17548"),
+                (IP, "peer.example.com"),
+            ])
+            .to_string();
+
+            let query = raw_query.parse::<Query>().unwrap();
+
+            let announce_request = Announce::try_from(query).unwrap();
+
+            assert_eq!(announce_request.ip, None);
+        }
+
+        #[test]
+        fn should_truncate_a_bittorrent_v2_info_hash_to_20_bytes() {
+            let raw_query = Query::from(vec![
+                (
+                    INFO_HASH,
+                    "%3B%24U%04%CF%5F%11%BB%DB%E1%20%1C%EAjk%F4Z%EE%1B%C0%B8%A1%A4%D5%8D%14%D7%A2%1B%7Dz%45",
+                ),
+                (PEER_ID, "-qB00000000000000001"),
+                (PORT, "17548"),
+            ])
+            .to_string();
+
+            let query = raw_query.parse::<Query>().unwrap();
+
+            let announce_request = Announce::try_from(query).unwrap();
+
+            assert_eq!(
+                announce_request.info_hash,
+                "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".parse::<InfoHash>().unwrap()
+            );
+        }
+
         mod when_it_is_instantiated_from_the_url_query_params {
 
             use crate::servers::http::v1::query::Query;
             use crate::servers::http::v1::requests::announce::{
-                Announce, COMPACT, DOWNLOADED, EVENT, INFO_HASH, LEFT, NUMWANT, PEER_ID, PORT, UPLOADED,
+                Announce, COMPACT, DOWNLOADED, EVENT, INFO_HASH, IPV4, IPV6, LEFT, NO_PEER_ID, NUMWANT, PEER_ID, PORT, UPLOADED,
             };
 
             #[test]
@@ -586,6 +868,45 @@ mod tests {
 
                 assert!(Announce::try_from(raw_query.parse::<Query>().unwrap()).is_err());
             }
+
+            #[test]
+            fn it_should_fail_if_the_no_peer_id_param_is_invalid() {
+                let raw_query = Query::from(vec![
+                    (INFO_HASH, "%3B%24U%04%CF%5F%11%BB%DB%E1%20%1C%EAjk%F4Z%EE%1B%C0"),
+                    (PEER_ID, "-qB00000000000000001"),
+                    (PORT, "17548"),
+                    (NO_PEER_ID, "INVALID_NO_PEER_ID_VALUE"),
+                ])
+                .to_string();
+
+                assert!(Announce::try_from(raw_query.parse::<Query>().unwrap()).is_err());
+            }
+
+            #[test]
+            fn it_should_fail_if_the_ipv4_param_is_invalid() {
+                let raw_query = Query::from(vec![
+                    (INFO_HASH, "%3B%24U%04%CF%5F%11%BB%DB%E1%20%1C%EAjk%F4Z%EE%1B%C0"),
+                    (PEER_ID, "-qB00000000000000001"),
+                    (PORT, "17548"),
+                    (IPV4, "2001%3Adb8%3A%3A1"),
+                ])
+                .to_string();
+
+                assert!(Announce::try_from(raw_query.parse::<Query>().unwrap()).is_err());
+            }
+
+            #[test]
+            fn it_should_fail_if_the_ipv6_param_is_invalid() {
+                let raw_query = Query::from(vec![
+                    (INFO_HASH, "%3B%24U%04%CF%5F%11%BB%DB%E1%20%1C%EAjk%F4Z%EE%1B%C0"),
+                    (PEER_ID, "-qB00000000000000001"),
+                    (PORT, "17548"),
+                    (IPV6, "126.0.0.1"),
+                ])
+                .to_string();
+
+                assert!(Announce::try_from(raw_query.parse::<Query>().unwrap()).is_err());
+            }
         }
     }
 }
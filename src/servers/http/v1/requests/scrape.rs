@@ -40,10 +40,24 @@ impl From<ParseScrapeQueryError> for responses::error::Error {
     fn from(err: ParseScrapeQueryError) -> Self {
         responses::error::Error {
             failure_reason: format!("Bad request. Cannot parse query params for scrape request: {err}"),
+            retry_in: None,
         }
     }
 }
 
+/// It returns `true` if the `scrape` request does not contain any `info_hash`
+/// param. When the full scrape is enabled, the tracker returns the scrape data
+/// for all the torrents for those requests.
+#[must_use]
+pub fn is_full_scrape(maybe_raw_query: Option<&str>) -> bool {
+    match maybe_raw_query {
+        Some(raw_query) => raw_query
+            .parse::<Query>()
+            .map_or(raw_query.is_empty(), |query| query.get_param_vec(INFO_HASH).is_none()),
+        None => true,
+    }
+}
+
 impl TryFrom<Query> for Scrape {
     type Error = ParseScrapeQueryError;
 
@@ -124,5 +138,26 @@ mod tests {
                 assert!(Scrape::try_from(raw_query.parse::<Query>().unwrap()).is_err());
             }
         }
+
+        mod full_scrape {
+
+            use crate::servers::http::v1::query::Query;
+            use crate::servers::http::v1::requests::scrape::{is_full_scrape, INFO_HASH};
+
+            #[test]
+            fn it_should_be_a_request_without_the_info_hash_param() {
+                assert!(is_full_scrape(None));
+                assert!(is_full_scrape(Some("")));
+                assert!(is_full_scrape(Some("another_param=NOT_RELEVANT")));
+            }
+
+            #[test]
+            fn it_should_not_be_a_request_with_the_info_hash_param() {
+                let raw_query =
+                    Query::from(vec![(INFO_HASH, "%3B%24U%04%CF%5F%11%BB%DB%E1%20%1C%EAjk%F4Z%EE%1B%C0")]).to_string();
+
+                assert!(!is_full_scrape(Some(&raw_query)));
+            }
+        }
     }
 }
@@ -1,8 +1,9 @@
 //! `Announce` response for the HTTP tracker [`announce`](crate::servers::http::v1::requests::announce::Announce) request.
 //!
 //! Data structures and logic to build the `announce` response.
+use std::borrow::Cow;
 use std::io::Write;
-use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 
 use axum::http::StatusCode;
 use derive_more::{AsRef, Constructor, From};
@@ -29,6 +30,7 @@ use crate::servers::http::v1::responses;
 /// - [BEP 03: The `BitTorrent` Protocol Specification](https://www.bittorrent.org/beps/bep_0003.html)
 /// - [BEP 23: Tracker Returns Compact Peer Lists](https://www.bittorrent.org/beps/bep_0023.html)
 /// - [BEP 07: IPv6 Tracker Extension](https://www.bittorrent.org/beps/bep_0007.html)
+/// - [BEP 24: Tracker Returns External IP](https://www.bittorrent.org/beps/bep_0024.html)
 
 #[derive(Debug, AsRef, PartialEq, Constructor)]
 pub struct Announce<E>
@@ -63,6 +65,46 @@ impl<E: From<AnnounceData> + Into<Vec<u8>>> Response for Announce<E> {
     }
 }
 
+/// The optional fields of the [`Normal`] and [`Compact`] responses that are
+/// not part of the [`AnnounceData`].
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct OptionalFields {
+    /// A message for the user. Unlike the `failure reason`, the request has
+    /// been processed normally.
+    pub warning_message: Option<String>,
+    /// An ID the client should send back in the `trackerid` param of the next
+    /// announce requests.
+    pub tracker_id: Option<String>,
+    /// The IP address of the client as seen by the tracker. Refer to
+    /// [BEP 24: Tracker Returns External IP](https://www.bittorrent.org/beps/bep_0024.html).
+    pub external_ip: Option<IpAddr>,
+}
+
+impl OptionalFields {
+    /// It adds the fields that are present to the bencoded response
+    /// dictionary.
+    fn add_to(self, response: &mut BencodeMut<'_>) {
+        let dict = response.dict_mut().expect("the announce response should be a dictionary");
+
+        if let Some(warning_message) = self.warning_message {
+            dict.insert(Cow::from(&b"warning message"[..]), ben_bytes!(warning_message));
+        }
+
+        if let Some(tracker_id) = self.tracker_id {
+            dict.insert(Cow::from(&b"tracker id"[..]), ben_bytes!(tracker_id));
+        }
+
+        if let Some(external_ip) = self.external_ip {
+            let ip_bytes = match external_ip {
+                IpAddr::V4(ip) => ip.octets().to_vec(),
+                IpAddr::V6(ip) => ip.octets().to_vec(),
+            };
+
+            dict.insert(Cow::from(&b"external ip"[..]), ben_bytes!(ip_bytes));
+        }
+    }
+}
+
 /// Format of the [`Normal`] (Non-Compact) Encoding
 pub struct Normal {
     complete: i64,
@@ -70,6 +112,24 @@ pub struct Normal {
     interval: i64,
     min_interval: i64,
     peers: Vec<NormalPeer>,
+    no_peer_id: bool,
+    optional_fields: OptionalFields,
+}
+
+impl Normal {
+    /// It omits the `peer id` of the peers, when the client sends the
+    /// `no_peer_id` param.
+    #[must_use]
+    pub fn without_peer_ids(mut self) -> Self {
+        self.no_peer_id = true;
+        self
+    }
+
+    #[must_use]
+    pub fn with_optional_fields(mut self, optional_fields: OptionalFields) -> Self {
+        self.optional_fields = optional_fields;
+        self
+    }
 }
 
 impl From<AnnounceData> for Normal {
@@ -80,6 +140,8 @@ impl From<AnnounceData> for Normal {
             interval: data.policy.interval.into(),
             min_interval: data.policy.interval_min.into(),
             peers: data.peers.iter().map(AsRef::as_ref).copied().collect(),
+            no_peer_id: false,
+            optional_fields: OptionalFields::default(),
         }
     }
 }
@@ -90,17 +152,24 @@ impl Into<Vec<u8>> for Normal {
         let mut peers_list = ben_list!();
         let peers_list_mut = peers_list.list_mut().unwrap();
         for peer in &self.peers {
-            peers_list_mut.push(peer.into());
+            if self.no_peer_id {
+                peers_list_mut.push(peer.without_peer_id());
+            } else {
+                peers_list_mut.push(peer.into());
+            }
         }
 
-        (ben_map! {
+        let mut response = ben_map! {
             "complete" => ben_int!(self.complete),
             "incomplete" => ben_int!(self.incomplete),
             "interval" => ben_int!(self.interval),
             "min interval" => ben_int!(self.min_interval),
             "peers" => peers_list.clone()
-        })
-        .encode()
+        };
+
+        self.optional_fields.add_to(&mut response);
+
+        response.encode()
     }
 }
 
@@ -112,11 +181,26 @@ pub struct Compact {
     min_interval: i64,
     peers: Vec<u8>,
     peers6: Vec<u8>,
+    optional_fields: OptionalFields,
+}
+
+impl Compact {
+    #[must_use]
+    pub fn with_optional_fields(mut self, optional_fields: OptionalFields) -> Self {
+        self.optional_fields = optional_fields;
+        self
+    }
 }
 
 impl From<AnnounceData> for Compact {
     fn from(data: AnnounceData) -> Self {
-        let compact_peers: Vec<CompactPeer> = data.peers.iter().map(AsRef::as_ref).copied().collect();
+        // Dual-stack peers are included in both lists
+        let compact_peers: Vec<CompactPeer> = data
+            .peers
+            .iter()
+            .flat_map(|peer| peer.addresses())
+            .map(CompactPeer::from)
+            .collect();
 
         let (peers, peers6): (Vec<CompactPeerData<Ipv4Addr>>, Vec<CompactPeerData<Ipv6Addr>>) =
             compact_peers.into_iter().collect();
@@ -131,6 +215,7 @@ impl From<AnnounceData> for Compact {
             min_interval: data.policy.interval_min.into(),
             peers: peers_encoded.0,
             peers6: peers_encoded_6.0,
+            optional_fields: OptionalFields::default(),
         }
     }
 }
@@ -138,15 +223,18 @@ impl From<AnnounceData> for Compact {
 #[allow(clippy::from_over_into)]
 impl Into<Vec<u8>> for Compact {
     fn into(self) -> Vec<u8> {
-        (ben_map! {
+        let mut response = ben_map! {
             "complete" => ben_int!(self.complete),
             "incomplete" => ben_int!(self.incomplete),
             "interval" => ben_int!(self.interval),
             "min interval" => ben_int!(self.min_interval),
             "peers" => ben_bytes!(self.peers),
             "peers6" => ben_bytes!(self.peers6)
-        })
-        .encode()
+        };
+
+        self.optional_fields.add_to(&mut response);
+
+        response.encode()
     }
 }
 
@@ -175,6 +263,16 @@ pub struct NormalPeer {
 
 impl peer::Encoding for NormalPeer {}
 
+impl NormalPeer {
+    /// The bencoded peer without the `peer id`.
+    fn without_peer_id(&self) -> BencodeMut<'_> {
+        ben_map! {
+            "ip" => ben_bytes!(self.ip.to_string()),
+            "port" => ben_int!(i64::from(self.port))
+        }
+    }
+}
+
 impl From<peer::Peer> for NormalPeer {
     fn from(peer: peer::Peer) -> Self {
         NormalPeer {
@@ -229,7 +327,13 @@ impl peer::Encoding for CompactPeer {}
 
 impl From<peer::Peer> for CompactPeer {
     fn from(peer: peer::Peer) -> Self {
-        match (peer.peer_addr.ip(), peer.peer_addr.port()) {
+        peer.peer_addr.into()
+    }
+}
+
+impl From<SocketAddr> for CompactPeer {
+    fn from(addr: SocketAddr) -> Self {
+        match (addr.ip(), addr.port()) {
             (IpAddr::V4(ip), port) => Self::V4(CompactPeerData { ip, port }),
             (IpAddr::V6(ip), port) => Self::V6(CompactPeerData { ip, port }),
         }
@@ -301,12 +405,12 @@ mod tests {
     use std::sync::Arc;
 
     use aquatic_udp_protocol::PeerId;
-    use torrust_tracker_configuration::AnnouncePolicy;
+    use torrust_tracker_configuration::{AnnouncePolicy, IntervalMinAction};
     use torrust_tracker_primitives::peer::fixture::PeerBuilder;
     use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
 
     use crate::core::AnnounceData;
-    use crate::servers::http::v1::responses::announce::{Announce, Compact, Normal, Response};
+    use crate::servers::http::v1::responses::announce::{Announce, Compact, Normal, OptionalFields, Response};
 
     // Some ascii values used in tests:
     //
@@ -321,7 +425,7 @@ mod tests {
     // is also a valid string which makes asserts more readable.
 
     fn setup_announce_data() -> AnnounceData {
-        let policy = AnnouncePolicy::new(111, 222);
+        let policy = AnnouncePolicy::new(111, 222, IntervalMinAction::Disabled, 50, 74, 74);
 
         let peer_ipv4 = PeerBuilder::default()
             .with_peer_id(&PeerId(*b"-qB00000000000000001"))
@@ -337,7 +441,7 @@ mod tests {
             .build();
 
         let peers = vec![Arc::new(peer_ipv4), Arc::new(peer_ipv6)];
-        let stats = SwarmMetadata::new(333, 333, 444);
+        let stats = SwarmMetadata::new(333, 333, 444, 444);
 
         AnnounceData::new(peers, stats, policy)
     }
@@ -356,6 +460,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn compact_announce_response_should_include_dual_stack_peers_in_both_peer_lists() {
+        let peer = PeerBuilder::default()
+            .with_peer_id(&PeerId(*b"-qB00000000000000001"))
+            .with_peer_addr(&SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0x69, 0x69, 0x69, 0x69)), 0x7070))
+            .with_alternative_addr(&SocketAddr::new(
+                IpAddr::V6(Ipv6Addr::new(0x6969, 0x6969, 0x6969, 0x6969, 0x6969, 0x6969, 0x6969, 0x6969)),
+                0x7070,
+            ))
+            .build();
+
+        let data = AnnounceData::new(
+            vec![Arc::new(peer)],
+            SwarmMetadata::new(333, 333, 444, 444),
+            AnnouncePolicy::new(111, 222, IntervalMinAction::Disabled, 50, 74, 74),
+        );
+
+        let response: Announce<Compact> = data.into();
+        let bytes = response.body().expect("it should encode the response");
+
+        let expected_bytes =
+            // cspell:disable-next-line
+            b"d8:completei333e10:incompletei444e8:intervali111e12:min intervali222e5:peers6:iiiipp6:peers618:iiiiiiiiiiiiiiiippe";
+
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            String::from_utf8(expected_bytes.to_vec()).unwrap()
+        );
+    }
+
     #[test]
     fn compact_announce_response_can_be_bencoded() {
         let response: Announce<Compact> = setup_announce_data().into();
@@ -370,4 +504,39 @@ mod tests {
             String::from_utf8(expected_bytes.to_vec()).unwrap()
         );
     }
+
+    #[test]
+    fn non_compact_announce_response_can_be_bencoded_without_the_peer_ids() {
+        let response = Announce::new(Normal::from(setup_announce_data()).without_peer_ids());
+        let bytes = response.body().expect("it should encode the response");
+
+        // cspell:disable-next-line
+        let expected_bytes = b"d8:completei333e10:incompletei444e8:intervali111e12:min intervali222e5:peersld2:ip15:105.105.105.1054:porti28784eed2:ip39:6969:6969:6969:6969:6969:6969:6969:69694:porti28784eeee";
+
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            String::from_utf8(expected_bytes.to_vec()).unwrap()
+        );
+    }
+
+    #[test]
+    fn announce_responses_should_include_the_optional_fields() {
+        let optional_fields = OptionalFields {
+            warning_message: Some("ip".to_string()),
+            tracker_id: Some("pi".to_string()),
+            external_ip: Some(IpAddr::V4(Ipv4Addr::new(0x69, 0x69, 0x69, 0x69))),
+        };
+
+        let response = Announce::new(Compact::from(setup_announce_data()).with_optional_fields(optional_fields));
+        let bytes = response.body().expect("it should encode the response");
+
+        let expected_bytes =
+            // cspell:disable-next-line
+            b"d8:completei333e11:external ip4:iiii10:incompletei444e8:intervali111e12:min intervali222e5:peers6:iiiipp6:peers618:iiiiiiiiiiiiiiiipp10:tracker id2:pi15:warning message2:ipe";
+
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            String::from_utf8(expected_bytes.to_vec()).unwrap()
+        );
+    }
 }
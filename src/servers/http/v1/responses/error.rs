@@ -11,6 +11,11 @@
 //! > **NOTICE**: error responses are bencoded and always have a `200 OK` status
 //! > code. The official `BitTorrent` specification does not specify the status
 //! > code.
+//!
+//! From the [BEP 31. Failure Retry Extension](https://www.bittorrent.org/beps/bep_0031.html):
+//!
+//! _"The tracker may include a `retry in` key in the failure response. Its
+//! value is the number of minutes the client should wait before retrying."_
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use serde::Serialize;
@@ -21,6 +26,9 @@ pub struct Error {
     /// Human readable string which explains why the request failed.
     #[serde(rename = "failure reason")]
     pub failure_reason: String,
+    /// Number of minutes the client should wait before retrying the request.
+    #[serde(rename = "retry in", skip_serializing_if = "Option::is_none")]
+    pub retry_in: Option<u32>,
 }
 
 impl Error {
@@ -31,6 +39,7 @@ impl Error {
     ///
     /// let err = Error {
     ///    failure_reason: "error message".to_owned(),
+    ///    retry_in: None,
     /// };
     ///
     /// // cspell:disable-next-line
@@ -62,8 +71,20 @@ mod tests {
     fn http_tracker_errors_can_be_bencoded() {
         let err = Error {
             failure_reason: "error message".to_owned(),
+            retry_in: None,
         };
 
         assert_eq!(err.write(), "d14:failure reason13:error messagee"); // cspell:disable-line
     }
+
+    #[test]
+    fn http_tracker_errors_should_include_the_retry_in_key_when_the_client_should_retry_later() {
+        let err = Error {
+            failure_reason: "error message".to_owned(),
+            retry_in: Some(10),
+        };
+
+        // cspell:disable-next-line
+        assert_eq!(err.write(), "d14:failure reason13:error message8:retry ini10ee");
+    }
 }
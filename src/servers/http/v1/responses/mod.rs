@@ -6,7 +6,7 @@ pub mod announce;
 pub mod error;
 pub mod scrape;
 
-pub use announce::{Announce, Compact, Normal};
+pub use announce::{Announce, Compact, Normal, OptionalFields};
 
 /// Trait that defines the Announce Response Format
 pub trait Response: axum::response::IntoResponse {
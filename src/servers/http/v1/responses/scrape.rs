@@ -3,14 +3,19 @@
 //! Data structures and logic to build the `scrape` response.
 use std::borrow::Cow;
 
-use axum::http::StatusCode;
+use axum::http::{header, StatusCode};
 use axum::response::{IntoResponse, Response};
 use torrust_tracker_contrib_bencode::{ben_int, ben_map, BMutAccess};
 
+use crate::core::full_scrape::FullScrape;
 use crate::core::ScrapeData;
 
 /// The `Scrape` response for the HTTP tracker.
 ///
+/// Besides the BEP 48 keys, every file contains the number of `downloaders`
+/// ([BEP 21](https://www.bittorrent.org/beps/bep_0021.html)). Partial seeds
+/// are leechers (`incomplete`) but not `downloaders`.
+///
 /// ```rust
 /// use torrust_tracker::servers::http::v1::responses::scrape::Bencoded;
 /// use bittorrent_primitives::info_hash::InfoHash;
@@ -25,6 +30,7 @@ use crate::core::ScrapeData;
 ///         complete: 1,
 ///         downloaded: 2,
 ///         incomplete: 3,
+///         downloaders: 2,
 ///     },
 /// );
 ///
@@ -33,7 +39,7 @@ use crate::core::ScrapeData;
 /// let bytes = response.body();
 ///
 /// // cspell:disable-next-line
-/// let expected_bytes = b"d5:filesd20:iiiiiiiiiiiiiiiiiiiid8:completei1e10:downloadedi2e10:incompletei3eeee";
+/// let expected_bytes = b"d5:filesd20:iiiiiiiiiiiiiiiiiiiid8:completei1e10:downloadedi2e11:downloadersi2e10:incompletei3eeee";
 ///
 /// assert_eq!(
 ///     String::from_utf8(bytes).unwrap(),
@@ -64,6 +70,7 @@ impl Bencoded {
                 ben_map! {
                     "complete" => ben_int!(i64::from(value.complete)),
                     "downloaded" => ben_int!(i64::from(value.downloaded)),
+                    "downloaders" => ben_int!(i64::from(value.downloaders)),
                     "incomplete" => ben_int!(i64::from(value.incomplete))
                 },
             );
@@ -88,6 +95,22 @@ impl IntoResponse for Bencoded {
     }
 }
 
+/// The full `scrape` response for the HTTP tracker. It contains the cached
+/// bencoded scrape data for all the torrents.
+///
+/// The gzip-compressed [`FullScrape`] is returned, with the
+/// `Content-Encoding: gzip` header, when there is one and the client accepts
+/// it.
+#[must_use]
+pub fn full_scrape_response(full_scrape: &FullScrape, accepts_gzip: bool) -> Response {
+    match &full_scrape.gzipped_body {
+        Some(gzipped_body) if accepts_gzip => {
+            (StatusCode::OK, [(header::CONTENT_ENCODING, "gzip")], gzipped_body.clone()).into_response()
+        }
+        _ => (StatusCode::OK, full_scrape.body.clone()).into_response(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -107,6 +130,7 @@ mod tests {
                     complete: 1,
                     downloaded: 2,
                     incomplete: 3,
+                    downloaders: 2,
                 },
             );
             scrape_data
@@ -133,7 +157,8 @@ mod tests {
             let bytes = response.body();
 
             // cspell:disable-next-line
-            let expected_bytes = b"d5:filesd20:iiiiiiiiiiiiiiiiiiiid8:completei1e10:downloadedi2e10:incompletei3eeee";
+            let expected_bytes =
+                b"d5:filesd20:iiiiiiiiiiiiiiiiiiiid8:completei1e10:downloadedi2e11:downloadersi2e10:incompletei3eeee";
 
             assert_eq!(
                 String::from_utf8(bytes).unwrap(),
@@ -37,11 +37,23 @@ pub fn router(tracker: Arc<Tracker>, server_socket_addr: SocketAddr) -> Router {
         // Health check
         .route("/health_check", get(health_check::handler))
         // Announce request
-        .route("/announce", get(announce::handle_without_key).with_state(tracker.clone()))
-        .route("/announce/:key", get(announce::handle_with_key).with_state(tracker.clone()))
+        .route(
+            "/announce",
+            get(announce::handle_without_key).with_state((tracker.clone(), server_socket_addr)),
+        )
+        .route(
+            "/announce/:key",
+            get(announce::handle_with_key).with_state((tracker.clone(), server_socket_addr)),
+        )
         // Scrape request
-        .route("/scrape", get(scrape::handle_without_key).with_state(tracker.clone()))
-        .route("/scrape/:key", get(scrape::handle_with_key).with_state(tracker))
+        .route(
+            "/scrape",
+            get(scrape::handle_without_key).with_state((tracker.clone(), server_socket_addr)),
+        )
+        .route(
+            "/scrape/:key",
+            get(scrape::handle_with_key).with_state((tracker, server_socket_addr)),
+        )
         .route("/metrics", get(|| async move { metric_handle.render() }))
         .layer(prometheus_layer)
         // Add extension to get the client IP from the connection info
@@ -8,13 +8,15 @@
 //!
 //! It also sends an [`statistics::Event`]
 //! because events are specific for the HTTP tracker.
-use std::net::IpAddr;
+use std::net::SocketAddr;
 use std::sync::Arc;
 
 use bittorrent_primitives::info_hash::InfoHash;
 use torrust_tracker_primitives::peer;
 
-use crate::core::{statistics, AnnounceData, PeersWanted, Tracker};
+use crate::core::auth::Key;
+use crate::core::statistics::{self, RequestKind};
+use crate::core::{AnnounceData, PeersWanted, Tracker};
 
 /// The HTTP tracker `announce` service.
 ///
@@ -26,25 +28,35 @@ use crate::core::{statistics, AnnounceData, PeersWanted, Tracker};
 /// > **NOTICE**: as the HTTP tracker does not requires a connection request
 /// > like the UDP tracker, the number of TCP connections is incremented for
 /// > each `announce` request.
+///
+/// The `maybe_key` is the authentication key of the request, if any. The
+/// transfer of the announce is added to the stats of the key.
+///
+/// The `server_socket_addr` is the socket address the HTTP tracker handling
+/// the request is bound to.
 pub async fn invoke(
     tracker: Arc<Tracker>,
     info_hash: InfoHash,
     peer: &mut peer::Peer,
     peers_wanted: &PeersWanted,
+    maybe_key: Option<&Key>,
+    server_socket_addr: &SocketAddr,
 ) -> AnnounceData {
     let original_peer_ip = peer.peer_addr.ip();
 
     // The tracker could change the original peer ip
-    let announce_data = tracker.announce(&info_hash, peer, &original_peer_ip, peers_wanted);
-
-    match original_peer_ip {
-        IpAddr::V4(_) => {
-            tracker.send_stats_event(statistics::Event::Tcp4Announce).await;
-        }
-        IpAddr::V6(_) => {
-            tracker.send_stats_event(statistics::Event::Tcp6Announce).await;
-        }
-    }
+    let announce_data = match maybe_key {
+        Some(key) => tracker.announce_with_key(key, &info_hash, peer, &original_peer_ip, peers_wanted),
+        None => tracker.announce(&info_hash, peer, &original_peer_ip, peers_wanted),
+    };
+
+    tracker
+        .send_stats_event(statistics::Event::http(
+            *server_socket_addr,
+            &original_peer_ip,
+            RequestKind::Announce,
+        ))
+        .await;
 
     announce_data
 }
@@ -61,6 +73,10 @@ mod tests {
     use crate::core::services::tracker_factory;
     use crate::core::Tracker;
 
+    fn sample_server_socket_addr() -> SocketAddr {
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), 7070)
+    }
+
     fn public_tracker() -> Tracker {
         tracker_factory(&configuration::ephemeral_public())
     }
@@ -86,11 +102,14 @@ mod tests {
         peer::Peer {
             peer_id: PeerId(*b"-qB00000000000000000"),
             peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080),
+            alternative_addr: None,
+            key: None,
             updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
             uploaded: NumberOfBytes::new(0),
             downloaded: NumberOfBytes::new(0),
             left: NumberOfBytes::new(0),
             event: AnnounceEvent::Started,
+            upload_only: false,
         }
     }
 
@@ -105,9 +124,12 @@ mod tests {
         use torrust_tracker_test_helpers::configuration;
 
         use super::{sample_peer_using_ipv4, sample_peer_using_ipv6};
+        use crate::core::statistics::RequestKind;
         use crate::core::{statistics, AnnounceData, PeersWanted, Tracker};
         use crate::servers::http::v1::services::announce::invoke;
-        use crate::servers::http::v1::services::announce::tests::{public_tracker, sample_info_hash, sample_peer};
+        use crate::servers::http::v1::services::announce::tests::{
+            public_tracker, sample_info_hash, sample_peer, sample_server_socket_addr,
+        };
 
         #[tokio::test]
         async fn it_should_return_the_announce_data() {
@@ -115,7 +137,15 @@ mod tests {
 
             let mut peer = sample_peer();
 
-            let announce_data = invoke(tracker.clone(), sample_info_hash(), &mut peer, &PeersWanted::All).await;
+            let announce_data = invoke(
+                tracker.clone(),
+                sample_info_hash(),
+                &mut peer,
+                &PeersWanted::All,
+                None,
+                &sample_server_socket_addr(),
+            )
+            .await;
 
             let expected_announce_data = AnnounceData {
                 peers: vec![],
@@ -123,6 +153,7 @@ mod tests {
                     downloaded: 0,
                     complete: 1,
                     incomplete: 0,
+                    downloaders: 0,
                 },
                 policy: tracker.get_announce_policy(),
             };
@@ -135,7 +166,11 @@ mod tests {
             let mut stats_event_sender_mock = statistics::MockEventSender::new();
             stats_event_sender_mock
                 .expect_send_event()
-                .with(eq(statistics::Event::Tcp4Announce))
+                .with(eq(statistics::Event::http(
+                    sample_server_socket_addr(),
+                    &IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)),
+                    RequestKind::Announce,
+                )))
                 .times(1)
                 .returning(|_| Box::pin(future::ready(Some(Ok(())))));
             let stats_event_sender = Box::new(stats_event_sender_mock);
@@ -151,7 +186,15 @@ mod tests {
 
             let mut peer = sample_peer_using_ipv4();
 
-            let _announce_data = invoke(tracker, sample_info_hash(), &mut peer, &PeersWanted::All).await;
+            let _announce_data = invoke(
+                tracker,
+                sample_info_hash(),
+                &mut peer,
+                &PeersWanted::All,
+                None,
+                &sample_server_socket_addr(),
+            )
+            .await;
         }
 
         fn tracker_with_an_ipv6_external_ip(stats_event_sender: Box<dyn statistics::EventSender>) -> Tracker {
@@ -179,7 +222,11 @@ mod tests {
             let mut stats_event_sender_mock = statistics::MockEventSender::new();
             stats_event_sender_mock
                 .expect_send_event()
-                .with(eq(statistics::Event::Tcp4Announce))
+                .with(eq(statistics::Event::http(
+                    sample_server_socket_addr(),
+                    &IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)),
+                    RequestKind::Announce,
+                )))
                 .times(1)
                 .returning(|_| Box::pin(future::ready(Some(Ok(())))));
             let stats_event_sender = Box::new(stats_event_sender_mock);
@@ -191,6 +238,8 @@ mod tests {
                 sample_info_hash(),
                 &mut peer,
                 &PeersWanted::All,
+                None,
+                &sample_server_socket_addr(),
             )
             .await;
         }
@@ -201,7 +250,11 @@ mod tests {
             let mut stats_event_sender_mock = statistics::MockEventSender::new();
             stats_event_sender_mock
                 .expect_send_event()
-                .with(eq(statistics::Event::Tcp6Announce))
+                .with(eq(statistics::Event::http(
+                    sample_server_socket_addr(),
+                    &IpAddr::V6(Ipv6Addr::LOCALHOST),
+                    RequestKind::Announce,
+                )))
                 .times(1)
                 .returning(|_| Box::pin(future::ready(Some(Ok(())))));
             let stats_event_sender = Box::new(stats_event_sender_mock);
@@ -217,7 +270,15 @@ mod tests {
 
             let mut peer = sample_peer_using_ipv6();
 
-            let _announce_data = invoke(tracker, sample_info_hash(), &mut peer, &PeersWanted::All).await;
+            let _announce_data = invoke(
+                tracker,
+                sample_info_hash(),
+                &mut peer,
+                &PeersWanted::All,
+                None,
+                &sample_server_socket_addr(),
+            )
+            .await;
         }
     }
 }
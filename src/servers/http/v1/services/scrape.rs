@@ -8,12 +8,16 @@
 //!
 //! It also sends an [`statistics::Event`]
 //! because events are specific for the HTTP tracker.
-use std::net::IpAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
 
 use bittorrent_primitives::info_hash::InfoHash;
 
-use crate::core::{statistics, ScrapeData, Tracker};
+use crate::core::auth::Key;
+use crate::core::full_scrape::FullScrape;
+use crate::core::statistics::{self, RequestKind};
+use crate::core::{ScrapeData, Tracker};
+use crate::servers::http::v1::responses::scrape::Bencoded;
 
 /// The HTTP tracker `scrape` service.
 ///
@@ -25,10 +29,19 @@ use crate::core::{statistics, ScrapeData, Tracker};
 /// > **NOTICE**: as the HTTP tracker does not requires a connection request
 /// > like the UDP tracker, the number of TCP connections is incremented for
 /// > each `scrape` request.
-pub async fn invoke(tracker: &Arc<Tracker>, info_hashes: &Vec<InfoHash>, original_peer_ip: &IpAddr) -> ScrapeData {
-    let scrape_data = tracker.scrape(info_hashes).await;
-
-    send_scrape_event(original_peer_ip, tracker).await;
+///
+/// The `server_socket_addr` is the socket address the HTTP tracker handling
+/// the request is bound to.
+pub async fn invoke(
+    tracker: &Arc<Tracker>,
+    info_hashes: &Vec<InfoHash>,
+    key: Option<&Key>,
+    original_peer_ip: &IpAddr,
+    server_socket_addr: &SocketAddr,
+) -> ScrapeData {
+    let scrape_data = tracker.scrape(info_hashes, key).await;
+
+    send_scrape_event(original_peer_ip, server_socket_addr, tracker).await;
 
     scrape_data
 }
@@ -39,21 +52,50 @@ pub async fn invoke(tracker: &Arc<Tracker>, info_hashes: &Vec<InfoHash>, origina
 /// the tracker returns empty stats for all the torrents.
 ///
 /// > **NOTICE**: tracker statistics are not updated in this case.
-pub async fn fake(tracker: &Arc<Tracker>, info_hashes: &Vec<InfoHash>, original_peer_ip: &IpAddr) -> ScrapeData {
-    send_scrape_event(original_peer_ip, tracker).await;
+pub async fn fake(
+    tracker: &Arc<Tracker>,
+    info_hashes: &Vec<InfoHash>,
+    original_peer_ip: &IpAddr,
+    server_socket_addr: &SocketAddr,
+) -> ScrapeData {
+    send_scrape_event(original_peer_ip, server_socket_addr, tracker).await;
 
     ScrapeData::zeroed(info_hashes)
 }
 
-async fn send_scrape_event(original_peer_ip: &IpAddr, tracker: &Arc<Tracker>) {
-    match original_peer_ip {
-        IpAddr::V4(_) => {
-            tracker.send_stats_event(statistics::Event::Tcp4Scrape).await;
-        }
-        IpAddr::V6(_) => {
-            tracker.send_stats_event(statistics::Event::Tcp6Scrape).await;
-        }
-    }
+/// The HTTP tracker full `scrape` service. It returns the last full scrape
+/// generated by the [`update_full_scrape`] service, or `None` if the full
+/// scrape was not generated yet.
+///
+/// It sends the same statistics event as the `scrape` service.
+pub async fn full(tracker: &Arc<Tracker>, original_peer_ip: &IpAddr, server_socket_addr: &SocketAddr) -> Option<Arc<FullScrape>> {
+    send_scrape_event(original_peer_ip, server_socket_addr, tracker).await;
+
+    tracker.get_full_scrape()
+}
+
+/// It generates the bencoded full scrape response for all the torrents, and
+/// caches it in the tracker.
+///
+/// # Errors
+///
+/// Will return an error if the response cannot be compressed.
+pub async fn update_full_scrape(tracker: &Tracker) -> Result<(), std::io::Error> {
+    let scrape_data = tracker.get_full_scrape_data().await;
+
+    let torrents = scrape_data.files.len();
+
+    tracker.update_full_scrape(Bencoded::from(scrape_data).body(), torrents)
+}
+
+async fn send_scrape_event(original_peer_ip: &IpAddr, server_socket_addr: &SocketAddr, tracker: &Arc<Tracker>) {
+    tracker
+        .send_stats_event(statistics::Event::http(
+            *server_socket_addr,
+            original_peer_ip,
+            RequestKind::Scrape,
+        ))
+        .await;
 }
 
 #[cfg(test)]
@@ -69,6 +111,10 @@ mod tests {
     use crate::core::services::tracker_factory;
     use crate::core::Tracker;
 
+    fn sample_server_socket_addr() -> SocketAddr {
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), 7070)
+    }
+
     fn public_tracker() -> Tracker {
         tracker_factory(&configuration::ephemeral_public())
     }
@@ -85,11 +131,14 @@ mod tests {
         peer::Peer {
             peer_id: PeerId(*b"-qB00000000000000000"),
             peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8080),
+            alternative_addr: None,
+            key: None,
             updated: DurationSinceUnixEpoch::new(1_669_397_478_934, 0),
             uploaded: NumberOfBytes::new(0),
             downloaded: NumberOfBytes::new(0),
             left: NumberOfBytes::new(0),
             event: AnnounceEvent::Started,
+            upload_only: false,
         }
     }
 
@@ -103,10 +152,11 @@ mod tests {
         use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
         use torrust_tracker_test_helpers::configuration;
 
+        use crate::core::statistics::RequestKind;
         use crate::core::{statistics, PeersWanted, ScrapeData, Tracker};
         use crate::servers::http::v1::services::scrape::invoke;
         use crate::servers::http::v1::services::scrape::tests::{
-            public_tracker, sample_info_hash, sample_info_hashes, sample_peer,
+            public_tracker, sample_info_hash, sample_info_hashes, sample_peer, sample_server_socket_addr,
         };
 
         #[tokio::test]
@@ -121,7 +171,7 @@ mod tests {
             let original_peer_ip = peer.ip();
             tracker.announce(&info_hash, &mut peer, &original_peer_ip, &PeersWanted::All);
 
-            let scrape_data = invoke(&tracker, &info_hashes, &original_peer_ip).await;
+            let scrape_data = invoke(&tracker, &info_hashes, None, &original_peer_ip, &sample_server_socket_addr()).await;
 
             let mut expected_scrape_data = ScrapeData::empty();
             expected_scrape_data.add_file(
@@ -130,6 +180,7 @@ mod tests {
                     complete: 1,
                     downloaded: 0,
                     incomplete: 0,
+                    downloaders: 0,
                 },
             );
 
@@ -141,7 +192,11 @@ mod tests {
             let mut stats_event_sender_mock = statistics::MockEventSender::new();
             stats_event_sender_mock
                 .expect_send_event()
-                .with(eq(statistics::Event::Tcp4Scrape))
+                .with(eq(statistics::Event::http(
+                    sample_server_socket_addr(),
+                    &IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)),
+                    RequestKind::Scrape,
+                )))
                 .times(1)
                 .returning(|_| Box::pin(future::ready(Some(Ok(())))));
             let stats_event_sender = Box::new(stats_event_sender_mock);
@@ -157,7 +212,7 @@ mod tests {
 
             let peer_ip = IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1));
 
-            invoke(&tracker, &sample_info_hashes(), &peer_ip).await;
+            invoke(&tracker, &sample_info_hashes(), None, &peer_ip, &sample_server_socket_addr()).await;
         }
 
         #[tokio::test]
@@ -165,7 +220,11 @@ mod tests {
             let mut stats_event_sender_mock = statistics::MockEventSender::new();
             stats_event_sender_mock
                 .expect_send_event()
-                .with(eq(statistics::Event::Tcp6Scrape))
+                .with(eq(statistics::Event::http(
+                    sample_server_socket_addr(),
+                    &IpAddr::V6(Ipv6Addr::LOCALHOST),
+                    RequestKind::Scrape,
+                )))
                 .times(1)
                 .returning(|_| Box::pin(future::ready(Some(Ok(())))));
             let stats_event_sender = Box::new(stats_event_sender_mock);
@@ -181,7 +240,7 @@ mod tests {
 
             let peer_ip = IpAddr::V6(Ipv6Addr::new(0x6969, 0x6969, 0x6969, 0x6969, 0x6969, 0x6969, 0x6969, 0x6969));
 
-            invoke(&tracker, &sample_info_hashes(), &peer_ip).await;
+            invoke(&tracker, &sample_info_hashes(), None, &peer_ip, &sample_server_socket_addr()).await;
         }
     }
 
@@ -194,10 +253,11 @@ mod tests {
         use mockall::predicate::eq;
         use torrust_tracker_test_helpers::configuration;
 
+        use crate::core::statistics::RequestKind;
         use crate::core::{statistics, PeersWanted, ScrapeData, Tracker};
         use crate::servers::http::v1::services::scrape::fake;
         use crate::servers::http::v1::services::scrape::tests::{
-            public_tracker, sample_info_hash, sample_info_hashes, sample_peer,
+            public_tracker, sample_info_hash, sample_info_hashes, sample_peer, sample_server_socket_addr,
         };
 
         #[tokio::test]
@@ -212,7 +272,7 @@ mod tests {
             let original_peer_ip = peer.ip();
             tracker.announce(&info_hash, &mut peer, &original_peer_ip, &PeersWanted::All);
 
-            let scrape_data = fake(&tracker, &info_hashes, &original_peer_ip).await;
+            let scrape_data = fake(&tracker, &info_hashes, &original_peer_ip, &sample_server_socket_addr()).await;
 
             let expected_scrape_data = ScrapeData::zeroed(&info_hashes);
 
@@ -224,7 +284,11 @@ mod tests {
             let mut stats_event_sender_mock = statistics::MockEventSender::new();
             stats_event_sender_mock
                 .expect_send_event()
-                .with(eq(statistics::Event::Tcp4Scrape))
+                .with(eq(statistics::Event::http(
+                    sample_server_socket_addr(),
+                    &IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)),
+                    RequestKind::Scrape,
+                )))
                 .times(1)
                 .returning(|_| Box::pin(future::ready(Some(Ok(())))));
             let stats_event_sender = Box::new(stats_event_sender_mock);
@@ -240,7 +304,7 @@ mod tests {
 
             let peer_ip = IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1));
 
-            fake(&tracker, &sample_info_hashes(), &peer_ip).await;
+            fake(&tracker, &sample_info_hashes(), &peer_ip, &sample_server_socket_addr()).await;
         }
 
         #[tokio::test]
@@ -248,7 +312,11 @@ mod tests {
             let mut stats_event_sender_mock = statistics::MockEventSender::new();
             stats_event_sender_mock
                 .expect_send_event()
-                .with(eq(statistics::Event::Tcp6Scrape))
+                .with(eq(statistics::Event::http(
+                    sample_server_socket_addr(),
+                    &IpAddr::V6(Ipv6Addr::LOCALHOST),
+                    RequestKind::Scrape,
+                )))
                 .times(1)
                 .returning(|_| Box::pin(future::ready(Some(Ok(())))));
             let stats_event_sender = Box::new(stats_event_sender_mock);
@@ -264,7 +332,65 @@ mod tests {
 
             let peer_ip = IpAddr::V6(Ipv6Addr::new(0x6969, 0x6969, 0x6969, 0x6969, 0x6969, 0x6969, 0x6969, 0x6969));
 
-            fake(&tracker, &sample_info_hashes(), &peer_ip).await;
+            fake(&tracker, &sample_info_hashes(), &peer_ip, &sample_server_socket_addr()).await;
+        }
+    }
+
+    mod with_full_scrape {
+
+        use std::sync::Arc;
+
+        use torrust_tracker_configuration::FullScrape;
+        use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
+        use torrust_tracker_test_helpers::configuration;
+
+        use crate::core::services::tracker_factory;
+        use crate::core::{PeersWanted, ScrapeData, Tracker};
+        use crate::servers::http::v1::responses::scrape::Bencoded;
+        use crate::servers::http::v1::services::scrape::tests::{sample_info_hash, sample_peer, sample_server_socket_addr};
+        use crate::servers::http::v1::services::scrape::{full, update_full_scrape};
+
+        fn tracker_with_full_scrape() -> Tracker {
+            let mut configuration = configuration::ephemeral_public();
+            configuration.core.full_scrape = Some(FullScrape::default());
+            tracker_factory(&configuration)
+        }
+
+        #[tokio::test]
+        async fn it_should_return_the_last_full_scrape_generated_for_all_the_torrents() {
+            let tracker = Arc::new(tracker_with_full_scrape());
+
+            let info_hash = sample_info_hash();
+            let mut peer = sample_peer();
+            let original_peer_ip = peer.ip();
+            tracker.announce(&info_hash, &mut peer, &original_peer_ip, &PeersWanted::All);
+
+            update_full_scrape(&tracker).await.unwrap();
+
+            let full_scrape = full(&tracker, &original_peer_ip, &sample_server_socket_addr()).await.unwrap();
+
+            let mut expected_scrape_data = ScrapeData::empty();
+            expected_scrape_data.add_file(
+                &info_hash,
+                SwarmMetadata {
+                    complete: 1,
+                    downloaded: 0,
+                    incomplete: 0,
+                    downloaders: 0,
+                },
+            );
+
+            assert_eq!(full_scrape.body, Bencoded::from(expected_scrape_data).body());
+            assert_eq!(full_scrape.torrents, 1);
+        }
+
+        #[tokio::test]
+        async fn it_should_not_return_the_full_scrape_before_it_is_generated() {
+            let tracker = Arc::new(tracker_with_full_scrape());
+
+            let full_scrape = full(&tracker, &sample_peer().ip(), &sample_server_socket_addr()).await;
+
+            assert!(full_scrape.is_none());
         }
     }
 }
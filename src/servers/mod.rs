@@ -1,5 +1,6 @@
 //! Servers. Services that can be started and stopped.
 pub mod apis;
+pub mod content_encoding;
 pub mod custom_axum_server;
 pub mod health_check_api;
 pub mod http;
@@ -44,8 +44,9 @@ pub enum Error {
         source: LocatedError<'static, dyn std::error::Error + Send + Sync>,
     },
 
-    /// Error returned when tracker requires authentication.
-    #[error("domain tracker requires authentication but is not supported in current UDP implementation. Location: {location}")]
+    /// Error returned when tracker requires authentication but the request
+    /// does not contain the authentication key.
+    #[error("domain tracker requires authentication but the request does not include a key. Location: {location}")]
     TrackerAuthenticationRequired { location: &'static Location<'static> },
 }
 
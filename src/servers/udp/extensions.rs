@@ -0,0 +1,255 @@
+//! UDP tracker protocol extensions.
+//!
+//! Refer to [BEP 41. UDP Tracker Protocol Extensions](https://www.bittorrent.org/beps/bep_0041.html).
+//!
+//! Clients can append a list of options to the end of an `announce` request.
+//! Each option starts with a one-byte type:
+//!
+//! Type  | Name           | Body
+//! ------|----------------|----------------------------------------
+//! `0x0` | `EndOfOptions` | None. The tracker stops parsing options.
+//! `0x1` | `NOP`          | None. It's used for padding.
+//! `0x2` | `URLData`      | One-byte length followed by the data.
+//!
+//! Any other option type is also followed by a one-byte length, so the tracker
+//! can skip options it does not know.
+//!
+//! The `URLData` options contain the path and query string of the tracker URL,
+//! split into chunks of up to 255 bytes. The tracker concatenates all of them
+//! to rebuild the request URL.
+//!
+//! For example, if the tracker URL is `udp://tracker.example.com:6969/announce/YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ?a=b`,
+//! the client will send the following `URLData`:
+//!
+//! ```text
+//! /announce/YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ?a=b
+//! ```
+//!
+//! The tracker uses it to get the authentication key when it runs in private
+//! mode, the same way the HTTP tracker uses the `GET /announce/:key` path.
+use std::mem::size_of;
+
+use aquatic_udp_protocol::AnnounceRequest;
+use thiserror::Error;
+
+use crate::core::auth::{Key, ParseKeyError};
+
+/// The size of the fixed part of the `announce` request. The options, if any,
+/// start right after it.
+pub const ANNOUNCE_REQUEST_SIZE: usize = size_of::<AnnounceRequest>();
+
+const END_OF_OPTIONS: u8 = 0x0;
+const NOP: u8 = 0x1;
+const URL_DATA: u8 = 0x2;
+
+/// Error returned when the request options cannot be parsed.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    #[error("option {option_type:#04x} at position {position} is truncated")]
+    TruncatedOption { option_type: u8, position: usize },
+
+    #[error("the URL data option is not a valid UTF-8 string")]
+    InvalidUrlData,
+}
+
+/// The request URL rebuilt from the `URLData` options.
+///
+/// ```text
+/// /announce/YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ?a=b
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct UrlData {
+    /// The URL path. For example: `/announce/YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ`.
+    pub path: String,
+    /// The URL query string without the leading `?`. For example: `a=b`.
+    pub query: Option<String>,
+}
+
+impl UrlData {
+    /// It extracts the authentication key from the path. The path must have
+    /// the same format used by the HTTP tracker: `/announce/:key`.
+    ///
+    /// It returns `None` if the path does not contain a key, and `Some(Err(_))`
+    /// if the key in the path has an invalid format.
+    #[must_use]
+    pub fn key(&self) -> Option<Result<Key, ParseKeyError>> {
+        let mut segments = self.path.split('/').filter(|segment| !segment.is_empty());
+
+        match (segments.next(), segments.next(), segments.next()) {
+            (Some("announce"), Some(key), None) => Some(key.parse::<Key>()),
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for UrlData {
+    fn from(url_data: &str) -> Self {
+        match url_data.split_once('?') {
+            Some((path, query)) => Self {
+                path: path.to_owned(),
+                query: Some(query.to_owned()),
+            },
+            None => Self {
+                path: url_data.to_owned(),
+                query: None,
+            },
+        }
+    }
+}
+
+/// It parses the options following the fixed part of a request and returns
+/// the URL data, or `None` if the request does not contain any `URLData`
+/// option.
+///
+/// Parsing stops at the `EndOfOptions` option or at the end of the packet.
+/// Unknown options are skipped.
+///
+/// # Errors
+///
+/// Will return a `ParseError` if an option is truncated or the URL data is not
+/// a valid UTF-8 string.
+pub fn parse(options: &[u8]) -> Result<Option<UrlData>, ParseError> {
+    let mut url_data: Option<Vec<u8>> = None;
+    let mut position = 0;
+
+    while let Some(option_type) = options.get(position) {
+        match *option_type {
+            END_OF_OPTIONS => break,
+            NOP => position += 1,
+            option_type => {
+                let truncated = ParseError::TruncatedOption { option_type, position };
+
+                let length = options.get(position + 1).ok_or(truncated.clone())?;
+                let start = position + 2;
+                let end = start + usize::from(*length);
+                let data = options.get(start..end).ok_or(truncated)?;
+
+                if option_type == URL_DATA {
+                    url_data.get_or_insert_with(Vec::new).extend_from_slice(data);
+                }
+
+                position = end;
+            }
+        }
+    }
+
+    match url_data {
+        Some(bytes) => {
+            let url_data = String::from_utf8(bytes).map_err(|_| ParseError::InvalidUrlData)?;
+            Ok(Some(UrlData::from(url_data.as_str())))
+        }
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    mod parsing_the_request_options {
+        use crate::servers::udp::extensions::{parse, ParseError, UrlData};
+
+        #[test]
+        fn should_return_none_when_there_are_no_options() {
+            assert_eq!(parse(&[]), Ok(None));
+        }
+
+        #[test]
+        fn should_return_none_when_there_are_no_url_data_options() {
+            assert_eq!(parse(&[0x1, 0x1, 0x0]), Ok(None));
+        }
+
+        #[test]
+        fn should_concatenate_all_the_url_data_options() {
+            let options = [&[0x2, 0x9][..], b"/announce", &[0x1, 0x2, 0x4][..], b"?a=b", &[0x0][..]].concat();
+
+            assert_eq!(
+                parse(&options),
+                Ok(Some(UrlData {
+                    path: "/announce".to_string(),
+                    query: Some("a=b".to_string())
+                }))
+            );
+        }
+
+        #[test]
+        fn should_ignore_the_options_after_the_end_of_options() {
+            let options = [&[0x2, 0x1][..], b"/", &[0x0, 0x2, 0x1][..], b"a"].concat();
+
+            assert_eq!(parse(&options), Ok(Some(UrlData::from("/"))));
+        }
+
+        #[test]
+        fn should_skip_unknown_options() {
+            let options = [&[0x7, 0x2][..], b"xx", &[0x2, 0x1][..], b"/"].concat();
+
+            assert_eq!(parse(&options), Ok(Some(UrlData::from("/"))));
+        }
+
+        #[test]
+        fn should_fail_when_an_option_is_truncated() {
+            let options = [&[0x2, 0x9][..], b"/ann"].concat();
+
+            assert_eq!(
+                parse(&options),
+                Err(ParseError::TruncatedOption {
+                    option_type: 0x2,
+                    position: 0
+                })
+            );
+        }
+
+        #[test]
+        fn should_fail_when_an_option_does_not_have_the_length_byte() {
+            assert_eq!(
+                parse(&[0x1, 0x2]),
+                Err(ParseError::TruncatedOption {
+                    option_type: 0x2,
+                    position: 1
+                })
+            );
+        }
+
+        #[test]
+        fn should_fail_when_the_url_data_is_not_a_valid_utf8_string() {
+            assert_eq!(parse(&[0x2, 0x2, 0xc3, 0x28]), Err(ParseError::InvalidUrlData));
+        }
+    }
+
+    mod url_data {
+        use crate::core::auth::Key;
+        use crate::servers::udp::extensions::UrlData;
+
+        #[test]
+        fn should_split_the_path_and_the_query() {
+            assert_eq!(
+                UrlData::from("/announce?a=b&c=d"),
+                UrlData {
+                    path: "/announce".to_string(),
+                    query: Some("a=b&c=d".to_string())
+                }
+            );
+        }
+
+        #[test]
+        fn should_extract_the_key_from_the_announce_path() {
+            let key = UrlData::from("/announce/YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ?a=b")
+                .key()
+                .unwrap()
+                .unwrap();
+
+            assert_eq!(key, "YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ".parse::<Key>().unwrap());
+        }
+
+        #[test]
+        fn should_not_return_a_key_when_the_path_does_not_contain_it() {
+            assert!(UrlData::from("/announce").key().is_none());
+            assert!(UrlData::from("/").key().is_none());
+            assert!(UrlData::from("/scrape/YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ").key().is_none());
+        }
+
+        #[test]
+        fn should_fail_when_the_key_has_an_invalid_format() {
+            assert!(UrlData::from("/announce/INVALID").key().unwrap().is_err());
+        }
+    }
+}
@@ -1,7 +1,8 @@
 //! Handlers for the UDP server.
 use std::hash::{DefaultHasher, Hash, Hasher as _};
-use std::net::{IpAddr, SocketAddr};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::ops::Range;
+use std::panic::Location;
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -12,14 +13,17 @@ use aquatic_udp_protocol::{
 };
 use bittorrent_primitives::info_hash::InfoHash;
 use torrust_tracker_clock::clock::Time as _;
+use torrust_tracker_configuration::{IntervalMinAction, UdpRateLimitAction as RateLimitAction};
 use tracing::{instrument, Level};
 use uuid::Uuid;
 use zerocopy::network_endian::I32;
 
 use super::connection_cookie::{check, make};
 use super::RawRequest;
-use crate::core::{statistics, PeersWanted, Tracker};
+use crate::core::statistics::{self, RequestKind};
+use crate::core::{PeersWanted, ScrapeData, Tracker};
 use crate::servers::udp::error::Error;
+use crate::servers::udp::extensions::{self, UrlData};
 use crate::servers::udp::peer_builder;
 use crate::shared::bit_torrent::common::MAX_SCRAPE_TORRENTS;
 use crate::CurrentClock;
@@ -65,8 +69,17 @@ pub(crate) async fn handle_packet(
 
     let response =
         match Request::parse_bytes(&udp_request.payload[..udp_request.payload.len()], MAX_SCRAPE_TORRENTS).map_err(Error::from) {
-            Ok(request) => match handle_request(request, udp_request.from, tracker, cookie_time_values.clone()).await {
-                Ok(response) => return response,
+            Ok(request) => match handle_request(
+                request,
+                &udp_request.payload,
+                udp_request.from,
+                tracker,
+                cookie_time_values.clone(),
+                local_addr,
+            )
+            .await
+            {
+                Ok(response) => response,
                 Err((e, transaction_id)) => {
                     handle_error(
                         udp_request.from,
@@ -74,46 +87,169 @@ pub(crate) async fn handle_packet(
                         cookie_time_values.valid_range.clone(),
                         &e,
                         Some(transaction_id),
+                        local_addr,
                     )
                     .await
                 }
             },
-            Err(e) => handle_error(udp_request.from, tracker, cookie_time_values.valid_range.clone(), &e, None).await,
+            Err(e) => {
+                handle_error(
+                    udp_request.from,
+                    tracker,
+                    cookie_time_values.valid_range.clone(),
+                    &e,
+                    None,
+                    local_addr,
+                )
+                .await
+            }
         };
 
     let latency = start_time.elapsed();
     tracing::trace!(?latency, "responded");
 
+    tracker
+        .send_stats_latency(statistics::RequestLatency {
+            protocol: statistics::Protocol::Udp,
+            kind: request_kind(&response),
+            duration: latency,
+        })
+        .await;
+
     response
 }
 
+/// It handles a request rejected by the [rate limiter](crate::servers::udp::server::rate_limiter).
+///
+/// It returns an error response when the tracker is configured to answer the
+/// requests exceeding the limits and the transaction ID can be read from the
+/// packet. Otherwise, the request is dropped.
+pub(crate) async fn handle_rate_limited(
+    udp_request: &RawRequest,
+    tracker: &Tracker,
+    local_addr: SocketAddr,
+    action: RateLimitAction,
+) -> Option<Response> {
+    tracing::trace!("handle rate limited");
+
+    tracker
+        .send_stats_event(statistics::Event::udp(
+            local_addr,
+            &udp_request.from,
+            RequestKind::RateLimited,
+        ))
+        .await;
+
+    match action {
+        RateLimitAction::Drop => None,
+        RateLimitAction::Error => {
+            // The transaction ID is at the same offset in all the requests
+            let transaction_id: [u8; 4] = udp_request.payload.get(12..16)?.try_into().ok()?;
+
+            Some(Response::from(ErrorResponse {
+                transaction_id: TransactionId(I32::from_bytes(transaction_id)),
+                message: "rate limit exceeded".into(),
+            }))
+        }
+    }
+}
+
+/// It returns the type of request answered with the `response`.
+fn request_kind(response: &Response) -> RequestKind {
+    match response {
+        Response::Connect(_) => RequestKind::Connect,
+        Response::AnnounceIpv4(_) | Response::AnnounceIpv6(_) => RequestKind::Announce,
+        Response::Scrape(_) => RequestKind::Scrape,
+        Response::Error(_) => RequestKind::Error,
+    }
+}
+
 /// It dispatches the request to the correct handler.
 ///
+/// The `payload` is the whole UDP packet. It's used to parse the BEP 41
+/// options appended to the `announce` requests.
+///
 /// # Errors
 ///
 /// If a error happens in the `handle_request` function, it will just return the  `ServerError`.
-#[instrument(skip(request, remote_addr, tracker, cookie_time_values))]
+#[instrument(skip(request, payload, remote_addr, tracker, cookie_time_values, server_socket_addr))]
 pub async fn handle_request(
     request: Request,
+    payload: &[u8],
     remote_addr: SocketAddr,
     tracker: &Tracker,
     cookie_time_values: CookieTimeValues,
+    server_socket_addr: SocketAddr,
 ) -> Result<Response, (Error, TransactionId)> {
     tracing::trace!("handle request");
 
-    match request {
-        Request::Connect(connect_request) => {
-            Ok(handle_connect(remote_addr, &connect_request, tracker, cookie_time_values.issue_time).await)
+    // Availability. The `connect` requests are still handled in maintenance
+    // mode, so that the health check keeps working.
+    if !matches!(request, Request::Connect(_)) {
+        if let Err(e) = tracker.check_availability() {
+            return Err((
+                Error::TrackerError {
+                    source: (Arc::new(e) as Arc<dyn std::error::Error + Send + Sync>).into(),
+                },
+                transaction_id(&request),
+            ));
         }
+    }
+
+    match request {
+        Request::Connect(connect_request) => Ok(handle_connect(
+            remote_addr,
+            &connect_request,
+            tracker,
+            cookie_time_values.issue_time,
+            server_socket_addr,
+        )
+        .await),
         Request::Announce(announce_request) => {
-            handle_announce(remote_addr, &announce_request, tracker, cookie_time_values.valid_range).await
+            let url_data = parse_announce_options(payload).map_err(|e| (e, announce_request.transaction_id))?;
+
+            handle_announce(
+                remote_addr,
+                &announce_request,
+                url_data.as_ref(),
+                tracker,
+                cookie_time_values.valid_range,
+                server_socket_addr,
+            )
+            .await
         }
         Request::Scrape(scrape_request) => {
-            handle_scrape(remote_addr, &scrape_request, tracker, cookie_time_values.valid_range).await
+            handle_scrape(
+                remote_addr,
+                &scrape_request,
+                tracker,
+                cookie_time_values.valid_range,
+                server_socket_addr,
+            )
+            .await
         }
     }
 }
 
+/// It returns the transaction ID of the request.
+fn transaction_id(request: &Request) -> TransactionId {
+    match request {
+        Request::Connect(connect_request) => connect_request.transaction_id,
+        Request::Announce(announce_request) => announce_request.transaction_id,
+        Request::Scrape(scrape_request) => scrape_request.transaction_id,
+    }
+}
+
+/// It parses the BEP 41 options appended to an `announce` request and
+/// returns the URL data, if any.
+fn parse_announce_options(payload: &[u8]) -> Result<Option<UrlData>, Error> {
+    let options = payload.get(extensions::ANNOUNCE_REQUEST_SIZE..).unwrap_or_default();
+
+    extensions::parse(options).map_err(|e| Error::BadRequest {
+        source: (Arc::new(e) as Arc<dyn std::error::Error + Send + Sync>).into(),
+    })
+}
+
 /// It handles the `Connect` request. Refer to [`Connect`](crate::servers::udp#connect)
 /// request for more information.
 ///
@@ -126,6 +262,7 @@ pub async fn handle_connect(
     request: &ConnectRequest,
     tracker: &Tracker,
     cookie_issue_time: f64,
+    server_socket_addr: SocketAddr,
 ) -> Response {
     tracing::Span::current().record("transaction_id", request.transaction_id.0.to_string());
 
@@ -139,14 +276,9 @@ pub async fn handle_connect(
     };
 
     // send stats event
-    match remote_addr {
-        SocketAddr::V4(_) => {
-            tracker.send_stats_event(statistics::Event::Udp4Connect).await;
-        }
-        SocketAddr::V6(_) => {
-            tracker.send_stats_event(statistics::Event::Udp6Connect).await;
-        }
-    }
+    tracker
+        .send_stats_event(statistics::Event::udp(server_socket_addr, &remote_addr, RequestKind::Connect))
+        .await;
 
     Response::from(response)
 }
@@ -154,6 +286,9 @@ pub async fn handle_connect(
 /// It handles the `Announce` request. Refer to [`Announce`](crate::servers::udp#announce)
 /// request for more information.
 ///
+/// When the tracker is private, the authentication key is taken from the
+/// BEP 41 `URLData` option. Refer to [`extensions`](crate::servers::udp::extensions).
+///
 /// # Errors
 ///
 /// If a error happens in the `handle_announce` function, it will just return the  `ServerError`.
@@ -161,8 +296,10 @@ pub async fn handle_connect(
 pub async fn handle_announce(
     remote_addr: SocketAddr,
     request: &AnnounceRequest,
+    url_data: Option<&UrlData>,
     tracker: &Tracker,
     cookie_valid_range: Range<f64>,
+    server_socket_addr: SocketAddr,
 ) -> Result<Response, (Error, TransactionId)> {
     tracing::Span::current()
         .record("transaction_id", request.transaction_id.0.to_string())
@@ -178,31 +315,119 @@ pub async fn handle_announce(
     )
     .map_err(|e| (e, request.transaction_id))?;
 
+    // Authentication
+    let authenticated_key = if tracker.requires_authentication() {
+        let key = match url_data.and_then(UrlData::key) {
+            Some(Ok(key)) => key,
+            Some(Err(e)) => {
+                return Err((
+                    Error::BadRequest {
+                        source: (Arc::new(e) as Arc<dyn std::error::Error + Send + Sync>).into(),
+                    },
+                    request.transaction_id,
+                ))
+            }
+            None => {
+                return Err((
+                    Error::TrackerAuthenticationRequired {
+                        location: Location::caller(),
+                    },
+                    request.transaction_id,
+                ))
+            }
+        };
+
+        tracker
+            .authenticate(&key)
+            .await
+            .map_err(|e| Error::TrackerError {
+                source: (Arc::new(e) as Arc<dyn std::error::Error + Send + Sync>).into(),
+            })
+            .map_err(|e| (e, request.transaction_id))?;
+
+        Some(key)
+    } else {
+        None
+    };
+
     let info_hash = request.info_hash.into();
-    let remote_client_ip = remote_addr.ip();
+
+    // The IP in the request is only used for clients in the trusted networks
+    let announced_ip = Some(Ipv4Addr::from(request.ip_address))
+        .filter(|ip| !ip.is_unspecified())
+        .map(IpAddr::V4);
+    let remote_client_ip = tracker.resolve_peer_ip(&remote_addr.ip(), announced_ip);
+
+    // IP blocklist. The requests from blocked source IPs are dropped before
+    // they get here, but the announced IP can be blocked too.
+    tracker
+        .check_ip(&remote_client_ip)
+        .map_err(|e| Error::TrackerError {
+            source: (Arc::new(e) as Arc<dyn std::error::Error + Send + Sync>).into(),
+        })
+        .map_err(|e| (e, request.transaction_id))?;
 
     // Authorization
     tracker
-        .authorize(&info_hash)
+        .authorize(&info_hash, authenticated_key.as_ref())
         .await
         .map_err(|e| Error::TrackerError {
             source: (Arc::new(e) as Arc<dyn std::error::Error + Send + Sync>).into(),
         })
         .map_err(|e| (e, request.transaction_id))?;
 
+    // Client filter
+    tracker
+        .check_client(&request.peer_id)
+        .map_err(|e| Error::TrackerError {
+            source: (Arc::new(e) as Arc<dyn std::error::Error + Send + Sync>).into(),
+        })
+        .map_err(|e| (e, request.transaction_id))?;
+
     let mut peer = peer_builder::from_request(request, &remote_client_ip);
-    let peers_wanted: PeersWanted = i32::from(request.peers_wanted.0).into();
+    let announce_policy = tracker.get_announce_policy();
+    let peers_wanted =
+        PeersWanted::from(i32::from(request.peers_wanted.0)).at_most(announce_policy.numwant, announce_policy.numwant_max_udp);
+
+    let response = match tracker.check_announce_interval(&info_hash, &peer) {
+        Ok(()) => {
+            let response = match &authenticated_key {
+                Some(key) => tracker.announce_with_key(key, &info_hash, &mut peer, &remote_client_ip, &peers_wanted),
+                None => tracker.announce(&info_hash, &mut peer, &remote_client_ip, &peers_wanted),
+            };
 
-    let response = tracker.announce(&info_hash, &mut peer, &remote_client_ip, &peers_wanted);
+            tracker
+                .send_stats_event(statistics::Event::udp(
+                    server_socket_addr,
+                    &remote_addr,
+                    RequestKind::Announce,
+                ))
+                .await;
 
-    match remote_client_ip {
-        IpAddr::V4(_) => {
-            tracker.send_stats_event(statistics::Event::Udp4Announce).await;
+            response
         }
-        IpAddr::V6(_) => {
-            tracker.send_stats_event(statistics::Event::Udp6Announce).await;
+        Err(error) => {
+            tracker
+                .send_stats_event(statistics::Event::udp(
+                    server_socket_addr,
+                    &remote_addr,
+                    RequestKind::AnnounceTooFrequent,
+                ))
+                .await;
+
+            match announce_policy.interval_min_action {
+                IntervalMinAction::Cached => tracker.get_announce_data(&info_hash, &mut peer, &remote_client_ip, &peers_wanted),
+                IntervalMinAction::Disabled | IntervalMinAction::Reject => {
+                    return Err((
+                        Error::TrackerError {
+                            source: (Arc::new(error) as Arc<dyn std::error::Error + Send + Sync>).into(),
+                        },
+                        request.transaction_id,
+                    ))
+                }
+            }
         }
-    }
+    };
 
     #[allow(clippy::cast_possible_truncation)]
     if remote_addr.is_ipv4() {
@@ -217,10 +442,10 @@ pub async fn handle_announce(
                 .peers
                 .iter()
                 .filter_map(|peer| {
-                    if let IpAddr::V4(ip) = peer.peer_addr.ip() {
+                    if let Some(SocketAddr::V4(addr)) = peer.ipv4_addr() {
                         Some(ResponsePeer::<Ipv4AddrBytes> {
-                            ip_address: ip.into(),
-                            port: Port(peer.peer_addr.port().into()),
+                            ip_address: (*addr.ip()).into(),
+                            port: Port(addr.port().into()),
                         })
                     } else {
                         None
@@ -242,10 +467,10 @@ pub async fn handle_announce(
                 .peers
                 .iter()
                 .filter_map(|peer| {
-                    if let IpAddr::V6(ip) = peer.peer_addr.ip() {
+                    if let Some(SocketAddr::V6(addr)) = peer.ipv6_addr() {
                         Some(ResponsePeer::<Ipv6AddrBytes> {
-                            ip_address: ip.into(),
-                            port: Port(peer.peer_addr.port().into()),
+                            ip_address: (*addr.ip()).into(),
+                            port: Port(addr.port().into()),
                         })
                     } else {
                         None
@@ -261,6 +486,10 @@ pub async fn handle_announce(
 /// It handles the `Scrape` request. Refer to [`Scrape`](crate::servers::udp#scrape)
 /// request for more information.
 ///
+/// UDP scrape requests cannot carry an authentication key, so a private
+/// tracker always returns zeroed swarm metadata, like the HTTP tracker does
+/// for unauthenticated scrapes.
+///
 /// # Errors
 ///
 /// This function does not ever return an error.
@@ -270,6 +499,7 @@ pub async fn handle_scrape(
     request: &ScrapeRequest,
     tracker: &Tracker,
     cookie_valid_range: Range<f64>,
+    server_socket_addr: SocketAddr,
 ) -> Result<Response, (Error, TransactionId)> {
     tracing::Span::current()
         .record("transaction_id", request.transaction_id.0.to_string())
@@ -290,7 +520,11 @@ pub async fn handle_scrape(
         info_hashes.push((*info_hash).into());
     }
 
-    let scrape_data = tracker.scrape(&info_hashes).await;
+    let scrape_data = if tracker.requires_authentication() {
+        ScrapeData::zeroed(&info_hashes)
+    } else {
+        tracker.scrape(&info_hashes, None).await
+    };
 
     let mut torrent_stats: Vec<TorrentScrapeStatistics> = Vec::new();
 
@@ -310,14 +544,9 @@ pub async fn handle_scrape(
     }
 
     // send stats event
-    match remote_addr {
-        SocketAddr::V4(_) => {
-            tracker.send_stats_event(statistics::Event::Udp4Scrape).await;
-        }
-        SocketAddr::V6(_) => {
-            tracker.send_stats_event(statistics::Event::Udp6Scrape).await;
-        }
-    }
+    tracker
+        .send_stats_event(statistics::Event::udp(server_socket_addr, &remote_addr, RequestKind::Scrape))
+        .await;
 
     let response = ScrapeResponse {
         transaction_id: request.transaction_id,
@@ -334,6 +563,7 @@ async fn handle_error(
     cookie_valid_range: Range<f64>,
     e: &Error,
     transaction_id: Option<TransactionId>,
+    server_socket_addr: SocketAddr,
 ) -> Response {
     tracing::trace!("handle error");
 
@@ -358,14 +588,9 @@ async fn handle_error(
 
     if e.1.is_some() {
         // send stats event
-        match remote_addr {
-            SocketAddr::V4(_) => {
-                tracker.send_stats_event(statistics::Event::Udp4Error).await;
-            }
-            SocketAddr::V6(_) => {
-                tracker.send_stats_event(statistics::Event::Udp6Error).await;
-            }
-        }
+        tracker
+            .send_stats_event(statistics::Event::udp(server_socket_addr, &remote_addr, RequestKind::Error))
+            .await;
     }
 
     Response::from(ErrorResponse {
@@ -410,6 +635,10 @@ mod tests {
         initialized_tracker(&configuration::ephemeral_public())
     }
 
+    fn private_tracker() -> Arc<Tracker> {
+        initialized_tracker(&configuration::ephemeral_private())
+    }
+
     fn whitelisted_tracker() -> Arc<Tracker> {
         initialized_tracker(&configuration::ephemeral_listed())
     }
@@ -442,6 +671,10 @@ mod tests {
         SocketAddr::new(IpAddr::V6(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1)), 8080)
     }
 
+    fn sample_server_socket_addr() -> SocketAddr {
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), 6969)
+    }
+
     fn sample_issue_time() -> f64 {
         1_000_000_000_f64
     }
@@ -484,6 +717,12 @@ mod tests {
             self
         }
 
+        #[must_use]
+        pub fn with_key(mut self, key: i32) -> Self {
+            self.peer.key = Some(aquatic_udp_protocol::PeerKey::new(key));
+            self
+        }
+
         #[must_use]
         pub fn into(self) -> peer::Peer {
             self.peer
@@ -526,7 +765,7 @@ mod tests {
         use crate::servers::udp::handlers::handle_connect;
         use crate::servers::udp::handlers::tests::{
             public_tracker, sample_ipv4_remote_addr, sample_ipv4_remote_addr_fingerprint, sample_ipv6_remote_addr_fingerprint,
-            sample_issue_time,
+            sample_issue_time, sample_server_socket_addr,
         };
 
         fn sample_connect_request() -> ConnectRequest {
@@ -541,7 +780,14 @@ mod tests {
                 transaction_id: TransactionId(0i32.into()),
             };
 
-            let response = handle_connect(sample_ipv4_remote_addr(), &request, &public_tracker(), sample_issue_time()).await;
+            let response = handle_connect(
+                sample_ipv4_remote_addr(),
+                &request,
+                &public_tracker(),
+                sample_issue_time(),
+                sample_server_socket_addr(),
+            )
+            .await;
 
             assert_eq!(
                 response,
@@ -558,7 +804,14 @@ mod tests {
                 transaction_id: TransactionId(0i32.into()),
             };
 
-            let response = handle_connect(sample_ipv4_remote_addr(), &request, &public_tracker(), sample_issue_time()).await;
+            let response = handle_connect(
+                sample_ipv4_remote_addr(),
+                &request,
+                &public_tracker(),
+                sample_issue_time(),
+                sample_server_socket_addr(),
+            )
+            .await;
 
             assert_eq!(
                 response,
@@ -575,7 +828,14 @@ mod tests {
                 transaction_id: TransactionId(0i32.into()),
             };
 
-            let response = handle_connect(sample_ipv6_remote_addr(), &request, &public_tracker(), sample_issue_time()).await;
+            let response = handle_connect(
+                sample_ipv6_remote_addr(),
+                &request,
+                &public_tracker(),
+                sample_issue_time(),
+                sample_server_socket_addr(),
+            )
+            .await;
 
             assert_eq!(
                 response,
@@ -591,7 +851,11 @@ mod tests {
             let mut stats_event_sender_mock = statistics::MockEventSender::new();
             stats_event_sender_mock
                 .expect_send_event()
-                .with(eq(statistics::Event::Udp4Connect))
+                .with(eq(statistics::Event::udp(
+                    sample_server_socket_addr(),
+                    &sample_ipv4_remote_addr(),
+                    statistics::RequestKind::Connect,
+                )))
                 .times(1)
                 .returning(|_| Box::pin(future::ready(Some(Ok(())))));
             let stats_event_sender = Box::new(stats_event_sender_mock);
@@ -611,6 +875,7 @@ mod tests {
                 &sample_connect_request(),
                 &torrent_tracker,
                 sample_issue_time(),
+                sample_server_socket_addr(),
             )
             .await;
         }
@@ -620,7 +885,11 @@ mod tests {
             let mut stats_event_sender_mock = statistics::MockEventSender::new();
             stats_event_sender_mock
                 .expect_send_event()
-                .with(eq(statistics::Event::Udp6Connect))
+                .with(eq(statistics::Event::udp(
+                    sample_server_socket_addr(),
+                    &sample_ipv6_remote_addr(),
+                    statistics::RequestKind::Connect,
+                )))
                 .times(1)
                 .returning(|_| Box::pin(future::ready(Some(Ok(())))));
             let stats_event_sender = Box::new(stats_event_sender_mock);
@@ -638,6 +907,7 @@ mod tests {
                 &sample_connect_request(),
                 &torrent_tracker,
                 sample_issue_time(),
+                sample_server_socket_addr(),
             )
             .await;
         }
@@ -733,7 +1003,7 @@ mod tests {
             use crate::servers::udp::handlers::tests::announce_request::AnnounceRequestBuilder;
             use crate::servers::udp::handlers::tests::{
                 gen_remote_fingerprint, public_tracker, sample_cookie_valid_range, sample_ipv4_socket_address, sample_issue_time,
-                tracker_configuration, TorrentPeerBuilder,
+                sample_server_socket_addr, tracker_configuration, TorrentPeerBuilder,
             };
             use crate::servers::udp::handlers::{handle_announce, AnnounceResponseFixedData};
 
@@ -756,15 +1026,23 @@ mod tests {
                     .with_port(client_port)
                     .into();
 
-                handle_announce(remote_addr, &request, &tracker, sample_cookie_valid_range())
-                    .await
-                    .unwrap();
+                handle_announce(
+                    remote_addr,
+                    &request,
+                    None,
+                    &tracker,
+                    sample_cookie_valid_range(),
+                    sample_server_socket_addr(),
+                )
+                .await
+                .unwrap();
 
                 let peers = tracker.get_torrent_peers(&info_hash.0.into());
 
                 let expected_peer = TorrentPeerBuilder::new()
                     .with_peer_id(peer_id)
                     .with_peer_address(SocketAddr::new(IpAddr::V4(client_ip), client_port))
+                    .with_key(0)
                     .into();
 
                 assert_eq!(peers[0], Arc::new(expected_peer));
@@ -778,9 +1056,16 @@ mod tests {
                     .with_connection_id(make(gen_remote_fingerprint(&remote_addr), sample_issue_time()).unwrap())
                     .into();
 
-                let response = handle_announce(remote_addr, &request, &public_tracker(), sample_cookie_valid_range())
-                    .await
-                    .unwrap();
+                let response = handle_announce(
+                    remote_addr,
+                    &request,
+                    None,
+                    &public_tracker(),
+                    sample_cookie_valid_range(),
+                    sample_server_socket_addr(),
+                )
+                .await
+                .unwrap();
 
                 let empty_peer_vector: Vec<ResponsePeer<Ipv4AddrBytes>> = vec![];
                 assert_eq!(
@@ -823,15 +1108,86 @@ mod tests {
                     .with_port(client_port)
                     .into();
 
-                handle_announce(remote_addr, &request, &tracker, sample_cookie_valid_range())
-                    .await
-                    .unwrap();
+                handle_announce(
+                    remote_addr,
+                    &request,
+                    None,
+                    &tracker,
+                    sample_cookie_valid_range(),
+                    sample_server_socket_addr(),
+                )
+                .await
+                .unwrap();
 
                 let peers = tracker.get_torrent_peers(&info_hash.0.into());
 
                 assert_eq!(peers[0].peer_addr, SocketAddr::new(IpAddr::V4(remote_client_ip), client_port));
             }
 
+            #[tokio::test]
+            async fn the_tracker_should_use_the_peer_address_in_the_announce_request_when_the_remote_client_is_in_a_trusted_network(
+            ) {
+                let mut configuration = tracker_configuration();
+                configuration.core.net.trusted_networks = vec!["126.0.0.0/24".parse().unwrap()];
+                let tracker: Arc<core::Tracker> = core::services::tracker_factory(&configuration).into();
+
+                let info_hash = AquaticInfoHash([0u8; 20]);
+                let client_port = 8080;
+
+                let remote_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8081);
+                let peer_address = Ipv4Addr::new(126, 0, 1, 2);
+
+                let request = AnnounceRequestBuilder::default()
+                    .with_connection_id(make(gen_remote_fingerprint(&remote_addr), sample_issue_time()).unwrap())
+                    .with_info_hash(info_hash)
+                    .with_ip_address(peer_address)
+                    .with_port(client_port)
+                    .into();
+
+                handle_announce(
+                    remote_addr,
+                    &request,
+                    None,
+                    &tracker,
+                    sample_cookie_valid_range(),
+                    sample_server_socket_addr(),
+                )
+                .await
+                .unwrap();
+
+                let peers = tracker.get_torrent_peers(&info_hash.0.into());
+
+                assert_eq!(peers[0].peer_addr, SocketAddr::new(IpAddr::V4(peer_address), client_port));
+            }
+
+            #[tokio::test]
+            async fn the_tracker_should_reject_the_announce_when_the_peer_address_in_the_request_is_blocked() {
+                let mut configuration = tracker_configuration();
+                configuration.core.net.trusted_networks = vec!["126.0.0.0/24".parse().unwrap()];
+                let tracker: Arc<core::Tracker> = core::services::tracker_factory(&configuration).into();
+
+                tracker.add_blocked_network(&"126.0.1.0/24".parse().unwrap()).unwrap();
+
+                let remote_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), 8081);
+
+                let request = AnnounceRequestBuilder::default()
+                    .with_connection_id(make(gen_remote_fingerprint(&remote_addr), sample_issue_time()).unwrap())
+                    .with_ip_address(Ipv4Addr::new(126, 0, 1, 2))
+                    .into();
+
+                let response = handle_announce(
+                    remote_addr,
+                    &request,
+                    None,
+                    &tracker,
+                    sample_cookie_valid_range(),
+                    sample_server_socket_addr(),
+                )
+                .await;
+
+                assert!(response.is_err());
+            }
+
             fn add_a_torrent_peer_using_ipv6(tracker: &Arc<core::Tracker>) {
                 let info_hash = AquaticInfoHash([0u8; 20]);
 
@@ -843,6 +1199,7 @@ mod tests {
                 let peer_using_ipv6 = TorrentPeerBuilder::new()
                     .with_peer_id(peer_id)
                     .with_peer_address(SocketAddr::new(IpAddr::V6(client_ip_v6), client_port))
+                    .with_key(0)
                     .into();
 
                 tracker.upsert_peer_and_get_stats(&info_hash.0.into(), &peer_using_ipv6);
@@ -854,9 +1211,16 @@ mod tests {
                     .with_connection_id(make(gen_remote_fingerprint(&remote_addr), sample_issue_time()).unwrap())
                     .into();
 
-                handle_announce(remote_addr, &request, &tracker, sample_cookie_valid_range())
-                    .await
-                    .unwrap()
+                handle_announce(
+                    remote_addr,
+                    &request,
+                    None,
+                    &tracker,
+                    sample_cookie_valid_range(),
+                    sample_server_socket_addr(),
+                )
+                .await
+                .unwrap()
             }
 
             #[tokio::test]
@@ -881,7 +1245,11 @@ mod tests {
                 let mut stats_event_sender_mock = statistics::MockEventSender::new();
                 stats_event_sender_mock
                     .expect_send_event()
-                    .with(eq(statistics::Event::Udp4Announce))
+                    .with(eq(statistics::Event::udp(
+                        sample_server_socket_addr(),
+                        &sample_ipv4_socket_address(),
+                        statistics::RequestKind::Announce,
+                    )))
                     .times(1)
                     .returning(|_| Box::pin(future::ready(Some(Ok(())))));
                 let stats_event_sender = Box::new(stats_event_sender_mock);
@@ -898,8 +1266,10 @@ mod tests {
                 handle_announce(
                     sample_ipv4_socket_address(),
                     &AnnounceRequestBuilder::default().into(),
+                    None,
                     &tracker,
                     sample_cookie_valid_range(),
+                    sample_server_socket_addr(),
                 )
                 .await
                 .unwrap();
@@ -915,7 +1285,8 @@ mod tests {
                 use crate::servers::udp::handlers::handle_announce;
                 use crate::servers::udp::handlers::tests::announce_request::AnnounceRequestBuilder;
                 use crate::servers::udp::handlers::tests::{
-                    gen_remote_fingerprint, public_tracker, sample_cookie_valid_range, sample_issue_time, TorrentPeerBuilder,
+                    gen_remote_fingerprint, public_tracker, sample_cookie_valid_range, sample_issue_time,
+                    sample_server_socket_addr, TorrentPeerBuilder,
                 };
 
                 #[tokio::test]
@@ -937,9 +1308,16 @@ mod tests {
                         .with_port(client_port)
                         .into();
 
-                    handle_announce(remote_addr, &request, &tracker, sample_cookie_valid_range())
-                        .await
-                        .unwrap();
+                    handle_announce(
+                        remote_addr,
+                        &request,
+                        None,
+                        &tracker,
+                        sample_cookie_valid_range(),
+                        sample_server_socket_addr(),
+                    )
+                    .await
+                    .unwrap();
 
                     let peers = tracker.get_torrent_peers(&info_hash.0.into());
 
@@ -948,6 +1326,7 @@ mod tests {
                     let expected_peer = TorrentPeerBuilder::new()
                         .with_peer_id(peer_id)
                         .with_peer_address(SocketAddr::new(external_ip_in_tracker_configuration, client_port))
+                        .with_key(0)
                         .into();
 
                     assert_eq!(peers[0], Arc::new(expected_peer));
@@ -972,7 +1351,7 @@ mod tests {
             use crate::servers::udp::handlers::tests::announce_request::AnnounceRequestBuilder;
             use crate::servers::udp::handlers::tests::{
                 gen_remote_fingerprint, public_tracker, sample_cookie_valid_range, sample_ipv6_remote_addr, sample_issue_time,
-                tracker_configuration, TorrentPeerBuilder,
+                sample_server_socket_addr, tracker_configuration, TorrentPeerBuilder,
             };
             use crate::servers::udp::handlers::{handle_announce, AnnounceResponseFixedData};
 
@@ -996,15 +1375,23 @@ mod tests {
                     .with_port(client_port)
                     .into();
 
-                handle_announce(remote_addr, &request, &tracker, sample_cookie_valid_range())
-                    .await
-                    .unwrap();
+                handle_announce(
+                    remote_addr,
+                    &request,
+                    None,
+                    &tracker,
+                    sample_cookie_valid_range(),
+                    sample_server_socket_addr(),
+                )
+                .await
+                .unwrap();
 
                 let peers = tracker.get_torrent_peers(&info_hash.0.into());
 
                 let expected_peer = TorrentPeerBuilder::new()
                     .with_peer_id(peer_id)
                     .with_peer_address(SocketAddr::new(IpAddr::V6(client_ip_v6), client_port))
+                    .with_key(0)
                     .into();
 
                 assert_eq!(peers[0], Arc::new(expected_peer));
@@ -1021,9 +1408,16 @@ mod tests {
                     .with_connection_id(make(gen_remote_fingerprint(&remote_addr), sample_issue_time()).unwrap())
                     .into();
 
-                let response = handle_announce(remote_addr, &request, &public_tracker(), sample_cookie_valid_range())
-                    .await
-                    .unwrap();
+                let response = handle_announce(
+                    remote_addr,
+                    &request,
+                    None,
+                    &public_tracker(),
+                    sample_cookie_valid_range(),
+                    sample_server_socket_addr(),
+                )
+                .await
+                .unwrap();
 
                 let empty_peer_vector: Vec<ResponsePeer<Ipv6AddrBytes>> = vec![];
                 assert_eq!(
@@ -1066,9 +1460,16 @@ mod tests {
                     .with_port(client_port)
                     .into();
 
-                handle_announce(remote_addr, &request, &tracker, sample_cookie_valid_range())
-                    .await
-                    .unwrap();
+                handle_announce(
+                    remote_addr,
+                    &request,
+                    None,
+                    &tracker,
+                    sample_cookie_valid_range(),
+                    sample_server_socket_addr(),
+                )
+                .await
+                .unwrap();
 
                 let peers = tracker.get_torrent_peers(&info_hash.0.into());
 
@@ -1100,9 +1501,16 @@ mod tests {
                     .with_connection_id(make(gen_remote_fingerprint(&remote_addr), sample_issue_time()).unwrap())
                     .into();
 
-                handle_announce(remote_addr, &request, &tracker, sample_cookie_valid_range())
-                    .await
-                    .unwrap()
+                handle_announce(
+                    remote_addr,
+                    &request,
+                    None,
+                    &tracker,
+                    sample_cookie_valid_range(),
+                    sample_server_socket_addr(),
+                )
+                .await
+                .unwrap()
             }
 
             #[tokio::test]
@@ -1127,7 +1535,11 @@ mod tests {
                 let mut stats_event_sender_mock = statistics::MockEventSender::new();
                 stats_event_sender_mock
                     .expect_send_event()
-                    .with(eq(statistics::Event::Udp6Announce))
+                    .with(eq(statistics::Event::udp(
+                        sample_server_socket_addr(),
+                        &sample_ipv6_remote_addr(),
+                        statistics::RequestKind::Announce,
+                    )))
                     .times(1)
                     .returning(|_| Box::pin(future::ready(Some(Ok(())))));
                 let stats_event_sender = Box::new(stats_event_sender_mock);
@@ -1147,9 +1559,16 @@ mod tests {
                     .with_connection_id(make(gen_remote_fingerprint(&remote_addr), sample_issue_time()).unwrap())
                     .into();
 
-                handle_announce(remote_addr, &announce_request, &tracker, sample_cookie_valid_range())
-                    .await
-                    .unwrap();
+                handle_announce(
+                    remote_addr,
+                    &announce_request,
+                    None,
+                    &tracker,
+                    sample_cookie_valid_range(),
+                    sample_server_socket_addr(),
+                )
+                .await
+                .unwrap();
             }
 
             mod from_a_loopback_ip {
@@ -1164,7 +1583,8 @@ mod tests {
                 use crate::servers::udp::handlers::handle_announce;
                 use crate::servers::udp::handlers::tests::announce_request::AnnounceRequestBuilder;
                 use crate::servers::udp::handlers::tests::{
-                    gen_remote_fingerprint, sample_cookie_valid_range, sample_issue_time, TrackerConfigurationBuilder,
+                    gen_remote_fingerprint, sample_cookie_valid_range, sample_issue_time, sample_server_socket_addr,
+                    TrackerConfigurationBuilder,
                 };
 
                 #[tokio::test]
@@ -1194,9 +1614,16 @@ mod tests {
                         .with_port(client_port)
                         .into();
 
-                    handle_announce(remote_addr, &request, &tracker, sample_cookie_valid_range())
-                        .await
-                        .unwrap();
+                    handle_announce(
+                        remote_addr,
+                        &request,
+                        None,
+                        &tracker,
+                        sample_cookie_valid_range(),
+                        sample_server_socket_addr(),
+                    )
+                    .await
+                    .unwrap();
 
                     let peers = tracker.get_torrent_peers(&info_hash.0.into());
 
@@ -1213,6 +1640,228 @@ mod tests {
                 }
             }
         }
+
+        mod with_a_private_tracker {
+
+            use std::time::Duration;
+
+            use aquatic_udp_protocol::{AnnounceRequest, NumberOfBytes};
+
+            use crate::servers::udp::connection_cookie::make;
+            use crate::servers::udp::error::Error;
+            use crate::servers::udp::extensions::UrlData;
+            use crate::servers::udp::handlers::handle_announce;
+            use crate::servers::udp::handlers::tests::announce_request::AnnounceRequestBuilder;
+            use crate::servers::udp::handlers::tests::{
+                private_tracker, sample_cookie_valid_range, sample_ipv4_remote_addr, sample_ipv4_remote_addr_fingerprint,
+                sample_issue_time, sample_server_socket_addr,
+            };
+
+            #[tokio::test]
+            async fn it_should_announce_a_peer_when_the_request_includes_a_valid_key() {
+                let tracker = private_tracker();
+
+                let peer_key = tracker.generate_auth_key(Some(Duration::from_secs(100))).await.unwrap();
+
+                let request = AnnounceRequestBuilder::default()
+                    .with_connection_id(make(sample_ipv4_remote_addr_fingerprint(), sample_issue_time()).unwrap())
+                    .into();
+
+                let url_data = UrlData::from(format!("/announce/{}", peer_key.key).as_str());
+
+                let response = handle_announce(
+                    sample_ipv4_remote_addr(),
+                    &request,
+                    Some(&url_data),
+                    &tracker,
+                    sample_cookie_valid_range(),
+                    sample_server_socket_addr(),
+                )
+                .await;
+
+                assert!(response.is_ok());
+            }
+
+            #[tokio::test]
+            async fn it_should_add_the_transfer_of_the_announce_to_the_stats_of_the_key() {
+                let tracker = private_tracker();
+
+                let peer_key = tracker.generate_auth_key(Some(Duration::from_secs(100))).await.unwrap();
+
+                let mut request: AnnounceRequest = AnnounceRequestBuilder::default()
+                    .with_connection_id(make(sample_ipv4_remote_addr_fingerprint(), sample_issue_time()).unwrap())
+                    .into();
+                request.bytes_uploaded = NumberOfBytes::new(200);
+                request.bytes_downloaded = NumberOfBytes::new(100);
+
+                let url_data = UrlData::from(format!("/announce/{}", peer_key.key).as_str());
+
+                handle_announce(
+                    sample_ipv4_remote_addr(),
+                    &request,
+                    Some(&url_data),
+                    &tracker,
+                    sample_cookie_valid_range(),
+                    sample_server_socket_addr(),
+                )
+                .await
+                .unwrap();
+
+                let stats = tracker.get_key_stats(&peer_key.key());
+
+                assert_eq!((stats.uploaded, stats.downloaded), (200, 100));
+            }
+
+            #[tokio::test]
+            async fn it_should_fail_when_the_request_does_not_include_a_key() {
+                let tracker = private_tracker();
+
+                let request = AnnounceRequestBuilder::default()
+                    .with_connection_id(make(sample_ipv4_remote_addr_fingerprint(), sample_issue_time()).unwrap())
+                    .into();
+
+                let response = handle_announce(
+                    sample_ipv4_remote_addr(),
+                    &request,
+                    None,
+                    &tracker,
+                    sample_cookie_valid_range(),
+                    sample_server_socket_addr(),
+                )
+                .await;
+
+                assert!(matches!(response, Err((Error::TrackerAuthenticationRequired { .. }, _))));
+            }
+
+            #[tokio::test]
+            async fn it_should_fail_when_the_key_has_an_invalid_format() {
+                let tracker = private_tracker();
+
+                let request = AnnounceRequestBuilder::default()
+                    .with_connection_id(make(sample_ipv4_remote_addr_fingerprint(), sample_issue_time()).unwrap())
+                    .into();
+
+                let response = handle_announce(
+                    sample_ipv4_remote_addr(),
+                    &request,
+                    Some(&UrlData::from("/announce/INVALID")),
+                    &tracker,
+                    sample_cookie_valid_range(),
+                    sample_server_socket_addr(),
+                )
+                .await;
+
+                assert!(matches!(response, Err((Error::BadRequest { .. }, _))));
+            }
+
+            #[tokio::test]
+            async fn it_should_fail_when_the_key_is_not_registered() {
+                let tracker = private_tracker();
+
+                let request = AnnounceRequestBuilder::default()
+                    .with_connection_id(make(sample_ipv4_remote_addr_fingerprint(), sample_issue_time()).unwrap())
+                    .into();
+
+                let response = handle_announce(
+                    sample_ipv4_remote_addr(),
+                    &request,
+                    Some(&UrlData::from("/announce/YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ")),
+                    &tracker,
+                    sample_cookie_valid_range(),
+                    sample_server_socket_addr(),
+                )
+                .await;
+
+                assert!(matches!(response, Err((Error::TrackerError { .. }, _))));
+            }
+        }
+
+        mod with_a_min_announce_interval {
+
+            use torrust_tracker_configuration::IntervalMinAction;
+
+            use crate::core::services::tracker_factory;
+            use crate::core::Tracker;
+            use crate::servers::udp::connection_cookie::make;
+            use crate::servers::udp::error::Error;
+            use crate::servers::udp::handlers::handle_announce;
+            use crate::servers::udp::handlers::tests::announce_request::AnnounceRequestBuilder;
+            use crate::servers::udp::handlers::tests::{
+                default_testing_tracker_configuration, sample_cookie_valid_range, sample_ipv4_remote_addr,
+                sample_ipv4_remote_addr_fingerprint, sample_issue_time, sample_server_socket_addr,
+            };
+
+            fn tracker_with_interval_min_action(action: IntervalMinAction) -> Tracker {
+                let mut configuration = default_testing_tracker_configuration();
+                configuration.core.announce_policy.interval_min_action = action;
+                tracker_factory(&configuration)
+            }
+
+            #[tokio::test]
+            async fn it_should_reject_a_second_announce_received_before_the_min_interval() {
+                let tracker = tracker_with_interval_min_action(IntervalMinAction::Reject);
+
+                let request = AnnounceRequestBuilder::default()
+                    .with_connection_id(make(sample_ipv4_remote_addr_fingerprint(), sample_issue_time()).unwrap())
+                    .into();
+
+                let first_response = handle_announce(
+                    sample_ipv4_remote_addr(),
+                    &request,
+                    None,
+                    &tracker,
+                    sample_cookie_valid_range(),
+                    sample_server_socket_addr(),
+                )
+                .await;
+
+                let second_response = handle_announce(
+                    sample_ipv4_remote_addr(),
+                    &request,
+                    None,
+                    &tracker,
+                    sample_cookie_valid_range(),
+                    sample_server_socket_addr(),
+                )
+                .await;
+
+                assert!(first_response.is_ok());
+                assert!(matches!(second_response, Err((Error::TrackerError { .. }, _))));
+            }
+
+            #[tokio::test]
+            async fn it_should_return_the_cached_announce_data_for_a_second_announce_received_before_the_min_interval() {
+                let tracker = tracker_with_interval_min_action(IntervalMinAction::Cached);
+
+                let request = AnnounceRequestBuilder::default()
+                    .with_connection_id(make(sample_ipv4_remote_addr_fingerprint(), sample_issue_time()).unwrap())
+                    .into();
+
+                let first_response = handle_announce(
+                    sample_ipv4_remote_addr(),
+                    &request,
+                    None,
+                    &tracker,
+                    sample_cookie_valid_range(),
+                    sample_server_socket_addr(),
+                )
+                .await
+                .unwrap();
+
+                let second_response = handle_announce(
+                    sample_ipv4_remote_addr(),
+                    &request,
+                    None,
+                    &tracker,
+                    sample_cookie_valid_range(),
+                    sample_server_socket_addr(),
+                )
+                .await
+                .unwrap();
+
+                assert_eq!(second_response, first_response);
+            }
+        }
     }
 
     mod scrape_request {
@@ -1229,7 +1878,7 @@ mod tests {
         use crate::servers::udp::connection_cookie::make;
         use crate::servers::udp::handlers::handle_scrape;
         use crate::servers::udp::handlers::tests::{
-            public_tracker, sample_cookie_valid_range, sample_ipv4_remote_addr, sample_issue_time,
+            public_tracker, sample_cookie_valid_range, sample_ipv4_remote_addr, sample_issue_time, sample_server_socket_addr,
         };
 
         fn zeroed_torrent_statistics() -> TorrentScrapeStatistics {
@@ -1253,9 +1902,15 @@ mod tests {
                 info_hashes,
             };
 
-            let response = handle_scrape(remote_addr, &request, &public_tracker(), sample_cookie_valid_range())
-                .await
-                .unwrap();
+            let response = handle_scrape(
+                remote_addr,
+                &request,
+                &public_tracker(),
+                sample_cookie_valid_range(),
+                sample_server_socket_addr(),
+            )
+            .await
+            .unwrap();
 
             let expected_torrent_stats = vec![zeroed_torrent_statistics()];
 
@@ -1298,9 +1953,15 @@ mod tests {
 
             let request = build_scrape_request(&remote_addr, &info_hash);
 
-            handle_scrape(remote_addr, &request, &tracker, sample_cookie_valid_range())
-                .await
-                .unwrap()
+            handle_scrape(
+                remote_addr,
+                &request,
+                &tracker,
+                sample_cookie_valid_range(),
+                sample_server_socket_addr(),
+            )
+            .await
+            .unwrap()
         }
 
         fn match_scrape_response(response: Response) -> Option<ScrapeResponse> {
@@ -1339,7 +2000,9 @@ mod tests {
             use crate::servers::udp::handlers::tests::scrape_request::{
                 add_a_seeder, build_scrape_request, match_scrape_response, zeroed_torrent_statistics,
             };
-            use crate::servers::udp::handlers::tests::{sample_cookie_valid_range, sample_ipv4_remote_addr, whitelisted_tracker};
+            use crate::servers::udp::handlers::tests::{
+                sample_cookie_valid_range, sample_ipv4_remote_addr, sample_server_socket_addr, whitelisted_tracker,
+            };
 
             #[tokio::test]
             async fn should_return_the_torrent_statistics_when_the_requested_torrent_is_whitelisted() {
@@ -1355,9 +2018,15 @@ mod tests {
                 let request = build_scrape_request(&remote_addr, &info_hash);
 
                 let torrent_stats = match_scrape_response(
-                    handle_scrape(remote_addr, &request, &tracker, sample_cookie_valid_range())
-                        .await
-                        .unwrap(),
+                    handle_scrape(
+                        remote_addr,
+                        &request,
+                        &tracker,
+                        sample_cookie_valid_range(),
+                        sample_server_socket_addr(),
+                    )
+                    .await
+                    .unwrap(),
                 )
                 .unwrap();
 
@@ -1382,9 +2051,15 @@ mod tests {
                 let request = build_scrape_request(&remote_addr, &info_hash);
 
                 let torrent_stats = match_scrape_response(
-                    handle_scrape(remote_addr, &request, &tracker, sample_cookie_valid_range())
-                        .await
-                        .unwrap(),
+                    handle_scrape(
+                        remote_addr,
+                        &request,
+                        &tracker,
+                        sample_cookie_valid_range(),
+                        sample_server_socket_addr(),
+                    )
+                    .await
+                    .unwrap(),
                 )
                 .unwrap();
 
@@ -1394,6 +2069,24 @@ mod tests {
             }
         }
 
+        mod with_a_private_tracker {
+            use crate::servers::udp::handlers::tests::private_tracker;
+            use crate::servers::udp::handlers::tests::scrape_request::{
+                add_a_sample_seeder_and_scrape, match_scrape_response, zeroed_torrent_statistics,
+            };
+
+            #[tokio::test]
+            async fn should_return_zeroed_statistics_because_udp_scrapes_cannot_be_authenticated() {
+                let tracker = private_tracker();
+
+                let torrent_stats = match_scrape_response(add_a_sample_seeder_and_scrape(tracker.clone()).await);
+
+                let expected_torrent_stats = vec![zeroed_torrent_statistics()];
+
+                assert_eq!(torrent_stats.unwrap().torrent_stats, expected_torrent_stats);
+            }
+        }
+
         fn sample_scrape_request(remote_addr: &SocketAddr) -> ScrapeRequest {
             let info_hash = InfoHash([0u8; 20]);
             let info_hashes = vec![info_hash];
@@ -1415,7 +2108,7 @@ mod tests {
             use crate::core::{self, statistics};
             use crate::servers::udp::handlers::handle_scrape;
             use crate::servers::udp::handlers::tests::{
-                sample_cookie_valid_range, sample_ipv4_remote_addr, tracker_configuration,
+                sample_cookie_valid_range, sample_ipv4_remote_addr, sample_server_socket_addr, tracker_configuration,
             };
 
             #[tokio::test]
@@ -1423,7 +2116,11 @@ mod tests {
                 let mut stats_event_sender_mock = statistics::MockEventSender::new();
                 stats_event_sender_mock
                     .expect_send_event()
-                    .with(eq(statistics::Event::Udp4Scrape))
+                    .with(eq(statistics::Event::udp(
+                        sample_server_socket_addr(),
+                        &sample_ipv4_remote_addr(),
+                        statistics::RequestKind::Scrape,
+                    )))
                     .times(1)
                     .returning(|_| Box::pin(future::ready(Some(Ok(())))));
                 let stats_event_sender = Box::new(stats_event_sender_mock);
@@ -1443,6 +2140,7 @@ mod tests {
                     &sample_scrape_request(&remote_addr),
                     &tracker,
                     sample_cookie_valid_range(),
+                    sample_server_socket_addr(),
                 )
                 .await
                 .unwrap();
@@ -1459,7 +2157,7 @@ mod tests {
             use crate::core::{self, statistics};
             use crate::servers::udp::handlers::handle_scrape;
             use crate::servers::udp::handlers::tests::{
-                sample_cookie_valid_range, sample_ipv6_remote_addr, tracker_configuration,
+                sample_cookie_valid_range, sample_ipv6_remote_addr, sample_server_socket_addr, tracker_configuration,
             };
 
             #[tokio::test]
@@ -1467,7 +2165,11 @@ mod tests {
                 let mut stats_event_sender_mock = statistics::MockEventSender::new();
                 stats_event_sender_mock
                     .expect_send_event()
-                    .with(eq(statistics::Event::Udp6Scrape))
+                    .with(eq(statistics::Event::udp(
+                        sample_server_socket_addr(),
+                        &sample_ipv6_remote_addr(),
+                        statistics::RequestKind::Scrape,
+                    )))
                     .times(1)
                     .returning(|_| Box::pin(future::ready(Some(Ok(())))));
                 let stats_event_sender = Box::new(stats_event_sender_mock);
@@ -1487,10 +2189,105 @@ mod tests {
                     &sample_scrape_request(&remote_addr),
                     &tracker,
                     sample_cookie_valid_range(),
+                    sample_server_socket_addr(),
                 )
                 .await
                 .unwrap();
             }
         }
     }
+
+    mod rate_limited_request {
+
+        use std::future;
+        use std::sync::Arc;
+
+        use aquatic_udp_protocol::{ConnectRequest, ErrorResponse, Request, Response, TransactionId};
+        use mockall::predicate::eq;
+        use torrust_tracker_configuration::UdpRateLimitAction;
+
+        use super::{public_tracker, sample_ipv4_remote_addr, sample_server_socket_addr, tracker_configuration};
+        use crate::core::{self, statistics};
+        use crate::servers::udp::handlers::handle_rate_limited;
+        use crate::servers::udp::RawRequest;
+
+        fn sample_raw_connect_request() -> RawRequest {
+            let mut payload = Vec::new();
+
+            Request::from(ConnectRequest {
+                transaction_id: TransactionId(123i32.into()),
+            })
+            .write_bytes(&mut payload)
+            .unwrap();
+
+            RawRequest {
+                payload,
+                from: sample_ipv4_remote_addr(),
+            }
+        }
+
+        #[tokio::test]
+        async fn it_should_not_respond_when_the_requests_over_the_limit_are_dropped() {
+            let response = handle_rate_limited(
+                &sample_raw_connect_request(),
+                &public_tracker(),
+                sample_server_socket_addr(),
+                UdpRateLimitAction::Drop,
+            )
+            .await;
+
+            assert_eq!(response, None);
+        }
+
+        #[tokio::test]
+        async fn it_should_respond_with_an_error_containing_the_request_transaction_id() {
+            let response = handle_rate_limited(
+                &sample_raw_connect_request(),
+                &public_tracker(),
+                sample_server_socket_addr(),
+                UdpRateLimitAction::Error,
+            )
+            .await;
+
+            assert_eq!(
+                response,
+                Some(Response::from(ErrorResponse {
+                    transaction_id: TransactionId(123i32.into()),
+                    message: "rate limit exceeded".into(),
+                }))
+            );
+        }
+
+        #[tokio::test]
+        async fn it_should_send_the_udp4_rate_limited_event() {
+            let mut stats_event_sender_mock = statistics::MockEventSender::new();
+            stats_event_sender_mock
+                .expect_send_event()
+                .with(eq(statistics::Event::udp(
+                    sample_server_socket_addr(),
+                    &sample_ipv4_remote_addr(),
+                    statistics::RequestKind::RateLimited,
+                )))
+                .times(1)
+                .returning(|_| Box::pin(future::ready(Some(Ok(())))));
+            let stats_event_sender = Box::new(stats_event_sender_mock);
+
+            let tracker = Arc::new(
+                core::Tracker::new(
+                    &tracker_configuration().core,
+                    Some(stats_event_sender),
+                    statistics::Repo::new(),
+                )
+                .unwrap(),
+            );
+
+            handle_rate_limited(
+                &sample_raw_connect_request(),
+                &tracker,
+                sample_server_socket_addr(),
+                UdpRateLimitAction::Drop,
+            )
+            .await;
+        }
+    }
 }
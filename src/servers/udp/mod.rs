@@ -21,8 +21,8 @@
 //! and to [BEP 41. UDP Tracker Protocol Extensions](https://www.bittorrent.org/beps/bep_0041.html)
 //! for more information about the UDP tracker protocol.
 //!
-//! > **NOTICE**: [BEP-41](https://www.bittorrent.org/beps/bep_0041.html) is not
-//! > implemented yet.
+//! > **NOTICE**: [BEP-41](https://www.bittorrent.org/beps/bep_0041.html) options
+//! > are only parsed for `announce` requests.
 //!
 //! > **NOTICE**: we are using the [`aquatic_udp_protocol`](https://crates.io/crates/aquatic_udp_protocol)
 //! > crate so requests and responses are handled by it.
@@ -339,7 +339,10 @@
 //! `port`             | [`Port`](aquatic_udp_protocol::common::Port)                    | `17548`
 //!
 //! > **NOTICE**: the `peers_wanted` field is the `num_want` field in the UDP
-//! > packet.
+//! > packet. Zero or negative values mean the default number of peers. The
+//! > tracker never returns more than
+//! > [`numwant_max_udp`](torrust_tracker_configuration::AnnouncePolicy::numwant_max_udp)
+//! > peers.
 //!
 //! We are using a wrapper struct for the aquatic [`AnnounceRequest`](aquatic_udp_protocol::request::AnnounceRequest)
 //! struct, because we have our internal [`InfoHash`](bittorrent_primitives::info_hash::InfoHash)
@@ -619,7 +622,22 @@
 //! ## Extensions
 //!
 //! Extensions described in [BEP 41. UDP Tracker Protocol Extensions](https://www.bittorrent.org/beps/bep_0041.html)
-//! are not supported yet.
+//! are supported for `announce` requests. The tracker parses the options
+//! appended to the request and rebuilds the request path and query from the
+//! `URLData` options. Malformed options are rejected with an error response.
+//!
+//! Clients using a private tracker must include the authentication key in the
+//! tracker URL path, for example:
+//!
+//! ```text
+//! udp://tracker.example.com:6969/announce/YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ
+//! ```
+//!
+//! The client sends the path in the `URLData` option appended to the
+//! `announce` request. Refer to [`extensions`] for more information.
+//!
+//! `scrape` requests cannot carry the key, so a private tracker always
+//! responds to them with zeroed swarm metadata.
 //!
 //! ## Links
 //!
@@ -640,6 +658,7 @@ use std::net::SocketAddr;
 
 pub mod connection_cookie;
 pub mod error;
+pub mod extensions;
 pub mod handlers;
 pub mod peer_builder;
 pub mod server;
@@ -17,10 +17,13 @@ pub fn from_request(announce_request: &aquatic_udp_protocol::AnnounceRequest, pe
     peer::Peer {
         peer_id: announce_request.peer_id,
         peer_addr: SocketAddr::new(*peer_ip, announce_request.port.0.into()),
+        alternative_addr: None,
+        key: Some(announce_request.key),
         updated: CurrentClock::now(),
         uploaded: announce_request.bytes_uploaded,
         downloaded: announce_request.bytes_downloaded,
         left: announce_request.bytes_left,
         event: announce_request.event.into(),
+        upload_only: false,
     }
 }
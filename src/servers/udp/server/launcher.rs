@@ -7,6 +7,8 @@ use derive_more::Constructor;
 use futures_util::StreamExt;
 use tokio::select;
 use tokio::sync::oneshot;
+use torrust_tracker_clock::clock::Time as _;
+use torrust_tracker_configuration::UdpRateLimit;
 use tracing::instrument;
 
 use super::request_buffer::ActiveRequests;
@@ -17,8 +19,10 @@ use crate::servers::registar::ServiceHealthCheckJob;
 use crate::servers::signals::{shutdown_signal_with_message, Halted};
 use crate::servers::udp::server::bound_socket::BoundSocket;
 use crate::servers::udp::server::processor::Processor;
+use crate::servers::udp::server::rate_limiter::RateLimiter;
 use crate::servers::udp::server::receiver::Receiver;
 use crate::servers::udp::UDP_TRACKER_LOG_TARGET;
+use crate::CurrentClock;
 
 /// A UDP server instance launcher.
 #[derive(Constructor)]
@@ -31,23 +35,18 @@ impl Launcher {
     ///
     /// It panics if unable to bind to udp socket, and get the address from the udp socket.
     /// It panics if unable to send address of socket.
-    /// It panics if the udp server is loaded when the tracker is private.
     ///
     #[instrument(skip(tracker, bind_to, tx_start, rx_halt))]
     pub async fn run_with_graceful_shutdown(
         tracker: Arc<Tracker>,
         bind_to: SocketAddr,
         cookie_lifetime: Duration,
+        rate_limit: Option<UdpRateLimit>,
         tx_start: oneshot::Sender<Started>,
         rx_halt: oneshot::Receiver<Halted>,
     ) {
         tracing::info!(target: UDP_TRACKER_LOG_TARGET, "Starting on: {bind_to}");
 
-        if tracker.requires_authentication() {
-            tracing::error!("udp services cannot be used for private trackers");
-            panic!("it should not use udp if using authentication");
-        }
-
         let socket = tokio::time::timeout(Duration::from_millis(5000), BoundSocket::new(bind_to))
             .await
             .expect("it should bind to the socket within five seconds");
@@ -73,7 +72,7 @@ impl Launcher {
             let local_addr = local_udp_url.clone();
             tokio::task::spawn(async move {
                 tracing::debug!(target: UDP_TRACKER_LOG_TARGET, local_addr, "Udp::run_with_graceful_shutdown::task (listening...)");
-                let () = Self::run_udp_server_main(receiver, tracker.clone(), cookie_lifetime).await;
+                let () = Self::run_udp_server_main(receiver, tracker.clone(), cookie_lifetime, rate_limit).await;
             })
         };
 
@@ -111,8 +110,14 @@ impl Launcher {
     }
 
     #[instrument(skip(receiver, tracker))]
-    async fn run_udp_server_main(mut receiver: Receiver, tracker: Arc<Tracker>, cookie_lifetime: Duration) {
+    async fn run_udp_server_main(
+        mut receiver: Receiver,
+        tracker: Arc<Tracker>,
+        cookie_lifetime: Duration,
+        rate_limit: Option<UdpRateLimit>,
+    ) {
         let active_requests = &mut ActiveRequests::default();
+        let mut rate_limiter = rate_limit.map(RateLimiter::new);
 
         let addr = receiver.bound_socket_address();
         let local_addr = format!("udp://{addr}");
@@ -140,6 +145,14 @@ impl Launcher {
                     }
                 };
 
+                if let Some(rate_limiter) = rate_limiter.as_mut() {
+                    if !rate_limiter.check(&req.from.ip(), CurrentClock::now()) {
+                        tracing::debug!(target: UDP_TRACKER_LOG_TARGET, local_addr, from = %req.from, "Udp::run_udp_server::loop (rate limited)");
+                        processor.reject_request(req, rate_limiter.exceeded_action()).await;
+                        continue;
+                    }
+                }
+
                 // We spawn the new task even if there active requests buffer is
                 // full. This could seem counterintuitive because we are accepting
                 // more request and consuming more memory even if the server is
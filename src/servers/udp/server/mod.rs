@@ -9,6 +9,7 @@ use super::RawRequest;
 pub mod bound_socket;
 pub mod launcher;
 pub mod processor;
+pub mod rate_limiter;
 pub mod receiver;
 pub mod request_buffer;
 pub mod spawner;
@@ -76,7 +77,7 @@ mod tests {
         let stopped = Server::new(Spawner::new(bind_to));
 
         let started = stopped
-            .start(tracker, register.give_form(), config.cookie_lifetime)
+            .start(tracker, register.give_form(), config.cookie_lifetime, config.rate_limit)
             .await
             .expect("it should start the server");
 
@@ -98,7 +99,7 @@ mod tests {
         let stopped = Server::new(Spawner::new(bind_to));
 
         let started = stopped
-            .start(tracker, register.give_form(), config.cookie_lifetime)
+            .start(tracker, register.give_form(), config.cookie_lifetime, config.rate_limit)
             .await
             .expect("it should start the server");
 
@@ -3,6 +3,7 @@ use std::net::SocketAddr;
 use std::sync::Arc;
 
 use aquatic_udp_protocol::Response;
+use torrust_tracker_configuration::UdpRateLimitAction;
 use tracing::{instrument, Level};
 
 use super::bound_socket::BoundSocket;
@@ -25,9 +26,20 @@ impl Processor {
         }
     }
 
+    /// It handles a request and sends the response.
+    ///
+    /// The requests from blocked IPs are dropped without response. Answering
+    /// them with an error would let spoofed requests reflect traffic to
+    /// other hosts.
     #[instrument(skip(self, request))]
     pub async fn process_request(self, request: RawRequest) {
         let from = request.from;
+
+        if self.tracker.check_ip(&from.ip()).is_err() {
+            tracing::debug!(%from, "dropped request from a blocked IP");
+            return;
+        }
+
         let response = handlers::handle_packet(
             request,
             &self.tracker,
@@ -38,6 +50,17 @@ impl Processor {
         self.send_response(from, response).await;
     }
 
+    /// It handles a request from a client that has exceeded the rate limit.
+    /// The request is dropped or answered with an error response, depending
+    /// on the `action`.
+    #[instrument(skip(self, request))]
+    pub async fn reject_request(self, request: RawRequest, action: UdpRateLimitAction) {
+        let from = request.from;
+        if let Some(response) = handlers::handle_rate_limited(&request, &self.tracker, self.socket.address(), action).await {
+            self.send_response(from, response).await;
+        }
+    }
+
     #[instrument(skip(self))]
     async fn send_response(self, target: SocketAddr, response: Response) {
         tracing::debug!("send response");
@@ -0,0 +1,307 @@
+//! Rate limiting per source IP for the UDP tracker.
+//!
+//! The limiter uses the [token bucket](https://en.wikipedia.org/wiki/Token_bucket)
+//! algorithm. Every source IP has a bucket, and all the IPs in the same subnet
+//! share another bucket. A request is only accepted when both buckets have at
+//! least one token left.
+//!
+//! Refer to the [`RateLimit`] configuration for the available options.
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::time::Duration;
+
+use torrust_tracker_configuration::{UdpRateLimit as RateLimit, UdpRateLimitAction};
+use torrust_tracker_primitives::DurationSinceUnixEpoch;
+
+/// Interval between removals of the buckets of the clients that are not
+/// sending requests.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// A token bucket rate limiter for the source IPs and subnets.
+pub struct RateLimiter {
+    config: RateLimit,
+    ips: HashMap<IpAddr, TokenBucket>,
+    subnets: HashMap<IpAddr, TokenBucket>,
+    last_prune: DurationSinceUnixEpoch,
+}
+
+impl RateLimiter {
+    #[must_use]
+    pub fn new(config: RateLimit) -> Self {
+        Self {
+            config,
+            ips: HashMap::new(),
+            subnets: HashMap::new(),
+            last_prune: DurationSinceUnixEpoch::ZERO,
+        }
+    }
+
+    /// What to do with the requests exceeding the limits.
+    #[must_use]
+    pub fn exceeded_action(&self) -> UdpRateLimitAction {
+        self.config.exceeded_action
+    }
+
+    /// It returns `true` if a request from the `ip` is allowed at the time
+    /// `now`, and takes a token from the IP and subnet buckets. It returns
+    /// `false` when the client has exceeded the limits.
+    pub fn check(&mut self, ip: &IpAddr, now: DurationSinceUnixEpoch) -> bool {
+        if now.saturating_sub(self.last_prune) >= PRUNE_INTERVAL {
+            self.prune(now);
+        }
+
+        let ip_limit = Limit::new(self.config.requests_per_second, self.config.burst);
+        let subnet_limit = Limit::new(self.config.subnet_requests_per_second, self.config.subnet_burst);
+
+        let ip_bucket = self
+            .ips
+            .entry(*ip)
+            .or_insert_with(|| TokenBucket::full(&ip_limit, now))
+            .refill(&ip_limit, now);
+
+        if !ip_bucket.has_tokens() {
+            return false;
+        }
+
+        if subnet_limit.is_enabled() {
+            let subnet = subnet(ip, self.config.ipv4_subnet_prefix_len, self.config.ipv6_subnet_prefix_len);
+
+            let subnet_bucket = self
+                .subnets
+                .entry(subnet)
+                .or_insert_with(|| TokenBucket::full(&subnet_limit, now))
+                .refill(&subnet_limit, now);
+
+            if !subnet_bucket.has_tokens() {
+                return false;
+            }
+
+            subnet_bucket.take();
+        }
+
+        ip_bucket.take();
+
+        true
+    }
+
+    /// It removes the buckets that would be full by now. Those clients have
+    /// not sent requests for a while, so the buckets can be created again.
+    fn prune(&mut self, now: DurationSinceUnixEpoch) {
+        let ip_limit = Limit::new(self.config.requests_per_second, self.config.burst);
+        let subnet_limit = Limit::new(self.config.subnet_requests_per_second, self.config.subnet_burst);
+
+        self.ips.retain(|_, bucket| !bucket.refill(&ip_limit, now).is_full(&ip_limit));
+        self.subnets
+            .retain(|_, bucket| !bucket.refill(&subnet_limit, now).is_full(&subnet_limit));
+
+        self.last_prune = now;
+    }
+
+    /// Number of IPs and subnets with a bucket.
+    #[cfg(test)]
+    fn number_of_buckets(&self) -> (usize, usize) {
+        (self.ips.len(), self.subnets.len())
+    }
+}
+
+/// The refill rate and capacity of a bucket.
+struct Limit {
+    requests_per_second: f64,
+    burst: f64,
+}
+
+impl Limit {
+    fn new(requests_per_second: u32, burst: u32) -> Self {
+        Self {
+            requests_per_second: f64::from(requests_per_second),
+            burst: f64::from(burst),
+        }
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.requests_per_second > 0.0
+    }
+}
+
+struct TokenBucket {
+    tokens: f64,
+    updated: DurationSinceUnixEpoch,
+}
+
+impl TokenBucket {
+    fn full(limit: &Limit, now: DurationSinceUnixEpoch) -> Self {
+        Self {
+            tokens: limit.burst,
+            updated: now,
+        }
+    }
+
+    fn refill(&mut self, limit: &Limit, now: DurationSinceUnixEpoch) -> &mut Self {
+        let elapsed = now.saturating_sub(self.updated).as_secs_f64();
+
+        self.tokens = (self.tokens + elapsed * limit.requests_per_second).min(limit.burst);
+        self.updated = self.updated.max(now);
+
+        self
+    }
+
+    fn has_tokens(&self) -> bool {
+        self.tokens >= 1.0
+    }
+
+    fn is_full(&self, limit: &Limit) -> bool {
+        self.tokens >= limit.burst
+    }
+
+    fn take(&mut self) {
+        self.tokens -= 1.0;
+    }
+}
+
+/// It returns the network address of the subnet the `ip` belongs to.
+fn subnet(ip: &IpAddr, ipv4_prefix_len: u8, ipv6_prefix_len: u8) -> IpAddr {
+    match ip {
+        IpAddr::V4(ip) => {
+            let host_bits = 32 - u32::from(ipv4_prefix_len.min(32));
+            let mask = u32::MAX.checked_shl(host_bits).unwrap_or(0);
+
+            IpAddr::V4(Ipv4Addr::from(u32::from(*ip) & mask))
+        }
+        IpAddr::V6(ip) => {
+            let host_bits = 128 - u32::from(ipv6_prefix_len.min(128));
+            let mask = u128::MAX.checked_shl(host_bits).unwrap_or(0);
+
+            IpAddr::V6(Ipv6Addr::from(u128::from(*ip) & mask))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+    use std::time::Duration;
+
+    use torrust_tracker_configuration::UdpRateLimit as RateLimit;
+    use torrust_tracker_primitives::DurationSinceUnixEpoch;
+
+    use super::{subnet, RateLimiter};
+
+    fn sample_time() -> DurationSinceUnixEpoch {
+        DurationSinceUnixEpoch::from_secs(1_669_397_478)
+    }
+
+    fn rate_limiter(requests_per_second: u32, burst: u32) -> RateLimiter {
+        RateLimiter::new(RateLimit {
+            requests_per_second,
+            burst,
+            subnet_requests_per_second: 0,
+            ..Default::default()
+        })
+    }
+
+    fn sample_ip() -> IpAddr {
+        IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1))
+    }
+
+    #[test]
+    fn it_should_allow_a_burst_of_requests_from_the_same_ip() {
+        let mut rate_limiter = rate_limiter(1, 3);
+
+        for _ in 0..3 {
+            assert!(rate_limiter.check(&sample_ip(), sample_time()));
+        }
+
+        assert!(!rate_limiter.check(&sample_ip(), sample_time()));
+    }
+
+    #[test]
+    fn it_should_allow_new_requests_from_an_ip_once_the_bucket_is_refilled() {
+        let mut rate_limiter = rate_limiter(2, 1);
+
+        assert!(rate_limiter.check(&sample_ip(), sample_time()));
+        assert!(!rate_limiter.check(&sample_ip(), sample_time()));
+
+        let half_a_second_later = sample_time() + Duration::from_millis(500);
+
+        assert!(rate_limiter.check(&sample_ip(), half_a_second_later));
+    }
+
+    #[test]
+    fn it_should_limit_each_ip_independently() {
+        let mut rate_limiter = rate_limiter(1, 1);
+
+        assert!(rate_limiter.check(&sample_ip(), sample_time()));
+        assert!(rate_limiter.check(&IpAddr::V4(Ipv4Addr::new(126, 0, 0, 2)), sample_time()));
+    }
+
+    #[test]
+    fn it_should_limit_all_the_ips_in_the_same_subnet_together() {
+        let mut rate_limiter = RateLimiter::new(RateLimit {
+            requests_per_second: 10,
+            burst: 10,
+            subnet_requests_per_second: 1,
+            subnet_burst: 2,
+            ipv4_subnet_prefix_len: 24,
+            ..Default::default()
+        });
+
+        assert!(rate_limiter.check(&IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), sample_time()));
+        assert!(rate_limiter.check(&IpAddr::V4(Ipv4Addr::new(126, 0, 0, 2)), sample_time()));
+        assert!(!rate_limiter.check(&IpAddr::V4(Ipv4Addr::new(126, 0, 0, 3)), sample_time()));
+        assert!(rate_limiter.check(&IpAddr::V4(Ipv4Addr::new(126, 0, 1, 1)), sample_time()));
+    }
+
+    #[test]
+    fn it_should_not_take_a_token_from_the_ip_bucket_when_the_subnet_limit_is_exceeded() {
+        let mut rate_limiter = RateLimiter::new(RateLimit {
+            requests_per_second: 1,
+            burst: 1,
+            subnet_requests_per_second: 1,
+            subnet_burst: 1,
+            ..Default::default()
+        });
+
+        assert!(rate_limiter.check(&IpAddr::V4(Ipv4Addr::new(126, 0, 0, 1)), sample_time()));
+        assert!(!rate_limiter.check(&IpAddr::V4(Ipv4Addr::new(126, 0, 0, 2)), sample_time()));
+
+        let one_second_later = sample_time() + Duration::from_secs(1);
+
+        assert!(rate_limiter.check(&IpAddr::V4(Ipv4Addr::new(126, 0, 0, 2)), one_second_later));
+    }
+
+    #[test]
+    fn it_should_remove_the_buckets_of_the_ips_that_stopped_sending_requests() {
+        let mut rate_limiter = rate_limiter(1, 1);
+
+        rate_limiter.check(&sample_ip(), sample_time());
+        rate_limiter.check(
+            &IpAddr::V4(Ipv4Addr::new(126, 0, 0, 2)),
+            sample_time() + Duration::from_secs(59),
+        );
+
+        assert_eq!(rate_limiter.number_of_buckets(), (2, 0));
+
+        rate_limiter.check(
+            &IpAddr::V4(Ipv4Addr::new(126, 0, 0, 2)),
+            sample_time() + Duration::from_secs(60),
+        );
+
+        assert_eq!(rate_limiter.number_of_buckets(), (1, 0));
+    }
+
+    #[test]
+    fn it_should_calculate_the_subnet_of_an_ip() {
+        assert_eq!(
+            subnet(&IpAddr::V4(Ipv4Addr::new(126, 1, 2, 3)), 24, 48),
+            IpAddr::V4(Ipv4Addr::new(126, 1, 2, 0))
+        );
+        assert_eq!(
+            subnet(&IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0x1, 0x2, 0, 0, 0, 0x1)), 24, 48),
+            IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0x1, 0, 0, 0, 0, 0))
+        );
+        assert_eq!(
+            subnet(&IpAddr::V4(Ipv4Addr::new(126, 1, 2, 3)), 0, 48),
+            IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0))
+        );
+    }
+}
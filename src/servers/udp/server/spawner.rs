@@ -7,6 +7,7 @@ use derive_more::derive::Display;
 use derive_more::Constructor;
 use tokio::sync::oneshot;
 use tokio::task::JoinHandle;
+use torrust_tracker_configuration::UdpRateLimit;
 
 use super::launcher::Launcher;
 use crate::bootstrap::jobs::Started;
@@ -29,13 +30,14 @@ impl Spawner {
         &self,
         tracker: Arc<Tracker>,
         cookie_lifetime: Duration,
+        rate_limit: Option<UdpRateLimit>,
         tx_start: oneshot::Sender<Started>,
         rx_halt: oneshot::Receiver<Halted>,
     ) -> JoinHandle<Spawner> {
         let spawner = Self::new(self.bind_to);
 
         tokio::spawn(async move {
-            Launcher::run_with_graceful_shutdown(tracker, spawner.bind_to, cookie_lifetime, tx_start, rx_halt).await;
+            Launcher::run_with_graceful_shutdown(tracker, spawner.bind_to, cookie_lifetime, rate_limit, tx_start, rx_halt).await;
             spawner
         })
     }
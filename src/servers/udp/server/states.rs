@@ -6,6 +6,7 @@ use std::time::Duration;
 use derive_more::derive::Display;
 use derive_more::Constructor;
 use tokio::task::JoinHandle;
+use torrust_tracker_configuration::UdpRateLimit;
 use tracing::{instrument, Level};
 
 use super::spawner::Spawner;
@@ -68,6 +69,7 @@ impl Server<Stopped> {
         tracker: Arc<Tracker>,
         form: ServiceRegistrationForm,
         cookie_lifetime: Duration,
+        rate_limit: Option<UdpRateLimit>,
     ) -> Result<Server<Running>, std::io::Error> {
         let (tx_start, rx_start) = tokio::sync::oneshot::channel::<Started>();
         let (tx_halt, rx_halt) = tokio::sync::oneshot::channel::<Halted>();
@@ -75,7 +77,10 @@ impl Server<Stopped> {
         assert!(!tx_halt.is_closed(), "Halt channel for UDP tracker should be open");
 
         // May need to wrap in a task to about a tokio bug.
-        let task = self.state.spawner.spawn_launcher(tracker, cookie_lifetime, tx_start, rx_halt);
+        let task = self
+            .state
+            .spawner
+            .spawn_launcher(tracker, cookie_lifetime, rate_limit, tx_start, rx_halt);
 
         let local_addr = rx_start.await.expect("it should be able to start the service").address;
 
@@ -2,5 +2,7 @@
 //!
 //! - [`bit_torrent`]: `BitTorrent` protocol related logic.
 //! - [`crypto`]: Encryption related logic.
+//! - [`net`]: Network related logic.
 pub mod bit_torrent;
 pub mod crypto;
+pub mod net;
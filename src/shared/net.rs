@@ -0,0 +1,113 @@
+//! Network related logic.
+use std::net::IpAddr;
+
+use ipnet::IpNet;
+
+lazy_static! {
+    /// Networks that should not be used by peers on the public Internet.
+    ///
+    /// Refer to the [IANA IPv4](https://www.iana.org/assignments/iana-ipv4-special-registry/iana-ipv4-special-registry.xhtml)
+    /// and [IANA IPv6](https://www.iana.org/assignments/iana-ipv6-special-registry/iana-ipv6-special-registry.xhtml)
+    /// special-purpose address registries.
+    static ref BOGON_NETWORKS: Vec<IpNet> = [
+        // IPv4
+        "0.0.0.0/8",
+        "10.0.0.0/8",
+        "100.64.0.0/10",
+        "127.0.0.0/8",
+        "169.254.0.0/16",
+        "172.16.0.0/12",
+        "192.0.0.0/24",
+        "192.0.2.0/24",
+        "192.168.0.0/16",
+        "198.18.0.0/15",
+        "198.51.100.0/24",
+        "203.0.113.0/24",
+        "224.0.0.0/4",
+        "240.0.0.0/4",
+        // IPv6
+        "::/128",
+        "::1/128",
+        "100::/64",
+        "2001:db8::/32",
+        "fc00::/7",
+        "fe80::/10",
+        "ff00::/8",
+    ]
+    .iter()
+    .map(|network| network.parse().expect("it should be a valid network"))
+    .collect();
+}
+
+/// It returns `true` if the IP address is a bogon: a private, reserved or
+/// special-purpose address that is not routable on the public Internet.
+///
+/// IPv4-mapped IPv6 addresses are checked as IPv4 addresses.
+///
+/// ```rust
+/// use std::net::IpAddr;
+/// use torrust_tracker::shared::net::is_bogon;
+///
+/// assert!(is_bogon(&"192.168.1.88".parse::<IpAddr>().unwrap()));
+/// assert!(!is_bogon(&"2.137.87.41".parse::<IpAddr>().unwrap()));
+/// ```
+#[must_use]
+pub fn is_bogon(ip: &IpAddr) -> bool {
+    let ip = canonical(ip);
+
+    BOGON_NETWORKS.iter().any(|network| network.contains(&ip))
+}
+
+/// It converts an IPv4-mapped IPv6 address (`::ffff:a.b.c.d`) into the IPv4
+/// address. Other addresses are returned unchanged.
+#[must_use]
+pub fn canonical(ip: &IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(ipv6) => ipv6.to_ipv4_mapped().map_or(*ip, IpAddr::V4),
+        IpAddr::V4(_) => *ip,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+
+    use super::is_bogon;
+
+    fn ip(ip: &str) -> IpAddr {
+        ip.parse().unwrap()
+    }
+
+    #[test]
+    fn private_loopback_and_reserved_ips_should_be_bogons() {
+        for bogon in [
+            "0.0.0.0",
+            "10.1.2.3",
+            "127.0.0.1",
+            "172.16.0.1",
+            "192.168.1.88",
+            "224.0.0.1",
+            "255.255.255.255",
+            "::",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "2001:db8::1",
+        ] {
+            assert!(is_bogon(&ip(bogon)), "{bogon} should be a bogon");
+        }
+    }
+
+    #[test]
+    fn public_ips_should_not_be_bogons() {
+        for public in ["2.137.87.41", "126.0.0.1", "2a00:1450:4001::1"] {
+            assert!(!is_bogon(&ip(public)), "{public} should not be a bogon");
+        }
+    }
+
+    #[test]
+    fn ipv4_mapped_ipv6_ips_should_be_checked_as_ipv4_ips() {
+        assert!(is_bogon(&ip("::ffff:192.168.1.88")));
+        assert!(!is_bogon(&ip("::ffff:2.137.87.41")));
+    }
+}
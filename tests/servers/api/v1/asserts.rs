@@ -1,7 +1,11 @@
 // code-review: should we use macros to return the exact line where the assert fails?
 
 use reqwest::Response;
-use torrust_tracker::servers::apis::v1::context::auth_key::resources::AuthKey;
+use torrust_tracker::servers::apis::v1::context::auth_key::resources::{AllowList, AuthKey, TorrentGroup};
+use torrust_tracker::servers::apis::v1::context::client_filter::resources::ClientFilter;
+use torrust_tracker::servers::apis::v1::context::ip_blocklist::resources::IpBlocklist;
+use torrust_tracker::servers::apis::v1::context::maintenance::resources::Maintenance;
+use torrust_tracker::servers::apis::v1::context::ratio::resources::{KeyStats, TorrentMultipliers};
 use torrust_tracker::servers::apis::v1::context::stats::resources::Stats;
 use torrust_tracker::servers::apis::v1::context::torrent::resources::torrent::{ListItem, Torrent};
 
@@ -13,6 +17,54 @@ pub async fn assert_stats(response: Response, stats: Stats) {
     assert_eq!(response.json::<Stats>().await.unwrap(), stats);
 }
 
+pub async fn assert_maintenance(response: Response, maintenance: Maintenance) {
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
+    assert_eq!(response.json::<Maintenance>().await.unwrap(), maintenance);
+}
+
+pub async fn assert_client_filter(response: Response, client_filter: ClientFilter) {
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
+    assert_eq!(response.json::<ClientFilter>().await.unwrap(), client_filter);
+}
+
+pub async fn assert_ip_blocklist(response: Response, ip_blocklist: IpBlocklist) {
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
+    assert_eq!(response.json::<IpBlocklist>().await.unwrap(), ip_blocklist);
+}
+
+pub async fn assert_allow_list(response: Response, allow_list: AllowList) {
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
+    assert_eq!(response.json::<AllowList>().await.unwrap(), allow_list);
+}
+
+pub async fn assert_torrent_group(response: Response, torrent_group: TorrentGroup) {
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
+    assert_eq!(response.json::<TorrentGroup>().await.unwrap(), torrent_group);
+}
+
+pub async fn assert_key_stats(response: Response, key_stats: KeyStats) {
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
+    assert_eq!(response.json::<KeyStats>().await.unwrap(), key_stats);
+}
+
+pub async fn assert_key_stats_list(response: Response, key_stats: Vec<KeyStats>) {
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
+    assert_eq!(response.json::<Vec<KeyStats>>().await.unwrap(), key_stats);
+}
+
+pub async fn assert_torrent_multipliers(response: Response, multipliers: TorrentMultipliers) {
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
+    assert_eq!(response.json::<TorrentMultipliers>().await.unwrap(), multipliers);
+}
+
 pub async fn assert_torrent_list(response: Response, torrents: Vec<ListItem>) {
     assert_eq!(response.status(), 200);
     assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
@@ -89,7 +141,7 @@ pub async fn assert_torrent_not_known(response: Response) {
 pub async fn assert_invalid_infohash_param(response: Response, invalid_infohash: &str) {
     assert_bad_request(
         response,
-        &format!("Invalid URL: invalid infohash param: string \"{invalid_infohash}\", expected a 40 character long string"),
+        &format!("Invalid URL: invalid infohash param: string \"{invalid_infohash}\", expected a 40 or 64 character long string"),
     )
     .await;
 }
@@ -98,6 +150,30 @@ pub async fn assert_invalid_auth_key_get_param(response: Response, invalid_auth_
     assert_bad_request(response, &format!("Invalid auth key id param \"{}\"", &invalid_auth_key)).await;
 }
 
+pub async fn assert_invalid_torrent_group_param(response: Response, invalid_group: &str) {
+    assert_bad_request_with_text(
+        response,
+        &format!("Invalid URL: invalid torrent group param: string \"{}\"", &invalid_group),
+    )
+    .await;
+}
+
+pub async fn assert_invalid_peer_id_prefix_param(response: Response, invalid_prefix: &str) {
+    assert_bad_request_with_text(
+        response,
+        &format!("Invalid URL: invalid peer id prefix param: string \"{}\"", &invalid_prefix),
+    )
+    .await;
+}
+
+pub async fn assert_invalid_network_param(response: Response, invalid_network: &str) {
+    assert_bad_request_with_text(
+        response,
+        &format!("Invalid URL: invalid network param: string \"{}\"", &invalid_network),
+    )
+    .await;
+}
+
 pub async fn assert_invalid_auth_key_post_param(response: Response, invalid_auth_key: &str) {
     assert_bad_request_with_text(
         response,
@@ -142,6 +218,46 @@ pub async fn assert_failed_to_reload_whitelist(response: Response) {
     assert_unhandled_rejection(response, "failed to reload whitelist").await;
 }
 
+pub async fn assert_failed_to_remove_torrent_from_blacklist(response: Response) {
+    assert_unhandled_rejection(response, "failed to remove torrent from blacklist").await;
+}
+
+pub async fn assert_failed_to_blacklist_torrent(response: Response) {
+    assert_unhandled_rejection(response, "failed to blacklist torrent").await;
+}
+
+pub async fn assert_failed_to_reload_blacklist(response: Response) {
+    assert_unhandled_rejection(response, "failed to reload blacklist").await;
+}
+
+pub async fn assert_failed_to_set_torrent_multipliers(response: Response) {
+    assert_unhandled_rejection(response, "failed to set torrent multipliers").await;
+}
+
+pub async fn assert_failed_to_update_allow_list(response: Response) {
+    assert_unhandled_rejection(response, "failed to update allow-list").await;
+}
+
+pub async fn assert_failed_to_update_torrent_group(response: Response) {
+    assert_unhandled_rejection(response, "failed to update torrent group").await;
+}
+
+pub async fn assert_failed_to_update_client_filter(response: Response) {
+    assert_unhandled_rejection(response, "failed to update client filter").await;
+}
+
+pub async fn assert_failed_to_reload_client_filter(response: Response) {
+    assert_unhandled_rejection(response, "failed to reload client filter").await;
+}
+
+pub async fn assert_failed_to_update_ip_blocklist(response: Response) {
+    assert_unhandled_rejection(response, "failed to update IP blocklist").await;
+}
+
+pub async fn assert_failed_to_reload_ip_blocklist(response: Response) {
+    assert_unhandled_rejection(response, "failed to reload IP blocklist").await;
+}
+
 pub async fn assert_failed_to_generate_key(response: Response) {
     assert_unhandled_rejection(response, "failed to generate key").await;
 }
@@ -154,6 +270,10 @@ pub async fn assert_failed_to_reload_keys(response: Response) {
     assert_unhandled_rejection(response, "failed to reload keys").await;
 }
 
+pub async fn assert_full_scrape_not_available(response: Response) {
+    assert_unhandled_rejection(response, "full scrape not available").await;
+}
+
 async fn assert_unhandled_rejection(response: Response, reason: &str) {
     assert_eq!(response.status(), 500);
     assert_eq!(response.headers().get("content-type").unwrap(), "text/plain; charset=utf-8");
@@ -34,6 +34,44 @@ impl Client {
         self.get("keys/reload", Query::default()).await
     }
 
+    pub async fn get_key_allow_list(&self, key: &str) -> Response {
+        self.get(&format!("keys/{}/torrents", &key), Query::default()).await
+    }
+
+    pub async fn remove_key_allow_list(&self, key: &str) -> Response {
+        self.delete(&format!("keys/{}/torrents", &key)).await
+    }
+
+    pub async fn add_torrent_to_key_allow_list(&self, key: &str, info_hash: &str) -> Response {
+        self.post_empty(&format!("keys/{}/torrents/{}", &key, &info_hash)).await
+    }
+
+    pub async fn remove_torrent_from_key_allow_list(&self, key: &str, info_hash: &str) -> Response {
+        self.delete(&format!("keys/{}/torrents/{}", &key, &info_hash)).await
+    }
+
+    pub async fn add_group_to_key_allow_list(&self, key: &str, group: &str) -> Response {
+        self.post_empty(&format!("keys/{}/groups/{}", &key, &group)).await
+    }
+
+    pub async fn remove_group_from_key_allow_list(&self, key: &str, group: &str) -> Response {
+        self.delete(&format!("keys/{}/groups/{}", &key, &group)).await
+    }
+
+    pub async fn get_torrent_group(&self, group: &str) -> Response {
+        self.get(&format!("torrent-groups/{}", &group), Query::default()).await
+    }
+
+    pub async fn add_torrent_to_group(&self, group: &str, info_hash: &str) -> Response {
+        self.post_empty(&format!("torrent-groups/{}/torrents/{}", &group, &info_hash))
+            .await
+    }
+
+    pub async fn remove_torrent_from_group(&self, group: &str, info_hash: &str) -> Response {
+        self.delete(&format!("torrent-groups/{}/torrents/{}", &group, &info_hash))
+            .await
+    }
+
     pub async fn whitelist_a_torrent(&self, info_hash: &str) -> Response {
         self.post_empty(&format!("whitelist/{}", &info_hash)).await
     }
@@ -46,6 +84,18 @@ impl Client {
         self.get("whitelist/reload", Query::default()).await
     }
 
+    pub async fn blacklist_a_torrent(&self, info_hash: &str) -> Response {
+        self.post_empty(&format!("blacklist/{}", &info_hash)).await
+    }
+
+    pub async fn remove_torrent_from_blacklist(&self, info_hash: &str) -> Response {
+        self.delete(&format!("blacklist/{}", &info_hash)).await
+    }
+
+    pub async fn reload_blacklist(&self) -> Response {
+        self.get("blacklist/reload", Query::default()).await
+    }
+
     pub async fn get_torrent(&self, info_hash: &str) -> Response {
         self.get(&format!("torrent/{}", &info_hash), Query::default()).await
     }
@@ -54,10 +104,93 @@ impl Client {
         self.get("torrents", params).await
     }
 
+    pub async fn get_full_scrape(&self) -> Response {
+        self.get("torrents/scrape", Query::default()).await
+    }
+
+    pub async fn add_hybrid_torrent(&self, info_hash: &str, info_hash_v2: &str) -> Response {
+        self.post_empty(&format!("torrent/{}/hybrid/{}", &info_hash, &info_hash_v2))
+            .await
+    }
+
+    pub async fn remove_hybrid_torrent(&self, info_hash: &str, info_hash_v2: &str) -> Response {
+        self.delete(&format!("torrent/{}/hybrid/{}", &info_hash, &info_hash_v2)).await
+    }
+
+    pub async fn get_maintenance(&self) -> Response {
+        self.get("maintenance", Query::default()).await
+    }
+
+    pub async fn enable_maintenance(&self) -> Response {
+        self.post_empty("maintenance").await
+    }
+
+    pub async fn disable_maintenance(&self) -> Response {
+        self.delete("maintenance").await
+    }
+
+    pub async fn get_client_filter(&self) -> Response {
+        self.get("client-filter", Query::default()).await
+    }
+
+    pub async fn add_client_prefix(&self, prefix: &str) -> Response {
+        self.post_empty(&format!("client-filter/prefixes/{}", &prefix)).await
+    }
+
+    pub async fn remove_client_prefix(&self, prefix: &str) -> Response {
+        self.delete(&format!("client-filter/prefixes/{}", &prefix)).await
+    }
+
+    pub async fn reload_client_filter(&self) -> Response {
+        self.get("client-filter/reload", Query::default()).await
+    }
+
+    pub async fn get_ip_blocklist(&self) -> Response {
+        self.get("ip-blocklist", Query::default()).await
+    }
+
+    pub async fn add_blocked_network(&self, network: &str) -> Response {
+        self.post_empty(&format!("ip-blocklist/networks/{}", &network)).await
+    }
+
+    pub async fn remove_blocked_network(&self, network: &str) -> Response {
+        self.delete(&format!("ip-blocklist/networks/{}", &network)).await
+    }
+
+    pub async fn reload_ip_blocklist(&self) -> Response {
+        self.get("ip-blocklist/reload", Query::default()).await
+    }
+
+    pub async fn get_all_key_stats(&self) -> Response {
+        self.get("ratio/keys", Query::default()).await
+    }
+
+    pub async fn get_key_stats(&self, key: &str) -> Response {
+        self.get(&format!("ratio/keys/{}", &key), Query::default()).await
+    }
+
+    pub async fn get_torrent_multipliers(&self, info_hash: &str) -> Response {
+        self.get(&format!("ratio/torrents/{}", &info_hash), Query::default()).await
+    }
+
+    pub async fn set_torrent_multipliers(&self, info_hash: &str, multipliers_form: MultipliersForm) -> Response {
+        self.post_form(&format!("ratio/torrents/{}", &info_hash), &multipliers_form)
+            .await
+    }
+
+    pub async fn remove_torrent_multipliers(&self, info_hash: &str) -> Response {
+        self.delete(&format!("ratio/torrents/{}", &info_hash)).await
+    }
+
     pub async fn get_tracker_statistics(&self) -> Response {
         self.get("stats", Query::default()).await
     }
 
+    pub async fn get_tracker_statistics_in_prometheus_format(&self) -> Response {
+        self.get("stats", Query::params([QueryParam::new("format", "prometheus")].to_vec()))
+            .await
+    }
+
     pub async fn get(&self, path: &str, params: Query) -> Response {
         let mut query: Query = params;
 
@@ -136,3 +269,11 @@ pub struct AddKeyForm {
     pub opt_key: Option<String>,
     pub seconds_valid: Option<u64>,
 }
+
+#[derive(Serialize, Debug)]
+pub struct MultipliersForm {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upload: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub download: Option<f64>,
+}
@@ -1,18 +1,25 @@
+use std::str::FromStr;
 use std::time::Duration;
 
+use bittorrent_primitives::info_hash::InfoHash;
 use serde::Serialize;
+use torrust_tracker::core::access::TorrentGroup;
 use torrust_tracker::core::auth::Key;
+use torrust_tracker::servers::apis::v1::context::auth_key::resources::{self, AllowList};
 use torrust_tracker_test_helpers::configuration;
 use tracing::level_filters::LevelFilter;
 
 use crate::common::logging::{tracing_stderr_init, INIT};
 use crate::servers::api::connection_info::{connection_with_invalid_token, connection_with_no_token};
 use crate::servers::api::v1::asserts::{
-    assert_auth_key_utf8, assert_failed_to_delete_key, assert_failed_to_generate_key, assert_failed_to_reload_keys,
-    assert_invalid_auth_key_get_param, assert_invalid_auth_key_post_param, assert_ok, assert_token_not_valid,
-    assert_unauthorized, assert_unprocessable_auth_key_duration_param,
+    assert_allow_list, assert_auth_key_utf8, assert_failed_to_delete_key, assert_failed_to_generate_key,
+    assert_failed_to_reload_keys, assert_failed_to_update_allow_list, assert_failed_to_update_torrent_group,
+    assert_invalid_auth_key_get_param, assert_invalid_auth_key_post_param, assert_invalid_infohash_param,
+    assert_invalid_torrent_group_param, assert_ok, assert_token_not_valid, assert_torrent_group, assert_unauthorized,
+    assert_unprocessable_auth_key_duration_param,
 };
 use crate::servers::api::v1::client::{AddKeyForm, Client};
+use crate::servers::api::v1::contract::fixtures::invalid_infohashes_returning_bad_request;
 use crate::servers::api::{force_database_error, Started};
 
 #[tokio::test]
@@ -492,3 +499,296 @@ mod deprecated_generate_key_endpoint {
         env.stop().await;
     }
 }
+
+fn sample_key() -> Key {
+    Key::from_str("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ").unwrap()
+}
+
+fn sample_info_hash() -> InfoHash {
+    InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap()
+}
+
+fn linux_isos() -> TorrentGroup {
+    TorrentGroup::from_str("linux-isos").unwrap()
+}
+
+#[tokio::test]
+async fn should_allow_adding_a_torrent_to_the_allow_list_of_a_key() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let api_client = Client::new(env.get_connection_info());
+
+    let response = api_client
+        .add_torrent_to_key_allow_list(&sample_key().to_string(), &sample_info_hash().to_string())
+        .await;
+
+    assert_ok(response).await;
+
+    let response = api_client.get_key_allow_list(&sample_key().to_string()).await;
+
+    assert_allow_list(
+        response,
+        AllowList {
+            key: sample_key().to_string(),
+            restricted: true,
+            info_hashes: vec![sample_info_hash().to_string()],
+            groups: vec![],
+        },
+    )
+    .await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_removing_a_torrent_from_the_allow_list_of_a_key() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    env.tracker
+        .add_torrent_to_key_allow_list(&sample_key(), &sample_info_hash())
+        .unwrap();
+
+    let response = Client::new(env.get_connection_info())
+        .remove_torrent_from_key_allow_list(&sample_key().to_string(), &sample_info_hash().to_string())
+        .await;
+
+    assert_ok(response).await;
+    assert!(env.tracker.get_key_allow_list(&sample_key()).unwrap().is_empty());
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_removing_the_allow_list_of_a_key() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    env.tracker
+        .add_torrent_to_key_allow_list(&sample_key(), &sample_info_hash())
+        .unwrap();
+
+    let api_client = Client::new(env.get_connection_info());
+
+    let response = api_client.remove_key_allow_list(&sample_key().to_string()).await;
+
+    assert_ok(response).await;
+
+    let response = api_client.get_key_allow_list(&sample_key().to_string()).await;
+
+    assert_allow_list(
+        response,
+        AllowList {
+            key: sample_key().to_string(),
+            restricted: false,
+            info_hashes: vec![],
+            groups: vec![],
+        },
+    )
+    .await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_adding_and_removing_a_torrent_group_from_the_allow_list_of_a_key() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let api_client = Client::new(env.get_connection_info());
+
+    let response = api_client
+        .add_group_to_key_allow_list(&sample_key().to_string(), &linux_isos().to_string())
+        .await;
+
+    assert_ok(response).await;
+    assert!(env
+        .tracker
+        .get_key_allow_list(&sample_key())
+        .unwrap()
+        .groups
+        .contains(&linux_isos()));
+
+    let response = api_client
+        .remove_group_from_key_allow_list(&sample_key().to_string(), &linux_isos().to_string())
+        .await;
+
+    assert_ok(response).await;
+    assert!(env.tracker.get_key_allow_list(&sample_key()).unwrap().is_empty());
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_adding_and_removing_a_torrent_from_a_torrent_group() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let api_client = Client::new(env.get_connection_info());
+
+    let response = api_client
+        .add_torrent_to_group(&linux_isos().to_string(), &sample_info_hash().to_string())
+        .await;
+
+    assert_ok(response).await;
+
+    let response = api_client.get_torrent_group(&linux_isos().to_string()).await;
+
+    assert_torrent_group(
+        response,
+        resources::TorrentGroup {
+            name: linux_isos().to_string(),
+            info_hashes: vec![sample_info_hash().to_string()],
+        },
+    )
+    .await;
+
+    let response = api_client
+        .remove_torrent_from_group(&linux_isos().to_string(), &sample_info_hash().to_string())
+        .await;
+
+    assert_ok(response).await;
+    assert!(env.tracker.get_torrent_group(&linux_isos()).is_empty());
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_updating_the_allow_list_of_a_key_when_the_key_is_invalid() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let invalid_key = "INVALID_KEY";
+
+    let response = Client::new(env.get_connection_info())
+        .add_torrent_to_key_allow_list(invalid_key, &sample_info_hash().to_string())
+        .await;
+
+    assert_invalid_auth_key_get_param(response, invalid_key).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_updating_the_allow_list_of_a_key_when_the_infohash_is_invalid() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let api_client = Client::new(env.get_connection_info());
+
+    for invalid_infohash in &invalid_infohashes_returning_bad_request() {
+        let response = api_client
+            .add_torrent_to_key_allow_list(&sample_key().to_string(), invalid_infohash)
+            .await;
+
+        assert_invalid_infohash_param(response, invalid_infohash).await;
+    }
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_updating_a_torrent_group_when_the_group_name_is_invalid() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let api_client = Client::new(env.get_connection_info());
+
+    for invalid_group in ["linux.isos", &"a".repeat(65)] {
+        let response = api_client
+            .add_torrent_to_group(invalid_group, &sample_info_hash().to_string())
+            .await;
+
+        assert_invalid_torrent_group_param(response, invalid_group).await;
+    }
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_when_the_allow_list_of_a_key_cannot_be_updated() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    force_database_error(&env.tracker);
+
+    let response = Client::new(env.get_connection_info())
+        .add_torrent_to_key_allow_list(&sample_key().to_string(), &sample_info_hash().to_string())
+        .await;
+
+    assert_failed_to_update_allow_list(response).await;
+    assert!(env.tracker.get_key_allow_list(&sample_key()).is_none());
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_when_a_torrent_group_cannot_be_updated() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    force_database_error(&env.tracker);
+
+    let response = Client::new(env.get_connection_info())
+        .add_torrent_to_group(&linux_isos().to_string(), &sample_info_hash().to_string())
+        .await;
+
+    assert_failed_to_update_torrent_group(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_allow_updating_the_allow_list_of_a_key_for_unauthenticated_users() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let response = Client::new(connection_with_invalid_token(env.get_connection_info().bind_address.as_str()))
+        .add_torrent_to_key_allow_list(&sample_key().to_string(), &sample_info_hash().to_string())
+        .await;
+
+    assert_token_not_valid(response).await;
+
+    let response = Client::new(connection_with_no_token(env.get_connection_info().bind_address.as_str()))
+        .add_torrent_to_key_allow_list(&sample_key().to_string(), &sample_info_hash().to_string())
+        .await;
+
+    assert_unauthorized(response).await;
+    assert!(env.tracker.get_key_allow_list(&sample_key()).is_none());
+
+    env.stop().await;
+}
@@ -0,0 +1,256 @@
+use std::str::FromStr;
+
+use bittorrent_primitives::info_hash::InfoHash;
+use torrust_tracker_test_helpers::configuration;
+use tracing::level_filters::LevelFilter;
+
+use crate::common::logging::{tracing_stderr_init, INIT};
+use crate::servers::api::connection_info::{connection_with_invalid_token, connection_with_no_token};
+use crate::servers::api::v1::asserts::{
+    assert_failed_to_blacklist_torrent, assert_failed_to_reload_blacklist, assert_failed_to_remove_torrent_from_blacklist,
+    assert_invalid_infohash_param, assert_not_found, assert_ok, assert_token_not_valid, assert_unauthorized,
+};
+use crate::servers::api::v1::client::Client;
+use crate::servers::api::v1::contract::fixtures::{
+    invalid_infohashes_returning_bad_request, invalid_infohashes_returning_not_found,
+};
+use crate::servers::api::{force_database_error, Started};
+
+#[tokio::test]
+async fn should_allow_blacklisting_a_torrent() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let info_hash = "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".to_owned();
+
+    let response = Client::new(env.get_connection_info()).blacklist_a_torrent(&info_hash).await;
+
+    assert_ok(response).await;
+    assert!(
+        env.tracker
+            .is_info_hash_blacklisted(&InfoHash::from_str(&info_hash).unwrap())
+            .await
+    );
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_blacklisting_a_torrent_using_its_v2_info_hash() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let info_hash_v2 = "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0b8a1a4d58d14d7a21b7d7a45".to_owned();
+
+    let response = Client::new(env.get_connection_info())
+        .blacklist_a_torrent(&info_hash_v2)
+        .await;
+
+    assert_ok(response).await;
+    assert!(
+        env.tracker
+            .is_info_hash_blacklisted(&InfoHash::from_str("3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0").unwrap())
+            .await
+    );
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_blacklisting_a_torrent_that_has_been_already_blacklisted() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let info_hash = "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".to_owned();
+
+    let api_client = Client::new(env.get_connection_info());
+
+    let response = api_client.blacklist_a_torrent(&info_hash).await;
+    assert_ok(response).await;
+
+    let response = api_client.blacklist_a_torrent(&info_hash).await;
+    assert_ok(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_allow_blacklisting_a_torrent_for_unauthenticated_users() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let info_hash = "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".to_owned();
+
+    let response = Client::new(connection_with_invalid_token(env.get_connection_info().bind_address.as_str()))
+        .blacklist_a_torrent(&info_hash)
+        .await;
+
+    assert_token_not_valid(response).await;
+
+    let response = Client::new(connection_with_no_token(env.get_connection_info().bind_address.as_str()))
+        .blacklist_a_torrent(&info_hash)
+        .await;
+
+    assert_unauthorized(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_when_the_torrent_cannot_be_blacklisted() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let info_hash = "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".to_owned();
+
+    force_database_error(&env.tracker);
+
+    let response = Client::new(env.get_connection_info()).blacklist_a_torrent(&info_hash).await;
+
+    assert_failed_to_blacklist_torrent(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_blacklisting_a_torrent_when_the_provided_infohash_is_invalid() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    for invalid_infohash in &invalid_infohashes_returning_bad_request() {
+        let response = Client::new(env.get_connection_info())
+            .blacklist_a_torrent(invalid_infohash)
+            .await;
+
+        assert_invalid_infohash_param(response, invalid_infohash).await;
+    }
+
+    for invalid_infohash in &invalid_infohashes_returning_not_found() {
+        let response = Client::new(env.get_connection_info())
+            .blacklist_a_torrent(invalid_infohash)
+            .await;
+
+        assert_not_found(response).await;
+    }
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_removing_a_torrent_from_the_blacklist() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let hash = "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".to_owned();
+    let info_hash = InfoHash::from_str(&hash).unwrap();
+    env.tracker.add_torrent_to_blacklist(&info_hash).await.unwrap();
+
+    let response = Client::new(env.get_connection_info())
+        .remove_torrent_from_blacklist(&hash)
+        .await;
+
+    assert_ok(response).await;
+    assert!(!env.tracker.is_info_hash_blacklisted(&info_hash).await);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_fail_trying_to_remove_a_non_blacklisted_torrent_from_the_blacklist() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let non_blacklisted_torrent_hash = "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".to_owned();
+
+    let response = Client::new(env.get_connection_info())
+        .remove_torrent_from_blacklist(&non_blacklisted_torrent_hash)
+        .await;
+
+    assert_ok(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_when_the_torrent_cannot_be_removed_from_the_blacklist() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let hash = "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".to_owned();
+    let info_hash = InfoHash::from_str(&hash).unwrap();
+    env.tracker.add_torrent_to_blacklist(&info_hash).await.unwrap();
+
+    force_database_error(&env.tracker);
+
+    let response = Client::new(env.get_connection_info())
+        .remove_torrent_from_blacklist(&hash)
+        .await;
+
+    assert_failed_to_remove_torrent_from_blacklist(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_reload_the_blacklist_from_the_database() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let hash = "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d".to_owned();
+    let info_hash = InfoHash::from_str(&hash).unwrap();
+    env.tracker.add_torrent_to_blacklist(&info_hash).await.unwrap();
+
+    let response = Client::new(env.get_connection_info()).reload_blacklist().await;
+
+    assert_ok(response).await;
+    assert!(env.tracker.is_info_hash_blacklisted(&info_hash).await);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_when_the_blacklist_cannot_be_reloaded_from_the_database() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    force_database_error(&env.tracker);
+
+    let response = Client::new(env.get_connection_info()).reload_blacklist().await;
+
+    assert_failed_to_reload_blacklist(response).await;
+
+    env.stop().await;
+}
@@ -0,0 +1,176 @@
+use std::str::FromStr;
+
+use torrust_tracker::core::client_filter::PeerIdPrefix;
+use torrust_tracker::servers::apis::v1::context::client_filter::resources::ClientFilter;
+use torrust_tracker_configuration::ClientFilter as ClientFilterMode;
+use torrust_tracker_test_helpers::configuration;
+use tracing::level_filters::LevelFilter;
+
+use crate::common::logging::{tracing_stderr_init, INIT};
+use crate::servers::api::connection_info::{connection_with_invalid_token, connection_with_no_token};
+use crate::servers::api::v1::asserts::{
+    assert_client_filter, assert_failed_to_reload_client_filter, assert_failed_to_update_client_filter,
+    assert_invalid_peer_id_prefix_param, assert_ok, assert_token_not_valid, assert_unauthorized,
+};
+use crate::servers::api::v1::client::Client;
+use crate::servers::api::{force_database_error, Started};
+
+fn qbittorrent() -> PeerIdPrefix {
+    PeerIdPrefix::from_str("-qB4500-").unwrap()
+}
+
+#[tokio::test]
+async fn should_allow_getting_the_client_filter() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let mut configuration = configuration::ephemeral();
+    configuration.core.client_filter = ClientFilterMode::Deny;
+
+    let env = Started::new(&configuration.into()).await;
+
+    env.tracker.add_client_prefix(&qbittorrent()).unwrap();
+
+    let response = Client::new(env.get_connection_info()).get_client_filter().await;
+
+    assert_client_filter(
+        response,
+        ClientFilter {
+            mode: ClientFilterMode::Deny,
+            prefixes: vec!["-qB4500-".to_string()],
+        },
+    )
+    .await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_adding_a_prefix_to_the_client_filter() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info()).add_client_prefix("-qB4500-").await;
+
+    assert_ok(response).await;
+    assert_eq!(env.tracker.get_client_prefixes(), vec![qbittorrent()]);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_removing_a_prefix_from_the_client_filter() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    env.tracker.add_client_prefix(&qbittorrent()).unwrap();
+
+    let response = Client::new(env.get_connection_info()).remove_client_prefix("-qB4500-").await;
+
+    assert_ok(response).await;
+    assert!(env.tracker.get_client_prefixes().is_empty());
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_adding_an_invalid_prefix_to_the_client_filter() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let invalid_prefix = "a".repeat(21);
+
+    let response = Client::new(env.get_connection_info())
+        .add_client_prefix(&invalid_prefix)
+        .await;
+
+    assert_invalid_peer_id_prefix_param(response, &invalid_prefix).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_when_the_client_filter_cannot_be_updated() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    force_database_error(&env.tracker);
+
+    let response = Client::new(env.get_connection_info()).add_client_prefix("-qB4500-").await;
+
+    assert_failed_to_update_client_filter(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_reloading_the_client_filter_from_the_database() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    env.tracker.add_client_prefix(&qbittorrent()).unwrap();
+
+    let response = Client::new(env.get_connection_info()).reload_client_filter().await;
+
+    assert_ok(response).await;
+    assert_eq!(env.tracker.get_client_prefixes(), vec![qbittorrent()]);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_when_the_client_filter_cannot_be_reloaded_from_the_database() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    force_database_error(&env.tracker);
+
+    let response = Client::new(env.get_connection_info()).reload_client_filter().await;
+
+    assert_failed_to_reload_client_filter(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_allow_changing_the_client_filter_for_unauthenticated_users() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(connection_with_invalid_token(env.get_connection_info().bind_address.as_str()))
+        .add_client_prefix("-qB4500-")
+        .await;
+
+    assert_token_not_valid(response).await;
+
+    let response = Client::new(connection_with_no_token(env.get_connection_info().bind_address.as_str()))
+        .add_client_prefix("-qB4500-")
+        .await;
+
+    assert_unauthorized(response).await;
+    assert!(env.tracker.get_client_prefixes().is_empty());
+
+    env.stop().await;
+}
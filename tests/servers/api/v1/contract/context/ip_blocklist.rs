@@ -0,0 +1,197 @@
+use std::str::FromStr;
+
+use ipnet::IpNet;
+use torrust_tracker::servers::apis::v1::context::ip_blocklist::resources::IpBlocklist;
+use torrust_tracker_test_helpers::configuration;
+use tracing::level_filters::LevelFilter;
+
+use crate::common::logging::{tracing_stderr_init, INIT};
+use crate::servers::api::connection_info::{connection_with_invalid_token, connection_with_no_token};
+use crate::servers::api::v1::asserts::{
+    assert_failed_to_reload_ip_blocklist, assert_failed_to_update_ip_blocklist, assert_invalid_network_param,
+    assert_ip_blocklist, assert_ok, assert_token_not_valid, assert_unauthorized,
+};
+use crate::servers::api::v1::client::Client;
+use crate::servers::api::{force_database_error, Started};
+
+fn network() -> IpNet {
+    IpNet::from_str("10.0.0.0/8").unwrap()
+}
+
+#[tokio::test]
+async fn should_allow_getting_the_ip_blocklist() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    env.tracker.add_blocked_network(&network()).unwrap();
+
+    let response = Client::new(env.get_connection_info()).get_ip_blocklist().await;
+
+    assert_ip_blocklist(
+        response,
+        IpBlocklist {
+            networks: vec!["10.0.0.0/8".to_string()],
+            file_networks: 0,
+        },
+    )
+    .await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_blocking_a_network() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info()).add_blocked_network("10.0.0.0/8").await;
+
+    assert_ok(response).await;
+    assert_eq!(env.tracker.get_blocked_networks(), vec![network()]);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_blocking_an_ipv6_network() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .add_blocked_network("2001:db8::/32")
+        .await;
+
+    assert_ok(response).await;
+    assert_eq!(
+        env.tracker.get_blocked_networks(),
+        vec![IpNet::from_str("2001:db8::/32").unwrap()]
+    );
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_unblocking_a_network() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    env.tracker.add_blocked_network(&network()).unwrap();
+
+    let response = Client::new(env.get_connection_info())
+        .remove_blocked_network("10.0.0.0/8")
+        .await;
+
+    assert_ok(response).await;
+    assert!(env.tracker.get_blocked_networks().is_empty());
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_blocking_an_invalid_network() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let invalid_networks = ["10.0.0.0/33", "not-an-ip/8", "10.0.0.0/x"];
+
+    for invalid_network in invalid_networks {
+        let response = Client::new(env.get_connection_info())
+            .add_blocked_network(invalid_network)
+            .await;
+
+        assert_invalid_network_param(response, invalid_network).await;
+    }
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_when_the_ip_blocklist_cannot_be_updated() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    force_database_error(&env.tracker);
+
+    let response = Client::new(env.get_connection_info()).add_blocked_network("10.0.0.0/8").await;
+
+    assert_failed_to_update_ip_blocklist(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_reloading_the_ip_blocklist() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    env.tracker.add_blocked_network(&network()).unwrap();
+
+    let response = Client::new(env.get_connection_info()).reload_ip_blocklist().await;
+
+    assert_ok(response).await;
+    assert_eq!(env.tracker.get_blocked_networks(), vec![network()]);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_when_the_ip_blocklist_cannot_be_reloaded() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    force_database_error(&env.tracker);
+
+    let response = Client::new(env.get_connection_info()).reload_ip_blocklist().await;
+
+    assert_failed_to_reload_ip_blocklist(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_allow_changing_the_ip_blocklist_for_unauthenticated_users() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(connection_with_invalid_token(env.get_connection_info().bind_address.as_str()))
+        .add_blocked_network("10.0.0.0/8")
+        .await;
+
+    assert_token_not_valid(response).await;
+
+    let response = Client::new(connection_with_no_token(env.get_connection_info().bind_address.as_str()))
+        .add_blocked_network("10.0.0.0/8")
+        .await;
+
+    assert_unauthorized(response).await;
+    assert!(env.tracker.get_blocked_networks().is_empty());
+
+    env.stop().await;
+}
@@ -1,5 +1,10 @@
 pub mod auth_key;
+pub mod blacklist;
+pub mod client_filter;
 pub mod health_check;
+pub mod ip_blocklist;
+pub mod maintenance;
+pub mod ratio;
 pub mod stats;
 pub mod torrent;
 pub mod whitelist;
@@ -0,0 +1,300 @@
+use std::str::FromStr;
+
+use aquatic_udp_protocol::NumberOfBytes;
+use bittorrent_primitives::info_hash::InfoHash;
+use torrust_tracker::core::auth::Key;
+use torrust_tracker::core::ratio::Multipliers;
+use torrust_tracker::servers::apis::v1::context::ratio::resources::{KeyStats, TorrentMultipliers};
+use torrust_tracker_primitives::peer::fixture::PeerBuilder;
+use torrust_tracker_test_helpers::configuration;
+use tracing::level_filters::LevelFilter;
+
+use crate::common::logging::{tracing_stderr_init, INIT};
+use crate::servers::api::connection_info::{connection_with_invalid_token, connection_with_no_token};
+use crate::servers::api::v1::asserts::{
+    assert_bad_request, assert_failed_to_set_torrent_multipliers, assert_invalid_auth_key_get_param,
+    assert_invalid_infohash_param, assert_key_stats, assert_key_stats_list, assert_ok, assert_token_not_valid,
+    assert_torrent_multipliers, assert_unauthorized,
+};
+use crate::servers::api::v1::client::{Client, MultipliersForm};
+use crate::servers::api::v1::contract::fixtures::invalid_infohashes_returning_bad_request;
+use crate::servers::api::{force_database_error, Started};
+
+fn sample_key() -> Key {
+    Key::from_str("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ").unwrap()
+}
+
+fn sample_info_hash() -> InfoHash {
+    InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap()
+}
+
+fn freeleech() -> MultipliersForm {
+    MultipliersForm {
+        upload: None,
+        download: Some(0.0),
+    }
+}
+
+#[tokio::test]
+async fn should_allow_getting_the_stats_of_a_key() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let mut peer = PeerBuilder::leecher().build();
+    peer.uploaded = NumberOfBytes::new(200);
+    peer.downloaded = NumberOfBytes::new(100);
+
+    env.tracker.account_transfer(&sample_key(), &sample_info_hash(), None, &peer);
+
+    let response = Client::new(env.get_connection_info())
+        .get_key_stats(&sample_key().to_string())
+        .await;
+
+    assert_key_stats(
+        response,
+        KeyStats {
+            key: sample_key().to_string(),
+            uploaded: 200,
+            downloaded: 100,
+            ratio: Some(2.0),
+            seeding_time: 0,
+        },
+    )
+    .await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_return_empty_stats_for_a_key_that_has_not_announced_anything() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .get_key_stats(&sample_key().to_string())
+        .await;
+
+    assert_key_stats(
+        response,
+        KeyStats {
+            key: sample_key().to_string(),
+            uploaded: 0,
+            downloaded: 0,
+            ratio: None,
+            seeding_time: 0,
+        },
+    )
+    .await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_getting_the_stats_of_all_the_keys() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let mut peer = PeerBuilder::leecher().build();
+    peer.uploaded = NumberOfBytes::new(200);
+
+    env.tracker.account_transfer(&sample_key(), &sample_info_hash(), None, &peer);
+
+    let response = Client::new(env.get_connection_info()).get_all_key_stats().await;
+
+    assert_key_stats_list(
+        response,
+        vec![KeyStats {
+            key: sample_key().to_string(),
+            uploaded: 200,
+            downloaded: 0,
+            ratio: None,
+            seeding_time: 0,
+        }],
+    )
+    .await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_getting_the_stats_of_a_key_when_the_key_is_invalid() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let invalid_key = "INVALID_KEY";
+
+    let response = Client::new(env.get_connection_info()).get_key_stats(invalid_key).await;
+
+    assert_invalid_auth_key_get_param(response, invalid_key).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_setting_the_multipliers_of_a_torrent() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let api_client = Client::new(env.get_connection_info());
+
+    let response = api_client
+        .set_torrent_multipliers(&sample_info_hash().to_string(), freeleech())
+        .await;
+
+    assert_ok(response).await;
+    assert_eq!(
+        env.tracker.get_torrent_multipliers(&sample_info_hash()),
+        Multipliers {
+            upload: 1.0,
+            download: 0.0
+        }
+    );
+
+    let response = api_client.get_torrent_multipliers(&sample_info_hash().to_string()).await;
+
+    assert_torrent_multipliers(
+        response,
+        TorrentMultipliers {
+            info_hash: sample_info_hash().to_string(),
+            upload: 1.0,
+            download: 0.0,
+        },
+    )
+    .await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_removing_the_multipliers_of_a_torrent() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let api_client = Client::new(env.get_connection_info());
+
+    let response = api_client
+        .set_torrent_multipliers(&sample_info_hash().to_string(), freeleech())
+        .await;
+
+    assert_ok(response).await;
+
+    let response = api_client.remove_torrent_multipliers(&sample_info_hash().to_string()).await;
+
+    assert_ok(response).await;
+    assert_eq!(
+        env.tracker.get_torrent_multipliers(&sample_info_hash()),
+        Multipliers::default()
+    );
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_setting_negative_multipliers() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let response = Client::new(env.get_connection_info())
+        .set_torrent_multipliers(
+            &sample_info_hash().to_string(),
+            MultipliersForm {
+                upload: Some(-1.0),
+                download: None,
+            },
+        )
+        .await;
+
+    assert_bad_request(response, "Invalid multipliers: they must be finite and not negative").await;
+    assert_eq!(
+        env.tracker.get_torrent_multipliers(&sample_info_hash()),
+        Multipliers::default()
+    );
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_setting_the_multipliers_of_a_torrent_when_the_provided_infohash_is_invalid() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let api_client = Client::new(env.get_connection_info());
+
+    for invalid_infohash in &invalid_infohashes_returning_bad_request() {
+        let response = api_client.set_torrent_multipliers(invalid_infohash, freeleech()).await;
+
+        assert_invalid_infohash_param(response, invalid_infohash).await;
+    }
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_when_the_torrent_multipliers_cannot_be_saved() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    force_database_error(&env.tracker);
+
+    let response = Client::new(env.get_connection_info())
+        .set_torrent_multipliers(&sample_info_hash().to_string(), freeleech())
+        .await;
+
+    assert_failed_to_set_torrent_multipliers(response).await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_not_allow_setting_the_multipliers_of_a_torrent_for_unauthenticated_users() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+    let response = Client::new(connection_with_invalid_token(env.get_connection_info().bind_address.as_str()))
+        .set_torrent_multipliers(&sample_info_hash().to_string(), freeleech())
+        .await;
+
+    assert_token_not_valid(response).await;
+
+    let response = Client::new(connection_with_no_token(env.get_connection_info().bind_address.as_str()))
+        .set_torrent_multipliers(&sample_info_hash().to_string(), freeleech())
+        .await;
+
+    assert_unauthorized(response).await;
+    assert_eq!(
+        env.tracker.get_torrent_multipliers(&sample_info_hash()),
+        Multipliers::default()
+    );
+
+    env.stop().await;
+}
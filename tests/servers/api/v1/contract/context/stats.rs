@@ -37,17 +37,25 @@ async fn should_allow_getting_tracker_statistics() {
             tcp4_connections_handled: 0,
             tcp4_announces_handled: 0,
             tcp4_scrapes_handled: 0,
+            tcp4_announces_too_frequent: 0,
             tcp6_connections_handled: 0,
             tcp6_announces_handled: 0,
             tcp6_scrapes_handled: 0,
+            tcp6_announces_too_frequent: 0,
             udp4_connections_handled: 0,
             udp4_announces_handled: 0,
             udp4_scrapes_handled: 0,
             udp4_errors_handled: 0,
+            udp4_requests_rate_limited: 0,
+            udp4_announces_too_frequent: 0,
             udp6_connections_handled: 0,
             udp6_announces_handled: 0,
             udp6_scrapes_handled: 0,
             udp6_errors_handled: 0,
+            udp6_requests_rate_limited: 0,
+            udp6_announces_too_frequent: 0,
+            listeners: vec![],
+            latencies: vec![],
         },
     )
     .await;
@@ -55,6 +63,38 @@ async fn should_allow_getting_tracker_statistics() {
     env.stop().await;
 }
 
+#[tokio::test]
+async fn should_allow_getting_tracker_statistics_in_prometheus_format() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    env.add_torrent_peer(
+        &InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap(),
+        &PeerBuilder::default().into(),
+    );
+
+    let response = Client::new(env.get_connection_info())
+        .get_tracker_statistics_in_prometheus_format()
+        .await;
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers().get("content-type").unwrap(),
+        "text/plain; version=0.0.4; charset=utf-8"
+    );
+
+    let text = response.text().await.unwrap();
+
+    assert!(text.contains("torrust_tracker_torrents 1\n"));
+    assert!(text.contains("torrust_tracker_seeders 1\n"));
+    assert!(text.contains("torrust_tracker_announces_handled_total{protocol=\"udp\",ip_version=\"ipv4\"} 0\n"));
+
+    env.stop().await;
+}
+
 #[tokio::test]
 async fn should_not_allow_getting_tracker_statistics_for_unauthenticated_users() {
     INIT.call_once(|| {
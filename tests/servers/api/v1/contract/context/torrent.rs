@@ -1,9 +1,14 @@
 use std::str::FromStr;
 
 use bittorrent_primitives::info_hash::InfoHash;
+use torrust_tracker::core::ScrapeData;
 use torrust_tracker::servers::apis::v1::context::torrent::resources::peer::Peer;
 use torrust_tracker::servers::apis::v1::context::torrent::resources::torrent::{self, Torrent};
+use torrust_tracker::servers::http::v1::responses::scrape::Bencoded;
+use torrust_tracker::servers::http::v1::services::scrape::update_full_scrape;
+use torrust_tracker_configuration::FullScrape;
 use torrust_tracker_primitives::peer::fixture::PeerBuilder;
+use torrust_tracker_primitives::swarm_metadata::SwarmMetadata;
 use torrust_tracker_test_helpers::configuration;
 use tracing::level_filters::LevelFilter;
 
@@ -11,8 +16,8 @@ use crate::common::http::{Query, QueryParam};
 use crate::common::logging::{tracing_stderr_init, INIT};
 use crate::servers::api::connection_info::{connection_with_invalid_token, connection_with_no_token};
 use crate::servers::api::v1::asserts::{
-    assert_bad_request, assert_invalid_infohash_param, assert_not_found, assert_token_not_valid, assert_torrent_info,
-    assert_torrent_list, assert_torrent_not_known, assert_unauthorized,
+    assert_bad_request, assert_full_scrape_not_available, assert_invalid_infohash_param, assert_not_found, assert_ok,
+    assert_token_not_valid, assert_torrent_info, assert_torrent_list, assert_torrent_not_known, assert_unauthorized,
 };
 use crate::servers::api::v1::client::Client;
 use crate::servers::api::v1::contract::fixtures::{
@@ -219,7 +224,9 @@ async fn should_fail_getting_torrents_when_the_info_hash_parameter_is_invalid()
 
         assert_bad_request(
             response,
-            &format!("Invalid URL: invalid infohash param: string \"{invalid_info_hash}\", expected a 40 character long string"),
+            &format!(
+                "Invalid URL: invalid infohash param: string \"{invalid_info_hash}\", expected a 40 or 64 character long string"
+            ),
         )
         .await;
     }
@@ -250,6 +257,60 @@ async fn should_not_allow_getting_torrents_for_unauthenticated_users() {
     env.stop().await;
 }
 
+#[tokio::test]
+async fn should_allow_getting_the_full_scrape() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let mut configuration = configuration::ephemeral();
+    configuration.core.full_scrape = Some(FullScrape::default());
+
+    let env = Started::new(&configuration.into()).await;
+
+    let info_hash = InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap();
+
+    env.add_torrent_peer(&info_hash, &PeerBuilder::default().into());
+
+    update_full_scrape(&env.tracker).await.unwrap();
+
+    let response = Client::new(env.get_connection_info()).get_full_scrape().await;
+
+    let mut expected_scrape_data = ScrapeData::empty();
+    expected_scrape_data.add_file(
+        &info_hash,
+        SwarmMetadata {
+            complete: 1,
+            downloaded: 0,
+            incomplete: 0,
+            downloaders: 0,
+        },
+    );
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.bytes().await.unwrap().to_vec(),
+        Bencoded::from(expected_scrape_data).body()
+    );
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_getting_the_full_scrape_when_it_is_not_enabled() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let response = Client::new(env.get_connection_info()).get_full_scrape().await;
+
+    assert_full_scrape_not_available(response).await;
+
+    env.stop().await;
+}
+
 #[tokio::test]
 async fn should_allow_getting_a_torrent_info() {
     INIT.call_once(|| {
@@ -351,3 +412,98 @@ async fn should_not_allow_getting_a_torrent_info_for_unauthenticated_users() {
 
     env.stop().await;
 }
+
+#[tokio::test]
+async fn should_allow_getting_a_torrent_info_using_the_v2_info_hash_of_a_hybrid_torrent() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let info_hash = InfoHash::from_str("9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d").unwrap();
+    let info_hash_v2 = "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0b8a1a4d58d14d7a21b7d7a45";
+
+    let peer = PeerBuilder::default().into();
+
+    env.add_torrent_peer(&info_hash, &peer);
+
+    let api_client = Client::new(env.get_connection_info());
+
+    let response = api_client.add_hybrid_torrent(&info_hash.to_string(), info_hash_v2).await;
+
+    assert_ok(response).await;
+
+    let response = api_client.get_torrent(info_hash_v2).await;
+
+    assert_torrent_info(
+        response,
+        Torrent {
+            info_hash: "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0".to_string(),
+            seeders: 1,
+            completed: 0,
+            leechers: 0,
+            peers: Some(vec![Peer::from(peer)]),
+        },
+    )
+    .await;
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_allow_removing_a_hybrid_torrent() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let info_hash = "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d";
+    let info_hash_v2 = "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0b8a1a4d58d14d7a21b7d7a45";
+
+    let api_client = Client::new(env.get_connection_info());
+
+    let response = api_client.add_hybrid_torrent(info_hash, info_hash_v2).await;
+    assert_ok(response).await;
+
+    let response = api_client.remove_hybrid_torrent(info_hash, info_hash_v2).await;
+    assert_ok(response).await;
+
+    let truncated_info_hash_v2 = InfoHash::from_str("3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0").unwrap();
+
+    assert_eq!(env.tracker.resolve_info_hash(&truncated_info_hash_v2), truncated_info_hash_v2);
+
+    env.stop().await;
+}
+
+#[tokio::test]
+async fn should_fail_adding_a_hybrid_torrent_when_the_provided_infohashes_are_invalid() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let info_hash = "9e0217d0fa71c87332cd8bf9dbeabcb2c2cf3c4d";
+    let info_hash_v2 = "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0b8a1a4d58d14d7a21b7d7a45";
+
+    let api_client = Client::new(env.get_connection_info());
+
+    for invalid_infohash in &invalid_infohashes_returning_bad_request() {
+        let response = api_client.add_hybrid_torrent(invalid_infohash, info_hash_v2).await;
+
+        assert_invalid_infohash_param(response, invalid_infohash).await;
+
+        let response = api_client.add_hybrid_torrent(info_hash, invalid_infohash).await;
+
+        assert_invalid_infohash_param(response, invalid_infohash).await;
+    }
+
+    // A v1 infohash is not a valid v2 infohash
+    let response = api_client.add_hybrid_torrent(info_hash, info_hash).await;
+
+    assert_invalid_infohash_param(response, info_hash).await;
+
+    env.stop().await;
+}
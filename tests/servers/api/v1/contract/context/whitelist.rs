@@ -38,6 +38,30 @@ async fn should_allow_whitelisting_a_torrent() {
     env.stop().await;
 }
 
+#[tokio::test]
+async fn should_allow_whitelisting_a_torrent_using_its_v2_info_hash() {
+    INIT.call_once(|| {
+        tracing_stderr_init(LevelFilter::ERROR);
+    });
+
+    let env = Started::new(&configuration::ephemeral().into()).await;
+
+    let info_hash_v2 = "3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0b8a1a4d58d14d7a21b7d7a45".to_owned();
+
+    let response = Client::new(env.get_connection_info())
+        .whitelist_a_torrent(&info_hash_v2)
+        .await;
+
+    assert_ok(response).await;
+    assert!(
+        env.tracker
+            .is_info_hash_whitelisted(&InfoHash::from_str("3b245504cf5f11bbdbe1201cea6a6bf45aee1bc0").unwrap())
+            .await
+    );
+
+    env.stop().await;
+}
+
 #[tokio::test]
 async fn should_allow_whitelisting_a_torrent_that_has_been_already_whitelisted() {
     INIT.call_once(|| {
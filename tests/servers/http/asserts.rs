@@ -24,7 +24,7 @@ pub fn assert_bencoded_error(response_text: &String, expected_failure_reason: &s
 
 pub async fn assert_empty_announce_response(response: Response) {
     assert_eq!(response.status(), 200);
-    let announce_response: Announce = serde_bencode::from_str(&response.text().await.unwrap()).unwrap();
+    let announce_response: Announce = serde_bencode::from_bytes(&response.bytes().await.unwrap()).unwrap();
     assert!(announce_response.peers.is_empty());
 }
 
@@ -59,7 +59,7 @@ pub async fn assert_compact_announce_response(response: Response, expected_respo
 /// Sample bencoded scrape response as byte array:
 ///
 /// ```text
-/// b"d5:filesd20:\x9c8B\"\x13\xe3\x0b\xff!+0\xc3`\xd2o\x9a\x02\x13d\"d8:completei1e10:downloadedi0e10:incompletei0eeee"
+/// b"d5:filesd20:\x9c8B\"\x13\xe3\x0b\xff!+0\xc3`\xd2o\x9a\x02\x13d\"d8:completei1e10:downloadedi0e11:downloadersi0e10:incompletei0eeee"
 /// ```
 pub async fn assert_scrape_response(response: Response, expected_response: &scrape::Response) {
     assert_eq!(response.status(), 200);
@@ -71,9 +71,9 @@ pub async fn assert_scrape_response(response: Response, expected_response: &scra
 
 pub async fn assert_is_announce_response(response: Response) {
     assert_eq!(response.status(), 200);
-    let body = response.text().await.unwrap();
-    let _announce_response: Announce = serde_bencode::from_str(&body)
-        .unwrap_or_else(|_| panic!("response body should be a valid announce response, got \"{}\"", &body));
+    let body = response.bytes().await.unwrap();
+    let _announce_response: Announce = serde_bencode::from_bytes(&body)
+        .unwrap_or_else(|_| panic!("response body should be a valid announce response, got \"{:#?}\"", &body));
 }
 
 // Error responses
@@ -114,6 +114,38 @@ pub async fn assert_torrent_not_in_whitelist_error_response(response: Response)
     assert_bencoded_error(&response.text().await.unwrap(), "is not whitelisted", Location::caller());
 }
 
+pub async fn assert_torrent_blacklisted_error_response(response: Response, reason: &str) {
+    assert_eq!(response.status(), 200);
+
+    assert_bencoded_error(
+        &response.text().await.unwrap(),
+        &format!("is blacklisted: {reason}"),
+        Location::caller(),
+    );
+}
+
+pub async fn assert_torrent_not_allowed_for_key_error_response(response: Response) {
+    assert_eq!(response.status(), 200);
+
+    assert_bencoded_error(
+        &response.text().await.unwrap(),
+        "is not allowed for the key",
+        Location::caller(),
+    );
+}
+
+pub async fn assert_client_not_allowed_error_response(response: Response) {
+    assert_eq!(response.status(), 200);
+
+    assert_bencoded_error(&response.text().await.unwrap(), "is not allowed", Location::caller());
+}
+
+pub async fn assert_ip_blocked_error_response(response: Response) {
+    assert_eq!(response.status(), 200);
+
+    assert_bencoded_error(&response.text().await.unwrap(), "is blocked", Location::caller());
+}
+
 pub async fn assert_could_not_find_remote_address_on_x_forwarded_for_header_error_response(response: Response) {
     assert_eq!(response.status(), 200);
 
@@ -138,6 +170,18 @@ pub async fn assert_cannot_parse_query_params_error_response(response: Response,
     );
 }
 
+pub async fn assert_maintenance_error_response(response: Response, expected_retry_in: u32) {
+    assert_eq!(response.status(), 200);
+
+    let response_text = response.text().await.unwrap();
+
+    assert_bencoded_error(&response_text, "The tracker is in maintenance mode", Location::caller());
+
+    let error: Error = serde_bencode::from_str(&response_text).unwrap();
+
+    assert_eq!(error.retry_in, Some(expected_retry_in));
+}
+
 pub async fn assert_authentication_error_response(response: Response) {
     assert_eq!(response.status(), 200);
 
@@ -126,6 +126,16 @@ impl QueryBuilder {
         self
     }
 
+    pub fn with_uploaded(mut self, uploaded: BaseTenASCII) -> Self {
+        self.announce_query.uploaded = uploaded;
+        self
+    }
+
+    pub fn with_downloaded(mut self, downloaded: BaseTenASCII) -> Self {
+        self.announce_query.downloaded = downloaded;
+        self
+    }
+
     pub fn without_compact(mut self) -> Self {
         self.announce_query.compact = None;
         self
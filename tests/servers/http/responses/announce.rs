@@ -33,6 +33,37 @@ impl From<peer::Peer> for DictionaryPeer {
     }
 }
 
+/// The fields that are only included in the announce responses in some cases.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct OptionalFields {
+    #[serde(rename = "warning message")]
+    pub warning_message: Option<String>,
+    #[serde(rename = "tracker id")]
+    pub tracker_id: Option<String>,
+    #[serde(rename = "external ip", default, with = "serde_bytes")]
+    pub external_ip: Option<Vec<u8>>,
+}
+
+impl OptionalFields {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, serde_bencode::Error> {
+        serde_bencode::from_bytes::<OptionalFields>(bytes)
+    }
+}
+
+/// A non-compact announce response where the peer IDs could be omitted.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct AnnounceWithOptionalPeerIds {
+    pub peers: Vec<DictionaryPeerWithOptionalId>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct DictionaryPeerWithOptionalId {
+    pub ip: String,
+    #[serde(rename = "peer id", default, with = "serde_bytes")]
+    pub peer_id: Option<Vec<u8>>,
+    pub port: u16,
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct DeserializedCompact {
     pub complete: u32,
@@ -26,9 +26,10 @@ impl Response {
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Default)]
 pub struct File {
-    pub complete: i64,   // The number of active peers that have completed downloading
-    pub downloaded: i64, // The number of peers that have ever completed downloading
-    pub incomplete: i64, // The number of active peers that have not completed downloading
+    pub complete: i64,    // The number of active peers that have completed downloading
+    pub downloaded: i64,  // The number of peers that have ever completed downloading
+    pub incomplete: i64,  // The number of active peers that have not completed downloading
+    pub downloaders: i64, // The number of active peers that want to download more pieces (BEP 21)
 }
 
 impl File {
@@ -145,6 +146,7 @@ fn parse_bencoded_file(value: &Value) -> Result<File, BencodeParseError> {
             let mut complete = None;
             let mut downloaded = None;
             let mut incomplete = None;
+            let mut downloaders = None;
 
             for file_field in dict {
                 let field_name = file_field.0;
@@ -162,6 +164,8 @@ fn parse_bencoded_file(value: &Value) -> Result<File, BencodeParseError> {
                     downloaded = Some(field_value);
                 } else if field_name == b"incomplete" {
                     incomplete = Some(field_value);
+                } else if field_name == b"downloaders" {
+                    downloaders = Some(field_value);
                 } else {
                     return Err(BencodeParseError::InvalidFileField {
                         value: file_field.1.clone(),
@@ -187,10 +191,17 @@ fn parse_bencoded_file(value: &Value) -> Result<File, BencodeParseError> {
                 });
             }
 
+            if downloaders.is_none() {
+                return Err(BencodeParseError::MissingFileField {
+                    field_name: "downloaders".to_string(),
+                });
+            }
+
             File {
                 complete: complete.unwrap(),
                 downloaded: downloaded.unwrap(),
                 incomplete: incomplete.unwrap(),
+                downloaders: downloaders.unwrap(),
             }
         }
         _ => return Err(BencodeParseError::InvalidValueExpectedDict { value: value.clone() }),
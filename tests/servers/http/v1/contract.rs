@@ -104,9 +104,12 @@ mod for_all_config_modes {
 
         use aquatic_udp_protocol::PeerId;
         use bittorrent_primitives::info_hash::InfoHash;
+        use ipnet::IpNet;
         use local_ip_address::local_ip;
         use reqwest::{Response, StatusCode};
         use tokio::net::TcpListener;
+        use torrust_tracker::core::client_filter::PeerIdPrefix;
+        use torrust_tracker_configuration::ClientFilter;
         use torrust_tracker_primitives::peer::fixture::PeerBuilder;
         use torrust_tracker_test_helpers::configuration;
         use tracing::level_filters::LevelFilter;
@@ -115,12 +118,17 @@ mod for_all_config_modes {
         use crate::common::logging::{tracing_stderr_init, INIT};
         use crate::servers::http::asserts::{
             assert_announce_response, assert_bad_announce_request_error_response, assert_cannot_parse_query_param_error_response,
-            assert_cannot_parse_query_params_error_response, assert_compact_announce_response, assert_empty_announce_response,
-            assert_is_announce_response, assert_missing_query_params_for_announce_request_error_response,
+            assert_cannot_parse_query_params_error_response, assert_client_not_allowed_error_response,
+            assert_compact_announce_response, assert_empty_announce_response, assert_ip_blocked_error_response,
+            assert_is_announce_response, assert_maintenance_error_response,
+            assert_missing_query_params_for_announce_request_error_response, assert_torrent_blacklisted_error_response,
         };
         use crate::servers::http::client::Client;
         use crate::servers::http::requests::announce::{Compact, QueryBuilder};
-        use crate::servers::http::responses::announce::{Announce, CompactPeer, CompactPeerList, DictionaryPeer};
+        use crate::servers::http::responses::announce::{
+            Announce, AnnounceWithOptionalPeerIds, CompactPeer, CompactPeerList, DictionaryPeer, DictionaryPeerWithOptionalId,
+            OptionalFields,
+        };
         use crate::servers::http::{responses, Started};
 
         #[tokio::test]
@@ -248,6 +256,154 @@ mod for_all_config_modes {
             env.stop().await;
         }
 
+        #[tokio::test]
+        async fn should_fail_with_a_retry_in_key_when_the_tracker_is_in_maintenance_mode() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let mut configuration = configuration::ephemeral();
+            configuration.core.maintenance.enabled = true;
+            configuration.core.maintenance.retry_in = 5;
+
+            let env = Started::new(&configuration.into()).await;
+
+            let response = Client::new(*env.bind_address())
+                .announce(&QueryBuilder::default().query())
+                .await;
+
+            assert_maintenance_error_response(response, 5).await;
+
+            env.stop().await;
+        }
+
+        #[tokio::test]
+        async fn should_fail_when_the_client_software_is_denied() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let mut configuration = configuration::ephemeral();
+            configuration.core.client_filter = ClientFilter::Deny;
+
+            let env = Started::new(&configuration.into()).await;
+
+            env.tracker
+                .add_client_prefix(&PeerIdPrefix::from_str("-qB").unwrap())
+                .unwrap();
+
+            let response = Client::new(*env.bind_address())
+                .announce(
+                    &QueryBuilder::default()
+                        .with_peer_id(&PeerId(*b"-qB00000000000000001"))
+                        .query(),
+                )
+                .await;
+
+            assert_client_not_allowed_error_response(response).await;
+
+            env.stop().await;
+        }
+
+        #[tokio::test]
+        async fn should_fail_when_the_client_software_is_not_in_the_allow_list() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let mut configuration = configuration::ephemeral();
+            configuration.core.client_filter = ClientFilter::Allow;
+
+            let env = Started::new(&configuration.into()).await;
+
+            env.tracker
+                .add_client_prefix(&PeerIdPrefix::from_str("-TR").unwrap())
+                .unwrap();
+
+            let response = Client::new(*env.bind_address())
+                .announce(
+                    &QueryBuilder::default()
+                        .with_peer_id(&PeerId(*b"-qB00000000000000001"))
+                        .query(),
+                )
+                .await;
+
+            assert_client_not_allowed_error_response(response).await;
+
+            env.stop().await;
+        }
+
+        #[tokio::test]
+        async fn should_fail_when_the_peer_ip_is_in_a_blocked_network() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let env = Started::new(&configuration::ephemeral().into()).await;
+
+            env.tracker
+                .add_blocked_network(&IpNet::from_str("127.0.0.0/8").unwrap())
+                .unwrap();
+
+            let response = Client::new(*env.bind_address())
+                .announce(&QueryBuilder::default().query())
+                .await;
+
+            assert_ip_blocked_error_response(response).await;
+
+            env.stop().await;
+        }
+
+        #[tokio::test]
+        async fn should_fail_when_the_announced_ip_is_in_a_blocked_network() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let mut configuration = configuration::ephemeral();
+            configuration.core.net.trusted_networks = vec!["127.0.0.0/8".parse().unwrap()];
+
+            let env = Started::new(&configuration.into()).await;
+
+            env.tracker
+                .add_blocked_network(&IpNet::from_str("126.0.0.0/8").unwrap())
+                .unwrap();
+
+            let params = QueryBuilder::default().query().params();
+
+            let response = Client::new(*env.bind_address())
+                .get(&format!("announce?{params}&ip=126.0.0.2"))
+                .await;
+
+            assert_ip_blocked_error_response(response).await;
+
+            env.stop().await;
+        }
+
+        #[tokio::test]
+        async fn should_fail_when_the_torrent_is_blacklisted() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let mut configuration = configuration::ephemeral();
+            configuration.core.blacklist.reason = "DMCA takedown".to_string();
+
+            let env = Started::new(&configuration.into()).await;
+
+            let info_hash = InfoHash::from_str("9c38422213e30bff212b30c360d26f9a02136422").unwrap();
+
+            env.tracker.add_torrent_to_blacklist(&info_hash).await.unwrap();
+
+            let response = Client::new(*env.bind_address())
+                .announce(&QueryBuilder::default().with_info_hash(&info_hash).query())
+                .await;
+
+            assert_torrent_blacklisted_error_response(response, "DMCA takedown").await;
+
+            env.stop().await;
+        }
+
         #[tokio::test]
         async fn should_not_fail_when_the_peer_address_param_is_invalid() {
             INIT.call_once(|| {
@@ -1026,6 +1182,182 @@ mod for_all_config_modes {
 
             env.stop().await;
         }
+
+        #[tokio::test]
+        async fn should_keep_the_ipv6_address_in_the_request_param_for_a_dual_stack_peer_announcing_from_an_ipv4_address() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            // IPv6 Tracker Extension
+            // https://www.bittorrent.org/beps/bep_0007.html
+
+            let env = Started::new(&configuration::ephemeral_public().into()).await;
+
+            let info_hash = InfoHash::from_str("9c38422213e30bff212b30c360d26f9a02136422").unwrap();
+
+            let params = QueryBuilder::default().with_info_hash(&info_hash).query().params();
+
+            let response = Client::new(*env.bind_address())
+                .get(&format!("announce?{params}&ipv6=2606:4700::1111"))
+                .await;
+
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let peers = env.tracker.get_torrent_peers(&info_hash);
+
+            assert!(peers[0].peer_addr.is_ipv4());
+            assert_eq!(
+                peers[0].alternative_addr,
+                Some(SocketAddr::new(IpAddr::from_str("2606:4700::1111").unwrap(), 17548))
+            );
+
+            env.stop().await;
+        }
+
+        #[tokio::test]
+        async fn should_ignore_the_ipv6_address_in_the_request_param_when_it_is_not_a_public_ip() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let env = Started::new(&configuration::ephemeral_public().into()).await;
+
+            let info_hash = InfoHash::from_str("9c38422213e30bff212b30c360d26f9a02136422").unwrap();
+
+            let params = QueryBuilder::default().with_info_hash(&info_hash).query().params();
+
+            let response = Client::new(*env.bind_address())
+                .get(&format!("announce?{params}&ipv6=fd00::1"))
+                .await;
+
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let peers = env.tracker.get_torrent_peers(&info_hash);
+
+            assert_eq!(peers[0].alternative_addr, None);
+
+            env.stop().await;
+        }
+
+        #[tokio::test]
+        async fn should_omit_the_peer_ids_in_the_non_compact_response_when_the_client_sends_the_no_peer_id_param() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let env = Started::new(&configuration::ephemeral_public().into()).await;
+
+            let info_hash = InfoHash::from_str("9c38422213e30bff212b30c360d26f9a02136422").unwrap();
+
+            // Peer 1
+            let previously_announced_peer = PeerBuilder::default().with_peer_id(&PeerId(*b"-qB00000000000000001")).build();
+
+            env.add_torrent_peer(&info_hash, &previously_announced_peer);
+
+            // Announce the new Peer 2
+            let params = QueryBuilder::default()
+                .with_info_hash(&info_hash)
+                .with_peer_id(&PeerId(*b"-qB00000000000000002"))
+                .query()
+                .params();
+
+            let response = Client::new(*env.bind_address())
+                .get(&format!("announce?{params}&no_peer_id=1"))
+                .await;
+
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let announce_response: AnnounceWithOptionalPeerIds =
+                serde_bencode::from_bytes(&response.bytes().await.unwrap()).unwrap();
+
+            assert_eq!(
+                announce_response.peers,
+                vec![DictionaryPeerWithOptionalId {
+                    ip: previously_announced_peer.peer_addr.ip().to_string(),
+                    peer_id: None,
+                    port: previously_announced_peer.peer_addr.port(),
+                }]
+            );
+
+            env.stop().await;
+        }
+
+        #[tokio::test]
+        async fn should_return_the_tracker_id_when_it_is_configured() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let mut configuration = configuration::ephemeral_public();
+            configuration.core.tracker_id = Some("torrust".to_string());
+
+            let env = Started::new(&configuration.into()).await;
+
+            let response = Client::new(*env.bind_address())
+                .announce(&QueryBuilder::default().query())
+                .await;
+
+            let optional_fields = OptionalFields::from_bytes(&response.bytes().await.unwrap()).unwrap();
+
+            assert_eq!(optional_fields.tracker_id, Some("torrust".to_string()));
+
+            env.stop().await;
+        }
+
+        #[tokio::test]
+        async fn should_return_the_warning_message_configured_for_the_torrent() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let info_hash = InfoHash::from_str("9c38422213e30bff212b30c360d26f9a02136422").unwrap();
+
+            let mut configuration = configuration::ephemeral_public();
+            configuration
+                .core
+                .torrent_warning_messages
+                .insert(info_hash.to_hex_string(), "This torrent has been replaced".to_string());
+
+            let env = Started::new(&configuration.into()).await;
+
+            let response = Client::new(*env.bind_address())
+                .announce(&QueryBuilder::default().with_info_hash(&info_hash).query())
+                .await;
+
+            let optional_fields = OptionalFields::from_bytes(&response.bytes().await.unwrap()).unwrap();
+
+            assert_eq!(
+                optional_fields.warning_message,
+                Some("This torrent has been replaced".to_string())
+            );
+
+            env.stop().await;
+        }
+
+        #[tokio::test]
+        async fn should_return_the_external_ip_of_the_client() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            // Tracker Returns External IP
+            // https://www.bittorrent.org/beps/bep_0024.html
+
+            let env = Started::new(&configuration::ephemeral_public().into()).await;
+
+            let response = Client::new(*env.bind_address())
+                .announce(&QueryBuilder::default().with_compact(Compact::Accepted).query())
+                .await;
+
+            let optional_fields = OptionalFields::from_bytes(&response.bytes().await.unwrap()).unwrap();
+
+            assert_eq!(optional_fields.external_ip, Some(Ipv4Addr::LOCALHOST.octets().to_vec()));
+            assert_eq!(optional_fields.tracker_id, None);
+            assert_eq!(optional_fields.warning_message, None);
+
+            env.stop().await;
+        }
     }
 
     mod receiving_an_scrape_request {
@@ -1038,12 +1370,16 @@ mod for_all_config_modes {
         // Vuze (bittorrent client) docs:
         // https://wiki.vuze.com/w/Scrape
 
+        use std::io::Read;
         use std::net::{IpAddr, Ipv6Addr, SocketAddrV6};
         use std::str::FromStr;
 
         use aquatic_udp_protocol::PeerId;
         use bittorrent_primitives::info_hash::InfoHash;
+        use flate2::read::GzDecoder;
         use tokio::net::TcpListener;
+        use torrust_tracker::servers::http::v1::services::scrape::update_full_scrape;
+        use torrust_tracker_configuration::FullScrape;
         use torrust_tracker_primitives::peer::fixture::PeerBuilder;
         use torrust_tracker_test_helpers::configuration;
         use tracing::level_filters::LevelFilter;
@@ -1074,6 +1410,133 @@ mod for_all_config_modes {
             env.stop().await;
         }
 
+        #[tokio::test]
+        async fn should_return_the_full_scrape_when_the_request_is_empty_and_the_full_scrape_is_enabled() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let mut configuration = configuration::ephemeral_public();
+            configuration.core.full_scrape = Some(FullScrape::default());
+
+            let env = Started::new(&configuration.into()).await;
+
+            let info_hash = InfoHash::from_str("9c38422213e30bff212b30c360d26f9a02136422").unwrap();
+
+            env.add_torrent_peer(
+                &info_hash,
+                &PeerBuilder::default()
+                    .with_peer_id(&PeerId(*b"-qB00000000000000001"))
+                    .with_bytes_pending_to_download(1)
+                    .build(),
+            );
+
+            update_full_scrape(&env.tracker).await.unwrap();
+
+            let response = Client::new(*env.bind_address()).get("scrape").await;
+
+            let expected_scrape_response = ResponseBuilder::default()
+                .add_file(
+                    info_hash.bytes(),
+                    File {
+                        complete: 0,
+                        downloaded: 0,
+                        incomplete: 1,
+                        downloaders: 1,
+                    },
+                )
+                .build();
+
+            assert_scrape_response(response, &expected_scrape_response).await;
+
+            env.stop().await;
+        }
+
+        async fn start_with_a_gzipped_full_scrape(info_hash: &InfoHash) -> Started {
+            let mut configuration = configuration::ephemeral_public();
+            configuration.core.full_scrape = Some(FullScrape {
+                gzip: true,
+                ..FullScrape::default()
+            });
+
+            let env = Started::new(&configuration.into()).await;
+
+            env.add_torrent_peer(
+                info_hash,
+                &PeerBuilder::default()
+                    .with_peer_id(&PeerId(*b"-qB00000000000000001"))
+                    .with_bytes_pending_to_download(1)
+                    .build(),
+            );
+
+            update_full_scrape(&env.tracker).await.unwrap();
+
+            env
+        }
+
+        fn sample_full_scrape_response(info_hash: &InfoHash) -> scrape::Response {
+            ResponseBuilder::default()
+                .add_file(
+                    info_hash.bytes(),
+                    File {
+                        complete: 0,
+                        downloaded: 0,
+                        incomplete: 1,
+                        downloaders: 1,
+                    },
+                )
+                .build()
+        }
+
+        #[tokio::test]
+        async fn should_return_the_gzipped_full_scrape_when_the_client_accepts_gzip() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let info_hash = InfoHash::from_str("9c38422213e30bff212b30c360d26f9a02136422").unwrap();
+
+            let env = start_with_a_gzipped_full_scrape(&info_hash).await;
+
+            let response = Client::new(*env.bind_address())
+                .get_with_header("scrape", "Accept-Encoding", "gzip")
+                .await;
+
+            assert_eq!(response.status(), 200);
+            assert_eq!(response.headers().get("content-encoding").unwrap(), "gzip");
+
+            let mut bencoded = vec![];
+            GzDecoder::new(response.bytes().await.unwrap().as_ref())
+                .read_to_end(&mut bencoded)
+                .unwrap();
+
+            assert_eq!(
+                scrape::Response::try_from_bencoded(&bencoded).unwrap(),
+                sample_full_scrape_response(&info_hash)
+            );
+
+            env.stop().await;
+        }
+
+        #[tokio::test]
+        async fn should_return_the_uncompressed_full_scrape_when_the_client_does_not_accept_gzip() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let info_hash = InfoHash::from_str("9c38422213e30bff212b30c360d26f9a02136422").unwrap();
+
+            let env = start_with_a_gzipped_full_scrape(&info_hash).await;
+
+            let response = Client::new(*env.bind_address()).get("scrape").await;
+
+            assert_eq!(response.headers().get("content-encoding"), None);
+
+            assert_scrape_response(response, &sample_full_scrape_response(&info_hash)).await;
+
+            env.stop().await;
+        }
+
         #[tokio::test]
         async fn should_fail_when_the_info_hash_param_is_invalid() {
             INIT.call_once(|| {
@@ -1128,6 +1591,7 @@ mod for_all_config_modes {
                         complete: 0,
                         downloaded: 0,
                         incomplete: 1,
+                        downloaders: 1,
                     },
                 )
                 .build();
@@ -1170,6 +1634,7 @@ mod for_all_config_modes {
                         complete: 1,
                         downloaded: 0,
                         incomplete: 0,
+                        downloaders: 0,
                     },
                 )
                 .build();
@@ -1440,6 +1905,7 @@ mod configured_as_whitelisted {
                         complete: 0,
                         downloaded: 0,
                         incomplete: 1,
+                        downloaders: 1,
                     },
                 )
                 .build();
@@ -1463,7 +1929,9 @@ mod configured_as_private {
         use tracing::level_filters::LevelFilter;
 
         use crate::common::logging::{tracing_stderr_init, INIT};
-        use crate::servers::http::asserts::{assert_authentication_error_response, assert_is_announce_response};
+        use crate::servers::http::asserts::{
+            assert_authentication_error_response, assert_is_announce_response, assert_torrent_not_allowed_for_key_error_response,
+        };
         use crate::servers::http::client::Client;
         use crate::servers::http::requests::announce::QueryBuilder;
         use crate::servers::http::Started;
@@ -1544,6 +2012,72 @@ mod configured_as_private {
 
             env.stop().await;
         }
+
+        #[tokio::test]
+        async fn should_add_the_transfer_reported_by_the_peer_to_the_stats_of_the_authentication_key() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+            let expiring_key = env.tracker.generate_auth_key(Some(Duration::from_secs(60))).await.unwrap();
+
+            let client = Client::authenticated(*env.bind_address(), expiring_key.key());
+
+            for (uploaded, downloaded) in [(100, 200), (300, 500)] {
+                let response = client
+                    .announce(
+                        &QueryBuilder::default()
+                            .with_uploaded(uploaded)
+                            .with_downloaded(downloaded)
+                            .query(),
+                    )
+                    .await;
+
+                assert_is_announce_response(response).await;
+            }
+
+            let stats = env.tracker.get_key_stats(&expiring_key.key());
+
+            assert_eq!((stats.uploaded, stats.downloaded), (300, 500));
+
+            env.stop().await;
+        }
+
+        #[tokio::test]
+        async fn should_fail_if_the_torrent_is_not_in_the_allow_list_of_the_authentication_key() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+            let expiring_key = env.tracker.generate_auth_key(Some(Duration::from_secs(60))).await.unwrap();
+
+            let allowed_info_hash = InfoHash::from_str("9c38422213e30bff212b30c360d26f9a02136422").unwrap();
+            let other_info_hash = InfoHash::from_str("99c82bb73505a3c0b453f9fa0e881d6e5a32a0c1").unwrap();
+
+            env.tracker
+                .add_torrent_to_key_allow_list(&expiring_key.key(), &allowed_info_hash)
+                .unwrap();
+
+            let client = Client::authenticated(*env.bind_address(), expiring_key.key());
+
+            let response = client
+                .announce(&QueryBuilder::default().with_info_hash(&allowed_info_hash).query())
+                .await;
+
+            assert_is_announce_response(response).await;
+
+            let response = client
+                .announce(&QueryBuilder::default().with_info_hash(&other_info_hash).query())
+                .await;
+
+            assert_torrent_not_allowed_for_key_error_response(response).await;
+
+            env.stop().await;
+        }
     }
 
     mod receiving_an_scrape_request {
@@ -1651,6 +2185,7 @@ mod configured_as_private {
                         complete: 0,
                         downloaded: 0,
                         incomplete: 1,
+                        downloaders: 1,
                     },
                 )
                 .build();
@@ -1697,6 +2232,46 @@ mod configured_as_private {
 
             env.stop().await;
         }
+
+        #[tokio::test]
+        async fn should_return_the_zeroed_file_when_the_torrent_is_not_in_the_allow_list_of_the_authentication_key() {
+            INIT.call_once(|| {
+                tracing_stderr_init(LevelFilter::ERROR);
+            });
+
+            let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+            let info_hash = InfoHash::from_str("9c38422213e30bff212b30c360d26f9a02136422").unwrap();
+            let allowed_info_hash = InfoHash::from_str("99c82bb73505a3c0b453f9fa0e881d6e5a32a0c1").unwrap();
+
+            env.add_torrent_peer(
+                &info_hash,
+                &PeerBuilder::default()
+                    .with_peer_id(&PeerId(*b"-qB00000000000000001"))
+                    .with_bytes_pending_to_download(1)
+                    .build(),
+            );
+
+            let expiring_key = env.tracker.generate_auth_key(Some(Duration::from_secs(60))).await.unwrap();
+
+            env.tracker
+                .add_torrent_to_key_allow_list(&expiring_key.key(), &allowed_info_hash)
+                .unwrap();
+
+            let response = Client::authenticated(*env.bind_address(), expiring_key.key())
+                .scrape(
+                    &requests::scrape::QueryBuilder::default()
+                        .with_one_info_hash(&info_hash)
+                        .query(),
+                )
+                .await;
+
+            let expected_scrape_response = ResponseBuilder::default().add_file(info_hash.bytes(), File::zeroed()).build();
+
+            assert_scrape_response(response, &expected_scrape_response).await;
+
+            env.stop().await;
+        }
     }
 }
 
@@ -113,20 +113,191 @@ mod receiving_a_connection_request {
     }
 }
 
+mod receiving_requests_over_the_rate_limit {
+    use aquatic_udp_protocol::{ConnectRequest, TransactionId};
+    use bittorrent_tracker_client::udp::client::UdpTrackerClient;
+    use torrust_tracker_configuration::{UdpRateLimit, UdpRateLimitAction, DEFAULT_TIMEOUT};
+    use torrust_tracker_test_helpers::configuration;
+    use tracing::level_filters::LevelFilter;
+
+    use crate::common::logging::{tracing_stderr_init, INIT};
+    use crate::servers::udp::asserts::{get_error_response_message, is_connect_response};
+    use crate::servers::udp::Started;
+
+    #[tokio::test]
+    async fn should_return_an_error_response_when_the_client_exceeds_the_rate_limit() {
+        INIT.call_once(|| {
+            tracing_stderr_init(LevelFilter::ERROR);
+        });
+
+        let mut configuration = configuration::ephemeral();
+
+        configuration.udp_trackers.as_mut().unwrap()[0].rate_limit = Some(UdpRateLimit {
+            requests_per_second: 1,
+            burst: 1,
+            exceeded_action: UdpRateLimitAction::Error,
+            ..Default::default()
+        });
+
+        let env = Started::new(&configuration.into()).await;
+
+        let client = match UdpTrackerClient::new(env.bind_address(), DEFAULT_TIMEOUT).await {
+            Ok(udp_tracker_client) => udp_tracker_client,
+            Err(err) => panic!("{err}"),
+        };
+
+        let mut responses = vec![];
+
+        for transaction_id in [123, 124] {
+            let connect_request = ConnectRequest {
+                transaction_id: TransactionId::new(transaction_id),
+            };
+
+            match client.send(connect_request.into()).await {
+                Ok(_) => (),
+                Err(err) => panic!("{err}"),
+            };
+
+            match client.receive().await {
+                Ok(response) => responses.push(response),
+                Err(err) => panic!("{err}"),
+            };
+        }
+
+        assert!(is_connect_response(&responses[0], TransactionId::new(123)));
+        assert!(get_error_response_message(&responses[1])
+            .unwrap()
+            .contains("rate limit exceeded"));
+
+        let stats = env.tracker.get_stats().await;
+
+        assert_eq!(stats.udp4_requests_rate_limited, 1);
+
+        drop(stats);
+
+        env.stop().await;
+    }
+}
+
+mod receiving_requests_in_maintenance_mode {
+    use aquatic_udp_protocol::{InfoHash, ScrapeRequest, TransactionId};
+    use bittorrent_tracker_client::udp::client::UdpTrackerClient;
+    use torrust_tracker_configuration::DEFAULT_TIMEOUT;
+    use torrust_tracker_test_helpers::configuration;
+    use tracing::level_filters::LevelFilter;
+
+    use crate::common::logging::{tracing_stderr_init, INIT};
+    use crate::servers::udp::asserts::get_error_response_message;
+    use crate::servers::udp::contract::send_connection_request;
+    use crate::servers::udp::Started;
+
+    #[tokio::test]
+    async fn should_return_an_error_response_for_the_scrape_requests_when_the_tracker_is_in_maintenance_mode() {
+        INIT.call_once(|| {
+            tracing_stderr_init(LevelFilter::ERROR);
+        });
+
+        let mut configuration = configuration::ephemeral();
+        configuration.core.maintenance.enabled = true;
+
+        let env = Started::new(&configuration.into()).await;
+
+        let client = match UdpTrackerClient::new(env.bind_address(), DEFAULT_TIMEOUT).await {
+            Ok(udp_tracker_client) => udp_tracker_client,
+            Err(err) => panic!("{err}"),
+        };
+
+        let transaction_id = TransactionId::new(123);
+
+        // The connect requests are still handled
+        let connection_id = send_connection_request(transaction_id, &client).await;
+
+        let scrape_request = ScrapeRequest {
+            connection_id,
+            transaction_id,
+            info_hashes: vec![InfoHash([0u8; 20])],
+        };
+
+        match client.send(scrape_request.into()).await {
+            Ok(_) => (),
+            Err(err) => panic!("{err}"),
+        };
+
+        let response = match client.receive().await {
+            Ok(response) => response,
+            Err(err) => panic!("{err}"),
+        };
+
+        assert!(get_error_response_message(&response)
+            .unwrap()
+            .contains("The tracker is in maintenance mode"));
+
+        env.stop().await;
+    }
+}
+
+mod receiving_requests_from_a_blocked_network {
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    use aquatic_udp_protocol::{ConnectRequest, TransactionId};
+    use bittorrent_tracker_client::udp::client::UdpTrackerClient;
+    use ipnet::IpNet;
+    use torrust_tracker_test_helpers::configuration;
+    use tracing::level_filters::LevelFilter;
+
+    use crate::common::logging::{tracing_stderr_init, INIT};
+    use crate::servers::udp::Started;
+
+    #[tokio::test]
+    async fn should_not_respond_to_the_requests() {
+        INIT.call_once(|| {
+            tracing_stderr_init(LevelFilter::ERROR);
+        });
+
+        let env = Started::new(&configuration::ephemeral().into()).await;
+
+        env.tracker
+            .add_blocked_network(&IpNet::from_str("127.0.0.0/8").unwrap())
+            .unwrap();
+
+        let client = match UdpTrackerClient::new(env.bind_address(), Duration::from_millis(500)).await {
+            Ok(udp_tracker_client) => udp_tracker_client,
+            Err(err) => panic!("{err}"),
+        };
+
+        let connect_request = ConnectRequest {
+            transaction_id: TransactionId::new(123),
+        };
+
+        match client.send(connect_request.into()).await {
+            Ok(_) => (),
+            Err(err) => panic!("{err}"),
+        };
+
+        assert!(client.receive().await.is_err());
+
+        env.stop().await;
+    }
+}
+
 mod receiving_an_announce_request {
     use std::net::Ipv4Addr;
+    use std::str::FromStr;
 
     use aquatic_udp_protocol::{
         AnnounceActionPlaceholder, AnnounceEvent, AnnounceRequest, ConnectionId, InfoHash, NumberOfBytes, NumberOfPeers, PeerId,
-        PeerKey, Port, TransactionId,
+        PeerKey, Port, Request, TransactionId,
     };
     use bittorrent_tracker_client::udp::client::UdpTrackerClient;
-    use torrust_tracker_configuration::DEFAULT_TIMEOUT;
+    use torrust_tracker::core::client_filter::PeerIdPrefix;
+    use torrust_tracker::core::statistics::{Listener, Protocol, RequestKind};
+    use torrust_tracker_configuration::{ClientFilter, DEFAULT_TIMEOUT};
     use torrust_tracker_test_helpers::configuration;
     use tracing::level_filters::LevelFilter;
 
     use crate::common::logging::{tracing_stderr_init, INIT};
-    use crate::servers::udp::asserts::is_ipv4_announce_response;
+    use crate::servers::udp::asserts::{get_error_response_message, is_ipv4_announce_response};
     use crate::servers::udp::contract::send_connection_request;
     use crate::servers::udp::Started;
 
@@ -186,6 +357,237 @@ mod receiving_an_announce_request {
         env.stop().await;
     }
 
+    #[tokio::test]
+    async fn should_return_an_error_response_when_the_request_options_are_malformed() {
+        INIT.call_once(|| {
+            tracing_stderr_init(LevelFilter::ERROR);
+        });
+
+        let env = Started::new(&configuration::ephemeral().into()).await;
+
+        let client = match UdpTrackerClient::new(env.bind_address(), DEFAULT_TIMEOUT).await {
+            Ok(udp_tracker_client) => udp_tracker_client,
+            Err(err) => panic!("{err}"),
+        };
+
+        let tx_id = TransactionId::new(123);
+
+        let c_id = send_connection_request(tx_id, &client).await;
+
+        let announce_request = AnnounceRequest {
+            connection_id: ConnectionId(c_id.0),
+            action_placeholder: AnnounceActionPlaceholder::default(),
+            transaction_id: tx_id,
+            info_hash: InfoHash([0u8; 20]),
+            peer_id: PeerId([255u8; 20]),
+            bytes_downloaded: NumberOfBytes(0i64.into()),
+            bytes_uploaded: NumberOfBytes(0i64.into()),
+            bytes_left: NumberOfBytes(0i64.into()),
+            event: AnnounceEvent::Started.into(),
+            ip_address: Ipv4Addr::new(0, 0, 0, 0).into(),
+            key: PeerKey::new(0i32),
+            peers_wanted: NumberOfPeers(1i32.into()),
+            port: Port(client.client.socket.local_addr().unwrap().port().into()),
+        };
+
+        let mut payload = Vec::new();
+
+        Request::from(announce_request).write_bytes(&mut payload).unwrap();
+
+        // A `URLData` option announcing 9 bytes but containing only 4
+        payload.extend_from_slice(&[0x2, 0x9]);
+        payload.extend_from_slice(b"/ann");
+
+        match client.client.send(&payload).await {
+            Ok(_) => (),
+            Err(err) => panic!("{err}"),
+        };
+
+        let response = match client.receive().await {
+            Ok(response) => response,
+            Err(err) => panic!("{err}"),
+        };
+
+        assert!(get_error_response_message(&response).unwrap().contains("is truncated"));
+
+        env.stop().await;
+    }
+
+    #[tokio::test]
+    async fn should_return_an_error_response_when_the_client_software_is_not_allowed() {
+        INIT.call_once(|| {
+            tracing_stderr_init(LevelFilter::ERROR);
+        });
+
+        let mut configuration = configuration::ephemeral();
+        configuration.core.client_filter = ClientFilter::Deny;
+
+        let env = Started::new(&configuration.into()).await;
+
+        env.tracker
+            .add_client_prefix(&PeerIdPrefix::from_str("-qB").unwrap())
+            .unwrap();
+
+        let client = match UdpTrackerClient::new(env.bind_address(), DEFAULT_TIMEOUT).await {
+            Ok(udp_tracker_client) => udp_tracker_client,
+            Err(err) => panic!("{err}"),
+        };
+
+        let tx_id = TransactionId::new(123);
+
+        let c_id = send_connection_request(tx_id, &client).await;
+
+        let announce_request = AnnounceRequest {
+            connection_id: ConnectionId(c_id.0),
+            action_placeholder: AnnounceActionPlaceholder::default(),
+            transaction_id: tx_id,
+            info_hash: InfoHash([0u8; 20]),
+            peer_id: PeerId(*b"-qB00000000000000001"),
+            bytes_downloaded: NumberOfBytes(0i64.into()),
+            bytes_uploaded: NumberOfBytes(0i64.into()),
+            bytes_left: NumberOfBytes(0i64.into()),
+            event: AnnounceEvent::Started.into(),
+            ip_address: Ipv4Addr::new(0, 0, 0, 0).into(),
+            key: PeerKey::new(0i32),
+            peers_wanted: NumberOfPeers(1i32.into()),
+            port: Port(client.client.socket.local_addr().unwrap().port().into()),
+        };
+
+        match client.send(announce_request.into()).await {
+            Ok(_) => (),
+            Err(err) => panic!("{err}"),
+        };
+
+        let response = match client.receive().await {
+            Ok(response) => response,
+            Err(err) => panic!("{err}"),
+        };
+
+        assert!(get_error_response_message(&response).unwrap().contains("is not allowed"));
+
+        env.stop().await;
+    }
+
+    #[tokio::test]
+    async fn should_return_an_error_response_when_the_torrent_is_blacklisted() {
+        INIT.call_once(|| {
+            tracing_stderr_init(LevelFilter::ERROR);
+        });
+
+        let mut configuration = configuration::ephemeral();
+        configuration.core.blacklist.reason = "DMCA takedown".to_string();
+
+        let env = Started::new(&configuration.into()).await;
+
+        let info_hash = InfoHash([0u8; 20]);
+
+        env.tracker.add_torrent_to_blacklist(&info_hash.0.into()).await.unwrap();
+
+        let client = match UdpTrackerClient::new(env.bind_address(), DEFAULT_TIMEOUT).await {
+            Ok(udp_tracker_client) => udp_tracker_client,
+            Err(err) => panic!("{err}"),
+        };
+
+        let tx_id = TransactionId::new(123);
+
+        let c_id = send_connection_request(tx_id, &client).await;
+
+        let announce_request = AnnounceRequest {
+            connection_id: ConnectionId(c_id.0),
+            action_placeholder: AnnounceActionPlaceholder::default(),
+            transaction_id: tx_id,
+            info_hash,
+            peer_id: PeerId([255u8; 20]),
+            bytes_downloaded: NumberOfBytes(0i64.into()),
+            bytes_uploaded: NumberOfBytes(0i64.into()),
+            bytes_left: NumberOfBytes(0i64.into()),
+            event: AnnounceEvent::Started.into(),
+            ip_address: Ipv4Addr::new(0, 0, 0, 0).into(),
+            key: PeerKey::new(0i32),
+            peers_wanted: NumberOfPeers(1i32.into()),
+            port: Port(client.client.socket.local_addr().unwrap().port().into()),
+        };
+
+        match client.send(announce_request.into()).await {
+            Ok(_) => (),
+            Err(err) => panic!("{err}"),
+        };
+
+        let response = match client.receive().await {
+            Ok(response) => response,
+            Err(err) => panic!("{err}"),
+        };
+
+        assert!(get_error_response_message(&response)
+            .unwrap()
+            .contains("is blacklisted: DMCA takedown"));
+
+        env.stop().await;
+    }
+
+    #[tokio::test]
+    async fn should_increase_the_announce_requests_handled_by_the_udp_tracker_in_the_listener_statistics() {
+        INIT.call_once(|| {
+            tracing_stderr_init(LevelFilter::ERROR);
+        });
+
+        let env = Started::new(&configuration::ephemeral().into()).await;
+
+        let client = match UdpTrackerClient::new(env.bind_address(), DEFAULT_TIMEOUT).await {
+            Ok(udp_tracker_client) => udp_tracker_client,
+            Err(err) => panic!("{err}"),
+        };
+
+        let tx_id = TransactionId::new(123);
+
+        let c_id = send_connection_request(tx_id, &client).await;
+
+        send_and_get_announce(tx_id, c_id, &client).await;
+
+        let listeners_stats = env.tracker.get_listeners_stats().await;
+
+        let listener = Listener {
+            protocol: Protocol::Udp,
+            socket_addr: env.bind_address(),
+        };
+
+        assert_eq!(listeners_stats[&listener].ipv4.connections_handled, 1);
+        assert_eq!(listeners_stats[&listener].ipv4.announces_handled, 1);
+
+        drop(listeners_stats);
+
+        env.stop().await;
+    }
+
+    #[tokio::test]
+    async fn should_record_the_time_taken_to_handle_the_announce_request() {
+        INIT.call_once(|| {
+            tracing_stderr_init(LevelFilter::ERROR);
+        });
+
+        let env = Started::new(&configuration::ephemeral().into()).await;
+
+        let client = match UdpTrackerClient::new(env.bind_address(), DEFAULT_TIMEOUT).await {
+            Ok(udp_tracker_client) => udp_tracker_client,
+            Err(err) => panic!("{err}"),
+        };
+
+        let tx_id = TransactionId::new(123);
+
+        let c_id = send_connection_request(tx_id, &client).await;
+
+        send_and_get_announce(tx_id, c_id, &client).await;
+
+        let latency_stats = env.tracker.get_latency_stats().await;
+
+        assert_eq!(latency_stats[&(Protocol::Udp, RequestKind::Connect)].count, 1);
+        assert_eq!(latency_stats[&(Protocol::Udp, RequestKind::Announce)].count, 1);
+
+        drop(latency_stats);
+
+        env.stop().await;
+    }
+
     #[tokio::test]
     async fn should_return_many_announce_response() {
         INIT.call_once(|| {
@@ -267,3 +669,169 @@ mod receiving_an_scrape_request {
         env.stop().await;
     }
 }
+
+mod receiving_an_announce_request_in_private_mode {
+    use std::net::Ipv4Addr;
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    use aquatic_udp_protocol::{
+        AnnounceActionPlaceholder, AnnounceEvent, AnnounceRequest, ConnectionId, InfoHash, NumberOfBytes, NumberOfPeers, PeerId,
+        PeerKey, Port, Request, Response, TransactionId,
+    };
+    use bittorrent_tracker_client::udp::client::UdpTrackerClient;
+    use torrust_tracker::core::auth::Key;
+    use torrust_tracker_configuration::DEFAULT_TIMEOUT;
+    use torrust_tracker_primitives::DurationSinceUnixEpoch;
+    use torrust_tracker_test_helpers::configuration;
+    use tracing::level_filters::LevelFilter;
+
+    use crate::common::logging::{tracing_stderr_init, INIT};
+    use crate::servers::udp::asserts::{get_error_response_message, is_ipv4_announce_response};
+    use crate::servers::udp::contract::send_connection_request;
+    use crate::servers::udp::Started;
+
+    /// It sends an announce request with the BEP 41 `URLData` option
+    /// containing the given path.
+    async fn send_announce_with_url_data(c_id: ConnectionId, url_data: Option<&str>, client: &UdpTrackerClient) -> Response {
+        let announce_request = AnnounceRequest {
+            connection_id: ConnectionId(c_id.0),
+            action_placeholder: AnnounceActionPlaceholder::default(),
+            transaction_id: TransactionId::new(123),
+            info_hash: InfoHash([0u8; 20]),
+            peer_id: PeerId([255u8; 20]),
+            bytes_downloaded: NumberOfBytes(0i64.into()),
+            bytes_uploaded: NumberOfBytes(0i64.into()),
+            bytes_left: NumberOfBytes(0i64.into()),
+            event: AnnounceEvent::Started.into(),
+            ip_address: Ipv4Addr::new(0, 0, 0, 0).into(),
+            key: PeerKey::new(0i32),
+            peers_wanted: NumberOfPeers(1i32.into()),
+            port: Port(client.client.socket.local_addr().unwrap().port().into()),
+        };
+
+        let mut payload = Vec::new();
+
+        Request::from(announce_request).write_bytes(&mut payload).unwrap();
+
+        if let Some(url_data) = url_data {
+            for chunk in url_data.as_bytes().chunks(255) {
+                payload.push(0x2);
+                payload.push(u8::try_from(chunk.len()).unwrap());
+                payload.extend_from_slice(chunk);
+            }
+            payload.push(0x0);
+        }
+
+        match client.client.send(&payload).await {
+            Ok(_) => (),
+            Err(err) => panic!("{err}"),
+        };
+
+        match client.receive().await {
+            Ok(response) => response,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_return_an_announce_response_when_the_request_includes_a_valid_key() {
+        INIT.call_once(|| {
+            tracing_stderr_init(LevelFilter::ERROR);
+        });
+
+        let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+        let expiring_key = env.tracker.generate_auth_key(Some(Duration::from_secs(60))).await.unwrap();
+
+        let client = match UdpTrackerClient::new(env.bind_address(), DEFAULT_TIMEOUT).await {
+            Ok(udp_tracker_client) => udp_tracker_client,
+            Err(err) => panic!("{err}"),
+        };
+
+        let c_id = send_connection_request(TransactionId::new(123), &client).await;
+
+        let response = send_announce_with_url_data(c_id, Some(&format!("/announce/{}", expiring_key.key)), &client).await;
+
+        assert!(is_ipv4_announce_response(&response));
+
+        env.stop().await;
+    }
+
+    #[tokio::test]
+    async fn should_return_an_error_response_when_the_key_has_expired() {
+        INIT.call_once(|| {
+            tracing_stderr_init(LevelFilter::ERROR);
+        });
+
+        let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+        let expired_key = env
+            .tracker
+            .add_auth_key(
+                Key::from_str("YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ").unwrap(),
+                Some(DurationSinceUnixEpoch::from_secs(1)),
+            )
+            .await
+            .unwrap();
+
+        let client = match UdpTrackerClient::new(env.bind_address(), DEFAULT_TIMEOUT).await {
+            Ok(udp_tracker_client) => udp_tracker_client,
+            Err(err) => panic!("{err}"),
+        };
+
+        let c_id = send_connection_request(TransactionId::new(123), &client).await;
+
+        let response = send_announce_with_url_data(c_id, Some(&format!("/announce/{}", expired_key.key)), &client).await;
+
+        assert!(get_error_response_message(&response).unwrap().contains("Key has expired"));
+
+        env.stop().await;
+    }
+
+    #[tokio::test]
+    async fn should_return_an_error_response_when_the_request_does_not_include_a_key() {
+        INIT.call_once(|| {
+            tracing_stderr_init(LevelFilter::ERROR);
+        });
+
+        let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+        let client = match UdpTrackerClient::new(env.bind_address(), DEFAULT_TIMEOUT).await {
+            Ok(udp_tracker_client) => udp_tracker_client,
+            Err(err) => panic!("{err}"),
+        };
+
+        let c_id = send_connection_request(TransactionId::new(123), &client).await;
+
+        let response = send_announce_with_url_data(c_id, None, &client).await;
+
+        assert!(get_error_response_message(&response)
+            .unwrap()
+            .contains("domain tracker requires authentication"));
+
+        env.stop().await;
+    }
+
+    #[tokio::test]
+    async fn should_return_an_error_response_when_the_key_is_not_registered() {
+        INIT.call_once(|| {
+            tracing_stderr_init(LevelFilter::ERROR);
+        });
+
+        let env = Started::new(&configuration::ephemeral_private().into()).await;
+
+        let client = match UdpTrackerClient::new(env.bind_address(), DEFAULT_TIMEOUT).await {
+            Ok(udp_tracker_client) => udp_tracker_client,
+            Err(err) => panic!("{err}"),
+        };
+
+        let c_id = send_connection_request(TransactionId::new(123), &client).await;
+
+        let response = send_announce_with_url_data(c_id, Some("/announce/YZSl4lMZupRuOpSRC3krIKR5BPB14nrJ"), &client).await;
+
+        assert!(get_error_response_message(&response).unwrap().contains("Failed to read key"));
+
+        env.stop().await;
+    }
+}
@@ -56,13 +56,14 @@ impl Environment<Stopped> {
     #[allow(dead_code)]
     pub async fn start(self) -> Environment<Running> {
         let cookie_lifetime = self.config.cookie_lifetime;
+        let rate_limit = self.config.rate_limit;
         Environment {
             config: self.config,
             tracker: self.tracker.clone(),
             registar: self.registar.clone(),
             server: self
                 .server
-                .start(self.tracker, self.registar.give_form(), cookie_lifetime)
+                .start(self.tracker, self.registar.give_form(), cookie_lifetime, rate_limit)
                 .await
                 .unwrap(),
         }